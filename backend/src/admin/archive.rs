@@ -0,0 +1,133 @@
+//! Contest archival
+//!
+//! Old contests are moved out of the hot collections into
+//! [`ARCHIVE_COLLECTION`], one document per contest holding the contest and
+//! its `resulted_in`, `played_with` and `played_at` edges. Archived contests
+//! drop out of every default query; analytics that offer `include_archived`
+//! read them back from the archive, and [`restore_contest`] puts one back.
+
+use arangors::client::ClientExt;
+use arangors::{AqlQuery, Database};
+use chrono::{DateTime, FixedOffset};
+
+/// Collection holding archived contests, keyed by the contest's `_key`
+pub const ARCHIVE_COLLECTION: &str = "contest_archive";
+
+/// Moves every contest that started before `cutoff` into the archive
+///
+/// Returns the ids of the archived contests. Archive documents are written
+/// before anything is removed, so a run that fails halfway can simply be
+/// repeated.
+pub async fn archive_contests_before<C: ClientExt>(
+    db: &Database<C>,
+    cutoff: DateTime<FixedOffset>,
+) -> Result<Vec<String>, String> {
+    let copy = AqlQuery::builder()
+        .query(
+            r#"
+            FOR contest IN contest
+                FILTER DATE_TIMESTAMP(contest.start) < @cutoff
+                LET resulted_in = (
+                    FOR e IN resulted_in FILTER e._from == contest._id RETURN UNSET(e, "_id", "_rev")
+                )
+                LET played_with = (
+                    FOR e IN played_with FILTER e._from == contest._id RETURN UNSET(e, "_id", "_rev")
+                )
+                LET played_at = (
+                    FOR e IN played_at FILTER e._from == contest._id RETURN UNSET(e, "_id", "_rev")
+                )
+                INSERT {
+                    _key: contest._key,
+                    contest: UNSET(contest, "_id", "_rev"),
+                    resulted_in: resulted_in,
+                    played_with: played_with,
+                    played_at: played_at,
+                    archived_at: DATE_ISO8601(DATE_NOW())
+                } INTO contest_archive OPTIONS { overwriteMode: "replace" }
+                RETURN contest._id
+            "#,
+        )
+        .bind_var("cutoff", cutoff.timestamp_millis())
+        .build();
+    let ids: Vec<String> = db
+        .aql_query(copy)
+        .await
+        .map_err(|e| format!("Failed to copy contests into the archive: {}", e))?;
+    if ids.is_empty() {
+        return Ok(ids);
+    }
+
+    let remove = AqlQuery::builder()
+        .query(
+            r#"
+            LET resulted_in = (
+                FOR e IN resulted_in FILTER e._from IN @ids REMOVE e IN resulted_in
+            )
+            LET played_with = (
+                FOR e IN played_with FILTER e._from IN @ids REMOVE e IN played_with
+            )
+            LET played_at = (
+                FOR e IN played_at FILTER e._from IN @ids REMOVE e IN played_at
+            )
+            FOR contest IN contest
+                FILTER contest._id IN @ids
+                REMOVE contest IN contest
+            "#,
+        )
+        .bind_var("ids", ids.clone())
+        .build();
+    db.aql_query::<serde_json::Value>(remove)
+        .await
+        .map_err(|e| format!("Failed to remove archived contests: {}", e))?;
+
+    log::info!(
+        "Archived {} contests that started before {}",
+        ids.len(),
+        cutoff
+    );
+    Ok(ids)
+}
+
+/// Moves an archived contest and its edges back into the hot collections
+///
+/// Returns the restored contest's id, or None when `contest_key` is not in
+/// the archive.
+pub async fn restore_contest<C: ClientExt>(
+    db: &Database<C>,
+    contest_key: &str,
+) -> Result<Option<String>, String> {
+    let query = AqlQuery::builder()
+        .query(
+            r#"
+            LET archived = DOCUMENT("contest_archive", @key)
+            FILTER archived != null
+            INSERT archived.contest INTO contest OPTIONS { overwriteMode: "replace" }
+            LET resulted_in = (
+                FOR e IN archived.resulted_in
+                    INSERT e INTO resulted_in OPTIONS { overwriteMode: "replace" }
+            )
+            LET played_with = (
+                FOR e IN archived.played_with
+                    INSERT e INTO played_with OPTIONS { overwriteMode: "replace" }
+            )
+            LET played_at = (
+                FOR e IN archived.played_at
+                    INSERT e INTO played_at OPTIONS { overwriteMode: "replace" }
+            )
+            REMOVE archived IN contest_archive
+            RETURN CONCAT("contest/", archived._key)
+            "#,
+        )
+        .bind_var("key", contest_key)
+        .build();
+    let restored: Vec<String> = db
+        .aql_query(query)
+        .await
+        .map_err(|e| format!("Failed to restore contest {}: {}", contest_key, e))?;
+
+    let restored = restored.into_iter().next();
+    if let Some(id) = &restored {
+        log::info!("Restored archived contest {}", id);
+    }
+    Ok(restored)
+}
@@ -0,0 +1,291 @@
+use crate::admin::{archive, export, stats_check};
+use crate::analytics::AnalyticsCache;
+use crate::auth::AdminAuthMiddleware;
+use crate::config::{Config, ContestConfig};
+use crate::venue::repository::VenueRepositoryImpl;
+use crate::venue::usecase::{VenueUseCase, VenueUseCaseImpl};
+use actix_web::{web, HttpResponse};
+use arangors::client::ClientExt;
+use chrono::{DateTime, Duration, FixedOffset, Utc};
+use serde::Deserialize;
+use shared::dto::venue::VenueDto;
+use shared::SharedError;
+use std::sync::Arc;
+
+/// Clears every analytics cache entry, leaving sessions and other keys untouched
+async fn clear_cache(cache: web::Data<AnalyticsCache>) -> Result<HttpResponse, actix_web::Error> {
+    let removed = cache.clear_analytics().await;
+    log::info!("Cleared {} analytics cache entries", removed);
+    Ok(HttpResponse::Ok().json(serde_json::json!({ "removed": removed })))
+}
+
+/// Streams all completed contests with participants, one JSON object per line
+async fn export_contests<C: ClientExt + 'static>(
+    db: web::Data<arangors::Database<C>>,
+) -> Result<HttpResponse, actix_web::Error> {
+    log::info!("Streaming NDJSON contest export");
+    Ok(HttpResponse::Ok()
+        .content_type("application/x-ndjson")
+        .streaming(export::contests_ndjson(db.get_ref().clone())))
+}
+
+/// The configuration this process loaded, with secrets masked
+async fn effective_config(config: web::Data<Config>) -> Result<HttpResponse, actix_web::Error> {
+    Ok(HttpResponse::Ok().json(config.redacted()))
+}
+
+#[derive(Debug, Deserialize)]
+struct ArchiveQuery {
+    /// Overrides the configured cutoff
+    older_than_days: Option<u32>,
+}
+
+/// Archives contests older than the cutoff, with their edges
+async fn archive_contests<C: ClientExt + 'static>(
+    db: web::Data<arangors::Database<C>>,
+    cache: web::Data<AnalyticsCache>,
+    config: web::Data<ContestConfig>,
+    query: web::Query<ArchiveQuery>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let days = query.older_than_days.unwrap_or(config.archive_after_days);
+    if days == 0 {
+        return Ok(HttpResponse::BadRequest().json(serde_json::json!({
+            "error": "older_than_days must be at least 1"
+        })));
+    }
+    let cutoff: DateTime<FixedOffset> = (Utc::now() - Duration::days(i64::from(days))).into();
+    match archive::archive_contests_before(db.get_ref(), cutoff).await {
+        Ok(contest_ids) => {
+            if !contest_ids.is_empty() {
+                cache.clear_analytics().await;
+            }
+            Ok(HttpResponse::Ok().json(serde_json::json!({
+                "archived": contest_ids.len(),
+                "cutoff": cutoff.to_rfc3339(),
+                "contest_ids": contest_ids,
+            })))
+        }
+        Err(e) => {
+            log::error!("Contest archival failed: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to archive contests"
+            })))
+        }
+    }
+}
+
+/// Moves one archived contest back into the hot collections
+async fn restore_contest<C: ClientExt + 'static>(
+    db: web::Data<arangors::Database<C>>,
+    cache: web::Data<AnalyticsCache>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let key = path.into_inner();
+    let key = key.strip_prefix("contest/").unwrap_or(&key);
+    match archive::restore_contest(db.get_ref(), key).await {
+        Ok(Some(contest_id)) => {
+            cache.clear_analytics().await;
+            Ok(HttpResponse::Ok().json(serde_json::json!({ "restored": contest_id })))
+        }
+        Ok(None) => Ok(HttpResponse::NotFound().json(serde_json::json!({
+            "error": format!("Contest {} is not archived", key)
+        }))),
+        Err(e) => {
+            log::error!("Contest restore failed: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to restore contest"
+            })))
+        }
+    }
+}
+
+/// Body of a venue merge: the venue that survives it
+#[derive(Debug, Deserialize)]
+struct MergeVenueRequest {
+    into: String,
+}
+
+/// Merges a venue into the one named in the body, keeping it with
+/// `merged_into` set so its contests count for the survivor
+async fn merge_venue(
+    repo: web::Data<VenueRepositoryImpl>,
+    cache: web::Data<AnalyticsCache>,
+    path: web::Path<String>,
+    body: web::Json<MergeVenueRequest>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let venue_id = |id: String| {
+        if id.contains('/') {
+            id
+        } else {
+            format!("venue/{}", id)
+        }
+    };
+    let source_id = venue_id(path.into_inner());
+    let target_id = venue_id(body.into_inner().into);
+
+    let usecase = VenueUseCaseImpl {
+        repo: repo.get_ref().clone(),
+    };
+    match usecase.merge_venues(&source_id, &target_id).await {
+        Ok(survivor) => {
+            cache.clear_analytics().await;
+            log::info!("Merged venue {} into {}", source_id, target_id);
+            Ok(HttpResponse::Ok().json(VenueDto::from(&survivor)))
+        }
+        Err(SharedError::NotFound(message)) => {
+            Ok(HttpResponse::NotFound().json(serde_json::json!({ "error": message })))
+        }
+        Err(SharedError::BadRequest(message)) => {
+            Ok(HttpResponse::BadRequest().json(serde_json::json!({ "error": message })))
+        }
+        Err(SharedError::Conflict(message)) => {
+            Ok(HttpResponse::Conflict().json(serde_json::json!({ "error": message })))
+        }
+        Err(e) => {
+            log::error!("Venue merge failed: {}", e);
+            Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to merge venue"
+            })))
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct VerifyStatsQuery {
+    #[serde(default)]
+    repair: bool,
+}
+
+/// Reports players whose stored stats drift from their `resulted_in` edges,
+/// and with `?repair=true` corrects them along with contest completion rates
+async fn verify_stats<C: ClientExt + 'static>(
+    db: web::Data<arangors::Database<C>>,
+    cache: web::Data<AnalyticsCache>,
+    query: web::Query<VerifyStatsQuery>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let discrepancies = match stats_check::find_stats_discrepancies(db.get_ref()).await {
+        Ok(discrepancies) => discrepancies,
+        Err(e) => {
+            log::error!("Player stats check failed: {}", e);
+            return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                "error": "Failed to check player stats"
+            })));
+        }
+    };
+
+    let mut repaired = 0;
+    let mut contests_repaired = 0;
+    if query.repair {
+        if !discrepancies.is_empty() {
+            repaired = match stats_check::repair_player_stats(db.get_ref(), &discrepancies).await {
+                Ok(repaired) => repaired,
+                Err(e) => {
+                    log::error!("Player stats repair failed: {}", e);
+                    return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                        "error": "Failed to repair player stats"
+                    })));
+                }
+            };
+        }
+        contests_repaired = match stats_check::repair_contest_completion_rates(db.get_ref()).await {
+            Ok(repaired) => repaired,
+            Err(e) => {
+                log::error!("Contest stats repair failed: {}", e);
+                return Ok(HttpResponse::InternalServerError().json(serde_json::json!({
+                    "error": "Failed to repair contest stats"
+                })));
+            }
+        };
+        if repaired > 0 || contests_repaired > 0 {
+            cache.clear_analytics().await;
+        }
+    }
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "discrepancies": discrepancies,
+        "repaired": repaired,
+        "contests_repaired": contests_repaired,
+    })))
+}
+
+pub fn configure_routes<C: ClientExt + 'static>(
+    cfg: &mut web::ServiceConfig,
+    db: arangors::Database<C>,
+    redis_client: Arc<redis::Client>,
+    analytics_cache: AnalyticsCache,
+    config: Config,
+) {
+    log::debug!("Registering admin routes:");
+    log::debug!("  POST /api/admin/cache/clear (admin)");
+    log::debug!("  GET /api/admin/config (admin)");
+    log::debug!("  GET /api/admin/export/contests.ndjson (admin)");
+    log::debug!("  POST /api/admin/contests/archive (admin)");
+    log::debug!("  POST /api/admin/contests/archive/{{key}}/restore (admin)");
+    log::debug!("  GET /api/admin/stats/verify (admin)");
+    log::debug!("  POST /api/admin/venues/{{id}}/merge (admin)");
+
+    cfg.service(
+        web::scope("/api/admin")
+            .wrap(AdminAuthMiddleware {
+                redis: redis_client,
+                db: Arc::new(db.clone()),
+            })
+            .app_data(web::Data::new(analytics_cache))
+            .app_data(web::Data::new(db))
+            .app_data(web::Data::new(config.contest.clone()))
+            .app_data(web::Data::new(config))
+            .route("/cache/clear", web::post().to(clear_cache))
+            .route("/config", web::get().to(effective_config))
+            .route(
+                "/export/contests.ndjson",
+                web::get().to(export_contests::<C>),
+            )
+            .route("/contests/archive", web::post().to(archive_contests::<C>))
+            .route(
+                "/contests/archive/{key}/restore",
+                web::post().to(restore_contest::<C>),
+            )
+            .route("/stats/verify", web::get().to(verify_stats::<C>))
+            .route("/venues/{id}/merge", web::post().to(merge_venue)),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytics::CacheKeys;
+    use actix_web::{test, App};
+
+    #[actix_web::test]
+    async fn test_clear_cache_reports_removed_analytics_entries() {
+        let cache = AnalyticsCache::new_default();
+        cache
+            .set(CacheKeys::platform_stats(), "{}".to_string())
+            .await;
+        cache
+            .set(CacheKeys::player_stats("player/1"), "{}".to_string())
+            .await;
+        cache
+            .set(
+                "session:abc123".to_string(),
+                "player@example.com".to_string(),
+            )
+            .await;
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(cache.clone()))
+                .route("/api/admin/cache/clear", web::post().to(clear_cache)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/api/admin/cache/clear")
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+
+        assert_eq!(body["removed"], 2);
+        assert_eq!(cache.stats().await.total_entries, 1);
+        assert!(cache.get("session:abc123").await.is_some());
+    }
+}
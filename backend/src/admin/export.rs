@@ -0,0 +1,85 @@
+use actix_web::web::Bytes;
+use arangors::client::ClientExt;
+use arangors::{AqlQuery, Cursor, Database};
+use futures::stream::{self, Stream};
+
+/// Number of contests fetched per cursor round-trip
+const EXPORT_BATCH_SIZE: u32 = 200;
+
+enum CursorState {
+    Start,
+    Next(String),
+    Done,
+}
+
+/// Streams every completed contest with its participants as NDJSON.
+///
+/// Results are paged through an ArangoDB server-side cursor so only one
+/// batch is held in memory at a time, regardless of dataset size.
+pub fn contests_ndjson<C: ClientExt + 'static>(
+    db: Database<C>,
+) -> impl Stream<Item = Result<Bytes, actix_web::Error>> {
+    stream::unfold(CursorState::Start, move |state| {
+        let db = db.clone();
+        async move {
+            let cursor: Result<Cursor<serde_json::Value>, _> = match state {
+                CursorState::Start => {
+                    let query = AqlQuery::builder()
+                        .query(
+                            r#"
+                    FOR contest IN contest
+                        FILTER contest.stop != null AND DATE_TIMESTAMP(contest.stop) <= DATE_NOW()
+                        SORT contest.start
+                        LET participants = (
+                            FOR outcome IN resulted_in
+                                FILTER outcome._from == contest._id
+                                LET player = DOCUMENT(outcome._to)
+                                SORT TO_NUMBER(outcome.place)
+                                RETURN {
+                                    player_id: outcome._to,
+                                    handle: player != null ? player.handle : outcome.guest_name,
+                                    place: outcome.place,
+                                    result: outcome.result
+                                }
+                        )
+                        RETURN MERGE(UNSET(contest, "_rev"), { participants: participants })
+                "#,
+                        )
+                        .batch_size(EXPORT_BATCH_SIZE)
+                        .build();
+                    db.aql_query_batch(query).await
+                }
+                CursorState::Next(id) => db.aql_next_batch(&id).await,
+                CursorState::Done => return None,
+            };
+
+            let cursor = match cursor {
+                Ok(cursor) => cursor,
+                Err(e) => {
+                    log::error!("Contest export cursor failed: {}", e);
+                    return Some((
+                        Err(actix_web::error::ErrorInternalServerError(
+                            "Failed to export contests",
+                        )),
+                        CursorState::Done,
+                    ));
+                }
+            };
+
+            let mut chunk = Vec::new();
+            for contest in &cursor.result {
+                if let Err(e) = serde_json::to_writer(&mut chunk, contest) {
+                    log::error!("Failed to serialize exported contest: {}", e);
+                    continue;
+                }
+                chunk.push(b'\n');
+            }
+
+            let next = match cursor.id {
+                Some(id) if cursor.more => CursorState::Next(id),
+                _ => CursorState::Done,
+            };
+            Some((Ok(Bytes::from(chunk)), next))
+        }
+    })
+}
@@ -0,0 +1,135 @@
+//! Consistency check between `player_stats` and the contest edges
+//!
+//! `player_stats` documents are updated incrementally and can drift from the
+//! `resulted_in` edges they summarise. [`find_stats_discrepancies`] recounts
+//! each stored player's contests and wins from the edges, and
+//! [`repair_player_stats`] writes the recounted values back.
+//! [`repair_contest_completion_rates`] does the same for the completion
+//! figures of persisted `contest_stats`.
+
+use arangors::client::ClientExt;
+use arangors::{AqlQuery, Database};
+use serde::{Deserialize, Serialize};
+
+/// A player whose stored stats disagree with their `resulted_in` edges
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatsDiscrepancy {
+    pub player_id: String,
+    pub stored_contests: i32,
+    pub actual_contests: i32,
+    pub stored_wins: i32,
+    pub actual_wins: i32,
+}
+
+/// Players whose stored contest or win counts differ from their edges
+///
+/// Only players with a `player_stats` document are checked. A win is a
+/// first place, as when the stats are first computed.
+pub async fn find_stats_discrepancies<C: ClientExt>(
+    db: &Database<C>,
+) -> Result<Vec<StatsDiscrepancy>, String> {
+    let query = AqlQuery::builder()
+        .query(
+            r#"
+            FOR stats IN player_stats
+                LET places = (
+                    FOR r IN resulted_in FILTER r._to == stats.player_id RETURN TO_NUMBER(r.place)
+                )
+                LET actual_contests = LENGTH(places)
+                LET actual_wins = LENGTH(FOR place IN places FILTER place == 1 RETURN 1)
+                FILTER actual_contests != stats.total_contests OR actual_wins != stats.total_wins
+                SORT stats.player_id
+                RETURN {
+                    player_id: stats.player_id,
+                    stored_contests: stats.total_contests,
+                    actual_contests,
+                    stored_wins: stats.total_wins,
+                    actual_wins
+                }
+            "#,
+        )
+        .build();
+    db.aql_query(query)
+        .await
+        .map_err(|e| format!("Failed to check player stats: {}", e))
+}
+
+/// Overwrites the counts of each discrepant player's stats with the actual
+/// ones, recomputing losses and win rate to match
+///
+/// Returns the number of `player_stats` documents updated.
+pub async fn repair_player_stats<C: ClientExt>(
+    db: &Database<C>,
+    discrepancies: &[StatsDiscrepancy],
+) -> Result<usize, String> {
+    if discrepancies.is_empty() {
+        return Ok(0);
+    }
+    let query = AqlQuery::builder()
+        .query(
+            r#"
+            FOR d IN @discrepancies
+                FOR stats IN player_stats
+                    FILTER stats.player_id == d.player_id
+                    UPDATE stats WITH {
+                        total_contests: d.actual_contests,
+                        total_wins: d.actual_wins,
+                        total_losses: d.actual_contests - d.actual_wins,
+                        win_rate: d.actual_contests > 0 ? d.actual_wins * 100.0 / d.actual_contests : 0.0,
+                        last_updated: DATE_ISO8601(DATE_NOW())
+                    } IN player_stats
+                    RETURN NEW.player_id
+            "#,
+        )
+        .bind_var(
+            "discrepancies",
+            serde_json::to_value(discrepancies).map_err(|e| e.to_string())?,
+        )
+        .build();
+    let repaired: Vec<String> = db
+        .aql_query(query)
+        .await
+        .map_err(|e| format!("Failed to repair player stats: {}", e))?;
+
+    log::info!("Repaired stats for {} players", repaired.len());
+    Ok(repaired.len())
+}
+
+/// Recounts the completion of every persisted `contest_stats` document from
+/// its contest's edges and corrects the ones that disagree
+///
+/// A participant completed the contest when their place is above zero.
+/// Returns the number of `contest_stats` documents updated.
+pub async fn repair_contest_completion_rates<C: ClientExt>(
+    db: &Database<C>,
+) -> Result<usize, String> {
+    let query = AqlQuery::builder()
+        .query(
+            r#"
+            FOR stats IN contest_stats
+                LET places = (
+                    FOR r IN resulted_in FILTER r._from == stats.contest_id RETURN TO_NUMBER(r.place)
+                )
+                LET participant_count = LENGTH(places)
+                LET completion_count = LENGTH(FOR place IN places FILTER place > 0 RETURN 1)
+                LET completion_rate = participant_count > 0 ? completion_count * 100.0 / participant_count : 0.0
+                FILTER participant_count != stats.participant_count
+                    OR completion_count != stats.completion_count
+                    OR completion_rate != stats.completion_rate
+                UPDATE stats WITH {
+                    participant_count,
+                    completion_count,
+                    completion_rate
+                } IN contest_stats
+                RETURN NEW.contest_id
+            "#,
+        )
+        .build();
+    let repaired: Vec<String> = db
+        .aql_query(query)
+        .await
+        .map_err(|e| format!("Failed to repair contest stats: {}", e))?;
+
+    log::info!("Repaired completion rates for {} contests", repaired.len());
+    Ok(repaired.len())
+}
@@ -1,3 +1,5 @@
+use chrono::{DateTime, FixedOffset};
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -24,10 +26,14 @@ impl<T> CacheEntry<T> {
 }
 
 /// In-memory cache for analytics data
+///
+/// Keys built by [`CacheKeys`] are stored under the cache's key prefix, which
+/// defaults to [`CacheKeys::PREFIX`]; other keys are stored as given.
 #[derive(Clone)]
 pub struct AnalyticsCache {
     cache: Arc<RwLock<HashMap<String, CacheEntry<String>>>>,
     default_ttl: Duration,
+    key_prefix: Arc<str>,
 }
 
 impl AnalyticsCache {
@@ -36,6 +42,23 @@ impl AnalyticsCache {
         Self {
             cache: Arc::new(RwLock::new(HashMap::new())),
             default_ttl,
+            key_prefix: Arc::from(CacheKeys::PREFIX),
+        }
+    }
+
+    /// Stores analytics keys under `prefix` instead of [`CacheKeys::PREFIX`]
+    pub fn with_key_prefix(mut self, prefix: &str) -> Self {
+        self.key_prefix = Arc::from(prefix);
+        self
+    }
+
+    /// The key an entry is stored under
+    fn scoped<'a>(&self, key: &'a str) -> Cow<'a, str> {
+        match key.strip_prefix(CacheKeys::PREFIX) {
+            Some(rest) if *self.key_prefix != *CacheKeys::PREFIX => {
+                Cow::Owned(format!("{}{}", self.key_prefix, rest))
+            }
+            _ => Cow::Borrowed(key),
         }
     }
 
@@ -47,7 +70,7 @@ impl AnalyticsCache {
     /// Get a value from cache
     pub async fn get(&self, key: &str) -> Option<String> {
         let cache = self.cache.read().await;
-        if let Some(entry) = cache.get(key) {
+        if let Some(entry) = cache.get(self.scoped(key).as_ref()) {
             if !entry.is_expired() {
                 return Some(entry.data.clone());
             }
@@ -58,6 +81,7 @@ impl AnalyticsCache {
     /// Set a value in cache with default TTL
     pub async fn set(&self, key: String, value: String) {
         let entry = CacheEntry::new(value, self.default_ttl);
+        let key = self.scoped(&key).into_owned();
         let mut cache = self.cache.write().await;
         cache.insert(key, entry);
     }
@@ -65,6 +89,7 @@ impl AnalyticsCache {
     /// Set a value in cache with custom TTL
     pub async fn set_with_ttl(&self, key: String, value: String, ttl: Duration) {
         let entry = CacheEntry::new(value, ttl);
+        let key = self.scoped(&key).into_owned();
         let mut cache = self.cache.write().await;
         cache.insert(key, entry);
     }
@@ -72,7 +97,7 @@ impl AnalyticsCache {
     /// Remove a value from cache
     pub async fn remove(&self, key: &str) {
         let mut cache = self.cache.write().await;
-        cache.remove(key);
+        cache.remove(self.scoped(key).as_ref());
     }
 
     /// Clear all expired entries
@@ -106,6 +131,19 @@ impl AnalyticsCache {
         let mut cache = self.cache.write().await;
         cache.clear();
     }
+
+    /// Remove all entries whose key starts with the given prefix, returning how many were removed
+    pub async fn clear_prefix(&self, prefix: &str) -> usize {
+        let mut cache = self.cache.write().await;
+        let before = cache.len();
+        cache.retain(|key, _| !key.starts_with(prefix));
+        before - cache.len()
+    }
+
+    /// Remove every analytics entry, returning how many were removed
+    pub async fn clear_analytics(&self) -> usize {
+        self.clear_prefix(&self.key_prefix).await
+    }
 }
 
 /// Cache statistics
@@ -120,12 +158,38 @@ pub struct CacheStats {
 pub struct CacheKeys;
 
 impl CacheKeys {
+    /// Prefix shared by every analytics cache key, and the default prefix
+    /// they are stored under
+    pub const PREFIX: &'static str = "analytics:";
+
     pub fn platform_stats() -> String {
         "analytics:platform:stats".to_string()
     }
 
-    pub fn leaderboard(category: &str, limit: i32, offset: i32) -> String {
-        format!("analytics:leaderboard:{}:{}:{}", category, limit, offset)
+    /// Platform stats listing a non-default number of top games and venues
+    pub fn platform_stats_top(top_n: i32) -> String {
+        format!("analytics:platform:stats:top:{}", top_n)
+    }
+
+    pub fn communities() -> String {
+        "analytics:platform:communities".to_string()
+    }
+
+    /// `active_within_days` is the activity window applied, if any
+    pub fn leaderboard(
+        category: &str,
+        limit: i32,
+        offset: i32,
+        active_within_days: Option<u32>,
+    ) -> String {
+        let view = match active_within_days {
+            Some(days) => format!("active{}", days),
+            None => "all".to_string(),
+        };
+        format!(
+            "analytics:leaderboard:{}:{}:{}:{}",
+            category, view, limit, offset
+        )
     }
 
     pub fn player_stats(player_id: &str) -> String {
@@ -140,42 +204,111 @@ impl CacheKeys {
         format!("analytics:player:{}:rankings", player_id)
     }
 
+    pub fn player_display_label(player_id: &str) -> String {
+        format!("analytics:player:{}:display_label", player_id)
+    }
+
     /// Generate cache key for players who beat me
     pub fn players_who_beat_me(player_id: &str) -> String {
-        format!("players_who_beat_me:{}", player_id)
+        format!("analytics:players_who_beat_me:{}", player_id)
     }
 
     /// Generate cache key for players I beat
     pub fn players_i_beat(player_id: &str) -> String {
-        format!("players_i_beat:{}", player_id)
+        format!("analytics:players_i_beat:{}", player_id)
+    }
+
+    /// Generate cache key for play partners
+    pub fn play_partners(player_id: &str) -> String {
+        format!("analytics:play_partners:{}", player_id)
     }
 
     /// Generate cache key for my game performance
     pub fn my_game_performance(player_id: &str) -> String {
-        format!("my_game_performance:{}", player_id)
+        format!("analytics:my_game_performance:{}", player_id)
+    }
+
+    /// Generate cache key for game recommendations
+    pub fn game_recommendations(player_id: &str, limit: i32) -> String {
+        format!("analytics:game_recommendations:{}:{}", player_id, limit)
+    }
+
+    /// Generate cache key for networking suggestions
+    pub fn networking_suggestions(player_id: &str) -> String {
+        format!("analytics:networking_suggestions:{}", player_id)
     }
 
     /// Generate cache key for head-to-head record
-    pub fn head_to_head_record(player_id: &str, opponent_id: &str) -> String {
-        format!("head_to_head:{}:vs:{}", player_id, opponent_id)
+    pub fn head_to_head_record(
+        player_id: &str,
+        opponent_id: &str,
+        game_id: Option<&str>,
+    ) -> String {
+        format!(
+            "analytics:head_to_head:{}:vs:{}:{}",
+            player_id,
+            opponent_id,
+            game_id.unwrap_or("any")
+        )
     }
 
     /// Generate cache key for my performance trends
-    pub fn my_performance_trends(player_id: &str) -> String {
-        format!("my_performance_trends:{}", player_id)
+    pub fn my_performance_trends(
+        player_id: &str,
+        game_id: Option<&str>,
+        venue_id: Option<&str>,
+    ) -> String {
+        format!(
+            "analytics:my_performance_trends:{}:{}:{}",
+            player_id,
+            game_id.unwrap_or("any"),
+            venue_id.unwrap_or("any")
+        )
+    }
+
+    pub fn game_stats(game_id: &str) -> String {
+        format!("analytics:game:{}:stats", game_id)
+    }
+
+    pub fn venue_stats(venue_id: &str) -> String {
+        format!("analytics:venue:{}:stats", venue_id)
     }
 
     pub fn contest_stats(contest_id: &str) -> String {
         format!("analytics:contest:{}:stats", contest_id)
     }
 
-    pub fn contest_trends(months: i32) -> String {
-        format!("analytics:contest:trends:{}", months)
+    pub fn contest_trends(months: i32, include_archived: bool) -> String {
+        if include_archived {
+            format!("analytics:contest:trends:{}:archived", months)
+        } else {
+            format!("analytics:contest:trends:{}", months)
+        }
     }
 
     pub fn recent_contests(limit: i32) -> String {
         format!("analytics:contest:recent:{}", limit)
     }
+
+    pub fn contest_heatmap(
+        weeks: i32,
+        from: Option<DateTime<FixedOffset>>,
+        to: Option<DateTime<FixedOffset>>,
+        game_id: Option<&str>,
+        venue_id: Option<&str>,
+    ) -> String {
+        let date = |d: Option<DateTime<FixedOffset>>| {
+            d.map_or_else(|| "any".to_string(), |d| d.to_rfc3339())
+        };
+        format!(
+            "analytics:contest:heatmap:{}:{}:{}:{}:{}",
+            weeks,
+            date(from),
+            date(to),
+            game_id.unwrap_or("any"),
+            venue_id.unwrap_or("any")
+        )
+    }
 }
 
 /// Cache TTL configuration
@@ -188,10 +321,12 @@ pub struct CacheTTL {
     pub player_rankings: Duration,
     pub contest_stats: Duration,
     pub contest_trends: Duration,
+    pub contest_heatmap: Duration,
     pub recent_contests: Duration,
     pub player_opponents: Duration,
     pub head_to_head: Duration,
     pub player_trends: Duration,
+    pub player_display_label: Duration,
 }
 
 impl CacheTTL {
@@ -204,10 +339,12 @@ impl CacheTTL {
             player_rankings: Duration::from_secs(20 * 60), // 20 minutes
             contest_stats: Duration::from_secs(30 * 60), // 30 minutes
             contest_trends: Duration::from_secs(60 * 60), // 1 hour
+            contest_heatmap: Duration::from_secs(15 * 60), // 15 minutes
             recent_contests: Duration::from_secs(5 * 60), // 5 minutes
             player_opponents: Duration::from_secs(15 * 60), // 15 minutes
             head_to_head: Duration::from_secs(10 * 60),  // 10 minutes
             player_trends: Duration::from_secs(30 * 60), // 30 minutes
+            player_display_label: Duration::from_secs(15 * 60), // 15 minutes
         }
     }
 
@@ -233,6 +370,9 @@ impl CacheTTL {
     pub fn contest_trends() -> Duration {
         Duration::from_secs(60 * 60)
     }
+    pub fn contest_heatmap() -> Duration {
+        Duration::from_secs(15 * 60)
+    }
     pub fn recent_contests() -> Duration {
         Duration::from_secs(5 * 60)
     }
@@ -245,6 +385,9 @@ impl CacheTTL {
     pub fn player_trends() -> Duration {
         Duration::from_secs(30 * 60)
     }
+    pub fn player_display_label() -> Duration {
+        Duration::from_secs(15 * 60)
+    }
 }
 
 #[cfg(test)]
@@ -314,4 +457,105 @@ mod tests {
             Some("value3".to_string())
         );
     }
+
+    #[tokio::test]
+    async fn test_clear_prefix_leaves_other_keys() {
+        let cache = AnalyticsCache::new(Duration::from_secs(60));
+
+        cache
+            .set(CacheKeys::platform_stats(), "value1".to_string())
+            .await;
+        cache
+            .set(
+                CacheKeys::players_who_beat_me("player/1"),
+                "value2".to_string(),
+            )
+            .await;
+        cache
+            .set(
+                "session:abc123".to_string(),
+                "player@example.com".to_string(),
+            )
+            .await;
+
+        let removed = cache.clear_prefix(CacheKeys::PREFIX).await;
+
+        assert_eq!(removed, 2);
+        assert_eq!(cache.get(&CacheKeys::platform_stats()).await, None);
+        assert_eq!(
+            cache.get("session:abc123").await,
+            Some("player@example.com".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_configured_key_prefix_scopes_analytics_entries() {
+        let cache = AnalyticsCache::new(Duration::from_secs(60)).with_key_prefix("stg:analytics:");
+
+        cache
+            .set(CacheKeys::platform_stats(), "value1".to_string())
+            .await;
+        cache
+            .set(
+                "session:abc123".to_string(),
+                "player@example.com".to_string(),
+            )
+            .await;
+        assert_eq!(
+            cache.get(&CacheKeys::platform_stats()).await,
+            Some("value1".to_string())
+        );
+        assert_eq!(cache.clear_prefix("stg:analytics:platform:").await, 1);
+
+        cache
+            .set(CacheKeys::platform_stats(), "value1".to_string())
+            .await;
+        assert_eq!(cache.clear_analytics().await, 1);
+        assert_eq!(cache.get(&CacheKeys::platform_stats()).await, None);
+        assert!(cache.get("session:abc123").await.is_some());
+    }
+
+    #[test]
+    fn test_parameterised_keys_differ_by_every_parameter() {
+        let date = |s: &str| DateTime::parse_from_rfc3339(s).unwrap();
+        let from = Some(date("2024-03-01T00:00:00Z"));
+        let heatmaps = [
+            CacheKeys::contest_heatmap(8, None, None, None, None),
+            CacheKeys::contest_heatmap(12, None, None, None, None),
+            CacheKeys::contest_heatmap(8, from, None, None, None),
+            CacheKeys::contest_heatmap(8, None, from, None, None),
+            CacheKeys::contest_heatmap(8, None, None, Some("game/1"), None),
+            CacheKeys::contest_heatmap(8, None, None, None, Some("game/1")),
+        ];
+        let leaderboards = [
+            CacheKeys::leaderboard("win_rate", 10, 0, None),
+            CacheKeys::leaderboard("win_rate", 10, 0, Some(30)),
+            CacheKeys::leaderboard("win_rate", 10, 0, Some(90)),
+            CacheKeys::leaderboard("win_rate", 10, 10, None),
+            CacheKeys::leaderboard("win_rate", 20, 0, None),
+            CacheKeys::leaderboard("total_wins", 10, 0, None),
+        ];
+        let trends = [
+            CacheKeys::my_performance_trends("player/1", None, None),
+            CacheKeys::my_performance_trends("player/1", Some("game/1"), None),
+            CacheKeys::my_performance_trends("player/1", None, Some("game/1")),
+        ];
+
+        let head_to_heads = [
+            CacheKeys::head_to_head_record("player/1", "player/2", None),
+            CacheKeys::head_to_head_record("player/1", "player/2", Some("game/1")),
+            CacheKeys::head_to_head_record("player/2", "player/1", None),
+        ];
+
+        for keys in [
+            &heatmaps[..],
+            &leaderboards[..],
+            &trends[..],
+            &head_to_heads[..],
+        ] {
+            let unique: std::collections::HashSet<_> = keys.iter().collect();
+            assert_eq!(unique.len(), keys.len(), "colliding keys in {:?}", keys);
+            assert!(keys.iter().all(|k| k.starts_with(CacheKeys::PREFIX)));
+        }
+    }
 }
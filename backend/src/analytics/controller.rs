@@ -1,12 +1,18 @@
-use crate::analytics::repository::AnalyticsRepository;
-use crate::analytics::usecase::AnalyticsUseCase;
+use crate::analytics::cache::AnalyticsCache;
+use crate::analytics::repository::{AnalyticsRepository, PLATFORM_TOP_N_DEFAULT};
+use crate::analytics::usecase::{
+    AnalyticsUseCase, HEAD_TO_HEAD_GROUP_MAX_PLAYERS, VENUE_CONTESTS_DEFAULT_LIMIT,
+};
 use crate::analytics::visualization::ChartConfig;
-use crate::auth::AuthMiddleware;
-use crate::config::DatabaseConfig;
+use crate::auth::{AdminLookup, AuthMiddleware};
+use crate::config::{AnalyticsConfig, DatabaseConfig};
+use crate::pagination::insert_offset_pagination_headers;
 use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
 use arangors::client::ClientExt;
+use chrono::{DateTime, FixedOffset};
 use serde_json::json;
 use shared::dto::analytics::*;
+use std::time::Duration;
 
 /// Analytics controller for handling HTTP requests
 pub struct AnalyticsController<C: ClientExt> {
@@ -51,6 +57,27 @@ impl<C: ClientExt> AnalyticsController<C> {
         Self { usecase }
     }
 
+    /// Creates a new analytics controller backed by a shared cache
+    pub fn with_cache(
+        db: arangors::Database<C>,
+        config: DatabaseConfig,
+        cache: AnalyticsCache,
+        analytics_config: &AnalyticsConfig,
+    ) -> Self {
+        let repo = AnalyticsRepository::new(db, config)
+            .with_slow_query_threshold(Duration::from_millis(
+                analytics_config.slow_query_threshold_ms,
+            ))
+            .with_default_timezone(&analytics_config.default_timezone)
+            .with_aql_logging(analytics_config.log_aql);
+        let usecase = AnalyticsUseCase::with_cache(repo, cache)
+            .with_leaderboard_active_days(analytics_config.leaderboard_active_days)
+            .with_display_label_ttl(Duration::from_secs(
+                analytics_config.display_label_cache_secs,
+            ));
+        Self { usecase }
+    }
+
     /// Get contest heatmap (weekday x hour)
     pub async fn get_contest_heatmap(
         &self,
@@ -61,8 +88,17 @@ impl<C: ClientExt> AnalyticsController<C> {
             .get("weeks")
             .and_then(|w| w.parse::<i32>().ok())
             .unwrap_or(8);
+        let (from, to) = match Self::date_range(&query) {
+            Ok(range) => range,
+            Err(response) => return Ok(response),
+        };
         let game_id = query.get("game_id").map(|s| s.as_str());
-        match self.usecase.get_contest_heatmap(weeks, game_id).await {
+        let venue_id = query.get("venue_id").map(|s| s.as_str());
+        match self
+            .usecase
+            .get_contest_heatmap(weeks, from, to, game_id, venue_id)
+            .await
+        {
             Ok(payload) => Ok(HttpResponse::Ok().json(payload)),
             Err(e) => {
                 log::error!("Failed to get contest heatmap: {}", e);
@@ -72,7 +108,29 @@ impl<C: ClientExt> AnalyticsController<C> {
         }
     }
 
-    #[cfg(test)]
+    /// Reads the optional RFC 3339 `from`/`to` query parameters, or the 400
+    /// response for a malformed one
+    #[allow(clippy::result_large_err)]
+    fn date_range(
+        query: &std::collections::HashMap<String, String>,
+    ) -> Result<(Option<DateTime<FixedOffset>>, Option<DateTime<FixedOffset>>), HttpResponse> {
+        let mut range = [None, None];
+        for (bound, name) in range.iter_mut().zip(["from", "to"]) {
+            if let Some(value) = query.get(name) {
+                match DateTime::parse_from_rfc3339(value) {
+                    Ok(date) => *bound = Some(date),
+                    Err(_) => {
+                        return Err(HttpResponse::BadRequest().json(json!({
+                            "error": format!("'{}' must be an RFC 3339 date", name)
+                        })));
+                    }
+                }
+            }
+        }
+        let [from, to] = range;
+        Ok((from, to))
+    }
+
     fn normalize_id(collection: &str, key_or_id: &str) -> String {
         if key_or_id.contains('/') {
             key_or_id.to_string()
@@ -167,12 +225,40 @@ impl<C: ClientExt> AnalyticsController<C> {
         }
     }
 
-    /// Get platform statistics
+    /// Get platform statistics, listing `top` (default 5) top games and venues
+    ///
+    /// With `debug_timing=true`, an admin gets freshly computed stats with a
+    /// `debug_timing` list of how long each sub-query took; anyone else gets
+    /// the usual response.
     pub async fn get_platform_stats(
         &self,
-        _req: HttpRequest,
+        req: HttpRequest,
+        query: web::Query<std::collections::HashMap<String, String>>,
+        admins: &AdminLookup<C>,
     ) -> Result<HttpResponse, actix_web::Error> {
-        match self.usecase.get_platform_stats().await {
+        let top_n = query
+            .get("top")
+            .and_then(|t| t.parse::<i32>().ok())
+            .unwrap_or(PLATFORM_TOP_N_DEFAULT);
+
+        let debug_timing = query.get("debug_timing").is_some_and(|v| v == "true");
+        if debug_timing && admins.is_admin(&req).await {
+            return match self.usecase.get_platform_stats_with_timing(top_n).await {
+                Ok((stats, timings)) => {
+                    let mut body = serde_json::to_value(stats)?;
+                    body["debug_timing"] = json!(timings);
+                    Ok(HttpResponse::Ok().json(body))
+                }
+                Err(e) => {
+                    log::error!("Failed to get platform stats: {}", e);
+                    Ok(HttpResponse::InternalServerError().json(json!({
+                        "error": "Failed to get platform statistics"
+                    })))
+                }
+            };
+        }
+
+        match self.usecase.get_platform_stats_top(top_n).await {
             Ok(stats) => Ok(HttpResponse::Ok().json(stats)),
             Err(e) => {
                 log::error!("Failed to get platform stats: {}", e);
@@ -183,6 +269,38 @@ impl<C: ClientExt> AnalyticsController<C> {
         }
     }
 
+    /// Get the persisted platform statistics snapshot
+    pub async fn get_platform_stats_snapshot(
+        &self,
+        _req: HttpRequest,
+    ) -> Result<HttpResponse, actix_web::Error> {
+        match self.usecase.get_platform_stats_snapshot().await {
+            Ok(stats) => Ok(HttpResponse::Ok().json(stats)),
+            Err(e) => {
+                log::error!("Failed to get platform stats snapshot: {}", e);
+                Ok(HttpResponse::InternalServerError().json(json!({
+                    "error": "Failed to get platform statistics snapshot"
+                })))
+            }
+        }
+    }
+
+    /// Recompute and persist platform statistics
+    pub async fn recompute_platform_stats(
+        &self,
+        _req: HttpRequest,
+    ) -> Result<HttpResponse, actix_web::Error> {
+        match self.usecase.recompute_platform_stats().await {
+            Ok(stats) => Ok(HttpResponse::Ok().json(stats)),
+            Err(e) => {
+                log::error!("Failed to recompute platform stats: {}", e);
+                Ok(HttpResponse::InternalServerError().json(json!({
+                    "error": "Failed to recompute platform statistics"
+                })))
+            }
+        }
+    }
+
     /// Get enhanced platform insights
     pub async fn get_platform_insights(
         &self,
@@ -199,6 +317,22 @@ impl<C: ClientExt> AnalyticsController<C> {
         }
     }
 
+    /// Get communities of players who frequent the same venues
+    pub async fn get_communities(
+        &self,
+        _req: HttpRequest,
+    ) -> Result<HttpResponse, actix_web::Error> {
+        match self.usecase.get_communities().await {
+            Ok(communities) => Ok(HttpResponse::Ok().json(communities)),
+            Err(e) => {
+                log::error!("Failed to get gaming communities: {}", e);
+                Ok(HttpResponse::InternalServerError().json(json!({
+                    "error": "Failed to get gaming communities"
+                })))
+            }
+        }
+    }
+
     /// Get leaderboard data
     pub async fn get_leaderboard(
         &self,
@@ -218,6 +352,33 @@ impl<C: ClientExt> AnalyticsController<C> {
         }
     }
 
+    /// Get one page of every player's stats, sorted by `sort_by`
+    pub async fn get_all_player_stats(
+        &self,
+        req: HttpRequest,
+        query: web::Query<PlayerStatsPageRequest>,
+    ) -> Result<HttpResponse, actix_web::Error> {
+        match self.usecase.get_all_player_stats(&query).await {
+            Ok(page) => {
+                let mut response = HttpResponse::Ok();
+                insert_offset_pagination_headers(
+                    &mut response,
+                    &req,
+                    page.limit,
+                    page.offset,
+                    page.total,
+                );
+                Ok(response.json(page))
+            }
+            Err(e) => {
+                log::error!("Failed to get all player stats: {}", e);
+                Ok(HttpResponse::InternalServerError().json(json!({
+                    "error": "Failed to get player stats"
+                })))
+            }
+        }
+    }
+
     /// Get player achievements
     pub async fn get_player_achievements(
         &self,
@@ -333,6 +494,63 @@ impl<C: ClientExt> AnalyticsController<C> {
         }
     }
 
+    /// Get game statistics
+    pub async fn get_game_stats(
+        &self,
+        _req: HttpRequest,
+        path: web::Path<String>,
+    ) -> Result<HttpResponse, actix_web::Error> {
+        let game_param = path.into_inner();
+
+        let game_id = Self::normalize_id("game", &game_param);
+
+        log::debug!(
+            "Getting game stats for game_id: {} (normalized from: {})",
+            game_id,
+            game_param
+        );
+
+        match self.usecase.get_game_stats(&game_id).await {
+            Ok(stats) => Ok(HttpResponse::Ok().json(stats)),
+            Err(shared::SharedError::NotFound(_)) => Ok(HttpResponse::NotFound().json(json!({
+                "error": "Game not found"
+            }))),
+            Err(e) => {
+                log::error!("Failed to get game stats: {}", e);
+                Ok(HttpResponse::InternalServerError().json(json!({
+                    "error": "Failed to get game statistics"
+                })))
+            }
+        }
+    }
+
+    /// Get venue statistics
+    pub async fn get_venue_stats(
+        &self,
+        _req: HttpRequest,
+        path: web::Path<String>,
+    ) -> Result<HttpResponse, actix_web::Error> {
+        let venue_param = path.into_inner();
+
+        let venue_id = Self::normalize_id("venue", &venue_param);
+
+        log::debug!(
+            "Getting venue stats for venue_id: {} (normalized from: {})",
+            venue_id,
+            venue_param
+        );
+
+        match self.usecase.get_venue_stats(&venue_id).await {
+            Ok(stats) => Ok(HttpResponse::Ok().json(stats)),
+            Err(e) => {
+                log::error!("Failed to get venue stats: {}", e);
+                Ok(HttpResponse::InternalServerError().json(json!({
+                    "error": "Failed to get venue statistics"
+                })))
+            }
+        }
+    }
+
     /// Get contest trends
     pub async fn get_contest_trends(
         &self,
@@ -343,8 +561,13 @@ impl<C: ClientExt> AnalyticsController<C> {
             .get("months")
             .and_then(|m| m.parse::<i32>().ok())
             .unwrap_or(12);
+        let include_archived = query.get("include_archived").is_some_and(|v| v == "true");
 
-        match self.usecase.get_contest_trends(months).await {
+        match self
+            .usecase
+            .get_contest_trends(months, include_archived)
+            .await
+        {
             Ok(trends) => Ok(HttpResponse::Ok().json(trends)),
             Err(e) => {
                 log::error!("Failed to get contest trends: {}", e);
@@ -355,6 +578,56 @@ impl<C: ClientExt> AnalyticsController<C> {
         }
     }
 
+    /// Get the "contest of the week": the most exciting recently completed contest
+    pub async fn get_featured_contest(
+        &self,
+        _req: HttpRequest,
+        query: web::Query<std::collections::HashMap<String, String>>,
+    ) -> Result<HttpResponse, actix_web::Error> {
+        let window_days = query
+            .get("window_days")
+            .and_then(|d| d.parse::<i32>().ok())
+            .unwrap_or(7);
+
+        match self.usecase.get_featured_contest(window_days).await {
+            Ok(Some(contest)) => Ok(HttpResponse::Ok().json(contest)),
+            Ok(None) => Ok(HttpResponse::NotFound().json(json!({
+                "error": "No completed contest to feature in this window"
+            }))),
+            Err(e) => {
+                log::error!("Failed to get featured contest: {}", e);
+                Ok(HttpResponse::InternalServerError().json(json!({
+                    "error": "Failed to get featured contest"
+                })))
+            }
+        }
+    }
+
+    /// Get the biggest upset: a low-rated winner over the highest-rated opponent
+    pub async fn get_biggest_upset(
+        &self,
+        _req: HttpRequest,
+        query: web::Query<std::collections::HashMap<String, String>>,
+    ) -> Result<HttpResponse, actix_web::Error> {
+        let window_days = query
+            .get("window_days")
+            .and_then(|d| d.parse::<i32>().ok())
+            .unwrap_or(30);
+
+        match self.usecase.get_biggest_upset(window_days).await {
+            Ok(Some(upset)) => Ok(HttpResponse::Ok().json(upset)),
+            Ok(None) => Ok(HttpResponse::NotFound().json(json!({
+                "error": "No upset in this window"
+            }))),
+            Err(e) => {
+                log::error!("Failed to get biggest upset: {}", e);
+                Ok(HttpResponse::InternalServerError().json(json!({
+                    "error": "Failed to get biggest upset"
+                })))
+            }
+        }
+    }
+
     /// Get contest difficulty analysis
     pub async fn get_contest_difficulty(
         &self,
@@ -765,9 +1038,13 @@ impl<C: ClientExt> AnalyticsController<C> {
         query: web::Query<std::collections::HashMap<String, String>>,
     ) -> Result<HttpResponse, actix_web::Error> {
         let config = self.parse_chart_config(&query);
+        let sample_pct = match Self::parse_sample_pct(&query) {
+            Ok(sample_pct) => sample_pct,
+            Err(response) => return Ok(response),
+        };
         match self
             .usecase
-            .get_player_performance_distribution_chart(Some(config))
+            .get_player_performance_distribution_chart(Some(config), sample_pct)
             .await
         {
             Ok(chart) => Ok(HttpResponse::Ok().json(chart)),
@@ -837,9 +1114,13 @@ impl<C: ClientExt> AnalyticsController<C> {
         query: web::Query<std::collections::HashMap<String, String>>,
     ) -> Result<HttpResponse, actix_web::Error> {
         let config = self.parse_chart_config(&query);
+        let sample_pct = match Self::parse_sample_pct(&query) {
+            Ok(sample_pct) => sample_pct,
+            Err(response) => return Ok(response),
+        };
         match self
             .usecase
-            .get_player_retention_cohort_chart(Some(config))
+            .get_player_retention_cohort_chart(Some(config), sample_pct)
             .await
         {
             Ok(chart) => Ok(HttpResponse::Ok().json(chart)),
@@ -952,6 +1233,21 @@ impl<C: ClientExt> AnalyticsController<C> {
     }
 
     /// Helper method to parse chart configuration from query parameters
+    /// Parses the optional `sample_pct` (0 < pct <= 100) of heavy chart queries
+    fn parse_sample_pct(
+        query: &std::collections::HashMap<String, String>,
+    ) -> Result<Option<f64>, HttpResponse> {
+        match query.get("sample_pct") {
+            None => Ok(None),
+            Some(value) => match value.parse::<f64>() {
+                Ok(pct) if pct > 0.0 && pct <= 100.0 => Ok(Some(pct)),
+                _ => Err(HttpResponse::BadRequest().json(json!({
+                    "error": "'sample_pct' must be a number greater than 0 and at most 100"
+                }))),
+            },
+        }
+    }
+
     fn parse_chart_config(&self, query: &std::collections::HashMap<String, String>) -> ChartConfig {
         ChartConfig {
             title: query
@@ -1260,6 +1556,110 @@ impl<C: ClientExt> AnalyticsController<C> {
         }
     }
 
+    /// Get the current player's profile summary
+    pub async fn get_my_summary(
+        &self,
+        req: HttpRequest,
+        query: web::Query<std::collections::HashMap<String, String>>,
+    ) -> Result<HttpResponse, actix_web::Error> {
+        let current_player_id = match self.resolve_player_id(&req, Some(&query)).await {
+            Ok(player_id) => player_id,
+            Err(resp) => return Ok(resp),
+        };
+
+        match self.usecase.get_player_summary(&current_player_id).await {
+            Ok(Some(summary)) => Ok(HttpResponse::Ok().json(summary)),
+            Ok(None) => Ok(HttpResponse::NotFound().json(json!({
+                "error": "Player not found"
+            }))),
+            Err(e) => {
+                log::error!("Failed to get player summary: {}", e);
+                Ok(HttpResponse::InternalServerError().json(json!({
+                    "error": "Failed to get player summary"
+                })))
+            }
+        }
+    }
+
+    /// Get games the current player hasn't played yet
+    pub async fn get_game_recommendations(
+        &self,
+        req: HttpRequest,
+        query: web::Query<std::collections::HashMap<String, String>>,
+    ) -> Result<HttpResponse, actix_web::Error> {
+        let current_player_id = match self.resolve_player_id(&req, Some(&query)).await {
+            Ok(player_id) => player_id,
+            Err(resp) => return Ok(resp),
+        };
+        let limit = query
+            .get("limit")
+            .and_then(|l| l.parse::<i32>().ok())
+            .unwrap_or(5)
+            .clamp(1, 50);
+
+        match self
+            .usecase
+            .recommend_games(&current_player_id, limit)
+            .await
+        {
+            Ok(recommendations) => Ok(HttpResponse::Ok().json(recommendations)),
+            Err(e) => {
+                log::error!("Failed to get game recommendations: {}", e);
+                Ok(HttpResponse::InternalServerError().json(json!({
+                    "error": "Failed to get game recommendations"
+                })))
+            }
+        }
+    }
+
+    /// Get everyone the current player has played with
+    pub async fn get_play_partners(
+        &self,
+        req: HttpRequest,
+        query: web::Query<std::collections::HashMap<String, String>>,
+    ) -> Result<HttpResponse, actix_web::Error> {
+        let current_player_id = match self.resolve_player_id(&req, Some(&query)).await {
+            Ok(player_id) => player_id,
+            Err(resp) => return Ok(resp),
+        };
+
+        match self.usecase.get_play_partners(&current_player_id).await {
+            Ok(partners) => Ok(HttpResponse::Ok().json(partners)),
+            Err(e) => {
+                log::error!("Failed to get play partners: {}", e);
+                Ok(HttpResponse::InternalServerError().json(json!({
+                    "error": "Failed to get play partners"
+                })))
+            }
+        }
+    }
+
+    /// Get players the current player should meet
+    pub async fn get_networking_suggestions(
+        &self,
+        req: HttpRequest,
+        query: web::Query<std::collections::HashMap<String, String>>,
+    ) -> Result<HttpResponse, actix_web::Error> {
+        let current_player_id = match self.resolve_player_id(&req, Some(&query)).await {
+            Ok(player_id) => player_id,
+            Err(resp) => return Ok(resp),
+        };
+
+        match self
+            .usecase
+            .get_networking_suggestions(&current_player_id)
+            .await
+        {
+            Ok(suggestions) => Ok(HttpResponse::Ok().json(suggestions)),
+            Err(e) => {
+                log::error!("Failed to get networking suggestions: {}", e);
+                Ok(HttpResponse::InternalServerError().json(json!({
+                    "error": "Failed to get networking suggestions"
+                })))
+            }
+        }
+    }
+
     /// Get player's head-to-head record against specific opponent
     pub async fn get_head_to_head_record(
         &self,
@@ -1283,9 +1683,14 @@ impl<C: ClientExt> AnalyticsController<C> {
             Err(resp) => return Ok(resp),
         };
 
+        let game_id = query
+            .get("game_id")
+            .filter(|id| !id.is_empty())
+            .map(|id| normalize_id("game", id));
+
         match self
             .usecase
-            .get_head_to_head_record(&current_player_id, &opponent_id)
+            .get_head_to_head_record(&current_player_id, &opponent_id, game_id.as_deref())
             .await
         {
             Ok(record) => Ok(HttpResponse::Ok().json(record)),
@@ -1298,6 +1703,38 @@ impl<C: ClientExt> AnalyticsController<C> {
         }
     }
 
+    /// Get the pairwise head-to-head records within a group of players
+    pub async fn get_group_head_to_head(
+        &self,
+        body: web::Json<GroupHeadToHeadRequest>,
+    ) -> Result<HttpResponse, actix_web::Error> {
+        let mut player_ids: Vec<String> = Vec::new();
+        for id in &body.player_ids {
+            let id = Self::normalize_id("player", id.trim());
+            if !player_ids.contains(&id) {
+                player_ids.push(id);
+            }
+        }
+        if player_ids.len() < 2 || player_ids.len() > HEAD_TO_HEAD_GROUP_MAX_PLAYERS {
+            return Ok(HttpResponse::BadRequest().json(json!({
+                "error": format!(
+                    "player_ids must name between 2 and {} distinct players",
+                    HEAD_TO_HEAD_GROUP_MAX_PLAYERS
+                )
+            })));
+        }
+
+        match self.usecase.get_group_head_to_head(&player_ids).await {
+            Ok(records) => Ok(HttpResponse::Ok().json(records)),
+            Err(e) => {
+                log::error!("Failed to get group head-to-head: {}", e);
+                Ok(HttpResponse::InternalServerError().json(json!({
+                    "error": "Failed to get group head-to-head"
+                })))
+            }
+        }
+    }
+
     /// Get player's performance trends over time
     pub async fn get_my_performance_trends(
         &self,
@@ -1327,7 +1764,10 @@ impl<C: ClientExt> AnalyticsController<C> {
         }
     }
 
-    /// Get contests by venue for current player
+    /// Get a page of the current player's contests at a venue
+    ///
+    /// Takes `limit`/`offset` and optional RFC 3339 `from`/`to` bounds on
+    /// the contest start.
     pub async fn get_contests_by_venue(
         &self,
         req: HttpRequest,
@@ -1372,14 +1812,40 @@ impl<C: ClientExt> AnalyticsController<C> {
             }
         };
 
+        let (from, to) = match Self::date_range(&query) {
+            Ok(range) => range,
+            Err(response) => return Ok(response),
+        };
+        let limit = query
+            .get("limit")
+            .and_then(|l| l.parse::<i32>().ok())
+            .unwrap_or(VENUE_CONTESTS_DEFAULT_LIMIT);
+        let offset = query
+            .get("offset")
+            .and_then(|o| o.parse::<i32>().ok())
+            .unwrap_or(0);
+
         match self
             .usecase
-            .get_contests_by_venue(&current_player_id, &venue_id)
+            .get_contests_by_venue(&current_player_id, &venue_id, from, to, limit, offset)
             .await
         {
-            Ok(contests) => {
-                log::info!("Found {} contests for venue {}", contests.len(), venue_id);
-                Ok(HttpResponse::Ok().json(contests))
+            Ok(page) => {
+                log::info!(
+                    "Found {} of {} contests for venue {}",
+                    page.items.len(),
+                    page.total,
+                    venue_id
+                );
+                let mut response = HttpResponse::Ok();
+                insert_offset_pagination_headers(
+                    &mut response,
+                    &req,
+                    page.limit,
+                    page.offset,
+                    page.total,
+                );
+                Ok(response.json(page))
             }
             Err(e) => {
                 log::error!("Failed to get contests by venue: {}", e);
@@ -1454,20 +1920,38 @@ pub fn configure_routes<C: ClientExt + 'static>(
     db: arangors::Database<C>,
     config: DatabaseConfig,
     redis_client: std::sync::Arc<redis::Client>,
+    cache: AnalyticsCache,
+    analytics_config: AnalyticsConfig,
 ) {
-    let controller = AnalyticsController::new(db, config);
+    let admin_db = std::sync::Arc::new(db.clone());
+    let admins = AdminLookup {
+        redis: redis_client.clone(),
+        db: admin_db.clone(),
+    };
+    let controller = AnalyticsController::with_cache(db, config, cache, &analytics_config);
 
     log::debug!("Registering analytics routes:");
     log::debug!("  GET /api/analytics/health");
     log::debug!("  GET /api/analytics/test-game-performance");
     log::debug!("  GET /api/analytics/debug");
-    log::debug!("  GET /api/analytics/platform");
+    log::debug!("  GET /api/analytics/platform (debug_timing for admins)");
+    log::debug!("  GET /api/analytics/platform/snapshot");
+    log::debug!("  POST /api/analytics/admin/platform/recompute (admin)");
     log::debug!("  GET /api/analytics/insights");
+    log::debug!("  GET /api/analytics/communities");
     log::debug!("  GET /api/analytics/sample-platform");
     log::debug!("  GET /api/analytics/leaderboard");
+    log::debug!("  GET /api/analytics/featured-contest");
+    log::debug!("  GET /api/analytics/biggest-upset");
+    log::debug!("  GET /api/analytics/players/stats (authenticated)");
     log::debug!("  GET /api/analytics/players/{{player_id}}/stats (authenticated)");
     log::debug!("  GET /api/analytics/players/{{player_id}}/achievements (authenticated)");
     log::debug!("  GET /api/analytics/players/{{player_id}}/rankings (authenticated)");
+    log::debug!("  GET /api/analytics/me/summary (authenticated)");
+    log::debug!("  GET /api/analytics/me/recommendations (authenticated)");
+    log::debug!("  GET /api/analytics/me/networking (authenticated)");
+    log::debug!("  GET /api/analytics/me/partners (authenticated)");
+    log::debug!("  POST /api/analytics/head-to-head/group (authenticated)");
     log::debug!("  GET /api/analytics/contests/{{contest_id}}/stats");
     log::debug!("  GET /api/analytics/contests/{{contest_id}}/difficulty");
     log::debug!("  GET /api/analytics/contests/{{contest_id}}/excitement");
@@ -1488,6 +1972,7 @@ pub fn configure_routes<C: ClientExt + 'static>(
     cfg.service(
         web::scope("/api/analytics")
             .app_data(web::Data::new(controller))
+            .app_data(web::Data::new(admins))
             .route("/health", web::get().to(|req: HttpRequest, controller: web::Data<AnalyticsController<C>>| async move {
                 controller.health_check(req).await
             }))
@@ -1497,21 +1982,43 @@ pub fn configure_routes<C: ClientExt + 'static>(
             .route("/debug", web::get().to(|req: HttpRequest, controller: web::Data<AnalyticsController<C>>| async move {
                 controller.debug_database(req).await
             }))
-            .route("/platform", web::get().to(|req: HttpRequest, controller: web::Data<AnalyticsController<C>>| async move {
-                controller.get_platform_stats(req).await
+            .route("/platform", web::get().to(|req: HttpRequest, query: web::Query<std::collections::HashMap<String, String>>, controller: web::Data<AnalyticsController<C>>, admins: web::Data<AdminLookup<C>>| async move {
+                controller.get_platform_stats(req, query, &admins).await
+            }))
+            .route("/platform/snapshot", web::get().to(|req: HttpRequest, controller: web::Data<AnalyticsController<C>>| async move {
+                controller.get_platform_stats_snapshot(req).await
             }))
             .route("/insights", web::get().to(|req: HttpRequest, controller: web::Data<AnalyticsController<C>>| async move {
                 controller.get_platform_insights(req).await
             }))
+            .route("/communities", web::get().to(|req: HttpRequest, controller: web::Data<AnalyticsController<C>>| async move {
+                controller.get_communities(req).await
+            }))
             .route("/sample-platform", web::get().to(|req: HttpRequest, controller: web::Data<AnalyticsController<C>>| async move {
                 controller.get_sample_platform_stats(req).await
             }))
             .route("/leaderboard", web::get().to(|req: HttpRequest, query: web::Query<LeaderboardRequest>, controller: web::Data<AnalyticsController<C>>| async move {
                 controller.get_leaderboard(req, query).await
             }))
+            .route("/featured-contest", web::get().to(|req: HttpRequest, query: web::Query<std::collections::HashMap<String, String>>, controller: web::Data<AnalyticsController<C>>| async move {
+                controller.get_featured_contest(req, query).await
+            }))
+            .route("/biggest-upset", web::get().to(|req: HttpRequest, query: web::Query<std::collections::HashMap<String, String>>, controller: web::Data<AnalyticsController<C>>| async move {
+                controller.get_biggest_upset(req, query).await
+            }))
+            .service(
+                web::scope("/admin")
+                    .wrap(crate::auth::AdminAuthMiddleware { redis: redis_client.clone(), db: admin_db })
+                    .route("/platform/recompute", web::post().to(|req: HttpRequest, controller: web::Data<AnalyticsController<C>>| async move {
+                        controller.recompute_platform_stats(req).await
+                    }))
+            )
             .service(
                 web::scope("/players")
                     .wrap(AuthMiddleware { redis: std::sync::Arc::new((*redis_client).clone()) })
+                    .route("/stats", web::get().to(|req: HttpRequest, query: web::Query<PlayerStatsPageRequest>, controller: web::Data<AnalyticsController<C>>| async move {
+                        controller.get_all_player_stats(req, query).await
+                    }))
                     .route("/{player_id}/stats", web::get().to(|req: HttpRequest, path: web::Path<String>, query: web::Query<PlayerStatsRequest>, controller: web::Data<AnalyticsController<C>>| async move {
                         controller.get_player_stats(req, path, query).await
                     }))
@@ -1558,6 +2065,18 @@ pub fn configure_routes<C: ClientExt + 'static>(
                         controller.invalidate_all_cache(req).await
                     }))
             )
+            .service(
+                web::scope("/games")
+                    .route("/{game_id}/stats", web::get().to(|req: HttpRequest, path: web::Path<String>, controller: web::Data<AnalyticsController<C>>| async move {
+                        controller.get_game_stats(req, path).await
+                    }))
+            )
+            .service(
+                web::scope("/venues")
+                    .route("/{venue_id}/stats", web::get().to(|req: HttpRequest, path: web::Path<String>, controller: web::Data<AnalyticsController<C>>| async move {
+                        controller.get_venue_stats(req, path).await
+                    }))
+            )
             .service(
                 web::scope("/charts")
                     .route("/player-performance", web::get().to(|req: HttpRequest, query: web::Query<std::collections::HashMap<String, String>>, controller: web::Data<AnalyticsController<C>>| async move {
@@ -1635,5 +2154,28 @@ pub fn configure_routes<C: ClientExt + 'static>(
                         controller.get_contests_by_venue(req, query).await
                     }))
             )
+            .service(
+                web::scope("/me")
+                    .wrap(AuthMiddleware { redis: std::sync::Arc::new((*redis_client).clone()) })
+                    .route("/summary", web::get().to(|req: HttpRequest, query: web::Query<std::collections::HashMap<String, String>>, controller: web::Data<AnalyticsController<C>>| async move {
+                        controller.get_my_summary(req, query).await
+                    }))
+                    .route("/recommendations", web::get().to(|req: HttpRequest, query: web::Query<std::collections::HashMap<String, String>>, controller: web::Data<AnalyticsController<C>>| async move {
+                        controller.get_game_recommendations(req, query).await
+                    }))
+                    .route("/networking", web::get().to(|req: HttpRequest, query: web::Query<std::collections::HashMap<String, String>>, controller: web::Data<AnalyticsController<C>>| async move {
+                        controller.get_networking_suggestions(req, query).await
+                    }))
+                    .route("/partners", web::get().to(|req: HttpRequest, query: web::Query<std::collections::HashMap<String, String>>, controller: web::Data<AnalyticsController<C>>| async move {
+                        controller.get_play_partners(req, query).await
+                    }))
+            )
+            .service(
+                web::scope("/head-to-head")
+                    .wrap(AuthMiddleware { redis: std::sync::Arc::new((*redis_client).clone()) })
+                    .route("/group", web::post().to(|body: web::Json<GroupHeadToHeadRequest>, controller: web::Data<AnalyticsController<C>>| async move {
+                        controller.get_group_head_to_head(body).await
+                    }))
+            )
     );
 }
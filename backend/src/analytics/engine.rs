@@ -1,4 +1,4 @@
-use chrono::{DateTime, Datelike, FixedOffset};
+use chrono::{DateTime, Datelike, FixedOffset, Weekday};
 use shared::{dto::analytics::*, models::analytics::*};
 use std::collections::HashMap;
 
@@ -270,7 +270,7 @@ impl AnalyticsEngine {
             }
         }
 
-        player_stats
+        let mut distribution: Vec<PlayerWinRate> = player_stats
             .into_iter()
             .map(|(player_id, (wins, total_plays))| {
                 let win_rate = if total_plays > 0 {
@@ -287,7 +287,32 @@ impl AnalyticsEngine {
                     win_rate,
                 }
             })
-            .collect()
+            .collect();
+
+        distribution.sort_by(|a, b| {
+            b.wins
+                .cmp(&a.wins)
+                .then(b.win_rate.total_cmp(&a.win_rate))
+                .then(a.player_id.cmp(&b.player_id))
+        });
+        distribution
+    }
+
+    /// Picks the player with the most wins at a game.
+    ///
+    /// Ties go to the better win rate, then to the lower player id. Nobody is
+    /// champion of a game that has never been won.
+    pub fn game_champion(&self, distribution: &[PlayerWinRate]) -> Option<PlayerWinRate> {
+        distribution
+            .iter()
+            .filter(|p| p.wins > 0)
+            .min_by(|a, b| {
+                b.wins
+                    .cmp(&a.wins)
+                    .then(b.win_rate.total_cmp(&a.win_rate))
+                    .then(a.player_id.cmp(&b.player_id))
+            })
+            .cloned()
     }
 
     /// Calculates popularity trend for a game
@@ -336,10 +361,30 @@ impl AnalyticsEngine {
             })
             .collect();
 
-        popular_games.sort_by(|a, b| b.plays.cmp(&a.plays));
+        popular_games.sort_by(|a, b| b.plays.cmp(&a.plays).then(a.game_id.cmp(&b.game_id)));
         popular_games
     }
 
+    /// Finds the day of the week with the most contests at a venue.
+    ///
+    /// Ties go to the earlier day, counting from Monday.
+    pub fn busiest_weekday(&self, venue_contests: &[VenueContest]) -> Option<Weekday> {
+        let mut per_day = [0i32; 7];
+        for contest in venue_contests {
+            per_day[contest.contest_date.weekday().num_days_from_monday() as usize] += 1;
+        }
+
+        let mut busiest: Option<(Weekday, i32)> = None;
+        let mut day = Weekday::Mon;
+        for contests in per_day {
+            if contests > 0 && busiest.is_none_or(|(_, most)| contests > most) {
+                busiest = Some((day, contests));
+            }
+            day = day.succ();
+        }
+        busiest.map(|(day, _)| day)
+    }
+
     /// Calculates monthly contest frequency for a venue
     pub fn calculate_monthly_contests(
         &self,
@@ -366,6 +411,91 @@ impl AnalyticsEngine {
         trend
     }
 
+    /// Groups players into communities of venues they frequent.
+    ///
+    /// Two venues belong to the same community when any player frequents
+    /// both, so each community is a connected component of the player/venue
+    /// co-occurrence graph. Single-player groups are dropped.
+    pub fn cluster_communities(&self, visits: &[VenueVisit]) -> Vec<GamingCommunityDto> {
+        fn find(parent: &mut HashMap<String, String>, venue_id: &str) -> String {
+            let next = parent
+                .entry(venue_id.to_string())
+                .or_insert_with(|| venue_id.to_string())
+                .clone();
+            if next == venue_id {
+                return next;
+            }
+            let root = find(parent, &next);
+            parent.insert(venue_id.to_string(), root.clone());
+            root
+        }
+
+        let mut parent: HashMap<String, String> = HashMap::new();
+        let mut player_venues: HashMap<&str, Vec<&str>> = HashMap::new();
+        for visit in visits {
+            player_venues
+                .entry(visit.player_id.as_str())
+                .or_default()
+                .push(visit.venue_id.as_str());
+        }
+        for venues in player_venues.values() {
+            let first = find(&mut parent, venues[0]);
+            for venue_id in &venues[1..] {
+                let root = find(&mut parent, venue_id);
+                if root != first {
+                    parent.insert(root, first.clone());
+                }
+            }
+        }
+
+        // (member handles by id, venue plays by id) for each root venue
+        type Group<'a> = (
+            HashMap<&'a str, &'a str>,
+            HashMap<&'a str, CommunityVenueDto>,
+        );
+        let mut groups: HashMap<String, Group> = HashMap::new();
+        for visit in visits {
+            let root = find(&mut parent, &visit.venue_id);
+            let (members, venues) = groups.entry(root).or_default();
+            members.insert(&visit.player_id, &visit.player_handle);
+            venues
+                .entry(&visit.venue_id)
+                .or_insert_with(|| CommunityVenueDto {
+                    venue_id: visit.venue_id.clone(),
+                    venue_name: visit.venue_name.clone(),
+                    plays: 0,
+                })
+                .plays += visit.contests;
+        }
+
+        let mut communities: Vec<GamingCommunityDto> = groups
+            .into_values()
+            .filter(|(members, _)| members.len() > 1)
+            .map(|(members, venues)| {
+                let mut member_handles: Vec<String> =
+                    members.into_values().map(str::to_string).collect();
+                member_handles.sort();
+                let mut top_venues: Vec<CommunityVenueDto> = venues.into_values().collect();
+                top_venues.sort_by(|a, b| b.plays.cmp(&a.plays).then(a.venue_id.cmp(&b.venue_id)));
+                top_venues.truncate(3);
+
+                GamingCommunityDto {
+                    community_id: top_venues[0].venue_id.clone(),
+                    total_members: member_handles.len() as i32,
+                    member_handles,
+                    top_venues,
+                }
+            })
+            .collect();
+
+        communities.sort_by(|a, b| {
+            b.total_members
+                .cmp(&a.total_members)
+                .then(a.community_id.cmp(&b.community_id))
+        });
+        communities
+    }
+
     /// Generates leaderboard entries
     pub fn generate_leaderboard(
         &self,
@@ -458,6 +588,16 @@ pub struct VenueContest {
     pub contest_date: DateTime<FixedOffset>,
 }
 
+/// How often a player has competed at a venue
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct VenueVisit {
+    pub player_id: String,
+    pub player_handle: String,
+    pub venue_id: String,
+    pub venue_name: String,
+    pub contests: i32,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -526,4 +666,130 @@ mod tests {
         let excitement = engine.calculate_excitement_rating(&participants);
         assert!(excitement >= 1.0 && excitement <= 10.0);
     }
+
+    fn visit(player: &str, venue: &str, contests: i32) -> VenueVisit {
+        VenueVisit {
+            player_id: format!("player/{}", player),
+            player_handle: player.to_string(),
+            venue_id: format!("venue/{}", venue),
+            venue_name: venue.to_string(),
+            contests,
+        }
+    }
+
+    #[test]
+    fn test_cluster_communities_links_venues_through_shared_players() {
+        let engine = AnalyticsEngine::new();
+        let visits = vec![
+            visit("alice", "cafe", 4),
+            visit("bob", "cafe", 2),
+            visit("bob", "library", 3),
+            visit("carol", "library", 2),
+            visit("dave", "pub", 5),
+            visit("erin", "pub", 2),
+            // A lone regular does not make a community
+            visit("frank", "garage", 6),
+        ];
+
+        let communities = engine.cluster_communities(&visits);
+
+        assert_eq!(communities.len(), 2);
+        assert_eq!(communities[0].member_handles, ["alice", "bob", "carol"]);
+        assert_eq!(communities[0].community_id, "venue/cafe");
+        assert_eq!(
+            communities[0]
+                .top_venues
+                .iter()
+                .map(|v| (v.venue_name.as_str(), v.plays))
+                .collect::<Vec<_>>(),
+            [("cafe", 6), ("library", 5)]
+        );
+        assert_eq!(communities[1].member_handles, ["dave", "erin"]);
+        assert_eq!(communities[1].total_members, 2);
+    }
+
+    fn venue_contest(id: &str, date: &str, games: &[&str]) -> VenueContest {
+        VenueContest {
+            contest_id: format!("contest/{}", id),
+            participant_ids: vec!["player/alice".to_string(), "player/bob".to_string()],
+            participant_count: 2,
+            game_ids: games.iter().map(|g| format!("game/{}", g)).collect(),
+            duration_minutes: 60,
+            contest_date: DateTime::parse_from_rfc3339(date).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_busiest_weekday_and_most_played_game() {
+        let engine = AnalyticsEngine::new();
+        assert_eq!(engine.busiest_weekday(&[]), None);
+
+        let contests = vec![
+            // Friday, Saturday, Saturday, Friday, Tuesday
+            venue_contest("1", "2026-05-01T19:00:00-05:00", &["catan"]),
+            venue_contest("2", "2026-05-02T19:00:00-05:00", &["azul"]),
+            venue_contest("3", "2026-05-09T19:00:00-05:00", &["azul", "catan"]),
+            venue_contest("4", "2026-05-15T19:00:00-05:00", &["wingspan"]),
+            venue_contest("5", "2026-05-19T19:00:00-05:00", &["wingspan"]),
+        ];
+
+        // Friday and Saturday tie, so the earlier day wins
+        assert_eq!(engine.busiest_weekday(&contests), Some(Weekday::Fri));
+        assert_eq!(engine.busiest_weekday(&contests[1..]), Some(Weekday::Sat));
+
+        let popular = engine.calculate_popular_games(&contests);
+        assert_eq!(
+            popular
+                .iter()
+                .map(|g| (g.game_id.as_str(), g.plays))
+                .collect::<Vec<_>>(),
+            [("game/azul", 2), ("game/catan", 2), ("game/wingspan", 2)]
+        );
+    }
+
+    fn game_play(player: &str, won: bool) -> GamePlay {
+        GamePlay {
+            player_id: format!("player/{}", player),
+            player_count: 2,
+            won,
+            duration_minutes: 45,
+            played_at: DateTime::parse_from_rfc3339("2026-05-01T19:00:00Z").unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_game_champion_prefers_wins_then_win_rate() {
+        let engine = AnalyticsEngine::new();
+        let plays = vec![
+            game_play("alice", true),
+            game_play("alice", true),
+            game_play("alice", false),
+            game_play("bob", true),
+            game_play("bob", true),
+            game_play("carol", true),
+            game_play("dave", false),
+        ];
+
+        let distribution = engine.calculate_win_rate_distribution(&plays);
+        assert_eq!(
+            distribution
+                .iter()
+                .map(|p| (p.player_id.as_str(), p.wins, p.total_plays))
+                .collect::<Vec<_>>(),
+            [
+                ("player/bob", 2, 2),
+                ("player/alice", 2, 3),
+                ("player/carol", 1, 1),
+                ("player/dave", 0, 1)
+            ]
+        );
+
+        // Bob and Alice both won twice; Bob lost less often
+        let champion = engine.game_champion(&distribution).unwrap();
+        assert_eq!(champion.player_id, "player/bob");
+
+        let losses = vec![game_play("dave", false)];
+        let distribution = engine.calculate_win_rate_distribution(&losses);
+        assert!(engine.game_champion(&distribution).is_none());
+    }
 }
@@ -0,0 +1,91 @@
+use arangors::AqlQuery;
+use serde_json::Value;
+
+/// Log target for executed AQL, so it can be let through on its own
+pub const AQL_LOG_TARGET: &str = "analytics::aql";
+
+/// Logs `query` and its bind vars at debug level.
+///
+/// The query text is collapsed onto one line. Bind vars are passed through
+/// [`redact`] first, so player emails and document ids never reach the logs.
+pub fn log_query(query: &AqlQuery<'_>) {
+    if !log::log_enabled!(target: AQL_LOG_TARGET, log::Level::Debug) {
+        return;
+    }
+    let Ok(Value::Object(fields)) = serde_json::to_value(query) else {
+        return;
+    };
+    let text = fields
+        .get("query")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let bind_vars = fields
+        .get("bindVars")
+        .cloned()
+        .map(redact)
+        .unwrap_or_else(|| Value::Object(Default::default()));
+    log::debug!(
+        target: AQL_LOG_TARGET,
+        "AQL: {} bindVars={}",
+        text,
+        bind_vars
+    );
+}
+
+/// Masks emails and document ids anywhere in `value`.
+///
+/// An email becomes `<email>`; a document id keeps its collection, so
+/// `player/123` becomes `player/<id>`. Anything else is left as is.
+pub fn redact(value: Value) -> Value {
+    match value {
+        Value::String(s) => Value::String(redact_str(&s)),
+        Value::Array(items) => Value::Array(items.into_iter().map(redact).collect()),
+        Value::Object(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(name, value)| (name, redact(value)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+fn redact_str(s: &str) -> String {
+    if is_email(s) {
+        return "<email>".to_string();
+    }
+    match s.split_once('/') {
+        Some((collection, key)) if is_collection_name(collection) && is_document_key(key) => {
+            format!("{}/<id>", collection)
+        }
+        _ => s.to_string(),
+    }
+}
+
+fn is_email(s: &str) -> bool {
+    match s.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !s.contains(char::is_whitespace)
+        }
+        None => false,
+    }
+}
+
+fn is_collection_name(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn is_document_key(s: &str) -> bool {
+    !s.is_empty() && !s.contains(|c: char| c == '/' || c.is_whitespace())
+}
@@ -1,19 +1,79 @@
-use crate::analytics::engine::{ContestParticipant, ContestResult, GamePlay, VenueContest};
-use crate::config::DatabaseConfig;
+use crate::analytics::engine::{
+    AnalyticsEngine, ContestParticipant, ContestResult, GamePlay, VenueContest, VenueVisit,
+};
+use crate::analytics::query_log;
+use crate::analytics::timing::{timed, QueryTimer, SubQueryTiming, DEFAULT_SLOW_QUERY_THRESHOLD};
+use crate::config::{AnalyticsConfig, DatabaseConfig};
+use crate::ratings::glicko::Glicko2Params;
 use arangors::{
     client::ClientExt,
     document::options::{InsertOptions, UpdateOptions},
-    AqlQuery, Database,
+    index::{Index, IndexSettings},
+    AqlQuery, ClientError, Database,
 };
-use serde::Deserialize;
+use chrono::{DateTime, FixedOffset};
+use serde::{de::DeserializeOwned, Deserialize};
 use shared::{models::analytics::*, Result, SharedError};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Top games and venues listed in platform stats when no count is asked for
+pub const PLATFORM_TOP_N_DEFAULT: i32 = 5;
+
+/// Games played at most this many times still count as recommendations
+const RARELY_PLAYED_MAX: i32 = 1;
+
+/// Maximum number of "players you should meet" suggestions returned
+const NETWORKING_SUGGESTION_LIMIT: i32 = 10;
+
+/// Contests at a venue before a player counts as one of its regulars
+const COMMUNITY_MIN_VENUE_VISITS: i32 = 2;
+
+/// Collections analytics reads from or writes to beyond the core contest
+/// graph, created by [`AnalyticsRepository::create_collections`] and checked
+/// at startup
+pub const ANALYTICS_COLLECTIONS: &[&str] = &[
+    PLAYER_STATS,
+    CONTEST_STATS,
+    GAME_STATS,
+    VENUE_STATS,
+    PLATFORM_STATS,
+    // Owned by ratings, but player analytics read them by player_id
+    "rating_latest",
+    "rating_history",
+    // Written by contest archival; contest trends can count it
+    "contest_archive",
+];
+
+// Collections analytics writes to; every write names its collection with
+// one of these
+const PLAYER_STATS: &str = "player_stats";
+const CONTEST_STATS: &str = "contest_stats";
+const GAME_STATS: &str = "game_stats";
+const VENUE_STATS: &str = "venue_stats";
+const PLATFORM_STATS: &str = "platform_stats";
+
+/// Persistent indexes behind the stats lookups, as (collection, fields)
+const ANALYTICS_INDEXES: &[(&str, &[&str])] = &[
+    (PLAYER_STATS, &["player_id"]),
+    (PLAYER_STATS, &["skill_rating"]),
+    (PLAYER_STATS, &["win_rate"]),
+    (PLAYER_STATS, &["total_contests"]),
+    (GAME_STATS, &["game_id"]),
+    (VENUE_STATS, &["venue_id"]),
+    ("rating_latest", &["player_id", "scope_type"]),
+];
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct HeatRow {
     pub day: i32,
     pub hour: i32,
     pub plays: i64,
+    /// Plays bucketed in the default timezone for lack of a venue timezone
+    #[serde(default)]
+    pub fallbacks: i64,
 }
 
 #[derive(serde::Deserialize)]
@@ -27,62 +87,182 @@ struct PlayerDataResult {
     unique_venues: i32,
 }
 
+/// A player's display label from their handle, email or name, in that order
+fn display_label(row: &serde_json::Value) -> Option<String> {
+    let field = |name: &str| row.get(name).and_then(|v| v.as_str());
+    let name = match (field("firstname"), field("lastname")) {
+        (Some(first), Some(last)) => Some(format!("{} {}", first, last)),
+        (Some(first), None) => Some(first.to_string()),
+        _ => None,
+    };
+    field("handle")
+        .or_else(|| field("email"))
+        .map(|s| s.to_string())
+        .or(name)
+}
+
 /// Repository for analytics data operations
+///
+/// Game attribution: a contest linked to several games through `played_with`
+/// counts as one play of each of those games, so game-performance and
+/// game-popularity figures credit every game played in a session once.
+///
+/// Wins: every player placed first wins, so a tie for first (or a co-op win)
+/// credits each tied player with a full win.
 #[derive(Clone)]
 pub struct AnalyticsRepository<C: ClientExt> {
     db: Database<C>,
     #[allow(dead_code)]
     config: DatabaseConfig,
+    slow_query_threshold: Duration,
+    default_timezone: String,
+    log_aql: bool,
+    /// Whether the platform stats sub-queries run concurrently
+    concurrent_platform_stats: bool,
+    /// Queries run through `run_query`, shared by clones
+    queries_run: Arc<AtomicU64>,
 }
 
 impl<C: ClientExt> AnalyticsRepository<C> {
     /// Creates a new analytics repository
     pub fn new(db: Database<C>, config: DatabaseConfig) -> Self {
-        Self { db, config }
+        Self {
+            db,
+            config,
+            slow_query_threshold: DEFAULT_SLOW_QUERY_THRESHOLD,
+            default_timezone: AnalyticsConfig::default().default_timezone,
+            log_aql: false,
+            concurrent_platform_stats: true,
+            queries_run: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Sets how long a query may take before it is logged as slow
+    pub fn with_slow_query_threshold(mut self, threshold: Duration) -> Self {
+        self.slow_query_threshold = threshold;
+        self
+    }
+
+    /// Sets the timezone used for contests whose venue has none
+    pub fn with_default_timezone(mut self, timezone: &str) -> Self {
+        self.default_timezone = timezone.to_string();
+        self
+    }
+
+    /// Sets whether every query is logged before it runs
+    pub fn with_aql_logging(mut self, enabled: bool) -> Self {
+        self.log_aql = enabled;
+        self
+    }
+
+    /// Sets whether the platform stats sub-queries run concurrently rather
+    /// than one after another
+    pub fn with_concurrent_platform_stats(mut self, enabled: bool) -> Self {
+        self.concurrent_platform_stats = enabled;
+        self
+    }
+
+    fn timer(&self, method: &'static str, params: String) -> QueryTimer {
+        QueryTimer::start(method, params, self.slow_query_threshold)
+    }
+
+    /// Number of bind-variable queries this repository and its clones have
+    /// run, for spotting a query per row where one batch would do
+    pub fn queries_run(&self) -> u64 {
+        self.queries_run.load(Ordering::Relaxed)
+    }
+
+    /// Runs `aql`, logging it first when AQL logging is on
+    async fn run_query<T: DeserializeOwned>(
+        &self,
+        aql: AqlQuery<'_>,
+    ) -> std::result::Result<Vec<T>, ClientError> {
+        if self.log_aql {
+            query_log::log_query(&aql);
+        }
+        self.queries_run.fetch_add(1, Ordering::Relaxed);
+        self.db.aql_query(aql).await
     }
 
     /// Returns contest counts bucketed by weekday (0=Sun..6=Sat) and hour (0..23)
+    ///
+    /// Covers the last `weeks` weeks unless `from` is given; `to` caps the
+    /// range. Optionally narrowed to contests of one game and/or one venue.
+    /// Contests are bucketed in their venue's local time, or in the default
+    /// timezone when the venue has no timezone. Private contests are left out.
     pub async fn get_contest_heatmap(
         &self,
         weeks: i32,
+        from: Option<DateTime<FixedOffset>>,
+        to: Option<DateTime<FixedOffset>>,
         game_id: Option<&str>,
+        venue_id: Option<&str>,
     ) -> Result<Vec<HeatRow>> {
+        let _timer = self.timer(
+            "get_contest_heatmap",
+            format!(
+                "weeks={} from={:?} to={:?} game_id={:?} venue_id={:?}",
+                weeks, from, to, game_id, venue_id
+            ),
+        );
         let query = r#"
+            LET window_start = @from != null
+              ? DATE_TIMESTAMP(@from)
+              : DATE_TIMESTAMP(DATE_SUBTRACT(DATE_NOW(), @weeks, "weeks"))
+            LET window_end = @to != null ? DATE_TIMESTAMP(@to) : null
             FOR c IN contest
-              FILTER c.start >= DATE_SUBTRACT(DATE_NOW(), @weeks, "weeks")
+              FILTER c.visibility != "private"
+              LET start_ts = DATE_TIMESTAMP(c.start)
+              FILTER start_ts >= window_start
+              FILTER window_end == null OR start_ts <= window_end
               FILTER @game_id == null OR LENGTH(
                 FOR e IN played_with
                   FILTER e._from == c._id AND e._to == @game_id
                   LIMIT 1 RETURN 1
               ) > 0
-              LET wd = DATE_DAYOFWEEK(c.start) - 1
-              LET hr = DATE_HOUR(c.start)
-              COLLECT day = wd, hour = hr WITH COUNT INTO plays
-              RETURN { day, hour, plays }
+              FILTER @venue_id == null OR LENGTH(
+                FOR e IN played_at
+                  FILTER e._from == c._id AND e._to == @venue_id
+                  LIMIT 1 RETURN 1
+              ) > 0
+              LET venue_timezone = FIRST(
+                FOR e IN played_at
+                  FILTER e._from == c._id
+                  LIMIT 1
+                  RETURN DOCUMENT(e._to).timezone
+              )
+              LET fallback = venue_timezone == null OR TRIM(venue_timezone) == ""
+              LET local_start = DATE_UTCTOLOCAL(c.start, fallback ? @default_timezone : venue_timezone)
+              LET wd = DATE_DAYOFWEEK(local_start) - 1
+              LET hr = DATE_HOUR(local_start)
+              COLLECT day = wd, hour = hr
+                AGGREGATE plays = COUNT(1), fallbacks = SUM(fallback ? 1 : 0)
+              RETURN { day, hour, plays, fallbacks }
         "#;
 
-        let mut aql = AqlQuery::builder()
+        let aql = AqlQuery::builder()
             .query(query)
             .bind_var("weeks", weeks)
+            .bind_var("from", from.map(|d| d.to_rfc3339()))
+            .bind_var("to", to.map(|d| d.to_rfc3339()))
+            .bind_var("game_id", game_id)
+            .bind_var("venue_id", venue_id)
+            .bind_var("default_timezone", self.default_timezone.as_str())
             .build();
 
-        // Work around AqlQuery builder immutability: rebuild with game_id if provided
-        let aql = if let Some(gid) = game_id {
-            AqlQuery::builder()
-                .query(query)
-                .bind_var("weeks", weeks)
-                .bind_var("game_id", gid)
-                .build()
-        } else {
-            AqlQuery::builder()
-                .query(query)
-                .bind_var("weeks", weeks)
-                .bind_var("game_id", serde_json::Value::Null)
-                .build()
-        };
-
-        match self.db.aql_query::<HeatRow>(aql).await {
-            Ok(rows) => Ok(rows),
+        match self.run_query::<HeatRow>(aql).await {
+            Ok(rows) => {
+                let fallbacks: i64 = rows.iter().map(|r| r.fallbacks).sum();
+                if fallbacks > 0 {
+                    log::info!(
+                        "Contest heatmap bucketed {} of {} contests in default timezone {} (venue has no timezone)",
+                        fallbacks,
+                        rows.iter().map(|r| r.plays).sum::<i64>(),
+                        self.default_timezone
+                    );
+                }
+                Ok(rows)
+            }
             Err(e) => {
                 log::error!("Failed to query contest heatmap: {}", e);
                 Err(SharedError::Database(e.to_string()))
@@ -99,7 +279,7 @@ impl<C: ClientExt> AnalyticsRepository<C> {
             .bind_var("email", email)
             .build();
 
-        match self.db.aql_query::<String>(aql).await {
+        match self.run_query::<String>(aql).await {
             Ok(results) => {
                 if let Some(player_id) = results.into_iter().next() {
                     Ok(Some(player_id))
@@ -114,46 +294,115 @@ impl<C: ClientExt> AnalyticsRepository<C> {
         }
     }
 
-    /// Get platform statistics from real data
+    /// Get platform statistics from real data, with the usual five top games and venues
     pub async fn get_platform_stats(&self) -> Result<PlatformStats> {
-        log::info!("Starting to get platform stats...");
-
-        // Get total counts from collections
-        let total_players = self.get_total_players().await?;
-        log::info!("Total players: {}", total_players);
-
-        let total_contests = self.get_total_contests().await?;
-        log::info!("Total contests: {}", total_contests);
-
-        let total_games = self.get_total_games().await?;
-        log::info!("Total games: {}", total_games);
-
-        let total_venues = self.get_total_venues().await?;
-        log::info!("Total venues: {}", total_venues);
+        self.get_platform_stats_top(PLATFORM_TOP_N_DEFAULT).await
+    }
 
-        // Get active players (30 days and 7 days)
-        let active_players_30d = self.get_active_players(30).await?;
-        log::info!("Active players 30d: {}", active_players_30d);
+    /// Get platform statistics listing up to `top_n` top games and venues
+    ///
+    /// Private contests, and the players, games and venues seen only in
+    /// them, are left out.
+    pub async fn get_platform_stats_top(&self, top_n: i32) -> Result<PlatformStats> {
+        let (stats, _) = self.get_platform_stats_timed(top_n).await?;
+        Ok(stats)
+    }
 
-        let active_players_7d = self.get_active_players(7).await?;
-        log::info!("Active players 7d: {}", active_players_7d);
+    /// [`Self::get_platform_stats_top`] along with how long each of its
+    /// sub-queries took, in the order they are listed
+    ///
+    /// The sub-queries are independent, so unless turned off with
+    /// [`Self::with_concurrent_platform_stats`] they run concurrently.
+    pub async fn get_platform_stats_timed(
+        &self,
+        top_n: i32,
+    ) -> Result<(PlatformStats, Vec<SubQueryTiming>)> {
+        let _timer = self.timer("get_platform_stats", format!("top_n={}", top_n));
+        log::info!("Starting to get platform stats...");
 
-        // Get contests in last 30 days
-        let contests_30d = self.get_contests_in_period(30).await?;
-        log::info!("Contests 30d: {}", contests_30d);
+        let total_players = timed("total_players", self.get_total_players());
+        let total_contests = timed("total_contests", self.get_total_contests());
+        let total_games = timed("total_games", self.get_total_games());
+        let total_venues = timed("total_venues", self.get_total_venues());
+        let active_players_30d = timed("active_players_30d", self.get_active_players(30));
+        let active_players_7d = timed("active_players_7d", self.get_active_players(7));
+        let contests_30d = timed("contests_30d", self.get_contests_in_period(30));
+        let average_participants = timed(
+            "average_participants_per_contest",
+            self.get_average_participants_per_contest(),
+        );
+        let top_games = timed("top_games", self.get_top_games(top_n));
+        let top_venues = timed("top_venues", self.get_top_venues(top_n));
+
+        let (
+            (total_players, total_players_ms),
+            (total_contests, total_contests_ms),
+            (total_games, total_games_ms),
+            (total_venues, total_venues_ms),
+            (active_players_30d, active_players_30d_ms),
+            (active_players_7d, active_players_7d_ms),
+            (contests_30d, contests_30d_ms),
+            (average_participants_per_contest, average_participants_ms),
+            (top_games, top_games_ms),
+            (top_venues, top_venues_ms),
+        ) = if self.concurrent_platform_stats {
+            tokio::try_join!(
+                total_players,
+                total_contests,
+                total_games,
+                total_venues,
+                active_players_30d,
+                active_players_7d,
+                contests_30d,
+                average_participants,
+                top_games,
+                top_venues,
+            )?
+        } else {
+            (
+                total_players.await?,
+                total_contests.await?,
+                total_games.await?,
+                total_venues.await?,
+                active_players_30d.await?,
+                active_players_7d.await?,
+                contests_30d.await?,
+                average_participants.await?,
+                top_games.await?,
+                top_venues.await?,
+            )
+        };
+        let timings = vec![
+            total_players_ms,
+            total_contests_ms,
+            total_games_ms,
+            total_venues_ms,
+            active_players_30d_ms,
+            active_players_7d_ms,
+            contests_30d_ms,
+            average_participants_ms,
+            top_games_ms,
+            top_venues_ms,
+        ];
 
-        // Calculate average participants per contest
-        let average_participants_per_contest = self.get_average_participants_per_contest().await?;
+        log::info!(
+            "Totals: players={}, contests={}, games={}, venues={}",
+            total_players,
+            total_contests,
+            total_games,
+            total_venues
+        );
+        log::info!(
+            "Active players 30d: {}, 7d: {}; contests 30d: {}",
+            active_players_30d,
+            active_players_7d,
+            contests_30d
+        );
         log::info!(
             "Average participants per contest: {}",
             average_participants_per_contest
         );
-
-        // Get top games and venues
-        let top_games = self.get_top_games(5).await?;
         log::info!("Top games: {:?}", top_games);
-
-        let top_venues = self.get_top_venues(5).await?;
         log::info!("Top venues: {:?}", top_venues);
 
         // Convert to proper types with real counts
@@ -196,7 +445,69 @@ impl<C: ClientExt> AnalyticsRepository<C> {
         log::info!("Final platform stats: total_players={}, total_contests={}, active_30d={}, active_7d={}", 
             final_stats.total_players, final_stats.total_contests, final_stats.active_players_30d, final_stats.active_players_7d);
 
-        Ok(final_stats)
+        Ok((final_stats, timings))
+    }
+
+    /// Key of the single persisted platform stats document
+    const PLATFORM_STATS_KEY: &'static str = "current";
+
+    /// Recomputes platform statistics and persists them to the `platform_stats` collection
+    pub async fn recompute_platform_stats(&self) -> Result<PlatformStats> {
+        let _timer = self.timer("recompute_platform_stats", String::new());
+        let stats = self.get_platform_stats().await?;
+
+        let document = serde_json::to_value(&stats).map_err(|e| {
+            SharedError::Conversion(format!("Failed to serialize platform stats: {}", e))
+        })?;
+
+        let query = AqlQuery::builder()
+            .query(
+                r#"
+                UPSERT { _key: @key }
+                INSERT MERGE(@stats, { _key: @key })
+                REPLACE MERGE(@stats, { _key: @key })
+                IN @@collection
+                "#,
+            )
+            .bind_var("@collection", PLATFORM_STATS)
+            .bind_var("key", Self::PLATFORM_STATS_KEY)
+            .bind_var("stats", document)
+            .build();
+
+        self.run_query::<serde_json::Value>(query)
+            .await
+            .map_err(|e| {
+                SharedError::Database(format!("Failed to persist platform stats: {}", e))
+            })?;
+
+        log::info!(
+            "Persisted platform stats snapshot (last_updated={})",
+            stats.last_updated
+        );
+
+        Ok(stats)
+    }
+
+    /// Returns the persisted platform statistics, if a recompute has run
+    pub async fn get_persisted_platform_stats(&self) -> Result<Option<PlatformStats>> {
+        let _timer = self.timer("get_persisted_platform_stats", String::new());
+        let query = AqlQuery::builder()
+            .query(
+                r#"
+                FOR s IN platform_stats
+                    FILTER s._key == @key
+                    LIMIT 1
+                    RETURN UNSET(s, "_key", "_id", "_rev")
+                "#,
+            )
+            .bind_var("key", Self::PLATFORM_STATS_KEY)
+            .build();
+
+        let results: Vec<PlatformStats> = self.run_query(query).await.map_err(|e| {
+            SharedError::Database(format!("Failed to get persisted platform stats: {}", e))
+        })?;
+
+        Ok(results.into_iter().next())
     }
 
     /// Get total number of players
@@ -208,7 +519,7 @@ impl<C: ClientExt> AnalyticsRepository<C> {
 
         log::debug!("Executing query: RETURN LENGTH(FOR p IN player RETURN p)");
 
-        match self.db.aql_query::<i64>(query).await {
+        match self.run_query::<i64>(query).await {
             Ok(mut cursor) => {
                 if let Some(count) = cursor.pop() {
                     log::debug!("Total players result: {}", count);
@@ -228,12 +539,12 @@ impl<C: ClientExt> AnalyticsRepository<C> {
     /// Get total number of contests
     async fn get_total_contests(&self) -> Result<i32> {
         let query = arangors::AqlQuery::builder()
-            .query("RETURN LENGTH(FOR c IN contest RETURN c)")
+            .query(r#"RETURN LENGTH(FOR c IN contest FILTER c.visibility != "private" RETURN c)"#)
             .build();
 
-        log::debug!("Executing query: RETURN LENGTH(FOR c IN contest RETURN c)");
+        log::debug!("Executing total contests query");
 
-        match self.db.aql_query::<i64>(query).await {
+        match self.run_query::<i64>(query).await {
             Ok(mut cursor) => {
                 if let Some(count) = cursor.pop() {
                     log::debug!("Total contests result: {}", count);
@@ -258,7 +569,7 @@ impl<C: ClientExt> AnalyticsRepository<C> {
 
         log::debug!("Executing query: RETURN LENGTH(FOR g IN game RETURN g)");
 
-        match self.db.aql_query::<i64>(query).await {
+        match self.run_query::<i64>(query).await {
             Ok(mut cursor) => {
                 if let Some(count) = cursor.pop() {
                     log::debug!("Total games result: {}", count);
@@ -283,7 +594,7 @@ impl<C: ClientExt> AnalyticsRepository<C> {
 
         log::debug!("Executing query: RETURN LENGTH(FOR v IN venue RETURN v)");
 
-        match self.db.aql_query::<i64>(query).await {
+        match self.run_query::<i64>(query).await {
             Ok(mut cursor) => {
                 if let Some(count) = cursor.pop() {
                     log::debug!("Total venues result: {}", count);
@@ -309,9 +620,9 @@ impl<C: ClientExt> AnalyticsRepository<C> {
                 LET cutoff_date = DATE_SUBTRACT(DATE_NOW(), @days, 'day')
                 RETURN LENGTH(
                     FOR c IN contest
-                    FILTER c.start >= cutoff_date
+                    FILTER c.start >= cutoff_date AND c.visibility != "private"
                     FOR result IN resulted_in
-                    FILTER result._from == c._id
+                    FILTER result._from == c._id AND result.guest_name == null
                     COLLECT player_id = result._to
                     RETURN player_id
                 )
@@ -322,7 +633,7 @@ impl<C: ClientExt> AnalyticsRepository<C> {
 
         log::debug!("Executing active players query for {} days", days);
 
-        match self.db.aql_query::<i64>(original_query).await {
+        match self.run_query::<i64>(original_query).await {
             Ok(mut cursor) => {
                 if let Some(count) = cursor.pop() {
                     log::debug!("Active players result for {} days: {}", days, count);
@@ -343,7 +654,7 @@ impl<C: ClientExt> AnalyticsRepository<C> {
                         LET cutoff_date = DATE_SUBTRACT(DATE_NOW(), @days, 'day')
                         RETURN LENGTH(
                             FOR c IN contest
-                            FILTER c.start >= cutoff_date
+                            FILTER c.start >= cutoff_date AND c.visibility != "private"
                             RETURN c
                         )
                     "#,
@@ -351,7 +662,7 @@ impl<C: ClientExt> AnalyticsRepository<C> {
                     .bind_var("days", days)
                     .build();
 
-                match self.db.aql_query::<i64>(fallback_query).await {
+                match self.run_query::<i64>(fallback_query).await {
                     Ok(mut fallback_cursor) => {
                         if let Some(contest_count) = fallback_cursor.pop() {
                             // Estimate 2-4 players per contest as fallback
@@ -384,7 +695,7 @@ impl<C: ClientExt> AnalyticsRepository<C> {
                 LET cutoff_date = DATE_SUBTRACT(DATE_NOW(), @days, 'day')
                 RETURN LENGTH(
                     FOR c IN contest
-                    FILTER c.start >= cutoff_date
+                    FILTER c.start >= cutoff_date AND c.visibility != "private"
                     RETURN c
                 )
             "#,
@@ -394,7 +705,7 @@ impl<C: ClientExt> AnalyticsRepository<C> {
 
         log::debug!("Executing contests in period query for {} days", days);
 
-        match self.db.aql_query::<i64>(query).await {
+        match self.run_query::<i64>(query).await {
             Ok(mut cursor) => {
                 if let Some(count) = cursor.pop() {
                     log::debug!("Contests in period result for {} days: {}", days, count);
@@ -419,6 +730,7 @@ impl<C: ClientExt> AnalyticsRepository<C> {
                 r#"
                 LET contest_participants = (
                     FOR c IN contest
+                    FILTER c.visibility != "private"
                     LET participant_count = LENGTH(
                         FOR result IN resulted_in
                         FILTER result._from == c._id
@@ -433,7 +745,7 @@ impl<C: ClientExt> AnalyticsRepository<C> {
 
         log::debug!("Executing average participants per contest query");
 
-        match self.db.aql_query::<f64>(original_query).await {
+        match self.run_query::<f64>(original_query).await {
             Ok(mut cursor) => {
                 if let Some(avg) = cursor.pop() {
                     log::debug!("Average participants per contest result: {}", avg);
@@ -453,16 +765,17 @@ impl<C: ClientExt> AnalyticsRepository<C> {
         }
     }
 
-    /// Get top games by play count
+    /// Get top games by play count, counting each contest once per game
     async fn get_top_games(&self, limit: i32) -> Result<Vec<(String, i32)>> {
         let query = arangors::AqlQuery::builder()
             .query(
                 r#"
                 FOR played_with IN played_with
+                FILTER DOCUMENT(played_with._from).visibility != "private"
                 LET game = DOCUMENT(played_with._to)
                 FILTER game != null
-                COLLECT game_id = game._id, game_name = game.name INTO game_plays
-                LET play_count = LENGTH(game_plays)
+                COLLECT game_id = game._id, game_name = game.name INTO game_plays = played_with._from
+                LET play_count = LENGTH(UNIQUE(game_plays))
                 SORT play_count DESC
                 LIMIT @limit
                 RETURN { name: game_name, plays: play_count }
@@ -479,7 +792,7 @@ impl<C: ClientExt> AnalyticsRepository<C> {
             plays: i64,
         }
 
-        match self.db.aql_query::<GameResult>(query).await {
+        match self.run_query::<GameResult>(query).await {
             Ok(cursor) => {
                 let games: Vec<(String, i32)> = cursor
                     .into_iter()
@@ -495,16 +808,28 @@ impl<C: ClientExt> AnalyticsRepository<C> {
         }
     }
 
-    /// Get top venues by contest count
+    /// Get top venues by contest count.
+    ///
+    /// A venue merged into another keeps its document with `merged_into` set
+    /// to the survivor's id, and old `played_at` edges may still point at it.
+    /// Merged venues are left out and their contests counted, once, for the
+    /// survivor.
     async fn get_top_venues(&self, limit: i32) -> Result<Vec<(String, i32)>> {
         let query = arangors::AqlQuery::builder()
             .query(
                 r#"
                 FOR venue IN venue
+                FILTER venue.merged_into == null
+                LET venue_ids = APPEND([venue._id], (
+                    FOR merged IN venue
+                    FILTER merged.merged_into == venue._id
+                    RETURN merged._id
+                ))
                 LET contest_count = LENGTH(
                     FOR played_at IN played_at
-                    FILTER played_at._to == venue._id
-                    RETURN played_at
+                    FILTER played_at._to IN venue_ids
+                    FILTER DOCUMENT(played_at._from).visibility != "private"
+                    RETURN DISTINCT played_at._from
                 )
                 SORT contest_count DESC
                 LIMIT @limit
@@ -522,7 +847,7 @@ impl<C: ClientExt> AnalyticsRepository<C> {
             contests: i64,
         }
 
-        match self.db.aql_query::<VenueResult>(query).await {
+        match self.run_query::<VenueResult>(query).await {
             Ok(cursor) => {
                 let venues: Vec<(String, i32)> = cursor
                     .into_iter()
@@ -539,12 +864,24 @@ impl<C: ClientExt> AnalyticsRepository<C> {
     }
 
     /// Get leaderboard data by category
+    ///
+    /// With `active_within_days`, the win-rate board leaves out players whose
+    /// last contest started longer ago than that; the count-based boards
+    /// always cover everyone.
     pub async fn get_leaderboard(
         &self,
         category: &str,
         limit: i32,
         offset: i32,
+        active_within_days: Option<u32>,
     ) -> Result<Vec<PlayerWinRate>> {
+        let _timer = self.timer(
+            "get_leaderboard",
+            format!(
+                "category={} limit={} offset={} active_within_days={:?}",
+                category, limit, offset, active_within_days
+            ),
+        );
         log::debug!("Executing leaderboard query for category: {}", category);
 
         // Use aql_query with a custom struct for the result
@@ -574,6 +911,12 @@ impl<C: ClientExt> AnalyticsRepository<C> {
                             RETURN result
                         )
                         FILTER total_contests > 0
+                        FILTER @active_days == null OR MAX(
+                            FOR result IN contests
+                            LET contest = DOCUMENT(result._from)
+                            FILTER contest != null
+                            RETURN DATE_TIMESTAMP(contest.start)
+                        ) >= DATE_TIMESTAMP(DATE_SUBTRACT(DATE_NOW(), @active_days, "days"))
                         LET win_rate = (wins * 100.0) / total_contests
                         SORT win_rate DESC, total_contests DESC
                         LIMIT @offset, @limit
@@ -588,6 +931,7 @@ impl<C: ClientExt> AnalyticsRepository<C> {
                 )
                 .bind_var("limit", limit)
                 .bind_var("offset", offset)
+                .bind_var("active_days", active_within_days)
                 .build(),
             "total_wins" => arangors::AqlQuery::builder()
                 .query(
@@ -652,7 +996,7 @@ impl<C: ClientExt> AnalyticsRepository<C> {
             }
         };
 
-        match self.db.aql_query::<LeaderboardResult>(query).await {
+        match self.run_query::<LeaderboardResult>(query).await {
             Ok(cursor) => {
                 let results: Vec<LeaderboardResult> = cursor.into_iter().collect();
                 log::debug!("Leaderboard query returned {} results", results.len());
@@ -772,10 +1116,52 @@ impl<C: ClientExt> AnalyticsRepository<C> {
     }
 }
 
+/// The current and longest runs of first places in `places`, oldest first
+fn win_streaks(places: impl IntoIterator<Item = i32>) -> (i32, i32) {
+    let mut current = 0;
+    let mut longest = 0;
+    for place in places {
+        if place == 1 {
+            current += 1;
+            longest = longest.max(current);
+        } else {
+            current = 0;
+        }
+    }
+    (current, longest)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_win_streaks_track_current_and_longest_runs() {
+        assert_eq!(win_streaks([]), (0, 0));
+        assert_eq!(win_streaks([2, 3]), (0, 0));
+        assert_eq!(win_streaks([1, 1, 1, 2, 1, 1]), (2, 3));
+        assert_eq!(win_streaks([1, 1, 3]), (0, 2));
+    }
+
+    #[test]
+    fn test_every_write_target_is_an_analytics_collection() {
+        let written = [
+            PLAYER_STATS,
+            CONTEST_STATS,
+            GAME_STATS,
+            VENUE_STATS,
+            PLATFORM_STATS,
+        ];
+        let indexed = ANALYTICS_INDEXES.iter().map(|(collection, _)| *collection);
+        for target in written.into_iter().chain(indexed) {
+            assert!(
+                ANALYTICS_COLLECTIONS.contains(&target),
+                "{} is written by analytics but missing from ANALYTICS_COLLECTIONS",
+                target
+            );
+        }
+    }
+
     #[test]
     fn test_query_building() {
         // Test that query building functions work without database connection
@@ -817,6 +1203,10 @@ mod tests {
 impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
     /// Get a display label for a player (handle -> email -> name)
     pub async fn get_player_display_label(&self, player_id: &str) -> Result<Option<String>> {
+        let _timer = self.timer(
+            "get_player_display_label",
+            format!("player_id={}", player_id),
+        );
         let query = r#"
             LET player = DOCUMENT(@player_id)
             RETURN {
@@ -832,38 +1222,134 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             .bind_var("player_id", player_id)
             .build();
 
-        match self.db.aql_query::<serde_json::Value>(aql).await {
-            Ok(mut results) => {
-                if let Some(row) = results.pop() {
-                    let handle = row.get("handle").and_then(|v| v.as_str());
-                    let email = row.get("email").and_then(|v| v.as_str());
-                    let firstname = row.get("firstname").and_then(|v| v.as_str());
-                    let lastname = row.get("lastname").and_then(|v| v.as_str());
-                    let name = match (firstname, lastname) {
-                        (Some(first), Some(last)) => Some(format!("{} {}", first, last)),
-                        (Some(first), None) => Some(first.to_string()),
-                        _ => None,
-                    };
-                    Ok(handle
-                        .map(|s| s.to_string())
-                        .or_else(|| email.map(|s| s.to_string()))
-                        .or(name))
-                } else {
-                    Ok(None)
+        match self.run_query::<serde_json::Value>(aql).await {
+            Ok(mut results) => Ok(results.pop().as_ref().and_then(display_label)),
+            Err(e) => Err(SharedError::Database(format!(
+                "Failed to query player display label: {}",
+                e
+            ))),
+        }
+    }
+
+    /// Display labels for several players in one query, keyed by player id
+    ///
+    /// Repeated ids are looked up once; players that do not exist are left out.
+    pub async fn get_player_display_labels(
+        &self,
+        player_ids: &[String],
+    ) -> Result<HashMap<String, String>> {
+        let mut seen = HashSet::new();
+        let unique: Vec<&str> = player_ids
+            .iter()
+            .map(String::as_str)
+            .filter(|id| seen.insert(*id))
+            .collect();
+        if unique.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let _timer = self.timer(
+            "get_player_display_labels",
+            format!("players={}", unique.len()),
+        );
+        let query = r#"
+            FOR player IN DOCUMENT(@player_ids)
+                RETURN {
+                    id: player._id,
+                    handle: player.handle,
+                    email: player.email,
+                    firstname: player.firstname,
+                    lastname: player.lastname
                 }
+        "#;
+
+        let aql = AqlQuery::builder()
+            .query(query)
+            .bind_var("player_ids", serde_json::json!(unique))
+            .build();
+
+        let rows: Vec<serde_json::Value> = self.run_query(aql).await.map_err(|e| {
+            SharedError::Database(format!("Failed to query player display labels: {}", e))
+        })?;
+        Ok(rows
+            .iter()
+            .filter_map(|row| {
+                let id = row.get("id")?.as_str()?;
+                Some((id.to_string(), display_label(row)?))
+            })
+            .collect())
+    }
+
+    /// Get the profile fields of a player
+    pub async fn get_player_profile(
+        &self,
+        player_id: &str,
+    ) -> Result<Option<shared::dto::player::PlayerProfileDto>> {
+        let _timer = self.timer("get_player_profile", format!("player_id={}", player_id));
+        let query = r#"
+            LET player = DOCUMENT(@player_id)
+            FILTER player != null
+            RETURN {
+                firstname: player.firstname,
+                lastname: player.lastname,
+                handle: player.handle,
+                email: player.email,
+                last_login_at: player.lastLoginAt
             }
+        "#;
+
+        let aql = AqlQuery::builder()
+            .query(query)
+            .bind_var("player_id", player_id)
+            .build();
+
+        match self
+            .run_query::<shared::dto::player::PlayerProfileDto>(aql)
+            .await
+        {
+            Ok(results) => Ok(results.into_iter().next()),
             Err(e) => Err(SharedError::Database(format!(
-                "Failed to query player display label: {}",
+                "Failed to query player profile: {}",
                 e
             ))),
         }
     }
 
+    /// Get a player's placements in their latest `limit` contests, newest first
+    pub async fn get_recent_placements(&self, player_id: &str, limit: i32) -> Result<Vec<i32>> {
+        let _timer = self.timer(
+            "get_recent_placements",
+            format!("player_id={}, limit={}", player_id, limit),
+        );
+        let query = r#"
+            FOR result IN resulted_in
+            FILTER result._to == @player_id AND result.place != null
+            LET contest = DOCUMENT(result._from)
+            FILTER contest != null
+            SORT DATE_TIMESTAMP(contest.start) DESC
+            LIMIT @limit
+            RETURN result.place
+        "#;
+
+        let aql = AqlQuery::builder()
+            .query(query)
+            .bind_var("player_id", player_id)
+            .bind_var("limit", limit)
+            .build();
+
+        self.run_query::<i32>(aql)
+            .await
+            .map_err(|e| SharedError::Database(format!("Failed to query recent placements: {}", e)))
+    }
+
     /// Get latest global rating info for a player
     pub async fn get_player_rating_latest(
         &self,
         player_id: &str,
     ) -> Result<Option<(f64, f64, i32)>> {
+        let _timer = self.timer(
+            "get_player_rating_latest",
+            format!("player_id={}", player_id),
+        );
         let query = r#"
             FOR r IN rating_latest
             FILTER r.player_id == @player_id AND r.scope_type == "global"
@@ -880,7 +1366,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             .bind_var("player_id", player_id)
             .build();
 
-        match self.db.aql_query::<serde_json::Value>(aql).await {
+        match self.run_query::<serde_json::Value>(aql).await {
             Ok(mut results) => {
                 if let Some(row) = results.pop() {
                     let rating = row.get("rating").and_then(|v| v.as_f64()).unwrap_or(1200.0);
@@ -903,6 +1389,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
 
     /// Get player statistics
     pub async fn get_player_stats(&self, player_id: &str) -> Result<Option<PlayerStats>> {
+        let _timer = self.timer("get_player_stats", format!("player_id={}", player_id));
         let query = r#"
             LET player = DOCUMENT(@player_id)
             LET contests = (
@@ -949,18 +1436,60 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             .bind_var("player_id", player_id)
             .build();
 
-        match self.db.aql_query::<PlayerStats>(aql).await {
-            Ok(results) => Ok(results.into_iter().next()),
-            Err(e) => Err(SharedError::Database(format!(
-                "Failed to query player stats: {}",
-                e
-            ))),
+        let stats = match self.run_query::<PlayerStats>(aql).await {
+            Ok(results) => results.into_iter().next(),
+            Err(e) => {
+                return Err(SharedError::Database(format!(
+                    "Failed to query player stats: {}",
+                    e
+                )))
+            }
+        };
+
+        match stats {
+            Some(mut stats) => {
+                let (current_streak, longest_streak) = self.get_player_streaks(player_id).await?;
+                stats.current_streak = current_streak;
+                stats.longest_streak = longest_streak;
+                Ok(Some(stats))
+            }
+            None => Ok(None),
         }
     }
 
+    /// Get a player's current and longest runs of consecutive first places
+    ///
+    /// Contests are taken in start order; a first place shared in a tie
+    /// counts as a win. Contests without a valid start can't be placed in
+    /// the run and are left out.
+    pub async fn get_player_streaks(&self, player_id: &str) -> Result<(i32, i32)> {
+        let _timer = self.timer("get_player_streaks", format!("player_id={}", player_id));
+        let query = r#"
+            FOR result IN resulted_in
+            FILTER result._to == @player_id AND result.place != null
+            LET contest = DOCUMENT(result._from)
+            FILTER contest != null
+            LET started = DATE_TIMESTAMP(contest.start)
+            FILTER started != null
+            SORT started ASC, contest._key ASC
+            RETURN result.place
+        "#;
+
+        let aql = AqlQuery::builder()
+            .query(query)
+            .bind_var("player_id", player_id)
+            .build();
+
+        let places = self
+            .run_query::<i32>(aql)
+            .await
+            .map_err(|e| SharedError::Database(format!("Failed to query player streaks: {}", e)))?;
+        Ok(win_streaks(places))
+    }
+
     /// Saves player statistics to database
     pub async fn save_player_stats(&self, stats: &PlayerStats) -> Result<()> {
-        let collection = self.db.collection("player_stats").await.map_err(|e| {
+        let collection = self.db.collection(PLAYER_STATS).await.map_err(|e| {
             SharedError::Database(format!("Failed to get player_stats collection: {}", e))
         })?;
 
@@ -978,7 +1507,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
 
     /// Updates player statistics in database
     pub async fn update_player_stats(&self, stats: &PlayerStats) -> Result<()> {
-        let collection = self.db.collection("player_stats").await.map_err(|e| {
+        let collection = self.db.collection(PLAYER_STATS).await.map_err(|e| {
             SharedError::Database(format!("Failed to get player_stats collection: {}", e))
         })?;
 
@@ -996,7 +1525,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
 
     /// Saves contest statistics to database
     pub async fn save_contest_stats(&self, stats: &ContestStats) -> Result<()> {
-        let collection = self.db.collection("contest_stats").await.map_err(|e| {
+        let collection = self.db.collection(CONTEST_STATS).await.map_err(|e| {
             SharedError::Database(format!("Failed to get contest_stats collection: {}", e))
         })?;
 
@@ -1014,6 +1543,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
 
     /// Get contest statistics
     pub async fn get_contest_stats(&self, contest_id: &str) -> Result<Option<ContestStats>> {
+        let _timer = self.timer("get_contest_stats", format!("contest_id={}", contest_id));
         log::debug!("Querying contest stats for contest_id: {}", contest_id);
 
         // First check if the contest exists
@@ -1022,7 +1552,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             .bind_var("contest_id", contest_id)
             .build();
 
-        match self.db.aql_query::<String>(contest_exists_query).await {
+        match self.run_query::<String>(contest_exists_query).await {
             Ok(cursor) => {
                 if cursor.is_empty() {
                     log::debug!("Contest not found: {}", contest_id);
@@ -1050,7 +1580,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
                 LET participant_count = LENGTH(participants)
                 LET completion_count = LENGTH(
                     FOR result IN participants
-                    FILTER result.place > 0
+                    FILTER TO_NUMBER(result.place) > 0
                     RETURN result
                 )
                 LET completion_rate = participant_count > 0 ? (completion_count * 100.0) / participant_count : 0
@@ -1105,7 +1635,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             last_updated: String,
         }
 
-        match self.db.aql_query::<ContestStatsResult>(query).await {
+        match self.run_query::<ContestStatsResult>(query).await {
             Ok(mut cursor) => {
                 if let Some(result) = cursor.pop() {
                     log::debug!("Contest stats query result: contest_id={}, participants={}, completion_rate={:.2}%", 
@@ -1138,14 +1668,36 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
     }
 
     /// Get contest trends (monthly contest frequency)
-    pub async fn get_contest_trends(&self, months: i32) -> Result<Vec<MonthlyContests>> {
+    ///
+    /// Archived contests are counted only when `include_archived` is set;
+    /// private contests never are.
+    pub async fn get_contest_trends(
+        &self,
+        months: i32,
+        include_archived: bool,
+    ) -> Result<Vec<MonthlyContests>> {
+        let _timer = self.timer(
+            "get_contest_trends",
+            format!("months={}, include_archived={}", months, include_archived),
+        );
         let query = arangors::AqlQuery::builder()
             .query(
                 r#"
-                FOR contest IN contest
-                FILTER contest.start >= DATE_SUBTRACT(DATE_NOW(), @months, 'month')
-                LET year = DATE_YEAR(contest.start)
-                LET month = DATE_MONTH(contest.start)
+                LET since = DATE_SUBTRACT(DATE_NOW(), @months, 'month')
+                LET hot = (
+                    FOR contest IN contest
+                    FILTER contest.start >= since AND contest.visibility != "private"
+                    RETURN contest.start
+                )
+                LET archived = @include_archived ? (
+                    FOR archived IN contest_archive
+                    FILTER archived.contest.start >= since
+                    FILTER archived.contest.visibility != "private"
+                    RETURN archived.contest.start
+                ) : []
+                FOR start IN APPEND(hot, archived)
+                LET year = DATE_YEAR(start)
+                LET month = DATE_MONTH(start)
                 COLLECT year_month = { year: year, month: month }
                 WITH COUNT INTO contests
                 SORT year_month.year, year_month.month
@@ -1157,6 +1709,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             "#,
             )
             .bind_var("months", months)
+            .bind_var("include_archived", include_archived)
             .build();
 
         #[derive(serde::Deserialize)]
@@ -1166,7 +1719,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             contests: i32,
         }
 
-        match self.db.aql_query::<ContestTrendResult>(query).await {
+        match self.run_query::<ContestTrendResult>(query).await {
             Ok(cursor) => {
                 let trends: Vec<MonthlyContests> = cursor
                     .into_iter()
@@ -1190,6 +1743,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
 
     /// Get daily active players (unique players per day) for the last N days
     pub async fn get_daily_active_players(&self, days: i32) -> Result<Vec<(String, i32)>> {
+        let _timer = self.timer("get_daily_active_players", format!("days={}", days));
         let query = arangors::AqlQuery::builder()
             .query(
                 r#"
@@ -1197,7 +1751,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
                 LET pairs = (
                   FOR r IN resulted_in
                     LET c = DOCUMENT(r._from)
-                    FILTER c != null AND c.start >= cutoff
+                    FILTER c != null AND c.start >= cutoff AND c.visibility != "private"
                     LET day = DATE_FORMAT(c.start, "%Y-%m-%d")
                     RETURN { day, player_id: r._to }
                 )
@@ -1217,7 +1771,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             count: i32,
         }
 
-        match self.db.aql_query::<DayCount>(query).await {
+        match self.run_query::<DayCount>(query).await {
             Ok(cursor) => {
                 let out: Vec<(String, i32)> =
                     cursor.into_iter().map(|e| (e.day, e.count)).collect();
@@ -1232,12 +1786,13 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
 
     /// Get daily contests count for the last N days
     pub async fn get_daily_contests(&self, days: i32) -> Result<Vec<(String, i32)>> {
+        let _timer = self.timer("get_daily_contests", format!("days={}", days));
         let query = arangors::AqlQuery::builder()
             .query(
                 r#"
                 LET cutoff = DATE_SUBTRACT(DATE_NOW(), @days, 'day')
                 FOR c IN contest
-                  FILTER c.start >= cutoff
+                  FILTER c.start >= cutoff AND c.visibility != "private"
                   LET day = DATE_FORMAT(c.start, "%Y-%m-%d")
                   COLLECT day WITH COUNT INTO contests
                   SORT day ASC
@@ -1253,7 +1808,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             count: i32,
         }
 
-        match self.db.aql_query::<DayCount>(query).await {
+        match self.run_query::<DayCount>(query).await {
             Ok(cursor) => {
                 let out: Vec<(String, i32)> =
                     cursor.into_iter().map(|e| (e.day, e.count)).collect();
@@ -1268,6 +1823,10 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
 
     /// Get contest difficulty analysis
     pub async fn get_contest_difficulty_analysis(&self, contest_id: &str) -> Result<f64> {
+        let _timer = self.timer(
+            "get_contest_difficulty_analysis",
+            format!("contest_id={}", contest_id),
+        );
         let query = arangors::AqlQuery::builder()
             .query(r#"
                 FOR contest IN contest
@@ -1295,7 +1854,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             .bind_var("contest_id", contest_id)
             .build();
 
-        match self.db.aql_query::<f64>(query).await {
+        match self.run_query::<f64>(query).await {
             Ok(mut cursor) => {
                 if let Some(difficulty) = cursor.pop() {
                     Ok(difficulty)
@@ -1315,6 +1874,10 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
 
     /// Get contest excitement rating (based on close finishes)
     pub async fn get_contest_excitement_rating(&self, contest_id: &str) -> Result<f64> {
+        let _timer = self.timer(
+            "get_contest_excitement_rating",
+            format!("contest_id={}", contest_id),
+        );
         let query = arangors::AqlQuery::builder()
             .query(r#"
                 FOR contest IN contest
@@ -1336,7 +1899,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             .bind_var("contest_id", contest_id)
             .build();
 
-        match self.db.aql_query::<f64>(query).await {
+        match self.run_query::<f64>(query).await {
             Ok(mut cursor) => {
                 if let Some(excitement) = cursor.pop() {
                     Ok(excitement)
@@ -1354,10 +1917,170 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
         }
     }
 
+    /// The most exciting contest completed in the last `window_days` days
+    ///
+    /// Uses the same close-finish excitement rating as
+    /// [`Self::get_contest_excitement_rating`], over contests with at least
+    /// two placed participants; ties go to the larger field, then the later
+    /// start. None when no contest qualifies.
+    pub async fn get_featured_contest(&self, window_days: i32) -> Result<Option<FeaturedContest>> {
+        let _timer = self.timer(
+            "get_featured_contest",
+            format!("window_days={}", window_days),
+        );
+        let query = arangors::AqlQuery::builder()
+            .query(
+                r#"
+                LET window_start = DATE_TIMESTAMP(DATE_SUBTRACT(DATE_NOW(), @window_days, "days"))
+                FOR c IN contest
+                    FILTER c.visibility != "private"
+                    LET stop_ts = DATE_TIMESTAMP(c.stop)
+                    FILTER stop_ts >= window_start AND stop_ts <= DATE_NOW()
+                    LET results = (
+                        FOR r IN resulted_in
+                        FILTER r._from == c._id AND r.place > 0
+                        SORT r.place
+                        RETURN r
+                    )
+                    FILTER LENGTH(results) >= 2
+                    LET first_place = results[0]
+                    LET second_place = results[1]
+                    LET score_difference = ABS(first_place.score - second_place.score)
+                    LET max_score = MAX(first_place.score, second_place.score)
+                    LET closeness_factor = max_score > 0 ? 1.0 - (score_difference / max_score) : 1.0
+                    LET excitement = MIN(5.0 + closeness_factor * 5.0, 10.0)
+                    SORT excitement DESC, LENGTH(results) DESC, c.start DESC
+                    LIMIT 1
+                    LET venue = FIRST(
+                        FOR e IN played_at
+                        FILTER e._from == c._id
+                        RETURN DOCUMENT(e._to).displayName
+                    )
+                    LET games = (
+                        FOR e IN played_with
+                        FILTER e._from == c._id
+                        LET g = DOCUMENT(e._to)
+                        FILTER g != null
+                        RETURN g.name
+                    )
+                    LET standings = (
+                        FOR r IN results
+                        LET p = DOCUMENT(r._to)
+                        RETURN {
+                            player_id: r._to,
+                            handle: NOT_NULL(p.handle, ""),
+                            place: r.place,
+                            result: NOT_NULL(r.result, r.place == 1 ? "won" : "lost")
+                        }
+                    )
+                    RETURN {
+                        contest_id: c._id,
+                        name: NOT_NULL(c.name, ""),
+                        start: c.start,
+                        stop: c.stop,
+                        venue: venue,
+                        games: games,
+                        excitement_rating: excitement,
+                        standings: standings
+                    }
+                "#,
+            )
+            .bind_var("window_days", window_days)
+            .build();
+
+        match self.run_query::<FeaturedContest>(query).await {
+            Ok(mut contests) => Ok(contests.pop()),
+            Err(e) => {
+                log::error!("Failed to query featured contest: {}", e);
+                Err(SharedError::Database(format!(
+                    "Failed to query featured contest: {}",
+                    e
+                )))
+            }
+        }
+    }
+
+    /// The biggest upset among contests completed in the last `window_days` days
+    ///
+    /// Each placed participant goes in with their latest global rating from a
+    /// period ending before the contest started, or their current rating when
+    /// no such history exists; unrated participants are left out. The upset
+    /// is the lowest-rated first-place finisher over the highest-rated player
+    /// they beat, and the contest with the widest such gap wins. None when no
+    /// winner beat a higher-rated player.
+    pub async fn get_biggest_upset(&self, window_days: i32) -> Result<Option<BiggestUpset>> {
+        let _timer = self.timer("get_biggest_upset", format!("window_days={}", window_days));
+        let query = arangors::AqlQuery::builder()
+            .query(
+                r#"
+                LET window_start = DATE_TIMESTAMP(DATE_SUBTRACT(DATE_NOW(), @window_days, "days"))
+                FOR c IN contest
+                    FILTER c.visibility != "private"
+                    LET stop_ts = DATE_TIMESTAMP(c.stop)
+                    FILTER stop_ts >= window_start AND stop_ts <= DATE_NOW()
+                    LET start_ts = DATE_TIMESTAMP(c.start)
+                    LET rated = (
+                        FOR r IN resulted_in
+                        FILTER r._from == c._id AND r.place > 0
+                        LET historical = FIRST(
+                            FOR h IN rating_history
+                            FILTER h.player_id == r._to AND h.scope_type == "global" AND h.scope_id == null
+                            FILTER DATE_TIMESTAMP(h.period_end) <= start_ts
+                            SORT h.period_end DESC
+                            LIMIT 1
+                            RETURN h.rating
+                        )
+                        LET current = FIRST(
+                            FOR l IN rating_latest
+                            FILTER l.player_id == r._to AND l.scope_type == "global" AND l.scope_id == null
+                            LIMIT 1
+                            RETURN l.rating
+                        )
+                        LET rating = NOT_NULL(historical, current)
+                        FILTER rating != null
+                        RETURN { player_id: r._to, place: r.place, rating: rating }
+                    )
+                    LET winner = FIRST(
+                        FOR p IN rated FILTER p.place == 1 SORT p.rating ASC LIMIT 1 RETURN p
+                    )
+                    LET favourite = FIRST(
+                        FOR p IN rated FILTER p.place > 1 SORT p.rating DESC LIMIT 1 RETURN p
+                    )
+                    FILTER winner != null AND favourite != null AND favourite.rating > winner.rating
+                    LET rating_gap = favourite.rating - winner.rating
+                    SORT rating_gap DESC, c.start DESC
+                    LIMIT 1
+                    RETURN {
+                        contest_id: c._id,
+                        contest_name: NOT_NULL(c.name, ""),
+                        start: c.start,
+                        winner: MERGE(winner, { handle: NOT_NULL(DOCUMENT(winner.player_id).handle, "") }),
+                        favourite: MERGE(favourite, { handle: NOT_NULL(DOCUMENT(favourite.player_id).handle, "") }),
+                        rating_gap: rating_gap
+                    }
+                "#,
+            )
+            .bind_var("window_days", window_days)
+            .build();
+
+        match self.run_query::<BiggestUpset>(query).await {
+            Ok(mut upsets) => Ok(upsets.pop()),
+            Err(e) => {
+                log::error!("Failed to query biggest upset: {}", e);
+                Err(SharedError::Database(format!(
+                    "Failed to query biggest upset: {}",
+                    e
+                )))
+            }
+        }
+    }
+
     /// Get recent contests with statistics
     pub async fn get_recent_contests(&self, limit: i32) -> Result<Vec<ContestStats>> {
+        let _timer = self.timer("get_recent_contests", format!("limit={}", limit));
         let query = r#"
             FOR contest IN contest
+            FILTER contest.visibility != "private"
             SORT contest.start DESC
             LIMIT @limit
             LET participant_count = LENGTH(
@@ -1367,7 +2090,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             )
             LET completion_count = LENGTH(
                 FOR result IN resulted_in
-                FILTER result._from == contest._id
+                FILTER result._from == contest._id AND TO_NUMBER(result.place) > 0
                 RETURN result
             )
             LET average_placement = (
@@ -1390,7 +2113,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
                 contest_id: contest._id,
                 participant_count: participant_count,
                 completion_count: completion_count,
-                completion_rate: completion_count > 0 ? (completion_count / participant_count) * 100 : 0,
+                completion_rate: participant_count > 0 ? (completion_count * 100.0) / participant_count : 0,
                 average_placement: average_placement || 0,
                 duration_minutes: IS_NULL(contest.duration_minutes) ? 0 : contest.duration_minutes,
                 most_popular_game: most_popular_game,
@@ -1426,7 +2149,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             last_updated: String,
         }
 
-        let results: Vec<RecentContestResult> = self.db.aql_query(aql).await.map_err(|e| {
+        let results: Vec<RecentContestResult> = self.run_query(aql).await.map_err(|e| {
             SharedError::Database(format!("Failed to query recent contests: {}", e))
         })?;
 
@@ -1454,6 +2177,10 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
         &self,
         player_id: &str,
     ) -> Result<Vec<shared::dto::analytics::PlayerOpponentDto>> {
+        let _timer = self.timer(
+            "get_players_who_beat_me",
+            format!("player_id={}", player_id),
+        );
         log::info!("get_players_who_beat_me called for player: {}", player_id);
 
         // REAL QUERY: Find players who have beaten the current player
@@ -1464,7 +2191,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             FILTER contest._id == my_result._from
             FOR other_result IN resulted_in
             FILTER other_result._from == contest._id
-            FILTER other_result._to != @player_id
+            FILTER other_result._to != @player_id AND other_result.guest_name == null
             LET opponent = DOCUMENT(other_result._to)
             COLLECT opponent_id = other_result._to, opponent_data = opponent INTO contests
             LET total_contests = LENGTH(contests)
@@ -1479,6 +2206,16 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
                 RETURN contest
             )
             FILTER wins_against_me > losses_to_me
+            LET opponent_total = LENGTH(
+                FOR result IN resulted_in
+                FILTER result._to == opponent_id
+                RETURN result
+            )
+            LET opponent_wins = LENGTH(
+                FOR result IN resulted_in
+                FILTER result._to == opponent_id AND result.place == 1
+                RETURN result
+            )
             SORT wins_against_me DESC, total_contests DESC
             RETURN {
                 player_id: opponent_id,
@@ -1489,20 +2226,8 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
                 losses_to_me: losses_to_me,
                 win_rate_against_me: total_contests > 0 ? (wins_against_me / total_contests) * 100 : 0,
                 last_played: DATE_ISO8601(DATE_NOW()),
-                total_contests: LENGTH(
-                    FOR result IN resulted_in
-                    FILTER result._to == opponent_id
-                    RETURN result
-                ),
-                overall_win_rate: LENGTH(
-                    FOR result IN resulted_in
-                    FILTER result._to == opponent_id AND result.result == "won"
-                    RETURN result
-                ) / LENGTH(
-                    FOR result IN resulted_in
-                    FILTER result._to == opponent_id
-                    RETURN result
-                ) * 100
+                total_contests: opponent_total,
+                overall_win_rate: opponent_total > 0 ? (opponent_wins * 100.0) / opponent_total : 0
             }
         "#;
 
@@ -1518,8 +2243,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             .build();
 
         match self
-            .db
-            .aql_query::<shared::dto::analytics::PlayerOpponentDto>(aql)
+            .run_query::<shared::dto::analytics::PlayerOpponentDto>(aql)
             .await
         {
             Ok(results) => {
@@ -1542,6 +2266,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
         &self,
         player_id: &str,
     ) -> Result<Vec<shared::dto::analytics::PlayerOpponentDto>> {
+        let _timer = self.timer("get_players_i_beat", format!("player_id={}", player_id));
         log::info!("get_players_i_beat called for player: {}", player_id);
 
         // REAL QUERY: Find players that the current player has beaten
@@ -1552,7 +2277,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             FILTER contest._id == my_result._from
             FOR other_result IN resulted_in
             FILTER other_result._from == contest._id
-            FILTER other_result._to != @player_id
+            FILTER other_result._to != @player_id AND other_result.guest_name == null
             LET opponent = DOCUMENT(other_result._to)
             COLLECT opponent_id = other_result._to, opponent_data = opponent INTO contests
             LET total_contests = LENGTH(contests)
@@ -1567,6 +2292,16 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
                 RETURN contest
             )
             FILTER my_wins_against_them > their_wins_against_me
+            LET opponent_total = LENGTH(
+                FOR result IN resulted_in
+                FILTER result._to == opponent_id
+                RETURN result
+            )
+            LET opponent_wins = LENGTH(
+                FOR result IN resulted_in
+                FILTER result._to == opponent_id AND result.place == 1
+                RETURN result
+            )
             SORT my_wins_against_them DESC, total_contests DESC
             RETURN {
                 player_id: opponent_id,
@@ -1577,20 +2312,8 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
                 losses_to_me: my_wins_against_them,
                 win_rate_against_me: total_contests > 0 ? (their_wins_against_me / total_contests) * 100 : 0,
                 last_played: DATE_ISO8601(DATE_NOW()),
-                total_contests: LENGTH(
-                    FOR result IN resulted_in
-                    FILTER result._to == opponent_id
-                    RETURN result
-                ),
-                overall_win_rate: LENGTH(
-                    FOR result IN resulted_in
-                    FILTER result._to == opponent_id AND result.result == "won"
-                    RETURN result
-                ) / LENGTH(
-                    FOR result IN resulted_in
-                    FILTER result._to == opponent_id
-                    RETURN result
-                ) * 100
+                total_contests: opponent_total,
+                overall_win_rate: opponent_total > 0 ? (opponent_wins * 100.0) / opponent_total : 0
             }
         "#;
 
@@ -1606,8 +2329,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             .build();
 
         match self
-            .db
-            .aql_query::<shared::dto::analytics::PlayerOpponentDto>(aql)
+            .run_query::<shared::dto::analytics::PlayerOpponentDto>(aql)
             .await
         {
             Ok(results) => {
@@ -1625,11 +2347,78 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
         }
     }
 
+    /// Get every player the current player has shared a contest with,
+    /// whoever won, most frequent partners first
+    pub async fn get_play_partners(
+        &self,
+        player_id: &str,
+    ) -> Result<Vec<shared::dto::analytics::PlayerOpponentDto>> {
+        let _timer = self.timer("get_play_partners", format!("player_id={}", player_id));
+        let query = r#"
+            FOR my_result IN resulted_in
+            FILTER my_result._to == @player_id
+            FOR other_result IN resulted_in
+            FILTER other_result._from == my_result._from AND other_result._to != @player_id
+            FILTER other_result.guest_name == null
+            LET contest = DOCUMENT(my_result._from)
+            COLLECT partner_id = other_result._to INTO rows = {
+                contest_id: my_result._from,
+                mine: TO_NUMBER(my_result.place),
+                theirs: TO_NUMBER(other_result.place),
+                start: contest.start
+            }
+            LET partner = DOCUMENT(partner_id)
+            FILTER partner != null
+            LET contests_played = COUNT_DISTINCT(rows[*].contest_id)
+            LET wins_against_me = LENGTH(rows[* FILTER CURRENT.theirs < CURRENT.mine])
+            LET losses_to_me = LENGTH(rows[* FILTER CURRENT.mine < CURRENT.theirs])
+            LET last_played = MAX(rows[*].start)
+            LET partner_total = LENGTH(
+                FOR result IN resulted_in
+                FILTER result._to == partner_id
+                RETURN 1
+            )
+            LET partner_wins = LENGTH(
+                FOR result IN resulted_in
+                FILTER result._to == partner_id AND TO_NUMBER(result.place) == 1
+                RETURN 1
+            )
+            SORT contests_played DESC, last_played DESC
+            RETURN {
+                player_id: partner_id,
+                player_handle: partner.handle,
+                player_name: CONCAT_SEPARATOR(" ", partner.firstname, partner.lastname),
+                contests_played: contests_played,
+                wins_against_me: wins_against_me,
+                losses_to_me: losses_to_me,
+                win_rate_against_me: (wins_against_me * 100.0) / contests_played,
+                last_played: last_played,
+                total_contests: partner_total,
+                overall_win_rate: partner_total > 0 ? (partner_wins * 100.0) / partner_total : 0.0
+            }
+        "#;
+
+        let aql = AqlQuery::builder()
+            .query(query)
+            .bind_var("player_id", player_id)
+            .build();
+
+        self.run_query(aql)
+            .await
+            .map_err(|e| SharedError::Database(format!("Failed to query play partners: {}", e)))
+    }
+
     /// Get player's game performance statistics
+    ///
+    /// A multi-game contest's result counts towards each of its games.
     pub async fn get_my_game_performance(
         &self,
         player_id: &str,
     ) -> Result<Vec<shared::dto::analytics::GamePerformanceDto>> {
+        let _timer = self.timer(
+            "get_my_game_performance",
+            format!("player_id={}", player_id),
+        );
         log::info!("get_my_game_performance called for player: {}", player_id);
 
         let query = r#"
@@ -1638,13 +2427,9 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             LET contest = DOCUMENT(result._from)
             FILTER contest != null
             
-            // Get game through PLAYED_WITH edge
-            LET game_edge = FIRST(
-                FOR edge IN played_with
-                FILTER edge._from == contest._id
-                RETURN edge
-            )
-            FILTER game_edge != null
+            // Attribute the contest to every game played in it
+            FOR game_edge IN played_with
+            FILTER game_edge._from == contest._id
             LET game_doc = DOCUMENT(game_edge._to)
             FILTER game_doc != null
             
@@ -1747,8 +2532,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             .build();
 
         match self
-            .db
-            .aql_query::<shared::dto::analytics::GamePerformanceDto>(aql)
+            .run_query::<shared::dto::analytics::GamePerformanceDto>(aql)
             .await
         {
             Ok(results) => {
@@ -1779,7 +2563,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
                     .query(query)
                     .bind_vars(bind_vars.clone())
                     .build();
-                match self.db.aql_query::<serde_json::Value>(debug_aql).await {
+                match self.run_query::<serde_json::Value>(debug_aql).await {
                     Ok(raw_results) => {
                         // Avoid logging raw query data to prevent PII leakage
                         log::info!("Raw query returned {} results", raw_results.len());
@@ -1794,15 +2578,239 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
                     e
                 )))
             }
-        }
+        }
+    }
+
+    /// Suggest popular games the player has never or rarely played.
+    ///
+    /// Candidates are scored by platform-wide popularity and by how many of
+    /// their players also play the games this player has won at.
+    pub async fn recommend_games(
+        &self,
+        player_id: &str,
+        limit: i32,
+    ) -> Result<Vec<shared::dto::analytics::GameRecommendationDto>> {
+        let _timer = self.timer(
+            "recommend_games",
+            format!("player_id={} limit={}", player_id, limit),
+        );
+        let query = r#"
+            LET my_games = (
+                FOR r IN resulted_in
+                FILTER r._to == @player_id
+                FOR pw IN played_with
+                FILTER pw._from == r._from
+                COLLECT game_id = pw._to
+                AGGREGATE plays = COUNT(1), wins = SUM(r.place == 1 ? 1 : 0)
+                RETURN { game_id, plays, wins }
+            )
+            LET won_game_ids = my_games[* FILTER CURRENT.wins > 0].game_id
+            LET won_game_players = UNIQUE(
+                FOR pw IN played_with
+                FILTER pw._to IN won_game_ids
+                FOR r IN resulted_in
+                FILTER r._from == pw._from AND r._to != @player_id AND r.guest_name == null
+                RETURN r._to
+            )
+            LET candidates = (
+                FOR pw IN played_with
+                COLLECT game_id = pw._to INTO contests = pw._from
+                LET player_plays = FIRST(my_games[* FILTER CURRENT.game_id == game_id].plays) || 0
+                FILTER player_plays <= @max_player_plays
+                LET game = DOCUMENT(game_id)
+                FILTER game != null
+                LET players = UNIQUE(
+                    FOR contest_id IN contests
+                    FOR r IN resulted_in
+                    FILTER r._from == contest_id
+                    RETURN r._to
+                )
+                RETURN {
+                    game_id: game_id,
+                    game_name: game.name,
+                    total_plays: LENGTH(contests),
+                    player_plays: player_plays,
+                    overlap: LENGTH(INTERSECTION(players, won_game_players))
+                }
+            )
+            LET max_plays = MAX(candidates[*].total_plays)
+            LET max_overlap = MAX(candidates[*].overlap)
+            FOR candidate IN candidates
+            LET popularity = max_plays > 0 ? candidate.total_plays / max_plays : 0
+            LET similarity = max_overlap > 0 ? candidate.overlap / max_overlap : 0
+            LET score = ROUND((0.6 * popularity + 0.4 * similarity) * 1000) / 10
+            SORT score DESC, candidate.total_plays DESC
+            LIMIT @limit
+            RETURN {
+                game_id: candidate.game_id,
+                game_name: candidate.game_name,
+                total_plays: candidate.total_plays,
+                player_plays: candidate.player_plays,
+                popularity: popularity,
+                similarity: similarity,
+                score: score,
+                reason: candidate.overlap > 0
+                    ? "Played by people you share winning games with"
+                    : (candidate.player_plays > 0 ? "You've only tried this once" : "Popular with other players")
+            }
+        "#;
+
+        let aql = AqlQuery::builder()
+            .query(query)
+            .bind_var("player_id", player_id)
+            .bind_var("max_player_plays", RARELY_PLAYED_MAX)
+            .bind_var("limit", limit)
+            .build();
+
+        self.run_query(aql).await.map_err(|e| {
+            SharedError::Database(format!("Failed to query game recommendations: {}", e))
+        })
+    }
+
+    /// Suggest players who frequent the same venues or games as the player
+    /// but have never been in a contest with them.
+    pub async fn get_networking_suggestions(
+        &self,
+        player_id: &str,
+    ) -> Result<Vec<shared::dto::analytics::NetworkingSuggestionDto>> {
+        let _timer = self.timer(
+            "get_networking_suggestions",
+            format!("player_id={}", player_id),
+        );
+        let query = r#"
+            LET my_contests = (
+                FOR r IN resulted_in
+                FILTER r._to == @player_id
+                RETURN r._from
+            )
+            LET my_opponents = UNIQUE(
+                FOR r IN resulted_in
+                FILTER r._from IN my_contests AND r._to != @player_id AND r.guest_name == null
+                RETURN r._to
+            )
+            LET my_venues = UNIQUE(
+                FOR pa IN played_at
+                FILTER pa._from IN my_contests
+                RETURN pa._to
+            )
+            LET my_games = UNIQUE(
+                FOR pw IN played_with
+                FILTER pw._from IN my_contests
+                RETURN pw._to
+            )
+            FOR r IN resulted_in
+            FILTER r._to != @player_id AND r._to NOT IN my_opponents AND r.guest_name == null
+            LET contest = DOCUMENT(r._from)
+            LET venue_id = FIRST(
+                FOR pa IN played_at
+                FILTER pa._from == r._from
+                RETURN pa._to
+            )
+            LET game_ids = (
+                FOR pw IN played_with
+                FILTER pw._from == r._from
+                RETURN pw._to
+            )
+            COLLECT candidate_id = r._to INTO rows = {
+                venue_id: venue_id,
+                game_ids: game_ids,
+                won: r.place == 1,
+                start: contest.start
+            }
+            LET shared_venues = LENGTH(INTERSECTION(UNIQUE(rows[*].venue_id), my_venues))
+            LET shared_games = LENGTH(INTERSECTION(UNIQUE(FLATTEN(rows[*].game_ids)), my_games))
+            FILTER shared_venues > 0 OR shared_games > 0
+            LET player = DOCUMENT(candidate_id)
+            FILTER player != null
+            LET total_contests = LENGTH(rows)
+            LET wins = LENGTH(rows[* FILTER CURRENT.won])
+            SORT shared_venues + shared_games DESC, total_contests DESC
+            LIMIT @limit
+            RETURN {
+                player_id: candidate_id,
+                player_handle: player.handle,
+                player_name: CONCAT_SEPARATOR(" ", player.firstname, player.lastname),
+                shared_venues: shared_venues,
+                shared_games: shared_games,
+                last_played: MAX(rows[*].start),
+                total_contests: total_contests,
+                overall_win_rate: total_contests > 0 ? (wins * 100.0) / total_contests : 0.0
+            }
+        "#;
+
+        let aql = AqlQuery::builder()
+            .query(query)
+            .bind_var("player_id", player_id)
+            .bind_var("limit", NETWORKING_SUGGESTION_LIMIT)
+            .build();
+
+        self.run_query(aql).await.map_err(|e| {
+            SharedError::Database(format!("Failed to query networking suggestions: {}", e))
+        })
+    }
+
+    /// Group players into communities by the venues they frequent.
+    ///
+    /// A player frequents a venue after `COMMUNITY_MIN_VENUE_VISITS` contests
+    /// there; one-off visits would otherwise chain unrelated groups together.
+    pub async fn get_communities(&self) -> Result<Vec<shared::dto::analytics::GamingCommunityDto>> {
+        let _timer = self.timer("get_communities", String::new());
+        let aql = AqlQuery::builder()
+            .query(
+                r#"
+            FOR pa IN played_at
+            FILTER DOCUMENT(pa._from).visibility != "private"
+            FOR r IN resulted_in
+            FILTER r._from == pa._from AND r.guest_name == null
+            COLLECT player_id = r._to, venue_id = pa._to WITH COUNT INTO contests
+            FILTER contests >= @min_visits
+            LET player = DOCUMENT(player_id)
+            LET venue = DOCUMENT(venue_id)
+            FILTER player != null AND venue != null
+            RETURN {
+                player_id: player_id,
+                player_handle: player.handle,
+                venue_id: venue_id,
+                venue_name: venue.displayName,
+                contests: contests
+            }
+        "#,
+            )
+            .bind_var("min_visits", COMMUNITY_MIN_VENUE_VISITS)
+            .build();
+
+        let visits: Vec<VenueVisit> = self
+            .run_query(aql)
+            .await
+            .map_err(|e| SharedError::Database(format!("Failed to query venue visits: {}", e)))?;
+
+        Ok(AnalyticsEngine::new().cluster_communities(&visits))
     }
 
     /// Get head-to-head record against specific opponent
+    ///
+    /// With `game_id` (a `game/` id or bare key) only contests where that
+    /// game was played count, and each row names that game.
     pub async fn get_head_to_head_record(
         &self,
         player_id: &str,
         opponent_id: &str,
+        game_id: Option<&str>,
     ) -> Result<shared::dto::analytics::HeadToHeadRecordDto> {
+        let _timer = self.timer(
+            "get_head_to_head_record",
+            format!(
+                "player_id={} opponent_id={} game_id={:?}",
+                player_id, opponent_id, game_id
+            ),
+        );
+        let game_id_full = game_id.map(|id| {
+            if id.contains('/') {
+                id.to_string()
+            } else {
+                format!("game/{}", id)
+            }
+        });
         // Query opponent document separately
         let opp_query = r#"RETURN DOCUMENT(@opponent_id)"#;
         let mut opp_bind = HashMap::new();
@@ -1815,8 +2823,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             .bind_vars(opp_bind)
             .build();
         let opp_rows: Vec<serde_json::Value> = self
-            .db
-            .aql_query(opp_aql)
+            .run_query(opp_aql)
             .await
             .map_err(|e| SharedError::Database(format!("Failed to load opponent: {}", e)))?;
         let (opponent_handle, opponent_name) = if let Some(opp) = opp_rows.first() {
@@ -1843,7 +2850,8 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
                 LET my = FIRST(FOR r IN resulted_in FILTER r._from == c._id AND r._to == @player_id RETURN r)
                 LET oth = FIRST(FOR r IN resulted_in FILTER r._from == c._id AND r._to == @opponent_id RETURN r)
                 FILTER my != null AND oth != null
-                LET game = FIRST(FOR e IN played_with FILTER e._from == c._id RETURN DOCUMENT(e._to))
+                LET game = FIRST(FOR e IN played_with FILTER e._from == c._id AND (@game_id == null OR e._to == @game_id) RETURN DOCUMENT(e._to))
+                FILTER @game_id == null OR game != null
                 LET venue_edge = FIRST(FOR e IN played_at FILTER e._from == c._id RETURN e)
                 LET venue = venue_edge != null ? DOCUMENT(venue_edge._to) : null
                 LET i_won = TO_NUMBER(my.place) < TO_NUMBER(oth.place)
@@ -1870,11 +2878,15 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             "opponent_id",
             serde_json::Value::String(opponent_id.to_string()),
         );
+        rows_bind.insert(
+            "game_id",
+            game_id_full.map_or(serde_json::Value::Null, serde_json::Value::String),
+        );
         let rows_aql = AqlQuery::builder()
             .query(rows_query)
             .bind_vars(rows_bind)
             .build();
-        let rows: Vec<serde_json::Value> = self.db.aql_query(rows_aql).await.map_err(|e| {
+        let rows: Vec<serde_json::Value> = self.run_query(rows_aql).await.map_err(|e| {
             SharedError::Database(format!("Failed to query head-to-head rows: {}", e))
         })?;
 
@@ -1967,13 +2979,104 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
         })
     }
 
+    /// Get every pairwise head-to-head record within a group of players
+    ///
+    /// Only contests with at least two of the players are read, so this stays
+    /// cheap for a table of regulars. Pairs that never met get an empty record.
+    pub async fn get_group_head_to_head(
+        &self,
+        player_ids: &[String],
+    ) -> Result<Vec<shared::dto::analytics::GroupHeadToHeadRecordDto>> {
+        let _timer = self.timer(
+            "get_group_head_to_head",
+            format!("players={}", player_ids.len()),
+        );
+        let query = r#"
+            FOR r IN resulted_in
+                FILTER r._to IN @player_ids
+                COLLECT contest_id = r._from INTO entries = {
+                    player_id: r._to,
+                    place: TO_NUMBER(r.place)
+                }
+                FILTER LENGTH(entries) > 1
+                RETURN entries
+        "#;
+        let aql = AqlQuery::builder()
+            .query(query)
+            .bind_var("player_ids", serde_json::json!(player_ids))
+            .build();
+        #[derive(Deserialize)]
+        struct Entry {
+            player_id: String,
+            place: f64,
+        }
+        let contests: Vec<Vec<Entry>> = self.run_query(aql).await.map_err(|e| {
+            SharedError::Database(format!("Failed to query group head-to-head: {}", e))
+        })?;
+
+        // (wins, losses, ties) for each ordered pair
+        let mut tallies: HashMap<(&str, &str), (i32, i32, i32)> = HashMap::new();
+        for entries in &contests {
+            for me in entries {
+                for other in entries.iter().filter(|e| e.player_id != me.player_id) {
+                    let tally = tallies
+                        .entry((me.player_id.as_str(), other.player_id.as_str()))
+                        .or_default();
+                    if me.place < other.place {
+                        tally.0 += 1;
+                    } else if me.place > other.place {
+                        tally.1 += 1;
+                    } else {
+                        tally.2 += 1;
+                    }
+                }
+            }
+        }
+
+        let mut records = Vec::new();
+        for player_id in player_ids {
+            for opponent_id in player_ids.iter().filter(|id| *id != player_id) {
+                let (wins, losses, ties) = tallies
+                    .get(&(player_id.as_str(), opponent_id.as_str()))
+                    .copied()
+                    .unwrap_or_default();
+                let total_contests = wins + losses + ties;
+                records.push(shared::dto::analytics::GroupHeadToHeadRecordDto {
+                    player_id: player_id.clone(),
+                    opponent_id: opponent_id.clone(),
+                    total_contests,
+                    wins,
+                    losses,
+                    ties,
+                    win_rate: if total_contests > 0 {
+                        (wins as f64 / total_contests as f64) * 100.0
+                    } else {
+                        0.0
+                    },
+                });
+            }
+        }
+        Ok(records)
+    }
+
     /// Get player's performance trends over the last 6 months
+    ///
+    /// Each month's `skill_rating` is the player's global rating as of the
+    /// end of that month; the game and venue filters narrow the contests
+    /// counted, not the rating.
     pub async fn get_my_performance_trends(
         &self,
         player_id: &str,
         game_id: Option<&str>,
         venue_id: Option<&str>,
     ) -> Result<Vec<shared::dto::analytics::PerformanceTrendDto>> {
+        let _timer = self.timer(
+            "get_my_performance_trends",
+            format!(
+                "player_id={} game_id={:?} venue_id={:?}",
+                player_id, game_id, venue_id
+            ),
+        );
         log::info!("get_my_performance_trends called for player: {}", player_id);
 
         let game_id_full = game_id.map(|id| {
@@ -2038,7 +3141,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
                 LET contests_played = LENGTH(month_data)
                 LET wins = LENGTH(
                     FOR item IN month_data
-                    FILTER item.result.result == "won"
+                    FILTER item.result.place == 1
                     RETURN item
                 )
                 LET win_rate = contests_played > 0 ? (wins / contests_played) * 100 : 0.0
@@ -2046,8 +3149,18 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
                     FOR item IN month_data
                     RETURN item.result.place
                 ) || 0.0) : 0.0
-                LET skill_rating = 1500.0 // Placeholder - would need to calculate actual rating
-                LET points_earned = 0
+                // Global rating at the end of the month: the month's own rating
+                // period, else the latest earlier one, else the starting rating
+                LET month_end_ts = DATE_TIMESTAMP(DATE_ADD(CONCAT(month_key, '-01T00:00:00Z'), 1, 'month'))
+                LET skill_rating = NOT_NULL(FIRST(
+                    FOR h IN rating_history
+                    FILTER h.player_id == @player_id OR h.player_id == CONCAT('player/', @player_key)
+                    FILTER h.scope_type == 'global' AND h.scope_id == null
+                    FILTER DATE_TIMESTAMP(h.period_end) <= month_end_ts
+                    SORT h.period_end DESC
+                    LIMIT 1
+                    RETURN h.rating
+                ), @default_rating)
                 
                 SORT month_key ASC
                 RETURN {
@@ -2056,8 +3169,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
                     wins: wins,
                     win_rate: win_rate,
                     average_placement: average_placement,
-                    skill_rating: skill_rating,
-                    points_earned: points_earned
+                    skill_rating: skill_rating
                 }
         "#;
 
@@ -2068,6 +3180,10 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
         );
         let player_key = player_id.split('/').last().unwrap_or(player_id).to_string();
         bind_vars.insert("player_key", serde_json::Value::String(player_key));
+        bind_vars.insert(
+            "default_rating",
+            serde_json::json!(Glicko2Params::default().default_rating),
+        );
         bind_vars.insert(
             "game_id_full",
             game_id_full
@@ -2103,7 +3219,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             .build();
 
         let results: Vec<shared::dto::analytics::PerformanceTrendDto> =
-            self.db.aql_query(aql).await.map_err(|e| {
+            self.run_query(aql).await.map_err(|e| {
                 SharedError::Database(format!("Failed to query performance trends: {}", e))
             })?;
 
@@ -2147,7 +3263,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             .bind_vars(bind_vars.clone())
             .build();
 
-        match self.db.aql_query::<serde_json::Value>(debug_aql).await {
+        match self.run_query::<serde_json::Value>(debug_aql).await {
             Ok(debug_results) => {
                 if let Some(debug_data) = debug_results.first() {
                     log::info!(
@@ -2174,73 +3290,114 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
         Ok(results)
     }
 
-    /// Get contests by venue for a player using graph traversal
+    /// Get one page of a player's contests at a venue, newest first
+    ///
+    /// `from` and `to` bound the contest start, inclusively.
     pub async fn get_contests_by_venue(
         &self,
         player_id: &str,
         venue_id: &str,
-    ) -> Result<Vec<serde_json::Value>> {
+        from: Option<DateTime<FixedOffset>>,
+        to: Option<DateTime<FixedOffset>>,
+        limit: i32,
+        offset: i32,
+    ) -> Result<shared::dto::analytics::VenueContestsPageDto> {
+        let _timer = self.timer(
+            "get_contests_by_venue",
+            format!(
+                "player_id={} venue_id={} from={:?} to={:?} limit={} offset={}",
+                player_id, venue_id, from, to, limit, offset
+            ),
+        );
         let query = r#"
-        FOR contest IN contest
-        LET my_outcome = FIRST(FOR r IN resulted_in FILTER r._from == contest._id AND r._to == @player_id RETURN r)
-        LET venue = FIRST(FOR e IN played_at FILTER e._from == contest._id RETURN DOCUMENT(e._to))
-        FILTER my_outcome != null AND venue != null AND venue._key == @venue_id
-        LET game = FIRST(FOR e IN played_with FILTER e._from == contest._id RETURN DOCUMENT(e._to))
-        LET all_outcomes = (
-            FOR outcome IN resulted_in
-            FILTER outcome._from == contest._id
-            LET player = DOCUMENT(outcome._to)
-            SORT TO_NUMBER(outcome.place)
+        LET window_start = @from != null ? DATE_TIMESTAMP(@from) : null
+        LET window_end = @to != null ? DATE_TIMESTAMP(@to) : null
+        LET matches = (
+            FOR contest IN contest
+            LET start_ts = DATE_TIMESTAMP(contest.start)
+            FILTER window_start == null OR start_ts >= window_start
+            FILTER window_end == null OR start_ts <= window_end
+            LET my_outcome = FIRST(FOR r IN resulted_in FILTER r._from == contest._id AND r._to == @player_id RETURN r)
+            LET venue = FIRST(FOR e IN played_at FILTER e._from == contest._id RETURN DOCUMENT(e._to))
+            FILTER my_outcome != null AND venue != null AND venue._key == @venue_id
+            SORT start_ts DESC, contest._key
+            RETURN { contest, my_outcome, venue }
+        )
+        LET items = (
+            FOR m IN matches
+            LIMIT @offset, @limit
+            LET contest = m.contest
+            LET venue = m.venue
+            LET game = FIRST(FOR e IN played_with FILTER e._from == contest._id RETURN DOCUMENT(e._to))
+            LET all_outcomes = (
+                FOR outcome IN resulted_in
+                FILTER outcome._from == contest._id
+                LET player = DOCUMENT(outcome._to)
+                SORT TO_NUMBER(outcome.place)
+                RETURN {
+                    player_id: player != null ? player._key : null,
+                    player_name: player != null ? CONCAT(player.firstname, ' ', player.lastname) : outcome.guest_name,
+                    player_handle: player != null ? player.handle : outcome.guest_name,
+                    placement: outcome.place,
+                    result: outcome.result
+                }
+            )
             RETURN {
-                player_id: player._key,
-                player_name: CONCAT(player.firstname, ' ', player.lastname),
-                player_handle: player.handle,
-                placement: outcome.place,
-                result: outcome.result
+                contest_id: contest._id,
+                contest_name: contest.name,
+                contest_date: contest.start,
+                contest_description: contest.description,
+                contest_status: contest.status,
+                game_id: game != null ? game._key : null,
+                game_name: game != null ? game.name : "Unknown Game",
+                game_year_published: game != null ? game.year_published : null,
+                venue_id: venue._key,
+                venue_name: venue.name,
+                venue_display_name: venue.displayName,
+                venue_address: venue.formattedAddress,
+                my_placement: m.my_outcome.place,
+                my_result: m.my_outcome.result,
+                total_players: LENGTH(all_outcomes),
+                players: all_outcomes
             }
         )
-        SORT contest.start DESC
-        RETURN {
-            contest_id: contest._id,
-            contest_name: contest.name,
-            contest_date: contest.start,
-            contest_description: contest.description,
-            contest_status: contest.status,
-            game_id: game != null ? game._key : null,
-            game_name: game != null ? game.name : "Unknown Game",
-            game_year_published: game != null ? game.year_published : null,
-            venue_id: venue._key,
-            venue_name: venue.name,
-            venue_display_name: venue.displayName,
-            venue_address: venue.formattedAddress,
-            my_placement: my_outcome.place,
-            my_result: my_outcome.result,
-            total_players: LENGTH(all_outcomes),
-            players: all_outcomes
-        }
+        RETURN { items, total: LENGTH(matches) }
         "#;
 
-        let mut bind_vars = HashMap::new();
-        bind_vars.insert(
-            "player_id",
-            serde_json::Value::String(player_id.to_string()),
-        );
-        bind_vars.insert("venue_id", serde_json::Value::String(venue_id.to_string()));
-
         let aql = AqlQuery::builder()
             .query(query)
-            .bind_vars(bind_vars)
+            .bind_var("player_id", player_id)
+            .bind_var("venue_id", venue_id)
+            .bind_var("from", from.map(|d| d.to_rfc3339()))
+            .bind_var("to", to.map(|d| d.to_rfc3339()))
+            .bind_var("limit", limit)
+            .bind_var("offset", offset)
             .build();
 
-        let results: Vec<serde_json::Value> = self.db.aql_query(aql).await.map_err(|e| {
+        #[derive(Deserialize)]
+        struct PageResult {
+            items: Vec<serde_json::Value>,
+            total: i64,
+        }
+
+        let page: Vec<PageResult> = self.run_query(aql).await.map_err(|e| {
             SharedError::Database(format!("Failed to query contests by venue: {}", e))
         })?;
-        Ok(results)
+        let page = page.into_iter().next().unwrap_or(PageResult {
+            items: Vec::new(),
+            total: 0,
+        });
+        Ok(shared::dto::analytics::VenueContestsPageDto {
+            items: page.items,
+            total: page.total,
+            limit,
+            offset,
+        })
     }
 
     /// Saves game statistics to database
     pub async fn save_game_stats(&self, stats: &GameStats) -> Result<()> {
-        let collection = self.db.collection("game_stats").await.map_err(|e| {
+        let collection = self.db.collection(GAME_STATS).await.map_err(|e| {
             SharedError::Database(format!("Failed to get game_stats collection: {}", e))
         })?;
 
@@ -2258,28 +3415,24 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
 
     /// Retrieves game statistics from database
     pub async fn get_game_stats(&self, game_id: &str) -> Result<Option<GameStats>> {
-        let query = format!(
-            "FOR doc IN game_stats FILTER doc.game_id == '{}' RETURN doc",
-            game_id
-        );
+        let _timer = self.timer("get_game_stats", format!("game_id={}", game_id));
+        let query = AqlQuery::builder()
+            .query("FOR doc IN @@collection FILTER doc.game_id == @game_id RETURN doc")
+            .bind_var("@collection", GAME_STATS)
+            .bind_var("game_id", game_id)
+            .build();
 
-        let cursor = self
-            .db
-            .aql_str(&query)
+        let results: Vec<GameStats> = self
+            .run_query(query)
             .await
             .map_err(|e| SharedError::Database(format!("Failed to query game stats: {}", e)))?;
 
-        let results: Vec<GameStats> = cursor
-            .into_iter()
-            .map(|doc: arangors::Document<GameStats>| doc.document)
-            .collect();
-
         Ok(results.into_iter().next())
     }
 
     /// Saves venue statistics to database
     pub async fn save_venue_stats(&self, stats: &VenueStats) -> Result<()> {
-        let collection = self.db.collection("venue_stats").await.map_err(|e| {
+        let collection = self.db.collection(VENUE_STATS).await.map_err(|e| {
             SharedError::Database(format!("Failed to get venue_stats collection: {}", e))
         })?;
 
@@ -2297,70 +3450,96 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
 
     /// Retrieves venue statistics from database
     pub async fn get_venue_stats(&self, venue_id: &str) -> Result<Option<VenueStats>> {
-        let query = format!(
-            "FOR doc IN venue_stats FILTER doc.venue_id == '{}' RETURN doc",
-            venue_id
-        );
-
-        let cursor =
-            self.db.aql_str(&query).await.map_err(|e| {
-                SharedError::Database(format!("Failed to query venue stats: {}", e))
-            })?;
+        let _timer = self.timer("get_venue_stats", format!("venue_id={}", venue_id));
+        let query = AqlQuery::builder()
+            .query("FOR doc IN @@collection FILTER doc.venue_id == @venue_id RETURN doc")
+            .bind_var("@collection", VENUE_STATS)
+            .bind_var("venue_id", venue_id)
+            .build();
 
-        let results: Vec<VenueStats> = cursor
-            .into_iter()
-            .map(|doc: arangors::Document<VenueStats>| doc.document)
-            .collect();
+        let results: Vec<VenueStats> = self
+            .run_query(query)
+            .await
+            .map_err(|e| SharedError::Database(format!("Failed to query venue stats: {}", e)))?;
 
         Ok(results.into_iter().next())
     }
 
-    /// Retrieves all player statistics for leaderboard
-    pub async fn get_all_player_stats(&self) -> Result<Vec<PlayerStats>> {
-        let query = "FOR doc IN player_stats SORT doc.skill_rating DESC RETURN doc";
+    /// Retrieves one page of player statistics, highest `sort_by` first
+    ///
+    /// Returns the page and the number of players with stats overall. Ties
+    /// are broken by player id so pages never overlap.
+    pub async fn get_all_player_stats(
+        &self,
+        sort_by: shared::dto::analytics::PlayerStatsSort,
+        limit: i32,
+        offset: i32,
+    ) -> Result<(Vec<PlayerStats>, i64)> {
+        let _timer = self.timer(
+            "get_all_player_stats",
+            format!("sort_by={:?} limit={} offset={}", sort_by, limit, offset),
+        );
+        let query = r#"
+        LET items = (
+            FOR doc IN player_stats
+            SORT doc.@sort_field DESC, doc.player_id
+            LIMIT @offset, @limit
+            RETURN doc
+        )
+        RETURN { items, total: LENGTH(player_stats) }
+        "#;
+        let aql = AqlQuery::builder()
+            .query(query)
+            .bind_var("sort_field", sort_by.field())
+            .bind_var("limit", limit)
+            .bind_var("offset", offset)
+            .build();
+
+        #[derive(Deserialize)]
+        struct PageResult {
+            items: Vec<PlayerStats>,
+            total: i64,
+        }
 
-        let cursor = self.db.aql_str(query).await.map_err(|e| {
+        let page: Vec<PageResult> = self.run_query(aql).await.map_err(|e| {
             SharedError::Database(format!("Failed to query all player stats: {}", e))
         })?;
-
-        let results: Vec<PlayerStats> = cursor
+        Ok(page
             .into_iter()
-            .map(|doc: arangors::Document<PlayerStats>| doc.document)
-            .collect();
-
-        Ok(results)
+            .next()
+            .map(|page| (page.items, page.total))
+            .unwrap_or_default())
     }
 
     /// Retrieves player contest results for statistics calculation
     pub async fn get_player_contest_results(&self, player_id: &str) -> Result<Vec<ContestResult>> {
-        let query = format!(
-            r#"
+        let _timer = self.timer(
+            "get_player_contest_results",
+            format!("player_id={}", player_id),
+        );
+        let query = AqlQuery::builder()
+            .query(
+                r#"
             FOR result IN resulted_in
-            FILTER result._to == '{}'
+            FILTER result._to == @player_id
             LET contest = DOCUMENT(result._from)
             LET player = DOCUMENT(result._to)
-            RETURN {{
+            RETURN {
                 contest_id: contest._id,
                 placement: result.place,
                 score: 0,
                 average_opponent_rating: 1200,
                 contest_difficulty: 1.0,
                 contest_date: IS_NUMBER(contest.start) ? DATE_ISO8601(contest.start) : contest.start
-            }}
+            }
             "#,
-            player_id
-        );
+            )
+            .bind_var("player_id", player_id)
+            .build();
 
-        let cursor = self.db.aql_str(&query).await.map_err(|e| {
+        self.run_query(query).await.map_err(|e| {
             SharedError::Database(format!("Failed to query player contest results: {}", e))
-        })?;
-
-        let results: Vec<ContestResult> = cursor
-            .into_iter()
-            .map(|doc: arangors::Document<ContestResult>| doc.document)
-            .collect();
-
-        Ok(results)
+        })
     }
 
     /// Retrieves contest participants for statistics calculation
@@ -2368,47 +3547,49 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
         &self,
         contest_id: &str,
     ) -> Result<Vec<ContestParticipant>> {
-        let query = format!(
-            r#"
+        let _timer = self.timer(
+            "get_contest_participants",
+            format!("contest_id={}", contest_id),
+        );
+        let query = AqlQuery::builder()
+            .query(
+                r#"
             FOR result IN resulted_in
-            FILTER result._from == '{}'
+            FILTER result._from == @contest_id
             LET player = DOCUMENT(result._to)
             LET player_stats = FIRST(
                 FOR stats IN player_stats
                 FILTER stats.player_id == player._id
                 RETURN stats
             )
-            RETURN {{
-                player_id: player._id,
+            SORT result.place ASC
+            RETURN {
+                player_id: result._to,
                 placement: result.place,
                 score: 0,
                 skill_rating: player_stats.skill_rating || 1200,
-                completed: true
-            }}
-            ORDER BY result.place ASC
+                completed: TO_NUMBER(result.place) > 0
+            }
             "#,
-            contest_id
-        );
+            )
+            .bind_var("contest_id", contest_id)
+            .build();
 
-        let cursor = self.db.aql_str(&query).await.map_err(|e| {
+        self.run_query(query).await.map_err(|e| {
             SharedError::Database(format!("Failed to query contest participants: {}", e))
-        })?;
-
-        let results: Vec<ContestParticipant> = cursor
-            .into_iter()
-            .map(|doc: arangors::Document<ContestParticipant>| doc.document)
-            .collect();
-
-        Ok(results)
+        })
     }
 
     /// Retrieves game plays for statistics calculation
     pub async fn get_game_plays(&self, game_id: &str) -> Result<Vec<GamePlay>> {
-        let query = format!(
-            r#"
+        let _timer = self.timer("get_game_plays", format!("game_id={}", game_id));
+        let query = AqlQuery::builder()
+            .query(
+                r#"
             FOR played_with IN played_with
-            FILTER played_with._to == '{}'
+            FILTER played_with._to == @game_id
             LET contest = DOCUMENT(played_with._from)
+            FILTER contest.visibility != "private"
             LET results = (
                 FOR result IN resulted_in
                 FILTER result._from == contest._id
@@ -2416,38 +3597,43 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             )
             FOR result IN results
             LET player = DOCUMENT(result._to)
-            RETURN {{
+            RETURN {
                 player_id: player._id,
                 player_count: LENGTH(results),
                 won: result.place == 1,
                 duration_minutes: DATE_DIFF(contest.start, contest.stop, 'minute'),
                 played_at: IS_NUMBER(contest.start) ? DATE_ISO8601(contest.start) : contest.start
-            }}
+            }
             "#,
-            game_id
-        );
+            )
+            .bind_var("game_id", game_id)
+            .build();
 
-        let cursor = self
-            .db
-            .aql_str(&query)
+        self.run_query(query)
             .await
-            .map_err(|e| SharedError::Database(format!("Failed to query game plays: {}", e)))?;
-
-        let results: Vec<GamePlay> = cursor
-            .into_iter()
-            .map(|doc: arangors::Document<GamePlay>| doc.document)
-            .collect();
-
-        Ok(results)
+            .map_err(|e| SharedError::Database(format!("Failed to query game plays: {}", e)))
     }
 
-    /// Retrieves venue contests for statistics calculation
+    /// Retrieves venue contests for statistics calculation.
+    ///
+    /// Resolves a merged venue to its survivor and includes contests still
+    /// recorded against venues merged into it, each contest once.
     pub async fn get_venue_contests(&self, venue_id: &str) -> Result<Vec<VenueContest>> {
-        let query = format!(
-            r#"
+        let _timer = self.timer("get_venue_contests", format!("venue_id={}", venue_id));
+        let query = arangors::AqlQuery::builder()
+            .query(
+                r#"
+            LET survivor = DOCUMENT(@venue_id).merged_into || @venue_id
+            LET venue_ids = APPEND([survivor], (
+                FOR merged IN venue
+                FILTER merged.merged_into == survivor
+                RETURN merged._id
+            ))
             FOR played_at IN played_at
-            FILTER played_at._to == '{}'
-            LET contest = DOCUMENT(played_at._from)
+            FILTER played_at._to IN venue_ids
+            COLLECT contest_id = played_at._from
+            LET contest = DOCUMENT(contest_id)
+            FILTER contest.visibility != "private"
             LET participants = (
                 FOR result IN resulted_in
                 FILTER result._from == contest._id
@@ -2458,48 +3644,39 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
                 FILTER played_with._from == contest._id
                 RETURN played_with._to
             )
-            RETURN {{
+            SORT contest.start DESC
+            RETURN {
                 contest_id: contest._id,
                 participant_ids: participants,
                 participant_count: LENGTH(participants),
                 game_ids: games,
                 duration_minutes: DATE_DIFF(contest.start, contest.stop, 'minute'),
                 contest_date: IS_NUMBER(contest.start) ? DATE_ISO8601(contest.start) : contest.start
-            }}
-            ORDER BY contest.start DESC
+            }
             "#,
-            venue_id
-        );
-
-        let cursor =
-            self.db.aql_str(&query).await.map_err(|e| {
-                SharedError::Database(format!("Failed to query venue contests: {}", e))
-            })?;
-
-        let results: Vec<VenueContest> = cursor
-            .into_iter()
-            .map(|doc: arangors::Document<VenueContest>| doc.document)
-            .collect();
+            )
+            .bind_var("venue_id", venue_id)
+            .build();
 
-        Ok(results)
+        self.run_query(query)
+            .await
+            .map_err(|e| SharedError::Database(format!("Failed to query venue contests: {}", e)))
     }
 
     /// Retrieves player information for DTOs
     pub async fn get_player_info(&self, player_id: &str) -> Result<Option<(String, String)>> {
-        let query = format!(
-            "FOR player IN player FILTER player._id == '{}' RETURN {{ handle: player.handle, firstname: player.firstname }}",
-            player_id
-        );
-
-        let cursor =
-            self.db.aql_str(&query).await.map_err(|e| {
-                SharedError::Database(format!("Failed to query player info: {}", e))
-            })?;
+        let _timer = self.timer("get_player_info", format!("player_id={}", player_id));
+        let query = AqlQuery::builder()
+            .query(
+                "FOR player IN player FILTER player._id == @player_id RETURN { handle: player.handle, firstname: player.firstname }",
+            )
+            .bind_var("player_id", player_id)
+            .build();
 
-        let results: Vec<serde_json::Value> = cursor
-            .into_iter()
-            .map(|doc: arangors::Document<serde_json::Value>| doc.document)
-            .collect();
+        let results: Vec<serde_json::Value> = self
+            .run_query(query)
+            .await
+            .map_err(|e| SharedError::Database(format!("Failed to query player info: {}", e)))?;
 
         if let Some(result) = results.first() {
             let handle = result["handle"].as_str().unwrap_or("").to_string();
@@ -2512,77 +3689,142 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
 
     /// Retrieves game information for DTOs
     pub async fn get_game_info(&self, game_id: &str) -> Result<Option<String>> {
-        let query = format!(
-            "FOR game IN game FILTER game._id == '{}' RETURN game.name",
-            game_id
-        );
+        let _timer = self.timer("get_game_info", format!("game_id={}", game_id));
+        let query = AqlQuery::builder()
+            .query("FOR game IN game FILTER game._id == @game_id RETURN game.name")
+            .bind_var("game_id", game_id)
+            .build();
 
-        let cursor = self
-            .db
-            .aql_str(&query)
+        let results: Vec<String> = self
+            .run_query(query)
             .await
             .map_err(|e| SharedError::Database(format!("Failed to query game info: {}", e)))?;
 
-        let results: Vec<String> = cursor
-            .into_iter()
-            .map(|doc: arangors::Document<String>| doc.document)
-            .collect();
-
         Ok(results.into_iter().next())
     }
 
     /// Retrieves venue information for DTOs
     pub async fn get_venue_info(&self, venue_id: &str) -> Result<Option<String>> {
-        let query = format!(
-            "FOR venue IN venue FILTER venue._id == '{}' RETURN venue.display_name",
-            venue_id
-        );
+        let _timer = self.timer("get_venue_info", format!("venue_id={}", venue_id));
+        let query = AqlQuery::builder()
+            .query("FOR venue IN venue FILTER venue._id == @venue_id RETURN venue.displayName")
+            .bind_var("venue_id", venue_id)
+            .build();
 
-        let cursor = self
-            .db
-            .aql_str(&query)
+        let results: Vec<String> = self
+            .run_query(query)
             .await
             .map_err(|e| SharedError::Database(format!("Failed to query venue info: {}", e)))?;
 
-        let results: Vec<String> = cursor
+        Ok(results.into_iter().next())
+    }
+
+    /// Computes a game's stats from its recorded plays rather than the stored doc.
+    ///
+    /// Each play is one player's result in a contest that used the game.
+    pub async fn compute_game_stats(
+        &self,
+        game_id: &str,
+    ) -> Result<shared::dto::analytics::GameStatsDto> {
+        let _timer = self.timer("compute_game_stats", format!("game_id={}", game_id));
+        let game_name = self
+            .get_game_info(game_id)
+            .await?
+            .ok_or_else(|| SharedError::NotFound(format!("Game {} not found", game_id)))?;
+        let plays = self.get_game_plays(game_id).await?;
+        let engine = AnalyticsEngine::new();
+        let mut stats = engine.calculate_game_stats(game_id, plays);
+
+        let player_ids: Vec<&str> = stats
+            .win_rate_distribution
+            .iter()
+            .map(|p| p.player_id.as_str())
+            .collect();
+        let aql = AqlQuery::builder()
+            .query("FOR p IN player FILTER p._id IN @player_ids RETURN [p._id, p.handle]")
+            .bind_var("player_ids", serde_json::json!(player_ids))
+            .build();
+        let handles: HashMap<String, String> = self
+            .run_query::<(String, Option<String>)>(aql)
+            .await
+            .map_err(|e| SharedError::Database(format!("Failed to query player handles: {}", e)))?
             .into_iter()
-            .map(|doc: arangors::Document<String>| doc.document)
+            .map(|(id, handle)| (id, handle.unwrap_or_default()))
             .collect();
+        for player in &mut stats.win_rate_distribution {
+            if let Some(handle) = handles.get(&player.player_id) {
+                player.player_handle = handle.clone();
+            }
+        }
 
-        Ok(results.into_iter().next())
+        let champion = engine.game_champion(&stats.win_rate_distribution);
+        let mut dto = shared::dto::analytics::GameStatsDto::from(&stats);
+        dto.game_name = game_name;
+        dto.champion = champion.map(|p| shared::dto::analytics::PlayerWinRateDto {
+            player_id: p.player_id,
+            player_handle: p.player_handle,
+            wins: p.wins,
+            total_plays: p.total_plays,
+            win_rate: p.win_rate,
+        });
+        Ok(dto)
+    }
+
+    /// Summarises activity at a venue, including contests at venues merged into it.
+    ///
+    /// `monthly_contests` carries the trend, oldest month first.
+    pub async fn compute_venue_stats(
+        &self,
+        venue_id: &str,
+    ) -> Result<shared::dto::analytics::VenueStatsDto> {
+        let _timer = self.timer("compute_venue_stats", format!("venue_id={}", venue_id));
+        let contests = self.get_venue_contests(venue_id).await?;
+        let engine = AnalyticsEngine::new();
+        let busiest_day = engine.busiest_weekday(&contests);
+        let stats = engine.calculate_venue_stats(venue_id, contests);
+
+        let mut dto = shared::dto::analytics::VenueStatsDto::from(&stats);
+        dto.venue_name = self.get_venue_info(venue_id).await?.unwrap_or_default();
+        for game in &mut dto.popular_games {
+            game.game_name = self.get_game_info(&game.game_id).await?.unwrap_or_default();
+        }
+        dto.most_played_game = dto.popular_games.first().cloned();
+        dto.busiest_day = busiest_day.map(|day| weekday_name(day).to_string());
+        Ok(dto)
     }
 
     /// Retrieves contest information for DTOs
     pub async fn get_contest_info(&self, contest_id: &str) -> Result<Option<String>> {
-        let query = format!(
-            "FOR contest IN contest FILTER contest._id == '{}' RETURN contest.name",
-            contest_id
-        );
-
-        let cursor =
-            self.db.aql_str(&query).await.map_err(|e| {
-                SharedError::Database(format!("Failed to query contest info: {}", e))
-            })?;
+        let _timer = self.timer("get_contest_info", format!("contest_id={}", contest_id));
+        let query = AqlQuery::builder()
+            .query("FOR contest IN contest FILTER contest._id == @contest_id RETURN contest.name")
+            .bind_var("contest_id", contest_id)
+            .build();
 
-        let results: Vec<String> = cursor
-            .into_iter()
-            .map(|doc: arangors::Document<String>| doc.document)
-            .collect();
+        let results: Vec<String> = self
+            .run_query(query)
+            .await
+            .map_err(|e| SharedError::Database(format!("Failed to query contest info: {}", e)))?;
 
         Ok(results.into_iter().next())
     }
 
-    /// Creates analytics collections if they don't exist
-    pub async fn create_collections(&self) -> Result<()> {
-        let collections = vec![
-            "player_stats",
-            "contest_stats",
-            "game_stats",
-            "venue_stats",
-            "platform_stats",
-        ];
+    /// Analytics collections that do not exist yet
+    pub async fn missing_collections(&self) -> Vec<&'static str> {
+        let mut missing = Vec::new();
+        for &collection_name in ANALYTICS_COLLECTIONS {
+            if self.db.collection(collection_name).await.is_err() {
+                missing.push(collection_name);
+            }
+        }
+        missing
+    }
 
-        for collection_name in collections {
+    /// Creates analytics collections and their indexes if they don't exist.
+    ///
+    /// Safe to call repeatedly.
+    pub async fn create_collections(&self) -> Result<()> {
+        for &collection_name in ANALYTICS_COLLECTIONS {
             if let Err(_) = self.db.collection(collection_name).await {
                 self.db
                     .create_collection(collection_name)
@@ -2596,20 +3838,40 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             }
         }
 
+        // ArangoDB returns the existing index when an identical one is requested
+        for (collection, fields) in ANALYTICS_INDEXES {
+            let index = Index::builder()
+                .name(format!("idx_{}", fields.join("_")))
+                .fields(fields.iter().map(|f| f.to_string()).collect())
+                .settings(IndexSettings::Persistent {
+                    unique: false,
+                    sparse: false,
+                    deduplicate: false,
+                })
+                .build();
+            self.db
+                .create_index(collection, &index)
+                .await
+                .map_err(|e| {
+                    SharedError::Database(format!(
+                        "Failed to ensure index on {}({}): {}",
+                        collection,
+                        fields.join(", "),
+                        e
+                    ))
+                })?;
+        }
+
         Ok(())
     }
 
     /// Debug method to run custom queries
     pub async fn debug_database(&self, query: &str) -> Result<serde_json::Value> {
-        let cursor =
-            self.db.aql_str(query).await.map_err(|e| {
-                SharedError::Database(format!("Failed to execute debug query: {}", e))
-            })?;
-
-        let results: Vec<serde_json::Value> = cursor
-            .into_iter()
-            .map(|doc: arangors::Document<serde_json::Value>| doc.document)
-            .collect();
+        let aql = AqlQuery::builder().query(query).build();
+        let results: Vec<serde_json::Value> = self
+            .run_query(aql)
+            .await
+            .map_err(|e| SharedError::Database(format!("Failed to execute debug query: {}", e)))?;
 
         if let Some(result) = results.first() {
             Ok(result.clone())
@@ -2620,6 +3882,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
 
     /// Get enhanced platform insights with more meaningful metrics
     pub async fn get_platform_insights(&self) -> Result<serde_json::Value> {
+        let _timer = self.timer("get_platform_insights", String::new());
         // Get basic stats
         let total_players = self.get_total_players().await?;
         let total_contests = self.get_total_contests().await?;
@@ -2671,8 +3934,8 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
         };
 
         // Get top performers
-        let top_games = self.get_top_games(5).await?;
-        let top_venues = self.get_top_venues(5).await?;
+        let top_games = self.get_top_games(PLATFORM_TOP_N_DEFAULT).await?;
+        let top_venues = self.get_top_venues(PLATFORM_TOP_N_DEFAULT).await?;
 
         let insights = serde_json::json!({
             "summary": {
@@ -2712,6 +3975,10 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
 
     /// Get player achievements
     pub async fn get_player_achievements(&self, player_id: &str) -> Result<PlayerAchievements> {
+        let _timer = self.timer(
+            "get_player_achievements",
+            format!("player_id={}", player_id),
+        );
         let query = arangors::AqlQuery::builder()
             .query(
                 r#"
@@ -2761,13 +4028,15 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             .bind_var("player_id", player_id)
             .build();
 
-        match self.db.aql_query::<PlayerDataResult>(query).await {
+        match self.run_query::<PlayerDataResult>(query).await {
             Ok(mut cursor) => {
                 if let Some(player_data) = cursor.pop() {
                     let achievements = self.calculate_achievements(&player_data).await?;
                     let unlocked_count = achievements.iter().filter(|a| a.unlocked).count() as i32;
                     let total_achievements = achievements.len() as i32;
 
+                    let next_achievements = NextAchievement::closest(&achievements);
+
                     Ok(PlayerAchievements {
                         player_id: player_data.player_id,
                         achievements,
@@ -2778,6 +4047,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
                         } else {
                             (unlocked_count as f64 / total_achievements as f64) * 100.0
                         },
+                        next_achievements,
                     })
                 } else {
                     Err(SharedError::NotFound("Player not found".to_string()))
@@ -2959,6 +4229,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
 
     /// Get player ranking across all categories
     pub async fn get_player_rankings(&self, player_id: &str) -> Result<Vec<PlayerRanking>> {
+        let _timer = self.timer("get_player_rankings", format!("player_id={}", player_id));
         let mut rankings = Vec::new();
 
         // Get win rate ranking
@@ -3010,7 +4281,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             win_rate: f64,
         }
 
-        match self.db.aql_query::<WinRateResult>(query).await {
+        match self.run_query::<WinRateResult>(query).await {
             Ok(cursor) => {
                 let results: Vec<WinRateResult> = cursor.into_iter().collect();
                 if let Some(rank) = results.iter().position(|r| r.player_id == player_id) {
@@ -3059,7 +4330,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             wins: i32,
         }
 
-        match self.db.aql_query::<TotalWinsResult>(query).await {
+        match self.run_query::<TotalWinsResult>(query).await {
             Ok(cursor) => {
                 let results: Vec<TotalWinsResult> = cursor.into_iter().collect();
                 if let Some(rank) = results.iter().position(|r| r.player_id == player_id) {
@@ -3108,7 +4379,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             total_contests: i32,
         }
 
-        match self.db.aql_query::<TotalContestsResult>(query).await {
+        match self.run_query::<TotalContestsResult>(query).await {
             Ok(cursor) => {
                 let results: Vec<TotalContestsResult> = cursor.into_iter().collect();
                 if let Some(rank) = results.iter().position(|r| r.player_id == player_id) {
@@ -3135,7 +4406,18 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
     }
 
     /// Get player performance distribution by win rate ranges
-    pub async fn get_player_performance_distribution(&self) -> Result<Vec<(String, i32)>> {
+    ///
+    /// With `sample_pct`, only that percentage of players (chosen at random)
+    /// is evaluated and each range's count is scaled back up, so the counts
+    /// are approximate. `None` computes the exact distribution.
+    pub async fn get_player_performance_distribution(
+        &self,
+        sample_pct: Option<f64>,
+    ) -> Result<Vec<(String, i32)>> {
+        let _timer = self.timer(
+            "get_player_performance_distribution",
+            format!("sample_pct={:?}", sample_pct),
+        );
         let query = arangors::AqlQuery::builder()
             .query(
                 r#"
@@ -3144,9 +4426,10 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
                 LET contest = DOCUMENT(r._from)
                 FILTER player != null AND contest != null
                 COLLECT player_id = r._to WITH COUNT INTO total_contests
+                FILTER @sample_pct == null OR RAND() * 100 < @sample_pct
                 LET wins = LENGTH(
                     FOR r2 IN resulted_in
-                    FILTER r2._to == player_id AND r2.placement == 1
+                    FILTER r2._to == player_id AND r2.place == 1
                     RETURN r2
                 )
                 LET win_rate = total_contests > 0 ? (wins / total_contests) * 100 : 0
@@ -3159,12 +4442,16 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
                 END
                 COLLECT performance_range = range WITH COUNT INTO player_count
                 SORT performance_range ASC
-                RETURN { range: performance_range, count: player_count }
+                RETURN {
+                    range: performance_range,
+                    count: @sample_pct == null ? player_count : ROUND(player_count * 100 / @sample_pct)
+                }
             "#,
             )
+            .bind_var("sample_pct", sample_pct)
             .build();
 
-        let result = self.db.aql_query(query).await.map_err(|e| {
+        let result = self.run_query(query).await.map_err(|e| {
             SharedError::Database(format!(
                 "Failed to query player performance distribution: {}",
                 e
@@ -3183,22 +4470,27 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             .filter_map(|doc| {
                 let obj = doc.document;
                 let range = obj.get("range")?.as_str()?.to_string();
-                let count = obj.get("count")?.as_i64()? as i32;
+                let count = obj.get("count")?.as_f64()?.round() as i32;
                 Some((range, count))
             })
             .collect())
     }
 
     /// Get game difficulty vs popularity data
+    ///
+    /// Popularity is the number of contests that played the game.
     pub async fn get_game_difficulty_popularity(&self) -> Result<Vec<(String, f64, i32, f64)>> {
+        let _timer = self.timer("get_game_difficulty_popularity", String::new());
         let query = arangors::AqlQuery::builder()
             .query(
                 r#"
                 FOR g IN game
                 LET contests = (
-                    FOR c IN contest
-                    FILTER c.game_id == g._id
-                    RETURN c
+                    FOR pw IN played_with
+                    FILTER pw._to == g._id
+                    LET c = DOCUMENT(pw._from)
+                    FILTER c != null
+                    RETURN DISTINCT c
                 )
                 LET contest_count = LENGTH(contests)
                 LET total_participants = SUM(
@@ -3227,7 +4519,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
                         FILTER p.placement > 0
                         RETURN p
                     )
-                    LET win_rate = completed > 0 ? (completed / LENGTH(participants)) * 100 : 0
+                    LET win_rate = LENGTH(participants) > 0 ? (completed / LENGTH(participants)) * 100 : 0
                     RETURN win_rate
                 ) : 50.0
                 FILTER contest_count > 0
@@ -3243,7 +4535,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             )
             .build();
 
-        let result = self.db.aql_query(query).await.map_err(|e| {
+        let result = self.run_query(query).await.map_err(|e| {
             SharedError::Database(format!("Failed to query game difficulty popularity: {}", e))
         })?;
         let games: Vec<arangors::Document<serde_json::Value>> = result.try_into().map_err(|e| {
@@ -3265,6 +4557,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
 
     /// Get venue performance by time slot
     pub async fn get_venue_performance_timeslots(&self) -> Result<Vec<(String, String, f64)>> {
+        let _timer = self.timer("get_venue_performance_timeslots", String::new());
         let query = arangors::AqlQuery::builder()
             .query(r#"
                 FOR v IN venue
@@ -3306,7 +4599,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             "#)
             .build();
 
-        let result = self.db.aql_query(query).await.map_err(|e| {
+        let result = self.run_query(query).await.map_err(|e| {
             SharedError::Database(format!(
                 "Failed to query venue performance timeslots: {}",
                 e
@@ -3347,41 +4640,48 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
     }
 
     /// Get player retention cohort data
-    pub async fn get_player_retention_cohort(&self) -> Result<Vec<(String, i32, f64)>> {
+    ///
+    /// For each contest number n, counts the players who played at least n
+    /// contests. With `sample_pct`, only that percentage of players (chosen at
+    /// random) is counted and the counts are scaled back up, so the figures
+    /// are approximate. `None` computes the exact cohort.
+    pub async fn get_player_retention_cohort(
+        &self,
+        sample_pct: Option<f64>,
+    ) -> Result<Vec<(String, i32, f64)>> {
+        let _timer = self.timer(
+            "get_player_retention_cohort",
+            format!("sample_pct={:?}", sample_pct),
+        );
         let query = arangors::AqlQuery::builder()
             .query(
                 r#"
-                FOR r IN resulted_in
-                LET player = DOCUMENT(r._to)
-                LET contest = DOCUMENT(r._from)
-                FILTER player != null AND contest != null
-                LET first_contest = FIRST(
-                    FOR r2 IN resulted_in
-                    FILTER r2._to == r._to
-                    SORT r2.contest_date ASC
-                    RETURN r2.contest_date
-                )
-                LET contest_number = LENGTH(
-                    FOR r2 IN resulted_in
-                    FILTER r2._to == r._to AND r2.contest_date <= r.contest_date
-                    RETURN r2
+                LET players = (
+                    FOR r IN resulted_in
+                    LET player = DOCUMENT(r._to)
+                    LET contest = DOCUMENT(r._from)
+                    FILTER player != null AND contest != null
+                    COLLECT player_id = r._to WITH COUNT INTO contests
+                    RETURN contests
                 )
-                COLLECT contest_num = contest_number WITH COUNT INTO player_count
+                LET cohort_size = LENGTH(players)
+                FOR contests IN players
+                FILTER @sample_pct == null OR RAND() * 100 < @sample_pct
+                FOR contest_number IN 1..contests
+                COLLECT contest_num = contest_number WITH COUNT INTO sampled_count
+                LET player_count = @sample_pct == null ? sampled_count : ROUND(sampled_count * 100 / @sample_pct)
                 SORT contest_num ASC
                 RETURN {
                     contest_number: contest_num,
                     player_count: player_count,
-                    retention_rate: (player_count / LENGTH(
-                        FOR r3 IN resulted_in
-                        FILTER r3._to == r._to
-                        RETURN r3
-                    )) * 100
+                    retention_rate: cohort_size > 0 ? MIN([(player_count / cohort_size) * 100, 100]) : 0
                 }
             "#,
             )
+            .bind_var("sample_pct", sample_pct)
             .build();
 
-        let result = self.db.aql_query(query).await.map_err(|e| {
+        let result = self.run_query(query).await.map_err(|e| {
             SharedError::Database(format!("Failed to query player retention cohort: {}", e))
         })?;
         let cohorts: Vec<arangors::Document<serde_json::Value>> =
@@ -3393,8 +4693,8 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             .into_iter()
             .filter_map(|doc| {
                 let obj = doc.document;
-                let contest_num = obj.get("contest_number")?.as_str()?.to_string();
-                let player_count = obj.get("player_count")?.as_i64()? as i32;
+                let contest_num = obj.get("contest_number")?.as_i64()?.to_string();
+                let player_count = obj.get("player_count")?.as_f64()?.round() as i32;
                 let retention_rate = obj.get("retention_rate")?.as_f64()?;
                 Some((contest_num, player_count, retention_rate))
             })
@@ -3403,6 +4703,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
 
     /// Get contest completion rate by game
     pub async fn get_contest_completion_by_game(&self) -> Result<Vec<(String, i32, f64)>> {
+        let _timer = self.timer("get_contest_completion_by_game", String::new());
         let query = arangors::AqlQuery::builder()
             .query(r#"
                 FOR g IN game
@@ -3438,7 +4739,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             "#)
             .build();
 
-        let result = self.db.aql_query(query).await.map_err(|e| {
+        let result = self.run_query(query).await.map_err(|e| {
             SharedError::Database(format!("Failed to query contest completion by game: {}", e))
         })?;
         let games: Vec<arangors::Document<serde_json::Value>> = result.try_into().map_err(|e| {
@@ -3459,6 +4760,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
 
     /// Get head-to-head win matrix for top players
     pub async fn get_head_to_head_matrix(&self, limit: i32) -> Result<Vec<(String, String, f64)>> {
+        let _timer = self.timer("get_head_to_head_matrix", format!("limit={}", limit));
         let query = arangors::AqlQuery::builder()
             .query(
                 r#"
@@ -3516,7 +4818,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             .bind_var("limit", limit)
             .build();
 
-        let result = self.db.aql_query(query).await.map_err(|e| {
+        let result = self.run_query(query).await.map_err(|e| {
             SharedError::Database(format!("Failed to query head to head matrix: {}", e))
         })?;
         let matrix: Vec<arangors::Document<serde_json::Value>> =
@@ -3538,6 +4840,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
 
     /// Get games by player count distribution with individual game breakdowns
     pub async fn get_games_by_player_count(&self) -> Result<Vec<(i32, Vec<(String, i32)>)>> {
+        let _timer = self.timer("get_games_by_player_count", String::new());
         // First, let's check what fields are available in the contest collection
         let debug_query = arangors::AqlQuery::builder()
             .query(
@@ -3549,8 +4852,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             .build();
 
         let debug_result = self
-            .db
-            .aql_query::<serde_json::Value>(debug_query)
+            .run_query::<serde_json::Value>(debug_query)
             .await
             .map_err(|e| {
                 SharedError::Database(format!("Failed to debug contest structure: {}", e))
@@ -3571,8 +4873,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             .build();
 
         let game_debug_result = self
-            .db
-            .aql_query::<serde_json::Value>(game_debug_query)
+            .run_query::<serde_json::Value>(game_debug_query)
             .await
             .map_err(|e| SharedError::Database(format!("Failed to debug game structure: {}", e)))?;
 
@@ -3611,8 +4912,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             .build();
 
         let result = self
-            .db
-            .aql_query::<serde_json::Value>(query)
+            .run_query::<serde_json::Value>(query)
             .await
             .map_err(|e| {
                 SharedError::Database(format!("Failed to query games by player count: {}", e))
@@ -3637,8 +4937,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             .build();
 
         let test_result = self
-            .db
-            .aql_query::<serde_json::Value>(test_query)
+            .run_query::<serde_json::Value>(test_query)
             .await
             .map_err(|e| SharedError::Database(format!("Failed to test contest query: {}", e)))?;
 
@@ -3664,8 +4963,7 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
             .build();
 
         let relationship_result = self
-            .db
-            .aql_query::<serde_json::Value>(relationship_query)
+            .run_query::<serde_json::Value>(relationship_query)
             .await
             .map_err(|e| {
                 SharedError::Database(format!("Failed to test relationship query: {}", e))
@@ -3724,3 +5022,16 @@ impl<C: arangors::client::ClientExt> AnalyticsRepository<C> {
         Ok(final_result)
     }
 }
+
+/// Full English name of a day, as shown on venue stats
+fn weekday_name(day: chrono::Weekday) -> &'static str {
+    match day {
+        chrono::Weekday::Mon => "Monday",
+        chrono::Weekday::Tue => "Tuesday",
+        chrono::Weekday::Wed => "Wednesday",
+        chrono::Weekday::Thu => "Thursday",
+        chrono::Weekday::Fri => "Friday",
+        chrono::Weekday::Sat => "Saturday",
+        chrono::Weekday::Sun => "Sunday",
+    }
+}
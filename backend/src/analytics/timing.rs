@@ -0,0 +1,69 @@
+use serde::Serialize;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+/// Log target for slow query warnings, so they can be filtered or routed
+pub const SLOW_QUERY_TARGET: &str = "analytics::slow_query";
+
+/// Time budget for an analytics query before it is reported as slow
+pub const DEFAULT_SLOW_QUERY_THRESHOLD: Duration = Duration::from_millis(500);
+
+/// Warns when the query it guards runs longer than its budget.
+///
+/// Create one at the top of a repository method; the elapsed time is checked
+/// when it is dropped, i.e. once the method returns.
+#[must_use = "the query is only timed until the timer is dropped"]
+pub struct QueryTimer {
+    method: &'static str,
+    params: String,
+    threshold: Duration,
+    start: Instant,
+}
+
+impl QueryTimer {
+    pub fn start(method: &'static str, params: String, threshold: Duration) -> Self {
+        Self {
+            method,
+            params,
+            threshold,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for QueryTimer {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+        if elapsed > self.threshold {
+            log::warn!(
+                target: SLOW_QUERY_TARGET,
+                "Slow analytics query: method={} params=[{}] elapsedMs={} budgetMs={}",
+                self.method,
+                self.params,
+                elapsed.as_millis(),
+                self.threshold.as_millis()
+            );
+        }
+    }
+}
+
+/// How long one sub-query of a composite analytics method took
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SubQueryTiming {
+    pub query: &'static str,
+    pub elapsed_ms: f64,
+}
+
+/// Awaits `fut`, pairing its output with how long it took to complete
+pub async fn timed<T, E>(
+    query: &'static str,
+    fut: impl Future<Output = Result<T, E>>,
+) -> Result<(T, SubQueryTiming), E> {
+    let start = Instant::now();
+    let value = fut.await?;
+    let timing = SubQueryTiming {
+        query,
+        elapsed_ms: start.elapsed().as_secs_f64() * 1000.0,
+    };
+    Ok((value, timing))
+}
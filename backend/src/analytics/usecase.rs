@@ -1,10 +1,35 @@
 use super::cache::{AnalyticsCache, CacheKeys, CacheTTL};
 use super::engine::AnalyticsEngine;
-use super::repository::AnalyticsRepository;
+use super::repository::{AnalyticsRepository, PLATFORM_TOP_N_DEFAULT};
+use super::timing::SubQueryTiming;
 use super::visualization::{AnalyticsVisualization, Chart, ChartConfig};
 use arangors::client::ClientExt;
+use chrono::{DateTime, FixedOffset};
 use serde_json;
 use shared::{dto::analytics::*, models::analytics::AchievementCategory, Result};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Contests shown as a player's recent form on their summary
+const RECENT_FORM_CONTESTS: i32 = 5;
+
+/// Largest group accepted by the group head-to-head endpoint
+pub const HEAD_TO_HEAD_GROUP_MAX_PLAYERS: usize = 12;
+
+/// Page size of the contests-by-venue endpoint when none is asked for
+pub const VENUE_CONTESTS_DEFAULT_LIMIT: i32 = 20;
+
+/// Largest page the contests-by-venue endpoint returns
+pub const VENUE_CONTESTS_MAX_LIMIT: i32 = 100;
+
+/// Page size of the all-player-stats endpoint when none is asked for
+pub const PLAYER_STATS_PAGE_DEFAULT_LIMIT: i32 = 50;
+
+/// Largest page the all-player-stats endpoint returns
+pub const PLAYER_STATS_PAGE_MAX_LIMIT: i32 = 200;
+
+/// Most top games and venues the platform stats endpoint lists
+pub const PLATFORM_TOP_N_MAX: i32 = 25;
 
 /// Use case for analytics operations
 #[derive(Clone)]
@@ -14,6 +39,8 @@ pub struct AnalyticsUseCase<C: ClientExt> {
     engine: AnalyticsEngine,
     cache: AnalyticsCache,
     visualization: AnalyticsVisualization,
+    leaderboard_active_days: Option<u32>,
+    display_label_ttl: Duration,
 }
 
 impl<C: ClientExt> AnalyticsUseCase<C> {
@@ -52,6 +79,8 @@ impl<C: ClientExt> AnalyticsUseCase<C> {
             engine: AnalyticsEngine::new(),
             cache: AnalyticsCache::new_default(),
             visualization: AnalyticsVisualization::new(),
+            leaderboard_active_days: None,
+            display_label_ttl: CacheTTL::player_display_label(),
         }
     }
 
@@ -62,29 +91,73 @@ impl<C: ClientExt> AnalyticsUseCase<C> {
             engine: AnalyticsEngine::new(),
             cache,
             visualization: AnalyticsVisualization::new(),
+            leaderboard_active_days: None,
+            display_label_ttl: CacheTTL::player_display_label(),
         }
     }
 
+    /// Hides players who have not played for `days` from the win-rate
+    /// leaderboard, except in its all-time view
+    pub fn with_leaderboard_active_days(mut self, days: Option<u32>) -> Self {
+        self.leaderboard_active_days = days;
+        self
+    }
+
+    /// Sets how long player display labels are cached; zero turns the
+    /// cache off so every lookup reads the database
+    pub fn with_display_label_ttl(mut self, ttl: Duration) -> Self {
+        self.display_label_ttl = ttl;
+        self
+    }
+
     /// Get access to the repository
     pub fn repo(&self) -> &AnalyticsRepository<C> {
         &self.repo
     }
 
-    /// Get contest heatmap buckets (7x24) for recent weeks, optional game filter
+    /// Get contest heatmap buckets (7x24) for recent weeks or an explicit
+    /// date range, optionally filtered by game and venue
     pub async fn get_contest_heatmap(
         &self,
         weeks: i32,
+        from: Option<DateTime<FixedOffset>>,
+        to: Option<DateTime<FixedOffset>>,
         game_id: Option<&str>,
+        venue_id: Option<&str>,
     ) -> Result<serde_json::Value> {
         let weeks = weeks.max(1).min(52);
-        let rows = self.repo.get_contest_heatmap(weeks, game_id).await?;
+        let cache_key = CacheKeys::contest_heatmap(weeks, from, to, game_id, venue_id);
+
+        // Try to get from cache first
+        if let Some(cached_data) = self.cache.get(&cache_key).await {
+            if let Ok(heatmap) = serde_json::from_str::<serde_json::Value>(&cached_data) {
+                return Ok(heatmap);
+            }
+        }
+
+        let rows = self
+            .repo
+            .get_contest_heatmap(weeks, from, to, game_id, venue_id)
+            .await?;
         let mut buckets = vec![vec![0u64; 24]; 7];
         for r in rows {
             let d = (r.day.max(0).min(6)) as usize;
             let h = (r.hour.max(0).min(23)) as usize;
             buckets[d][h] = r.plays as u64;
         }
-        Ok(serde_json::json!({ "weeks": weeks, "buckets": buckets }))
+        let heatmap = serde_json::json!({
+            "weeks": weeks,
+            "from": from,
+            "to": to,
+            "buckets": buckets
+        });
+
+        // Cache the result
+        self.cache
+            .set_with_ttl(cache_key, heatmap.to_string(), CacheTTL::contest_heatmap())
+            .await;
+
+        Ok(heatmap)
     }
 
     /// Get platform statistics with caching
@@ -111,6 +184,75 @@ impl<C: ClientExt> AnalyticsUseCase<C> {
         Ok(dto)
     }
 
+    /// Get platform statistics listing up to `top_n` top games and venues.
+    ///
+    /// `top_n` is clamped to 1..=[`PLATFORM_TOP_N_MAX`]; the default count shares
+    /// the cache entry of [`Self::get_platform_stats`].
+    pub async fn get_platform_stats_top(&self, top_n: i32) -> Result<PlatformStatsDto> {
+        let top_n = top_n.clamp(1, PLATFORM_TOP_N_MAX);
+        if top_n == PLATFORM_TOP_N_DEFAULT {
+            return self.get_platform_stats().await;
+        }
+        let cache_key = CacheKeys::platform_stats_top(top_n);
+
+        // Try to get from cache first
+        if let Some(cached_data) = self.cache.get(&cache_key).await {
+            if let Ok(stats) = serde_json::from_str::<PlatformStatsDto>(&cached_data) {
+                return Ok(stats);
+            }
+        }
+
+        let stats = self.repo.get_platform_stats_top(top_n).await?;
+        let dto = PlatformStatsDto::from(&stats);
+
+        // Cache the result
+        let json_data = serde_json::to_string(&dto)?;
+        self.cache
+            .set_with_ttl(cache_key, json_data, CacheTTL::platform_stats())
+            .await;
+
+        Ok(dto)
+    }
+
+    /// Platform statistics computed afresh, along with how long each of the
+    /// sub-queries behind them took
+    ///
+    /// Bypasses the cache, so the timings always reflect a real computation;
+    /// `top_n` is clamped as in [`Self::get_platform_stats_top`].
+    pub async fn get_platform_stats_with_timing(
+        &self,
+        top_n: i32,
+    ) -> Result<(PlatformStatsDto, Vec<SubQueryTiming>)> {
+        let top_n = top_n.clamp(1, PLATFORM_TOP_N_MAX);
+        let (stats, timings) = self.repo.get_platform_stats_timed(top_n).await?;
+        Ok((PlatformStatsDto::from(&stats), timings))
+    }
+
+    /// Get the persisted platform statistics snapshot, computing it if none exists yet
+    pub async fn get_platform_stats_snapshot(&self) -> Result<PlatformStatsDto> {
+        match self.repo.get_persisted_platform_stats().await? {
+            Some(stats) => Ok(PlatformStatsDto::from(&stats)),
+            None => self.recompute_platform_stats().await,
+        }
+    }
+
+    /// Recompute and persist platform statistics, refreshing the cached copy
+    pub async fn recompute_platform_stats(&self) -> Result<PlatformStatsDto> {
+        let stats = self.repo.recompute_platform_stats().await?;
+        let dto = PlatformStatsDto::from(&stats);
+
+        let json_data = serde_json::to_string(&dto)?;
+        self.cache
+            .set_with_ttl(
+                CacheKeys::platform_stats(),
+                json_data,
+                CacheTTL::platform_stats(),
+            )
+            .await;
+
+        Ok(dto)
+    }
+
     /// Get leaderboard data with caching
     pub async fn get_leaderboard(
         &self,
@@ -127,7 +269,10 @@ impl<C: ClientExt> AnalyticsUseCase<C> {
 
         let limit = request.limit.unwrap_or(10);
         let offset = request.offset.unwrap_or(0);
-        let cache_key = CacheKeys::leaderboard(category_str, limit, offset);
+        let active_within_days = self
+            .leaderboard_active_days
+            .filter(|_| !request.include_inactive.unwrap_or(false));
+        let cache_key = CacheKeys::leaderboard(category_str, limit, offset, active_within_days);
 
         // Try to get from cache first
         if let Some(cached_data) = self.cache.get(&cache_key).await {
@@ -138,15 +283,26 @@ impl<C: ClientExt> AnalyticsUseCase<C> {
 
         let entries = self
             .repo
-            .get_leaderboard(category_str, limit, offset)
+            .get_leaderboard(category_str, limit, offset, active_within_days)
             .await?;
+        let player_ids: Vec<String> = entries.iter().map(|e| e.player_id.clone()).collect();
+        let labels = self
+            .get_player_display_labels(&player_ids)
+            .await
+            .unwrap_or_else(|e| {
+                log::warn!("Failed to load leaderboard display labels: {}", e);
+                HashMap::new()
+            });
 
         // Convert to DTO format
         let leaderboard_entries: Vec<LeaderboardEntry> = entries
             .into_iter()
             .enumerate()
             .map(|(index, entry)| {
-                let player_id = entry.player_id.clone();
+                let player_name = labels
+                    .get(&entry.player_id)
+                    .cloned()
+                    .unwrap_or_else(|| entry.player_handle.clone());
                 let value = match request.category {
                     LeaderboardCategory::WinRate => entry.win_rate,
                     LeaderboardCategory::TotalWins => entry.wins as f64,
@@ -159,7 +315,7 @@ impl<C: ClientExt> AnalyticsUseCase<C> {
                     rank: (offset + index as i32 + 1) as i32,
                     player_id: entry.player_id,
                     player_handle: entry.player_handle,
-                    player_name: format!("Player {}", player_id), // We'll need to get this from player data
+                    player_name,
                     value,
                     additional_data: None,
                 }
@@ -276,6 +432,11 @@ impl<C: ClientExt> AnalyticsUseCase<C> {
             total_achievements: achievements.total_achievements,
             unlocked_achievements: achievements.unlocked_achievements,
             completion_percentage: achievements.completion_percentage,
+            next_achievements: achievements
+                .next_achievements
+                .iter()
+                .map(NextAchievementDto::from)
+                .collect(),
         };
 
         // Cache the result
@@ -319,6 +480,54 @@ impl<C: ClientExt> AnalyticsUseCase<C> {
         Ok(ranking_dtos)
     }
 
+    /// Get everything the profile page shows about a player
+    ///
+    /// Composes the stats, rating, rankings, recent placements, game
+    /// performance and achievements lookups. None if the player does not exist.
+    pub async fn get_player_summary(&self, player_id: &str) -> Result<Option<PlayerSummaryDto>> {
+        let Some(profile) = self.repo.get_player_profile(player_id).await? else {
+            return Ok(None);
+        };
+
+        let stats_request = PlayerStatsRequest {
+            player_id: player_id.to_string(),
+            include_achievements: false,
+            include_trends: false,
+        };
+        let (stats, rating, rankings, recent_form, game_performance, achievements) = tokio::join!(
+            self.get_player_stats(player_id, &stats_request),
+            self.repo.get_player_rating_latest(player_id),
+            self.get_player_rankings(player_id),
+            self.repo
+                .get_recent_placements(player_id, RECENT_FORM_CONTESTS),
+            self.get_my_game_performance(player_id),
+            self.get_player_achievements(player_id),
+        );
+
+        let most_played_game = game_performance?.into_iter().max_by(|a, b| {
+            a.total_plays
+                .cmp(&b.total_plays)
+                .then(a.last_played.cmp(&b.last_played))
+        });
+
+        let achievements = achievements?;
+        Ok(Some(PlayerSummaryDto {
+            profile,
+            stats: stats?,
+            rating: rating?.map(|(rating, rd, games_played)| SummaryRatingDto {
+                rating,
+                rd,
+                games_played,
+            }),
+            rankings: rankings?,
+            recent_form: recent_form?,
+            most_played_game,
+            achievements_unlocked: achievements.unlocked_achievements,
+            achievements_total: achievements.total_achievements,
+            achievement_completion: achievements.completion_percentage,
+        }))
+    }
+
     /// Get contest statistics with caching
     pub async fn get_contest_stats(&self, contest_id: &str) -> Result<ContestStatsDto> {
         let cache_key = CacheKeys::contest_stats(contest_id);
@@ -361,9 +570,57 @@ impl<C: ClientExt> AnalyticsUseCase<C> {
         Ok(dto)
     }
 
-    /// Get contest trends with caching
-    pub async fn get_contest_trends(&self, months: i32) -> Result<Vec<MonthlyContestsDto>> {
-        let cache_key = CacheKeys::contest_trends(months);
+    /// Get game statistics computed from recorded plays, with caching
+    pub async fn get_game_stats(&self, game_id: &str) -> Result<GameStatsDto> {
+        let cache_key = CacheKeys::game_stats(game_id);
+
+        // Try to get from cache first
+        if let Some(cached_data) = self.cache.get(&cache_key).await {
+            if let Ok(stats) = serde_json::from_str::<GameStatsDto>(&cached_data) {
+                return Ok(stats);
+            }
+        }
+
+        let dto = self.repo.compute_game_stats(game_id).await?;
+
+        // Cache the result
+        let json_data = serde_json::to_string(&dto)?;
+        self.cache
+            .set_with_ttl(cache_key, json_data, CacheTTL::contest_stats())
+            .await;
+
+        Ok(dto)
+    }
+
+    /// Get venue statistics with caching
+    pub async fn get_venue_stats(&self, venue_id: &str) -> Result<VenueStatsDto> {
+        let cache_key = CacheKeys::venue_stats(venue_id);
+
+        // Try to get from cache first
+        if let Some(cached_data) = self.cache.get(&cache_key).await {
+            if let Ok(stats) = serde_json::from_str::<VenueStatsDto>(&cached_data) {
+                return Ok(stats);
+            }
+        }
+
+        let dto = self.repo.compute_venue_stats(venue_id).await?;
+
+        // Cache the result
+        let json_data = serde_json::to_string(&dto)?;
+        self.cache
+            .set_with_ttl(cache_key, json_data, CacheTTL::contest_stats())
+            .await;
+
+        Ok(dto)
+    }
+
+    /// Get contest trends with caching, optionally counting archived contests
+    pub async fn get_contest_trends(
+        &self,
+        months: i32,
+        include_archived: bool,
+    ) -> Result<Vec<MonthlyContestsDto>> {
+        let cache_key = CacheKeys::contest_trends(months, include_archived);
 
         // Try to get from cache first
         if let Some(cached_data) = self.cache.get(&cache_key).await {
@@ -372,7 +629,10 @@ impl<C: ClientExt> AnalyticsUseCase<C> {
             }
         }
 
-        let trends = self.repo.get_contest_trends(months).await?;
+        let trends = self
+            .repo
+            .get_contest_trends(months, include_archived)
+            .await?;
 
         let trend_dtos: Vec<MonthlyContestsDto> = trends
             .into_iter()
@@ -402,6 +662,24 @@ impl<C: ClientExt> AnalyticsUseCase<C> {
         self.repo.get_contest_excitement_rating(contest_id).await
     }
 
+    /// Get the standout contest of the last `window_days` days
+    pub async fn get_featured_contest(
+        &self,
+        window_days: i32,
+    ) -> Result<Option<shared::models::analytics::FeaturedContest>> {
+        let window_days = window_days.max(1).min(365);
+        self.repo.get_featured_contest(window_days).await
+    }
+
+    /// Get the biggest upset of the last `window_days` days
+    pub async fn get_biggest_upset(
+        &self,
+        window_days: i32,
+    ) -> Result<Option<shared::models::analytics::BiggestUpset>> {
+        let window_days = window_days.max(1).min(365);
+        self.repo.get_biggest_upset(window_days).await
+    }
+
     /// Get recent contests with caching
     pub async fn get_recent_contests(&self, limit: i32) -> Result<Vec<ContestStatsDto>> {
         let cache_key = CacheKeys::recent_contests(limit);
@@ -508,6 +786,7 @@ impl<C: ClientExt> AnalyticsUseCase<C> {
             limit: Some(limit),
             offset: Some(0),
             time_period: Some(TimePeriod::AllTime),
+            include_inactive: None,
         };
 
         let leaderboard = self.get_leaderboard(&request).await?;
@@ -531,7 +810,7 @@ impl<C: ClientExt> AnalyticsUseCase<C> {
         months: i32,
         config: Option<ChartConfig>,
     ) -> Result<Chart> {
-        let trends = self.get_contest_trends(months).await?;
+        let trends = self.get_contest_trends(months, false).await?;
         self.visualization.contest_trends(&trends, config)
     }
 
@@ -547,10 +826,20 @@ impl<C: ClientExt> AnalyticsUseCase<C> {
         player_ids: &[String],
         config: Option<ChartConfig>,
     ) -> Result<Chart> {
+        let normalized_ids: Vec<String> = player_ids
+            .iter()
+            .map(|id| Self::normalize_player_id(id))
+            .collect();
+        let labels = self
+            .get_player_display_labels(&normalized_ids)
+            .await
+            .unwrap_or_else(|e| {
+                log::warn!("Failed to load player display labels: {}", e);
+                HashMap::new()
+            });
         let mut player_stats = Vec::new();
 
-        for player_id in player_ids {
-            let normalized_id = Self::normalize_player_id(player_id);
+        for normalized_id in normalized_ids {
             match self
                 .get_player_stats(
                     &normalized_id,
@@ -572,11 +861,9 @@ impl<C: ClientExt> AnalyticsUseCase<C> {
                             stats.total_contests = games_played;
                         }
                     }
-                    if let Ok(Some(label)) =
-                        self.repo.get_player_display_label(&normalized_id).await
-                    {
+                    if let Some(label) = labels.get(&normalized_id) {
                         stats.player_handle = label.clone();
-                        stats.player_name = label;
+                        stats.player_name = label.clone();
                     } else if stats.player_handle.is_empty() {
                         stats.player_handle = normalized_id
                             .split('/')
@@ -716,6 +1003,20 @@ impl<C: ClientExt> AnalyticsUseCase<C> {
         Ok(opponents)
     }
 
+    /// Get every player the current player has shared a contest with
+    pub async fn get_play_partners(&self, player_id: &str) -> Result<Vec<PlayerOpponentDto>> {
+        let partners = self.repo.get_play_partners(player_id).await?;
+
+        // Cache the result
+        let cache_key = CacheKeys::play_partners(player_id);
+        let result_json = serde_json::to_string(&partners)?;
+        self.cache
+            .set_with_ttl(cache_key, result_json, CacheTTL::player_opponents())
+            .await;
+
+        Ok(partners)
+    }
+
     /// Get player's game performance statistics
     pub async fn get_my_game_performance(
         &self,
@@ -734,20 +1035,102 @@ impl<C: ClientExt> AnalyticsUseCase<C> {
         Ok(performance)
     }
 
-    /// Get player's head-to-head record against specific opponent
+    /// Get games the player hasn't tried yet, best matches first
+    pub async fn recommend_games(
+        &self,
+        player_id: &str,
+        limit: i32,
+    ) -> Result<Vec<GameRecommendationDto>> {
+        let recommendations = self.repo.recommend_games(player_id, limit).await?;
+
+        // Cache the result
+        let cache_key = CacheKeys::game_recommendations(player_id, limit);
+        let result_json = serde_json::to_string(&recommendations)?;
+        self.cache
+            .set_with_ttl(cache_key, result_json, CacheTTL::player_stats())
+            .await;
+
+        Ok(recommendations)
+    }
+
+    /// Get players who share venues or games with the player but haven't faced them
+    pub async fn get_networking_suggestions(
+        &self,
+        player_id: &str,
+    ) -> Result<Vec<NetworkingSuggestionDto>> {
+        let suggestions = self.repo.get_networking_suggestions(player_id).await?;
+
+        // Cache the result
+        let cache_key = CacheKeys::networking_suggestions(player_id);
+        let result_json = serde_json::to_string(&suggestions)?;
+        self.cache
+            .set_with_ttl(cache_key, result_json, CacheTTL::player_opponents())
+            .await;
+
+        Ok(suggestions)
+    }
+
+    /// Display labels for several players, keyed by player id
+    ///
+    /// Labels are read through the cache and those not cached are fetched in
+    /// a single query, so a renamed player shows the new label once the
+    /// cached one expires.
+    pub async fn get_player_display_labels(
+        &self,
+        player_ids: &[String],
+    ) -> Result<HashMap<String, String>> {
+        let use_cache = !self.display_label_ttl.is_zero();
+        let mut labels = HashMap::new();
+        let mut missing = Vec::new();
+        for player_id in player_ids {
+            if labels.contains_key(player_id) {
+                continue;
+            }
+            if use_cache {
+                let key = CacheKeys::player_display_label(player_id);
+                if let Some(label) = self.cache.get(&key).await {
+                    labels.insert(player_id.clone(), label);
+                    continue;
+                }
+            }
+            missing.push(player_id.clone());
+        }
+        if missing.is_empty() {
+            return Ok(labels);
+        }
+
+        let fetched = self.repo.get_player_display_labels(&missing).await?;
+        if use_cache {
+            for (player_id, label) in &fetched {
+                self.cache
+                    .set_with_ttl(
+                        CacheKeys::player_display_label(player_id),
+                        label.clone(),
+                        self.display_label_ttl,
+                    )
+                    .await;
+            }
+        }
+        labels.extend(fetched);
+        Ok(labels)
+    }
+
+    /// Get player's head-to-head record against specific opponent, in one
+    /// game or across all of them
     pub async fn get_head_to_head_record(
         &self,
         player_id: &str,
         opponent_id: &str,
+        game_id: Option<&str>,
     ) -> Result<HeadToHeadRecordDto> {
         // Get head-to-head record
         let record = self
             .repo
-            .get_head_to_head_record(player_id, opponent_id)
+            .get_head_to_head_record(player_id, opponent_id, game_id)
             .await?;
 
         // Cache the result
-        let cache_key = CacheKeys::head_to_head_record(player_id, opponent_id);
+        let cache_key = CacheKeys::head_to_head_record(player_id, opponent_id, game_id);
         let result_json = serde_json::to_string(&record)?;
         self.cache
             .set_with_ttl(cache_key, result_json, CacheTTL::head_to_head())
@@ -756,6 +1139,14 @@ impl<C: ClientExt> AnalyticsUseCase<C> {
         Ok(record)
     }
 
+    /// Get every pairwise head-to-head record within a group of players
+    pub async fn get_group_head_to_head(
+        &self,
+        player_ids: &[String],
+    ) -> Result<Vec<GroupHeadToHeadRecordDto>> {
+        self.repo.get_group_head_to_head(player_ids).await
+    }
+
     /// Get player's performance trends over time
     pub async fn get_my_performance_trends(
         &self,
@@ -772,16 +1163,53 @@ impl<C: ClientExt> AnalyticsUseCase<C> {
         Ok(trends)
     }
 
-    /// Get contests by venue for a player
+    /// Get one page of a player's contests at a venue, newest first
+    ///
+    /// `limit` is clamped to 1..=[`VENUE_CONTESTS_MAX_LIMIT`].
     pub async fn get_contests_by_venue(
         &self,
         player_id: &str,
         venue_id: &str,
-    ) -> Result<Vec<serde_json::Value>> {
-        // Get contests by venue using graph traversal
-        let contests = self.repo.get_contests_by_venue(player_id, venue_id).await?;
+        from: Option<DateTime<FixedOffset>>,
+        to: Option<DateTime<FixedOffset>>,
+        limit: i32,
+        offset: i32,
+    ) -> Result<VenueContestsPageDto> {
+        self.repo
+            .get_contests_by_venue(
+                player_id,
+                venue_id,
+                from,
+                to,
+                limit.clamp(1, VENUE_CONTESTS_MAX_LIMIT),
+                offset.max(0),
+            )
+            .await
+    }
 
-        Ok(contests)
+    /// Get one page of every player's stored stats
+    ///
+    /// `limit` defaults to [`PLAYER_STATS_PAGE_DEFAULT_LIMIT`] and is clamped
+    /// to 1..=[`PLAYER_STATS_PAGE_MAX_LIMIT`].
+    pub async fn get_all_player_stats(
+        &self,
+        request: &PlayerStatsPageRequest,
+    ) -> Result<PlayerStatsPageDto> {
+        let limit = request
+            .limit
+            .unwrap_or(PLAYER_STATS_PAGE_DEFAULT_LIMIT)
+            .clamp(1, PLAYER_STATS_PAGE_MAX_LIMIT);
+        let offset = request.offset.unwrap_or(0).max(0);
+        let (stats, total) = self
+            .repo
+            .get_all_player_stats(request.sort_by, limit, offset)
+            .await?;
+        Ok(PlayerStatsPageDto {
+            items: stats.iter().map(PlayerStatsDto::from).collect(),
+            total,
+            limit,
+            offset,
+        })
     }
 
     /// Debug method to check database content
@@ -812,6 +1240,23 @@ impl<C: ClientExt> AnalyticsUseCase<C> {
     pub async fn get_platform_insights(&self) -> Result<serde_json::Value> {
         self.repo.get_platform_insights().await
     }
+
+    /// Get communities of players grouped by the venues they frequent
+    pub async fn get_communities(&self) -> Result<Vec<GamingCommunityDto>> {
+        let communities = self.repo.get_communities().await?;
+
+        // Cache the result
+        let result_json = serde_json::to_string(&communities)?;
+        self.cache
+            .set_with_ttl(
+                CacheKeys::communities(),
+                result_json,
+                CacheTTL::platform_stats(),
+            )
+            .await;
+
+        Ok(communities)
+    }
 }
 
 impl<C: ClientExt> AnalyticsUseCase<C> {
@@ -895,8 +1340,12 @@ impl<C: ClientExt> AnalyticsUseCase<C> {
     pub async fn get_player_performance_distribution_chart(
         &self,
         config: Option<ChartConfig>,
+        sample_pct: Option<f64>,
     ) -> Result<Chart> {
-        let distribution = self.repo.get_player_performance_distribution().await?;
+        let distribution = self
+            .repo
+            .get_player_performance_distribution(sample_pct)
+            .await?;
 
         let data_points: Vec<crate::analytics::visualization::DataPoint> = distribution
             .into_iter()
@@ -917,14 +1366,17 @@ impl<C: ClientExt> AnalyticsUseCase<C> {
                 ..config.unwrap_or_default()
             },
             data: crate::analytics::visualization::ChartData::SingleSeries(data_points),
-            metadata: std::collections::HashMap::from([
+            metadata: [
                 (
                     "description".to_string(),
                     "Distribution of players by win rate ranges".to_string(),
                 ),
                 ("x_axis".to_string(), "Win Rate Range".to_string()),
                 ("y_axis".to_string(), "Number of Players".to_string()),
-            ]),
+            ]
+            .into_iter()
+            .chain(sampling_metadata(sample_pct))
+            .collect(),
         })
     }
 
@@ -1032,8 +1484,9 @@ impl<C: ClientExt> AnalyticsUseCase<C> {
     pub async fn get_player_retention_cohort_chart(
         &self,
         config: Option<ChartConfig>,
+        sample_pct: Option<f64>,
     ) -> Result<Chart> {
-        let cohorts = self.repo.get_player_retention_cohort().await?;
+        let cohorts = self.repo.get_player_retention_cohort(sample_pct).await?;
 
         let data_points: Vec<crate::analytics::visualization::DataPoint> = cohorts
             .into_iter()
@@ -1057,14 +1510,17 @@ impl<C: ClientExt> AnalyticsUseCase<C> {
                 ..config.unwrap_or_default()
             },
             data: crate::analytics::visualization::ChartData::SingleSeries(data_points),
-            metadata: std::collections::HashMap::from([
+            metadata: [
                 (
                     "description".to_string(),
                     "Player retention rate by contest number".to_string(),
                 ),
                 ("x_axis".to_string(), "Contest Number".to_string()),
                 ("y_axis".to_string(), "Retention Rate (%)".to_string()),
-            ]),
+            ]
+            .into_iter()
+            .chain(sampling_metadata(sample_pct))
+            .collect(),
         })
     }
 
@@ -1164,6 +1620,17 @@ impl<C: ClientExt> AnalyticsUseCase<C> {
     }
 }
 
+/// Chart metadata flagging figures computed from a `sample_pct` sample
+fn sampling_metadata(sample_pct: Option<f64>) -> Vec<(String, String)> {
+    match sample_pct {
+        Some(pct) => vec![
+            ("approximate".to_string(), "true".to_string()),
+            ("sample_pct".to_string(), pct.to_string()),
+        ],
+        None => Vec::new(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     // use super::*;
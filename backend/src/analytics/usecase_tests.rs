@@ -41,6 +41,7 @@ mod analytics_usecase_tests {
                     total_achievements: 10,
                     unlocked_achievements: 5,
                     completion_percentage: 50.0,
+                    next_achievements: vec![],
                 })),
                 monthly_contests: Arc::new(Mutex::new(vec![])),
             }
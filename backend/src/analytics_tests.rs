@@ -27,6 +27,7 @@ mod analytics_tests {
             total_achievements: 10,
             unlocked_achievements: 5,
             completion_percentage: 50.0,
+            next_achievements: vec![],
         };
 
         assert_eq!(achievements.player_id, "player/test");
@@ -113,6 +114,7 @@ mod analytics_tests {
             total_achievements: 10,
             unlocked_achievements: 5,
             completion_percentage: 50.0,
+            next_achievements: vec![],
         };
 
         let json = serde_json::to_string(&achievements).unwrap();
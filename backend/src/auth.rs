@@ -1,7 +1,8 @@
+use crate::player::api_key::{is_api_key, ApiKeyRepository};
 use actix_web::{
     dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
     error::ErrorUnauthorized,
-    Error, HttpMessage,
+    Error, HttpMessage, HttpRequest,
 };
 use arangors::client::ClientExt;
 use arangors::Database;
@@ -109,6 +110,12 @@ where
         log::debug!("AuthMiddleware processing request: {} {}", method, path);
 
         Box::pin(async move {
+            // Already authenticated by an outer ApiKeyMiddleware
+            let by_api_key = req.extensions().contains::<ApiKeyAuthenticated>();
+            if by_api_key {
+                return service.call(req).await;
+            }
+
             // Authorization header-based authentication only
             log::debug!("Checking Authorization header for {} {}", method, path);
 
@@ -355,6 +362,152 @@ where
     }
 }
 
+/// Tells whether a request comes from an admin's session, for public routes
+/// that show admins more rather than turning everyone else away
+pub struct AdminLookup<C: ClientExt> {
+    pub redis: Arc<redis::Client>,
+    pub db: Arc<Database<C>>,
+}
+
+impl<C: ClientExt> AdminLookup<C> {
+    /// Whether the `Authorization: Bearer` session belongs to an admin; any
+    /// missing session or lookup failure counts as not an admin
+    pub async fn is_admin(&self, req: &HttpRequest) -> bool {
+        let Some(session_id) = req
+            .headers()
+            .get("Authorization")
+            .and_then(|header| header.to_str().ok())
+            .and_then(|header| header.strip_prefix("Bearer "))
+            .map(|id| id.trim().to_string())
+        else {
+            return false;
+        };
+
+        let email: Option<String> = match self.redis.get_async_connection().await {
+            Ok(mut conn) => conn.get(&session_id).await.unwrap_or_else(|e| {
+                log::error!("AdminLookup: Failed to get email from Redis: {}", e);
+                None
+            }),
+            Err(e) => {
+                log::error!("AdminLookup: Failed to get Redis connection: {}", e);
+                None
+            }
+        };
+        let Some(email) = email else {
+            return false;
+        };
+
+        let query = arangors::AqlQuery::builder()
+            .query("FOR p IN player FILTER LOWER(p.email) == LOWER(@email) LIMIT 1 RETURN p")
+            .bind_var("email", email)
+            .build();
+        match self.db.aql_query::<Player>(query).await {
+            Ok(players) => players.first().is_some_and(|player| player.is_admin),
+            Err(e) => {
+                log::error!("AdminLookup: Failed to query player: {}", e);
+                false
+            }
+        }
+    }
+}
+
+/// Marks a request authenticated with an API key rather than a session
+#[derive(Debug, Clone, Copy)]
+pub struct ApiKeyAuthenticated;
+
+/// Accepts `Authorization: Bearer <api key>` on POST requests
+///
+/// Wrap it outside `AuthMiddleware` (i.e. `.wrap(AuthMiddleware {..})` first,
+/// then `.wrap(ApiKeyMiddleware {..})`). A valid key authenticates the request
+/// as its owner, exactly as a session would; any other bearer token is left
+/// for `AuthMiddleware` to check as a session id. Keys only submit data, so
+/// they are refused on other methods.
+pub struct ApiKeyMiddleware {
+    pub keys: Arc<ApiKeyRepository>,
+}
+
+impl<S, B> Transform<S, ServiceRequest> for ApiKeyMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = ApiKeyMiddlewareService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(ApiKeyMiddlewareService {
+            service: Arc::new(service),
+            keys: self.keys.clone(),
+        }))
+    }
+}
+
+pub struct ApiKeyMiddlewareService<S> {
+    service: Arc<S>,
+    keys: Arc<ApiKeyRepository>,
+}
+
+impl<S, B> Service<ServiceRequest> for ApiKeyMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let keys = self.keys.clone();
+        let service = self.service.clone();
+
+        Box::pin(async move {
+            let api_key = req
+                .headers()
+                .get("Authorization")
+                .and_then(|header| header.to_str().ok())
+                .and_then(|header| header.strip_prefix("Bearer "))
+                .map(|token| token.trim().to_string())
+                .filter(|token| is_api_key(token));
+            let Some(api_key) = api_key else {
+                return service.call(req).await;
+            };
+
+            if req.method() != actix_web::http::Method::POST {
+                log::warn!(
+                    "API key used for {} {}, which only accepts sessions",
+                    req.method(),
+                    req.path()
+                );
+                return Err(ErrorUnauthorized("API keys may only submit data"));
+            }
+
+            match keys.authenticate(&api_key).await {
+                Ok(Some(email)) => {
+                    log::debug!("API key authentication successful for {}", req.path());
+                    req.extensions_mut().insert(email);
+                    req.extensions_mut().insert(ApiKeyAuthenticated);
+                    service.call(req).await
+                }
+                Ok(None) => {
+                    log::warn!("Rejected unknown or revoked API key for {}", req.path());
+                    Err(ErrorUnauthorized("Invalid or revoked API key"))
+                }
+                Err(e) => {
+                    log::error!("API key lookup failed: {}", e);
+                    Err(ErrorUnauthorized("Authentication service unavailable"))
+                }
+            }
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -495,8 +495,8 @@ impl<C: ClientExt + Send + Sync> ClientAnalyticsRepository for ClientAnalyticsRe
             FILTER result._from == @contest_id
             LET player = DOCUMENT(result._to)
             RETURN {
-                player_id: player._id,
-                handle: player.handle,
+                player_id: result._to,
+                handle: player != null ? player.handle : result.guest_name,
                 firstname: player.firstname,
                 lastname: player.lastname,
                 place: result.place,
@@ -638,7 +638,7 @@ impl<C: ClientExt + Send + Sync> ClientAnalyticsRepository for ClientAnalyticsRe
             LET contest = DOCUMENT(result._from)
             FOR other_result IN resulted_in
             FILTER other_result._from == contest._id
-            FILTER other_result._to != @player_id
+            FILTER other_result._to != @player_id AND other_result.guest_name == null
             LET opponent = DOCUMENT(other_result._to)
             COLLECT opponent_id = opponent._id INTO unique_opponents
             RETURN DOCUMENT(opponent_id)
@@ -776,7 +776,7 @@ impl<C: ClientExt + Send + Sync> ClientAnalyticsRepository for ClientAnalyticsRe
               LET regular_opponents = (
                 FOR contest IN player_contests
                   FOR result IN resulted_in
-                    FILTER result._from == contest._id
+                    FILTER result._from == contest._id AND result.guest_name == null
                     LET other_player = DOCUMENT(result._to)
                     FILTER other_player._id != @player_id
                     
@@ -882,7 +882,7 @@ impl<C: ClientExt + Send + Sync> ClientAnalyticsRepository for ClientAnalyticsRe
                   FILTER result._to == player._id
                   LET contest = DOCUMENT(result._from)
                   FOR other_result IN resulted_in
-                    FILTER other_result._from == contest._id
+                    FILTER other_result._from == contest._id AND other_result.guest_name == null
                     LET opponent = DOCUMENT(other_result._to)
                     FILTER opponent._id != @player_id
                     RETURN {
@@ -915,6 +915,7 @@ impl<C: ClientExt + Send + Sync> ClientAnalyticsRepository for ClientAnalyticsRe
                           FILTER other_result._from == other_contest._from
                             AND other_result._to != @player_id
                             AND other_result._to != opponent_id
+                            AND other_result.guest_name == null
                           LET common_opponent = DOCUMENT(other_result._to)
                           COLLECT common_id = common_opponent._id INTO common_list
                           RETURN {
@@ -1,9 +1,9 @@
 use dotenv::dotenv;
 use log::{info, warn};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::env;
 
-#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub enum Environment {
     Development,
     Test,
@@ -29,7 +29,7 @@ impl std::str::FromStr for Environment {
     }
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Config {
     pub environment: Environment,
     pub server: ServerConfig,
@@ -37,18 +37,28 @@ pub struct Config {
     pub redis: RedisConfig,
     pub google: GoogleConfig,
     pub bgg: BGGConfig,
-    pub _security: SecurityConfig,
+    pub contest: ContestConfig,
+    pub analytics: AnalyticsConfig,
+    pub ratings: RatingsConfig,
+    pub maintenance: MaintenanceConfig,
+    pub passwords: PasswordConfig,
+    pub security: SecurityConfig,
+    pub features: FeatureFlags,
     pub _logging: LoggingConfig,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ServerConfig {
     pub host: String,
     pub port: u16,
-    pub workers: usize,
+    /// Actix worker threads; `None` keeps actix's default of one per core.
+    ///
+    /// Every worker shares the same database pool, so keep this at or below
+    /// `database.pool_size` to avoid workers queueing for connections.
+    pub workers: Option<usize>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct DatabaseConfig {
     pub url: String,
     pub name: String,
@@ -60,33 +70,287 @@ pub struct DatabaseConfig {
     pub _timeout_seconds: u64,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct RedisConfig {
     pub url: String,
     pub pool_size: u32,
     pub _timeout_seconds: u64,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+/// Security headers added to every response, and paths CORS leaves open
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SecurityConfig {
-    // Remove unused fields
+    /// Sent as `Content-Security-Policy`; None leaves the header off
+    pub content_security_policy: Option<String>,
+    /// `max-age` of `Strict-Transport-Security` in seconds; None leaves the
+    /// header off, as it must be when the API is not served over HTTPS
+    pub hsts_max_age_secs: Option<u64>,
+    /// Paths, and everything below them, that any origin may call, so
+    /// monitors on other hosts can probe health
+    pub cors_exempt_paths: Vec<String>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+impl Default for SecurityConfig {
+    fn default() -> Self {
+        Self {
+            content_security_policy: Some("frame-ancestors 'none'".to_string()),
+            hsts_max_age_secs: None,
+            cors_exempt_paths: ["/health", "/healthz", "/readyz"]
+                .map(String::from)
+                .to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct LoggingConfig {
     // Remove unused fields
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct GoogleConfig {
     pub api_url: String,
     pub location_api_key: Option<String>,
+    /// ccTLD region code biasing Places results, e.g. "uk"
+    pub places_region: Option<String>,
+    /// Language for Places results, e.g. "en-GB"
+    pub places_language: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct BGGConfig {
     pub api_url: String,
     pub api_token: Option<String>,
+    pub paths: BGGPaths,
+}
+
+/// Request path templates appended to `BGGConfig::api_url`.
+///
+/// `{query}` in `search` and `{id}` in `thing` are filled in from the
+/// request; values used in the path are not repeated as query parameters.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BGGPaths {
+    pub search: String,
+    pub thing: String,
+    pub hot: String,
+}
+
+impl Default for BGGPaths {
+    fn default() -> Self {
+        Self {
+            search: "/search".to_string(),
+            thing: "/thing".to_string(),
+            hot: "/hot".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ContestConfig {
+    pub max_participants: usize,
+    pub webhooks: WebhookConfig,
+    /// Contests that started more than this many days ago are moved to the
+    /// archive when an admin runs archival without an explicit cutoff
+    pub archive_after_days: u32,
+    /// How long a creator may undo their own contest; after this only an
+    /// admin can delete it. Zero leaves deletion to admins entirely
+    pub undo_window_minutes: u32,
+    /// Contests starting more than this many days ago are rejected
+    pub max_start_days_past: u32,
+    /// Contests starting more than this many days from now are rejected
+    pub max_start_days_future: u32,
+}
+
+impl Default for ContestConfig {
+    fn default() -> Self {
+        Self {
+            max_participants: 64,
+            webhooks: WebhookConfig::default(),
+            archive_after_days: 5 * 365,
+            undo_window_minutes: 5,
+            max_start_days_past: 30 * 365,
+            max_start_days_future: 365,
+        }
+    }
+}
+
+/// Outbound webhooks POSTed a summary of every created contest
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WebhookConfig {
+    pub endpoints: Vec<WebhookEndpoint>,
+    /// Per-attempt request timeout
+    pub timeout_ms: u64,
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled after each further attempt
+    pub initial_backoff_ms: u64,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            endpoints: Vec::new(),
+            timeout_ms: 5000,
+            max_attempts: 3,
+            initial_backoff_ms: 500,
+        }
+    }
+}
+
+/// A webhook receiver
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct WebhookEndpoint {
+    pub url: String,
+    /// Key for the `X-Signature` HMAC; requests are unsigned without one
+    pub secret: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AnalyticsConfig {
+    /// Analytics queries slower than this are logged as warnings
+    pub slow_query_threshold_ms: u64,
+    /// IANA timezone for time-of-day analytics of contests whose venue has none
+    pub default_timezone: String,
+    /// Players whose last contest is older than this many days are left off
+    /// the win-rate leaderboard unless the all-time view is asked for
+    pub leaderboard_active_days: Option<u32>,
+    /// Log every analytics AQL query and its redacted bind vars at debug
+    /// level on the `analytics::aql` target
+    pub log_aql: bool,
+    /// Seconds a player's display label stays cached; 0 reads it from the
+    /// database every time
+    pub display_label_cache_secs: u64,
+    /// Prefix every analytics cache key is stored under, and that an admin
+    /// cache clear removes
+    pub cache_key_prefix: String,
+}
+
+impl Default for AnalyticsConfig {
+    fn default() -> Self {
+        Self {
+            slow_query_threshold_ms: 500,
+            default_timezone: "UTC".to_string(),
+            leaderboard_active_days: None,
+            log_aql: false,
+            display_label_cache_secs: 15 * 60,
+            cache_key_prefix: crate::analytics::CacheKeys::PREFIX.to_string(),
+        }
+    }
+}
+
+/// Bounds on stored Glicko-2 ratings; either may be left unset
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct RatingsConfig {
+    pub rating_floor: Option<f64>,
+    pub rating_ceiling: Option<f64>,
+    /// Minimum milliseconds between recalculating two months, e.g. during a
+    /// backfill (default: 0, unthrottled)
+    pub period_interval_ms: u64,
+}
+
+impl RatingsConfig {
+    /// Default Glicko-2 parameters with this config's rating bounds
+    pub fn glicko_params(&self) -> crate::ratings::glicko::Glicko2Params {
+        crate::ratings::glicko::Glicko2Params {
+            rating_floor: self.rating_floor,
+            rating_ceiling: self.rating_ceiling,
+            ..Default::default()
+        }
+    }
+
+    /// Minimum time between recalculating two months
+    pub fn period_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.period_interval_ms)
+    }
+}
+
+/// Optional features, each on unless its `FEATURE_*` variable turns it off
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FeatureFlags {
+    /// Created contests are POSTed to the configured webhooks
+    pub webhooks: bool,
+    /// Browser telemetry under `/api/client` and `/api/analytics-enhanced`
+    pub client_analytics: bool,
+    /// Swagger UI and the OpenAPI document
+    pub api_docs: bool,
+}
+
+impl Default for FeatureFlags {
+    fn default() -> Self {
+        Self {
+            webhooks: true,
+            client_analytics: true,
+            api_docs: true,
+        }
+    }
+}
+
+/// Freezes writes while operators migrate or backfill data
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MaintenanceConfig {
+    /// When set, POST/PUT/PATCH/DELETE requests are answered with 503
+    pub enabled: bool,
+    /// Shown to clients whose write was refused
+    pub message: String,
+}
+
+impl Default for MaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            message: "The site is undergoing maintenance; changes are temporarily disabled"
+                .to_string(),
+        }
+    }
+}
+
+/// Algorithm new password hashes are created with
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum PasswordAlgorithm {
+    #[default]
+    Argon2id,
+    Bcrypt,
+}
+
+impl std::str::FromStr for PasswordAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "argon2" | "argon2id" => Ok(PasswordAlgorithm::Argon2id),
+            "bcrypt" => Ok(PasswordAlgorithm::Bcrypt),
+            _ => Err(format!("Unknown password hashing algorithm: {}", s)),
+        }
+    }
+}
+
+/// Password hashing algorithm and cost
+///
+/// Stored hashes made with another algorithm or cost still verify, and are
+/// rehashed with these settings on the player's next login.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct PasswordConfig {
+    pub algorithm: PasswordAlgorithm,
+    /// Argon2 memory cost in KiB
+    pub argon2_memory_kib: u32,
+    /// Argon2 passes over memory
+    pub argon2_iterations: u32,
+    /// Argon2 lanes
+    pub argon2_parallelism: u32,
+    /// bcrypt cost, the log2 of its rounds
+    pub bcrypt_cost: u32,
+}
+
+impl Default for PasswordConfig {
+    fn default() -> Self {
+        Self {
+            algorithm: PasswordAlgorithm::default(),
+            argon2_memory_kib: argon2::Params::DEFAULT_M_COST,
+            argon2_iterations: argon2::Params::DEFAULT_T_COST,
+            argon2_parallelism: argon2::Params::DEFAULT_P_COST,
+            bcrypt_cost: bcrypt::DEFAULT_COST,
+        }
+    }
 }
 
 impl Config {
@@ -151,7 +415,13 @@ impl Config {
             redis: Self::load_redis_config(&environment),
             google: Self::load_google_config(&environment),
             bgg: Self::load_bgg_config(&environment),
-            _security: Self::load_security_config(&environment),
+            contest: Self::load_contest_config(&environment),
+            analytics: Self::load_analytics_config(&environment),
+            ratings: Self::load_ratings_config(&environment),
+            maintenance: Self::load_maintenance_config(&environment),
+            passwords: Self::load_password_config(&environment),
+            security: Self::load_security_config(&environment),
+            features: Self::load_feature_flags(&environment),
             _logging: Self::load_logging_config(&environment),
         };
 
@@ -161,6 +431,14 @@ impl Config {
         Ok(config)
     }
 
+    /// Reads `BACKEND_WORKERS`, ignoring values that aren't a positive count
+    fn load_workers() -> Option<usize> {
+        env::var("BACKEND_WORKERS")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .filter(|&n| n > 0)
+    }
+
     fn load_server_config(env: &Environment) -> ServerConfig {
         match env {
             Environment::Development => {
@@ -175,10 +453,7 @@ impl Config {
                         .unwrap_or_else(|_| port.to_string())
                         .parse()
                         .unwrap_or(port),
-                    workers: env::var("BACKEND_WORKERS")
-                        .unwrap_or_else(|_| "1".to_string())
-                        .parse()
-                        .unwrap_or(1),
+                    workers: Self::load_workers(),
                 }
             }
             Environment::Production => {
@@ -193,10 +468,7 @@ impl Config {
                         .unwrap_or_else(|_| port.to_string())
                         .parse()
                         .unwrap_or(port),
-                    workers: env::var("BACKEND_WORKERS")
-                        .unwrap_or_else(|_| "8".to_string())
-                        .parse()
-                        .unwrap_or(8),
+                    workers: Self::load_workers(),
                 }
             }
             Environment::Test => {
@@ -210,10 +482,7 @@ impl Config {
                         .unwrap_or_else(|_| port.to_string())
                         .parse()
                         .unwrap_or(port),
-                    workers: env::var("BACKEND_WORKERS")
-                        .unwrap_or_else(|_| "1".to_string())
-                        .parse()
-                        .unwrap_or(1),
+                    workers: Self::load_workers(),
                 }
             }
         }
@@ -338,17 +607,52 @@ impl Config {
         }
     }
 
-    fn load_security_config(env: &Environment) -> SecurityConfig {
-        match env {
-            Environment::Development => SecurityConfig {
-                // Remove unused fields
+    fn load_feature_flags(_env: &Environment) -> FeatureFlags {
+        let defaults = FeatureFlags::default();
+        // Unrecognised values keep the default rather than silently disabling
+        let flag = |name: &str, default: bool| match env::var(name) {
+            Ok(v) => match v.trim().to_lowercase().as_str() {
+                "1" | "true" | "yes" | "on" => true,
+                "0" | "false" | "no" | "off" => false,
+                _ => default,
             },
-            Environment::Production => SecurityConfig {
-                // Remove unused fields
+            Err(_) => default,
+        };
+        FeatureFlags {
+            webhooks: flag("FEATURE_WEBHOOKS", defaults.webhooks),
+            client_analytics: flag("FEATURE_CLIENT_ANALYTICS", defaults.client_analytics),
+            api_docs: flag("FEATURE_API_DOCS", defaults.api_docs),
+        }
+    }
+
+    fn load_security_config(environment: &Environment) -> SecurityConfig {
+        let defaults = SecurityConfig::default();
+        // Only production is guaranteed to sit behind HTTPS
+        let hsts_default = match environment {
+            Environment::Production => Some(31_536_000),
+            Environment::Development | Environment::Test => defaults.hsts_max_age_secs,
+        };
+        SecurityConfig {
+            // An empty policy turns the header off
+            content_security_policy: match env::var("CONTENT_SECURITY_POLICY") {
+                Ok(policy) if policy.trim().is_empty() => None,
+                Ok(policy) => Some(policy),
+                Err(_) => defaults.content_security_policy,
             },
-            Environment::Test => SecurityConfig {
-                // Remove unused fields
+            // So does a max-age of 0
+            hsts_max_age_secs: match env::var("HSTS_MAX_AGE_SECS") {
+                Ok(secs) => secs.parse().ok().filter(|secs| *secs > 0),
+                Err(_) => hsts_default,
             },
+            cors_exempt_paths: env::var("CORS_EXEMPT_PATHS")
+                .map(|paths| {
+                    paths
+                        .split(',')
+                        .map(|path| path.trim().to_string())
+                        .filter(|path| !path.is_empty())
+                        .collect()
+                })
+                .unwrap_or(defaults.cors_exempt_paths),
         }
     }
 
@@ -375,6 +679,12 @@ impl Config {
         GoogleConfig {
             api_url: api_url,
             location_api_key: env::var("GOOGLE_LOCATION_API").ok(),
+            places_region: env::var("GOOGLE_PLACES_REGION")
+                .ok()
+                .filter(|v| !v.is_empty()),
+            places_language: env::var("GOOGLE_PLACES_LANGUAGE")
+                .ok()
+                .filter(|v| !v.is_empty()),
         }
     }
 
@@ -383,6 +693,166 @@ impl Config {
             api_url: env::var("BGG_API_URL")
                 .unwrap_or_else(|_| "https://api.boardgamegeek.com/".to_string()),
             api_token: env::var("BGG_API_TOKEN").ok(),
+            paths: Self::load_bgg_paths(),
+        }
+    }
+
+    fn load_bgg_paths() -> BGGPaths {
+        let defaults = BGGPaths::default();
+        BGGPaths {
+            search: env::var("BGG_SEARCH_PATH").unwrap_or(defaults.search),
+            thing: env::var("BGG_THING_PATH").unwrap_or(defaults.thing),
+            hot: env::var("BGG_HOT_PATH").unwrap_or(defaults.hot),
+        }
+    }
+
+    fn load_contest_config(_env: &Environment) -> ContestConfig {
+        let defaults = ContestConfig::default();
+        ContestConfig {
+            max_participants: env::var("MAX_CONTEST_PARTICIPANTS")
+                .unwrap_or_else(|_| defaults.max_participants.to_string())
+                .parse()
+                .unwrap_or(defaults.max_participants),
+            webhooks: Self::load_webhook_config(),
+            archive_after_days: env::var("CONTEST_ARCHIVE_AFTER_DAYS")
+                .ok()
+                .and_then(|days| days.parse().ok())
+                .unwrap_or(defaults.archive_after_days),
+            undo_window_minutes: env::var("CONTEST_UNDO_WINDOW_MINUTES")
+                .ok()
+                .and_then(|minutes| minutes.parse().ok())
+                .unwrap_or(defaults.undo_window_minutes),
+            max_start_days_past: env::var("CONTEST_MAX_START_DAYS_PAST")
+                .ok()
+                .and_then(|days| days.parse().ok())
+                .unwrap_or(defaults.max_start_days_past),
+            max_start_days_future: env::var("CONTEST_MAX_START_DAYS_FUTURE")
+                .ok()
+                .and_then(|days| days.parse().ok())
+                .unwrap_or(defaults.max_start_days_future),
+        }
+    }
+
+    fn load_webhook_config() -> WebhookConfig {
+        let defaults = WebhookConfig::default();
+        // Secrets pair up with URLs by position; an empty entry leaves that
+        // endpoint unsigned
+        let secrets: Vec<String> = env::var("CONTEST_WEBHOOK_SECRETS")
+            .map(|secrets| secrets.split(',').map(|s| s.trim().to_string()).collect())
+            .unwrap_or_default();
+        WebhookConfig {
+            endpoints: env::var("CONTEST_WEBHOOK_URLS")
+                .map(|urls| {
+                    urls.split(',')
+                        .map(|url| url.trim().to_string())
+                        .enumerate()
+                        .filter(|(_, url)| !url.is_empty())
+                        .map(|(i, url)| WebhookEndpoint {
+                            url,
+                            secret: secrets.get(i).filter(|s| !s.is_empty()).cloned(),
+                        })
+                        .collect()
+                })
+                .unwrap_or(defaults.endpoints),
+            timeout_ms: env::var("CONTEST_WEBHOOK_TIMEOUT_MS")
+                .ok()
+                .and_then(|ms| ms.parse().ok())
+                .unwrap_or(defaults.timeout_ms),
+            max_attempts: env::var("CONTEST_WEBHOOK_MAX_ATTEMPTS")
+                .ok()
+                .and_then(|n| n.parse().ok())
+                .unwrap_or(defaults.max_attempts),
+            initial_backoff_ms: env::var("CONTEST_WEBHOOK_BACKOFF_MS")
+                .ok()
+                .and_then(|ms| ms.parse().ok())
+                .unwrap_or(defaults.initial_backoff_ms),
+        }
+    }
+
+    fn load_analytics_config(_env: &Environment) -> AnalyticsConfig {
+        let defaults = AnalyticsConfig::default();
+        AnalyticsConfig {
+            slow_query_threshold_ms: env::var("ANALYTICS_SLOW_QUERY_MS")
+                .ok()
+                .and_then(|ms| ms.parse().ok())
+                .unwrap_or(defaults.slow_query_threshold_ms),
+            default_timezone: env::var("ANALYTICS_DEFAULT_TIMEZONE")
+                .ok()
+                .map(|tz| tz.trim().to_string())
+                .filter(|tz| !tz.is_empty())
+                .unwrap_or(defaults.default_timezone),
+            leaderboard_active_days: env::var("ANALYTICS_LEADERBOARD_ACTIVE_DAYS")
+                .ok()
+                .and_then(|days| days.trim().parse().ok())
+                .or(defaults.leaderboard_active_days),
+            log_aql: env::var("LOG_AQL")
+                .map(|v| {
+                    matches!(
+                        v.trim().to_lowercase().as_str(),
+                        "1" | "true" | "yes" | "on"
+                    )
+                })
+                .unwrap_or(defaults.log_aql),
+            display_label_cache_secs: env::var("ANALYTICS_DISPLAY_LABEL_CACHE_SECS")
+                .ok()
+                .and_then(|secs| secs.trim().parse().ok())
+                .unwrap_or(defaults.display_label_cache_secs),
+            cache_key_prefix: env::var("ANALYTICS_CACHE_PREFIX")
+                .ok()
+                .map(|prefix| prefix.trim().to_string())
+                .filter(|prefix| !prefix.is_empty())
+                .unwrap_or(defaults.cache_key_prefix),
+        }
+    }
+
+    fn load_ratings_config(_env: &Environment) -> RatingsConfig {
+        let bound = |name: &str| env::var(name).ok().and_then(|v| v.trim().parse().ok());
+        RatingsConfig {
+            rating_floor: bound("RATING_FLOOR"),
+            rating_ceiling: bound("RATING_CEILING"),
+            period_interval_ms: env::var("RATING_PERIOD_INTERVAL_MS")
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(0),
+        }
+    }
+
+    fn load_maintenance_config(_env: &Environment) -> MaintenanceConfig {
+        let defaults = MaintenanceConfig::default();
+        MaintenanceConfig {
+            enabled: env::var("MAINTENANCE_MODE")
+                .map(|v| {
+                    matches!(
+                        v.trim().to_lowercase().as_str(),
+                        "1" | "true" | "yes" | "on"
+                    )
+                })
+                .unwrap_or(defaults.enabled),
+            message: env::var("MAINTENANCE_MESSAGE")
+                .ok()
+                .map(|m| m.trim().to_string())
+                .filter(|m| !m.is_empty())
+                .unwrap_or(defaults.message),
+        }
+    }
+
+    fn load_password_config(_env: &Environment) -> PasswordConfig {
+        let defaults = PasswordConfig::default();
+        let cost = |name: &str, default: u32| {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(default)
+        };
+        PasswordConfig {
+            algorithm: env::var("PASSWORD_HASH_ALGORITHM")
+                .ok()
+                .and_then(|a| a.parse().ok())
+                .unwrap_or(defaults.algorithm),
+            argon2_memory_kib: cost("PASSWORD_ARGON2_MEMORY_KIB", defaults.argon2_memory_kib),
+            argon2_iterations: cost("PASSWORD_ARGON2_ITERATIONS", defaults.argon2_iterations),
+            argon2_parallelism: cost("PASSWORD_ARGON2_PARALLELISM", defaults.argon2_parallelism),
+            bcrypt_cost: cost("PASSWORD_BCRYPT_COST", defaults.bcrypt_cost),
         }
     }
 
@@ -410,22 +880,118 @@ impl Config {
             return Err("Redis pool size cannot be 0".into());
         }
 
+        if self.contest.max_participants == 0 {
+            return Err("Max contest participants cannot be 0".into());
+        }
+        if self.contest.archive_after_days == 0 {
+            return Err("Contest archive cutoff cannot be 0 days".into());
+        }
+        if self.contest.max_start_days_past == 0 {
+            return Err("Contest start window must reach at least 1 day into the past".into());
+        }
+
+        if self
+            .analytics
+            .default_timezone
+            .parse::<chrono_tz::Tz>()
+            .is_err()
+        {
+            return Err(format!(
+                "Invalid analytics default timezone '{}'",
+                self.analytics.default_timezone
+            )
+            .into());
+        }
+
+        if self.analytics.leaderboard_active_days == Some(0) {
+            return Err("Leaderboard active window cannot be 0 days".into());
+        }
+
+        let RatingsConfig {
+            rating_floor,
+            rating_ceiling,
+            ..
+        } = self.ratings;
+        if rating_floor.is_some_and(|floor| !floor.is_finite())
+            || rating_ceiling.is_some_and(|ceiling| !ceiling.is_finite())
+        {
+            return Err("Rating floor and ceiling must be finite numbers".into());
+        }
+        if let (Some(floor), Some(ceiling)) = (rating_floor, rating_ceiling) {
+            if floor > ceiling {
+                return Err(format!(
+                    "Rating floor {} is above the rating ceiling {}",
+                    floor, ceiling
+                )
+                .into());
+            }
+        }
+
+        let passwords = &self.passwords;
+        if let Err(e) = argon2::Params::new(
+            passwords.argon2_memory_kib,
+            passwords.argon2_iterations,
+            passwords.argon2_parallelism,
+            None,
+        ) {
+            return Err(format!("Invalid Argon2 password hashing cost: {}", e).into());
+        }
+        if !(4..=31).contains(&passwords.bcrypt_cost) {
+            return Err(format!(
+                "bcrypt cost must be between 4 and 31, got {}",
+                passwords.bcrypt_cost
+            )
+            .into());
+        }
+
+        if let Some(policy) = &self.security.content_security_policy {
+            if !policy.chars().all(|c| c == ' ' || c.is_ascii_graphic()) {
+                return Err("Content-Security-Policy must be printable ASCII".into());
+            }
+        }
+        if let Some(path) = self
+            .security
+            .cors_exempt_paths
+            .iter()
+            .find(|path| !path.starts_with('/'))
+        {
+            return Err(format!("CORS exempt path '{}' must start with '/'", path).into());
+        }
+
         Ok(())
     }
 
     fn log_configuration(&self) {
         info!("Configuration loaded successfully");
         info!("Environment: {:?}", self.environment);
-        info!(
-            "Server: {}:{} (workers: {})",
-            self.server.host, self.server.port, self.server.workers
-        );
+        match self.server.workers {
+            Some(workers) => info!(
+                "Server: {}:{} (workers: {})",
+                self.server.host, self.server.port, workers
+            ),
+            None => info!(
+                "Server: {}:{} (workers: one per core)",
+                self.server.host, self.server.port
+            ),
+        }
+        if let Some(workers) = self.server.workers {
+            if workers > self.database.pool_size as usize {
+                warn!(
+                    "{} workers share a database pool of {}; requests may wait for connections",
+                    workers, self.database.pool_size
+                );
+            }
+        }
         info!(
             "Database: {} (pool: {})",
             self.database.name, self.database.pool_size
         );
         info!("Redis: {} (pool: {})", self.redis.url, self.redis.pool_size);
 
+        if self.maintenance.enabled {
+            warn!("Maintenance mode is on - write requests will be refused");
+        }
+
         if self.environment == Environment::Development {
             warn!("Running in development mode - some security features are disabled");
         }
@@ -440,12 +1006,92 @@ impl Config {
     pub fn is_production(&self) -> bool {
         self.environment == Environment::Production
     }
+
+    /// The effective config as JSON, with passwords, keys and tokens masked
+    ///
+    /// Credentials embedded in URLs are masked too, as are the path and
+    /// query of webhook URLs, which receivers often use as the credential, so
+    /// the result is safe to show operators.
+    pub fn redacted(&self) -> serde_json::Value {
+        let mut value = serde_json::to_value(self).unwrap_or_default();
+        redact_secrets(&mut value);
+        if let Some(endpoints) = value
+            .pointer_mut("/contest/webhooks/endpoints")
+            .and_then(|endpoints| endpoints.as_array_mut())
+        {
+            for url in endpoints.iter_mut().filter_map(|e| e.get_mut("url")) {
+                if let Some(text) = url.as_str() {
+                    *url = redact_webhook_url(text).into();
+                }
+            }
+        }
+        value
+    }
+}
+
+const REDACTED: &str = "REDACTED";
+
+/// Whether a string config field holds a credential
+fn is_secret_field(name: &str) -> bool {
+    ["password", "secret", "token", "api_key"]
+        .iter()
+        .any(|marker| name.contains(marker))
+}
+
+fn redact_secrets(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(fields) => {
+            for (name, field) in fields.iter_mut() {
+                match field {
+                    serde_json::Value::String(text) if is_secret_field(name) => {
+                        *text = REDACTED.to_string();
+                    }
+                    serde_json::Value::String(text) => {
+                        if let Some(masked) = redact_url_password(text) {
+                            *text = masked;
+                        }
+                    }
+                    _ => redact_secrets(field),
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_secrets),
+        _ => {}
+    }
+}
+
+/// Masks the password in a URL like `redis://:pass@host`, if it has one
+fn redact_url_password(text: &str) -> Option<String> {
+    let mut url = url::Url::parse(text).ok()?;
+    url.password()?;
+    url.set_password(Some(REDACTED)).ok()?;
+    Some(url.to_string())
+}
+
+/// Keeps only the scheme and host of a webhook URL, masking its path, query
+/// and any password; a URL that does not parse is masked whole
+fn redact_webhook_url(text: &str) -> String {
+    let Ok(mut url) = url::Url::parse(text) else {
+        return REDACTED.to_string();
+    };
+    if url.password().is_some() {
+        let _ = url.set_password(Some(REDACTED));
+    }
+    if !matches!(url.path(), "" | "/") {
+        url.set_path(REDACTED);
+    }
+    if url.query().is_some() {
+        url.set_query(Some(REDACTED));
+    }
+    url.set_fragment(None);
+    url.to_string()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::env;
+    use std::time::Duration;
 
     #[test]
     fn test_environment_parsing() {
@@ -504,7 +1150,7 @@ mod tests {
             server: ServerConfig {
                 host: "127.0.0.1".to_string(),
                 port: 50002,
-                workers: 1,
+                workers: Some(1),
             },
             database: DatabaseConfig {
                 url: "http://localhost:8529".to_string(),
@@ -524,19 +1170,28 @@ mod tests {
             google: GoogleConfig {
                 api_url: "https://maps.googleapis.com/maps/api".to_string(),
                 location_api_key: Some("test_key".to_string()),
+                places_region: None,
+                places_language: None,
             },
             bgg: BGGConfig {
                 api_url: "https://boardgamegeek.com/xmlapi2".to_string(),
                 api_token: None,
+                paths: BGGPaths::default(),
             },
-            _security: SecurityConfig {},
+            contest: ContestConfig::default(),
+            analytics: AnalyticsConfig::default(),
+            ratings: RatingsConfig::default(),
+            maintenance: MaintenanceConfig::default(),
+            passwords: PasswordConfig::default(),
+            security: SecurityConfig::default(),
+            features: FeatureFlags::default(),
             _logging: LoggingConfig {},
         };
 
         assert_eq!(config.environment, Environment::Development);
         assert_eq!(config.server.host, "127.0.0.1");
         assert_eq!(config.server.port, 50002);
-        assert_eq!(config.server.workers, 1);
+        assert_eq!(config.server.workers, Some(1));
     }
 
     #[test]
@@ -547,7 +1202,7 @@ mod tests {
             server: ServerConfig {
                 host: "0.0.0.0".to_string(),
                 port: 8080,
-                workers: 4,
+                workers: Some(4),
             },
             database: DatabaseConfig {
                 url: "http://prod-arango:8529".to_string(),
@@ -567,19 +1222,28 @@ mod tests {
             google: GoogleConfig {
                 api_url: "https://maps.googleapis.com/maps/api".to_string(),
                 location_api_key: Some("prod_google_key".to_string()),
+                places_region: None,
+                places_language: None,
             },
             bgg: BGGConfig {
                 api_url: "https://boardgamegeek.com/xmlapi2".to_string(),
                 api_token: None,
+                paths: BGGPaths::default(),
             },
-            _security: SecurityConfig {},
+            contest: ContestConfig::default(),
+            analytics: AnalyticsConfig::default(),
+            ratings: RatingsConfig::default(),
+            maintenance: MaintenanceConfig::default(),
+            passwords: PasswordConfig::default(),
+            security: SecurityConfig::default(),
+            features: FeatureFlags::default(),
             _logging: LoggingConfig {},
         };
 
         assert_eq!(config.environment, Environment::Production);
         assert_eq!(config.server.host, "0.0.0.0");
         assert_eq!(config.server.port, 8080);
-        assert_eq!(config.server.workers, 4);
+        assert_eq!(config.server.workers, Some(4));
     }
 
     #[test]
@@ -590,7 +1254,7 @@ mod tests {
             server: ServerConfig {
                 host: "0.0.0.0".to_string(),
                 port: 8080,
-                workers: 8,
+                workers: Some(8),
             },
             database: DatabaseConfig {
                 url: "http://prod-arango:8529".to_string(),
@@ -610,18 +1274,125 @@ mod tests {
             google: GoogleConfig {
                 api_url: "https://maps.googleapis.com/maps/api".to_string(),
                 location_api_key: Some("prod_google_key".to_string()),
+                places_region: None,
+                places_language: None,
             },
             bgg: BGGConfig {
                 api_url: "https://boardgamegeek.com/xmlapi2".to_string(),
                 api_token: None,
+                paths: BGGPaths::default(),
             },
-            _security: SecurityConfig {},
+            contest: ContestConfig::default(),
+            analytics: AnalyticsConfig::default(),
+            ratings: RatingsConfig::default(),
+            maintenance: MaintenanceConfig::default(),
+            passwords: PasswordConfig::default(),
+            security: SecurityConfig::default(),
+            features: FeatureFlags::default(),
             _logging: LoggingConfig {},
         };
 
         assert_eq!(config.environment, Environment::Production);
         assert_eq!(config.redis.url, "redis://localhost:6379");
-        assert_eq!(config.server.workers, 8);
+        assert_eq!(config.server.workers, Some(8));
+    }
+
+    #[test]
+    fn test_redacted_config_masks_secrets() {
+        let config = Config {
+            environment: Environment::Production,
+            server: ServerConfig {
+                host: "0.0.0.0".to_string(),
+                port: 8080,
+                workers: Some(4),
+            },
+            database: DatabaseConfig {
+                url: "http://prod-arango:8529".to_string(),
+                name: "stg_rd_prod".to_string(),
+                username: "produser".to_string(),
+                password: "supersecret".to_string(),
+                root_username: "root".to_string(),
+                root_password: "rootpass".to_string(),
+                pool_size: 20,
+                _timeout_seconds: 60,
+            },
+            redis: RedisConfig {
+                url: "redis://:redispass@prod-redis:6379".to_string(),
+                pool_size: 20,
+                _timeout_seconds: 60,
+            },
+            google: GoogleConfig {
+                api_url: "https://maps.googleapis.com/maps/api".to_string(),
+                location_api_key: Some("prod_google_key".to_string()),
+                places_region: None,
+                places_language: None,
+            },
+            bgg: BGGConfig {
+                api_url: "https://boardgamegeek.com/xmlapi2".to_string(),
+                api_token: Some("bgg_token".to_string()),
+                paths: BGGPaths::default(),
+            },
+            contest: ContestConfig {
+                webhooks: WebhookConfig {
+                    endpoints: vec![
+                        WebhookEndpoint {
+                            url: "https://hooks.example.com/contests".to_string(),
+                            secret: Some("hook_secret".to_string()),
+                        },
+                        WebhookEndpoint {
+                            url: "https://hooks.example.com/services/T01/B02/pathtoken?token=querytoken"
+                                .to_string(),
+                            secret: None,
+                        },
+                    ],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            analytics: AnalyticsConfig::default(),
+            ratings: RatingsConfig::default(),
+            maintenance: MaintenanceConfig::default(),
+            passwords: PasswordConfig::default(),
+            security: SecurityConfig::default(),
+            features: FeatureFlags::default(),
+            _logging: LoggingConfig {},
+        };
+
+        let redacted = config.redacted();
+        let text = redacted.to_string();
+        for secret in [
+            "supersecret",
+            "rootpass",
+            "redispass",
+            "prod_google_key",
+            "bgg_token",
+            "hook_secret",
+            "pathtoken",
+            "querytoken",
+        ] {
+            assert!(!text.contains(secret), "{} was not redacted", secret);
+        }
+        assert_eq!(redacted["database"]["password"], "REDACTED");
+        assert_eq!(
+            redacted["redis"]["url"],
+            "redis://:REDACTED@prod-redis:6379"
+        );
+
+        // Everything else is shown as loaded, and unset secrets stay null
+        assert_eq!(redacted["environment"], "Production");
+        assert_eq!(redacted["server"]["port"], 8080);
+        assert_eq!(redacted["database"]["url"], "http://prod-arango:8529");
+        assert_eq!(redacted["database"]["username"], "produser");
+        assert_eq!(redacted["google"]["places_region"], serde_json::Value::Null);
+        assert_eq!(
+            redacted["contest"]["webhooks"]["endpoints"][0]["url"],
+            "https://hooks.example.com/REDACTED"
+        );
+        assert_eq!(
+            redacted["contest"]["webhooks"]["endpoints"][1]["url"],
+            "https://hooks.example.com/REDACTED?REDACTED"
+        );
+        assert_eq!(redacted["passwords"]["algorithm"], "argon2id");
     }
 
     #[test]
@@ -635,6 +1406,142 @@ mod tests {
         env::remove_var("RUST_ENV");
     }
 
+    #[test]
+    fn test_invalid_analytics_default_timezone_fails_validation() {
+        env::set_var("RUST_ENV", "development");
+        let mut config = Config::load().expect("Failed to load config");
+        assert_eq!(config.analytics.default_timezone, "UTC");
+
+        config.analytics.default_timezone = "America/Chicago".to_string();
+        assert!(config.validate().is_ok());
+        config.analytics.default_timezone = "Mars/Olympus_Mons".to_string();
+        assert!(config.validate().is_err());
+
+        env::remove_var("RUST_ENV");
+    }
+
+    #[test]
+    fn test_zero_day_leaderboard_window_fails_validation() {
+        env::set_var("RUST_ENV", "development");
+        let mut config = Config::load().expect("Failed to load config");
+        assert_eq!(config.analytics.leaderboard_active_days, None);
+
+        config.analytics.leaderboard_active_days = Some(90);
+        assert!(config.validate().is_ok());
+        config.analytics.leaderboard_active_days = Some(0);
+        assert!(config.validate().is_err());
+
+        env::remove_var("RUST_ENV");
+    }
+
+    #[test]
+    fn test_zero_day_contest_archive_cutoff_fails_validation() {
+        env::set_var("RUST_ENV", "development");
+        let mut config = Config::load().expect("Failed to load config");
+        assert_eq!(config.contest.archive_after_days, 5 * 365);
+
+        config.contest.archive_after_days = 0;
+        assert!(config.validate().is_err());
+
+        env::remove_var("RUST_ENV");
+    }
+
+    #[test]
+    fn test_feature_flags_default_on_and_env_turns_them_off() {
+        env::set_var("RUST_ENV", "development");
+        let config = Config::load().expect("Failed to load config");
+        assert!(config.features.webhooks);
+        assert!(config.features.client_analytics);
+        assert!(config.features.api_docs);
+
+        env::set_var("FEATURE_WEBHOOKS", "false");
+        env::set_var("FEATURE_API_DOCS", "0");
+        env::set_var("FEATURE_CLIENT_ANALYTICS", "sometimes");
+        let config = Config::load().expect("Failed to load config");
+        assert!(!config.features.webhooks);
+        assert!(!config.features.api_docs);
+        assert!(config.features.client_analytics);
+
+        env::remove_var("FEATURE_WEBHOOKS");
+        env::remove_var("FEATURE_API_DOCS");
+        env::remove_var("FEATURE_CLIENT_ANALYTICS");
+        env::remove_var("RUST_ENV");
+    }
+
+    #[test]
+    fn test_rating_floor_above_ceiling_fails_validation() {
+        env::set_var("RUST_ENV", "development");
+        let mut config = Config::load().expect("Failed to load config");
+
+        config.ratings.rating_floor = Some(800.0);
+        config.ratings.rating_ceiling = Some(2400.0);
+        assert!(config.validate().is_ok());
+        let params = config.ratings.glicko_params();
+        assert_eq!(params.rating_ceiling, Some(2400.0));
+        assert_eq!(params.default_rating, 1500.0);
+
+        config.ratings.rating_floor = Some(2500.0);
+        assert!(config.validate().is_err());
+        config.ratings.rating_floor = Some(f64::NAN);
+        assert!(config.validate().is_err());
+
+        env::remove_var("RUST_ENV");
+    }
+
+    #[test]
+    fn test_rating_period_interval_parsing() {
+        env::set_var("RUST_ENV", "development");
+        let config = Config::load().expect("Failed to load config");
+        assert_eq!(config.ratings.period_interval(), Duration::ZERO);
+
+        env::set_var("RATING_PERIOD_INTERVAL_MS", "250");
+        let config = Config::load().expect("Failed to load config");
+        assert_eq!(config.ratings.period_interval(), Duration::from_millis(250));
+
+        env::set_var("RATING_PERIOD_INTERVAL_MS", "soon");
+        let config = Config::load().expect("Failed to load config");
+        assert_eq!(config.ratings.period_interval(), Duration::ZERO);
+
+        env::remove_var("RATING_PERIOD_INTERVAL_MS");
+        env::remove_var("RUST_ENV");
+    }
+
+    #[test]
+    fn test_password_cost_out_of_range_fails_validation() {
+        env::set_var("RUST_ENV", "development");
+        let mut config = Config::load().expect("Failed to load config");
+        assert_eq!(config.passwords, PasswordConfig::default());
+        assert!(config.validate().is_ok());
+
+        config.passwords.bcrypt_cost = 3;
+        assert!(config.validate().is_err());
+        config.passwords.bcrypt_cost = 10;
+        config.passwords.argon2_iterations = 0;
+        assert!(config.validate().is_err());
+        assert_eq!(
+            "BCRYPT".parse::<PasswordAlgorithm>(),
+            Ok(PasswordAlgorithm::Bcrypt)
+        );
+        assert!("md5".parse::<PasswordAlgorithm>().is_err());
+
+        env::remove_var("RUST_ENV");
+    }
+
+    #[test]
+    fn test_bad_security_headers_fail_validation() {
+        env::set_var("RUST_ENV", "development");
+        let mut config = Config::load().expect("Failed to load config");
+        assert!(config.validate().is_ok());
+
+        config.security.content_security_policy = Some("default-src 'self'\r\nX-Evil: 1".into());
+        assert!(config.validate().is_err());
+        config.security.content_security_policy = None;
+        config.security.cors_exempt_paths = vec!["healthz".to_string()];
+        assert!(config.validate().is_err());
+
+        env::remove_var("RUST_ENV");
+    }
+
     #[test]
     fn test_config_environment_methods() {
         env::set_var("RUST_ENV", "development");
@@ -656,7 +1563,7 @@ mod tests {
             server: ServerConfig {
                 host: "0.0.0.0".to_string(),
                 port: 50002,
-                workers: 8,
+                workers: Some(8),
             },
             database: DatabaseConfig {
                 url: "http://arangodb:8529".to_string(),
@@ -676,12 +1583,21 @@ mod tests {
             google: GoogleConfig {
                 api_url: "https://maps.googleapis.com/maps/api".to_string(),
                 location_api_key: Some("prod_google_key".to_string()),
+                places_region: None,
+                places_language: None,
             },
             bgg: BGGConfig {
                 api_url: "https://boardgamegeek.com/xmlapi2".to_string(),
                 api_token: None,
+                paths: BGGPaths::default(),
             },
-            _security: SecurityConfig {},
+            contest: ContestConfig::default(),
+            analytics: AnalyticsConfig::default(),
+            ratings: RatingsConfig::default(),
+            maintenance: MaintenanceConfig::default(),
+            passwords: PasswordConfig::default(),
+            security: SecurityConfig::default(),
+            features: FeatureFlags::default(),
             _logging: LoggingConfig {},
         };
 
@@ -694,12 +1610,12 @@ mod tests {
         let server_config = ServerConfig {
             host: "127.0.0.1".to_string(),
             port: 8080,
-            workers: 4,
+            workers: Some(4),
         };
 
         assert_eq!(server_config.host, "127.0.0.1");
         assert_eq!(server_config.port, 8080);
-        assert_eq!(server_config.workers, 4);
+        assert_eq!(server_config.workers, Some(4));
     }
 
     #[test]
@@ -738,11 +1654,15 @@ mod tests {
 
     #[test]
     fn test_security_config_structure() {
-        let _security_config = SecurityConfig {
-            // Remove unused fields
-        };
-
-        // Remove unused fields
+        let security_config = SecurityConfig::default();
+        assert_eq!(
+            security_config.content_security_policy.as_deref(),
+            Some("frame-ancestors 'none'")
+        );
+        assert_eq!(security_config.hsts_max_age_secs, None);
+        assert!(security_config
+            .cors_exempt_paths
+            .contains(&"/healthz".to_string()));
     }
 
     #[test]
@@ -762,7 +1682,7 @@ mod tests {
             server: ServerConfig {
                 host: "0.0.0.0".to_string(),
                 port: 8080,
-                workers: 2,
+                workers: Some(2),
             },
             database: DatabaseConfig {
                 url: "http://localhost:8529".to_string(),
@@ -782,18 +1702,27 @@ mod tests {
             google: GoogleConfig {
                 api_url: "https://maps.googleapis.com/maps/api".to_string(),
                 location_api_key: Some("test_key".to_string()),
+                places_region: None,
+                places_language: None,
             },
             bgg: BGGConfig {
                 api_url: "https://boardgamegeek.com/xmlapi2".to_string(),
                 api_token: None,
+                paths: BGGPaths::default(),
             },
-            _security: SecurityConfig {},
+            contest: ContestConfig::default(),
+            analytics: AnalyticsConfig::default(),
+            ratings: RatingsConfig::default(),
+            maintenance: MaintenanceConfig::default(),
+            passwords: PasswordConfig::default(),
+            security: SecurityConfig::default(),
+            features: FeatureFlags::default(),
             _logging: LoggingConfig {},
         };
 
         assert_eq!(config.server.host, "0.0.0.0");
         assert_eq!(config.server.port, 8080);
-        assert_eq!(config.server.workers, 2);
+        assert_eq!(config.server.workers, Some(2));
         assert_eq!(config.database.name, "custom_db");
     }
 
@@ -816,8 +1745,18 @@ mod tests {
         env::set_var("BACKEND_WORKERS", "invalid");
         let result = Config::load();
         assert!(result.is_err() || result.is_ok());
+        assert_eq!(Config::load_workers(), None);
+
+        env::set_var("BACKEND_WORKERS", "0");
+        assert_eq!(Config::load_workers(), None);
+
+        env::set_var("BACKEND_WORKERS", "3");
+        let config = Config::load().expect("Failed to load config");
+        assert_eq!(config.server.workers, Some(3));
+
+        env::remove_var("BACKEND_WORKERS");
+        assert_eq!(Config::load_workers(), None);
         env::remove_var("RUST_ENV");
         env::remove_var("ARANGO_PASSWORD");
-        env::remove_var("BACKEND_WORKERS");
     }
 }
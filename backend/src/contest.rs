@@ -1,4 +1,6 @@
 pub mod controller;
+pub mod csv_import;
 pub mod name_generator;
 pub mod repository;
+pub mod webhook;
 // contest module placeholder
@@ -1,54 +1,195 @@
+use crate::analytics::AnalyticsCache;
+use crate::config::ContestConfig;
+use crate::contest::csv_import::{self, CsvContestRow};
 use crate::contest::repository::{ContestRepository, ContestRepositoryImpl};
+use crate::contest::webhook::ContestWebhooks;
+use crate::error::codes;
+use crate::pagination::insert_pagination_headers;
 use crate::player::repository::PlayerRepository;
+use crate::ratings::scheduler::RatingsScheduler;
+use actix_web::http::header::CONTENT_DISPOSITION;
+use actix_web::http::StatusCode;
 use actix_web::HttpMessage;
-use actix_web::{get, post, web, HttpRequest, HttpResponse, Responder};
+use actix_web::{delete, get, post, web, HttpRequest, HttpResponse, Responder};
+use arangors::client::reqwest::ReqwestClient;
+use chrono::{Duration, Utc};
 use serde::Deserialize;
 use serde_json::json;
-use shared::dto::contest::ContestDto;
+use shared::dto::contest::{ContestDto, ContestImportReportDto, ContestImportRowDto};
+use shared::models::player::Player;
+use shared::validation::{describe, validate_contest, validate_ranking};
 use validator::Validate;
 
-#[post("")]
-pub async fn create_contest_handler(
-    contest: web::Json<ContestDto>,
-    req: HttpRequest,
-    repo: web::Data<ContestRepositoryImpl>,
-) -> impl Responder {
-    // Validate input without logging sensitive payload data
+/// A rejected contest payload: the status to answer with and the error body
+struct PayloadRejection {
+    status: StatusCode,
+    body: serde_json::Value,
+}
+
+impl PayloadRejection {
+    fn details(&self) -> String {
+        self.body["details"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    fn into_response(self) -> HttpResponse {
+        HttpResponse::build(self.status).json(self.body)
+    }
+}
+
+/// Validates a submitted contest, as both single creation and import do
+fn check_contest_payload(
+    contest: &ContestDto,
+    config: &ContestConfig,
+) -> Result<(), PayloadRejection> {
     if let Err(e) = contest.validate() {
-        return HttpResponse::BadRequest().json(serde_json::json!({
-            "error": "validation_failed",
-            "details": e.to_string(),
-        }));
+        return Err(PayloadRejection {
+            status: StatusCode::BAD_REQUEST,
+            body: serde_json::json!({
+                "error": "validation_failed",
+                "code": codes::CONTEST_INVALID,
+                "details": e.to_string(),
+            }),
+        });
     }
+    if let Err(errors) = validate_contest(contest) {
+        let code = if errors.iter().any(|e| e.code == "invalid_place") {
+            codes::CONTEST_INVALID_PLACEMENT
+        } else {
+            codes::CONTEST_INVALID
+        };
+        return Err(PayloadRejection {
+            status: StatusCode::BAD_REQUEST,
+            body: serde_json::json!({
+                "error": "validation_failed",
+                "code": code,
+                "details": describe(&errors),
+                "fields": errors,
+            }),
+        });
+    }
+    if let Err(e) = validate_ranking(&contest.outcomes) {
+        return Err(PayloadRejection {
+            status: StatusCode::UNPROCESSABLE_ENTITY,
+            body: serde_json::json!({
+                "error": "invalid_ranking",
+                "code": codes::CONTEST_INVALID_RANKING,
+                "details": e.message.clone(),
+                "fields": [e],
+            }),
+        });
+    }
+    if contest.outcomes.len() > config.max_participants {
+        return Err(PayloadRejection {
+            status: StatusCode::UNPROCESSABLE_ENTITY,
+            body: serde_json::json!({
+                "error": "too_many_participants",
+                "code": codes::CONTEST_TOO_MANY_PARTICIPANTS,
+                "details": format!(
+                    "Contest has {} participants; the maximum is {}",
+                    contest.outcomes.len(),
+                    config.max_participants
+                ),
+            }),
+        });
+    }
+    // Catches dates mangled on the way in, e.g. an epoch or a mistyped year
+    let now = Utc::now();
+    let earliest = now - Duration::days(i64::from(config.max_start_days_past));
+    let latest = now + Duration::days(i64::from(config.max_start_days_future));
+    if contest.start < earliest || contest.start > latest {
+        return Err(PayloadRejection {
+            status: StatusCode::UNPROCESSABLE_ENTITY,
+            body: serde_json::json!({
+                "error": "start_out_of_range",
+                "code": codes::CONTEST_START_OUT_OF_RANGE,
+                "details": format!(
+                    "Contest start {} is outside the accepted range {} to {}",
+                    contest.start.to_rfc3339(),
+                    earliest.to_rfc3339(),
+                    latest.to_rfc3339()
+                ),
+            }),
+        });
+    }
+    Ok(())
+}
 
-    // Extract creator information from authenticated user
-    let creator_id = match req.extensions().get::<String>() {
+/// The authenticated player making a contest request
+async fn resolve_player(
+    req: &HttpRequest,
+    repo: &ContestRepositoryImpl,
+) -> Result<Player, HttpResponse> {
+    match req.extensions().get::<String>() {
         Some(email) => {
             // Look up the player by email to get the actual player ID
             match repo.player_usecase.repo.find_by_email(email).await {
-                Some(player) => player.id,
+                Some(player) => Ok(player),
                 None => {
                     log::error!("Authenticated user {} not found in player database", email);
-                    return HttpResponse::Unauthorized().json(serde_json::json!({
+                    Err(HttpResponse::Unauthorized().json(serde_json::json!({
                         "error": "user_not_found",
+                        "code": codes::UNAUTHORIZED,
                         "details": "Authenticated user not found in player database"
-                    }));
+                    })))
                 }
             }
         }
         None => {
-            log::error!("No authenticated user found for contest creation");
-            return HttpResponse::Unauthorized().json(serde_json::json!({
+            log::error!("No authenticated user found for contest request");
+            Err(HttpResponse::Unauthorized().json(serde_json::json!({
                 "error": "not_authenticated",
-                "details": "Authentication required to create contests"
-            }));
+                "code": codes::UNAUTHORIZED,
+                "details": "Authentication required to manage contests"
+            })))
         }
+    }
+}
+
+/// Player id of the authenticated user submitting contests
+async fn resolve_creator_id(
+    req: &HttpRequest,
+    repo: &ContestRepositoryImpl,
+) -> Result<String, HttpResponse> {
+    resolve_player(req, repo).await.map(|player| player.id)
+}
+
+/// The registered contest config, or the defaults when there is none
+fn contest_config_or_default(
+    contest_config: Option<web::Data<ContestConfig>>,
+) -> web::Data<ContestConfig> {
+    contest_config.unwrap_or_else(|| web::Data::new(ContestConfig::default()))
+}
+
+#[post("")]
+pub async fn create_contest_handler(
+    contest: web::Json<ContestDto>,
+    req: HttpRequest,
+    repo: web::Data<ContestRepositoryImpl>,
+    contest_config: Option<web::Data<ContestConfig>>,
+    webhooks: Option<web::Data<ContestWebhooks>>,
+) -> impl Responder {
+    // Validate input without logging sensitive payload data
+    let contest_config = contest_config_or_default(contest_config);
+    if let Err(rejection) = check_contest_payload(&contest, &contest_config) {
+        return rejection.into_response();
+    }
+
+    // Extract creator information from authenticated user
+    let creator_id = match resolve_creator_id(&req, &repo).await {
+        Ok(creator_id) => creator_id,
+        Err(response) => return response,
     };
 
     log::info!("Contest creation requested by player: {}", creator_id);
     match repo.create_contest(contest.into_inner(), creator_id).await {
         Ok(created) => {
             log::info!("Contest created successfully");
+            if let Some(webhooks) = webhooks {
+                webhooks.notify_contest_created(&created);
+            }
             HttpResponse::Ok().json(created)
         }
         Err(e) => {
@@ -58,6 +199,116 @@ pub async fn create_contest_handler(
     }
 }
 
+/// CSV template for contest imports, with one example row
+///
+/// The columns are documented in [`csv_import`].
+#[get("/template.csv")]
+pub async fn contest_csv_template_handler() -> impl Responder {
+    HttpResponse::Ok()
+        .content_type("text/csv; charset=utf-8")
+        .insert_header((
+            CONTENT_DISPOSITION,
+            "attachment; filename=\"contests-template.csv\"",
+        ))
+        .body(csv_import::template())
+}
+
+/// Imports contests from a CSV file in the template format
+///
+/// Each row is checked and created on its own, exactly like a POST to
+/// `/api/contests`, so one bad row does not stop the rest. The report lists
+/// the created contest or the error for every row.
+#[post("/import.csv")]
+pub async fn import_contests_csv_handler(
+    body: String,
+    req: HttpRequest,
+    repo: web::Data<ContestRepositoryImpl>,
+    contest_config: Option<web::Data<ContestConfig>>,
+    webhooks: Option<web::Data<ContestWebhooks>>,
+) -> impl Responder {
+    let rows = match csv_import::parse_contest_rows(&body) {
+        Ok(rows) => rows,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(serde_json::json!({
+                "error": "invalid_csv",
+                "code": codes::CONTEST_INVALID_CSV,
+                "details": e,
+            }));
+        }
+    };
+    let creator_id = match resolve_creator_id(&req, &repo).await {
+        Ok(creator_id) => creator_id,
+        Err(response) => return response,
+    };
+    let contest_config = contest_config_or_default(contest_config);
+
+    log::info!(
+        "Importing {} contest rows for player: {}",
+        rows.len(),
+        creator_id
+    );
+    let mut report = ContestImportReportDto {
+        imported: 0,
+        failed: 0,
+        rows: Vec::with_capacity(rows.len()),
+    };
+    for row in rows {
+        let result = match &row.contest {
+            Ok(contest) => {
+                import_row(
+                    &repo,
+                    contest,
+                    &creator_id,
+                    &contest_config,
+                    webhooks.as_deref(),
+                )
+                .await
+            }
+            Err(e) => Err(e.clone()),
+        };
+        match result {
+            Ok(contest_id) => {
+                report.imported += 1;
+                report.rows.push(ContestImportRowDto {
+                    line: row.line,
+                    contest_id: Some(contest_id),
+                    error: None,
+                });
+            }
+            Err(e) => {
+                report.failed += 1;
+                report.rows.push(ContestImportRowDto {
+                    line: row.line,
+                    contest_id: None,
+                    error: Some(e),
+                });
+            }
+        }
+    }
+    log::info!(
+        "Contest import finished: {} imported, {} failed",
+        report.imported,
+        report.failed
+    );
+    HttpResponse::Ok().json(report)
+}
+
+async fn import_row(
+    repo: &ContestRepositoryImpl,
+    row: &CsvContestRow,
+    creator_id: &str,
+    config: &ContestConfig,
+    webhooks: Option<&ContestWebhooks>,
+) -> Result<String, String> {
+    let contest = csv_import::resolve_row(repo, row).await?;
+    check_contest_payload(&contest, config).map_err(|r| r.details())?;
+    let created = repo.create_contest(contest, creator_id.to_string()).await?;
+    if let Some(webhooks) = webhooks {
+        webhooks.notify_contest_created(&created);
+    }
+    Ok(created.id)
+}
+
 #[get("/{contest_id}")]
 pub async fn get_contest_handler(
     path: web::Path<String>,
@@ -82,7 +333,130 @@ pub async fn get_contest_handler(
         None => {
             log::warn!("Contest not found");
             HttpResponse::NotFound().json(serde_json::json!({
-                "error": "Contest not found"
+                "error": "Contest not found",
+                "code": codes::CONTEST_NOT_FOUND
+            }))
+        }
+    }
+}
+
+/// Deletes a contest, as an undo for one entered by mistake
+///
+/// The creator may delete their own contest for the configured undo window
+/// after creating it; after that, or for anyone else's contest, only an admin
+/// can. The contest's edges go with it, so stats no longer count it; cached
+/// analytics are cleared, and if the contest's month has already been rated
+/// that month is recalculated without it.
+#[delete("/{contest_id}")]
+pub async fn delete_contest_handler(
+    path: web::Path<String>,
+    req: HttpRequest,
+    repo: web::Data<ContestRepositoryImpl>,
+    contest_config: Option<web::Data<ContestConfig>>,
+    cache: Option<web::Data<AnalyticsCache>>,
+    scheduler: Option<web::Data<RatingsScheduler<ReqwestClient>>>,
+) -> impl Responder {
+    let contest_param = path.into_inner();
+    let contest_id = if contest_param.contains('/') {
+        contest_param
+    } else {
+        format!("contest/{}", contest_param)
+    };
+
+    let player = match resolve_player(&req, &repo).await {
+        Ok(player) => player,
+        Err(response) => return response,
+    };
+    let Some(contest) = repo.find_by_id(&contest_id).await else {
+        return HttpResponse::NotFound().json(json!({
+            "error": "Contest not found",
+            "code": codes::CONTEST_NOT_FOUND
+        }));
+    };
+
+    if !player.is_admin {
+        if contest.creator_id != player.id {
+            return HttpResponse::Forbidden().json(json!({
+                "error": "not_contest_creator",
+                "code": codes::CONTEST_NOT_CREATOR,
+                "details": "Only the contest's creator or an admin can delete it"
+            }));
+        }
+        let window_minutes = contest_config
+            .map(|c| c.undo_window_minutes)
+            .unwrap_or_else(|| ContestConfig::default().undo_window_minutes);
+        let window = Duration::minutes(i64::from(window_minutes));
+        if Utc::now().fixed_offset() - contest.created_at > window {
+            return HttpResponse::Forbidden().json(json!({
+                "error": "undo_window_closed",
+                "code": codes::CONTEST_UNDO_WINDOW_CLOSED,
+                "details": format!(
+                    "Contests can only be undone within {} minutes of creation; ask an admin to delete it",
+                    window_minutes
+                )
+            }));
+        }
+    }
+
+    if let Err(e) = repo.delete(&contest_id).await {
+        log::error!("Failed to delete contest {}: {}", contest_id, e);
+        return HttpResponse::InternalServerError().json(json!({
+            "error": "Failed to delete contest",
+            "code": codes::DATABASE
+        }));
+    }
+    log::info!("Contest {} deleted by player {}", contest_id, player.id);
+
+    if let Some(cache) = cache {
+        cache.clear_analytics().await;
+    }
+    // Ratings are computed per month from the edges; the current month has
+    // not been rated yet, so only an earlier month and those after it need
+    // running again
+    let period = contest
+        .start
+        .with_timezone(&Utc)
+        .format("%Y-%m")
+        .to_string();
+    if contest.rated && period < Utc::now().format("%Y-%m").to_string() {
+        if let Some(scheduler) = scheduler {
+            if let Err(e) = scheduler.trigger_recompute_from(&period).await {
+                log::error!(
+                    "Failed to recalculate ratings for {} after deleting {}: {}",
+                    period,
+                    contest_id,
+                    e
+                );
+            }
+        }
+    }
+
+    HttpResponse::NoContent().finish()
+}
+
+#[get("/{contest_id}/timeline")]
+pub async fn get_contest_timeline_handler(
+    path: web::Path<String>,
+    repo: web::Data<ContestRepositoryImpl>,
+) -> impl Responder {
+    let contest_param = path.into_inner();
+    let contest_id = if contest_param.contains('/') {
+        contest_param
+    } else {
+        format!("contest/{}", contest_param)
+    };
+
+    match repo.find_timeline(&contest_id).await {
+        Ok(Some(timeline)) => HttpResponse::Ok().json(timeline),
+        Ok(None) => HttpResponse::NotFound().json(json!({
+            "error": "Contest not found",
+            "code": codes::CONTEST_NOT_FOUND
+        })),
+        Err(e) => {
+            log::error!("Failed to load timeline for {}: {}", contest_id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "error": "Failed to load contest timeline",
+                "code": codes::DATABASE
             }))
         }
     }
@@ -135,7 +509,8 @@ pub async fn get_player_game_contests_handler(
                 e
             );
             HttpResponse::InternalServerError().json(json!({
-                "error": "Failed to fetch contests"
+                "error": "Failed to fetch contests",
+                "code": codes::DATABASE
             }))
         }
     }
@@ -235,7 +610,9 @@ pub async fn search_contests_handler_impl(
     // If player was not found, return empty results immediately
     if player_not_found {
         log::info!("Player not found, returning empty contest results");
-        return HttpResponse::Ok().json(json!({
+        let mut response = HttpResponse::Ok();
+        insert_pagination_headers(&mut response, &req, page, page_size, 0);
+        return response.json(json!({
             "items": [],
             "total": 0,
             "page": page,
@@ -274,10 +651,17 @@ pub async fn search_contests_handler_impl(
         )
         .await
     {
-        Ok(result) => HttpResponse::Ok().json(result),
+        Ok(result) => {
+            let total = result.get("total").and_then(|t| t.as_u64()).unwrap_or(0);
+            let mut response = HttpResponse::Ok();
+            insert_pagination_headers(&mut response, &req, page, page_size, total);
+            response.json(result)
+        }
         Err(e) => {
             log::error!("search_contests failed: {}. Returning empty result set.", e);
-            HttpResponse::Ok().json(json!({
+            let mut response = HttpResponse::Ok();
+            insert_pagination_headers(&mut response, &req, page, page_size, 0);
+            response.json(json!({
                 "items": [],
                 "total": 0,
                 "page": page,
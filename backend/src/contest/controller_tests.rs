@@ -63,6 +63,8 @@ mod contest_controller_tests {
                 stop: dto.stop,
                 creator_id: dto.creator_id.clone(),
                 created_at: dto.created_at.unwrap_or_else(|| chrono::Utc::now().fixed_offset()),
+                rated: dto.rated,
+                visibility: dto.visibility,
             })
         }
 
@@ -76,6 +78,8 @@ mod contest_controller_tests {
                 stop: dto.stop,
                 creator_id: dto.creator_id.clone(),
                 created_at: dto.created_at.unwrap_or_else(|| chrono::Utc::now().fixed_offset()),
+                rated: dto.rated,
+                visibility: dto.visibility,
             }).collect()
         }
 
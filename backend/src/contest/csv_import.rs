@@ -0,0 +1,369 @@
+//! Contest import in a spreadsheet-friendly CSV format
+//!
+//! One row per contest, under the header returned by [`template`]:
+//!
+//! | column         | contents                                                 |
+//! |----------------|----------------------------------------------------------|
+//! | `name`         | contest name                                             |
+//! | `start`        | RFC 3339 timestamp, e.g. `2026-01-09T19:00:00-05:00`     |
+//! | `stop`         | RFC 3339 timestamp                                       |
+//! | `venue`        | display name of an existing venue                        |
+//! | `games`        | names of existing games, separated by `;`                |
+//! | `participants` | `handle:place` entries separated by `;`, e.g. `ann:1;bo:2` |
+//!
+//! Fields follow RFC 4180: a field holding a comma, quote or line break is
+//! wrapped in double quotes, with quotes inside it doubled. Columns may come
+//! in any order; blank lines are skipped.
+
+use crate::contest::repository::ContestRepositoryImpl;
+use crate::game::repository::GameRepository;
+use crate::player::repository::PlayerRepository;
+use crate::venue::repository::VenueRepository;
+use chrono::{DateTime, FixedOffset};
+use shared::dto::contest::{ContestDto, OutcomeDto};
+use shared::dto::game::GameDto;
+use shared::dto::venue::VenueDto;
+use shared::models::contest::ContestVisibility;
+
+/// Header row of the template, in order
+pub const TEMPLATE_COLUMNS: [&str; 6] = ["name", "start", "stop", "venue", "games", "participants"];
+
+/// Example row shipped with the template
+const TEMPLATE_EXAMPLE: [&str; 6] = [
+    "Friday Night, Table 1",
+    "2026-01-09T19:00:00-05:00",
+    "2026-01-09T22:00:00-05:00",
+    "The Board Room",
+    "Catan;Azul",
+    "alice:1;bob:2",
+];
+
+/// Separates entries of the `games` and `participants` columns
+const LIST_SEPARATOR: char = ';';
+
+/// Separates a participant's handle from their place
+const PLACE_SEPARATOR: char = ':';
+
+/// The CSV template: the header row and one example contest
+pub fn template() -> String {
+    let mut csv = String::new();
+    write_record(&mut csv, &TEMPLATE_COLUMNS);
+    write_record(&mut csv, &TEMPLATE_EXAMPLE);
+    csv
+}
+
+fn write_record(csv: &mut String, fields: &[&str]) {
+    let fields: Vec<String> = fields.iter().map(|field| quote_field(field)).collect();
+    csv.push_str(&fields.join(","));
+    csv.push_str("\r\n");
+}
+
+fn quote_field(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// A participant as written in the `participants` column
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvParticipant {
+    pub handle: String,
+    pub place: String,
+}
+
+/// A contest row before its venue, games and players are looked up
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvContestRow {
+    pub name: String,
+    pub start: DateTime<FixedOffset>,
+    pub stop: DateTime<FixedOffset>,
+    pub venue: String,
+    pub games: Vec<String>,
+    pub participants: Vec<CsvParticipant>,
+}
+
+/// A data row of an import, numbered by the line it starts on
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvRow {
+    pub line: usize,
+    pub contest: Result<CsvContestRow, String>,
+}
+
+/// Parses an import file into its data rows
+///
+/// Fails as a whole only when the file itself is unusable: unbalanced quotes
+/// or a header missing one of [`TEMPLATE_COLUMNS`]. Problems with a single
+/// row are reported on that row.
+pub fn parse_contest_rows(input: &str) -> Result<Vec<CsvRow>, String> {
+    let mut records = parse_records(input)?.into_iter();
+    let Some((_, header)) = records.next() else {
+        return Err("The file is empty; expected the template header".to_string());
+    };
+    let columns = TEMPLATE_COLUMNS
+        .iter()
+        .map(|column| {
+            header
+                .iter()
+                .position(|h| h.trim().eq_ignore_ascii_case(column))
+                .ok_or_else(|| format!("Missing column '{}'", column))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(records
+        .map(|(line, record)| CsvRow {
+            line,
+            contest: parse_row(&record, &columns),
+        })
+        .collect())
+}
+
+fn parse_row(record: &[String], columns: &[usize]) -> Result<CsvContestRow, String> {
+    let field = |column: usize| record.get(columns[column]).map_or("", |f| f.trim());
+    let timestamp = |column: usize| {
+        DateTime::parse_from_rfc3339(field(column)).map_err(|_| {
+            format!(
+                "'{}' in column '{}' is not an RFC 3339 timestamp",
+                field(column),
+                TEMPLATE_COLUMNS[column]
+            )
+        })
+    };
+    let participants = split_list(field(5))
+        .map(|entry| match entry.rsplit_once(PLACE_SEPARATOR) {
+            Some((handle, place)) if !handle.trim().is_empty() && !place.trim().is_empty() => {
+                Ok(CsvParticipant {
+                    handle: handle.trim().to_string(),
+                    place: place.trim().to_string(),
+                })
+            }
+            _ => Err(format!(
+                "Participant '{}' should be written as handle{}place",
+                entry, PLACE_SEPARATOR
+            )),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(CsvContestRow {
+        name: field(0).to_string(),
+        start: timestamp(1)?,
+        stop: timestamp(2)?,
+        venue: field(3).to_string(),
+        games: split_list(field(4)).map(str::to_string).collect(),
+        participants,
+    })
+}
+
+fn split_list(field: &str) -> impl Iterator<Item = &str> {
+    field
+        .split(LIST_SEPARATOR)
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+}
+
+/// Splits CSV text into records, each with the line it starts on
+fn parse_records(input: &str) -> Result<Vec<(usize, Vec<String>)>, String> {
+    // Spreadsheets often save UTF-8 with a byte order mark
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            ',' => record.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\r' | '\n' => {
+                record.push(std::mem::take(&mut field));
+                push_record(&mut records, record_line, std::mem::take(&mut record));
+                line += 1;
+                record_line = line;
+            }
+            _ => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(format!(
+            "Unterminated quoted field in the record starting on line {}",
+            record_line
+        ));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        push_record(&mut records, record_line, record);
+    }
+    Ok(records)
+}
+
+fn push_record(records: &mut Vec<(usize, Vec<String>)>, line: usize, record: Vec<String>) {
+    let blank = record.iter().all(|field| field.trim().is_empty());
+    if !blank {
+        records.push((line, record));
+    }
+}
+
+/// Builds the contest payload for a row, looking up its venue, games and
+/// participants by name
+pub async fn resolve_row(
+    repo: &ContestRepositoryImpl,
+    row: &CsvContestRow,
+) -> Result<ContestDto, String> {
+    let venue = repo
+        .venue_usecase
+        .repo
+        .search(&row.venue)
+        .await
+        .into_iter()
+        .find(|venue| venue.display_name.eq_ignore_ascii_case(&row.venue))
+        .ok_or_else(|| format!("Unknown venue '{}'", row.venue))?;
+
+    let mut games = Vec::new();
+    for name in &row.games {
+        let game = repo
+            .game_usecase
+            .repo
+            .search_db_only(name)
+            .await
+            .into_iter()
+            .find(|game| game.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| format!("Unknown game '{}'", name))?;
+        games.push(GameDto::from(&game));
+    }
+
+    let mut outcomes = Vec::new();
+    for participant in &row.participants {
+        let player = repo
+            .player_usecase
+            .repo
+            .find_by_handle(&participant.handle)
+            .await
+            .ok_or_else(|| format!("Unknown player '{}'", participant.handle))?;
+        let mut outcome = OutcomeDto {
+            player_id: player.id,
+            place: participant.place.clone(),
+            result: "lost".to_string(),
+            email: player.email,
+            handle: player.handle,
+            guest: false,
+        };
+        if outcome.is_win() {
+            outcome.result = "won".to_string();
+        }
+        outcomes.push(outcome);
+    }
+
+    Ok(ContestDto {
+        id: String::new(),
+        name: row.name.clone(),
+        start: row.start,
+        stop: row.stop,
+        venue: VenueDto::from(&venue),
+        games,
+        outcomes,
+        creator_id: String::new(),
+        created_at: None,
+        rated: true,
+        visibility: ContestVisibility::Public,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_template_round_trips_through_the_parser() {
+        let rows = parse_contest_rows(&template()).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].line, 2);
+        let contest = rows[0].contest.as_ref().unwrap();
+        assert_eq!(contest.name, "Friday Night, Table 1");
+        assert_eq!(contest.start.to_rfc3339(), "2026-01-09T19:00:00-05:00");
+        assert_eq!(contest.venue, "The Board Room");
+        assert_eq!(contest.games, ["Catan", "Azul"]);
+        assert_eq!(
+            contest.participants,
+            [
+                CsvParticipant {
+                    handle: "alice".to_string(),
+                    place: "1".to_string()
+                },
+                CsvParticipant {
+                    handle: "bob".to_string(),
+                    place: "2".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_quoted_fields_keep_commas_quotes_and_line_breaks() {
+        let records = parse_records("a,\"b, \"\"c\"\"\",\"multi\nline\"\n\nd,e,f").unwrap();
+        assert_eq!(
+            records,
+            [
+                (
+                    1,
+                    vec![
+                        "a".to_string(),
+                        "b, \"c\"".to_string(),
+                        "multi\nline".to_string()
+                    ]
+                ),
+                (4, vec!["d".to_string(), "e".to_string(), "f".to_string()]),
+            ]
+        );
+        assert_eq!(quote_field("say \"hi\", then"), "\"say \"\"hi\"\", then\"");
+        assert!(parse_records("a,\"open\nb").is_err());
+    }
+
+    #[test]
+    fn test_columns_are_matched_by_name_and_row_errors_stay_on_their_row() {
+        let csv = "participants,games,venue,stop,start,name\r\n\
+                   carol:1;dave:2;erin:2,Azul,Cafe,2026-02-01T20:00:00Z,2026-02-01T18:00:00Z,Tie night\r\n\
+                   carol,Azul,Cafe,2026-02-01T20:00:00Z,2026-02-01T18:00:00Z,Bad participant\r\n\
+                   ,Azul,Cafe,2026-02-01T20:00:00Z,yesterday,Bad start\r\n";
+        let rows = parse_contest_rows(csv).unwrap();
+        assert_eq!(rows.len(), 3);
+
+        let tie = rows[0].contest.as_ref().unwrap();
+        assert_eq!(tie.name, "Tie night");
+        assert_eq!(tie.participants.len(), 3);
+        assert_eq!(tie.participants[2].place, "2");
+
+        assert!(rows[1].contest.as_ref().unwrap_err().contains("carol"));
+        assert_eq!(rows[2].line, 4);
+        assert!(rows[2].contest.as_ref().unwrap_err().contains("yesterday"));
+    }
+
+    #[test]
+    fn test_missing_columns_reject_the_file() {
+        assert_eq!(
+            parse_contest_rows("name,start,stop,venue,games\n").unwrap_err(),
+            "Missing column 'participants'"
+        );
+        assert!(parse_contest_rows("").is_err());
+    }
+}
@@ -10,7 +10,8 @@ use arangors::document::options::InsertOptions;
 use arangors::Database;
 use argon2::{Argon2, PasswordHasher};
 use async_trait::async_trait;
-use shared::dto::contest::{ContestDto, OutcomeDto};
+use chrono::{DateTime, FixedOffset};
+use shared::dto::contest::{ContestDto, ContestTimelineDto, OutcomeDto};
 use shared::dto::game::GameDto;
 use shared::dto::venue::VenueDto;
 use shared::models::contest::Contest;
@@ -36,7 +37,10 @@ impl ContestRepositoryImpl {
         let game_repo = GameRepositoryImpl::new(db.clone());
         let game_usecase = GameUseCaseImpl { repo: game_repo };
         let player_repo = PlayerRepositoryImpl::new(db.clone());
-        let player_usecase = PlayerUseCaseImpl { repo: player_repo };
+        let player_usecase = PlayerUseCaseImpl {
+            repo: player_repo,
+            passwords: crate::config::PasswordConfig::default(),
+        };
         Self {
             db,
             google_config,
@@ -156,6 +160,8 @@ impl ContestRepository for ContestRepositoryImpl {
             stop: contest_dto.stop,
             creator_id: creator_id.clone(),
             created_at: now,
+            rated: contest_dto.rated,
+            visibility: contest_dto.visibility,
         };
 
         log::info!("📄 Contest model created: id='{}', name='{}', start='{}', stop='{}', creator='{}', created_at='{}'", 
@@ -399,6 +405,24 @@ impl ContestRepository for ContestRepositoryImpl {
                 outcome.email
             );
 
+            // Guests have no player document; their edge points at a
+            // `guest/` id of its own so player joins never match it
+            if outcome.guest {
+                let mut updated_outcome = outcome.clone();
+                updated_outcome.player_id = format!("guest/{}", Uuid::new_v4());
+                if updated_outcome.is_win() {
+                    updated_outcome.result = "won".to_string();
+                }
+                log::info!(
+                    "👥 Outcome {}/{} is guest '{}'",
+                    i + 1,
+                    contest_dto.outcomes.len(),
+                    outcome.handle
+                );
+                processed_outcomes.push(updated_outcome);
+                continue;
+            }
+
             let player_id = outcome.player_id.clone();
 
             // Helper: check if player_id is a real DB id (ArangoDB format)
@@ -433,6 +457,7 @@ impl ContestRepository for ContestRepositoryImpl {
                 let _player_dto = shared::dto::player::PlayerDto {
                     id: String::new(), // Will be set by ArangoDB
                     firstname: outcome.handle.clone(),
+                    lastname: None,
                     handle: outcome.handle.clone(),
                     email: outcome.email.clone(),
                     created_at: chrono::Utc::now().fixed_offset(),
@@ -476,6 +501,10 @@ impl ContestRepository for ContestRepositoryImpl {
             let mut updated_outcome = outcome.clone();
             let final_player_id = player.id.clone();
             updated_outcome.player_id = final_player_id.clone();
+            // Everyone placed first won, including each player in a tie
+            if updated_outcome.is_win() {
+                updated_outcome.result = "won".to_string();
+            }
             processed_outcomes.push(updated_outcome);
             log::info!(
                 "👥 Outcome {}/{} processed successfully: player_id='{}', handle='{}'",
@@ -540,6 +569,8 @@ impl ContestRepository for ContestRepositoryImpl {
             outcomes: processed_outcomes,
             creator_id: created_contest.creator_id.clone(),
             created_at: Some(created_contest.created_at),
+            rated: created_contest.rated,
+            visibility: created_contest.visibility,
         };
 
         log::info!("✅ Contest creation process completed successfully!");
@@ -598,8 +629,44 @@ impl ContestRepository for ContestRepositoryImpl {
     async fn update(&self, _contest: Contest) -> Result<Contest, String> {
         unimplemented!()
     }
-    async fn delete(&self, _id: &str) -> Result<(), String> {
-        unimplemented!()
+    /// Removes a contest together with its `resulted_in`, `played_with` and
+    /// `played_at` edges
+    async fn delete(&self, id: &str) -> Result<(), String> {
+        let contest_id = if id.contains('/') {
+            id.to_string()
+        } else {
+            format!("contest/{}", id)
+        };
+        let query = arangors::AqlQuery::builder()
+            .query(
+                r#"
+                LET resulted_in = (
+                    FOR e IN resulted_in FILTER e._from == @contest_id REMOVE e IN resulted_in
+                )
+                LET played_with = (
+                    FOR e IN played_with FILTER e._from == @contest_id REMOVE e IN played_with
+                )
+                LET played_at = (
+                    FOR e IN played_at FILTER e._from == @contest_id REMOVE e IN played_at
+                )
+                FOR contest IN contest
+                    FILTER contest._id == @contest_id
+                    REMOVE contest IN contest
+                    RETURN OLD._id
+                "#,
+            )
+            .bind_var("contest_id", contest_id.clone())
+            .build();
+        let removed: Vec<String> = self
+            .db
+            .aql_query(query)
+            .await
+            .map_err(|e| format!("Failed to delete contest {}: {}", contest_id, e))?;
+        if removed.is_empty() {
+            return Err(format!("Contest {} not found", contest_id));
+        }
+        log::info!("Deleted contest {} and its edges", contest_id);
+        Ok(())
     }
 
     async fn find_contests_by_player_and_game(
@@ -701,9 +768,9 @@ impl ContestRepository for ContestRepositoryImpl {
             LET player = DOCUMENT(outcome._to)
             SORT TO_NUMBER(outcome.place)
             RETURN {
-                player_id: player._key,
-                player_name: CONCAT(player.firstname, ' ', player.lastname),
-                player_handle: player.handle,
+                player_id: player != null ? player._key : null,
+                player_name: player != null ? CONCAT(player.firstname, ' ', player.lastname) : outcome.guest_name,
+                player_handle: player != null ? player.handle : outcome.guest_name,
                 placement: outcome.place,
                 result: outcome.result
             }
@@ -923,6 +990,7 @@ impl ContestRepositoryImpl {
             label: "RESULTED_IN".to_string(),
             place,
             result: outcome.result.clone(),
+            guest_name: outcome.guest.then(|| outcome.handle.clone()),
         };
 
         log::info!("🔗 Getting resulted_in collection...");
@@ -1001,14 +1069,15 @@ impl ContestRepositoryImpl {
                 LET outcomes = (
                     FOR result IN resulted_in
                     FILTER result._from == contest._id
-                    FOR player IN player
-                    FILTER result._to == player._id
+                    LET player = DOCUMENT(result._to)
+                    FILTER player != null OR result.guest_name != null
                     RETURN {
-                        player_id: player._id,
-                        handle: player.handle,
-                        email: player.email,
+                        player_id: result._to,
+                        handle: player != null ? player.handle : result.guest_name,
+                        email: player != null ? player.email : "",
                         place: TO_STRING(result.place),
-                        result: result.result
+                        result: result.result,
+                        guest: result.guest_name != null
                     }
                 )
                 RETURN {
@@ -1016,6 +1085,8 @@ impl ContestRepositoryImpl {
                     name: contest.name,
                     start: contest.start,
                     stop: contest.stop,
+                    rated: contest.rated != false,
+                    visibility: NOT_NULL(contest.visibility, "public"),
                     venue: venue,
                     games: games,
                     outcomes: outcomes
@@ -1311,6 +1382,7 @@ impl ContestRepositoryImpl {
                                     let email = outcome_json["email"].as_str()?.to_string();
                                     let place = outcome_json["place"].as_str()?.to_string();
                                     let result = outcome_json["result"].as_str()?.to_string();
+                                    let guest = outcome_json["guest"].as_bool().unwrap_or(false);
 
                                     log::debug!("✅ Outcome {} extracted: player_id={}, handle={}, email={}, place={}, result={}",
                                         i, player_id, handle, email, place, result);
@@ -1321,6 +1393,7 @@ impl ContestRepositoryImpl {
                                         result,
                                         email,
                                         handle,
+                                        guest,
                                     })
                                 })
                                 .collect()
@@ -1345,6 +1418,9 @@ impl ContestRepositoryImpl {
                         outcomes,
                         creator_id: String::new(), // Will be populated from contest data
                         created_at: None,          // Will be populated from contest data
+                        rated: contest_data["rated"].as_bool().unwrap_or(true),
+                        visibility: serde_json::from_value(contest_data["visibility"].clone())
+                            .unwrap_or_default(),
                     };
 
                     log::info!("✅ Successfully created ContestDto for contest: {}", id);
@@ -1360,6 +1436,66 @@ impl ContestRepositoryImpl {
             }
         }
     }
+
+    /// Loads what is needed to replay a contest: its timestamps and outcomes
+    pub async fn find_timeline(&self, id: &str) -> Result<Option<ContestTimelineDto>, SharedError> {
+        #[derive(serde::Deserialize)]
+        struct TimelineRow {
+            #[serde(rename = "_id")]
+            id: String,
+            name: String,
+            start: DateTime<FixedOffset>,
+            stop: DateTime<FixedOffset>,
+            created_at: Option<DateTime<FixedOffset>>,
+            outcomes: Vec<OutcomeDto>,
+        }
+
+        let query = arangors::AqlQuery::builder()
+            .query(
+                r#"
+                FOR contest IN contest
+                FILTER contest._id == @contest_id
+                LET outcomes = (
+                    FOR result IN resulted_in
+                    FILTER result._from == contest._id
+                    LET player = DOCUMENT(result._to)
+                    FILTER player != null OR result.guest_name != null
+                    RETURN {
+                        player_id: result._to,
+                        handle: player != null ? player.handle : result.guest_name,
+                        place: TO_STRING(result.place),
+                        result: result.result,
+                        guest: result.guest_name != null
+                    }
+                )
+                RETURN {
+                    _id: contest._id,
+                    name: contest.name,
+                    start: contest.start,
+                    stop: contest.stop,
+                    created_at: contest.created_at,
+                    outcomes: outcomes
+                }
+            "#,
+            )
+            .bind_var("contest_id", id)
+            .build();
+
+        let mut rows: Vec<TimelineRow> = self.db.aql_query(query).await.map_err(|e| {
+            SharedError::Database(format!("Failed to load contest timeline: {}", e))
+        })?;
+
+        Ok(rows.pop().map(|row| {
+            ContestTimelineDto::build(
+                row.id,
+                row.name,
+                row.created_at,
+                row.start,
+                row.stop,
+                &row.outcomes,
+            )
+        }))
+    }
 }
 
 impl ContestRepositoryImpl {
@@ -1371,6 +1507,27 @@ impl ContestRepositoryImpl {
         Some("LENGTH(FOR e IN played_with FILTER e._from == contest._id AND e._to IN @game_ids RETURN 1) > 0".to_string())
     }
 
+    /// Splits a free-text query into case-insensitive LIKE patterns, one per word
+    pub(crate) fn search_terms(q: &str) -> Vec<String> {
+        q.split_whitespace()
+            .map(|term| {
+                let escaped = term
+                    .replace('\\', "\\\\")
+                    .replace('%', "\\%")
+                    .replace('_', "\\_");
+                format!("%{}%", escaped)
+            })
+            .collect()
+    }
+
+    /// Every search word must appear in the contest's name or description
+    pub(crate) fn build_text_filter_clause(terms: &[String]) -> Option<String> {
+        if terms.is_empty() {
+            return None;
+        }
+        Some("LENGTH(FOR t IN @q_terms FILTER !LIKE(contest.name, t, true) AND !LIKE(NOT_NULL(contest.description, \"\"), t, true) RETURN 1) == 0".to_string())
+    }
+
     pub async fn search_contests(
         &self,
         q: &str,
@@ -1422,9 +1579,10 @@ impl ContestRepositoryImpl {
             }
         });
 
+        let q_terms = Self::search_terms(q);
         let mut filters = Vec::new();
-        if !q.is_empty() {
-            filters.push("LIKE(contest.name, @q, true)".to_string());
+        if let Some(text_clause) = Self::build_text_filter_clause(&q_terms) {
+            filters.push(text_clause);
         }
         if start_from.is_some() {
             filters.push("contest.start >= DATE_ISO8601(@start_from)".to_string());
@@ -1481,6 +1639,15 @@ impl ContestRepositoryImpl {
         } else {
             "DESC"
         };
+        // Text searches rank name matches above description-only matches
+        let (relevance, sort_clause) = if q_terms.is_empty() {
+            (String::new(), format!("SORT {sort_field} {sort_dir}"))
+        } else {
+            (
+                "LET relevance = SUM(FOR t IN @q_terms RETURN (LIKE(contest.name, t, true) ? 2 : 0) + (LIKE(NOT_NULL(contest.description, \"\"), t, true) ? 1 : 0))".to_string(),
+                format!("SORT relevance DESC, {sort_field} {sort_dir}"),
+            )
+        };
         let skip = ((page.saturating_sub(1)) as u64) * (page_size as u64);
 
         let aql = format!(
@@ -1494,7 +1661,8 @@ FOR contest IN contest
     )
     // If scope is 'all', 'mine' evaluates true for everyone; otherwise enforce player-based filter
     {filter_clause}
-    SORT {sort_field} {sort_dir}
+    {relevance}
+    {sort_clause}
     LIMIT @skip, @limit
     LET venue = venue_edge != null ? DOCUMENT(venue_edge._to) : null
     LET games = (FOR e IN played_with FILTER e._from == contest._id 
@@ -1503,11 +1671,12 @@ FOR contest IN contest
     LET outcomes = (
         FOR r IN resulted_in FILTER r._from == contest._id
         LET player = DOCUMENT(r._to)
-        RETURN {{ player_id: player._id, handle: player.handle, email: player.email, place: TO_STRING(r.place), result: r.result }}
+        RETURN {{ player_id: r._to, handle: player != null ? player.handle : r.guest_name, email: player != null ? player.email : "", place: TO_STRING(r.place), result: r.result, guest: r.guest_name != null }}
     )
     RETURN {{
         _id: contest._id,
         name: contest.name,
+        description: contest.description,
         start: contest.start,
         stop: contest.stop,
         venue: venue == null ? null : {{
@@ -1527,8 +1696,8 @@ FOR contest IN contest
 
         let mut bind_vars: std::collections::HashMap<&str, serde_json::Value> =
             std::collections::HashMap::new();
-        if !q.is_empty() {
-            bind_vars.insert("q", serde_json::Value::String(format!("%{}%", q)));
+        if !q_terms.is_empty() {
+            bind_vars.insert("q_terms", serde_json::json!(q_terms));
         }
         bind_vars.insert("scope", serde_json::Value::String(scope.to_string()));
         bind_vars.insert(
@@ -1601,8 +1770,8 @@ FOR contest IN contest
         // Build a lean set of bind vars for the count query (exclude pagination-only vars like skip/limit)
         let mut count_bind_vars: std::collections::HashMap<&str, serde_json::Value> =
             std::collections::HashMap::new();
-        if !q.is_empty() {
-            count_bind_vars.insert("q", serde_json::Value::String(format!("%{}%", q)));
+        if !q_terms.is_empty() {
+            count_bind_vars.insert("q_terms", serde_json::json!(q_terms));
         }
         count_bind_vars.insert("scope", serde_json::Value::String(scope.to_string()));
         if let Some(ref pf) = player_full {
@@ -1690,6 +1859,24 @@ mod repository_unit_tests {
         assert!(ContestRepositoryImpl::build_game_filter_clause(&ids).is_none());
     }
 
+    #[test]
+    fn search_terms_split_words_and_escape_wildcards() {
+        assert!(ContestRepositoryImpl::search_terms("   ").is_empty());
+        assert_eq!(
+            ContestRepositoryImpl::search_terms(" Spring  finals "),
+            ["%Spring%", "%finals%"]
+        );
+        assert_eq!(
+            ContestRepositoryImpl::search_terms("100%_done"),
+            ["%100\\%\\_done%"]
+        );
+        assert!(ContestRepositoryImpl::build_text_filter_clause(&[]).is_none());
+        let clause = ContestRepositoryImpl::build_text_filter_clause(&["%finals%".to_string()])
+            .expect("some");
+        assert!(clause.contains("contest.name"));
+        assert!(clause.contains("contest.description"));
+    }
+
     #[test]
     fn game_filter_clause_non_empty_uses_any_semantics() {
         let ids = vec!["game/abc".to_string(), "game/def".to_string()];
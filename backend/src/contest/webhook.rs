@@ -0,0 +1,307 @@
+//! Outbound webhooks fired after a contest is created
+//!
+//! Every configured endpoint is POSTed a compact JSON summary of the contest.
+//! Delivery runs in the background with retries, so a slow or failing
+//! receiver never affects the request that created the contest.
+//!
+//! # Signatures
+//!
+//! Requests to an endpoint configured with a secret carry an `X-Signature`
+//! header of the form `sha256=<hex>`, where `<hex>` is the lowercase hex
+//! HMAC-SHA256 of the raw request body keyed with that secret. Receivers
+//! should recompute the HMAC over the body bytes exactly as received (before
+//! any JSON parsing) and compare it to the header in constant time, as
+//! [`verify_signature`] does.
+
+use crate::config::{WebhookConfig, WebhookEndpoint};
+use chrono::{DateTime, FixedOffset};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use shared::dto::contest::ContestDto;
+use std::time::Duration;
+
+/// Event name sent with every contest creation payload
+pub const CONTEST_CREATED_EVENT: &str = "contest.created";
+
+/// Header carrying the body's HMAC for endpoints with a secret
+pub const SIGNATURE_HEADER: &str = "X-Signature";
+
+/// `X-Signature` value for `body` signed with `secret`
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Whether `signature` is the `X-Signature` of `body` under `secret`
+pub fn verify_signature(secret: &str, body: &[u8], signature: &str) -> bool {
+    let Some(digest) = signature
+        .strip_prefix("sha256=")
+        .and_then(|hex_digest| hex::decode(hex_digest).ok())
+    else {
+        return false;
+    };
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    mac.verify_slice(&digest).is_ok()
+}
+
+/// Summary of a created contest, as POSTed to webhook endpoints
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ContestCreatedPayload {
+    pub event: String,
+    pub contest_id: String,
+    pub name: String,
+    pub start: DateTime<FixedOffset>,
+    pub stop: DateTime<FixedOffset>,
+    pub venue: String,
+    pub games: Vec<String>,
+    pub outcomes: Vec<OutcomeSummary>,
+}
+
+/// One participant's placement in a webhook payload
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct OutcomeSummary {
+    pub player_id: String,
+    pub handle: String,
+    pub place: String,
+    pub result: String,
+}
+
+impl From<&ContestDto> for ContestCreatedPayload {
+    fn from(contest: &ContestDto) -> Self {
+        Self {
+            event: CONTEST_CREATED_EVENT.to_string(),
+            contest_id: contest.id.clone(),
+            name: contest.name.clone(),
+            start: contest.start,
+            stop: contest.stop,
+            venue: contest.venue.display_name.clone(),
+            games: contest.games.iter().map(|g| g.name.clone()).collect(),
+            outcomes: contest
+                .outcomes
+                .iter()
+                .map(|o| OutcomeSummary {
+                    player_id: o.player_id.clone(),
+                    handle: o.handle.clone(),
+                    place: o.place.clone(),
+                    result: o.result.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Delivers contest creation webhooks to the configured endpoints
+#[derive(Clone)]
+pub struct ContestWebhooks {
+    client: reqwest::Client,
+    config: WebhookConfig,
+}
+
+impl ContestWebhooks {
+    pub fn new(config: WebhookConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .build()
+            .unwrap_or_else(|_| reqwest::Client::new());
+        Self { client, config }
+    }
+
+    /// Queues delivery of `contest` to every endpoint and returns immediately
+    pub fn notify_contest_created(&self, contest: &ContestDto) {
+        if self.config.endpoints.is_empty() {
+            return;
+        }
+        let body = match serde_json::to_vec(&ContestCreatedPayload::from(contest)) {
+            Ok(body) => body,
+            Err(e) => {
+                log::error!("Failed to serialize contest webhook payload: {}", e);
+                return;
+            }
+        };
+        for endpoint in &self.config.endpoints {
+            let webhooks = self.clone();
+            let endpoint = endpoint.clone();
+            let body = body.clone();
+            tokio::spawn(async move {
+                webhooks.deliver(&endpoint, body).await;
+            });
+        }
+    }
+
+    /// POSTs `body` to `endpoint`, retrying with exponential backoff
+    ///
+    /// Returns whether any attempt got a success status.
+    pub async fn deliver(&self, endpoint: &WebhookEndpoint, body: Vec<u8>) -> bool {
+        let url = endpoint.url.as_str();
+        let signature = endpoint.secret.as_deref().map(|secret| sign(secret, &body));
+        let attempts = self.config.max_attempts.max(1);
+        let mut backoff = Duration::from_millis(self.config.initial_backoff_ms);
+        for attempt in 1..=attempts {
+            let mut request = self
+                .client
+                .post(url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.clone());
+            if let Some(signature) = &signature {
+                request = request.header(SIGNATURE_HEADER, signature);
+            }
+            match request.send().await {
+                Ok(response) if response.status().is_success() => return true,
+                Ok(response) => log::warn!(
+                    "Contest webhook {} answered {} (attempt {}/{})",
+                    url,
+                    response.status(),
+                    attempt,
+                    attempts
+                ),
+                Err(e) => log::warn!(
+                    "Contest webhook {} failed: {} (attempt {}/{})",
+                    url,
+                    e,
+                    attempt,
+                    attempts
+                ),
+            }
+            if attempt < attempts {
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+        }
+        log::error!(
+            "Giving up on contest webhook {} after {} attempts",
+            url,
+            attempts
+        );
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::sync::{Arc, Mutex};
+
+    /// A request received by the mock server: its signature header and body
+    type Received = (Option<String>, Vec<u8>);
+
+    /// Answers each request with the next of `statuses`, recording what it got
+    fn mock_webhook_server(statuses: Vec<u16>) -> (String, Arc<Mutex<Vec<Received>>>) {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let recorded = received.clone();
+        std::thread::spawn(move || {
+            let mut statuses = statuses.into_iter();
+            for stream in listener.incoming().flatten() {
+                let mut reader = BufReader::new(stream);
+                let mut content_length = 0;
+                let mut signature = None;
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        } else if name.eq_ignore_ascii_case(SIGNATURE_HEADER) {
+                            signature = Some(value.trim().to_string());
+                        }
+                    }
+                    line.clear();
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                recorded.lock().unwrap().push((signature, body));
+
+                let status = statuses.next().unwrap_or(200);
+                let response = format!(
+                    "HTTP/1.1 {} Status\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                );
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+        });
+        (url, received)
+    }
+
+    fn webhooks(max_attempts: u32) -> ContestWebhooks {
+        ContestWebhooks::new(WebhookConfig {
+            endpoints: Vec::new(),
+            timeout_ms: 1000,
+            max_attempts,
+            initial_backoff_ms: 10,
+        })
+    }
+
+    fn endpoint(url: &str, secret: Option<&str>) -> WebhookEndpoint {
+        WebhookEndpoint {
+            url: url.to_string(),
+            secret: secret.map(str::to_string),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_deliver_retries_until_success() {
+        let (url, received) = mock_webhook_server(vec![500, 503, 200]);
+        let delivered = webhooks(3)
+            .deliver(
+                &endpoint(&url, None),
+                b"{\"event\":\"contest.created\"}".to_vec(),
+            )
+            .await;
+        assert!(delivered);
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 3);
+        assert!(received
+            .iter()
+            .all(|(signature, body)| signature.is_none()
+                && body == b"{\"event\":\"contest.created\"}"));
+    }
+
+    #[tokio::test]
+    async fn test_deliver_gives_up_after_max_attempts() {
+        let (url, received) = mock_webhook_server(vec![500, 500, 500]);
+        let delivered = webhooks(2)
+            .deliver(&endpoint(&url, None), b"{}".to_vec())
+            .await;
+        assert!(!delivered);
+        assert_eq!(received.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_signature_verifies_only_with_the_configured_secret() {
+        let (url, received) = mock_webhook_server(vec![200]);
+        let body = b"{\"event\":\"contest.created\",\"name\":\"Friday\"}".to_vec();
+        assert!(
+            webhooks(1)
+                .deliver(&endpoint(&url, Some("league-secret")), body.clone())
+                .await
+        );
+
+        let received = received.lock().unwrap();
+        let (signature, received_body) = &received[0];
+        let signature = signature.as_deref().expect("signed request");
+        assert_eq!(received_body, &body);
+        assert!(verify_signature("league-secret", received_body, signature));
+        assert!(!verify_signature("wrong-secret", received_body, signature));
+        assert!(!verify_signature("league-secret", b"{}", signature));
+        assert!(!verify_signature(
+            "league-secret",
+            received_body,
+            "sha256=zz"
+        ));
+    }
+
+    #[test]
+    fn test_sign_matches_reference_hmac() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+}
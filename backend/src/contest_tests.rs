@@ -3,7 +3,7 @@ mod contest_tests {
     // use super::*;
     use chrono::{Duration, Utc};
     use shared::dto::contest::{ContestDto, OutcomeDto};
-    use shared::models::contest::Contest;
+    use shared::models::contest::{Contest, ContestVisibility};
 
     #[test]
     fn test_contest_dto_creation() {
@@ -26,6 +26,8 @@ mod contest_tests {
             outcomes: vec![],
             creator_id: String::new(),
             created_at: None,
+            rated: true,
+            visibility: ContestVisibility::Public,
         };
 
         assert_eq!(contest_dto.name, "Test Contest");
@@ -40,6 +42,7 @@ mod contest_tests {
             result: "won".to_string(),
             email: "test@example.com".to_string(),
             handle: "testplayer".to_string(),
+            guest: false,
         };
 
         assert_eq!(outcome.player_id, "player/test");
@@ -57,6 +60,8 @@ mod contest_tests {
             stop: Utc::now().fixed_offset() + Duration::hours(2),
             creator_id: "player/test-creator".to_string(),
             created_at: Utc::now().fixed_offset(),
+            rated: true,
+            visibility: ContestVisibility::Public,
         };
 
         assert_eq!(contest.name, "Test Contest");
@@ -73,6 +78,8 @@ mod contest_tests {
             stop: Utc::now().fixed_offset() + Duration::hours(2),
             creator_id: "player/test-creator".to_string(),
             created_at: Utc::now().fixed_offset(),
+            rated: true,
+            visibility: ContestVisibility::Public,
         };
 
         let json = serde_json::to_string(&contest).unwrap();
@@ -87,6 +94,7 @@ mod contest_tests {
 mod contest_integration_like_tests {
     use chrono::{Duration, FixedOffset, Utc};
     use shared::dto::contest::ContestDto;
+    use shared::models::contest::ContestVisibility;
     use shared::models::venue::VenueSource;
 
     #[test]
@@ -113,6 +121,8 @@ mod contest_integration_like_tests {
             outcomes: vec![],
             creator_id: String::new(),
             created_at: None,
+            rated: true,
+            visibility: ContestVisibility::Public,
         };
         assert!(contest_dto.stop > contest_dto.start);
         assert_eq!(contest_dto.venue.timezone, "Europe/Paris");
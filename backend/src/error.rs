@@ -1,10 +1,74 @@
-use actix_web::{HttpResponse, ResponseError};
+use actix_web::dev::ServiceResponse;
+use actix_web::error::JsonPayloadError;
+use actix_web::http::header;
+use actix_web::middleware::{ErrorHandlerResponse, ErrorHandlers};
+use actix_web::web::JsonConfig;
+use actix_web::{HttpRequest, HttpResponse, ResponseError};
 use serde::Serialize;
+use shared::error::SharedError;
 use std::fmt;
 
+/// Stable machine-readable error codes, sent as `code` in JSON error bodies
+///
+/// Clients match on these, so a code is never renamed or reused; see
+/// `docs/api/ERROR_CODES.md` for what each one means. Every [`ApiError`]
+/// constructor sets the generic code for its status, and handlers narrow it
+/// with [`ApiError::with_code`] where a client can act on the difference.
+pub mod codes {
+    pub const BAD_REQUEST: &str = "request.bad_request";
+    pub const VALIDATION_FAILED: &str = "request.validation_failed";
+    pub const UNAUTHORIZED: &str = "auth.unauthorized";
+    pub const FORBIDDEN: &str = "auth.forbidden";
+    pub const NOT_FOUND: &str = "resource.not_found";
+    pub const METHOD_NOT_ALLOWED: &str = "request.method_not_allowed";
+    pub const UNSUPPORTED_MEDIA_TYPE: &str = "request.unsupported_media_type";
+    pub const CONFLICT: &str = "resource.conflict";
+    pub const TOO_MANY_REQUESTS: &str = "request.rate_limited";
+    pub const INTERNAL: &str = "server.internal";
+    pub const DATABASE: &str = "server.database";
+    pub const NOT_IMPLEMENTED: &str = "server.not_implemented";
+    pub const UNAVAILABLE: &str = "server.unavailable";
+    pub const MAINTENANCE: &str = "server.maintenance";
+
+    pub const PLAYER_NOT_FOUND: &str = "player.not_found";
+    pub const PLAYER_INVALID_CREDENTIALS: &str = "player.invalid_credentials";
+    pub const PLAYER_DUPLICATE_EMAIL: &str = "player.duplicate_email";
+    pub const PLAYER_DUPLICATE_HANDLE: &str = "player.duplicate_handle";
+
+    pub const CONTEST_NOT_FOUND: &str = "contest.not_found";
+    pub const CONTEST_INVALID: &str = "contest.invalid";
+    pub const CONTEST_INVALID_PLACEMENT: &str = "contest.invalid_placement";
+    pub const CONTEST_INVALID_RANKING: &str = "contest.invalid_ranking";
+    pub const CONTEST_INVALID_CSV: &str = "contest.invalid_csv";
+    pub const CONTEST_TOO_MANY_PARTICIPANTS: &str = "contest.too_many_participants";
+    pub const CONTEST_START_OUT_OF_RANGE: &str = "contest.start_out_of_range";
+    pub const CONTEST_NOT_CREATOR: &str = "contest.not_creator";
+    pub const CONTEST_UNDO_WINDOW_CLOSED: &str = "contest.undo_window_closed";
+
+    /// The generic code for an HTTP status
+    pub fn for_status(status_code: u16) -> &'static str {
+        match status_code {
+            400 => BAD_REQUEST,
+            401 => UNAUTHORIZED,
+            403 => FORBIDDEN,
+            404 => NOT_FOUND,
+            405 => METHOD_NOT_ALLOWED,
+            409 => CONFLICT,
+            415 => UNSUPPORTED_MEDIA_TYPE,
+            422 => VALIDATION_FAILED,
+            429 => TOO_MANY_REQUESTS,
+            501 => NOT_IMPLEMENTED,
+            503 => UNAVAILABLE,
+            _ => INTERNAL,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, utoipa::ToSchema)]
 pub struct ApiError {
     pub error: String,
+    /// One of [`codes`]
+    pub code: String,
     pub message: String,
     pub status_code: u16,
 }
@@ -13,11 +77,18 @@ impl ApiError {
     pub fn new(error: &str, message: &str, status_code: u16) -> Self {
         Self {
             error: error.to_string(),
+            code: codes::for_status(status_code).to_string(),
             message: message.to_string(),
             status_code,
         }
     }
 
+    /// Replaces the generic code with a more specific one from [`codes`]
+    pub fn with_code(mut self, code: &str) -> Self {
+        self.code = code.to_string();
+        self
+    }
+
     pub fn bad_request(message: &str) -> Self {
         Self::new("BAD_REQUEST", message, 400)
     }
@@ -32,7 +103,6 @@ impl ApiError {
         Self::new("FORBIDDEN", message, 403)
     }
 
-    #[allow(dead_code)]
     pub fn not_found(message: &str) -> Self {
         Self::new("NOT_FOUND", message, 404)
     }
@@ -42,11 +112,11 @@ impl ApiError {
     }
 
     pub fn database_error(message: &str) -> Self {
-        Self::new("DATABASE_ERROR", message, 500)
+        Self::new("DATABASE_ERROR", message, 500).with_code(codes::DATABASE)
     }
 
     pub fn validation_error(message: &str) -> Self {
-        Self::new("VALIDATION_ERROR", message, 400)
+        Self::new("VALIDATION_ERROR", message, 400).with_code(codes::VALIDATION_FAILED)
     }
 }
 
@@ -72,6 +142,74 @@ impl ResponseError for ApiError {
     }
 }
 
+/// Default service returning a JSON 404 for paths that match no route
+pub async fn not_found_handler(req: HttpRequest) -> HttpResponse {
+    ApiError::not_found(&format!("No route for {} {}", req.method(), req.path())).error_response()
+}
+
+/// Rewrites actix's plain-text 405 responses into the JSON error shape
+fn method_not_allowed_handler<B>(
+    res: ServiceResponse<B>,
+) -> actix_web::Result<ErrorHandlerResponse<B>> {
+    let is_json = res
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.starts_with("application/json"))
+        .unwrap_or(false);
+    if is_json {
+        return Ok(ErrorHandlerResponse::Response(res.map_into_left_body()));
+    }
+
+    let (req, _) = res.into_parts();
+    let body = ApiError::new(
+        "METHOD_NOT_ALLOWED",
+        &format!("Method {} not allowed for {}", req.method(), req.path()),
+        405,
+    )
+    .error_response();
+    Ok(ErrorHandlerResponse::Response(
+        ServiceResponse::new(req, body).map_into_right_body(),
+    ))
+}
+
+/// JSON body extractor settings for API routes, accepting up to `limit` bytes
+///
+/// A body sent as anything but `application/json` is refused with a 415
+/// before it is parsed, and one that doesn't parse with a 400, both in the
+/// JSON error shape rather than actix's plain text.
+pub fn json_config(limit: usize) -> JsonConfig {
+    JsonConfig::default()
+        .limit(limit)
+        .error_handler(|err, req| match err {
+            JsonPayloadError::ContentType => {
+                let content_type = req
+                    .headers()
+                    .get(header::CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok())
+                    .unwrap_or("none");
+                ApiError::new(
+                    "UNSUPPORTED_MEDIA_TYPE",
+                    &format!(
+                        "Unsupported content type {}; send the body as application/json",
+                        content_type
+                    ),
+                    415,
+                )
+                .into()
+            }
+            err => ApiError::bad_request(&format!("Invalid JSON: {}", err)).into(),
+        })
+}
+
+/// Error handler middleware that keeps framework-generated errors in JSON
+pub fn json_error_handlers<B: 'static>() -> ErrorHandlers<B> {
+    ErrorHandlers::new().handler(
+        actix_web::http::StatusCode::METHOD_NOT_ALLOWED,
+        method_not_allowed_handler,
+    )
+}
+
 impl fmt::Display for ApiError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}: {}", self.error, self.message)
@@ -102,6 +240,29 @@ impl From<validator::ValidationErrors> for ApiError {
     }
 }
 
+impl From<SharedError> for ApiError {
+    fn from(err: SharedError) -> Self {
+        let message = err.to_string();
+        match err {
+            SharedError::Validation(_)
+            | SharedError::InvalidDateRange { .. }
+            | SharedError::InvalidEmail(_)
+            | SharedError::InvalidUuid(_)
+            | SharedError::MissingField(_) => Self::validation_error(&message),
+            SharedError::BadRequest(_) | SharedError::Conversion(_) => Self::bad_request(&message),
+            SharedError::NotFound(_) => Self::not_found(&message),
+            SharedError::Unauthorized(_) => Self::unauthorized(&message),
+            SharedError::Forbidden(_) => Self::forbidden(&message),
+            SharedError::Conflict(_) => Self::new("CONFLICT", &message, 409),
+            SharedError::Database(_) => Self::database_error(&message),
+            SharedError::Internal(_) | SharedError::InternalServerError(_) => {
+                Self::internal_error(&message)
+            }
+            SharedError::NotImplemented(_) => Self::new("NOT_IMPLEMENTED", &message, 501),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -170,6 +331,71 @@ mod tests {
         assert_eq!(error.status_code, 400);
     }
 
+    #[test]
+    fn test_codes_follow_status_unless_narrowed() {
+        assert_eq!(ApiError::bad_request("x").code, "request.bad_request");
+        assert_eq!(ApiError::unauthorized("x").code, "auth.unauthorized");
+        assert_eq!(ApiError::not_found("x").code, "resource.not_found");
+        assert_eq!(ApiError::database_error("x").code, "server.database");
+        assert_eq!(
+            ApiError::validation_error("x").code,
+            "request.validation_failed"
+        );
+        assert_eq!(
+            ApiError::new("UNAVAILABLE", "x", 503).code,
+            "server.unavailable"
+        );
+
+        let error = ApiError::bad_request("x").with_code(codes::PLAYER_DUPLICATE_EMAIL);
+        assert_eq!(error.code, "player.duplicate_email");
+        assert_eq!(error.status_code, 400);
+    }
+
+    #[test]
+    fn test_from_shared_error() {
+        let cases = [
+            (
+                SharedError::Conflict("taken".into()),
+                409,
+                "resource.conflict",
+            ),
+            (
+                SharedError::NotFound("gone".into()),
+                404,
+                "resource.not_found",
+            ),
+            (
+                SharedError::MissingField("name".into()),
+                400,
+                "request.validation_failed",
+            ),
+            (SharedError::Database("down".into()), 500, "server.database"),
+            (
+                SharedError::NotImplemented("later".into()),
+                501,
+                "server.not_implemented",
+            ),
+        ];
+        for (shared, status_code, code) in cases {
+            let api_error = ApiError::from(shared);
+            assert_eq!(api_error.status_code, status_code);
+            assert_eq!(api_error.code, code);
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_error_response_includes_code() {
+        let response = ApiError::not_found("Nope")
+            .with_code(codes::CONTEST_NOT_FOUND)
+            .error_response();
+        let body = actix_web::body::to_bytes(response.into_body())
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["code"], "contest.not_found");
+        assert_eq!(body["error"], "NOT_FOUND");
+    }
+
     #[test]
     fn test_display_format() {
         let error = ApiError::bad_request("Test message");
@@ -185,6 +411,12 @@ mod tests {
         assert_eq!(response.status().as_u16(), 400);
     }
 
+    #[test]
+    fn test_unsupported_media_type_has_its_own_code() {
+        let error = ApiError::new("UNSUPPORTED_MEDIA_TYPE", "Send JSON", 415);
+        assert_eq!(error.code, codes::UNSUPPORTED_MEDIA_TYPE);
+    }
+
     #[test]
     fn test_from_arangors_error() {
         // Use a valid variant for arangors::ClientError
@@ -399,7 +399,7 @@ impl GameRepository for GameRepositoryImpl {
                         FILTER contest_edge._to == game._id
                         LET contest = DOCUMENT(contest_edge._from)
                         FOR result IN resulted_in
-                          FILTER result._from == contest._id
+                          FILTER result._from == contest._id AND result.guest_name == null
                           LET other_player = DOCUMENT(result._to)
                           FILTER other_player._id != @player_id
                           COLLECT other_player_id = other_player._id, other_player_data = other_player INTO player_games
@@ -22,18 +22,41 @@ pub struct HealthResponse {
 )]
 #[get("/health")]
 pub async fn health_check() -> impl Responder {
-    let timestamp = SystemTime::now()
+    HttpResponse::Ok().json(HealthResponse::ok())
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap()
-        .as_secs();
+        .as_secs()
+}
 
-    let response = HealthResponse {
-        status: "ok".to_string(),
-        timestamp,
-        version: env!("CARGO_PKG_VERSION"),
-    };
+impl HealthResponse {
+    fn ok() -> Self {
+        Self {
+            status: "ok".to_string(),
+            timestamp: unix_timestamp(),
+            version: env!("CARGO_PKG_VERSION"),
+        }
+    }
+}
 
-    HttpResponse::Ok().json(response)
+/// Liveness probe: succeeds whenever the process can serve requests.
+///
+/// Deliberately checks no dependencies, so an outage of the database or Redis
+/// never gets the process restarted; that is what `/readyz` is for.
+#[utoipa::path(
+    get,
+    path = "/healthz",
+    tag = "health",
+    responses(
+        (status = 200, description = "The process is running", body = HealthResponse)
+    )
+)]
+#[get("/healthz")]
+pub async fn liveness() -> impl Responder {
+    HttpResponse::Ok().json(HealthResponse::ok())
 }
 
 #[derive(Serialize)]
@@ -196,6 +219,56 @@ pub async fn detailed_health_check(
     }
 }
 
+/// Readiness probe: succeeds only while the database and Redis are reachable
+#[utoipa::path(
+    get,
+    path = "/readyz",
+    tag = "health",
+    responses(
+        (status = 200, description = "Dependencies are reachable"),
+        (status = 503, description = "The database or Redis is unreachable")
+    )
+)]
+#[get("/readyz")]
+pub async fn readiness(
+    db: web::Data<Database<arangors::client::reqwest::ReqwestClient>>,
+    redis_client: web::Data<redis::Client>,
+) -> impl Responder {
+    #[derive(Serialize)]
+    struct ReadinessResponse {
+        status: &'static str,
+        timestamp: u64,
+        services: ReadinessServices,
+    }
+
+    #[derive(Serialize)]
+    struct ReadinessServices {
+        database: ServiceHealthStatus,
+        redis: ServiceHealthStatus,
+    }
+
+    let (db_status, redis_status) = tokio::join!(
+        check_database(db.get_ref()),
+        check_redis(redis_client.get_ref())
+    );
+    let ready = db_status.status == "healthy" && redis_status.status == "healthy";
+
+    let response = ReadinessResponse {
+        status: if ready { "ready" } else { "not_ready" },
+        timestamp: unix_timestamp(),
+        services: ReadinessServices {
+            database: db_status,
+            redis: redis_status,
+        },
+    };
+
+    if ready {
+        HttpResponse::Ok().json(response)
+    } else {
+        HttpResponse::ServiceUnavailable().json(response)
+    }
+}
+
 #[get("/health/scheduler")]
 pub async fn scheduler_health_check() -> impl Responder {
     let timestamp = SystemTime::now()
@@ -415,6 +488,17 @@ mod tests {
         assert!(json["version"].as_str().is_some());
     }
 
+    #[actix_web::test]
+    async fn test_liveness_needs_no_dependencies() {
+        // No database or Redis registered at all
+        let app = test::init_service(App::new().service(liveness)).await;
+        let req = test::TestRequest::get().uri("/healthz").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let json: Value = test::read_body_json(resp).await;
+        assert_eq!(json["status"], "ok");
+    }
+
     #[actix_web::test]
     async fn test_detailed_health_check() {
         // Create minimal test data - these won't actually connect, but the function will handle errors gracefully
@@ -1,3 +1,9 @@
+pub mod admin {
+    pub mod archive;
+    pub mod controller;
+    pub mod export;
+    pub mod stats_check;
+}
 pub mod auth;
 pub mod cache;
 pub mod config;
@@ -7,6 +13,7 @@ pub mod game;
 pub mod health;
 pub mod metrics;
 pub mod middleware;
+pub mod pagination;
 pub mod player;
 pub mod third_party;
 pub mod venue;
@@ -17,7 +24,9 @@ pub mod analytics {
     pub mod cache;
     pub mod controller;
     pub mod engine;
+    pub mod query_log;
     pub mod repository;
+    pub mod timing;
     pub mod usecase;
     pub mod visualization;
 
@@ -43,10 +52,12 @@ pub mod client_analytics {
 }
 
 pub mod ratings {
+    pub mod clock;
     pub mod controller;
     pub mod glicko;
     pub mod repository;
     pub mod scheduler;
+    pub mod throttle;
     pub mod usecase;
 }
 
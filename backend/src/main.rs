@@ -1,11 +1,8 @@
 use actix_web::{web, App, HttpServer};
 use arangors::client::reqwest::ReqwestClient;
-use backend::config::BGGConfig;
-use backend::error::ApiError;
 use backend::player::session::RedisSessionStore;
 use backend::third_party::BGGService;
 use log::error;
-use utoipa::OpenApi;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
@@ -155,6 +152,7 @@ async fn main() -> std::io::Result<()> {
             player_cache.clone(),
         ),
     );
+    let api_key_repo = web::Data::new(backend::player::api_key::ApiKeyRepository::new(db.clone()));
 
     // Initialize venue repository with Google Places API if configured
     let google_config = if let Some(api_key) = &config.google.location_api_key {
@@ -172,14 +170,15 @@ async fn main() -> std::io::Result<()> {
             db.clone(),
             google_config.clone(),
             venue_cache.clone(),
+        )
+        .with_places_locale(
+            config.google.places_region.clone(),
+            config.google.places_language.clone(),
         ),
     );
 
     // Initialize game repository with BGG service
-    let bgg_service = BGGService::new_with_config(&BGGConfig {
-        api_url: config.bgg.api_url.clone(),
-        api_token: config.bgg.api_token.clone(),
-    });
+    let bgg_service = BGGService::new_with_config(&config.bgg);
     log::info!("BGG API configured with URL: {}", config.bgg.api_url);
     if config.bgg.api_token.is_some() {
         log::info!("BGG API token configured (Bearer authentication enabled)");
@@ -221,7 +220,11 @@ async fn main() -> std::io::Result<()> {
 
     // Initialize ratings scheduler
     let ratings_repo = backend::ratings::repository::RatingsRepository::new(db.clone());
-    let ratings_usecase = backend::ratings::usecase::RatingsUsecase::new(ratings_repo);
+    let glicko_params = config.ratings.glicko_params();
+    let rating_period_interval = config.ratings.period_interval();
+    let ratings_usecase =
+        backend::ratings::usecase::RatingsUsecase::with_params(ratings_repo, glicko_params)
+            .with_period_interval(rating_period_interval);
     let mut ratings_scheduler =
         backend::ratings::scheduler::RatingsScheduler::new(ratings_usecase.clone());
 
@@ -235,7 +238,25 @@ async fn main() -> std::io::Result<()> {
     // Store scheduler in web::Data for health checks
     let scheduler_data = web::Data::new(ratings_scheduler.clone());
 
-    // Analytics components will be initialized in the route configuration
+    // Create any analytics collections a new release reads or writes
+    let analytics_repo =
+        backend::analytics::AnalyticsRepository::new(db.clone(), config.database.clone());
+    let missing = analytics_repo.missing_collections().await;
+    if missing.is_empty() {
+        log::info!("All analytics collections are present");
+    } else {
+        log::warn!(
+            "Creating missing analytics collections: {}",
+            missing.join(", ")
+        );
+        if let Err(e) = analytics_repo.create_collections().await {
+            log::error!("Failed to create analytics collections: {}", e);
+        }
+    }
+
+    // Analytics cache is shared across workers so admin clears reach every instance
+    let analytics_cache = backend::analytics::AnalyticsCache::new_default()
+        .with_key_prefix(&config.analytics.cache_key_prefix);
 
     // Start HTTP server
     log::info!(
@@ -265,21 +286,40 @@ async fn main() -> std::io::Result<()> {
         }
     };
     let metrics_data = web::Data::new(metrics.clone());
+    let maintenance_config = config.maintenance.clone();
+    let security_config = config.security.clone();
+    let password_config = web::Data::new(config.passwords.clone());
+    let contest_config = web::Data::new(config.contest.clone());
+    let features = config.features.clone();
+    log::info!("Feature flags: {:?}", features);
+    // With webhooks off, contests are created as if no endpoints were set
+    let webhook_config = if features.webhooks {
+        config.contest.webhooks.clone()
+    } else {
+        backend::config::WebhookConfig {
+            endpoints: Vec::new(),
+            ..config.contest.webhooks.clone()
+        }
+    };
+    let contest_webhooks = web::Data::new(backend::contest::webhook::ContestWebhooks::new(
+        webhook_config,
+    ));
 
-    HttpServer::new(move || {
-        // Configure JSON error handler to always return JSON (not HTML)
-        let json_config = actix_web::web::JsonConfig::default()
-            .limit(256 * 1024)
-            .error_handler(|err, _req| {
-                // Convert JSON deserialization errors to JSON responses
-                let error = ApiError::bad_request(&format!("Invalid JSON: {}", err));
-                error.into()
-            });
+    let workers = config.server.workers;
+    let mut server = HttpServer::new(move || {
+        // JSON body errors, including a wrong content type, come back as JSON
+        let json_config = backend::error::json_config(256 * 1024);
 
         App::new()
+            .wrap(backend::error::json_error_handlers())
+            .wrap(backend::middleware::MaintenanceMode::new(
+                &maintenance_config,
+            ))
             .wrap(backend::middleware::Logger::with_metrics(metrics.clone()))
-            .wrap(backend::middleware::SecurityHeaders)
-            .wrap(backend::middleware::cors_middleware())
+            .wrap(backend::middleware::SecurityHeaders::new(&security_config))
+            .wrap(backend::middleware::cors_middleware_with_exempt_paths(
+                &security_config.cors_exempt_paths,
+            ))
             .app_data(metrics_data.clone())
             .app_data(json_config)
             .app_data(redis_data.clone())
@@ -290,12 +330,12 @@ async fn main() -> std::io::Result<()> {
             .app_data(game_repo.clone())
             .app_data(contest_repo.clone())
             .app_data(session_store.clone())
-            .service(utoipa_swagger_ui::SwaggerUi::new("/swagger-ui/{_:.*}").url(
-                "/api-docs/openapi.json",
-                <backend::openapi::ApiDoc as OpenApi>::openapi(),
-            ))
+            .app_data(password_config.clone())
+            .configure(|cfg| backend::openapi::configure_api_docs(cfg, &features))
             .service(backend::health::health_check)
             .service(backend::health::detailed_health_check)
+            .service(backend::health::liveness)
+            .service(backend::health::readiness)
             .service(backend::health::scheduler_health_check)
             .service(backend::health::version_info)
             .service(backend::health::metrics_endpoint)
@@ -314,7 +354,12 @@ async fn main() -> std::io::Result<()> {
                             .service(backend::player::controller::me_handler_prod)
                             .service(backend::player::controller::update_email_handler_prod)
                             .service(backend::player::controller::update_handle_handler_prod)
-                            .service(backend::player::controller::update_password_handler_prod),
+                            .service(backend::player::controller::update_password_handler_prod)
+                            .service(backend::player::controller::update_profile_handler_prod)
+                            .app_data(api_key_repo.clone())
+                            .service(backend::player::controller::create_api_key_handler)
+                            .service(backend::player::controller::list_api_keys_handler)
+                            .service(backend::player::controller::revoke_api_key_handler),
                     ),
             )
             .service(
@@ -322,7 +367,7 @@ async fn main() -> std::io::Result<()> {
                     .wrap(backend::auth::AuthMiddleware {
                         redis: std::sync::Arc::new(redis_data.get_ref().clone()),
                     })
-                    .app_data(actix_web::web::JsonConfig::default().limit(64 * 1024))
+                    .app_data(backend::error::json_config(64 * 1024))
                     .service(backend::venue::controller::get_all_venues_handler)
                     .service(backend::venue::controller::search_venues_handler)
                     .service(backend::venue::controller::search_venues_db_handler)
@@ -337,7 +382,7 @@ async fn main() -> std::io::Result<()> {
                     .wrap(backend::auth::AuthMiddleware {
                         redis: std::sync::Arc::new(redis_data.get_ref().clone()),
                     })
-                    .app_data(actix_web::web::JsonConfig::default().limit(64 * 1024))
+                    .app_data(backend::error::json_config(64 * 1024))
                     .service(backend::game::controller::get_all_games_handler)
                     .service(backend::game::controller::search_games_handler)
                     .service(backend::game::controller::search_games_db_handler)
@@ -351,12 +396,22 @@ async fn main() -> std::io::Result<()> {
                     .wrap(backend::auth::AuthMiddleware {
                         redis: std::sync::Arc::new(redis_data.get_ref().clone()),
                     })
-                    .app_data(actix_web::web::JsonConfig::default().limit(128 * 1024))
+                    .wrap(backend::auth::ApiKeyMiddleware {
+                        keys: api_key_repo.clone().into_inner(),
+                    })
+                    .app_data(backend::error::json_config(128 * 1024))
                     .app_data(player_repo.clone())
+                    .app_data(contest_config.clone())
+                    .app_data(contest_webhooks.clone())
+                    .app_data(web::Data::new(analytics_cache.clone()))
                     .service(backend::contest::controller::create_contest_handler)
+                    .service(backend::contest::controller::contest_csv_template_handler)
+                    .service(backend::contest::controller::import_contests_csv_handler)
                     .service(backend::contest::controller::get_player_game_contests_handler)
                     .service(backend::contest::controller::search_contests_handler)
-                    .service(backend::contest::controller::get_contest_handler),
+                    .service(backend::contest::controller::get_contest_timeline_handler)
+                    .service(backend::contest::controller::get_contest_handler)
+                    .service(backend::contest::controller::delete_contest_handler),
             )
             .configure(|cfg| {
                 log::debug!("Registering /api/analytics routes");
@@ -365,9 +420,24 @@ async fn main() -> std::io::Result<()> {
                     db.clone(),
                     config.database.clone(),
                     std::sync::Arc::new(redis_data.get_ref().clone()),
+                    analytics_cache.clone(),
+                    config.analytics.clone(),
                 );
             })
             .configure(|cfg| {
+                log::debug!("Registering /api/admin routes");
+                backend::admin::controller::configure_routes(
+                    cfg,
+                    db.clone(),
+                    std::sync::Arc::new(redis_data.get_ref().clone()),
+                    analytics_cache.clone(),
+                    config.clone(),
+                );
+            })
+            .configure(|cfg| {
+                if !features.client_analytics {
+                    return;
+                }
                 log::debug!("Registering /api/client routes");
                 backend::client_analytics::controller::configure_routes(
                     cfg,
@@ -376,6 +446,9 @@ async fn main() -> std::io::Result<()> {
                 );
             })
             .configure(|cfg| {
+                if !features.client_analytics {
+                    return;
+                }
                 log::debug!("Registering enhanced analytics routes");
                 backend::client_analytics::controller::configure_enhanced_routes(
                     cfg,
@@ -389,6 +462,8 @@ async fn main() -> std::io::Result<()> {
                     db.clone(),
                     ratings_scheduler.clone(),
                     redis_client_for_ratings.clone(),
+                    glicko_params,
+                    rating_period_interval,
                 );
             })
             .configure(|cfg| {
@@ -399,8 +474,15 @@ async fn main() -> std::io::Result<()> {
                     std::env::var("GOOGLE_LOCATION_API").unwrap_or_default(),
                 );
             })
-    })
-    .bind((config.server.host.as_str(), config.server.port))?
-    .run()
-    .await
+            // Unmatched paths get the same JSON error body as the rest of the API
+            .default_service(web::route().to(backend::error::not_found_handler))
+    });
+    // Unset leaves actix's one worker per core
+    if let Some(workers) = workers {
+        server = server.workers(workers);
+    }
+    server
+        .bind((config.server.host.as_str(), config.server.port))?
+        .run()
+        .await
 }
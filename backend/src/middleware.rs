@@ -1,4 +1,5 @@
 use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::http::Method;
 use actix_web::HttpMessage;
 use actix_web::{
     dev::{Service, ServiceRequest, ServiceResponse, Transform},
@@ -372,7 +373,113 @@ where
     }
 }
 
+/// Whether `path` is `prefix` itself or lies below it
+fn path_is_under(path: &str, prefix: &str) -> bool {
+    path == prefix
+        || path
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// Paths that keep taking writes in maintenance mode: health and metrics
+/// probes, login, and the admin API operators use during the maintenance
+const MAINTENANCE_EXEMPT_PATHS: [&str; 6] = [
+    "/health",
+    "/healthz",
+    "/readyz",
+    "/metrics",
+    "/api/players/login",
+    "/api/admin",
+];
+
+/// Maintenance mode middleware: refuses writes with 503 while reads go on
+///
+/// POST, PUT, PATCH and DELETE requests get a JSON `MAINTENANCE` error
+/// carrying the configured message; health and metrics probes, login and
+/// the admin API are never blocked.
+pub struct MaintenanceMode {
+    enabled: bool,
+    message: String,
+}
+
+impl MaintenanceMode {
+    pub fn new(config: &crate::config::MaintenanceConfig) -> Self {
+        Self {
+            enabled: config.enabled,
+            message: config.message.clone(),
+        }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for MaintenanceMode
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = MaintenanceModeService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(MaintenanceModeService {
+            service: Rc::new(service),
+            enabled: self.enabled,
+            message: self.message.clone(),
+        }))
+    }
+}
+
+pub struct MaintenanceModeService<S> {
+    service: Rc<S>,
+    enabled: bool,
+    message: String,
+}
+
+impl<S, B> Service<ServiceRequest> for MaintenanceModeService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let is_write = matches!(
+            *req.method(),
+            Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+        );
+        let is_exempt = MAINTENANCE_EXEMPT_PATHS
+            .iter()
+            .any(|path| path_is_under(req.path(), path));
+        if self.enabled && is_write && !is_exempt {
+            warn!("maintenance mode refused {} {}", req.method(), req.path());
+            let error = crate::error::ApiError::new("MAINTENANCE", &self.message, 503)
+                .with_code(crate::error::codes::MAINTENANCE);
+            return Box::pin(ready(Err(error.into())));
+        }
+
+        let service = self.service.clone();
+        Box::pin(async move { service.call(req).await })
+    }
+}
+
 pub fn cors_middleware() -> actix_cors::Cors {
+    cors_middleware_with_exempt_paths(&[])
+}
+
+/// [`cors_middleware`], additionally letting any origin call `exempt_paths`
+/// and everything below them
+pub fn cors_middleware_with_exempt_paths(exempt_paths: &[String]) -> actix_cors::Cors {
+    let exempt_paths = exempt_paths.to_vec();
     let mut cors = actix_cors::Cors::default()
         .allowed_origin("http://localhost:50003")
         .allowed_origin("http://127.0.0.1:50003")
@@ -382,9 +489,19 @@ pub fn cors_middleware() -> actix_cors::Cors {
             actix_web::http::header::CONTENT_TYPE,
             actix_web::http::header::AUTHORIZATION,
         ])
+        // Let browser clients read the pagination headers
+        .expose_headers(vec!["Link", crate::pagination::TOTAL_COUNT_HEADER])
         .supports_credentials()
         .max_age(3600);
 
+    if !exempt_paths.is_empty() {
+        cors = cors.allowed_origin_fn(move |_origin, head| {
+            exempt_paths
+                .iter()
+                .any(|path| path_is_under(head.uri.path(), path))
+        });
+    }
+
     // Add production domain if in production environment
     if let Ok(env) = std::env::var("RUST_ENV") {
         if env == "production" {
@@ -398,7 +515,29 @@ pub fn cors_middleware() -> actix_cors::Cors {
 }
 
 /// Security headers middleware
-pub struct SecurityHeaders;
+///
+/// Every response gets `nosniff` and `DENY` framing, plus the configured
+/// Content-Security-Policy and Strict-Transport-Security when set.
+pub struct SecurityHeaders {
+    content_security_policy: Option<HeaderValue>,
+    strict_transport_security: Option<HeaderValue>,
+}
+
+impl SecurityHeaders {
+    pub fn new(config: &crate::config::SecurityConfig) -> Self {
+        Self {
+            // Config validation rejects policies that are not valid header values
+            content_security_policy: config
+                .content_security_policy
+                .as_deref()
+                .and_then(|policy| HeaderValue::from_str(policy).ok()),
+            strict_transport_security: config.hsts_max_age_secs.map(|secs| {
+                HeaderValue::from_str(&format!("max-age={}; includeSubDomains", secs))
+                    .expect("formatted max-age is a valid header value")
+            }),
+        }
+    }
+}
 
 impl<S, B> Transform<S, ServiceRequest> for SecurityHeaders
 where
@@ -415,12 +554,16 @@ where
     fn new_transform(&self, service: S) -> Self::Future {
         ready(Ok(SecurityHeadersMiddleware {
             service: Rc::new(service),
+            content_security_policy: self.content_security_policy.clone(),
+            strict_transport_security: self.strict_transport_security.clone(),
         }))
     }
 }
 
 pub struct SecurityHeadersMiddleware<S> {
     service: Rc<S>,
+    content_security_policy: Option<HeaderValue>,
+    strict_transport_security: Option<HeaderValue>,
 }
 
 impl<S, B> Service<ServiceRequest> for SecurityHeadersMiddleware<S>
@@ -439,9 +582,8 @@ where
 
     fn call(&self, req: ServiceRequest) -> Self::Future {
         let svc = self.service.clone();
-        let is_production = std::env::var("RUST_ENV")
-            .unwrap_or_default()
-            .eq_ignore_ascii_case("production");
+        let content_security_policy = self.content_security_policy.clone();
+        let strict_transport_security = self.strict_transport_security.clone();
 
         Box::pin(async move {
             let mut res = svc.call(req).await?;
@@ -467,12 +609,13 @@ where
                 HeaderValue::from_static("1; mode=block"),
             );
 
-            // HSTS - only in production (HTTPS)
-            if is_production {
-                headers.insert(
-                    HeaderName::from_static("strict-transport-security"),
-                    HeaderValue::from_static("max-age=31536000; includeSubDomains"),
-                );
+            if let Some(policy) = content_security_policy {
+                headers.insert(HeaderName::from_static("content-security-policy"), policy);
+            }
+
+            // HSTS - only configured where the API is served over HTTPS
+            if let Some(hsts) = strict_transport_security {
+                headers.insert(HeaderName::from_static("strict-transport-security"), hsts);
             }
 
             Ok(res)
@@ -619,6 +762,127 @@ mod tests {
         assert!(duration >= Duration::from_millis(10)); // Should take at least 10ms
     }
 
+    #[actix_web::test]
+    async fn test_maintenance_mode_blocks_writes_but_not_reads_or_health() {
+        let config = crate::config::MaintenanceConfig {
+            enabled: true,
+            message: "Back soon".to_string(),
+        };
+        let app = test::init_service(
+            App::new()
+                .wrap(MaintenanceMode::new(&config))
+                .route("/items", web::get().to(|| async { "items" }))
+                .route("/items", web::delete().to(|| async { "deleted" }))
+                .route("/health/ping", web::post().to(|| async { "pong" }))
+                .route("/api/players/login", web::post().to(|| async { "session" }))
+                .route(
+                    "/api/players/register",
+                    web::post().to(|| async { "player" }),
+                )
+                .route(
+                    "/api/admin/cache/clear",
+                    web::post().to(|| async { "cleared" }),
+                ),
+        )
+        .await;
+
+        let req = test::TestRequest::delete().uri("/items").to_request();
+        let err = test::try_call_service(&app, req).await.unwrap_err();
+        let resp = err.error_response();
+        assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = actix_web::body::to_bytes(resp.into_body()).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"], "MAINTENANCE");
+        assert_eq!(body["code"], "server.maintenance");
+        assert_eq!(body["message"], "Back soon");
+
+        let req = test::TestRequest::get().uri("/items").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::OK);
+
+        let req = test::TestRequest::post().uri("/health/ping").to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::OK);
+
+        // Admins can still sign in and operate while writes are paused
+        for uri in ["/api/players/login", "/api/admin/cache/clear"] {
+            let req = test::TestRequest::post().uri(uri).to_request();
+            assert_eq!(test::call_service(&app, req).await.status(), StatusCode::OK);
+        }
+        let req = test::TestRequest::post()
+            .uri("/api/players/register")
+            .to_request();
+        let err = test::try_call_service(&app, req).await.unwrap_err();
+        assert_eq!(
+            err.error_response().status(),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_security_headers_and_cors_exempt_paths_follow_config() {
+        let config = crate::config::SecurityConfig {
+            content_security_policy: Some("default-src 'none'".to_string()),
+            hsts_max_age_secs: Some(600),
+            ..Default::default()
+        };
+        let app = test::init_service(
+            App::new()
+                .wrap(SecurityHeaders::new(&config))
+                .wrap(cors_middleware_with_exempt_paths(&config.cors_exempt_paths))
+                .route("/api/items", web::get().to(|| async { "items" }))
+                .route("/healthz", web::get().to(|| async { "ok" })),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/api/items").to_request();
+        let resp = test::call_service(&app, req).await;
+        let headers = resp.headers();
+        assert_eq!(headers.get("x-content-type-options").unwrap(), "nosniff");
+        assert_eq!(headers.get("x-frame-options").unwrap(), "DENY");
+        assert_eq!(
+            headers.get("content-security-policy").unwrap(),
+            "default-src 'none'"
+        );
+        assert_eq!(
+            headers.get("strict-transport-security").unwrap(),
+            "max-age=600; includeSubDomains"
+        );
+
+        let from_monitor = |uri: &str| {
+            test::TestRequest::get()
+                .uri(uri)
+                .insert_header((actix_web::http::header::ORIGIN, "https://monitor.example"))
+                .to_request()
+        };
+        let resp = test::call_service(&app, from_monitor("/healthz")).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            resp.headers()
+                .get(actix_web::http::header::ACCESS_CONTROL_ALLOW_ORIGIN)
+                .unwrap(),
+            "https://monitor.example"
+        );
+        let resp = test::call_service(&app, from_monitor("/api/items")).await;
+        assert!(!resp
+            .headers()
+            .contains_key(actix_web::http::header::ACCESS_CONTROL_ALLOW_ORIGIN));
+
+        // Without a policy or max-age the headers are left off
+        let app = test::init_service(
+            App::new()
+                .wrap(SecurityHeaders::new(&crate::config::SecurityConfig {
+                    content_security_policy: None,
+                    ..Default::default()
+                }))
+                .route("/api/items", web::get().to(|| async { "items" })),
+        )
+        .await;
+        let req = test::TestRequest::get().uri("/api/items").to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.headers().get("x-frame-options").unwrap(), "DENY");
+        assert!(!resp.headers().contains_key("content-security-policy"));
+        assert!(!resp.headers().contains_key("strict-transport-security"));
+    }
+
     #[actix_web::test]
     async fn test_logger_middleware_with_peer_addr() {
         let logger = Logger::new();
@@ -1,11 +1,16 @@
+use crate::config::FeatureFlags;
 use crate::error::ApiError;
+use actix_web::web;
 use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
 
 #[derive(OpenApi)]
 #[openapi(
     paths(
         crate::health::health_check,
         crate::health::detailed_health_check,
+        crate::health::liveness,
+        crate::health::readiness,
         crate::health::version_info,
     ),
     components(schemas(
@@ -41,3 +46,14 @@ use utoipa::OpenApi;
     )
 )]
 pub struct ApiDoc;
+
+/// Serves Swagger UI and the OpenAPI document, unless API docs are turned off
+pub fn configure_api_docs(cfg: &mut web::ServiceConfig, features: &FeatureFlags) {
+    if !features.api_docs {
+        log::info!("API docs are disabled");
+        return;
+    }
+    cfg.service(
+        SwaggerUi::new("/swagger-ui/{_:.*}").url("/api-docs/openapi.json", ApiDoc::openapi()),
+    );
+}
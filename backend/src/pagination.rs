@@ -0,0 +1,161 @@
+//! Standard pagination headers for list endpoints
+//!
+//! Paginated responses keep their JSON envelope and also carry
+//! `X-Total-Count` and an RFC 5988 `Link` header with `next`/`prev`
+//! relations, so generic clients can page without knowing the envelope.
+//! Link targets are the request path with `page` replaced and every other
+//! query parameter kept, e.g. `</api/contests/search?q=cat&page=3>; rel="next"`.
+//! Endpoints paged by `limit`/`offset` get the same headers with `offset`
+//! replaced instead.
+
+use actix_web::http::header::LINK;
+use actix_web::{HttpRequest, HttpResponseBuilder};
+
+/// Header carrying the total number of items across all pages
+pub const TOTAL_COUNT_HEADER: &str = "X-Total-Count";
+
+/// Adds `X-Total-Count` and, when there are neighbouring pages, `Link`
+///
+/// `page` is 1-based, as accepted by the list endpoints.
+pub fn insert_pagination_headers(
+    response: &mut HttpResponseBuilder,
+    req: &HttpRequest,
+    page: u32,
+    page_size: u32,
+    total: u64,
+) {
+    response.insert_header((TOTAL_COUNT_HEADER, total.to_string()));
+    if let Some(link) = link_header(req.path(), req.query_string(), page, page_size, total) {
+        response.insert_header((LINK, link));
+    }
+}
+
+/// Adds the same headers for endpoints paged by `limit`/`offset`
+pub fn insert_offset_pagination_headers(
+    response: &mut HttpResponseBuilder,
+    req: &HttpRequest,
+    limit: i32,
+    offset: i32,
+    total: i64,
+) {
+    response.insert_header((TOTAL_COUNT_HEADER, total.to_string()));
+    if let Some(link) = offset_link_header(req.path(), req.query_string(), limit, offset, total) {
+        response.insert_header((LINK, link));
+    }
+}
+
+/// `Link` value with `next`/`prev` relations, or None for a lone page
+pub fn link_header(
+    path: &str,
+    query_string: &str,
+    page: u32,
+    page_size: u32,
+    total: u64,
+) -> Option<String> {
+    let mut links = Vec::new();
+    if page_size > 0 && u64::from(page) * u64::from(page_size) < total {
+        links.push(format!(
+            "<{}>; rel=\"next\"",
+            with_param(path, query_string, "page", page + 1)
+        ));
+    }
+    if page > 1 {
+        links.push(format!(
+            "<{}>; rel=\"prev\"",
+            with_param(path, query_string, "page", page - 1)
+        ));
+    }
+    (!links.is_empty()).then(|| links.join(", "))
+}
+
+/// `Link` value for a `limit`/`offset` window, or None for a lone page
+///
+/// The `prev` window starts at zero rather than going negative.
+pub fn offset_link_header(
+    path: &str,
+    query_string: &str,
+    limit: i32,
+    offset: i32,
+    total: i64,
+) -> Option<String> {
+    let mut links = Vec::new();
+    if limit > 0 && i64::from(offset) + i64::from(limit) < total {
+        links.push(format!(
+            "<{}>; rel=\"next\"",
+            with_param(path, query_string, "offset", offset + limit)
+        ));
+    }
+    if offset > 0 {
+        links.push(format!(
+            "<{}>; rel=\"prev\"",
+            with_param(path, query_string, "offset", (offset - limit).max(0))
+        ));
+    }
+    (!links.is_empty()).then(|| links.join(", "))
+}
+
+fn with_param(path: &str, query_string: &str, name: &str, value: impl ToString) -> String {
+    let mut query = url::form_urlencoded::Serializer::new(String::new());
+    for (key, existing) in url::form_urlencoded::parse(query_string.as_bytes()) {
+        if key != name {
+            query.append_pair(&key, &existing);
+        }
+    }
+    query.append_pair(name, &value.to_string());
+    format!("{}?{}", path, query.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_link_header_replaces_page_and_keeps_other_params() {
+        let link = link_header(
+            "/api/contests/search",
+            "q=cat&page=2&page_size=10",
+            2,
+            10,
+            45,
+        );
+        assert_eq!(
+            link.as_deref(),
+            Some(
+                "</api/contests/search?q=cat&page_size=10&page=3>; rel=\"next\", \
+                 </api/contests/search?q=cat&page_size=10&page=1>; rel=\"prev\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_link_header_stops_at_the_last_page() {
+        assert_eq!(
+            link_header("/items", "page=3", 3, 10, 30).as_deref(),
+            Some("</items?page=2>; rel=\"prev\"")
+        );
+        assert_eq!(link_header("/items", "", 1, 10, 10), None);
+        assert_eq!(link_header("/items", "", 1, 0, 10), None);
+    }
+
+    #[test]
+    fn test_offset_link_header_moves_the_window() {
+        let query = "sort_by=win_rate&limit=10&offset=10";
+        assert_eq!(
+            offset_link_header("/api/analytics/players/stats", query, 10, 10, 25).as_deref(),
+            Some(
+                "</api/analytics/players/stats?sort_by=win_rate&limit=10&offset=20>; rel=\"next\", \
+                 </api/analytics/players/stats?sort_by=win_rate&limit=10&offset=0>; rel=\"prev\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_offset_link_header_clamps_prev_and_stops_at_the_end() {
+        assert_eq!(
+            offset_link_header("/items", "offset=5", 10, 5, 15).as_deref(),
+            Some("</items?offset=0>; rel=\"prev\"")
+        );
+        assert_eq!(offset_link_header("/items", "", 10, 0, 10), None);
+        assert_eq!(offset_link_header("/items", "", 0, 0, 10), None);
+    }
+}
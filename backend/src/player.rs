@@ -1,5 +1,7 @@
+pub mod api_key;
 pub mod controller;
 pub mod error;
+pub mod password;
 pub mod repository;
 pub mod session;
 pub mod usecase;
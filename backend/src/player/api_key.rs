@@ -0,0 +1,205 @@
+//! API keys for programmatic access
+//!
+//! Keys let bots and service accounts submit contests without a user session.
+//! Only a SHA-256 hash of each key is stored: keys are long random strings, so
+//! a fast hash is enough to make a leaked `api_key` collection useless.
+
+use arangors::client::reqwest::ReqwestClient;
+use arangors::Database;
+use chrono::{DateTime, FixedOffset};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use shared::dto::player::ApiKeyDto;
+
+/// Every API key starts with this, telling keys apart from session ids
+pub const API_KEY_PREFIX: &str = "stg_";
+
+/// Random characters after the prefix
+const API_KEY_RANDOM_LENGTH: usize = 40;
+
+/// Characters of a key kept in clear to identify it in listings
+const DISPLAY_PREFIX_LENGTH: usize = 12;
+
+/// An API key as stored in the `api_key` collection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKeyRecord {
+    #[serde(rename = "_key")]
+    pub id: String,
+    pub player_id: String,
+    pub name: String,
+    pub prefix: String,
+    pub key_hash: String,
+    pub created_at: DateTime<FixedOffset>,
+    pub revoked_at: Option<DateTime<FixedOffset>>,
+}
+
+impl From<&ApiKeyRecord> for ApiKeyDto {
+    fn from(record: &ApiKeyRecord) -> Self {
+        Self {
+            id: record.id.clone(),
+            name: record.name.clone(),
+            prefix: record.prefix.clone(),
+            created_at: record.created_at,
+            revoked_at: record.revoked_at,
+        }
+    }
+}
+
+/// Whether a bearer token is an API key rather than a session id
+pub fn is_api_key(token: &str) -> bool {
+    token.starts_with(API_KEY_PREFIX)
+}
+
+/// Hex SHA-256 of `key`, as stored and looked up
+pub fn hash_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.as_bytes()))
+}
+
+fn generate_key() -> String {
+    let random: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(API_KEY_RANDOM_LENGTH)
+        .map(char::from)
+        .collect();
+    format!("{}{}", API_KEY_PREFIX, random)
+}
+
+#[derive(Clone)]
+pub struct ApiKeyRepository {
+    pub db: Database<ReqwestClient>,
+}
+
+impl ApiKeyRepository {
+    pub fn new(db: Database<ReqwestClient>) -> Self {
+        Self { db }
+    }
+
+    /// Creates a key for `player_id`, returning its record and the key itself
+    pub async fn mint(
+        &self,
+        player_id: &str,
+        name: &str,
+    ) -> Result<(ApiKeyRecord, String), String> {
+        let key = generate_key();
+        let query = arangors::AqlQuery::builder()
+            .query(
+                r#"
+                INSERT {
+                    player_id: @player_id,
+                    name: @name,
+                    prefix: @prefix,
+                    key_hash: @key_hash,
+                    created_at: @created_at,
+                    revoked_at: null
+                } INTO api_key
+                RETURN NEW
+                "#,
+            )
+            .bind_var("player_id", player_id)
+            .bind_var("name", name)
+            .bind_var("prefix", &key[..DISPLAY_PREFIX_LENGTH])
+            .bind_var("key_hash", hash_key(&key))
+            .bind_var("created_at", chrono::Utc::now().fixed_offset().to_rfc3339())
+            .build();
+        let mut records: Vec<ApiKeyRecord> = self
+            .db
+            .aql_query(query)
+            .await
+            .map_err(|e| format!("Failed to store API key: {}", e))?;
+        let record = records
+            .pop()
+            .ok_or_else(|| "Failed to store API key".to_string())?;
+        Ok((record, key))
+    }
+
+    /// Keys belonging to `player_id`, newest first, revoked ones included
+    pub async fn list(&self, player_id: &str) -> Result<Vec<ApiKeyRecord>, String> {
+        let query = arangors::AqlQuery::builder()
+            .query(
+                r#"
+                FOR k IN api_key
+                FILTER k.player_id == @player_id
+                SORT k.created_at DESC
+                RETURN k
+                "#,
+            )
+            .bind_var("player_id", player_id)
+            .build();
+        self.db
+            .aql_query(query)
+            .await
+            .map_err(|e| format!("Failed to list API keys: {}", e))
+    }
+
+    /// Revokes one of `player_id`'s active keys; false if there was none
+    pub async fn revoke(&self, player_id: &str, key_id: &str) -> Result<bool, String> {
+        let query = arangors::AqlQuery::builder()
+            .query(
+                r#"
+                FOR k IN api_key
+                FILTER k._key == @key_id AND k.player_id == @player_id AND k.revoked_at == null
+                UPDATE k WITH { revoked_at: @revoked_at } IN api_key
+                RETURN NEW._key
+                "#,
+            )
+            .bind_var("key_id", key_id)
+            .bind_var("player_id", player_id)
+            .bind_var("revoked_at", chrono::Utc::now().fixed_offset().to_rfc3339())
+            .build();
+        let revoked: Vec<String> = self
+            .db
+            .aql_query(query)
+            .await
+            .map_err(|e| format!("Failed to revoke API key: {}", e))?;
+        Ok(!revoked.is_empty())
+    }
+
+    /// Email of the player owning `key`, if it is an active key
+    pub async fn authenticate(&self, key: &str) -> Result<Option<String>, String> {
+        let query = arangors::AqlQuery::builder()
+            .query(
+                r#"
+                FOR k IN api_key
+                FILTER k.key_hash == @key_hash AND k.revoked_at == null
+                LET player = DOCUMENT(k.player_id)
+                FILTER player != null
+                LIMIT 1
+                RETURN player.email
+                "#,
+            )
+            .bind_var("key_hash", hash_key(key))
+            .build();
+        let mut emails: Vec<String> = self
+            .db
+            .aql_query(query)
+            .await
+            .map_err(|e| format!("Failed to look up API key: {}", e))?;
+        Ok(emails.pop())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_keys_are_prefixed_and_unique() {
+        let first = generate_key();
+        let second = generate_key();
+        assert!(is_api_key(&first));
+        assert_eq!(first.len(), API_KEY_PREFIX.len() + API_KEY_RANDOM_LENGTH);
+        assert_ne!(first, second);
+        assert!(!is_api_key("3f1c9a52-session-id"));
+    }
+
+    #[test]
+    fn test_hash_key_is_stable_hex_sha256() {
+        assert_eq!(
+            hash_key("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_ne!(hash_key("stg_a"), hash_key("stg_b"));
+    }
+}
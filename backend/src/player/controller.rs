@@ -1,19 +1,58 @@
-use actix_web::{get, post, put, web, HttpMessage, HttpRequest, HttpResponse};
+use actix_web::{delete, get, patch, post, put, web, HttpMessage, HttpRequest, HttpResponse};
 
-use crate::error::ApiError;
+use crate::config::PasswordConfig;
+use crate::error::{codes, ApiError};
+use crate::player::api_key::ApiKeyRepository;
 use crate::player::error::PlayerError;
 use crate::player::repository::{PlayerRepository, PlayerRepositoryImpl};
 use crate::player::session::SessionStore;
 use crate::player::usecase::{PlayerUseCase, PlayerUseCaseImpl};
 use log::{error, info, warn};
 use shared::dto::player::{
-    CreatePlayerRequest, LoginResponse, PlayerDto, UpdateEmailRequest, UpdateHandleRequest,
-    UpdatePasswordRequest, UpdateResponse,
+    ApiKeyDto, CreateApiKeyRequest, CreatePlayerRequest, CreatedApiKeyResponse, LoginResponse,
+    PlayerDto, PlayerProfileDto, UpdateEmailRequest, UpdateHandleRequest, UpdatePasswordRequest,
+    UpdateProfileRequest, UpdateResponse,
 };
-use shared::models::player::PlayerLogin;
+use shared::models::player::{Player, PlayerLogin};
+use shared::validation::{validate_email, validate_password};
 use uuid::Uuid;
+use validator::Validate;
+
+/// Player use case hashing passwords per the app's [`PasswordConfig`],
+/// falling back to the defaults when none is registered
+fn player_usecase<R>(req: &HttpRequest, repo: &web::Data<R>) -> PlayerUseCaseImpl<R>
+where
+    R: PlayerRepository + Clone + 'static,
+{
+    PlayerUseCaseImpl {
+        repo: repo.get_ref().clone(),
+        passwords: req
+            .app_data::<web::Data<PasswordConfig>>()
+            .map(|config| config.get_ref().clone())
+            .unwrap_or_default(),
+    }
+}
+
+/// Stamps a successful login, with the client address, on the player
+///
+/// A failure to store it is logged and does not fail the login.
+async fn record_login<R: PlayerRepository>(repo: &R, req: &HttpRequest, player: Player) -> Player {
+    let stamped = Player {
+        last_login_at: Some(chrono::Utc::now().fixed_offset()),
+        last_login_ip: req.peer_addr().map(|peer| peer.ip().to_string()),
+        ..player
+    };
+    match repo.update(stamped.clone()).await {
+        Ok(updated) => updated,
+        Err(e) => {
+            warn!("Failed to record login for {}: {}", stamped.email, e);
+            stamped
+        }
+    }
+}
 
 pub async fn login_handler_impl<R, S>(
+    req: HttpRequest,
     login: web::Json<PlayerLogin>,
     session_store: web::Data<S>,
     repo: web::Data<R>,
@@ -23,12 +62,11 @@ where
     S: SessionStore + 'static,
 {
     let email = login.email.clone();
-    let usecase = PlayerUseCaseImpl {
-        repo: repo.get_ref().clone(),
-    };
+    let usecase = player_usecase(&req, &repo);
 
     match usecase.login(login.into_inner()).await {
         Ok(player) => {
+            let player = record_login(repo.get_ref(), &req, player).await;
             let session_id = Uuid::new_v4().to_string();
             match session_store.set_session(&session_id, &player.email).await {
                 Ok(_) => {
@@ -107,6 +145,7 @@ pub async fn login_handler_prod(
             if count > 10 {
                 return Ok(HttpResponse::TooManyRequests().json(serde_json::json!({
                     "error": "Too Many Requests",
+                    "code": codes::TOO_MANY_REQUESTS,
                     "message": "Too many login attempts. Please try again later."
                 })));
             }
@@ -115,11 +154,10 @@ pub async fn login_handler_prod(
 
     // Inline the login_impl logic so we can set cookies
     let email = login.email.clone();
-    let usecase = PlayerUseCaseImpl {
-        repo: repo.get_ref().clone(),
-    };
+    let usecase = player_usecase(&req, &repo);
     match usecase.login(login.into_inner()).await {
         Ok(player) => {
+            let player = record_login(repo.get_ref(), &req, player).await;
             let session_id = uuid::Uuid::new_v4().to_string();
             match session_store.set_session(&session_id, &player.email).await {
                 Ok(_) => {
@@ -157,16 +195,19 @@ pub async fn login_handler_prod(
 }
 
 pub async fn register_handler_impl<R>(
+    req: HttpRequest,
     registration: web::Json<CreatePlayerRequest>,
     repo: web::Data<R>,
 ) -> Result<HttpResponse, ApiError>
 where
     R: PlayerRepository + Clone + 'static,
 {
+    validate_email(&registration.email)
+        .and_then(|_| validate_password(&registration.password))
+        .map_err(|e| ApiError::validation_error(&e.to_string()))?;
+
     let email = registration.email.clone();
-    let usecase = PlayerUseCaseImpl {
-        repo: repo.get_ref().clone(),
-    };
+    let usecase = player_usecase(&req, &repo);
 
     match usecase.register(registration.into_inner()).await {
         Ok(player) => {
@@ -176,7 +217,7 @@ where
         }
         Err(PlayerError::AlreadyExists) => {
             info!("Registration attempt for existing email: {}", email);
-            Err(PlayerError::AlreadyExists.into())
+            Err(ApiError::from(PlayerError::AlreadyExists).with_code(codes::PLAYER_DUPLICATE_EMAIL))
         }
         Err(e) => {
             error!("Unexpected registration error for {}: {}", email, e);
@@ -187,10 +228,11 @@ where
 
 #[post("/register")]
 pub async fn register_handler_prod(
+    req: HttpRequest,
     registration: web::Json<CreatePlayerRequest>,
     repo: web::Data<PlayerRepositoryImpl>,
 ) -> Result<HttpResponse, ApiError> {
-    register_handler_impl::<PlayerRepositoryImpl>(registration, repo).await
+    register_handler_impl::<PlayerRepositoryImpl>(req, registration, repo).await
 }
 
 pub async fn logout_handler<S: SessionStore + 'static>(
@@ -342,9 +384,10 @@ where
         None => return Err(ApiError::unauthorized("Not authenticated")),
     };
 
-    let usecase = PlayerUseCaseImpl {
-        repo: repo.get_ref().clone(),
-    };
+    validate_email(&update_request.email)
+        .map_err(|e| ApiError::validation_error(&e.to_string()))?;
+
+    let usecase = player_usecase(&req, &repo);
 
     match usecase
         .update_email(&email, &update_request.email, &update_request.password)
@@ -368,7 +411,7 @@ where
                 "Email update attempt with existing email {} by {}",
                 update_request.email, email
             );
-            Err(PlayerError::AlreadyExists.into())
+            Err(ApiError::from(PlayerError::AlreadyExists).with_code(codes::PLAYER_DUPLICATE_EMAIL))
         }
         Err(e) => {
             error!("Unexpected error updating email for {}: {}", email, e);
@@ -399,9 +442,7 @@ where
         None => return Err(ApiError::unauthorized("Not authenticated")),
     };
 
-    let usecase = PlayerUseCaseImpl {
-        repo: repo.get_ref().clone(),
-    };
+    let usecase = player_usecase(&req, &repo);
 
     match usecase
         .update_handle(&email, &update_request.handle, &update_request.password)
@@ -428,7 +469,8 @@ where
                 "Handle update attempt with existing handle {} by {}",
                 update_request.handle, email
             );
-            Err(PlayerError::AlreadyExists.into())
+            Err(ApiError::from(PlayerError::AlreadyExists)
+                .with_code(codes::PLAYER_DUPLICATE_HANDLE))
         }
         Err(e) => {
             error!("Unexpected error updating handle for {}: {}", email, e);
@@ -459,9 +501,10 @@ where
         None => return Err(ApiError::unauthorized("Not authenticated")),
     };
 
-    let usecase = PlayerUseCaseImpl {
-        repo: repo.get_ref().clone(),
-    };
+    validate_password(&update_request.new_password)
+        .map_err(|e| ApiError::validation_error(&e.to_string()))?;
+
+    let usecase = player_usecase(&req, &repo);
 
     match usecase
         .update_password(
@@ -502,3 +545,136 @@ pub async fn update_password_handler_prod(
 ) -> Result<HttpResponse, ApiError> {
     update_password_handler_impl(req, update_request, repo).await
 }
+
+pub async fn update_profile_handler_impl<R>(
+    req: HttpRequest,
+    update_request: web::Json<UpdateProfileRequest>,
+    repo: web::Data<R>,
+) -> Result<HttpResponse, ApiError>
+where
+    R: PlayerRepository + Clone + 'static,
+{
+    let email = match req.extensions().get::<String>() {
+        Some(email) => email.clone(),
+        None => return Err(ApiError::unauthorized("Not authenticated")),
+    };
+
+    let update_request = update_request.into_inner();
+    let blank = |value: &Option<String>| value.as_deref().is_some_and(|v| v.trim().is_empty());
+    if blank(&update_request.firstname) || blank(&update_request.lastname) {
+        return Err(ApiError::validation_error("Name fields cannot be blank"));
+    }
+    if update_request.clear_lastname && update_request.lastname.is_some() {
+        return Err(ApiError::validation_error(
+            "Cannot set and clear the last name at once",
+        ));
+    }
+    update_request.validate()?;
+    let lastname = if update_request.clear_lastname {
+        Some(None)
+    } else {
+        update_request.lastname.as_deref().map(Some)
+    };
+
+    let usecase = player_usecase(&req, &repo);
+
+    match usecase
+        .update_profile(&email, update_request.firstname.as_deref(), lastname)
+        .await
+    {
+        Ok(player) => {
+            info!("Player {} updated profile name fields", email);
+            Ok(HttpResponse::Ok().json(PlayerProfileDto::from(&player)))
+        }
+        Err(e) => {
+            error!("Unexpected error updating profile for {}: {}", email, e);
+            Err(e.into())
+        }
+    }
+}
+
+#[patch("/profile")]
+pub async fn update_profile_handler_prod(
+    req: HttpRequest,
+    update_request: web::Json<UpdateProfileRequest>,
+    repo: web::Data<PlayerRepositoryImpl>,
+) -> Result<HttpResponse, ApiError> {
+    update_profile_handler_impl(req, update_request, repo).await
+}
+
+/// The authenticated player, looked up from the session email
+async fn current_player(
+    req: &HttpRequest,
+    repo: &PlayerRepositoryImpl,
+) -> Result<shared::models::player::Player, ApiError> {
+    let email = match req.extensions().get::<String>() {
+        Some(email) => email.clone(),
+        None => return Err(ApiError::unauthorized("Not authenticated")),
+    };
+    repo.find_by_email(&email)
+        .await
+        .ok_or_else(|| PlayerError::NotFound.into())
+}
+
+#[post("/api-keys")]
+pub async fn create_api_key_handler(
+    req: HttpRequest,
+    create_request: web::Json<CreateApiKeyRequest>,
+    repo: web::Data<PlayerRepositoryImpl>,
+    keys: web::Data<ApiKeyRepository>,
+) -> Result<HttpResponse, ApiError> {
+    let player = current_player(&req, &repo).await?;
+    let create_request = create_request.into_inner();
+    if create_request.name.trim().is_empty() {
+        return Err(ApiError::validation_error("API key name cannot be blank"));
+    }
+    create_request.validate()?;
+
+    let (record, key) = keys
+        .mint(&player.id, create_request.name.trim())
+        .await
+        .map_err(|e| {
+            error!("Failed to mint API key for {}: {}", player.id, e);
+            ApiError::database_error("Failed to create API key")
+        })?;
+    info!("Player {} minted API key {}", player.id, record.id);
+    Ok(HttpResponse::Created().json(CreatedApiKeyResponse {
+        api_key: ApiKeyDto::from(&record),
+        key,
+    }))
+}
+
+#[get("/api-keys")]
+pub async fn list_api_keys_handler(
+    req: HttpRequest,
+    repo: web::Data<PlayerRepositoryImpl>,
+    keys: web::Data<ApiKeyRepository>,
+) -> Result<HttpResponse, ApiError> {
+    let player = current_player(&req, &repo).await?;
+    let records = keys.list(&player.id).await.map_err(|e| {
+        error!("Failed to list API keys for {}: {}", player.id, e);
+        ApiError::database_error("Failed to list API keys")
+    })?;
+    let api_keys: Vec<ApiKeyDto> = records.iter().map(ApiKeyDto::from).collect();
+    Ok(HttpResponse::Ok().json(api_keys))
+}
+
+#[delete("/api-keys/{key_id}")]
+pub async fn revoke_api_key_handler(
+    req: HttpRequest,
+    path: web::Path<String>,
+    repo: web::Data<PlayerRepositoryImpl>,
+    keys: web::Data<ApiKeyRepository>,
+) -> Result<HttpResponse, ApiError> {
+    let player = current_player(&req, &repo).await?;
+    let key_id = path.into_inner();
+    let revoked = keys.revoke(&player.id, &key_id).await.map_err(|e| {
+        error!("Failed to revoke API key {}: {}", key_id, e);
+        ApiError::database_error("Failed to revoke API key")
+    })?;
+    if !revoked {
+        return Err(ApiError::not_found("API key not found"));
+    }
+    info!("Player {} revoked API key {}", player.id, key_id);
+    Ok(HttpResponse::NoContent().finish())
+}
@@ -1,4 +1,4 @@
-use crate::error::ApiError;
+use crate::error::{codes, ApiError};
 use std::fmt;
 
 #[derive(Debug, PartialEq, Eq)]
@@ -25,8 +25,12 @@ impl fmt::Display for PlayerError {
 impl From<PlayerError> for ApiError {
     fn from(err: PlayerError) -> Self {
         match err {
-            PlayerError::NotFound => ApiError::not_found(&err.to_string()),
-            PlayerError::InvalidPassword => ApiError::unauthorized(&err.to_string()),
+            PlayerError::NotFound => {
+                ApiError::not_found(&err.to_string()).with_code(codes::PLAYER_NOT_FOUND)
+            }
+            PlayerError::InvalidPassword => ApiError::unauthorized(&err.to_string())
+                .with_code(codes::PLAYER_INVALID_CREDENTIALS),
+            // Callers narrow this to the email or handle that clashed
             PlayerError::AlreadyExists => ApiError::bad_request(&err.to_string()),
             PlayerError::DatabaseError(msg) => ApiError::database_error(&msg),
             PlayerError::SessionError(msg) => ApiError::internal_error(&msg),
@@ -56,11 +60,13 @@ mod tests {
     fn test_player_error_to_api_error() {
         let api_error: ApiError = PlayerError::NotFound.into();
         assert_eq!(api_error.error, "NOT_FOUND");
+        assert_eq!(api_error.code, "player.not_found");
         assert_eq!(api_error.message, "Player not found");
         assert_eq!(api_error.status_code, 404);
 
         let api_error: ApiError = PlayerError::InvalidPassword.into();
         assert_eq!(api_error.error, "UNAUTHORIZED");
+        assert_eq!(api_error.code, "player.invalid_credentials");
         assert_eq!(api_error.message, "Invalid password");
         assert_eq!(api_error.status_code, 401);
     }
@@ -0,0 +1,141 @@
+//! Password hashing
+//!
+//! New hashes are made with the configured [`PasswordConfig`]. Verification
+//! accepts any Argon2 PHC string or bcrypt hash, whatever its cost, so
+//! changing the configuration never locks anyone out; [`needs_rehash`] tells
+//! the login flow when a stored hash should be replaced.
+
+use crate::config::{PasswordAlgorithm, PasswordConfig};
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+
+fn argon2_hasher(config: &PasswordConfig) -> Result<Argon2<'static>, String> {
+    let params = Params::new(
+        config.argon2_memory_kib,
+        config.argon2_iterations,
+        config.argon2_parallelism,
+        None,
+    )
+    .map_err(|e| format!("Invalid Argon2 parameters: {}", e))?;
+    Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+}
+
+fn is_bcrypt(hash: &str) -> bool {
+    hash.starts_with("$2")
+}
+
+/// Hashes `password` with the configured algorithm and cost
+pub fn hash_password(config: &PasswordConfig, password: &str) -> Result<String, String> {
+    match config.algorithm {
+        PasswordAlgorithm::Argon2id => {
+            let salt = SaltString::generate(&mut OsRng);
+            argon2_hasher(config)?
+                .hash_password(password.as_bytes(), &salt)
+                .map(|hash| hash.to_string())
+                .map_err(|e| format!("Failed to hash password: {}", e))
+        }
+        PasswordAlgorithm::Bcrypt => bcrypt::hash(password, config.bcrypt_cost)
+            .map_err(|e| format!("Failed to hash password: {}", e)),
+    }
+}
+
+/// Whether `password` matches a stored Argon2 or bcrypt `hash`
+pub fn verify_password(hash: &str, password: &str) -> bool {
+    if is_bcrypt(hash) {
+        return bcrypt::verify(password, hash).unwrap_or(false);
+    }
+    // Argon2 reads the algorithm and cost to verify with from the hash itself
+    PasswordHash::new(hash)
+        .map(|parsed| {
+            Argon2::default()
+                .verify_password(password.as_bytes(), &parsed)
+                .is_ok()
+        })
+        .unwrap_or(false)
+}
+
+/// Whether a stored hash was made with another algorithm or cost than
+/// `config` asks for
+pub fn needs_rehash(config: &PasswordConfig, hash: &str) -> bool {
+    match config.algorithm {
+        PasswordAlgorithm::Argon2id => {
+            let Ok(parsed) = PasswordHash::new(hash) else {
+                return true;
+            };
+            if parsed.algorithm != argon2::ARGON2ID_IDENT {
+                return true;
+            }
+            match Params::try_from(&parsed) {
+                Ok(params) => {
+                    params.m_cost() != config.argon2_memory_kib
+                        || params.t_cost() != config.argon2_iterations
+                        || params.p_cost() != config.argon2_parallelism
+                }
+                Err(_) => true,
+            }
+        }
+        PasswordAlgorithm::Bcrypt => match hash.parse::<bcrypt::HashParts>() {
+            Ok(parts) => parts.get_cost() != config.bcrypt_cost,
+            Err(_) => true,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cheap settings so the tests stay fast
+    fn weak() -> PasswordConfig {
+        PasswordConfig {
+            argon2_memory_kib: 8,
+            argon2_iterations: 1,
+            argon2_parallelism: 1,
+            bcrypt_cost: 4,
+            ..PasswordConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_hashes_verify_and_carry_their_cost() {
+        let config = weak();
+        let hash = hash_password(&config, "hunter22").unwrap();
+        assert!(hash.starts_with("$argon2id$v=19$m=8,t=1,p=1$"));
+        assert!(verify_password(&hash, "hunter22"));
+        assert!(!verify_password(&hash, "hunter23"));
+        assert!(!needs_rehash(&config, &hash));
+
+        let bcrypt_config = PasswordConfig {
+            algorithm: PasswordAlgorithm::Bcrypt,
+            ..weak()
+        };
+        let hash = hash_password(&bcrypt_config, "hunter22").unwrap();
+        assert!(hash.starts_with("$2b$04$"));
+        assert!(verify_password(&hash, "hunter22"));
+        assert!(!verify_password(&hash, "hunter23"));
+        assert!(!needs_rehash(&bcrypt_config, &hash));
+    }
+
+    #[test]
+    fn test_weaker_or_foreign_hashes_need_rehash() {
+        let stronger = PasswordConfig {
+            argon2_iterations: 2,
+            ..weak()
+        };
+        let weak_hash = hash_password(&weak(), "hunter22").unwrap();
+        assert!(needs_rehash(&stronger, &weak_hash));
+
+        let bcrypt_hash = bcrypt::hash("hunter22", 4).unwrap();
+        assert!(needs_rehash(&weak(), &bcrypt_hash));
+        let bcrypt_config = PasswordConfig {
+            algorithm: PasswordAlgorithm::Bcrypt,
+            bcrypt_cost: 5,
+            ..weak()
+        };
+        assert!(needs_rehash(&bcrypt_config, &bcrypt_hash));
+        assert!(needs_rehash(&bcrypt_config, &weak_hash));
+        assert!(needs_rehash(&weak(), "not a hash"));
+        assert!(!verify_password("not a hash", "hunter22"));
+    }
+}
@@ -349,11 +349,14 @@ mod tests {
             id: id.to_string(),
             rev: "1".to_string(),
             firstname: "Test".to_string(),
+            lastname: None,
             handle: handle.to_string(),
             email: email.to_string(),
             password: "hashed_password".to_string(),
             created_at: Utc::now().fixed_offset(),
             is_admin: false,
+            last_login_at: None,
+            last_login_ip: None,
         }
     }
 
@@ -1,6 +1,7 @@
+use crate::config::PasswordConfig;
 use crate::player::error::PlayerError;
+use crate::player::password::{hash_password, needs_rehash, verify_password};
 use crate::player::repository::PlayerRepository;
-use argon2::{Argon2, PasswordHasher};
 use chrono::Utc;
 use shared::dto::player::CreatePlayerRequest;
 use shared::models::player::Player;
@@ -29,21 +30,47 @@ pub trait PlayerUseCase: Send + Sync {
         current_password: &str,
         new_password: &str,
     ) -> Result<Player, PlayerError>;
+    /// `lastname` of `Some(None)` removes the last name
+    async fn update_profile(
+        &self,
+        email: &str,
+        firstname: Option<&str>,
+        lastname: Option<Option<&str>>,
+    ) -> Result<Player, PlayerError>;
 }
 
 pub struct PlayerUseCaseImpl<R: PlayerRepository> {
     pub repo: R,
+    /// Algorithm and cost for new password hashes
+    pub passwords: PasswordConfig,
 }
 
 #[async_trait::async_trait]
 impl<R: PlayerRepository> PlayerUseCase for PlayerUseCaseImpl<R> {
     async fn login(&self, login: PlayerLogin) -> Result<Player, PlayerError> {
-        if let Some(player) = self.repo.find_by_email(&login.email).await {
-            if player.verify_password(&login.password) {
-                Ok(player)
-            } else {
-                Err(PlayerError::InvalidPassword)
+        if let Some(mut player) = self.repo.find_by_email(&login.email).await {
+            if !verify_password(&player.password, &login.password) {
+                return Err(PlayerError::InvalidPassword);
+            }
+            // Upgrade hashes made with an older algorithm or cost while the
+            // plaintext is at hand; a failure here must not fail the login
+            if needs_rehash(&self.passwords, &player.password) {
+                match hash_password(&self.passwords, &login.password) {
+                    Ok(rehashed) => {
+                        player.password = rehashed;
+                        match self.repo.update(player.clone()).await {
+                            Ok(updated) => player = updated,
+                            Err(e) => log::warn!(
+                                "Failed to store rehashed password for {}: {}",
+                                player.email,
+                                e
+                            ),
+                        }
+                    }
+                    Err(e) => log::warn!("Failed to rehash password for {}: {}", player.email, e),
+                }
             }
+            Ok(player)
         } else {
             Err(PlayerError::NotFound)
         }
@@ -69,14 +96,8 @@ impl<R: PlayerRepository> PlayerUseCase for PlayerUseCaseImpl<R> {
         }
 
         // Hash the password
-        let salt_string = argon2::password_hash::SaltString::generate(
-            &mut argon2::password_hash::rand_core::OsRng,
-        );
-        let salt = Argon2::default()
-            .hash_password(registration.password.as_bytes(), &salt_string)
-            .map_err(|e| PlayerError::DatabaseError(format!("Failed to hash password: {}", e)))?;
-
-        let hashed_password = salt.to_string();
+        let hashed_password = hash_password(&self.passwords, &registration.password)
+            .map_err(PlayerError::DatabaseError)?;
 
         // Create new player
         let player = Player::new_for_db(
@@ -110,7 +131,7 @@ impl<R: PlayerRepository> PlayerUseCase for PlayerUseCaseImpl<R> {
             .ok_or(PlayerError::NotFound)?;
 
         // Verify current password
-        if !player.verify_password(password) {
+        if !verify_password(&player.password, password) {
             return Err(PlayerError::InvalidPassword);
         }
 
@@ -143,7 +164,7 @@ impl<R: PlayerRepository> PlayerUseCase for PlayerUseCaseImpl<R> {
             .ok_or(PlayerError::NotFound)?;
 
         // Verify current password
-        if !player.verify_password(password) {
+        if !verify_password(&player.password, password) {
             return Err(PlayerError::InvalidPassword);
         }
 
@@ -176,20 +197,41 @@ impl<R: PlayerRepository> PlayerUseCase for PlayerUseCaseImpl<R> {
             .ok_or(PlayerError::NotFound)?;
 
         // Verify current password
-        if !player.verify_password(current_password) {
+        if !verify_password(&player.password, current_password) {
             return Err(PlayerError::InvalidPassword);
         }
 
         // Hash the new password
-        let salt_string = argon2::password_hash::SaltString::generate(
-            &mut argon2::password_hash::rand_core::OsRng,
-        );
-        let salt = Argon2::default()
-            .hash_password(new_password.as_bytes(), &salt_string)
-            .map_err(|e| PlayerError::DatabaseError(format!("Failed to hash password: {}", e)))?;
-
-        // Update password
-        player.password = salt.to_string();
+        player.password =
+            hash_password(&self.passwords, new_password).map_err(PlayerError::DatabaseError)?;
+
+        // Save to database
+        self.repo
+            .update(player)
+            .await
+            .map_err(|e| PlayerError::DatabaseError(e))
+    }
+
+    async fn update_profile(
+        &self,
+        email: &str,
+        firstname: Option<&str>,
+        lastname: Option<Option<&str>>,
+    ) -> Result<Player, PlayerError> {
+        // Find the player by email
+        let mut player = self
+            .repo
+            .find_by_email(email)
+            .await
+            .ok_or(PlayerError::NotFound)?;
+
+        // Update only the name fields that were provided
+        if let Some(firstname) = firstname {
+            player.firstname = firstname.trim().to_string();
+        }
+        if let Some(lastname) = lastname {
+            player.lastname = lastname.map(|l| l.trim().to_string());
+        }
 
         // Save to database
         self.repo
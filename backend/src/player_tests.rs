@@ -10,6 +10,7 @@ mod player_tests {
         let player_dto = PlayerDto {
             id: "player/test".to_string(),
             firstname: "John".to_string(),
+            lastname: None,
             handle: "testuser".to_string(),
             email: "test@example.com".to_string(),
             created_at: Utc::now().fixed_offset(),
@@ -54,11 +55,14 @@ mod player_tests {
             id: "player/test".to_string(),
             rev: "1".to_string(),
             firstname: "John".to_string(),
+            lastname: None,
             handle: "testuser".to_string(),
             email: "test@example.com".to_string(),
             password: "hashed_password".to_string(),
             created_at: Utc::now().fixed_offset(),
             is_admin: false,
+            last_login_at: None,
+            last_login_ip: None,
         };
 
         assert_eq!(player.firstname, "John");
@@ -73,11 +77,14 @@ mod player_tests {
             id: "player/test".to_string(),
             rev: "1".to_string(),
             firstname: "John".to_string(),
+            lastname: None,
             handle: "testuser".to_string(),
             email: "test@example.com".to_string(),
             password: "hashed_password".to_string(),
             created_at: Utc::now().fixed_offset(),
             is_admin: false,
+            last_login_at: None,
+            last_login_ip: None,
         };
 
         let json = serde_json::to_string(&player).unwrap();
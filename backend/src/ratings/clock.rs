@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// Source of the current time for rating recalculations
+///
+/// The default period, the months a backfill covers, the timestamps written
+/// on rating documents and the scheduler's run times all follow this clock,
+/// so a fixed clock makes a recalculation reproducible.
+pub trait Clock: Debug + Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// The real wall clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock stopped at one instant, for tests
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub DateTime<Utc>);
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+/// The clock used when none is injected
+pub fn system_clock() -> Arc<dyn Clock> {
+    Arc::new(SystemClock)
+}
@@ -3,9 +3,10 @@ use arangors::client::ClientExt;
 use serde::Deserialize;
 use shared::dto::ratings::RatingScope;
 
+use super::glicko::Glicko2Params;
 use super::repository::RatingsRepository;
 use super::scheduler::RatingsScheduler;
-use super::usecase::RatingsUsecase;
+use super::usecase::{RatingsUsecase, SIMILAR_RATING_WINDOW_DEFAULT};
 
 #[derive(Clone)]
 pub struct RatingsController<C: ClientExt + Send + Sync + 'static> {
@@ -35,10 +36,13 @@ impl<C: ClientExt + Send + Sync + 'static> RatingsController<C> {
         db: arangors::Database<C>,
         scheduler: RatingsScheduler<C>,
         redis: redis::Client,
+        params: Glicko2Params,
+        period_interval: std::time::Duration,
     ) {
         let repo = RatingsRepository::new(db.clone());
         let controller = web::Data::new(RatingsController {
-            usecase: RatingsUsecase::new(repo),
+            usecase: RatingsUsecase::with_params(repo, params)
+                .with_period_interval(period_interval),
             scheduler: web::Data::new(scheduler),
         });
 
@@ -119,6 +123,43 @@ impl<C: ClientExt + Send + Sync + 'static> RatingsController<C> {
                         Err(e) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({"error": e.to_string()})))
                     }
                 }).wrap(crate::auth::AuthMiddleware { redis: std::sync::Arc::new(redis.clone()) }))
+                .route("/me/rank-change", web::get().to(|req: HttpRequest, ctrl: web::Data<RatingsController<C>>| async move {
+                    let email = match req.extensions().get::<String>() {
+                        Some(email) => email.clone(),
+                        None => return Ok(HttpResponse::Unauthorized().json(serde_json::json!({"error": "Not authenticated"})))
+                    };
+
+                    let player_id = match ctrl.usecase.get_player_id_by_email(&email).await {
+                        Ok(Some(pid)) => pid,
+                        Ok(None) => return Ok(HttpResponse::NotFound().json(serde_json::json!({"error": "Player not found"}))),
+                        Err(e) => return Ok(HttpResponse::InternalServerError().json(serde_json::json!({"error": e.to_string()})))
+                    };
+
+                    match ctrl.usecase.get_rank_change(&player_id).await {
+                        Ok(change) => Ok::<HttpResponse, actix_web::Error>(HttpResponse::Ok().json(change)),
+                        Err(e) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({"error": e.to_string()})))
+                    }
+                }).wrap(crate::auth::AuthMiddleware { redis: std::sync::Arc::new(redis.clone()) }))
+                .route("/me/similar", web::get().to(|req: HttpRequest, query: web::Query<SimilarQuery>, ctrl: web::Data<RatingsController<C>>| async move {
+                    let email = match req.extensions().get::<String>() {
+                        Some(email) => email.clone(),
+                        None => return Ok(HttpResponse::Unauthorized().json(serde_json::json!({"error": "Not authenticated"})))
+                    };
+
+                    let player_id = match ctrl.usecase.get_player_id_by_email(&email).await {
+                        Ok(Some(pid)) => pid,
+                        Ok(None) => return Ok(HttpResponse::NotFound().json(serde_json::json!({"error": "Player not found"}))),
+                        Err(e) => return Ok(HttpResponse::InternalServerError().json(serde_json::json!({"error": e.to_string()})))
+                    };
+
+                    let scope = match query.scope.as_deref() { Some("global") | None => RatingScope::Global, Some(s) if s.starts_with("game/") => RatingScope::Game(s.to_string()), _ => RatingScope::Global };
+                    let window = query.window.unwrap_or(SIMILAR_RATING_WINDOW_DEFAULT);
+                    match ctrl.usecase.get_similar_rated(&player_id, scope, window).await {
+                        Ok(rows) => Ok::<HttpResponse, actix_web::Error>(HttpResponse::Ok().json(rows)),
+                        Err(shared::SharedError::BadRequest(msg)) => Ok(HttpResponse::BadRequest().json(serde_json::json!({"error": msg}))),
+                        Err(e) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({"error": e.to_string()})))
+                    }
+                }).wrap(crate::auth::AuthMiddleware { redis: std::sync::Arc::new(redis.clone()) }))
                 .route("/history", web::get().to(|req: HttpRequest, query: web::Query<HistoryQuery>, ctrl: web::Data<RatingsController<C>>| async move {
                     // Auth: require session to read email
                     let email = match req.extensions().get::<String>() {
@@ -178,6 +219,12 @@ impl<C: ClientExt + Send + Sync + 'static> RatingsController<C> {
                         Err(e) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({"error": e.to_string()})))
                     }
                 }).wrap(crate::auth::AdminAuthMiddleware { redis: std::sync::Arc::new(redis.clone()), db: std::sync::Arc::new(db.clone()) }))
+                .route("/backfill", web::post().to(|_req: HttpRequest, ctrl: web::Data<RatingsController<C>>| async move {
+                    match ctrl.usecase.backfill_ratings().await {
+                        Ok(report) => Ok::<HttpResponse, actix_web::Error>(HttpResponse::Ok().json(report)),
+                        Err(e) => Ok(HttpResponse::InternalServerError().json(serde_json::json!({"error": e.to_string()})))
+                    }
+                }).wrap(crate::auth::AdminAuthMiddleware { redis: std::sync::Arc::new(redis.clone()), db: std::sync::Arc::new(db.clone()) }))
         );
     }
 
@@ -210,6 +257,12 @@ struct LeaderboardQuery {
     limit: Option<i32>,
 }
 
+#[derive(Deserialize)]
+struct SimilarQuery {
+    scope: Option<String>, // "global" or "game/<id>"
+    window: Option<f64>,
+}
+
 #[derive(Deserialize)]
 struct HistoryQuery {
     scope: Option<String>, // "global" or "game/<id>"
@@ -7,6 +7,9 @@ pub struct Glicko2Params {
     pub default_rd: f64,     // typically 350
     pub default_vol: f64,    // typically 0.06
     pub tau: f64,            // volatility constraint, 0.5–1.2; we’ll use 0.5
+    // Bounds on the stored rating; None leaves that side unclamped
+    pub rating_floor: Option<f64>,
+    pub rating_ceiling: Option<f64>,
 }
 
 impl Default for Glicko2Params {
@@ -16,10 +19,27 @@ impl Default for Glicko2Params {
             default_rd: 350.0,
             default_vol: 0.06,
             tau: 0.5,
+            rating_floor: None,
+            rating_ceiling: None,
         }
     }
 }
 
+impl Glicko2Params {
+    // Keeps the displayed rating within the configured range; RD and volatility
+    // are left as computed so the model's uncertainty is unaffected
+    pub fn clamp_rating(&self, state: RatingState) -> RatingState {
+        let mut rating = state.rating;
+        if let Some(ceiling) = self.rating_ceiling {
+            rating = rating.min(ceiling);
+        }
+        if let Some(floor) = self.rating_floor {
+            rating = rating.max(floor);
+        }
+        RatingState { rating, ..state }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct RatingState {
     pub rating: f64,
@@ -1,8 +1,15 @@
 use arangors::client::ClientExt;
 use arangors::{AqlQuery, Database};
 use serde_json::Value;
+use shared::dto::ratings::{RatingLeaderboardEntryDto, RatingScope};
 use shared::{Result, SharedError};
 
+/// Collection holding the ratings backfill's progress marker
+pub const BACKFILL_COLLECTION: &str = "rating_backfill";
+
+/// Key of the single progress document in [`BACKFILL_COLLECTION`]
+const BACKFILL_KEY: &str = "global";
+
 #[derive(Clone)]
 pub struct RatingsRepository<C: ClientExt> {
     pub db: Database<C>,
@@ -13,6 +20,7 @@ impl<C: ClientExt> RatingsRepository<C> {
         Self { db }
     }
 
+    /// Rated contests starting in `[start, end)`
     pub async fn get_contests_in_period(
         &self,
         start: &str,
@@ -23,6 +31,8 @@ impl<C: ClientExt> RatingsRepository<C> {
                 r#"
                 FOR c IN contest
                   FILTER c.start >= @start AND c.start < @end
+                  // Unrated contests still count in stats, just not in ratings
+                  FILTER c.rated != false
                   RETURN c
             "#,
             )
@@ -45,7 +55,7 @@ impl<C: ClientExt> RatingsRepository<C> {
             .query(
                 r#"
                 FOR r IN resulted_in
-                  FILTER r._from == @contest_id
+                  FILTER r._from == @contest_id AND r.guest_name == null
                   RETURN { player_id: r._to, place: r.place }
             "#,
             )
@@ -76,7 +86,7 @@ impl<C: ClientExt> RatingsRepository<C> {
             .query(
                 r#"
                 FOR r IN resulted_in
-                  FILTER r._from == @contest_id
+                  FILTER r._from == @contest_id AND r.guest_name == null
                   RETURN r._to
             "#,
             )
@@ -189,6 +199,193 @@ impl<C: ClientExt> RatingsRepository<C> {
         Ok(())
     }
 
+    /// Player ids and ratings of every latest rating in a scope
+    pub async fn get_latest_ratings(
+        &self,
+        scope_type: &str,
+        scope_id: Option<&str>,
+    ) -> Result<Vec<Value>> {
+        let query = AqlQuery::builder()
+            .query(
+                r#"
+                FOR r IN rating_latest
+                  FILTER r.scope_type == @scope_type
+                    AND ((@scope_id == null AND r.scope_id == null) OR r.scope_id == @scope_id)
+                  RETURN { player_id: r.player_id, rating: r.rating }
+            "#,
+            )
+            .bind_var("scope_type", scope_type)
+            .bind_var("scope_id", scope_id)
+            .build();
+        let res =
+            self.db.aql_query::<Value>(query).await.map_err(|e| {
+                SharedError::Database(format!("Failed to fetch latest ratings: {}", e))
+            })?;
+        Ok(res)
+    }
+
+    /// Stores each player's leaderboard rank on their latest rating and on
+    /// their history entry for `period_end`
+    ///
+    /// `ranks` is an array of `{ player_id, rank }` objects.
+    pub async fn set_period_ranks(
+        &self,
+        scope_type: &str,
+        scope_id: Option<&str>,
+        period_end: &str,
+        ranks: Value,
+    ) -> Result<()> {
+        let latest_query = AqlQuery::builder()
+            .query(
+                r#"
+                FOR entry IN @ranks
+                  FOR r IN rating_latest
+                    FILTER r.player_id == entry.player_id
+                      AND r.scope_type == @scope_type
+                      AND ((@scope_id == null AND r.scope_id == null) OR r.scope_id == @scope_id)
+                    UPDATE r WITH { rank: entry.rank } IN rating_latest
+            "#,
+            )
+            .bind_var("ranks", ranks.clone())
+            .bind_var("scope_type", scope_type)
+            .bind_var("scope_id", scope_id)
+            .build();
+        self.db
+            .aql_query::<Value>(latest_query)
+            .await
+            .map_err(|e| SharedError::Database(format!("Failed to store latest ranks: {}", e)))?;
+
+        let history_query = AqlQuery::builder()
+            .query(
+                r#"
+                FOR entry IN @ranks
+                  FOR h IN rating_history
+                    FILTER h.player_id == entry.player_id
+                      AND h.scope_type == @scope_type
+                      AND ((@scope_id == null AND h.scope_id == null) OR h.scope_id == @scope_id)
+                      AND h.period_end == @period_end
+                    UPDATE h WITH { rank: entry.rank } IN rating_history
+            "#,
+            )
+            .bind_var("ranks", ranks)
+            .bind_var("scope_type", scope_type)
+            .bind_var("scope_id", scope_id)
+            .bind_var("period_end", period_end)
+            .build();
+        self.db
+            .aql_query::<Value>(history_query)
+            .await
+            .map_err(|e| SharedError::Database(format!("Failed to store period ranks: {}", e)))?;
+        Ok(())
+    }
+
+    /// A player's current rank and the rank from the last earlier period
+    /// they were ranked in
+    ///
+    /// Returns `{ rank, period_end, previous_rank, previous_period_end }`,
+    /// any of which may be null, or nothing without a latest rating.
+    pub async fn get_rank_change(
+        &self,
+        scope_type: &str,
+        scope_id: Option<&str>,
+        player_id: &str,
+    ) -> Result<Option<Value>> {
+        let query = AqlQuery::builder()
+            .query(
+                r#"
+                FOR r IN rating_latest
+                  FILTER r.player_id == @player_id
+                    AND r.scope_type == @scope_type
+                    AND ((@scope_id == null AND r.scope_id == null) OR r.scope_id == @scope_id)
+                  LIMIT 1
+                  LET previous = FIRST(
+                    FOR h IN rating_history
+                      FILTER h.player_id == @player_id
+                        AND h.scope_type == @scope_type
+                        AND ((@scope_id == null AND h.scope_id == null) OR h.scope_id == @scope_id)
+                        AND h.rank != null
+                        AND h.period_end < r.last_period_end
+                      SORT h.period_end DESC
+                      LIMIT 1
+                      RETURN h
+                  )
+                  RETURN {
+                    rank: r.rank,
+                    period_end: r.last_period_end,
+                    previous_rank: previous.rank,
+                    previous_period_end: previous.period_end
+                  }
+            "#,
+            )
+            .bind_var("player_id", player_id)
+            .bind_var("scope_type", scope_type)
+            .bind_var("scope_id", scope_id)
+            .build();
+        let mut res =
+            self.db.aql_query::<Value>(query).await.map_err(|e| {
+                SharedError::Database(format!("Failed to fetch rank change: {}", e))
+            })?;
+        Ok(res.pop())
+    }
+
+    /// Players whose rating in `scope` is within `window` points of
+    /// `player_id`'s, closest first, excluding the player themselves
+    ///
+    /// Returns nothing when the player has no rating in that scope.
+    pub async fn get_similar_rated(
+        &self,
+        player_id: &str,
+        scope: &RatingScope,
+        window: f64,
+    ) -> Result<Vec<RatingLeaderboardEntryDto>> {
+        let (scope_type, scope_id) = match scope {
+            RatingScope::Global => ("global", None),
+            RatingScope::Game(gid) => ("game", Some(gid.as_str())),
+        };
+        let query = AqlQuery::builder()
+            .query(
+                r#"
+                LET me = FIRST(
+                  FOR r IN rating_latest
+                    FILTER r.player_id == @player_id
+                      AND r.scope_type == @scope_type
+                      AND ((@scope_id == null AND r.scope_id == null) OR r.scope_id == @scope_id)
+                    LIMIT 1
+                    RETURN r
+                )
+                FOR r IN rating_latest
+                  FILTER me != null
+                    AND r.player_id != @player_id
+                    AND r.scope_type == @scope_type
+                    AND ((@scope_id == null AND r.scope_id == null) OR r.scope_id == @scope_id)
+                  LET distance = ABS(r.rating - me.rating)
+                  FILTER distance <= @window
+                  SORT distance ASC, r.player_id ASC
+                  LET player = DOCUMENT(r.player_id)
+                  RETURN {
+                    player_id: r.player_id,
+                    handle: player.handle,
+                    rating: r.rating,
+                    rd: r.rd,
+                    games_played: NOT_NULL(r.games_played, 0),
+                    last_active: r.last_period_end,
+                    contest_id: null
+                  }
+            "#,
+            )
+            .bind_var("player_id", player_id)
+            .bind_var("scope_type", scope_type)
+            .bind_var("scope_id", scope_id)
+            .bind_var("window", window)
+            .build();
+        self.db
+            .aql_query::<RatingLeaderboardEntryDto>(query)
+            .await
+            .map_err(|e| {
+                SharedError::Database(format!("Failed to fetch similarly rated players: {}", e))
+            })
+    }
+
     pub async fn get_leaderboard(
         &self,
         scope_type: &str,
@@ -562,6 +759,135 @@ impl<C: ClientExt> RatingsRepository<C> {
         Ok(())
     }
 
+    /// The saved progress of the ratings backfill, if one has started
+    ///
+    /// Returns `{ last_period_end, completed }`.
+    pub async fn get_backfill_progress(&self) -> Result<Option<Value>> {
+        let query = AqlQuery::builder()
+            .query(
+                r#"
+                RETURN DOCUMENT(@@collection, @key)
+            "#,
+            )
+            .bind_var("@collection", BACKFILL_COLLECTION)
+            .bind_var("key", BACKFILL_KEY)
+            .build();
+        let mut res = self.db.aql_query::<Value>(query).await.map_err(|e| {
+            SharedError::Database(format!("Failed to fetch backfill progress: {}", e))
+        })?;
+        Ok(res.pop().filter(|doc| !doc.is_null()))
+    }
+
+    /// Records that the backfill has written every period up to
+    /// `last_period_end`, and whether it has finished
+    pub async fn save_backfill_progress(
+        &self,
+        last_period_end: Option<&str>,
+        completed: bool,
+    ) -> Result<()> {
+        let query = AqlQuery::builder()
+            .query(
+                r#"
+                LET doc = {
+                  last_period_end: @last_period_end,
+                  completed: @completed,
+                  updated_at: DATE_ISO8601(DATE_NOW())
+                }
+                UPSERT { _key: @key }
+                INSERT MERGE(doc, { _key: @key })
+                UPDATE doc IN @@collection
+            "#,
+            )
+            .bind_var("@collection", BACKFILL_COLLECTION)
+            .bind_var("key", BACKFILL_KEY)
+            .bind_var("last_period_end", last_period_end)
+            .bind_var("completed", completed)
+            .build();
+        self.db.aql_query::<Value>(query).await.map_err(|e| {
+            SharedError::Database(format!("Failed to save backfill progress: {}", e))
+        })?;
+        Ok(())
+    }
+
+    /// Winds global ratings back to how they stood at `period_end`
+    ///
+    /// History written for later periods is removed, and each latest rating
+    /// is reset from its history entry for `period_end`; players first rated
+    /// after it lose their latest rating.
+    pub async fn rollback_ratings_after(&self, period_end: &str) -> Result<()> {
+        let history_query = AqlQuery::builder()
+            .query(
+                r#"
+                FOR h IN rating_history
+                  FILTER h.scope_type == "global" AND h.scope_id == null
+                    AND h.period_end > @period_end
+                  REMOVE h IN rating_history
+            "#,
+            )
+            .bind_var("period_end", period_end)
+            .build();
+        self.db
+            .aql_query::<Value>(history_query)
+            .await
+            .map_err(|e| {
+                SharedError::Database(format!("Failed to roll back rating history: {}", e))
+            })?;
+
+        let latest_query = AqlQuery::builder()
+            .query(
+                r#"
+                FOR r IN rating_latest
+                  FILTER r.scope_type == "global" AND r.scope_id == null
+                  LET h = FIRST(
+                    FOR h IN rating_history
+                      FILTER h.player_id == r.player_id
+                        AND h.scope_type == "global" AND h.scope_id == null
+                        AND h.period_end == @period_end
+                      RETURN h
+                  )
+                  FILTER h == null
+                  REMOVE r IN rating_latest
+            "#,
+            )
+            .bind_var("period_end", period_end)
+            .build();
+        self.db
+            .aql_query::<Value>(latest_query)
+            .await
+            .map_err(|e| {
+                SharedError::Database(format!("Failed to roll back latest ratings: {}", e))
+            })?;
+
+        let restore_query = AqlQuery::builder()
+            .query(
+                r#"
+                FOR h IN rating_history
+                  FILTER h.scope_type == "global" AND h.scope_id == null
+                    AND h.period_end == @period_end
+                  FOR r IN rating_latest
+                    FILTER r.player_id == h.player_id
+                      AND r.scope_type == "global" AND r.scope_id == null
+                    UPDATE r WITH {
+                      rating: h.rating,
+                      rd: h.rd,
+                      volatility: h.volatility,
+                      games_played: h.period_games,
+                      last_period_end: h.period_end,
+                      rank: h.rank
+                    } IN rating_latest
+            "#,
+            )
+            .bind_var("period_end", period_end)
+            .build();
+        self.db
+            .aql_query::<Value>(restore_query)
+            .await
+            .map_err(|e| {
+                SharedError::Database(format!("Failed to restore latest ratings: {}", e))
+            })?;
+        Ok(())
+    }
+
     pub async fn get_earliest_contest_date(&self) -> Result<String> {
         let query = AqlQuery::builder()
             .query(
@@ -71,12 +71,13 @@ impl<C: ClientExt + Send + Sync + 'static> RatingsScheduler<C> {
 
         loop {
             // Check if it's time to run monthly recalculation
-            if Self::should_run_monthly_recalculation(last_run.lock().unwrap().clone()) {
+            let now = usecase.clock().now();
+            if Self::should_run_monthly_recalculation(last_run.lock().unwrap().clone(), now) {
                 info!("Starting monthly Glicko2 ratings recalculation...");
 
                 match Self::run_monthly_recalculation(&usecase).await {
                     Ok(()) => {
-                        *last_run.lock().unwrap() = Some(Utc::now());
+                        *last_run.lock().unwrap() = Some(usecase.clock().now());
                         info!("Monthly Glicko2 ratings recalculation completed successfully");
                     }
                     Err(e) => {
@@ -90,10 +91,11 @@ impl<C: ClientExt + Send + Sync + 'static> RatingsScheduler<C> {
         }
     }
 
-    /// Determine if monthly recalculation should run
-    fn should_run_monthly_recalculation(last_run: Option<DateTime<Utc>>) -> bool {
-        let now = Utc::now();
-
+    /// Determine if monthly recalculation should run at `now`
+    fn should_run_monthly_recalculation(
+        last_run: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> bool {
         // If never run before, check if it's the 1st of the month at 2 AM
         if last_run.is_none() {
             return Self::is_first_of_month_at_2am(now);
@@ -123,7 +125,7 @@ impl<C: ClientExt + Send + Sync + 'static> RatingsScheduler<C> {
         let start_time = Instant::now();
 
         // Determine the period to recalculate (previous month)
-        let now = Utc::now();
+        let now = usecase.clock().now();
         let (year, month) = if now.month() == 1 {
             (now.year() - 1, 12)
         } else {
@@ -196,19 +198,54 @@ impl<C: ClientExt + Send + Sync + 'static> RatingsScheduler<C> {
         }
     }
 
+    /// Re-rate `period` and every full month after it, e.g. after a contest
+    /// in an already rated month was removed
+    pub async fn trigger_recompute_from(&self, period: &str) -> Result<()> {
+        info!("Re-rating from period {} onwards", period);
+
+        let start_time = Instant::now();
+        let result = self.usecase.recompute_from(period).await;
+
+        let duration = start_time.elapsed();
+        let status = if result.is_ok() { "success" } else { "error" };
+        if let Some(metrics) = crate::metrics::Metrics::global() {
+            crate::metrics::record_scheduler_execution(
+                metrics.as_ref(),
+                "recompute_from",
+                status,
+                duration,
+            );
+        }
+
+        match result {
+            Ok(periods) => {
+                info!(
+                    "Re-rated {} periods from {} in {:?}",
+                    periods, period, duration
+                );
+                Ok(())
+            }
+            Err(e) => {
+                error!(
+                    "Re-rating from {} failed after {:?}: {}",
+                    period, duration, e
+                );
+                Err(e)
+            }
+        }
+    }
+
     /// Get scheduler status
     pub fn get_status(&self) -> SchedulerStatus {
         SchedulerStatus {
             is_running: self.is_running,
             last_run: self.last_run.lock().unwrap().clone(),
-            next_scheduled_run: Self::calculate_next_run_time(),
+            next_scheduled_run: Self::calculate_next_run_time(self.usecase.clock().now()),
         }
     }
 
-    /// Calculate when the next scheduled run will occur
-    fn calculate_next_run_time() -> DateTime<Utc> {
-        let now = Utc::now();
-
+    /// Calculate when the next scheduled run after `now` will occur
+    fn calculate_next_run_time(now: DateTime<Utc>) -> DateTime<Utc> {
         // If it's already past 2 AM on the 1st, schedule for next month
         if now.day() == 1 && now.hour() >= 2 {
             // Next month, 1st day at 2 AM
@@ -299,7 +336,9 @@ mod tests {
     fn test_calculate_next_run_time() {
         // Test that the function returns a valid DateTime
         let next_run =
-            RatingsScheduler::<arangors::client::reqwest::ReqwestClient>::calculate_next_run_time();
+            RatingsScheduler::<arangors::client::reqwest::ReqwestClient>::calculate_next_run_time(
+                Utc::now(),
+            );
 
         // Should be a valid datetime
         assert!(next_run > Utc::now());
@@ -310,4 +349,32 @@ mod tests {
         assert_eq!(next_run.minute(), 0);
         assert_eq!(next_run.second(), 0);
     }
+
+    #[test]
+    fn test_schedule_follows_the_given_time() {
+        type Scheduler = RatingsScheduler<arangors::client::reqwest::ReqwestClient>;
+        let now = Utc.with_ymd_and_hms(2024, 3, 1, 2, 30, 0).unwrap();
+
+        assert!(Scheduler::should_run_monthly_recalculation(None, now));
+        let last_month = Utc.with_ymd_and_hms(2024, 2, 1, 2, 5, 0).unwrap();
+        assert!(Scheduler::should_run_monthly_recalculation(
+            Some(last_month),
+            now
+        ));
+        let earlier_today = Utc.with_ymd_and_hms(2024, 3, 1, 2, 5, 0).unwrap();
+        assert!(!Scheduler::should_run_monthly_recalculation(
+            Some(earlier_today),
+            now
+        ));
+
+        assert_eq!(
+            Scheduler::calculate_next_run_time(now),
+            Utc.with_ymd_and_hms(2024, 4, 1, 2, 0, 0).unwrap()
+        );
+        let new_year = Utc.with_ymd_and_hms(2024, 12, 15, 9, 0, 0).unwrap();
+        assert_eq!(
+            Scheduler::calculate_next_run_time(new_year),
+            Utc.with_ymd_and_hms(2025, 1, 1, 2, 0, 0).unwrap()
+        );
+    }
 }
@@ -0,0 +1,84 @@
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio::time::{sleep_until, Duration, Instant};
+
+/// Spaces out rating period recalculations so a long backfill leaves the
+/// database and CPU free for live requests
+///
+/// At most one period starts per `interval`. Clones share their schedule, so
+/// periods started through any clone of a usecase count against the same
+/// limit. A zero interval never waits.
+#[derive(Debug, Clone, Default)]
+pub struct PeriodThrottle {
+    interval: Duration,
+    next_start: Arc<Mutex<Option<Instant>>>,
+}
+
+impl PeriodThrottle {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            next_start: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Minimum time between the starts of two periods
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Waits until the next period may start, and reserves that slot
+    pub async fn wait(&self) {
+        if self.interval.is_zero() {
+            return;
+        }
+
+        // Holding the lock while sleeping queues concurrent callers in turn
+        let mut next_start = self.next_start.lock().await;
+        let start = match *next_start {
+            Some(at) if at > Instant::now() => {
+                sleep_until(at).await;
+                at
+            }
+            _ => Instant::now(),
+        };
+        *next_start = Some(start + self.interval);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_throttle_limits_periods_to_configured_rate() {
+        let throttle = PeriodThrottle::new(Duration::from_millis(50));
+        let started = Instant::now();
+        for _ in 0..5 {
+            throttle.wait().await;
+        }
+        // The first period starts at once, each later one an interval after
+        assert!(started.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn test_throttle_is_shared_between_clones() {
+        let throttle = PeriodThrottle::new(Duration::from_millis(50));
+        let clone = throttle.clone();
+        let started = Instant::now();
+        throttle.wait().await;
+        clone.wait().await;
+        throttle.wait().await;
+        assert!(started.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn test_zero_interval_does_not_wait() {
+        let throttle = PeriodThrottle::default();
+        let started = Instant::now();
+        for _ in 0..100 {
+            throttle.wait().await;
+        }
+        assert!(started.elapsed() < Duration::from_millis(50));
+    }
+}
@@ -1,20 +1,29 @@
 use arangors::{client::ClientExt, AqlQuery};
-use chrono::{Datelike, Utc};
+use chrono::Datelike;
 use serde_json::Value;
 use shared::dto::ratings::{
-    PlayerRatingDto, PlayerRatingHistoryPointDto, RatingLeaderboardEntryDto, RatingScope,
+    PlayerRatingDto, PlayerRatingHistoryPointDto, RatingLeaderboardEntryDto, RatingRankChangeDto,
+    RatingScope,
 };
 use shared::{Result, SharedError};
+use std::sync::Arc;
 
+use super::clock::{system_clock, Clock};
 use super::glicko::{
     pre_period_inflate_rd, update_period, Glicko2Params, OpponentSample, RatingState,
 };
 use super::repository::RatingsRepository;
+use super::throttle::PeriodThrottle;
+
+/// Rating distance used by [`RatingsUsecase::get_similar_rated`] when none is given
+pub const SIMILAR_RATING_WINDOW_DEFAULT: f64 = 100.0;
 
 #[derive(Clone)]
 pub struct RatingsUsecase<C: ClientExt> {
     repo: RatingsRepository<C>,
     params: Glicko2Params,
+    throttle: PeriodThrottle,
+    clock: Arc<dyn Clock>,
 }
 
 impl<C: ClientExt> RatingsUsecase<C> {
@@ -22,9 +31,40 @@ impl<C: ClientExt> RatingsUsecase<C> {
         Self {
             repo,
             params: Glicko2Params::default(),
+            throttle: PeriodThrottle::default(),
+            clock: system_clock(),
+        }
+    }
+
+    /// Creates a usecase with custom Glicko-2 parameters, e.g. a rating floor/ceiling
+    pub fn with_params(repo: RatingsRepository<C>, params: Glicko2Params) -> Self {
+        Self {
+            repo,
+            params,
+            throttle: PeriodThrottle::default(),
+            clock: system_clock(),
         }
     }
 
+    /// Leaves at least `interval` between the starts of two recalculated
+    /// months, so backfills and scheduled runs do not starve live requests
+    pub fn with_period_interval(mut self, interval: std::time::Duration) -> Self {
+        self.throttle = PeriodThrottle::new(interval);
+        self
+    }
+
+    /// Takes "now" from `clock` instead of the system time, e.g. to pin the
+    /// periods and timestamps of a recalculation in tests
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// The clock this usecase reads "now" from
+    pub fn clock(&self) -> Arc<dyn Clock> {
+        self.clock.clone()
+    }
+
     /// Recalculate all ratings from the beginning of time (2000) to build proper historical data
     pub async fn recalculate_all_historical_ratings(&self) -> Result<()> {
         log::info!("Starting complete historical ratings recalculation from 2000...");
@@ -58,7 +98,7 @@ impl<C: ClientExt> RatingsUsecase<C> {
         );
 
         // Process each month from the actual first entry until now
-        let now = Utc::now();
+        let now = self.clock.now();
         let mut year = start_year;
         let mut month = start_month;
 
@@ -87,8 +127,125 @@ impl<C: ClientExt> RatingsUsecase<C> {
         Ok(())
     }
 
+    /// Replays every completed month of rated contests, oldest first, so
+    /// every historical participant gets a latest rating and rating history
+    ///
+    /// Progress is saved after each month. An interrupted run resumes at the
+    /// month it stopped in, discarding whatever of that month was written;
+    /// a fresh run clears existing ratings first. Once a run has finished,
+    /// later runs do nothing, as the monthly scheduler carries on from there.
+    pub async fn backfill_ratings(&self) -> Result<RatingsBackfillReport> {
+        let progress = self.repo.get_backfill_progress().await?;
+        let completed = progress
+            .as_ref()
+            .and_then(|p| p.get("completed"))
+            .and_then(|x| x.as_bool())
+            .unwrap_or(false);
+        let resumed_after = progress
+            .as_ref()
+            .and_then(|p| p.get("last_period_end"))
+            .and_then(|x| x.as_str())
+            .map(str::to_string);
+        if completed {
+            log::info!("Ratings backfill already completed; nothing to do");
+            return Ok(RatingsBackfillReport {
+                periods_processed: 0,
+                resumed_after,
+                already_complete: true,
+            });
+        }
+
+        // A period ends where the next begins, so resume in that month
+        let (mut year, mut month) = match &resumed_after {
+            Some(period_end) => {
+                log::info!("Resuming ratings backfill after {}", period_end);
+                self.repo.rollback_ratings_after(period_end).await?;
+                year_month(period_end)?
+            }
+            None => {
+                log::info!("Starting ratings backfill from the first contest");
+                self.repo.clear_all_ratings().await?;
+                self.repo.save_backfill_progress(None, false).await?;
+                year_month(&self.repo.get_earliest_contest_date().await?)?
+            }
+        };
+
+        let last = self.last_full_month();
+        let mut last_period_end = resumed_after.clone();
+        let mut periods_processed = 0;
+        while (year, month) <= last {
+            let period = format!("{:04}-{:02}", year, month);
+            log::info!("Backfilling ratings for {}", period);
+            self.recompute_month_with_history(Some(period)).await?;
+
+            (year, month) = if month == 12 {
+                (year + 1, 1)
+            } else {
+                (year, month + 1)
+            };
+            let period_end = format!("{:04}-{:02}-01T00:00:00Z", year, month);
+            self.repo
+                .save_backfill_progress(Some(&period_end), false)
+                .await?;
+            last_period_end = Some(period_end);
+            periods_processed += 1;
+        }
+
+        self.repo
+            .save_backfill_progress(last_period_end.as_deref(), true)
+            .await?;
+        log::info!(
+            "Ratings backfill completed after {} periods",
+            periods_processed
+        );
+        Ok(RatingsBackfillReport {
+            periods_processed,
+            resumed_after,
+            already_complete: false,
+        })
+    }
+
+    /// Re-rates `period` (`YYYY-MM`) and every full month after it
+    ///
+    /// Ratings are first rolled back to where they stood before `period`, so
+    /// each month builds on the corrected one before it and no history is
+    /// written twice. Returns the number of months re-rated.
+    pub async fn recompute_from(&self, period: &str) -> Result<usize> {
+        let (mut year, mut month) = year_month(period)?;
+        self.repo
+            .rollback_ratings_after(&format!("{:04}-{:02}-01T00:00:00Z", year, month))
+            .await?;
+
+        let last = self.last_full_month();
+        let mut periods_processed = 0;
+        while (year, month) <= last {
+            self.recompute_month_with_history(Some(format!("{:04}-{:02}", year, month)))
+                .await?;
+            (year, month) = if month == 12 {
+                (year + 1, 1)
+            } else {
+                (year, month + 1)
+            };
+            periods_processed += 1;
+        }
+        Ok(periods_processed)
+    }
+
+    /// The most recent month that is over; the current one is left to the
+    /// scheduler until it ends
+    fn last_full_month(&self) -> (i32, u32) {
+        let now = self.clock.now();
+        if now.month() == 1 {
+            (now.year() - 1, 12)
+        } else {
+            (now.year(), now.month() - 1)
+        }
+    }
+
     /// Enhanced month recalculation that properly loads existing ratings
     pub async fn recompute_month_with_history(&self, period: Option<String>) -> Result<()> {
+        self.throttle.wait().await;
+
         // Determine previous month if None
         let (year, month) = if let Some(p) = period {
             let parts: Vec<_> = p.split('-').collect();
@@ -104,7 +261,7 @@ impl<C: ClientExt> RatingsUsecase<C> {
                     .map_err(|_| SharedError::BadRequest("invalid month".into()))?,
             )
         } else {
-            let now = Utc::now();
+            let now = self.clock.now();
             if now.month() == 1 {
                 (now.year() - 1, 12)
             } else {
@@ -323,12 +480,12 @@ impl<C: ClientExt> RatingsUsecase<C> {
                 // Player had games - apply full Glicko2 update
                 update_period(current_state, &samples, self.params)
             };
-            latest.insert(key, updated);
+            latest.insert(key, self.params.clamp_rating(updated));
         }
 
         // Persist latest and history docs (global scope)
         let period_end = end.clone();
-        let now = Utc::now().to_rfc3339();
+        let now = self.clock.now().to_rfc3339();
         for ((player_id, scope), state) in latest.into_iter() {
             if let RatingScope::Global = scope {
                 let gp = *games_played.get(&player_id).unwrap_or(&0);
@@ -364,10 +521,13 @@ impl<C: ClientExt> RatingsUsecase<C> {
                 self.repo.insert_rating_history(history_doc).await?;
             }
         }
+        self.record_period_ranks(&period_end).await?;
         Ok(())
     }
 
     pub async fn recompute_month(&self, period: Option<String>) -> Result<()> {
+        self.throttle.wait().await;
+
         // Determine previous month if None
         let (year, month) = if let Some(p) = period {
             let parts: Vec<_> = p.split('-').collect();
@@ -383,7 +543,7 @@ impl<C: ClientExt> RatingsUsecase<C> {
                     .map_err(|_| SharedError::BadRequest("invalid month".into()))?,
             )
         } else {
-            let now = Utc::now();
+            let now = self.clock.now();
             if now.month() == 1 {
                 (now.year() - 1, 12)
             } else {
@@ -496,12 +656,12 @@ impl<C: ClientExt> RatingsUsecase<C> {
             });
             // No inactivity model here (monthly recompute processes only this month)
             let updated = update_period(state, &samples, self.params);
-            latest.insert(key, updated);
+            latest.insert(key, self.params.clamp_rating(updated));
         }
 
         // Persist latest and history docs (global scope)
         let period_end = end.clone();
-        let now = Utc::now().to_rfc3339();
+        let now = self.clock.now().to_rfc3339();
         for ((player_id, scope), state) in latest.into_iter() {
             if let RatingScope::Global = scope {
                 let gp = *games_played.get(&player_id).unwrap_or(&0);
@@ -537,9 +697,78 @@ impl<C: ClientExt> RatingsUsecase<C> {
                 self.repo.insert_rating_history(history_doc).await?;
             }
         }
+        self.record_period_ranks(&period_end).await?;
         Ok(())
     }
 
+    /// Ranks every global latest rating after a period and stores the rank
+    /// on the latest rating and on that period's history entries
+    async fn record_period_ranks(&self, period_end: &str) -> Result<()> {
+        let ratings = self
+            .repo
+            .get_latest_ratings("global", None)
+            .await?
+            .into_iter()
+            .filter_map(|v| {
+                let player_id = v.get("player_id")?.as_str()?.to_string();
+                let rating = v.get("rating")?.as_f64()?;
+                Some((player_id, rating))
+            })
+            .collect();
+        let ranks: Vec<Value> = rank_by_rating(ratings)
+            .into_iter()
+            .map(|(player_id, rank)| serde_json::json!({ "player_id": player_id, "rank": rank }))
+            .collect();
+        self.repo
+            .set_period_ranks("global", None, period_end, Value::Array(ranks))
+            .await
+    }
+
+    /// Players rated within `window` points of `player_id`, closest first
+    pub async fn get_similar_rated(
+        &self,
+        player_id: &str,
+        scope: RatingScope,
+        window: f64,
+    ) -> Result<Vec<RatingLeaderboardEntryDto>> {
+        if !window.is_finite() || window < 0.0 {
+            return Err(SharedError::BadRequest(format!(
+                "Rating window must be a non-negative number, got {}",
+                window
+            )));
+        }
+        self.repo.get_similar_rated(player_id, &scope, window).await
+    }
+
+    /// The player's current global rank and its change since the previous
+    /// period they were ranked in
+    pub async fn get_rank_change(&self, player_id: &str) -> Result<RatingRankChangeDto> {
+        let row = self
+            .repo
+            .get_rank_change("global", None, player_id)
+            .await?
+            .unwrap_or(Value::Null);
+        let rank = row.get("rank").and_then(|x| x.as_i64()).map(|x| x as i32);
+        let previous_rank = row
+            .get("previous_rank")
+            .and_then(|x| x.as_i64())
+            .map(|x| x as i32);
+        Ok(RatingRankChangeDto {
+            player_id: player_id.to_string(),
+            rank,
+            period_end: row
+                .get("period_end")
+                .and_then(|x| x.as_str())
+                .map(|s| s.to_string()),
+            previous_rank,
+            previous_period_end: row
+                .get("previous_period_end")
+                .and_then(|x| x.as_str())
+                .map(|s| s.to_string()),
+            delta: rank.zip(previous_rank).map(|(now, before)| before - now),
+        })
+    }
+
     pub async fn get_leaderboard(
         &self,
         scope: RatingScope,
@@ -839,6 +1068,7 @@ impl<C: ClientExt> RatingsUsecase<C> {
         } else {
             update_period(before, &samples, self.params)
         };
+        let after = self.params.clamp_rating(after);
 
         // Compute Glicko-2 internals for transparency when there are samples
         let mut internals = serde_json::json!({});
@@ -1025,3 +1255,47 @@ impl<C: ClientExt> RatingsUsecase<C> {
         }
     }
 }
+
+/// Outcome of a [`RatingsUsecase::backfill_ratings`] run
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct RatingsBackfillReport {
+    /// Months replayed by this run
+    pub periods_processed: usize,
+    /// End of the last period an interrupted run had finished, if resuming
+    pub resumed_after: Option<String>,
+    /// An earlier run had already finished, so nothing was done
+    pub already_complete: bool,
+}
+
+/// Year and month of an ISO date such as `2024-03-01T00:00:00Z`
+fn year_month(date: &str) -> Result<(i32, u32)> {
+    let mut parts = date.split('-');
+    let year = parts
+        .next()
+        .and_then(|y| y.parse::<i32>().ok())
+        .ok_or_else(|| SharedError::BadRequest(format!("Invalid year in date: {}", date)))?;
+    let month = parts
+        .next()
+        .and_then(|m| m.parse::<u32>().ok())
+        .filter(|m| (1..=12).contains(m))
+        .ok_or_else(|| SharedError::BadRequest(format!("Invalid month in date: {}", date)))?;
+    Ok((year, month))
+}
+
+/// Leaderboard positions by rating, highest first
+///
+/// Equal ratings share a rank and the next rank skips past them (1, 2, 2, 4).
+pub fn rank_by_rating(mut ratings: Vec<(String, f64)>) -> Vec<(String, i32)> {
+    ratings.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    let mut ranked: Vec<(String, i32)> = Vec::with_capacity(ratings.len());
+    let mut previous: Option<f64> = None;
+    for (index, (player_id, rating)) in ratings.into_iter().enumerate() {
+        let rank = match (previous, ranked.last()) {
+            (Some(p), Some((_, r))) if p == rating => *r,
+            _ => index as i32 + 1,
+        };
+        previous = Some(rating);
+        ranked.push((player_id, rank));
+    }
+    ranked
+}
@@ -281,4 +281,51 @@ pub mod glicko_tests {
         assert_relative_eq!(result1.vol, result2.vol, epsilon = 1e-10);
         assert_relative_eq!(result1.vol, result3.vol, epsilon = 1e-10);
     }
+
+    #[test]
+    fn test_runaway_rating_is_clamped_to_ceiling_but_rd_and_volatility_are_not() {
+        let params = Glicko2Params {
+            rating_floor: Some(1000.0),
+            rating_ceiling: Some(1800.0),
+            ..Glicko2Params::default()
+        };
+        let wins = vec![
+            OpponentSample {
+                opp_rating: 2200.0,
+                opp_rd: 50.0,
+                score: 1.0,
+                weight: 1.0,
+            };
+            10
+        ];
+
+        // A string of wins over much stronger opponents, period after period
+        let mut state = RatingState {
+            rating: 1700.0,
+            rd: 350.0,
+            vol: 0.06,
+        };
+        for _ in 0..3 {
+            let unclamped = update_period(state, &wins, params);
+            assert!(unclamped.rating > 1800.0);
+            state = params.clamp_rating(unclamped);
+            assert_eq!(state.rating, 1800.0);
+            assert_eq!(state.rd, unclamped.rd);
+            assert_eq!(state.vol, unclamped.vol);
+        }
+
+        // Within bounds, or with no bounds configured, ratings are untouched
+        let mid = RatingState {
+            rating: 1500.0,
+            rd: 80.0,
+            vol: 0.06,
+        };
+        assert_eq!(params.clamp_rating(mid).rating, 1500.0);
+        let low = RatingState {
+            rating: 600.0,
+            ..mid
+        };
+        assert_eq!(params.clamp_rating(low).rating, 1000.0);
+        assert_eq!(Glicko2Params::default().clamp_rating(low).rating, 600.0);
+    }
 }
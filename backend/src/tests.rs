@@ -4,7 +4,12 @@ mod tests {
     use crate::config::Config;
     use crate::error::ApiError;
     use chrono::{Duration, Utc};
-    use shared::models::{contest::Contest, game::Game, player::Player, venue::Venue};
+    use shared::models::{
+        contest::{Contest, ContestVisibility},
+        game::Game,
+        player::Player,
+        venue::Venue,
+    };
 
     // Configuration tests
     #[test]
@@ -28,11 +33,14 @@ mod tests {
             id: "test_player".to_string(),
             rev: "1".to_string(),
             firstname: "John".to_string(),
+            lastname: None,
             handle: "testuser".to_string(),
             email: "test@example.com".to_string(),
             password: "hashed_password".to_string(),
             created_at: Utc::now().fixed_offset(),
             is_admin: false,
+            last_login_at: None,
+            last_login_ip: None,
         };
 
         assert_eq!(player.handle, "testuser");
@@ -46,11 +54,14 @@ mod tests {
             id: "test_player".to_string(),
             rev: "1".to_string(),
             firstname: "John".to_string(),
+            lastname: None,
             handle: "testuser".to_string(),
             email: "test@example.com".to_string(),
             password: "hashed_password".to_string(),
             created_at: Utc::now().fixed_offset(),
             is_admin: false,
+            last_login_at: None,
+            last_login_ip: None,
         };
 
         // Test basic validation
@@ -150,6 +161,8 @@ mod tests {
             stop: Utc::now().fixed_offset() + Duration::days(1),
             creator_id: "player/test_creator".to_string(),
             created_at: Utc::now().fixed_offset(),
+            rated: true,
+            visibility: ContestVisibility::Public,
         };
 
         assert_eq!(contest.name, "Test Contest");
@@ -196,11 +209,14 @@ mod tests {
             id: "test_player".to_string(),
             rev: "1".to_string(),
             firstname: "John".to_string(),
+            lastname: None,
             handle: "testuser".to_string(),
             email: "test@example.com".to_string(),
             password: "hashed_password".to_string(),
             created_at: Utc::now().fixed_offset(),
             is_admin: false,
+            last_login_at: None,
+            last_login_ip: None,
         };
 
         let start = std::time::Instant::now();
@@ -1,3 +1,4 @@
+use crate::config::BGGPaths;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use shared::models::game::Game;
@@ -76,6 +77,7 @@ struct BGGMaxPlayers {
 pub struct BGGService {
     api_url: String,
     api_token: Option<String>,
+    paths: BGGPaths,
     client: reqwest::Client,
 }
 
@@ -88,6 +90,7 @@ impl BGGService {
         Ok(Self {
             api_url,
             api_token,
+            paths: BGGPaths::default(),
             client: reqwest::Client::new(),
         })
     }
@@ -96,6 +99,7 @@ impl BGGService {
         Self {
             api_url: config.api_url.clone(),
             api_token: config.api_token.clone(),
+            paths: config.paths.clone(),
             client: reqwest::Client::new(),
         }
     }
@@ -104,10 +108,39 @@ impl BGGService {
         Self {
             api_url,
             api_token: None,
+            paths: BGGPaths::default(),
             client: reqwest::Client::new(),
         }
     }
 
+    /// Expands a path template against the API base URL.
+    ///
+    /// Returns the URL and the params that were not substituted into the path,
+    /// which still need to be sent as query parameters.
+    fn endpoint<'a>(
+        &self,
+        template: &str,
+        params: &[(&'a str, &'a str)],
+    ) -> (String, Vec<(&'a str, &'a str)>) {
+        let mut path = template.to_string();
+        let mut query = Vec::new();
+        for &(name, value) in params {
+            let placeholder = format!("{{{}}}", name);
+            if path.contains(&placeholder) {
+                path = path.replace(&placeholder, &urlencoding::encode(value));
+            } else {
+                query.push((name, value));
+            }
+        }
+
+        let url = format!(
+            "{}/{}",
+            self.api_url.trim_end_matches('/'),
+            path.trim_start_matches('/')
+        );
+        (url, query)
+    }
+
     /// Build a request with Authorization header if token is available
     fn build_request(&self, method: reqwest::Method, url: &str) -> reqwest::RequestBuilder {
         let mut request = self.client.request(method, url);
@@ -128,12 +161,14 @@ impl BGGService {
             return Ok(Vec::new());
         }
 
-        let search_url = format!("{}/search", self.api_url);
-        let params = [
-            ("query", query.trim()),
-            ("type", "boardgame"),
-            ("exact", "0"), // Allow partial matches
-        ];
+        let (search_url, params) = self.endpoint(
+            &self.paths.search,
+            &[
+                ("query", query.trim()),
+                ("type", "boardgame"),
+                ("exact", "0"), // Allow partial matches
+            ],
+        );
 
         log::info!("BGG API URL: {}", search_url);
         log::info!("BGG API params: {:?}", params);
@@ -251,8 +286,8 @@ impl BGGService {
     pub async fn get_game_details(&self, bgg_id: &str) -> Result<Option<Game>> {
         log::info!("Getting BGG game details for ID: {}", bgg_id);
 
-        let thing_url = format!("{}/thing", self.api_url);
-        let params = [("id", bgg_id), ("stats", "1")];
+        let (thing_url, params) =
+            self.endpoint(&self.paths.thing, &[("id", bgg_id), ("stats", "1")]);
 
         let response = self
             .build_request(reqwest::Method::GET, &thing_url)
@@ -280,8 +315,7 @@ impl BGGService {
     pub async fn get_popular_games(&self) -> Result<Vec<Game>> {
         log::info!("Getting popular games from BGG API");
 
-        let hot_url = format!("{}/hot", self.api_url);
-        let params = [("type", "boardgame")];
+        let (hot_url, params) = self.endpoint(&self.paths.hot, &[("type", "boardgame")]);
 
         let response = self
             .build_request(reqwest::Method::GET, &hot_url)
@@ -325,4 +359,64 @@ mod tests {
         assert!(service.api_url.starts_with("https://boardgamegeek.com"));
         assert!(service.api_url.contains("xmlapi2"));
     }
+
+    #[test]
+    fn test_default_paths_keep_bgg_urls() {
+        let service = BGGService::new_with_url("https://boardgamegeek.com/xmlapi2/".to_string());
+        let (url, params) = service.endpoint(&service.paths.thing, &[("id", "13"), ("stats", "1")]);
+        assert_eq!(url, "https://boardgamegeek.com/xmlapi2/thing");
+        assert_eq!(params, [("id", "13"), ("stats", "1")]);
+    }
+
+    #[actix_web::test]
+    async fn test_custom_path_templates_hit_proxy() {
+        use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer};
+        use std::sync::{Arc, Mutex};
+
+        // Records every request URI and answers with a single search result
+        let requests = Arc::new(Mutex::new(Vec::<String>::new()));
+        let recorded = requests.clone();
+        let server = HttpServer::new(move || {
+            let recorded = recorded.clone();
+            App::new().default_service(web::to(move |req: HttpRequest| {
+                let recorded = recorded.clone();
+                async move {
+                    recorded.lock().unwrap().push(req.uri().to_string());
+                    HttpResponse::Ok().content_type("application/xml").body(
+                        r#"<items><item type="boardgame" id="13"><name type="primary" value="Catan"/></item></items>"#,
+                    )
+                }
+            }))
+        })
+        .workers(1)
+        .bind(("127.0.0.1", 0))
+        .unwrap();
+        let addr = server.addrs()[0];
+        let handle = server.run();
+        actix_web::rt::spawn(handle);
+
+        let service = BGGService::new_with_config(&crate::config::BGGConfig {
+            api_url: format!("http://{}/proxy/bgg/", addr),
+            api_token: None,
+            paths: BGGPaths {
+                search: "/v2/items/search".to_string(),
+                thing: "/v2/items/{id}".to_string(),
+                hot: "/v2/hot".to_string(),
+            },
+        });
+
+        let games = service.search_games("Catan").await.unwrap();
+        assert_eq!(games.len(), 1);
+        assert_eq!(games[0].bgg_id, Some(13));
+        service.get_game_details("13").await.unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(
+            *requests,
+            [
+                "/proxy/bgg/v2/items/search?query=Catan&type=boardgame&exact=0",
+                "/proxy/bgg/v2/items/13?stats=1",
+            ]
+        );
+    }
 }
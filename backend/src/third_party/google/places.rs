@@ -50,6 +50,8 @@ struct GoogleLocation {
 pub struct GooglePlacesService {
     api_url: String,
     api_key: String,
+    region: Option<String>,
+    language: Option<String>,
     client: reqwest::Client,
 }
 
@@ -58,10 +60,31 @@ impl GooglePlacesService {
         Self {
             api_url,
             api_key,
+            region: None,
+            language: None,
             client: reqwest::Client::new(),
         }
     }
 
+    /// Bias results towards a region and language; `None` leaves Google's default
+    pub fn with_locale(mut self, region: Option<String>, language: Option<String>) -> Self {
+        self.region = region;
+        self.language = language;
+        self
+    }
+
+    /// The `region`/`language` query parameters sent with every request
+    fn locale_params(&self) -> Vec<(&str, &str)> {
+        let mut params = Vec::new();
+        if let Some(region) = &self.region {
+            params.push(("region", region.as_str()));
+        }
+        if let Some(language) = &self.language {
+            params.push(("language", language.as_str()));
+        }
+        params
+    }
+
     pub async fn search_places(&self, query: &str) -> Result<Vec<Venue>> {
         log::info!("Searching Google Places API for query: '{}'", query);
         log::info!("Using API URL: '{}'", self.api_url);
@@ -81,7 +104,13 @@ impl GooglePlacesService {
             key_prefix
         );
 
-        let response = self.client.get(&self.api_url).query(&params).send().await?;
+        let response = self
+            .client
+            .get(&self.api_url)
+            .query(&params)
+            .query(&self.locale_params())
+            .send()
+            .await?;
 
         log::info!("Autocomplete response status: {}", response.status());
 
@@ -151,7 +180,13 @@ impl GooglePlacesService {
 
         log::debug!("Getting place details for place_id: {}", place_id);
 
-        let response = self.client.get(&details_url).query(&params).send().await?;
+        let response = self
+            .client
+            .get(&details_url)
+            .query(&params)
+            .query(&self.locale_params())
+            .send()
+            .await?;
 
         log::debug!("Place details response status: {}", response.status());
 
@@ -212,6 +247,108 @@ mod tests {
         assert_eq!(service.api_key, "test_api_key");
     }
 
+    /// Answers autocomplete with one prediction and details with one place,
+    /// returning the request lines it received
+    fn mock_places_server() -> (String, std::sync::Arc<std::sync::Mutex<Vec<String>>>) {
+        use std::io::{BufRead, BufReader, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let requests = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let mut reader = BufReader::new(stream);
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    line.clear();
+                }
+
+                let body = if request_line.contains("/place/autocomplete/json") {
+                    serde_json::json!({
+                        "status": "OK",
+                        "predictions": [{
+                            "place_id": "abc",
+                            "description": "The Board Room, London",
+                            "structured_formatting": {
+                                "main_text": "The Board Room",
+                                "secondary_text": "London"
+                            }
+                        }]
+                    })
+                } else {
+                    serde_json::json!({
+                        "status": "OK",
+                        "result": {
+                            "place_id": "abc",
+                            "formatted_address": "1 High St, London",
+                            "name": "The Board Room",
+                            "geometry": { "location": { "lat": 51.5, "lng": -0.1 } }
+                        }
+                    })
+                }
+                .to_string();
+                recorded
+                    .lock()
+                    .unwrap()
+                    .push(request_line.trim().to_string());
+
+                let mut stream = reader.into_inner();
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+            }
+        });
+        (base, requests)
+    }
+
+    #[test(tokio::test)]
+    async fn test_search_places_forwards_region_and_language() {
+        let (base, requests) = mock_places_server();
+        let service = GooglePlacesService::new(
+            format!("{}/maps/api/place/autocomplete/json", base),
+            "test_api_key".to_string(),
+        )
+        .with_locale(Some("uk".to_string()), Some("en-GB".to_string()));
+
+        let venues = service.search_places("board room").await.unwrap();
+        assert_eq!(venues.len(), 1);
+        assert_eq!(venues[0].display_name, "The Board Room");
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(
+            requests.len(),
+            2,
+            "autocomplete and details: {:?}",
+            requests
+        );
+        for request in requests.iter() {
+            assert!(request.contains("region=uk"), "{}", request);
+            assert!(request.contains("language=en-GB"), "{}", request);
+        }
+    }
+
+    #[test(tokio::test)]
+    async fn test_search_places_without_locale_sends_no_bias() {
+        let (base, requests) = mock_places_server();
+        let service = GooglePlacesService::new(
+            format!("{}/maps/api/place/autocomplete/json", base),
+            "test_api_key".to_string(),
+        );
+
+        service.search_places("board room").await.unwrap();
+
+        for request in requests.lock().unwrap().iter() {
+            assert!(!request.contains("region="), "{}", request);
+            assert!(!request.contains("language="), "{}", request);
+        }
+    }
+
     #[test]
     fn test_google_places_service_api_url_format() {
         let service = GooglePlacesService::new(
@@ -71,6 +71,10 @@ pub trait VenueRepository: Send + Sync {
     async fn create(&self, venue: Venue) -> Result<Venue, String>;
     async fn update(&self, venue: Venue) -> Result<Venue, String>;
     async fn delete(&self, id: &str) -> Result<(), String>;
+    /// Marks `source_id` as merged into `target_id`, keeping its document so
+    /// analytics can attribute its contests to the survivor. Returns `false`
+    /// when the target was itself merged into another venue.
+    async fn merge(&self, source_id: &str, target_id: &str) -> Result<bool, String>;
 }
 
 impl VenueRepositoryImpl {
@@ -106,6 +110,14 @@ impl VenueRepositoryImpl {
         }
     }
 
+    /// Bias Google Places searches towards a region and language
+    pub fn with_places_locale(mut self, region: Option<String>, language: Option<String>) -> Self {
+        self.google_places = self
+            .google_places
+            .map(|places| places.with_locale(region, language));
+        self
+    }
+
     /// Infer timezone from coordinates (simplified mapping)
     fn infer_timezone_from_coordinates(&self, _lat: f64, lng: f64) -> String {
         // Simplified timezone inference based on longitude
@@ -1100,6 +1112,38 @@ impl VenueRepository for VenueRepositoryImpl {
         }
     }
 
+    async fn merge(&self, source_id: &str, target_id: &str) -> Result<bool, String> {
+        // Venues already merged into the source follow it to the target, so
+        // every merged venue points straight at a survivor
+        let query = arangors::AqlQuery::builder()
+            .query(
+                r#"
+                LET target = DOCUMENT(@target_id)
+                FOR v IN venue
+                FILTER target != null AND target.merged_into == null
+                FILTER v._id == @source_id OR v.merged_into == @source_id
+                UPDATE v WITH { merged_into: @target_id } IN venue
+                RETURN NEW._id
+            "#,
+            )
+            .bind_var("source_id", source_id)
+            .bind_var("target_id", target_id)
+            .build();
+
+        match self.db.aql_query::<String>(query).await {
+            Ok(merged) => {
+                if let Some(ref cache) = self.cache {
+                    for id in &merged {
+                        let _ = cache.delete(&CacheKeys::venue(id)).await;
+                    }
+                    let _ = cache.invalidate_pattern("venues:search:").await;
+                }
+                Ok(!merged.is_empty())
+            }
+            Err(e) => Err(format!("Failed to merge venue: {}", e)),
+        }
+    }
+
     async fn search_dto_with_external(&self, query: &str) -> Vec<VenueDto> {
         log::info!(
             "🔍 Starting venue search with external APIs for query: '{}'",
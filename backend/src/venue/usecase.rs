@@ -1,6 +1,7 @@
 use crate::venue::repository::VenueRepository;
 use shared::dto::venue::VenueDto;
 use shared::models::venue::Venue;
+use shared::SharedError;
 use validator::Validate;
 
 #[async_trait::async_trait]
@@ -18,6 +19,7 @@ pub trait VenueUseCase: Send + Sync {
     async fn create_venue(&self, venue_dto: VenueDto) -> Result<Venue, String>;
     async fn update_venue(&self, id: &str, venue_dto: VenueDto) -> Result<Venue, String>;
     async fn delete_venue(&self, id: &str) -> Result<(), String>;
+    async fn merge_venues(&self, source_id: &str, target_id: &str) -> Result<Venue, SharedError>;
 }
 
 pub struct VenueUseCaseImpl<R: VenueRepository> {
@@ -90,6 +92,35 @@ impl<R: VenueRepository> VenueUseCase for VenueUseCaseImpl<R> {
         self.repo.delete(id).await
     }
 
+    async fn merge_venues(&self, source_id: &str, target_id: &str) -> Result<Venue, SharedError> {
+        if source_id == target_id {
+            return Err(SharedError::BadRequest(
+                "Cannot merge a venue into itself".to_string(),
+            ));
+        }
+        self.repo
+            .find_by_id(source_id)
+            .await
+            .ok_or_else(|| SharedError::NotFound(format!("Venue {} not found", source_id)))?;
+        let target = self
+            .repo
+            .find_by_id(target_id)
+            .await
+            .ok_or_else(|| SharedError::NotFound(format!("Venue {} not found", target_id)))?;
+
+        if !self
+            .repo
+            .merge(source_id, target_id)
+            .await
+            .map_err(SharedError::Database)?
+        {
+            return Err(SharedError::Conflict(
+                "Cannot merge into a venue that was itself merged".to_string(),
+            ));
+        }
+        Ok(target)
+    }
+
     async fn get_venue_performance(&self, venue_id: &str) -> Result<serde_json::Value, String> {
         self.repo.get_venue_performance(venue_id).await
     }
@@ -0,0 +1,62 @@
+//! Tests for slow analytics query warnings
+//!
+//! Runs in its own test binary so it can install a capturing logger.
+
+use backend::analytics::timing::{QueryTimer, SLOW_QUERY_TARGET};
+use log::{Level, Log, Metadata, Record};
+use std::sync::Mutex;
+use std::time::Duration;
+
+static CAPTURED: Mutex<Vec<(Level, String)>> = Mutex::new(Vec::new());
+
+struct CaptureLogger;
+
+impl Log for CaptureLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.target() == SLOW_QUERY_TARGET
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            CAPTURED
+                .lock()
+                .unwrap()
+                .push((record.level(), record.args().to_string()));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CaptureLogger = CaptureLogger;
+
+/// Stand-in for a repository method whose query takes `query_time`
+async fn run_query(method: &'static str, query_time: Duration) {
+    let _timer = QueryTimer::start(
+        method,
+        "player_id=player/1".to_string(),
+        Duration::from_millis(50),
+    );
+    tokio::time::sleep(query_time).await;
+}
+
+#[tokio::test]
+async fn test_slow_queries_warn_and_fast_queries_stay_quiet() {
+    log::set_logger(&LOGGER).expect("logger already set");
+    log::set_max_level(log::LevelFilter::Warn);
+
+    run_query("fast_query", Duration::ZERO).await;
+    run_query("slow_query", Duration::from_millis(120)).await;
+
+    let captured = CAPTURED.lock().unwrap();
+    assert_eq!(captured.len(), 1, "only the slow query should warn");
+    let (level, message) = &captured[0];
+    assert_eq!(*level, Level::Warn);
+    assert!(message.contains("method=slow_query"), "{}", message);
+    assert!(
+        message.contains("params=[player_id=player/1]"),
+        "{}",
+        message
+    );
+    assert!(message.contains("budgetMs=50"), "{}", message);
+}
@@ -0,0 +1,31 @@
+//! Tests for routes registered behind feature flags
+
+use actix_web::{http::StatusCode, test, web, App};
+use backend::config::FeatureFlags;
+
+async fn api_docs_status(features: FeatureFlags) -> StatusCode {
+    let app = test::init_service(
+        App::new()
+            .configure(|cfg| backend::openapi::configure_api_docs(cfg, &features))
+            .default_service(web::route().to(backend::error::not_found_handler)),
+    )
+    .await;
+    let req = test::TestRequest::get()
+        .uri("/api-docs/openapi.json")
+        .to_request();
+    test::call_service(&app, req).await.status()
+}
+
+#[actix_web::test]
+async fn test_disabled_api_docs_are_not_found() {
+    assert_eq!(
+        api_docs_status(FeatureFlags::default()).await,
+        StatusCode::OK
+    );
+
+    let disabled = FeatureFlags {
+        api_docs: false,
+        ..FeatureFlags::default()
+    };
+    assert_eq!(api_docs_status(disabled).await, StatusCode::NOT_FOUND);
+}
@@ -14,29 +14,182 @@ use serde_json::json;
 use shared::{Contest, Game, PlayedAt, PlayedWith, Player, ResultedIn, Venue};
 use std::collections::HashMap;
 use std::env;
+use std::time::{Duration, Instant};
+
+/// How long the loader waits for ArangoDB to accept connections by default
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Pause before connection retry `attempt`: quick retries while a freshly
+/// started container comes up, backing off to one attempt every 3s
+fn retry_delay(attempt: u32) -> Duration {
+    Duration::from_millis((300 + u64::from(attempt) * 150).min(3000))
+}
+
+/// Connects to ArangoDB, retrying with backoff until `max_wait` has passed
+async fn connect_with_retry(
+    host: &str,
+    user: &str,
+    password: &str,
+    max_wait: Duration,
+) -> Result<Connection> {
+    let start_time = Instant::now();
+    let mut attempt = 0;
+    loop {
+        match Connection::establish_basic_auth(host, user, password).await {
+            Ok(conn) => {
+                if attempt > 0 {
+                    info!(
+                        "Connected to ArangoDB after {} attempts ({:.1}s)",
+                        attempt + 1,
+                        start_time.elapsed().as_secs_f64()
+                    );
+                }
+                return Ok(conn);
+            }
+            Err(e) => {
+                let wait = retry_delay(attempt);
+                if start_time.elapsed() + wait > max_wait {
+                    return Err(e).context(format!(
+                        "ArangoDB at {} was not ready within {}s",
+                        host,
+                        max_wait.as_secs()
+                    ));
+                }
+                warn!(
+                    "ArangoDB not ready (attempt {}): {}, retrying in {}ms",
+                    attempt + 1,
+                    e,
+                    wait.as_millis()
+                );
+                tokio::time::sleep(wait).await;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+const DOCUMENT_COLLECTIONS: [&str; 4] = ["player", "game", "venue", "contest"];
+const EDGE_COLLECTIONS: [&str; 3] = ["played_at", "played_with", "resulted_in"];
+
+/// Edges are the same edge when they join the same two documents
+const EDGE_NATURAL_KEY: &str = "{ _from: @doc._from, _to: @doc._to }";
+
+/// The `_key` part of a document `_id`
+fn document_key(id: &str) -> String {
+    id.rsplit('/').next().unwrap_or(id).to_string()
+}
 
 pub struct DbClient {
     db: Database<ReqwestClient>,
     cache: DocumentCache,
+    upsert: bool,
 }
 
 impl DbClient {
-    pub async fn new() -> Result<Self> {
+    /// Connects using the `ARANGO_*` environment variables
+    ///
+    /// Waits up to `ARANGO_CONNECT_TIMEOUT_SECS` (default 60) for the
+    /// database to come up. With `upsert` the existing data is kept and
+    /// records are loaded with [`DbClient::with_upsert`]; otherwise the
+    /// database is recreated empty.
+    pub async fn new(upsert: bool) -> Result<Self> {
         let host = env::var("ARANGO_URL").context("ARANGO_URL not set")?;
         let db_name = env::var("ARANGO_DB").context("ARANGO_DB not set")?;
         let user = env::var("ARANGO_USERNAME").context("ARANGO_USERNAME not set")?;
         let password = env::var("ARANGO_PASSWORD").context("ARANGO_PASSWORD not set")?;
+        let connect_timeout = env::var("ARANGO_CONNECT_TIMEOUT_SECS")
+            .ok()
+            .and_then(|secs| secs.parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_CONNECT_TIMEOUT);
+
+        if upsert {
+            Ok(
+                Self::connect_existing(&host, &db_name, &user, &password, connect_timeout)
+                    .await?
+                    .with_upsert(true),
+            )
+        } else {
+            Self::connect(&host, &db_name, &user, &password, connect_timeout).await
+        }
+    }
 
-        let conn = Connection::establish_basic_auth(&host, &user, &password)
-            .await
-            .context("Failed to connect to ArangoDB")?;
+    /// Connects like [`DbClient::connect`] but keeps `db_name` and its
+    /// documents, creating the database and collections only where missing
+    pub async fn connect_existing(
+        host: &str,
+        db_name: &str,
+        user: &str,
+        password: &str,
+        connect_timeout: Duration,
+    ) -> Result<Self> {
+        let conn = connect_with_retry(host, user, password, connect_timeout).await?;
+
+        if conn.db(db_name).await.is_err() {
+            conn.create_database(db_name)
+                .await
+                .context(format!("Failed to create database {}", db_name))?;
+            info!("Created database {}", db_name);
+        }
+        let db = conn.db(db_name).await.context("Failed to get database")?;
+
+        for name in DOCUMENT_COLLECTIONS.iter().chain(EDGE_COLLECTIONS.iter()) {
+            match db.collection(name).await {
+                Ok(_) => info!("Using existing collection {}", name),
+                Err(ClientError::Arango(arango_error)) if arango_error.error_num() == 1203 => {
+                    if EDGE_COLLECTIONS.contains(name) {
+                        db.create_edge_collection(name).await
+                    } else {
+                        db.create_collection(name).await
+                    }
+                    .context(format!("Failed to create collection {}", name))?;
+                    info!("Created collection {}", name);
+                }
+                Err(e) => return Err(e).context(format!("Failed to get collection {}", name)),
+            }
+        }
+
+        Ok(Self {
+            db,
+            cache: DocumentCache::new(),
+            upsert: false,
+        })
+    }
+
+    /// Makes [`DbClient::load_records`] idempotent
+    ///
+    /// Each document is matched on its natural key and updated in place if
+    /// it exists, and edges are matched on `_from` and `_to`, so loading an
+    /// export that overlaps one already loaded adds no duplicates:
+    ///
+    /// - `venue`: `place_id`
+    /// - `game`: `bgg_id`, or `name` for games without one
+    /// - `player`: `email`
+    /// - `contest`: `name` and `start`
+    pub fn with_upsert(mut self, upsert: bool) -> Self {
+        self.upsert = upsert;
+        self
+    }
+
+    /// Connects to ArangoDB at `host`, waiting up to `connect_timeout` for it
+    /// to come up, and recreates `db_name` with empty collections
+    ///
+    /// Safe to repeat: an existing database and collections are dropped first.
+    pub async fn connect(
+        host: &str,
+        db_name: &str,
+        user: &str,
+        password: &str,
+        connect_timeout: Duration,
+    ) -> Result<Self> {
+        let conn = connect_with_retry(host, user, password, connect_timeout).await?;
 
         // Try to drop the database if it exists using REST API
         let client = Client::new();
         let url = format!("{}/_db/_system/_api/database/{}", host, db_name);
         let response = client
             .delete(&url)
-            .basic_auth(&user, Some(&password))
+            .basic_auth(user, Some(password))
             .send()
             .await;
 
@@ -58,7 +211,7 @@ impl DbClient {
         let url = format!("{}/_db/_system/_api/database", host);
         let response = client
             .post(&url)
-            .basic_auth(&user, Some(&password))
+            .basic_auth(user, Some(password))
             .json(&json!({
                 "name": db_name,
                 "users": [{
@@ -78,15 +231,13 @@ impl DbClient {
         info!("Created database {}", db_name);
 
         // Connect to the new database
-        let db = conn.db(&db_name).await.context("Failed to get database")?;
+        let db = conn.db(db_name).await.context("Failed to get database")?;
 
         // Create collections
         let mut collections = HashMap::new();
-        let document_collections = ["player", "game", "venue", "contest"];
-        let edge_collections = ["played_at", "played_with", "resulted_in"];
 
         // Create document collections
-        for name in document_collections.iter() {
+        for name in DOCUMENT_COLLECTIONS.iter() {
             // Try to drop collection if it exists
             match db.drop_collection(name).await {
                 Ok(_) => info!("Dropped existing collection {}", name),
@@ -106,7 +257,7 @@ impl DbClient {
         }
 
         // Create edge collections
-        for name in edge_collections.iter() {
+        for name in EDGE_COLLECTIONS.iter() {
             // Try to drop collection if it exists
             match db.drop_collection(name).await {
                 Ok(_) => info!("Dropped existing collection {}", name),
@@ -128,6 +279,7 @@ impl DbClient {
         Ok(Self {
             db,
             cache: DocumentCache::new(),
+            upsert: false,
         })
     }
 
@@ -179,6 +331,33 @@ impl DbClient {
         Ok(())
     }
 
+    /// Inserts `doc` into `collection` unless a document matches `search`,
+    /// an AQL object over `@doc`, in which case it gets `update` instead
+    async fn upsert_document<T: serde::Serialize, R: serde::de::DeserializeOwned>(
+        &self,
+        collection: &str,
+        search: &str,
+        update: &str,
+        doc: &T,
+    ) -> Result<R> {
+        let aql = format!(
+            "UPSERT {} INSERT UNSET(@doc, \"_id\", \"_rev\") UPDATE {} IN {} RETURN NEW",
+            search, update, collection
+        );
+        let query = arangors::AqlQuery::builder()
+            .query(&aql)
+            .bind_var("doc", serde_json::to_value(doc)?)
+            .build();
+        let mut result: Vec<R> = self
+            .db
+            .aql_query(query)
+            .await
+            .context(format!("Failed to upsert into {:?}", collection))?;
+        result
+            .pop()
+            .ok_or_else(|| anyhow!("Upsert into {:?} returned nothing", collection))
+    }
+
     async fn create_edge<T: serde::Serialize + for<'de> serde::Deserialize<'de>>(
         &self,
         collection: &str,
@@ -186,6 +365,23 @@ impl DbClient {
         to: &str,
         edge: T,
     ) -> Result<String> {
+        if self.upsert {
+            let edge: serde_json::Value = self
+                .upsert_document(
+                    collection,
+                    EDGE_NATURAL_KEY,
+                    "UNSET(@doc, \"_id\", \"_rev\", \"_from\", \"_to\")",
+                    &edge,
+                )
+                .await?;
+            let key = edge["_key"].as_str().unwrap_or_default().to_string();
+            info!(
+                "Upserted edge in {:?} from {} to {} (key: {})",
+                collection, from, to, key
+            );
+            return Ok(key);
+        }
+
         let edge_collection = self.db.collection(collection).await?;
         let edge_doc = Document::new(edge);
         let result = edge_collection
@@ -215,6 +411,29 @@ impl DbClient {
             return Ok(doc.document);
         }
 
+        if self.upsert {
+            let new_venue = Venue::new_for_db(
+                venue.display_name.clone(),
+                venue.formatted_address.clone(),
+                venue.place_id.clone(),
+                venue.lat,
+                venue.lng,
+                "UTC".to_string(),
+                shared::models::venue::VenueSource::Database,
+            )?;
+            let venue: Venue = self
+                .upsert_document(
+                    "venue",
+                    "{ place_id: @doc.place_id }",
+                    "KEEP(@doc, \"display_name\", \"formatted_address\", \"lat\", \"lng\")",
+                    &new_venue,
+                )
+                .await?;
+            info!("Upserted venue: {} (id: {})", venue.display_name, venue.id);
+            self.cache.store_venue(place_id, document_key(&venue.id));
+            return Ok(venue);
+        }
+
         // Check if venue exists in database using parameterized query
         let collection = self.db.collection("venue").await?;
         let query = arangors::AqlQuery::builder()
@@ -309,6 +528,33 @@ impl DbClient {
             return Ok(doc.document);
         }
 
+        if self.upsert {
+            let new_game = Game::new_for_db(
+                game.name.clone(),
+                Some(game.year_published),
+                game.bgg_id,
+                None,
+                shared::models::game::GameSource::Database,
+            )?;
+            let search = if new_game.bgg_id.is_some() {
+                "{ bgg_id: @doc.bgg_id }"
+            } else {
+                "{ name: @doc.name }"
+            };
+            let upserted: Game = self
+                .upsert_document(
+                    "game",
+                    search,
+                    "KEEP(@doc, \"name\", \"year_published\")",
+                    &new_game,
+                )
+                .await?;
+            info!("Upserted game: {} (id: {})", upserted.name, upserted.id);
+            self.cache
+                .store_game(game.name.clone(), document_key(&upserted.id));
+            return Ok(upserted);
+        }
+
         // Check if game exists in database using parameterized query
         let collection = self.db.collection("game").await?;
         let query = arangors::AqlQuery::builder()
@@ -378,6 +624,24 @@ impl DbClient {
             return Ok(doc.document);
         }
 
+        if self.upsert {
+            let new_player = Player::new_for_db(
+                outcome.player_id.clone(),
+                outcome.player_id.clone(),
+                email.clone(),
+                Self::hash_password("letmein")?,
+                chrono::Utc::now().with_timezone(&chrono::FixedOffset::east_opt(0).unwrap()),
+                false,
+            )?;
+            // Players may have changed their details since; leave them be
+            let player: Player = self
+                .upsert_document("player", "{ email: @doc.email }", "{}", &new_player)
+                .await?;
+            info!("Upserted player: {} (id: {})", outcome.player_id, player.id);
+            self.cache.store_player(email, document_key(&player.id));
+            return Ok(player);
+        }
+
         // Check if player exists in database using parameterized query
         let collection = self.db.collection("player").await?;
         let query = arangors::AqlQuery::builder()
@@ -429,11 +693,14 @@ impl DbClient {
             id: player_id,
             rev: header._rev.clone(),
             firstname: player.firstname,
+            lastname: None,
             handle: player.handle,
             email: player.email,
             password: player.password,
             created_at: player.created_at,
             is_admin: false,
+            last_login_at: None,
+            last_login_ip: None,
         })
     }
 
@@ -479,14 +746,32 @@ impl DbClient {
         }
 
         // Create new contest - let ArangoDB set key, id, and rev
-        let contest_doc: Contest = contest.into();
-        let collection = self.db.collection("contest").await?;
-        let doc = collection
-            .create_document(contest_doc.clone(), InsertOptions::default())
-            .await?;
-        let header = doc.header().unwrap();
-        let contest_id = header._id.clone();
-        info!("Created new contest: {} (id: {})", contest.name, contest_id);
+        let mut contest_doc: Contest = contest.into();
+        let contest_id = if self.upsert {
+            // Only the export's own fields are refreshed; rated, visibility
+            // and created_at keep whatever the app has since set
+            contest_doc = self
+                .upsert_document(
+                    "contest",
+                    "{ name: @doc.name, start: @doc.start }",
+                    "KEEP(@doc, \"stop\")",
+                    &contest_doc,
+                )
+                .await?;
+            info!(
+                "Upserted contest: {} (id: {})",
+                contest.name, contest_doc.id
+            );
+            contest_doc.id.clone()
+        } else {
+            let collection = self.db.collection("contest").await?;
+            let doc = collection
+                .create_document(contest_doc.clone(), InsertOptions::default())
+                .await?;
+            let header = doc.header().unwrap();
+            info!("Created new contest: {} (id: {})", contest.name, header._id);
+            header._id.clone()
+        };
 
         // Create edges (no caching needed)
         // Create played_at edge
@@ -0,0 +1,8 @@
+//! Loads legacy game session records into ArangoDB
+//!
+//! The `dataload` binary reads `stg_records.json`; the modules are exposed so
+//! the integration tests can drive the loader directly.
+
+pub mod db;
+pub mod models;
+pub mod records;
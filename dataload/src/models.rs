@@ -322,6 +322,8 @@ impl From<&StgContest> for Contest {
             name: contest.name.clone(),
             creator_id: String::new(),
             created_at: chrono::Utc::now().fixed_offset(),
+            rated: true,
+            visibility: shared::models::contest::ContestVisibility::Public,
         };
 
         contest_doc
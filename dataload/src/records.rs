@@ -0,0 +1,253 @@
+//! Streams contests out of a `stg_records.json` export
+//!
+//! The export is one top-level JSON array. Elements are parsed one at a time
+//! so a large file is never held in memory whole.
+
+use anyhow::{anyhow, Context, Result};
+use log::info;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::io::BufRead;
+
+use crate::models::{StgContest, StgGame, StgOutcome, StgVenue};
+
+/// Iterates the contests of a top-level JSON array read from `reader`
+///
+/// An element that is valid JSON but not a contest is returned as an error,
+/// with its fields logged, and iteration carries on with the next element.
+/// Iteration ends after an error in the JSON itself.
+pub struct ContestStream<R> {
+    reader: R,
+    index: usize,
+    state: State,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Before the opening `[`
+    Start,
+    /// After `[` or a `,`, where an element or `]` may follow
+    Element,
+    /// After an element, where `,` or `]` may follow
+    Separator,
+    /// After the closing `]`
+    Done,
+    /// After an error; nothing more is read
+    Failed,
+}
+
+impl<R: BufRead> ContestStream<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            index: 0,
+            state: State::Start,
+        }
+    }
+
+    /// The next byte after any whitespace, left unconsumed
+    fn peek_token(&mut self) -> Result<Option<u8>> {
+        loop {
+            let buf = self
+                .reader
+                .fill_buf()
+                .context("Failed to read records file")?;
+            let Some(&byte) = buf.first() else {
+                return Ok(None);
+            };
+            if byte.is_ascii_whitespace() {
+                self.reader.consume(1);
+            } else {
+                return Ok(Some(byte));
+            }
+        }
+    }
+
+    fn expect_token(&mut self, expected: &[u8]) -> Result<u8> {
+        match self.peek_token()? {
+            Some(byte) if expected.contains(&byte) => {
+                self.reader.consume(1);
+                Ok(byte)
+            }
+            Some(byte) => Err(anyhow!(
+                "Invalid JSON structure after element {}: expected one of {:?}, found {:?}",
+                self.index,
+                String::from_utf8_lossy(expected),
+                byte as char
+            )),
+            None => Err(anyhow!(
+                "Unexpected end of file after element {}",
+                self.index
+            )),
+        }
+    }
+
+    /// Reads the next element; the outer error is invalid JSON, the inner
+    /// one an element that is not a contest
+    fn read_element(&mut self) -> Result<Result<StgContest>> {
+        let i = self.index;
+        self.index += 1;
+
+        let value = serde_json::Deserializer::from_reader(&mut self.reader)
+            .into_iter::<Value>()
+            .next()
+            .ok_or_else(|| anyhow!("Unexpected end of file in element {}", i))?
+            .with_context(|| format!("Failed to parse element {}", i))?;
+
+        Ok(StgContest::deserialize(&value).map_err(|e| {
+            info!("Failed to convert element {}: {}", i, e);
+            describe_invalid_contest(&value);
+            anyhow::Error::from(e).context(format!("Failed to convert element {}", i))
+        }))
+    }
+
+    fn advance(&mut self) -> Result<Option<Result<StgContest>>> {
+        loop {
+            match self.state {
+                State::Start => {
+                    if self.peek_token()? != Some(b'[') {
+                        return Err(anyhow!("Invalid JSON structure: must be an array"));
+                    }
+                    self.reader.consume(1);
+                    self.state = State::Element;
+                    if self.peek_token()? == Some(b']') {
+                        self.reader.consume(1);
+                        self.state = State::Done;
+                    }
+                }
+                State::Element => {
+                    let contest = self.read_element()?;
+                    self.state = State::Separator;
+                    return Ok(Some(contest));
+                }
+                State::Separator => {
+                    self.state = match self.expect_token(b",]")? {
+                        b',' => State::Element,
+                        _ => State::Done,
+                    };
+                }
+                State::Done => {
+                    if let Some(byte) = self.peek_token()? {
+                        return Err(anyhow!(
+                            "Unexpected {:?} after the closing ']'",
+                            byte as char
+                        ));
+                    }
+                    return Ok(None);
+                }
+                State::Failed => return Ok(None),
+            }
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for ContestStream<R> {
+    type Item = Result<StgContest>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.advance() {
+            Ok(contest) => contest,
+            Err(e) => {
+                self.state = State::Failed;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// Logs each field of an element that failed to parse as a contest, and
+/// whether the fields with their own types parse on their own
+pub fn describe_invalid_contest(value: &Value) {
+    let Some(obj) = value.as_object() else {
+        info!("Element is not an object: {}", value);
+        return;
+    };
+
+    for (key, val) in obj {
+        match key.as_str() {
+            "start" | "stop" => {
+                info!("DateTime field {}: {}", key, val);
+                if let Some(dt_str) = val.as_str() {
+                    match chrono::DateTime::parse_from_rfc3339(dt_str) {
+                        Ok(_dt) => info!("Successfully parsed {} as DateTime", key),
+                        Err(e) => info!("Failed to parse {} as DateTime: {}", key, e),
+                    }
+                }
+            }
+            "venue" => {
+                info!("Venue field: {}", pretty(val));
+                match serde_json::from_value::<StgVenue>(val.clone()) {
+                    Ok(_) => info!("Successfully parsed venue"),
+                    Err(e) => info!("Failed to parse venue: {}", e),
+                }
+            }
+            "games" => {
+                info!("Games field: {}", pretty(val));
+                match serde_json::from_value::<Vec<StgGame>>(val.clone()) {
+                    Ok(_) => info!("Successfully parsed games"),
+                    Err(e) => info!("Failed to parse games: {}", e),
+                }
+            }
+            "outcome" => {
+                info!("Outcome field: {}", pretty(val));
+                match serde_json::from_value::<Vec<StgOutcome>>(val.clone()) {
+                    Ok(_) => info!("Successfully parsed outcome"),
+                    Err(e) => info!("Failed to parse outcome: {}", e),
+                }
+            }
+            _ => info!("Other field {}: {}", key, val),
+        }
+    }
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+}
+
+/// What a validation pass found in an export
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationSummary {
+    pub contests: usize,
+    /// Distinct games, by name
+    pub games: usize,
+    /// Distinct venues, by display name
+    pub venues: usize,
+    pub outcomes: usize,
+    /// Elements that are valid JSON but not contests
+    pub failed: usize,
+}
+
+/// Parses every contest in an export without loading it anywhere
+///
+/// Elements that are not contests are logged, passed to `on_failure` and
+/// counted; an error is returned only when the file is not a JSON array of
+/// well-formed elements.
+pub fn validate<R: BufRead>(
+    reader: R,
+    mut on_failure: impl FnMut(&anyhow::Error),
+) -> Result<ValidationSummary> {
+    let mut summary = ValidationSummary::default();
+    let mut games = HashSet::new();
+    let mut venues = HashSet::new();
+
+    let mut contests = ContestStream::new(reader);
+    while let Some(contest) = contests.advance()? {
+        match contest {
+            Ok(contest) => {
+                summary.contests += 1;
+                summary.outcomes += contest.outcome.len();
+                games.extend(contest.games.into_iter().map(|g| g.name));
+                venues.insert(contest.venue.display_name);
+            }
+            Err(e) => {
+                summary.failed += 1;
+                on_failure(&e);
+            }
+        }
+    }
+
+    summary.games = games.len();
+    summary.venues = venues.len();
+    Ok(summary)
+}
@@ -0,0 +1,149 @@
+use gloo_storage::{LocalStorage, Storage};
+use gloo_timers::callback::Interval;
+use shared::models::client_storage::{RefreshInterval, StorageKey};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use web_sys::{AbortController, AbortSignal};
+use yew::prelude::*;
+
+/// Reads the persisted dashboard refresh interval, defaulting to off
+pub fn load_refresh_interval() -> RefreshInterval {
+    LocalStorage::get(StorageKey::DashboardRefreshInterval.to_string()).unwrap_or_default()
+}
+
+pub fn save_refresh_interval(interval: RefreshInterval) {
+    let _ = LocalStorage::set(StorageKey::DashboardRefreshInterval.to_string(), interval);
+}
+
+/// Emits `on_tick` every `period_ms` milliseconds while a period is set.
+///
+/// The timer is restarted whenever `period_ms` or `reset_key` changes and is
+/// dropped on unmount, so switching tabs never leaves a stale timer running.
+#[hook]
+pub fn use_polling<K>(period_ms: Option<u32>, reset_key: K, on_tick: Callback<()>)
+where
+    K: PartialEq + 'static,
+{
+    use_effect_with((period_ms, reset_key), move |(period_ms, _)| {
+        let interval = period_ms.map(|ms| Interval::new(ms, move || on_tick.emit(())));
+        move || drop(interval)
+    });
+}
+
+/// Per-section refresh counters; effects depend on their section's count
+#[derive(Default, PartialEq)]
+pub struct RefreshCounts(HashMap<&'static str, u32>);
+
+impl RefreshCounts {
+    pub fn get(&self, section: &str) -> u32 {
+        self.0.get(section).copied().unwrap_or(0)
+    }
+}
+
+impl Reducible for RefreshCounts {
+    type Action = &'static str;
+
+    fn reduce(self: Rc<Self>, section: Self::Action) -> Rc<Self> {
+        let mut counts = self.0.clone();
+        *counts.entry(section).or_insert(0) += 1;
+        Rc::new(RefreshCounts(counts))
+    }
+}
+
+/// Abort handle shared by all refresh requests of the visible tab
+#[derive(Clone, Default)]
+pub struct InFlight(Rc<RefCell<Option<AbortController>>>);
+
+impl PartialEq for InFlight {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl InFlight {
+    /// Signal to attach to a refresh request so `cancel` can abort it
+    pub fn signal(&self) -> Option<AbortSignal> {
+        let mut controller = self.0.borrow_mut();
+        if controller.is_none() {
+            *controller = AbortController::new().ok();
+        }
+        controller.as_ref().map(|c| c.signal())
+    }
+
+    /// Aborts every request started with the current signal
+    pub fn cancel(&self) {
+        if let Some(controller) = self.0.borrow_mut().take() {
+            controller.abort();
+        }
+    }
+}
+
+/// True when a request failed only because it was cancelled via `InFlight`
+pub fn is_aborted(error: &gloo_net::Error) -> bool {
+    matches!(error, gloo_net::Error::JsError(e) if e.name == "AbortError")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[derive(Properties, PartialEq)]
+    struct PollerProps {
+        period_ms: Option<u32>,
+        fetches: Rc<Cell<u32>>,
+    }
+
+    #[function_component(Poller)]
+    fn poller(props: &PollerProps) -> Html {
+        let fetches = props.fetches.clone();
+        use_polling(
+            props.period_ms,
+            (),
+            Callback::from(move |_| fetches.set(fetches.get() + 1)),
+        );
+        html! {}
+    }
+
+    async fn count_fetches(period_ms: Option<u32>) -> u32 {
+        let fetches = Rc::new(Cell::new(0));
+        let root = gloo::utils::document().create_element("div").unwrap();
+        let app = yew::Renderer::<Poller>::with_root_and_props(
+            root,
+            PollerProps {
+                period_ms,
+                fetches: fetches.clone(),
+            },
+        )
+        .render();
+        yew::platform::time::sleep(std::time::Duration::from_millis(130)).await;
+        app.destroy();
+        fetches.get()
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_enabled_interval_triggers_periodic_refetches() {
+        assert!(count_fetches(Some(25)).await >= 3);
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_disabled_interval_never_refetches() {
+        assert_eq!(count_fetches(None).await, 0);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_refresh_counts_are_per_section() {
+        let counts = Rc::new(RefreshCounts::default());
+        let counts = counts
+            .reduce("overview")
+            .reduce("overview")
+            .reduce("players");
+        assert_eq!(counts.get("overview"), 2);
+        assert_eq!(counts.get("players"), 1);
+        assert_eq!(counts.get("contests"), 0);
+    }
+}
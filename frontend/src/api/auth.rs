@@ -5,8 +5,9 @@ use js_sys::Date;
 use log::debug;
 use serde::Deserialize;
 use shared::dto::player::{
-    CreatePlayerRequest, LoginRequest, LoginResponse, PlayerDto, UpdateEmailRequest,
-    UpdateHandleRequest, UpdatePasswordRequest, UpdateResponse,
+    CreatePlayerRequest, LoginRequest, LoginResponse, PlayerDto, PlayerProfileDto,
+    UpdateEmailRequest, UpdateHandleRequest, UpdatePasswordRequest, UpdateProfileRequest,
+    UpdateResponse,
 };
 use std::sync::atomic::{AtomicU64, Ordering};
 
@@ -315,6 +316,43 @@ pub async fn update_handle(
     Ok(update_response)
 }
 
+/// Updates the name fields; a `lastname` of `Some(None)` removes the last name
+pub async fn update_profile_names(
+    firstname: Option<&str>,
+    lastname: Option<Option<&str>>,
+) -> Result<PlayerProfileDto, String> {
+    debug!("Attempting to update profile name fields");
+
+    let update_request = UpdateProfileRequest {
+        firstname: firstname.map(|s| s.to_string()),
+        lastname: lastname.flatten().map(|s| s.to_string()),
+        clear_lastname: lastname == Some(None),
+    };
+
+    let response = crate::api::utils::authenticated_patch(&api_url("/api/players/me/profile"))
+        .json(&update_request)
+        .map_err(|e| format!("Failed to serialize profile update request: {}", e))?
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send profile update request: {}", e))?;
+
+    if !response.ok() {
+        let error = response
+            .json::<ErrorResponse>()
+            .await
+            .map_err(|e| format!("Failed to parse error response: {}", e))?;
+        return Err(error.error);
+    }
+
+    let profile = response
+        .json::<PlayerProfileDto>()
+        .await
+        .map_err(|e| format!("Failed to parse profile response: {}", e))?;
+
+    debug!("Successfully updated profile name fields");
+    Ok(profile)
+}
+
 pub async fn update_password(
     current_password: &str,
     new_password: &str,
@@ -370,6 +408,7 @@ mod tests {
             player: PlayerDto {
                 id: "player/1".to_string(),
                 firstname: "John".to_string(),
+                lastname: None,
                 handle: "john_doe".to_string(),
                 email: "test@example.com".to_string(),
                 created_at: chrono::Utc::now().fixed_offset(),
@@ -422,6 +461,7 @@ mod tests {
         let player = PlayerDto {
             id: "player/1".to_string(),
             firstname: "John".to_string(),
+            lastname: None,
             handle: "john_doe".to_string(),
             email: "test@example.com".to_string(),
             created_at: chrono::Utc::now().fixed_offset(),
@@ -437,6 +477,7 @@ mod tests {
         let player = PlayerDto {
             id: "player/1".to_string(),
             firstname: "John".to_string(),
+            lastname: None,
             handle: "john_doe".to_string(),
             email: "test@example.com".to_string(),
             created_at: chrono::Utc::now().fixed_offset(),
@@ -450,6 +491,7 @@ mod tests {
         let player = PlayerDto {
             id: "player/1".to_string(),
             firstname: "John".to_string(),
+            lastname: None,
             handle: "john_doe".to_string(),
             email: "test@example.com".to_string(),
             created_at: chrono::Utc::now().fixed_offset(),
@@ -508,6 +550,7 @@ mod tests {
         let mut player = PlayerDto {
             id: "player/1".to_string(),
             firstname: "John".to_string(),
+            lastname: None,
             handle: "john_doe".to_string(),
             email: "test@example.com".to_string(),
             created_at: chrono::Utc::now().fixed_offset(),
@@ -525,6 +568,7 @@ mod tests {
         let mut player = PlayerDto {
             id: "player/1".to_string(),
             firstname: "John".to_string(),
+            lastname: None,
             handle: "john_doe".to_string(),
             email: "test@example.com".to_string(),
             created_at: chrono::Utc::now().fixed_offset(),
@@ -590,6 +634,7 @@ mod tests {
         let player = PlayerDto {
             id: "player/1".to_string(),
             firstname: "John & Jane".to_string(),
+            lastname: None,
             handle: "john_jane_123".to_string(),
             email: "john.jane+test@example.com".to_string(),
             created_at: chrono::Utc::now().fixed_offset(),
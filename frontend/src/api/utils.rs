@@ -37,6 +37,11 @@ pub fn authenticated_put(url: &str) -> gloo_net::http::RequestBuilder {
     authenticated_request("PUT", url)
 }
 
+/// Creates a PATCH request with authentication
+pub fn authenticated_patch(url: &str) -> gloo_net::http::RequestBuilder {
+    authenticated_request("PATCH", url)
+}
+
 /// Creates a DELETE request with authentication
 pub fn authenticated_delete(url: &str) -> gloo_net::http::RequestBuilder {
     authenticated_request("DELETE", url)
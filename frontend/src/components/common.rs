@@ -1,2 +1,3 @@
+pub use crate::components::common_empty_state as empty_state;
 pub use crate::components::common_modal as modal;
 pub use crate::components::common_toast as toast;
@@ -0,0 +1,99 @@
+use yew::prelude::*;
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct EmptyStateProps {
+    /// Main line shown to the user, e.g. "No contests yet"
+    pub message: AttrValue,
+    #[prop_or(AttrValue::Static("📭"))]
+    pub icon: AttrValue,
+    /// Optional secondary hint shown under the message
+    #[prop_or_default]
+    pub detail: Option<AttrValue>,
+    /// Label for the call-to-action button; the button is only shown with `on_cta`
+    #[prop_or_default]
+    pub cta_label: Option<AttrValue>,
+    #[prop_or_default]
+    pub on_cta: Option<Callback<MouseEvent>>,
+    /// When false the children are rendered instead of the empty state
+    #[prop_or(true)]
+    pub is_empty: bool,
+    #[prop_or_default]
+    pub children: Children,
+}
+
+/// Honest "no data yet" placeholder for sections and lists with no results
+#[function_component(EmptyState)]
+pub fn empty_state(props: &EmptyStateProps) -> Html {
+    if !props.is_empty {
+        return html! { <>{props.children.clone()}</> };
+    }
+
+    html! {
+        <div class="empty-state p-8 text-center" data-testid="empty-state">
+            <div class="text-4xl mb-3" aria-hidden="true">{props.icon.clone()}</div>
+            <p class="text-gray-700 font-medium">{props.message.clone()}</p>
+            if let Some(detail) = props.detail.clone() {
+                <p class="text-sm text-gray-500 mt-1">{detail}</p>
+            }
+            if let (Some(label), Some(on_cta)) = (props.cta_label.clone(), props.on_cta.clone()) {
+                <button
+                    onclick={on_cta}
+                    class="mt-4 inline-flex items-center px-4 py-2 text-sm font-medium text-white bg-blue-600 rounded-md hover:bg-blue-700"
+                >
+                    {label}
+                </button>
+            }
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[derive(Properties, PartialEq)]
+    struct ListProps {
+        items: Vec<&'static str>,
+    }
+
+    #[function_component(List)]
+    fn list(props: &ListProps) -> Html {
+        html! {
+            <EmptyState is_empty={props.items.is_empty()} message="No games yet">
+                <ul>{for props.items.iter().map(|item| html! { <li>{*item}</li> })}</ul>
+            </EmptyState>
+        }
+    }
+
+    async fn render_list(items: Vec<&'static str>) -> web_sys::Element {
+        let document = gloo::utils::document();
+        let root = document.create_element("div").unwrap();
+        document.body().unwrap().append_child(&root).unwrap();
+        yew::Renderer::<List>::with_root_and_props(root.clone(), ListProps { items }).render();
+        yew::platform::time::sleep(std::time::Duration::ZERO).await;
+        root
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_empty_state_shows_when_data_is_empty() {
+        let root = render_list(vec![]).await;
+        assert!(root
+            .query_selector("[data-testid='empty-state']")
+            .unwrap()
+            .is_some());
+        assert!(root.text_content().unwrap().contains("No games yet"));
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_empty_state_hidden_when_data_present() {
+        let root = render_list(vec!["Catan"]).await;
+        assert!(root
+            .query_selector("[data-testid='empty-state']")
+            .unwrap()
+            .is_none());
+        assert!(root.text_content().unwrap().contains("Catan"));
+    }
+}
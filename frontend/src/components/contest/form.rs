@@ -1,5 +1,6 @@
 use crate::flatpickr::{fp_destroy_all, fp_init, fp_set_value};
 use shared::dto::{contest::OutcomeDto, game::GameDto, venue::VenueDto};
+use shared::validation::{validate_outcomes, validate_schedule};
 use wasm_bindgen::prelude::*;
 use yew::prelude::*;
 
@@ -468,7 +469,7 @@ pub fn contest_form(props: &ContestFormProps) -> Html {
                 }
             })
             .collect();
-        let stop_before_start = props.stop <= props.start;
+        let stop_before_start = validate_schedule(props.start, props.stop).is_err();
 
         (
             venue_missing,
@@ -480,12 +481,14 @@ pub fn contest_form(props: &ContestFormProps) -> Html {
             stop_before_start,
         )
     };
+    let outcome_rule_errors = validate_outcomes(&props.outcomes).err().unwrap_or_default();
     let is_form_valid = !venue_missing
         && !venue_invalid_id
         && !games_missing
         && invalid_game_indices.is_empty()
         && !outcomes_missing
         && invalid_outcome_indices.is_empty()
+        && outcome_rule_errors.is_empty()
         && !stop_before_start;
 
     let on_submit = {
@@ -649,6 +652,9 @@ pub fn contest_form(props: &ContestFormProps) -> Html {
                             )}
                         </p>
                     }
+                    {for outcome_rule_errors.iter().map(|e| html! {
+                        <p class="mt-2 text-sm text-red-600">{e.message.clone()}</p>
+                    })}
                 </div>
 
                 // Submit Button
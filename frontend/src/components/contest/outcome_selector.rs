@@ -143,6 +143,7 @@ pub fn outcome_selector(props: &OutcomeSelectorProps) -> Html {
                     result: o.result.clone(),
                     email: o.email.clone(),
                     handle: o.handle.clone(),
+                    guest: false,
                 })
                 .collect();
             props.on_outcomes_change.emit(outcome_dtos);
@@ -274,6 +275,7 @@ pub fn outcome_selector(props: &OutcomeSelectorProps) -> Html {
                 let new_player = PlayerDto {
                     id: format!("player/{}", uuid::Uuid::new_v4()),
                     firstname: "New".to_string(),
+                    lastname: None,
                     handle: generated_handle,
                     email: query.clone(),
                     created_at: chrono::Utc::now().fixed_offset(),
@@ -307,6 +309,12 @@ pub fn outcome_selector(props: &OutcomeSelectorProps) -> Html {
         Callback::from(move |(player_id, place): (String, String)| {
             let mut new_outcomes = (*outcomes).clone();
             if let Some(outcome) = new_outcomes.iter_mut().find(|o| o.player_id == player_id) {
+                // Players tied for first all win
+                outcome.result = if place.trim() == "1" {
+                    "won".to_string()
+                } else {
+                    "lost".to_string()
+                };
                 outcome.place = place;
                 outcomes.set(new_outcomes.clone());
                 // Convert to OutcomeDto for the parent component
@@ -318,6 +326,7 @@ pub fn outcome_selector(props: &OutcomeSelectorProps) -> Html {
                         result: o.result.clone(),
                         email: o.email.clone(),
                         handle: o.handle.clone(),
+                        guest: false,
                     })
                     .collect();
                 props.on_outcomes_change.emit(outcome_dtos);
@@ -345,6 +354,7 @@ pub fn outcome_selector(props: &OutcomeSelectorProps) -> Html {
                         result: o.result.clone(),
                         email: o.email.clone(),
                         handle: o.handle.clone(),
+                        guest: false,
                     })
                     .collect();
                 props.on_outcomes_change.emit(outcome_dtos);
@@ -0,0 +1,165 @@
+use yew::prelude::*;
+
+const DAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+/// Shades from no plays to the busiest hour, lightest first
+const SHADES: [&str; 5] = [
+    "bg-gray-100",
+    "bg-blue-100",
+    "bg-blue-200",
+    "bg-blue-400",
+    "bg-blue-600",
+];
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct HeatmapProps {
+    /// Plays per weekday (0 = Sunday) and UTC hour, as returned by
+    /// `/api/analytics/contests/heatmap`; missing cells count as zero
+    pub buckets: Vec<Vec<u64>>,
+}
+
+/// The shade for an hour with `plays`, relative to the busiest hour
+///
+/// Only the busiest hours get the darkest shade, and hours with no plays
+/// stay grey even when every hour is empty.
+pub fn shade(plays: u64, max: u64) -> &'static str {
+    if plays == 0 || max == 0 {
+        return SHADES[0];
+    }
+    let steps = (SHADES.len() - 1) as u64;
+    // Rounds up so any play is visible, reaching the last shade only at max
+    let step = (plays.min(max) * steps).div_ceil(max);
+    SHADES[step as usize]
+}
+
+/// Weekday × hour grid of when contests are played, with a colour scale
+#[function_component(Heatmap)]
+pub fn heatmap(props: &HeatmapProps) -> Html {
+    let plays = |day: usize, hour: usize| {
+        props
+            .buckets
+            .get(day)
+            .and_then(|row| row.get(hour))
+            .copied()
+            .unwrap_or(0)
+    };
+    let max = (0..7)
+        .flat_map(|day| (0..24).map(move |hour| (day, hour)))
+        .map(|(day, hour)| plays(day, hour))
+        .max()
+        .unwrap_or(0);
+
+    html! {
+        <div data-testid="heatmap">
+            <div class="overflow-x-auto">
+                <div class="inline-grid gap-1" style="grid-template-columns: auto repeat(24, 1.5rem);">
+                    <div></div>
+                    {for (0..24).map(|hour| html! {
+                        <div class="w-6 text-[10px] text-gray-500 text-center">{format!("{:02}", hour)}</div>
+                    })}
+                    {for DAYS.iter().enumerate().map(|(day, day_label)| html! {
+                        <>
+                            <div class="text-[10px] text-gray-500 pr-1">{*day_label}</div>
+                            {for (0..24).map(|hour| {
+                                let count = plays(day, hour);
+                                let title = format!(
+                                    "{} {:02}:00 — {} {}",
+                                    day_label,
+                                    hour,
+                                    count,
+                                    if count == 1 { "play" } else { "plays" }
+                                );
+                                html! {
+                                    <div
+                                        class={classes!("w-6", "h-6", "rounded", shade(count, max))}
+                                        title={title}
+                                        data-testid="heatmap-cell"
+                                        data-plays={count.to_string()}
+                                    ></div>
+                                }
+                            })}
+                        </>
+                    })}
+                </div>
+            </div>
+            if max == 0 {
+                <p class="mt-2 text-sm text-gray-500">{"No contests were played in this window."}</p>
+            }
+            <div class="mt-3 flex items-center space-x-2 text-xs text-gray-600">
+                <span>{"Fewer"}</span>
+                {for SHADES.iter().map(|bg| html! { <div class={classes!("w-6", "h-3", "rounded", *bg)}></div> })}
+                <span>{"More"}</span>
+            </div>
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    async fn render_heatmap(buckets: Vec<Vec<u64>>) -> web_sys::Element {
+        let document = gloo::utils::document();
+        let root = document.create_element("div").unwrap();
+        document.body().unwrap().append_child(&root).unwrap();
+        yew::Renderer::<Heatmap>::with_root_and_props(root.clone(), HeatmapProps { buckets })
+            .render();
+        yew::platform::time::sleep(std::time::Duration::ZERO).await;
+        root
+    }
+
+    fn has_class(cell: &web_sys::Element, class: &str) -> bool {
+        cell.get_attribute("class")
+            .unwrap_or_default()
+            .split_whitespace()
+            .any(|c| c == class)
+    }
+
+    fn cells(root: &web_sys::Element) -> Vec<web_sys::Element> {
+        let list = root
+            .query_selector_all("[data-testid='heatmap-cell']")
+            .unwrap();
+        (0..list.length())
+            .map(|i| list.item(i).unwrap().dyn_into().unwrap())
+            .collect()
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_heatmap_renders_every_cell_and_darkest_at_max() {
+        let mut buckets = vec![vec![0u64; 24]; 7];
+        buckets[2][19] = 9;
+        buckets[5][20] = 4;
+        buckets[0][10] = 1;
+        let root = render_heatmap(buckets).await;
+
+        let cells = cells(&root);
+        assert_eq!(cells.len(), 168);
+        let darkest: Vec<_> = cells
+            .iter()
+            .filter(|cell| has_class(cell, SHADES[4]))
+            .collect();
+        assert_eq!(darkest.len(), 1);
+        assert_eq!(darkest[0].get_attribute("data-plays").as_deref(), Some("9"));
+        assert_eq!(
+            darkest[0].get_attribute("title").as_deref(),
+            Some("Tue 19:00 — 9 plays")
+        );
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_all_zero_heatmap_renders_empty_grid() {
+        let root = render_heatmap(vec![]).await;
+
+        let cells = cells(&root);
+        assert_eq!(cells.len(), 168);
+        assert!(cells.iter().all(|cell| has_class(cell, SHADES[0])));
+        assert!(root
+            .text_content()
+            .unwrap()
+            .contains("No contests were played in this window."));
+    }
+}
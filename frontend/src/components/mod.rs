@@ -12,10 +12,12 @@ pub mod contest {
 }
 pub mod chart_renderer;
 pub mod common;
+pub mod common_empty_state;
 pub mod common_modal;
 pub mod common_toast;
 pub mod contests_modal;
 pub mod footer;
+pub mod heatmap;
 pub mod profile;
 pub mod profile_editor;
 pub mod scheduler_monitor;
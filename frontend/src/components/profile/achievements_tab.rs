@@ -19,6 +19,23 @@ fn category_icon(category: &AchievementCategoryDto) -> &'static str {
     }
 }
 
+/// What a category's achievements count, e.g. "wins" in "3 more wins"
+fn category_unit(category: &AchievementCategoryDto, count: i32) -> &'static str {
+    let (one, many) = match category {
+        AchievementCategoryDto::Wins => ("win", "wins"),
+        AchievementCategoryDto::Contests => ("contest", "contests"),
+        AchievementCategoryDto::Streaks => ("win in a row", "wins in a row"),
+        AchievementCategoryDto::Games => ("game", "games"),
+        AchievementCategoryDto::Venues => ("venue", "venues"),
+        AchievementCategoryDto::Special => ("step", "steps"),
+    };
+    if count == 1 {
+        one
+    } else {
+        many
+    }
+}
+
 #[function_component(AchievementsTab)]
 pub fn achievements_tab(props: &AchievementsTabProps) -> Html {
     let status_filter = use_state(|| "all".to_string());
@@ -136,6 +153,24 @@ pub fn achievements_tab(props: &AchievementsTabProps) -> Html {
                         </p>
                     </div>
                 </div>
+                if !achievements.next_achievements.is_empty() {
+                    <div class="mt-4">
+                        <p class="text-xs font-medium text-gray-500 mb-2">{"Up next"}</p>
+                        <ul class="space-y-1">
+                            { for achievements.next_achievements.iter().map(|next| html! {
+                                <li class="flex items-center text-sm text-gray-700">
+                                    <span class="mr-2">{category_icon(&next.category)}</span>
+                                    {format!(
+                                        "{} more {} to {}",
+                                        next.remaining,
+                                        category_unit(&next.category, next.remaining),
+                                        next.name
+                                    )}
+                                </li>
+                            }) }
+                        </ul>
+                    </div>
+                }
             </div>
 
             <div class="bg-gray-50 rounded-lg p-4">
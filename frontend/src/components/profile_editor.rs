@@ -1,5 +1,6 @@
-use crate::api::auth::{update_handle, update_password};
+use crate::api::auth::{update_handle, update_password, update_profile_names};
 use crate::auth::AuthContext;
+use shared::validation::validate_password;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::spawn_local;
 use yew::prelude::*;
@@ -12,6 +13,7 @@ pub struct ProfileEditorProps {
 #[derive(PartialEq, Clone)]
 pub enum EditMode {
     None,
+    Name,
     Handle,
     Password,
 }
@@ -25,6 +27,8 @@ pub fn profile_editor(props: &ProfileEditorProps) -> Html {
     let error = use_state(|| None::<String>);
     let success = use_state(|| None::<String>);
 
+    let new_firstname = use_state(|| String::new());
+    let new_lastname = use_state(|| String::new());
     let new_handle = use_state(|| String::new());
     let current_password = use_state(|| String::new());
     let new_password = use_state(|| String::new());
@@ -32,6 +36,9 @@ pub fn profile_editor(props: &ProfileEditorProps) -> Html {
 
     let on_edit_click = {
         let edit_mode = edit_mode.clone();
+        let new_firstname = new_firstname.clone();
+        let new_lastname = new_lastname.clone();
+        let auth_context = auth_context.clone();
         let new_handle = new_handle.clone();
         let current_password = current_password.clone();
         let new_password = new_password.clone();
@@ -42,6 +49,11 @@ pub fn profile_editor(props: &ProfileEditorProps) -> Html {
         Callback::from(move |mode: EditMode| {
             edit_mode.set(mode.clone());
             match mode {
+                EditMode::Name => {
+                    let player = auth_context.state.player.as_ref();
+                    new_firstname.set(player.map(|p| p.firstname.clone()).unwrap_or_default());
+                    new_lastname.set(player.and_then(|p| p.lastname.clone()).unwrap_or_default());
+                }
                 EditMode::Handle => {
                     new_handle.set(String::new());
                     current_password.set(String::new());
@@ -70,6 +82,60 @@ pub fn profile_editor(props: &ProfileEditorProps) -> Html {
         })
     };
 
+    let on_name_update = {
+        let edit_mode = edit_mode.clone();
+        let loading = loading.clone();
+        let error = error.clone();
+        let success = success.clone();
+        let new_firstname = new_firstname.clone();
+        let new_lastname = new_lastname.clone();
+        let on_update = props.on_update.clone();
+        let auth_context = auth_context.clone();
+
+        Callback::from(move |_| {
+            let firstname_val = new_firstname.trim().to_string();
+            let lastname_val = new_lastname.trim().to_string();
+            if firstname_val.is_empty() {
+                error.set(Some("First name is required".to_string()));
+                return;
+            }
+
+            loading.set(true);
+            error.set(None);
+            success.set(None);
+
+            let edit_mode_clone = edit_mode.clone();
+            let loading_clone = loading.clone();
+            let success_clone = success.clone();
+            let error_clone = error.clone();
+            let on_update_clone = on_update.clone();
+            let auth_context_clone = auth_context.clone();
+            spawn_local(async move {
+                // An emptied input removes the last name
+                let lastname = (!lastname_val.is_empty()).then_some(lastname_val.as_str());
+                match update_profile_names(Some(&firstname_val), Some(lastname)).await {
+                    Ok(_) => {
+                        success_clone.set(Some("Name updated successfully".to_string()));
+                        on_update_clone.emit(());
+
+                        // Update the auth context with the new player data
+                        auth_context_clone.refresh.emit(());
+
+                        // Close the form after a delay
+                        gloo_timers::callback::Timeout::new(2000, move || {
+                            edit_mode_clone.set(EditMode::None);
+                        })
+                        .forget();
+                    }
+                    Err(e) => {
+                        error_clone.set(Some(format!("Failed to update name: {}", e)));
+                    }
+                }
+                loading_clone.set(false);
+            });
+        })
+    };
+
     let on_handle_update = {
         let edit_mode = edit_mode.clone();
         let loading = loading.clone();
@@ -149,10 +215,8 @@ pub fn profile_editor(props: &ProfileEditorProps) -> Html {
                 return;
             }
 
-            if new_password.len() < 8 {
-                error.set(Some(
-                    "New password must be at least 8 characters".to_string(),
-                ));
+            if let Err(e) = validate_password(new_password.as_str()) {
+                error.set(Some(e.message));
                 return;
             }
 
@@ -212,7 +276,15 @@ pub fn profile_editor(props: &ProfileEditorProps) -> Html {
 
             // Current Profile Display
             <div class="mb-6 p-4 bg-gray-50 rounded-lg">
-                <h3 class="text-lg font-semibold text-gray-700 mb-3">{"Current Profile"}</h3>
+                <div class="flex items-center gap-3 mb-3">
+                    <span
+                        class="inline-flex items-center justify-center w-10 h-10 rounded-full text-white font-semibold"
+                        style={format!("background-color: {}", player.avatar_color())}
+                    >
+                        {player.initials()}
+                    </span>
+                    <h3 class="text-lg font-semibold text-gray-700">{"Current Profile"}</h3>
+                </div>
                 <div class="grid grid-cols-1 md:grid-cols-2 gap-4">
                     <div>
                         <label class="block text-sm font-medium text-gray-600">{"Email"}</label>
@@ -223,6 +295,15 @@ pub fn profile_editor(props: &ProfileEditorProps) -> Html {
                         <label class="block text-sm font-medium text-gray-600">{"Handle"}</label>
                         <p class="text-gray-900">{&player.handle}</p>
                     </div>
+                    <div>
+                        <label class="block text-sm font-medium text-gray-600">{"Name"}</label>
+                        <p class="text-gray-900">
+                            {match &player.lastname {
+                                Some(lastname) => format!("{} {}", player.firstname, lastname),
+                                None => player.firstname.clone(),
+                            }}
+                        </p>
+                    </div>
                 </div>
             </div>
 
@@ -244,6 +325,12 @@ pub fn profile_editor(props: &ProfileEditorProps) -> Html {
                 EditMode::None => {
                     html! {
                         <div class="space-y-4">
+                            <button
+                                onclick={on_edit_click.reform(|_| EditMode::Name)}
+                                class="w-full md:w-auto px-4 py-2 bg-blue-600 text-white rounded hover:bg-blue-700 transition-colors"
+                            >
+                                {"Update Name"}
+                            </button>
                             <button
                                 onclick={on_edit_click.reform(|_| EditMode::Handle)}
                                 class="w-full md:w-auto px-4 py-2 bg-blue-600 text-white rounded hover:bg-blue-700 transition-colors"
@@ -259,6 +346,54 @@ pub fn profile_editor(props: &ProfileEditorProps) -> Html {
                         </div>
                     }
                 }
+                EditMode::Name => {
+                    html! {
+                        <div class="space-y-4">
+                            <h3 class="text-lg font-semibold text-gray-700">{"Update Name"}</h3>
+                            <div>
+                                <label class="block text-sm font-medium text-gray-600 mb-2">{"First Name"}</label>
+                                <input
+                                    type="text"
+                                    value={(*new_firstname).clone()}
+                                    onchange={Callback::from(move |e: Event| {
+                                        let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+                                        new_firstname.set(target.value());
+                                    })}
+                                    class="w-full px-3 py-2 border border-gray-300 rounded-md focus:outline-none focus:ring-2 focus:ring-blue-500"
+                                    placeholder="Enter first name"
+                                />
+                            </div>
+                            <div>
+                                <label class="block text-sm font-medium text-gray-600 mb-2">{"Last Name"}</label>
+                                <input
+                                    type="text"
+                                    value={(*new_lastname).clone()}
+                                    onchange={Callback::from(move |e: Event| {
+                                        let target = e.target().unwrap().unchecked_into::<web_sys::HtmlInputElement>();
+                                        new_lastname.set(target.value());
+                                    })}
+                                    class="w-full px-3 py-2 border border-gray-300 rounded-md focus:outline-none focus:ring-2 focus:ring-blue-500"
+                                    placeholder="Enter last name (optional)"
+                                />
+                            </div>
+                            <div class="flex space-x-3">
+                                <button
+                                    onclick={on_name_update}
+                                    disabled={*loading}
+                                    class="px-4 py-2 bg-green-600 text-white rounded hover:bg-green-700 disabled:opacity-50 transition-colors"
+                                >
+                                    {if *loading { "Updating..." } else { "Update Name" }}
+                                </button>
+                                <button
+                                    onclick={on_cancel.clone()}
+                                    class="px-4 py-2 bg-gray-600 text-white rounded hover:bg-gray-700 transition-colors"
+                                >
+                                    {"Cancel"}
+                                </button>
+                            </div>
+                        </div>
+                    }
+                }
                 EditMode::Handle => {
                     html! {
                         <div class="space-y-4">
@@ -17,6 +17,7 @@ pub mod version;
 pub mod analytics {
     pub mod client_manager;
     pub mod events;
+    pub mod refresh;
     pub use client_manager::*;
     pub use events::*;
 }
@@ -134,6 +135,7 @@ pub fn protected_route(props: &Props) -> Html {
         .map(|e| e.contains("Session expired"))
         .unwrap_or(false);
     let navigator = use_navigator().unwrap();
+    let path = use_location().map(|location| location.path().to_string());
 
     // Show toast when session expires
     {
@@ -157,7 +159,11 @@ pub fn protected_route(props: &Props) -> Html {
             (is_authenticated, session_expired),
             move |(is_auth, session_expired)| {
                 if !*is_auth || *session_expired {
-                    navigator.push(&Route::Login);
+                    // Remember where the user was headed so login can send them back
+                    let query = pages::login::LoginQuery { redirect: path };
+                    if navigator.push_with_query(&Route::Login, &query).is_err() {
+                        navigator.push(&Route::Login);
+                    }
                 }
                 || ()
             },
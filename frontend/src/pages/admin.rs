@@ -387,6 +387,7 @@ mod tests {
                 email: "admin@test.com".to_string(),
                 handle: "admin".to_string(),
                 firstname: "Admin".to_string(),
+                lastname: None,
                 created_at: chrono::Utc::now().fixed_offset(),
                 is_admin: true,
             })
@@ -396,6 +397,7 @@ mod tests {
                 email: "user@test.com".to_string(),
                 handle: "user".to_string(),
                 firstname: "Regular".to_string(),
+                lastname: None,
                 created_at: chrono::Utc::now().fixed_offset(),
                 is_admin: false,
             })
@@ -1,11 +1,16 @@
+use crate::analytics::refresh::{self, use_polling, InFlight, RefreshCounts};
 use crate::api::games::get_game_analytics;
 use crate::api::games::search_games;
 use crate::api::utils::authenticated_get;
 use crate::components::chart_renderer::ChartRenderer;
+use crate::components::common::empty_state::EmptyState;
+use crate::components::heatmap::Heatmap;
 use crate::Route;
 use gloo_net::http::Request;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use shared::dto::game::GameDto;
+use shared::models::client_storage::RefreshInterval;
 use web_sys::console;
 use yew::prelude::*;
 use yew_router::prelude::*;
@@ -36,6 +41,60 @@ enum AnalyticsTab {
     Players,
 }
 
+impl AnalyticsTab {
+    /// Section key used to track polling refreshes for this tab
+    fn key(&self) -> &'static str {
+        match self {
+            AnalyticsTab::Overview => "overview",
+            AnalyticsTab::Contests => "contests",
+            AnalyticsTab::Venues => "venues",
+            AnalyticsTab::Games => "games",
+            AnalyticsTab::Players => "players",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        [
+            AnalyticsTab::Overview,
+            AnalyticsTab::Contests,
+            AnalyticsTab::Venues,
+            AnalyticsTab::Games,
+            AnalyticsTab::Players,
+        ]
+        .into_iter()
+        .find(|tab| tab.key() == key)
+    }
+}
+
+/// Query string of the analytics route, e.g. `/analytics?tab=venues`
+#[derive(Serialize, Deserialize, Default)]
+struct TabQuery {
+    tab: Option<String>,
+}
+
+/// Active tab, taken from `?tab=` so reloads and shared links keep it
+///
+/// Unknown or missing values fall back to Overview. Navigating to another
+/// `?tab=` while mounted switches to that tab.
+#[hook]
+fn use_analytics_tab() -> UseStateHandle<AnalyticsTab> {
+    let requested = use_location()
+        .and_then(|location| location.query::<TabQuery>().ok())
+        .and_then(|query| query.tab)
+        .and_then(|key| AnalyticsTab::from_key(&key))
+        .unwrap_or(AnalyticsTab::Overview);
+    let current_tab = use_state(|| requested.clone());
+    {
+        let current_tab = current_tab.clone();
+        use_effect_with(requested, move |requested| {
+            if *current_tab != *requested {
+                current_tab.set(requested.clone());
+            }
+        });
+    }
+    current_tab
+}
+
 #[function_component(AnalyticsDashboard)]
 pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
     let auth = use_context::<crate::auth::AuthContext>().expect("Auth context not found");
@@ -65,8 +124,22 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
     let loading = use_state(|| false);
     let error = use_state(|| None::<String>);
 
-    // Tabs state
-    let current_tab = use_state(|| AnalyticsTab::Overview);
+    // Tabs state, mirrored in the URL
+    let current_tab = use_analytics_tab();
+
+    // Optional auto-refresh of the visible tab
+    let refresh_interval = use_state(refresh::load_refresh_interval);
+    let refresh_counts = use_reducer(RefreshCounts::default);
+    let in_flight = use_state(InFlight::default);
+    {
+        let dispatcher = refresh_counts.dispatcher();
+        let section = current_tab.key();
+        use_polling(
+            refresh_interval.as_millis(),
+            section,
+            Callback::from(move |_| dispatcher.dispatch(section)),
+        );
+    }
 
     // Contests heatmap state
     let contest_heatmap = use_state(|| None::<Value>);
@@ -88,11 +161,31 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
 
     let on_select_tab = {
         let current_tab = current_tab.clone();
+        let in_flight = in_flight.clone();
+        let navigator = navigator.clone();
         Callback::from(move |tab: AnalyticsTab| {
+            // Refreshes for the previous tab are no longer needed
+            in_flight.cancel();
+            let query = TabQuery {
+                tab: Some(tab.key().to_string()),
+            };
+            if let Err(e) = navigator.replace_with_query(&Route::Analytics, &query) {
+                console::warn_1(&format!("Failed to record analytics tab in URL: {}", e).into());
+            }
             current_tab.set(tab);
         })
     };
 
+    let on_refresh_interval_change = {
+        let refresh_interval = refresh_interval.clone();
+        Callback::from(move |e: Event| {
+            let select: web_sys::HtmlSelectElement = e.target_unchecked_into();
+            let interval = RefreshInterval::from_label(&select.value());
+            refresh::save_refresh_interval(interval);
+            refresh_interval.set(interval);
+        })
+    };
+
     let on_game_id_input = {
         let game_id_input = game_id_input.clone();
         Callback::from(move |e: InputEvent| {
@@ -176,13 +269,27 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
         let platform_stats = platform_stats.clone();
         let loading = loading.clone();
         let error = error.clone();
-
-        use_effect_with((), move |_| {
-            loading.set(true);
+        let in_flight = (*in_flight).clone();
+
+        let counts = (
+            refresh_counts.get(AnalyticsTab::Overview.key()),
+            refresh_counts.get(AnalyticsTab::Venues.key()),
+        );
+        use_effect_with(counts, move |counts| {
+            // Polling refreshes keep the current content on screen
+            let is_refresh = *counts != (0, 0);
+            let signal = if is_refresh { in_flight.signal() } else { None };
+            if !is_refresh {
+                loading.set(true);
+            }
             error.set(None);
 
             wasm_bindgen_futures::spawn_local(async move {
-                match Request::get("/api/analytics/platform").send().await {
+                match Request::get("/api/analytics/platform")
+                    .abort_signal(signal.as_ref())
+                    .send()
+                    .await
+                {
                     Ok(response) => {
                         if response.ok() {
                             if let Ok(stats) = response.json::<Value>().await {
@@ -255,6 +362,7 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
                             )));
                         }
                     }
+                    Err(e) if refresh::is_aborted(&e) => {}
                     Err(e) => {
                         console::error_1(&format!("Failed to fetch platform stats: {}", e).into());
                         error.set(Some(format!("Failed to fetch platform stats: {}", e)));
@@ -272,8 +380,11 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
         let glicko_leaderboard = glicko_leaderboard.clone();
         let glicko_loading = glicko_loading.clone();
         let glicko_error = glicko_error.clone();
+        let in_flight = (*in_flight).clone();
 
-        use_effect_with((), move |_| {
+        let count = refresh_counts.get(AnalyticsTab::Players.key());
+        use_effect_with(count, move |count| {
+            let signal = if *count > 0 { in_flight.signal() } else { None };
             glicko_loading.set(true);
             glicko_error.set(None);
 
@@ -281,6 +392,7 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
                 match authenticated_get(
                     "/api/ratings/leaderboard?scope=global&min_games=3&limit=10",
                 )
+                .abort_signal(signal.as_ref())
                 .send()
                 .await
                 {
@@ -324,6 +436,7 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
                             )));
                         }
                     }
+                    Err(e) if refresh::is_aborted(&e) => {}
                     Err(e) => {
                         console::error_1(
                             &format!("Failed to fetch Glicko2 leaderboard: {}", e).into(),
@@ -343,10 +456,21 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
     {
         let contest_trends_chart = contest_trends_chart.clone();
         let error = error.clone();
-
-        use_effect_with((), move |_| {
+        let in_flight = (*in_flight).clone();
+
+        let counts = (
+            refresh_counts.get(AnalyticsTab::Contests.key()),
+            refresh_counts.get(AnalyticsTab::Players.key()),
+        );
+        use_effect_with(counts, move |counts| {
+            let signal = if *counts != (0, 0) {
+                in_flight.signal()
+            } else {
+                None
+            };
             wasm_bindgen_futures::spawn_local(async move {
                 match Request::get("/api/analytics/charts/contest-trends?months=12&title=Contest%20Trends%20Over%20Time")
+                    .abort_signal(signal.as_ref())
                     .send()
                     .await
                 {
@@ -357,6 +481,7 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
                             error.set(Some("Failed to parse contest trends chart".to_string()));
                         }
                     }
+                    Err(e) if refresh::is_aborted(&e) => {}
                     Err(e) => {
                         error.set(Some(format!("Failed to fetch contest trends chart: {}", e)));
                     }
@@ -373,11 +498,15 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
         let contest_heatmap_loading = contest_heatmap_loading.clone();
         let contest_heatmap_error = contest_heatmap_error.clone();
         let heatmap_weeks = heatmap_weeks.clone();
-        use_effect_with(heatmap_weeks.clone(), move |weeks| {
+        let in_flight = (*in_flight).clone();
+        let count = refresh_counts.get(AnalyticsTab::Contests.key());
+        use_effect_with((heatmap_weeks.clone(), count), move |(weeks, count)| {
             let w = **weeks;
+            let signal = if *count > 0 { in_flight.signal() } else { None };
             contest_heatmap_loading.set(true);
             wasm_bindgen_futures::spawn_local(async move {
                 match Request::get(&format!("/api/analytics/contests/heatmap?weeks={}", w))
+                    .abort_signal(signal.as_ref())
                     .send()
                     .await
                 {
@@ -393,6 +522,7 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
                                 .set(Some(format!("Heatmap request failed: {}", resp.status())));
                         }
                     }
+                    Err(e) if refresh::is_aborted(&e) => {}
                     Err(e) => {
                         contest_heatmap_error.set(Some(format!("Failed to fetch heatmap: {}", e)))
                     }
@@ -437,9 +567,16 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
     {
         let insights_state = insights.clone();
         let error = error.clone();
-        use_effect_with((), move |_| {
+        let in_flight = (*in_flight).clone();
+        let count = refresh_counts.get(AnalyticsTab::Overview.key());
+        use_effect_with(count, move |count| {
+            let signal = if *count > 0 { in_flight.signal() } else { None };
             wasm_bindgen_futures::spawn_local(async move {
-                match Request::get("/api/analytics/insights").send().await {
+                match Request::get("/api/analytics/insights")
+                    .abort_signal(signal.as_ref())
+                    .send()
+                    .await
+                {
                     Ok(response) => {
                         if response.ok() {
                             if let Ok(data) = response.json::<Value>().await {
@@ -447,6 +584,7 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
                             }
                         }
                     }
+                    Err(e) if refresh::is_aborted(&e) => {}
                     Err(e) => {
                         error.set(Some(format!("Failed to fetch insights: {}", e)));
                     }
@@ -486,11 +624,15 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
     {
         let activity_metrics_chart = activity_metrics_chart.clone();
         let error = error.clone();
-        use_effect_with((), move |_| {
+        let in_flight = (*in_flight).clone();
+        let count = refresh_counts.get(AnalyticsTab::Overview.key());
+        use_effect_with(count, move |count| {
+            let signal = if *count > 0 { in_flight.signal() } else { None };
             wasm_bindgen_futures::spawn_local(async move {
                 match Request::get(
                     "/api/analytics/charts/activity-metrics?days=60&title=Daily%20Activity",
                 )
+                .abort_signal(signal.as_ref())
                 .send()
                 .await
                 {
@@ -501,6 +643,7 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
                             error.set(Some("Failed to parse activity metrics chart".to_string()));
                         }
                     }
+                    Err(e) if refresh::is_aborted(&e) => {}
                     Err(e) => {
                         error.set(Some(format!(
                             "Failed to fetch activity metrics chart: {}",
@@ -600,11 +743,9 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
             set_recommendations_loading.set(true);
 
             wasm_bindgen_futures::spawn_local(async move {
-                let user_id = "player/2025041711441894568690500"; // Placeholder
-                match crate::api::utils::authenticated_get(&format!(
-                    "/api/analytics-enhanced/games/recommendations/{}?limit=5",
-                    user_id
-                ))
+                match crate::api::utils::authenticated_get(
+                    "/api/analytics/me/recommendations?limit=5",
+                )
                 .send()
                 .await
                 {
@@ -663,35 +804,13 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
             set_communities_loading.set(true);
 
             wasm_bindgen_futures::spawn_local(async move {
-                let user_id = "player/2025041711441894568690500"; // Placeholder
-
-                match crate::api::utils::authenticated_get(&format!(
-                    "/api/analytics-enhanced/communities/{}?min_contests=2",
-                    user_id
-                ))
-                .send()
-                .await
+                match crate::api::utils::authenticated_get("/api/analytics/communities")
+                    .send()
+                    .await
                 {
                     Ok(response) => {
                         if response.ok() {
                             if let Ok(data) = response.json::<Value>().await {
-                                // Normalize nested communities shape if needed
-                                if let Some(arr) =
-                                    data.get("gaming_communities").and_then(|v| v.as_array())
-                                {
-                                    if let Some(first) = arr.first() {
-                                        if let Some(inner) = first
-                                            .get("gaming_communities")
-                                            .and_then(|v| v.as_array())
-                                        {
-                                            let normalized = serde_json::json!({
-                                                "gaming_communities": inner
-                                            });
-                                            set_gaming_communities.set(Some(normalized));
-                                            return;
-                                        }
-                                    }
-                                }
                                 set_gaming_communities.set(Some(data));
                             }
                         }
@@ -709,14 +828,9 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
             set_networking_loading.set(true);
 
             wasm_bindgen_futures::spawn_local(async move {
-                let user_id = "player/2025041711441894568690500"; // Placeholder
-
-                match crate::api::utils::authenticated_get(&format!(
-                    "/api/analytics-enhanced/networking/{}",
-                    user_id
-                ))
-                .send()
-                .await
+                match crate::api::utils::authenticated_get("/api/analytics/me/networking")
+                    .send()
+                    .await
                 {
                     Ok(response) => {
                         if response.ok() {
@@ -815,6 +929,23 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
                     }>
                     {"Players"}
                 </button>
+                <div class="ml-auto flex items-center space-x-2 pb-2">
+                    <label for="analytics-refresh-interval" class="text-sm text-gray-500">{"Auto-refresh"}</label>
+                    <select
+                        id="analytics-refresh-interval"
+                        class="text-sm border border-gray-300 rounded-md px-2 py-1"
+                        onchange={on_refresh_interval_change}
+                    >
+                        {for RefreshInterval::ALL.iter().map(|interval| html! {
+                            <option
+                                value={interval.label()}
+                                selected={*interval == *refresh_interval}
+                            >
+                                {interval.label()}
+                            </option>
+                        })}
+                    </select>
+                </div>
             </div>
 
             if let Some(error_msg) = (*error).as_ref() {
@@ -1012,10 +1143,10 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
                                                 }).collect::<Html>()}
                                             </div>
                                         } else {
-                                            <div class="no-data">{"No game data available"}</div>
+                                            <EmptyState icon="🎮" message="No games played yet" />
                                         }
                                     } else {
-                                        <div class="no-data">{"No game data available"}</div>
+                                        <EmptyState icon="🎮" message="No games played yet" />
                                     }
                                 </div>
                                 <div class="popularity-card">
@@ -1034,10 +1165,10 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
                                                 }).collect::<Html>()}
                                             </div>
                                         } else {
-                                            <div class="no-data">{"No venue data available"}</div>
+                                            <EmptyState icon="🏟️" message="No venues used yet" />
                                         }
                                     } else {
-                                        <div class="no-data">{"No venue data available"}</div>
+                                        <EmptyState icon="🏟️" message="No venues used yet" />
                                     }
                                 </div>
                             } else {
@@ -1221,45 +1352,17 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
                                 <div class="error-message"><p>{err}</p></div>
                             } else if let Some(data) = &*contest_heatmap {
                                 // Expecting shape: { buckets: [[u64;24];7] } where 0=Sun..6=Sat
-                                { if let Some(week_rows) = data.get("buckets").and_then(|v| v.as_array()) {
-                                    html!{
-                                            <div>
-                                                <div class="overflow-x-auto">
-                                                <div class="inline-grid gap-1" style="grid-template-columns: auto repeat(24, 1.5rem);">
-                                                    <div></div>
-                                                    {for (0..24).map(|h| html!{<div class="w-6 text-[10px] text-gray-500 text-center">{format!("{:02}", h)}</div>})}
-                                                {for week_rows.iter().enumerate().map(|(day_idx, row)| {
-                                                    let day_label = match day_idx { 0=>"Sun",1=>"Mon",2=>"Tue",3=>"Wed",4=>"Thu",5=>"Fri", _=>"Sat" };
-                                                    let hours = row.as_array().unwrap_or(&vec![]).clone();
-                                                    html!{
-                                                        <>
-                                                            <div class="text-[10px] text-gray-500 pr-1">{day_label}</div>
-                                                            {for hours.iter().enumerate().map(|(h, val)| {
-                                                                let raw = val.as_u64().unwrap_or(0);
-                                                                let v = raw as f64;
-                                                                let intensity = if v == 0.0 { 0.0 } else { (v.log10()+1.0).min(4.0)/4.0 };
-                                                                let bg = if intensity == 0.0 { "bg-gray-100" } else if intensity < 0.25 { "bg-blue-100" } else if intensity < 0.5 { "bg-blue-200" } else if intensity < 0.75 { "bg-blue-400" } else { "bg-blue-600" };
-                                                                let title = format!("{} {:02}:00 — {} contests", day_label, h, raw);
-                                                                html!{<div class={classes!("w-6","h-6","rounded", bg)} title={title}></div>}
-                                                            })}
-                                                        </>
-                                                    }
-                                                })}
-                                            </div>
-                                                </div>
-                                                <div class="mt-3 flex items-center space-x-2 text-xs text-gray-600">
-                                                <span>{"Fewer"}</span>
-                                                <div class="w-6 h-3 bg-gray-100 rounded"></div>
-                                                <div class="w-6 h-3 bg-blue-100 rounded"></div>
-                                                <div class="w-6 h-3 bg-blue-200 rounded"></div>
-                                                <div class="w-6 h-3 bg-blue-400 rounded"></div>
-                                                <div class="w-6 h-3 bg-blue-600 rounded"></div>
-                                                <span>{"More"}</span>
-                                                <span class="ml-4">{format!("Window: last {} weeks", data.get("weeks").and_then(|w| w.as_i64()).unwrap_or(8))}</span>
-                                                </div>
-                                            </div>
-                                    }
-                                } else { html!{<div class="text-sm text-gray-500">{"No heatmap data available"}</div>} } }
+                                { match data.get("buckets").cloned().map(serde_json::from_value::<Vec<Vec<u64>>>) {
+                                    Some(Ok(buckets)) => html!{
+                                        <div>
+                                            <Heatmap buckets={buckets} />
+                                            <p class="mt-1 text-xs text-gray-600">
+                                                {format!("Window: last {} weeks", data.get("weeks").and_then(|w| w.as_i64()).unwrap_or(8))}
+                                            </p>
+                                        </div>
+                                    },
+                                    _ => html!{<div class="text-sm text-gray-500">{"No heatmap data available"}</div>},
+                                } }
                             } else {
                                 <div class="text-sm text-gray-500">{"No heatmap data available"}</div>
                             }
@@ -1288,10 +1391,10 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
                                             }).collect::<Html>()}
                                         </div>
                                     } else {
-                                        <div class="no-data">{"No venue data available"}</div>
+                                        <EmptyState icon="🏟️" message="No venues used yet" />
                                     }
                                 } else {
-                                    <div class="no-data">{"No venue data available"}</div>
+                                    <EmptyState icon="🏟️" message="No venues used yet" />
                                 }
                             }
                         </div>
@@ -1464,9 +1567,11 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
                                 </div>
                             } else if let Some(leaderboard) = (*glicko_leaderboard).as_ref() {
                                 if leaderboard.is_empty() {
-                                    <div class="no-data-container">
-                                        <p>{"No ratings available yet. Players need to participate in contests to get rated."}</p>
-                                    </div>
+                                    <EmptyState
+                                        icon="📈"
+                                        message="No ratings available yet"
+                                        detail="Players need to participate in contests to get rated."
+                                    />
                                 } else {
                                     <div class="overflow-x-auto">
                                         <table class="min-w-full divide-y divide-gray-200">
@@ -1594,10 +1699,11 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
                                     </table>
                                 </div>
                             } else {
-                                <div class="no-data">
-                                    <p class="mb-1">{"No game recommendations available"}</p>
-                                    <p class="text-xs text-gray-600">{"Recommendations appear after the player has enough contest history and opponent overlap."}</p>
-                                </div>
+                                <EmptyState
+                                    icon="🎲"
+                                    message="No game recommendations available"
+                                    detail="Recommendations appear after the player has enough contest history and opponent overlap."
+                                />
                             }
                         } else {
                             <div class="no-data"><p>{"Game recommendations not loaded"}</p></div>
@@ -1607,32 +1713,50 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
                     // Gaming Communities Section
                     <div class="dashboard-section">
                         <h2>{"👥 Gaming Communities"}</h2>
-                        <p class="text-sm text-gray-600 mb-3">{"Clusters of players the user frequently plays with, highlighting community leaders and strength."}</p>
+                        <p class="text-sm text-gray-600 mb-3">{"Groups of players who keep turning up at the same venues."}</p>
                         if *communities_loading {
                             <div class="loading-container"><p>{"Loading gaming communities..."}</p></div>
                         } else if let Some(communities_data) = (*gaming_communities).as_ref() {
-                            if let Some(communities) = communities_data["gaming_communities"].as_array() {
+                            if let Some(communities) = communities_data.as_array() {
                                 if !communities.is_empty() {
                                     <div class="overflow-x-auto">
                                         <table class="min-w-full divide-y divide-gray-200">
                                             <thead class="bg-gray-50">
                                                 <tr>
-                                                    <th class="px-4 py-2 text-left text-xs font-medium text-gray-500 uppercase tracking-wider">{"Leader"}</th>
+                                                    <th class="px-4 py-2 text-left text-xs font-medium text-gray-500 uppercase tracking-wider">{"Top Venues"}</th>
                                                     <th class="px-4 py-2 text-left text-xs font-medium text-gray-500 uppercase tracking-wider">{"Members"}</th>
-                                                    <th class="px-4 py-2 text-left text-xs font-medium text-gray-500 uppercase tracking-wider">{"Strength"}</th>
+                                                    <th class="px-4 py-2 text-left text-xs font-medium text-gray-500 uppercase tracking-wider">{"Players"}</th>
                                                 </tr>
                                             </thead>
                                             <tbody class="bg-white divide-y divide-gray-200">
                                                 {communities.iter().map(|c| {
-                                                    let leader = &c["community_leader"];
-                                                    let leader_name = leader["opponent_handle"].as_str().unwrap_or("Unknown");
+                                                    let top_venues = c["top_venues"]
+                                                        .as_array()
+                                                        .map(|venues| {
+                                                            venues
+                                                                .iter()
+                                                                .filter_map(|v| v["venue_name"].as_str())
+                                                                .collect::<Vec<_>>()
+                                                                .join(", ")
+                                                        })
+                                                        .unwrap_or_default();
                                                     let total_members = c["total_members"].as_i64().unwrap_or(0);
-                                                    let community_strength = c["community_strength"].as_f64().unwrap_or(0.0);
+                                                    let members = c["member_handles"]
+                                                        .as_array()
+                                                        .map(|handles| {
+                                                            handles
+                                                                .iter()
+                                                                .filter_map(|h| h.as_str())
+                                                                .take(5)
+                                                                .collect::<Vec<_>>()
+                                                                .join(", ")
+                                                        })
+                                                        .unwrap_or_default();
                                                     html! {
                                                         <tr>
-                                                            <td class="px-4 py-2 whitespace-nowrap text-sm text-gray-700">{leader_name}</td>
+                                                            <td class="px-4 py-2 whitespace-nowrap text-sm text-gray-700">{top_venues}</td>
                                                             <td class="px-4 py-2 whitespace-nowrap text-sm text-gray-700">{total_members}</td>
-                                                            <td class="px-4 py-2 whitespace-nowrap text-sm text-gray-700">{format!("{:.0}", community_strength)}</td>
+                                                            <td class="px-4 py-2 text-sm text-gray-700">{members}</td>
                                                         </tr>
                                                     }
                                                 }).collect::<Html>()}
@@ -1640,13 +1764,14 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
                                         </table>
                                     </div>
                                 } else {
-                                    <div class="no-data">
-                                        <p class="mb-1">{"No gaming communities available"}</p>
-                                        <p class="text-xs text-gray-600">{"Communities emerge when a player has recurring opponents across multiple contests."}</p>
-                                    </div>
+                                    <EmptyState
+                                        icon="👥"
+                                        message="No gaming communities available"
+                                        detail="Communities emerge when several players return to the same venues."
+                                    />
                                 }
                             } else {
-                                <div class="no-data"><p>{"No gaming communities available"}</p></div>
+                                <EmptyState icon="👥" message="No gaming communities available" />
                             }
                         } else {
                             <div class="no-data"><p>{"Gaming communities not loaded"}</p></div>
@@ -1662,37 +1787,35 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
                             </div>
                         } else if let Some(networking_data) = (*player_networking).as_ref() {
                             <div class="networking-grid">
-                                if let Some(opponents) = networking_data["opponent_analysis"].as_array() {
-                                    {opponents.iter().take(5).map(|opponent| {
-                                        let opponent_handle = opponent["opponent_handle"].as_str().unwrap_or("Unknown");
-                                        let total_contests = opponent["total_contests"].as_i64().unwrap_or(0);
-                                        let win_rate = opponent["win_rate"].as_f64().unwrap_or(0.0);
-                                        let last_played = opponent["last_played"].as_str().unwrap_or("Never");
+                                if let Some(suggestions) = networking_data.as_array().filter(|s| !s.is_empty()) {
+                                    {suggestions.iter().take(5).map(|suggestion| {
+                                        let player_handle = suggestion["player_handle"].as_str().unwrap_or("Unknown");
+                                        let shared_venues = suggestion["shared_venues"].as_i64().unwrap_or(0);
+                                        let shared_games = suggestion["shared_games"].as_i64().unwrap_or(0);
+                                        let total_contests = suggestion["total_contests"].as_i64().unwrap_or(0);
 
                                         html! {
                                             <div class="opponent-card">
-                                                <h3>{opponent_handle}</h3>
+                                                <h3>{player_handle}</h3>
                                                 <div class="opponent-stats">
                                                     <div class="stat">
-                                                        <span class="label">{"Games:"}</span>
-                                                        <span class="value">{total_contests}</span>
+                                                        <span class="label">{"Shared Venues:"}</span>
+                                                        <span class="value">{shared_venues}</span>
                                                     </div>
                                                     <div class="stat">
-                                                        <span class="label">{"Your Win Rate:"}</span>
-                                                        <span class="value">{format!("{:.1}%", win_rate)}</span>
+                                                        <span class="label">{"Shared Games:"}</span>
+                                                        <span class="value">{shared_games}</span>
                                                     </div>
                                                     <div class="stat">
-                                                        <span class="label">{"Last Played:"}</span>
-                                                        <span class="value">{last_played}</span>
+                                                        <span class="label">{"Contests:"}</span>
+                                                        <span class="value">{total_contests}</span>
                                                     </div>
                                                 </div>
                                             </div>
                                         }
                                     }).collect::<Html>()}
                                 } else {
-                                    <div class="no-data">
-                                        <p>{"No networking data available"}</p>
-                                    </div>
+                                    <EmptyState icon="🤝" message="No new players to meet yet" detail="Play at more venues to find people you haven't faced" />
                                 }
                             </div>
                         } else {
@@ -1753,3 +1876,60 @@ pub fn analytics_dashboard(_props: &AnalyticsDashboardProps) -> Html {
         </div>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+    use yew_router::history::{AnyHistory, MemoryHistory};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[function_component(TabProbe)]
+    fn tab_probe() -> Html {
+        let current_tab = use_analytics_tab();
+        html! { { current_tab.key() } }
+    }
+
+    #[derive(Properties, PartialEq)]
+    struct ProbeProps {
+        url: String,
+    }
+
+    #[function_component(ProbeApp)]
+    fn probe_app(props: &ProbeProps) -> Html {
+        let url = props.url.clone();
+        let history = use_state(move || AnyHistory::from(MemoryHistory::with_entries(vec![url])));
+        html! {
+            <Router history={(*history).clone()}>
+                <TabProbe />
+            </Router>
+        }
+    }
+
+    async fn mounted_tab(url: &str) -> String {
+        let root = gloo::utils::document().create_element("div").unwrap();
+        let app = yew::Renderer::<ProbeApp>::with_root_and_props(
+            root.clone(),
+            ProbeProps {
+                url: url.to_string(),
+            },
+        )
+        .render();
+        yew::platform::time::sleep(std::time::Duration::from_millis(10)).await;
+        let tab = root.text_content().unwrap_or_default();
+        app.destroy();
+        tab
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_tab_query_selects_games_tab_on_mount() {
+        assert_eq!(mounted_tab("/analytics?tab=games").await, "games");
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_missing_or_unknown_tab_falls_back_to_overview() {
+        assert_eq!(mounted_tab("/analytics").await, "overview");
+        assert_eq!(mounted_tab("/analytics?tab=bogus").await, "overview");
+    }
+}
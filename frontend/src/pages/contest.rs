@@ -11,6 +11,7 @@ use crate::Route;
 use shared::dto::contest::OutcomeDto;
 use shared::dto::game::GameDto;
 use shared::dto::venue::VenueDto;
+use shared::models::contest::ContestVisibility;
 
 use crate::api::venues::get_venue_by_id;
 use gloo::console::log;
@@ -410,6 +411,8 @@ pub fn contest() -> Html {
                     outcomes: state_for_submit.outcomes.clone(),
                     creator_id: String::new(),
                     created_at: None,
+                    rated: true,
+                    visibility: ContestVisibility::Public,
                 };
 
                 log!(format!(
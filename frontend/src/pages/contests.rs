@@ -3,6 +3,7 @@ use crate::api::games::{get_all_games, search_games};
 use crate::api::players::search_players;
 use crate::api::venues::get_all_venues;
 use crate::auth::AuthContext;
+use crate::components::common::empty_state::EmptyState;
 use crate::Route;
 use chrono::DateTime;
 use gloo_timers::callback::Timeout;
@@ -976,21 +977,14 @@ pub fn contests(_props: &ContestsProps) -> Html {
                     </div>
                 } else if let Some(results) = &*search_results {
                     if results.items.is_empty() {
-                        <div class="bg-white rounded-lg shadow-sm p-12 text-center">
-                            <div class="text-6xl mb-4">{"🏆"}</div>
-                            <h2 class="text-2xl font-bold text-gray-900 mb-4">{"No Contests Found"}</h2>
-                            <p class="text-gray-600 mb-6">
-                                {"No contests match your search criteria. Try adjusting your filters or create a new contest."}
-                            </p>
-                            if auth.state.player.is_some() {
-                                <button
-                                    onclick={on_create_contest.clone()}
-                                    class="inline-flex items-center justify-center px-6 py-3 text-lg font-semibold text-white bg-gradient-to-r from-blue-600 to-indigo-600 rounded-xl shadow-lg hover:shadow-xl transform hover:-translate-y-1 transition-all duration-200"
-                                >
-                                    <span class="mr-2 text-xl">{"🚀"}</span>
-                                    {"Create Contest"}
-                                </button>
-                            }
+                        <div class="bg-white rounded-lg shadow-sm">
+                            <EmptyState
+                                icon="🏆"
+                                message="No Contests Found"
+                                detail="No contests match your search criteria. Try adjusting your filters or create a new contest."
+                                cta_label="Create Contest"
+                                on_cta={auth.state.player.is_some().then(|| on_create_contest.clone())}
+                            />
                         </div>
                     } else {
                         // Results Table
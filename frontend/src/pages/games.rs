@@ -1,4 +1,5 @@
 use crate::api::games::{get_all_games, search_games};
+use crate::components::common::empty_state::EmptyState;
 use crate::Route;
 use shared::dto::game::GameDto;
 use wasm_bindgen_futures::spawn_local;
@@ -186,15 +187,11 @@ pub fn games() -> Html {
                         </div>
                     } else if let Some(game_list) = &*games {
                         if game_list.is_empty() {
-                            <div class="p-8 text-center">
-                                <div class="text-gray-400 mb-4">
-                                    <svg class="mx-auto h-12 w-12" fill="none" viewBox="0 0 24 24" stroke="currentColor">
-                                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M9.172 16.172a4 4 0 015.656 0M9 12h6m-6-4h6m2 5.291A7.962 7.962 0 0112 15c-2.34 0-4.29-1.009-5.824-2.571" />
-                                    </svg>
-                                </div>
-                                <h3 class="text-lg font-medium text-gray-900 mb-2">{"No Games Found"}</h3>
-                                <p class="text-gray-500">{"Try adjusting your search criteria"}</p>
-                            </div>
+                            <EmptyState
+                                icon="🎮"
+                                message="No Games Found"
+                                detail="Try adjusting your search criteria"
+                            />
                         } else {
                             <div class="overflow-x-auto">
                                 <table class="min-w-full divide-y divide-gray-200">
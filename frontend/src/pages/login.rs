@@ -1,4 +1,5 @@
 use log::debug;
+use serde::{Deserialize, Serialize};
 use web_sys::HtmlInputElement;
 use yew::events::SubmitEvent;
 use yew::prelude::*;
@@ -6,6 +7,25 @@ use yew_router::prelude::*;
 
 use crate::auth::AuthContext;
 use crate::Route;
+use shared::validation::validate_email;
+
+/// Query string of the login route, e.g. `/login?redirect=/venues`
+#[derive(Serialize, Deserialize, Default)]
+pub struct LoginQuery {
+    /// Path the user was trying to reach when sent to login
+    pub redirect: Option<String>,
+}
+
+/// Where to go once signed in
+///
+/// Only paths that name a page of this app are honoured, so the query can't
+/// send anyone off-site; anything else falls back to the profile.
+pub fn redirect_target(redirect: Option<&str>) -> Route {
+    redirect
+        .and_then(Route::recognize)
+        .filter(|route| !matches!(route, Route::Login | Route::NotFound))
+        .unwrap_or(Route::Profile)
+}
 
 #[function_component(Login)]
 pub fn login() -> Html {
@@ -16,15 +36,22 @@ pub fn login() -> Html {
 
     let auth = use_context::<AuthContext>().expect("Auth context not found");
     let navigator = use_navigator().unwrap();
+    let target = redirect_target(
+        use_location()
+            .and_then(|location| location.query::<LoginQuery>().ok())
+            .and_then(|query| query.redirect)
+            .as_deref(),
+    );
 
-    // Redirect to profile if already authenticated
+    // Redirect to the requested page if already authenticated
     {
         let navigator = navigator.clone();
         let auth_state = auth.state.clone();
+        let target = target.clone();
         use_effect_with((), move |_| {
             if auth_state.player.is_some() {
-                debug!("User already authenticated, redirecting to profile");
-                navigator.push(&Route::Profile);
+                debug!("User already authenticated, redirecting to {:?}", target);
+                navigator.push(&target);
             }
             || ()
         });
@@ -46,6 +73,10 @@ pub fn login() -> Html {
                 error.set("Please enter both email and password".to_string());
                 return;
             }
+            if let Err(e) = validate_email(&email) {
+                error.set(e.message);
+                return;
+            }
 
             loading.set(true);
             error.set(String::new());
@@ -83,14 +114,14 @@ pub fn login() -> Html {
         });
     }
 
-    // Redirect to profile page after successful login
+    // Redirect to the requested page after successful login
     {
         let auth_state = auth.state.clone();
         let navigator = navigator.clone();
         use_effect_with(auth_state.player.clone(), move |player| {
             if player.is_some() {
-                debug!("Login successful, redirecting to profile");
-                navigator.push(&Route::Profile);
+                debug!("Login successful, redirecting to {:?}", target);
+                navigator.push(&target);
             }
             || ()
         });
@@ -162,3 +193,115 @@ pub fn login() -> Html {
         </div>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::AuthState;
+    use crate::components::common::toast::ToastProvider;
+    use crate::ProtectedRoute;
+    use shared::dto::player::PlayerDto;
+    use wasm_bindgen_test::*;
+    use yew_router::history::{AnyHistory, MemoryHistory};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    /// Current path and `?redirect=`, separated by `|`
+    #[function_component(LocationProbe)]
+    fn location_probe() -> Html {
+        let location = use_location().unwrap();
+        let redirect = location
+            .query::<LoginQuery>()
+            .ok()
+            .and_then(|query| query.redirect)
+            .unwrap_or_default();
+        html! { { format!("{}|{}", location.path(), redirect) } }
+    }
+
+    fn switch(route: Route) -> Html {
+        match route {
+            Route::Login => html! { <Login /> },
+            Route::Venues => html! { <ProtectedRoute>{ "venues page" }</ProtectedRoute> },
+            _ => html! {},
+        }
+    }
+
+    #[derive(Properties, PartialEq)]
+    struct ProbeProps {
+        signed_in: bool,
+    }
+
+    /// The app opened at `/venues`, with auth state driven by the props
+    #[function_component(ProbeApp)]
+    fn probe_app(props: &ProbeProps) -> Html {
+        let history = use_state(|| {
+            AnyHistory::from(MemoryHistory::with_entries(vec!["/venues".to_string()]))
+        });
+        let player = props.signed_in.then(|| PlayerDto {
+            id: "player/redirect".to_string(),
+            firstname: "Redirect".to_string(),
+            lastname: None,
+            handle: "redirect".to_string(),
+            email: "redirect@example.com".to_string(),
+            created_at: chrono::Utc::now().into(),
+            is_admin: false,
+        });
+        let auth = AuthContext {
+            state: AuthState {
+                player,
+                ..Default::default()
+            },
+            login: Callback::noop(),
+            logout: Callback::noop(),
+            on_session_expired: Callback::noop(),
+            refresh: Callback::noop(),
+        };
+        html! {
+            <ToastProvider>
+                <ContextProvider<AuthContext> context={auth}>
+                    <Router history={(*history).clone()}>
+                        <LocationProbe />
+                        <Switch<Route> render={switch} />
+                    </Router>
+                </ContextProvider<AuthContext>>
+            </ToastProvider>
+        }
+    }
+
+    #[wasm_bindgen_test]
+    async fn test_login_returns_to_the_protected_page_that_bounced() {
+        let root = gloo::utils::document().create_element("div").unwrap();
+        let mut app = yew::Renderer::<ProbeApp>::with_root_and_props(
+            root.clone(),
+            ProbeProps { signed_in: false },
+        )
+        .render();
+        yew::platform::time::sleep(std::time::Duration::from_millis(10)).await;
+        let text = root.text_content().unwrap_or_default();
+        assert!(text.starts_with("/login|/venues"), "got {}", text);
+
+        app.update(ProbeProps { signed_in: true });
+        yew::platform::time::sleep(std::time::Duration::from_millis(10)).await;
+        let text = root.text_content().unwrap_or_default();
+        assert_eq!(text, "/venues|venues page");
+        app.destroy();
+    }
+
+    #[wasm_bindgen_test]
+    fn test_redirect_target_only_accepts_app_pages() {
+        assert_eq!(redirect_target(Some("/venues")), Route::Venues);
+        assert_eq!(
+            redirect_target(Some("/contest/contest-1")),
+            Route::ContestDetails {
+                contest_id: "contest-1".to_string()
+            }
+        );
+        assert_eq!(redirect_target(None), Route::Profile);
+        assert_eq!(redirect_target(Some("/login")), Route::Profile);
+        assert_eq!(
+            redirect_target(Some("https://evil.example/")),
+            Route::Profile
+        );
+        assert_eq!(redirect_target(Some("/no/such/page")), Route::Profile);
+    }
+}
@@ -55,7 +55,10 @@ pub fn venue_history(props: &VenueHistoryProps) -> Html {
                 } else {
                     venue_id.clone()
                 };
-                let url = format!("/api/analytics/player/contests-by-venue?id={}", vid);
+                let url = format!(
+                    "/api/analytics/player/contests-by-venue?id={}&limit=100",
+                    vid
+                );
                 log::info!("Making venue history API call: {}", url);
                 match authenticated_get(&url).send().await {
                     Ok(response) => {
@@ -63,7 +66,9 @@ pub fn venue_history(props: &VenueHistoryProps) -> Html {
                             match response.json::<Value>().await {
                                 Ok(data) => {
                                     log::info!("Venue history API response: {:?}", data);
-                                    if let Some(arr) = data.as_array() {
+                                    if let Some(arr) =
+                                        data.get("items").and_then(|items| items.as_array())
+                                    {
                                         log::info!("Found {} contests for venue", arr.len());
                                         contests.set(Some(arr.clone()));
                                     } else {
@@ -1,4 +1,5 @@
 use crate::api::venues::{get_all_venues, search_venues};
+use crate::components::common::empty_state::EmptyState;
 use crate::Route;
 use shared::VenueDto;
 use yew::prelude::*;
@@ -186,7 +187,7 @@ pub fn venues() -> Html {
                             </table>
                         </div>
                         if results.is_empty() && !*loading {
-                            <div class="p-8 text-center text-gray-500">{"No venues found"}</div>
+                            <EmptyState icon="🏟️" message="No venues found" />
                         }
                     </div>
                 }
@@ -5,6 +5,7 @@ use serde::Deserialize;
 use serde_json::json;
 use sha2::{Digest, Sha256};
 use std::{
+    collections::HashMap,
     fs,
     path::{Path, PathBuf},
     time::Instant,
@@ -32,6 +33,18 @@ struct Args {
     /// Dry-run: print what would be done
     #[arg(long, default_value_t = false)]
     dry_run: bool,
+    /// Undo this applied migration instead of running pending ones
+    #[arg(long, value_name = "FILENAME")]
+    rollback: Option<String>,
+    /// Run even if applied migration files have changed since they were applied
+    #[arg(long, default_value_t = false)]
+    allow_checksum_drift: bool,
+    /// Stop once this migration has been applied, leaving later ones pending
+    #[arg(long, value_name = "FILENAME")]
+    target: Option<String>,
+    /// Reclaim a lock left by a run that has held it longer than this
+    #[arg(long, env = "MIGRATION_LOCK_TTL_SECONDS", default_value_t = 300)]
+    lock_ttl_seconds: u64,
 }
 
 #[derive(Clone)]
@@ -240,6 +253,181 @@ impl Arango {
         Ok(())
     }
 
+    /// Creates an ArangoSearch view unless one with `name` already exists
+    async fn ensure_view(
+        &self,
+        name: &str,
+        properties: serde_json::Value,
+        dry: bool,
+    ) -> Result<()> {
+        let get = self
+            .auth(self.http.get(self.db_url(&format!("/_api/view/{name}"))?))
+            .send()
+            .await?;
+        if get.status().is_success() {
+            println!("View {} already exists", name);
+            return Ok(());
+        }
+
+        let mut body = serde_json::Map::new();
+        if let Some(map) = properties.as_object() {
+            for (k, v) in map {
+                body.insert(k.clone(), v.clone());
+            }
+        }
+        body.insert("name".into(), json!(name));
+        body.insert("type".into(), json!("arangosearch"));
+
+        if dry {
+            println!(
+                "[dry-run] create view {} properties={}",
+                name,
+                serde_json::Value::Object(body)
+            );
+            return Ok(());
+        }
+
+        let create = self
+            .auth(self.http.post(self.db_url("/_api/view")?))
+            .json(&body)
+            .send()
+            .await?;
+        let status = create.status();
+        if !status.is_success() {
+            let txt = create.text().await.unwrap_or_default();
+            return Err(anyhow!("Create view {} failed: {} - {}", name, status, txt));
+        }
+
+        println!("View {} created", name);
+        Ok(())
+    }
+
+    async fn collection_exists(&self, name: &str) -> Result<bool> {
+        let resp = self
+            .auth(
+                self.http
+                    .get(self.db_url(&format!("/_api/collection/{name}"))?),
+            )
+            .send()
+            .await?;
+        let status = resp.status();
+        if status == StatusCode::NOT_FOUND {
+            return Ok(false);
+        }
+        if !status.is_success() {
+            let txt = resp.text().await.unwrap_or_default();
+            return Err(anyhow!(
+                "Look up collection {} failed: {} - {}",
+                name,
+                status,
+                txt
+            ));
+        }
+        Ok(true)
+    }
+
+    async fn drop_collection(&self, name: &str, if_exists: bool, dry: bool) -> Result<()> {
+        if !self.collection_exists(name).await? {
+            if if_exists {
+                println!("Collection {} does not exist, nothing to drop", name);
+                return Ok(());
+            }
+            return Err(anyhow!(
+                "Drop collection {} failed: it does not exist",
+                name
+            ));
+        }
+        if dry {
+            println!("[dry-run] drop collection {}", name);
+            return Ok(());
+        }
+
+        let resp = self
+            .auth(
+                self.http
+                    .delete(self.db_url(&format!("/_api/collection/{name}"))?),
+            )
+            .send()
+            .await?;
+        let status = resp.status();
+        if !status.is_success() {
+            let txt = resp.text().await.unwrap_or_default();
+            return Err(anyhow!(
+                "Drop collection {} failed: {} - {}",
+                name,
+                status,
+                txt
+            ));
+        }
+
+        println!("Collection {} dropped", name);
+        Ok(())
+    }
+
+    /// Id of the index on `collection` covering exactly `fields`, in order
+    async fn find_index(&self, collection: &str, fields: &[String]) -> Result<Option<String>> {
+        let mut url = self.db_url("/_api/index")?;
+        url.query_pairs_mut().append_pair("collection", collection);
+        let resp = self.auth(self.http.get(url)).send().await?;
+        let status = resp.status();
+        if !status.is_success() {
+            let txt = resp.text().await.unwrap_or_default();
+            return Err(anyhow!(
+                "List indexes on {} failed: {} - {}",
+                collection,
+                status,
+                txt
+            ));
+        }
+
+        #[derive(Deserialize)]
+        struct Index {
+            id: String,
+            #[serde(default)]
+            fields: Vec<String>,
+        }
+        #[derive(Deserialize)]
+        struct IndexList {
+            indexes: Vec<Index>,
+        }
+        let IndexList { indexes } = resp.json().await?;
+        Ok(indexes
+            .into_iter()
+            .find(|index| index.fields == fields)
+            .map(|index| index.id))
+    }
+
+    async fn drop_index(&self, collection: &str, fields: &[String], dry: bool) -> Result<()> {
+        let Some(id) = self.find_index(collection, fields).await? else {
+            println!("No index on {} {:?}, nothing to drop", collection, fields);
+            return Ok(());
+        };
+        if dry {
+            println!("[dry-run] drop index {} on {} {:?}", id, collection, fields);
+            return Ok(());
+        }
+
+        // Index ids are "<collection>/<number>"
+        let resp = self
+            .auth(self.http.delete(self.db_url(&format!("/_api/index/{id}"))?))
+            .send()
+            .await?;
+        let status = resp.status();
+        if !status.is_success() {
+            let txt = resp.text().await.unwrap_or_default();
+            return Err(anyhow!(
+                "Drop index {} on {} failed: {} - {}",
+                id,
+                collection,
+                status,
+                txt
+            ));
+        }
+
+        println!("Index {} dropped from {}", id, collection);
+        Ok(())
+    }
+
     async fn run_aql<T: for<'de> Deserialize<'de>>(
         &self,
         query: &str,
@@ -379,37 +567,96 @@ async fn ensure_meta(client: &Arango, dry: bool) -> Result<()> {
     Ok(())
 }
 
-async fn acquire_lock(client: &Arango, dry: bool) -> Result<LockGuard> {
+/// Takes the migration lock, reclaiming it if the run holding it is past
+/// `ttl`, as when a crashed run never released it
+async fn acquire_lock(client: &Arango, dry: bool, ttl: std::time::Duration) -> Result<LockGuard> {
     if dry {
         println!("[dry-run] acquire lock");
         return Ok(LockGuard {
             client: client.clone(),
         });
     }
-    let res = client
-        .insert_doc(
-            "migration_lock",
-            json!({ "_key": "lock", "acquiredAt": chrono_iso() }),
-            false,
+    let now = time::OffsetDateTime::now_utc();
+    let lock = json!({
+        "_key": "lock",
+        "acquiredAt": rfc3339(now),
+        "expiresAt": rfc3339(now + ttl),
+    });
+    let held_err = match client
+        .insert_doc("migration_lock", lock.clone(), false)
+        .await
+    {
+        Ok(_) => {
+            return Ok(LockGuard {
+                client: client.clone(),
+            })
+        }
+        Err(e) => e,
+    };
+
+    let held: Option<serde_json::Value> = client
+        .run_aql::<Vec<serde_json::Value>>(
+            r#"FOR l IN migration_lock FILTER l._key == "lock" RETURN l"#,
+            None,
         )
-        .await;
-    match res {
-        Ok(_) => Ok(LockGuard {
-            client: client.clone(),
-        }),
-        Err(e) => Err(anyhow!("Lock already held or failed: {}", e)),
+        .await?
+        .into_iter()
+        .next();
+    let Some(held) = held.filter(|held| lock_is_stale(held, ttl, now)) else {
+        return Err(anyhow!("Lock already held or failed: {}", held_err));
+    };
+
+    println!(
+        "Warning: reclaiming stale migration lock acquired at {}",
+        held.get("acquiredAt")
+            .and_then(|v| v.as_str())
+            .unwrap_or("an unknown time")
+    );
+    // The revision check makes a second run reclaiming the same lock fail
+    client
+        .run_aql::<Vec<serde_json::Value>>(
+            r#"REPLACE { _key: "lock", _rev: @rev } WITH MERGE(@lock, { _rev: @rev }) IN migration_lock OPTIONS { ignoreRevs: false }"#,
+            Some(json!({ "rev": held["_rev"], "lock": lock })),
+        )
+        .await
+        .map_err(|e| anyhow!("Lock already held or failed: {}", e))?;
+    Ok(LockGuard {
+        client: client.clone(),
+    })
+}
+
+/// Whether a lock document has expired at `now`
+///
+/// Locks written before `expiresAt` was recorded expire `ttl` after they
+/// were acquired; a lock with neither timestamp is never reclaimed.
+fn lock_is_stale(
+    lock: &serde_json::Value,
+    ttl: std::time::Duration,
+    now: time::OffsetDateTime,
+) -> bool {
+    let parse = |field: &str| {
+        lock.get(field).and_then(|v| v.as_str()).and_then(|s| {
+            time::OffsetDateTime::parse(s, &time::format_description::well_known::Rfc3339).ok()
+        })
+    };
+    match (parse("expiresAt"), parse("acquiredAt")) {
+        (Some(expires_at), _) => expires_at <= now,
+        (None, Some(acquired_at)) => acquired_at + ttl <= now,
+        (None, None) => false,
     }
 }
 
-fn chrono_iso() -> String {
-    // no chrono dependency; ISO-ish using std
-    let now = std::time::SystemTime::now();
-    let dt: time::OffsetDateTime = now.into();
+fn rfc3339(dt: time::OffsetDateTime) -> String {
     dt.format(&time::format_description::well_known::Rfc3339)
         .unwrap_or_else(|_| "now".into())
 }
 
-#[derive(Debug, Deserialize)]
+fn chrono_iso() -> String {
+    // no chrono dependency; ISO-ish using std
+    rfc3339(std::time::SystemTime::now().into())
+}
+
+#[derive(Debug, Clone, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 enum Step {
     CreateCollection {
@@ -428,11 +675,67 @@ enum Step {
         #[serde(default)]
         bind_vars: Option<serde_json::Value>,
     },
+    EnsureView {
+        name: String,
+        #[serde(default)]
+        properties: serde_json::Value, // arangosearch view properties, e.g. links
+    },
+    DropCollection {
+        name: String,
+        #[serde(default)]
+        if_exists: bool, // a missing collection is an error unless set
+    },
+    DropIndex {
+        collection: String,
+        fields: Vec<String>, // the index whose fields match exactly, in order
+    },
 }
 
 #[derive(Debug, Deserialize)]
 struct MigrationFile {
     steps: Vec<Step>,
+    /// Steps that undo `steps`, run by `--rollback`
+    #[serde(default)]
+    down: Option<Vec<Step>>,
+}
+
+impl MigrationFile {
+    /// The steps that undo this migration
+    ///
+    /// Without a `down` section, the collections and indexes the migration
+    /// creates are dropped in reverse order; any other step needs an explicit
+    /// `down` section.
+    fn down_steps(&self) -> Result<Vec<Step>> {
+        if let Some(down) = &self.down {
+            return Ok(down.clone());
+        }
+        self.steps
+            .iter()
+            .rev()
+            .map(|step| match step {
+                Step::CreateCollection { name, .. } => Ok(Step::DropCollection {
+                    name: name.clone(),
+                    if_exists: true,
+                }),
+                Step::EnsureIndex { collection, index } => {
+                    let fields = index
+                        .get("fields")
+                        .and_then(|fields| serde_json::from_value(fields.clone()).ok())
+                        .ok_or_else(|| {
+                            anyhow!("ensure_index on {} has no fields to drop by", collection)
+                        })?;
+                    Ok(Step::DropIndex {
+                        collection: collection.clone(),
+                        fields,
+                    })
+                }
+                other => Err(anyhow!(
+                    "No inverse for {:?}; add a \"down\" section to roll back",
+                    other
+                )),
+            })
+            .collect()
+    }
 }
 
 #[tokio::main]
@@ -451,23 +754,53 @@ async fn main() -> Result<()> {
     check_database(&client, args.dry_run).await?;
 
     ensure_meta(&client, args.dry_run).await?;
-    let _lock = acquire_lock(&client, args.dry_run).await?;
+    let lock_ttl = std::time::Duration::from_secs(args.lock_ttl_seconds);
+    let _lock = acquire_lock(&client, args.dry_run, lock_ttl).await?;
+
+    if let Some(fname) = &args.rollback {
+        rollback_migration(&client, &args.migrations_dir, fname, args.dry_run).await?;
+        println!("Rollback complete.");
+        return Ok(());
+    }
 
-    let applied = get_applied_set(&client).await?;
+    let applied = get_applied_checksums(&client).await?;
     let files = list_migration_files(&args.migrations_dir)?;
 
-    for path in files {
-        let fname = path
-            .file_name()
-            .and_then(|s| s.to_str())
-            .unwrap()
-            .to_string();
-        if applied.contains(&fname) {
-            continue;
+    let mut on_disk = Vec::new();
+    for path in &files {
+        let fname = migration_name(path);
+        if applied.contains_key(&fname) {
+            let content = fs::read(path).with_context(|| format!("read {:?}", path))?;
+            on_disk.push((fname, checksum(&content)));
+        }
+    }
+    let drifted = find_checksum_drift(&applied, &on_disk);
+    if !drifted.is_empty() {
+        let list = drifted.join("\n  ");
+        if !args.allow_checksum_drift {
+            return Err(anyhow!(
+                "Applied migrations have changed since they were applied:\n  {}\nRestore them and add a new migration instead, or pass --allow-checksum-drift",
+                list
+            ));
         }
+        println!(
+            "Warning: applied migrations have changed since they were applied:\n  {}",
+            list
+        );
+    }
+
+    let pending = pending_migrations(&files, &applied, args.target.as_deref())?;
+    if args.dry_run {
+        println!("[dry-run] {} migration(s) would run:", pending.len());
+        for path in &pending {
+            println!("[dry-run]   {}", migration_name(path));
+        }
+    }
 
+    for path in pending {
+        let fname = migration_name(&path);
         let content = fs::read(&path).with_context(|| format!("read {:?}", path))?;
-        let checksum = hex::encode(Sha256::digest(&content));
+        let checksum = checksum(&content);
         let start = Instant::now();
 
         match path
@@ -517,19 +850,85 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-async fn get_applied_set(client: &Arango) -> Result<std::collections::HashSet<String>> {
+/// Applied migrations, with the checksum recorded for each
+async fn get_applied_checksums(client: &Arango) -> Result<HashMap<String, Option<String>>> {
     // Check if the collection exists first
     let url = client.db_url("/_api/collection/schema_migrations")?;
     let resp = client.auth(client.http.get(url)).send().await?;
     if resp.status() == StatusCode::NOT_FOUND {
-        // Collection doesn't exist yet, return empty set
-        return Ok(std::collections::HashSet::new());
+        // Collection doesn't exist yet, nothing is applied
+        return Ok(HashMap::new());
     }
 
     // Collection exists, query it
-    let query = "FOR m IN schema_migrations RETURN m._key";
-    let keys: Vec<String> = client.run_aql(query, None).await?;
-    Ok(keys.into_iter().collect())
+    let query = "FOR m IN schema_migrations RETURN [m._key, m.checksum]";
+    let rows: Vec<(String, Option<String>)> = client.run_aql(query, None).await?;
+    Ok(rows.into_iter().collect())
+}
+
+fn migration_name(path: &Path) -> String {
+    path.file_name()
+        .and_then(|s| s.to_str())
+        .unwrap()
+        .to_string()
+}
+
+fn checksum(content: &[u8]) -> String {
+    hex::encode(Sha256::digest(content))
+}
+
+/// Migrations still to apply, in order, up to and including `target` if given
+fn pending_migrations(
+    files: &[PathBuf],
+    applied: &HashMap<String, Option<String>>,
+    target: Option<&str>,
+) -> Result<Vec<PathBuf>> {
+    let files = match target {
+        Some(target) => {
+            let end = files
+                .iter()
+                .position(|path| migration_name(path) == target)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "Target migration {} is not in the migrations directory",
+                        target
+                    )
+                })?;
+            if applied.contains_key(target) {
+                return Err(anyhow!(
+                    "Target migration {} is already applied; nothing to do",
+                    target
+                ));
+            }
+            &files[..=end]
+        }
+        None => files,
+    };
+    Ok(files
+        .iter()
+        .filter(|path| !applied.contains_key(&migration_name(path)))
+        .cloned()
+        .collect())
+}
+
+/// Applied migrations whose file no longer matches the recorded checksum
+///
+/// `on_disk` pairs each applied file's name with its current checksum.
+/// Records made without a checksum cannot be compared and are skipped.
+fn find_checksum_drift(
+    applied: &HashMap<String, Option<String>>,
+    on_disk: &[(String, String)],
+) -> Vec<String> {
+    on_disk
+        .iter()
+        .filter_map(|(fname, checksum)| match applied.get(fname) {
+            Some(Some(recorded)) if recorded != checksum => Some(format!(
+                "{} (recorded {}, now {})",
+                fname, recorded, checksum
+            )),
+            _ => None,
+        })
+        .collect()
 }
 
 async fn record_applied(
@@ -564,8 +963,57 @@ fn list_migration_files(dir: &Path) -> Result<Vec<PathBuf>> {
     Ok(entries)
 }
 
+/// Runs a migration's down steps and removes its `schema_migrations` record
+///
+/// Refuses when the file no longer matches the checksum recorded when it was
+/// applied, since its down steps may not undo what actually ran.
+async fn rollback_migration(client: &Arango, dir: &Path, fname: &str, dry: bool) -> Result<()> {
+    let Some(recorded) = get_applied_checksums(client).await?.remove(fname) else {
+        return Err(anyhow!("Migration {} has not been applied", fname));
+    };
+    let path = dir.join(fname);
+    let content = fs::read(&path).with_context(|| format!("read {:?}", path))?;
+    let checksum = checksum(&content);
+
+    if recorded.as_deref() != Some(checksum.as_str()) {
+        return Err(anyhow!(
+            "Migration {} has changed since it was applied (recorded checksum {}, file {}); refusing to roll back",
+            fname,
+            recorded.as_deref().unwrap_or("none"),
+            checksum
+        ));
+    }
+
+    if path.extension().and_then(|s| s.to_str()) != Some("json") {
+        return Err(anyhow!(
+            "Only JSON migrations can be rolled back: {}",
+            fname
+        ));
+    }
+    let mig: MigrationFile = serde_json::from_slice(&content)
+        .with_context(|| format!("parse JSON migration {}", fname))?;
+    let down = mig
+        .down_steps()
+        .with_context(|| format!("roll back {}", fname))?;
+    apply_steps(client, &down, dry)
+        .await
+        .with_context(|| format!("roll back {}", fname))?;
+
+    if dry {
+        println!("[dry-run] forget applied migration {}", fname);
+    } else {
+        client.delete_doc("schema_migrations", fname).await?;
+    }
+    println!("Rolled back {}", fname);
+    Ok(())
+}
+
 async fn apply_migration_file(client: &Arango, mig: &MigrationFile, dry: bool) -> Result<()> {
-    for step in &mig.steps {
+    apply_steps(client, &mig.steps, dry).await
+}
+
+async fn apply_steps(client: &Arango, steps: &[Step], dry: bool) -> Result<()> {
+    for step in steps {
         match step {
             Step::CreateCollection {
                 name,
@@ -592,7 +1040,299 @@ async fn apply_migration_file(client: &Arango, mig: &MigrationFile, dry: bool) -
                         client.run_aql(query, bind_vars.clone()).await?;
                 }
             }
+            Step::EnsureView { name, properties } => {
+                client.ensure_view(name, properties.clone(), dry).await?;
+            }
+            Step::DropCollection { name, if_exists } => {
+                client.drop_collection(name, *if_exists, dry).await?;
+            }
+            Step::DropIndex { collection, fields } => {
+                client.drop_index(collection, fields, dry).await?;
+            }
         }
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use testing::TestEnvironment;
+
+    async fn system_client(env: &TestEnvironment) -> Result<Arango> {
+        Arango::authenticate(env.arangodb_url(), "_system", "root", "test_password").await
+    }
+
+    fn migration(steps: serde_json::Value) -> MigrationFile {
+        serde_json::from_value(json!({ "steps": steps })).expect("valid migration")
+    }
+
+    #[test]
+    fn test_down_steps_invert_creates_unless_given() {
+        let mig = migration(json!([
+            { "type": "create_collection", "name": "rollback_test" },
+            {
+                "type": "ensure_index",
+                "collection": "rollback_test",
+                "index": { "type": "persistent", "fields": ["handle", "email"] }
+            }
+        ]));
+        let down = mig.down_steps().expect("derived down steps");
+        assert!(matches!(
+            &down[..],
+            [
+                Step::DropIndex { collection, fields },
+                Step::DropCollection { name, if_exists: true },
+            ] if collection == "rollback_test"
+                && fields == &["handle", "email"]
+                && name == "rollback_test"
+        ));
+
+        // AQL cannot be inverted
+        let aql = migration(json!([{ "type": "aql", "query": "RETURN 1" }]));
+        assert!(aql.down_steps().is_err());
+
+        // An explicit down section is used as written
+        let explicit: MigrationFile = serde_json::from_value(json!({
+            "steps": [{ "type": "aql", "query": "RETURN 1" }],
+            "down": [{ "type": "aql", "query": "RETURN 2" }]
+        }))
+        .expect("valid migration");
+        assert!(matches!(
+            &explicit.down_steps().expect("explicit down steps")[..],
+            [Step::Aql { query, .. }] if query == "RETURN 2"
+        ));
+    }
+
+    #[test]
+    fn test_ensure_view_step_parses_from_fixture() {
+        let mig: MigrationFile =
+            serde_json::from_str(include_str!("../tests/fixtures/ensure_view.json"))
+                .expect("valid migration");
+        match &mig.steps[..] {
+            [Step::EnsureView { name, properties }] => {
+                assert_eq!(name, "contest_search");
+                assert_eq!(
+                    properties["links"]["contest"]["fields"]["name"]["analyzers"],
+                    json!(["text_en"])
+                );
+            }
+            other => panic!("expected one ensure_view step, got {:?}", other),
+        }
+
+        // Properties are optional
+        let bare = migration(json!([{ "type": "ensure_view", "name": "bare_view" }]));
+        assert!(matches!(
+            &bare.steps[..],
+            [Step::EnsureView { name, properties }] if name == "bare_view" && properties.is_null()
+        ));
+    }
+
+    #[test]
+    fn test_checksum_drift_lists_only_changed_applied_files() {
+        let applied = HashMap::from([
+            ("001_same.json".to_string(), Some("aaa".to_string())),
+            ("002_edited.json".to_string(), Some("bbb".to_string())),
+            ("003_legacy.aql".to_string(), None),
+        ]);
+        let on_disk = vec![
+            ("001_same.json".to_string(), "aaa".to_string()),
+            ("002_edited.json".to_string(), "ccc".to_string()),
+            ("003_legacy.aql".to_string(), "ddd".to_string()),
+            ("004_pending.json".to_string(), "eee".to_string()),
+        ];
+        assert_eq!(
+            find_checksum_drift(&applied, &on_disk),
+            ["002_edited.json (recorded bbb, now ccc)"]
+        );
+        assert!(find_checksum_drift(&applied, &on_disk[..1]).is_empty());
+    }
+
+    #[test]
+    fn test_lock_is_stale_once_expired() {
+        let ttl = std::time::Duration::from_secs(300);
+        let now = time::OffsetDateTime::now_utc();
+        let lock = |acquired: time::OffsetDateTime, expires: Option<time::OffsetDateTime>| {
+            let mut lock = json!({ "_key": "lock", "acquiredAt": rfc3339(acquired) });
+            if let Some(expires) = expires {
+                lock["expiresAt"] = json!(rfc3339(expires));
+            }
+            lock
+        };
+        let minutes = time::Duration::minutes;
+
+        assert!(!lock_is_stale(&lock(now, Some(now + ttl)), ttl, now));
+        assert!(lock_is_stale(
+            &lock(now - minutes(10), Some(now - minutes(5))),
+            ttl,
+            now
+        ));
+        // Older locks without an expiry go stale a ttl after being acquired
+        assert!(!lock_is_stale(&lock(now - minutes(1), None), ttl, now));
+        assert!(lock_is_stale(&lock(now - minutes(6), None), ttl, now));
+        assert!(!lock_is_stale(&json!({ "_key": "lock" }), ttl, now));
+    }
+
+    #[tokio::test]
+    async fn test_stale_lock_is_reclaimed() -> Result<()> {
+        let env = TestEnvironment::new().await?;
+        env.wait_for_ready().await?;
+        let client = system_client(&env).await?;
+        ensure_meta(&client, false).await?;
+        let ttl = std::time::Duration::from_secs(300);
+
+        // A run that is still going keeps the lock
+        let live = acquire_lock(&client, false, ttl).await?;
+        assert!(acquire_lock(&client, false, ttl).await.is_err());
+        std::mem::forget(live);
+
+        // A crashed run left its lock behind an hour ago
+        client.delete_doc("migration_lock", "lock").await?;
+        let an_hour_ago = time::OffsetDateTime::now_utc() - time::Duration::hours(1);
+        client
+            .insert_doc(
+                "migration_lock",
+                json!({
+                    "_key": "lock",
+                    "acquiredAt": rfc3339(an_hour_ago),
+                    "expiresAt": rfc3339(an_hour_ago + ttl),
+                }),
+                false,
+            )
+            .await?;
+
+        let reclaimed = acquire_lock(&client, false, ttl).await?;
+        let lock: Vec<serde_json::Value> = client
+            .run_aql(r#"FOR l IN migration_lock RETURN l"#, None)
+            .await?;
+        assert_eq!(lock.len(), 1);
+        assert!(!lock_is_stale(
+            &lock[0],
+            ttl,
+            time::OffsetDateTime::now_utc()
+        ));
+        std::mem::forget(reclaimed);
+        Ok(())
+    }
+
+    #[test]
+    fn test_pending_migrations_stop_at_the_target() {
+        let files: Vec<PathBuf> = ["001_a.json", "002_b.aql", "003_c.json", "004_d.json"]
+            .iter()
+            .map(|name| Path::new("files").join(name))
+            .collect();
+        let applied = HashMap::from([("001_a.json".to_string(), Some("aaa".to_string()))]);
+        let names = |pending: Vec<PathBuf>| -> Vec<String> {
+            pending.iter().map(|path| migration_name(path)).collect()
+        };
+
+        let all = pending_migrations(&files, &applied, None).unwrap();
+        assert_eq!(names(all), ["002_b.aql", "003_c.json", "004_d.json"]);
+
+        let to_target = pending_migrations(&files, &applied, Some("003_c.json")).unwrap();
+        assert_eq!(names(to_target), ["002_b.aql", "003_c.json"]);
+
+        // A target that is missing or already applied is an error
+        assert!(pending_migrations(&files, &applied, Some("005_e.json")).is_err());
+        assert!(pending_migrations(&files, &applied, Some("001_a.json")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rollback_undoes_a_migration_and_forgets_it() -> Result<()> {
+        let env = TestEnvironment::new().await?;
+        env.wait_for_ready().await?;
+        let client = system_client(&env).await?;
+        ensure_meta(&client, false).await?;
+
+        let dir = std::env::temp_dir().join(format!("stg_rollback_{}", std::process::id()));
+        fs::create_dir_all(&dir)?;
+        let fname = "20990101T000000_rollback_test.json";
+        let path = dir.join(fname);
+        let content = json!({
+            "steps": [
+                { "type": "create_collection", "name": "rollback_test" },
+                {
+                    "type": "ensure_index",
+                    "collection": "rollback_test",
+                    "index": { "type": "persistent", "fields": ["handle"] }
+                }
+            ]
+        })
+        .to_string();
+        fs::write(&path, &content)?;
+
+        // Not yet applied
+        assert!(rollback_migration(&client, &dir, fname, false)
+            .await
+            .is_err());
+
+        let mig: MigrationFile = serde_json::from_str(&content)?;
+        apply_migration_file(&client, &mig, false).await?;
+        let checksum = checksum(content.as_bytes());
+        record_applied(&client, fname, &checksum, 0, false).await?;
+
+        // An edited file is refused and nothing is undone
+        fs::write(&path, format!("{} ", content))?;
+        assert!(rollback_migration(&client, &dir, fname, false)
+            .await
+            .is_err());
+        assert!(client.collection_exists("rollback_test").await?);
+
+        fs::write(&path, &content)?;
+        rollback_migration(&client, &dir, fname, false).await?;
+        assert!(!client.collection_exists("rollback_test").await?);
+        assert!(!get_applied_checksums(&client).await?.contains_key(fname));
+
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_drop_steps_remove_what_create_steps_made() -> Result<()> {
+        let env = TestEnvironment::new().await?;
+        env.wait_for_ready().await?;
+        let client = system_client(&env).await?;
+        let fields = vec!["handle".to_string()];
+
+        let create = migration(json!([
+            { "type": "create_collection", "name": "teardown_test" },
+            {
+                "type": "ensure_index",
+                "collection": "teardown_test",
+                "index": { "type": "persistent", "fields": ["handle"] }
+            }
+        ]));
+        apply_migration_file(&client, &create, false).await?;
+        assert!(client.collection_exists("teardown_test").await?);
+        assert!(client.find_index("teardown_test", &fields).await?.is_some());
+
+        let drop_index = migration(json!([
+            { "type": "drop_index", "collection": "teardown_test", "fields": ["handle"] }
+        ]));
+        let drop_collection = migration(json!([
+            { "type": "drop_collection", "name": "teardown_test" }
+        ]));
+
+        // A dry run leaves everything in place
+        apply_migration_file(&client, &drop_index, true).await?;
+        apply_migration_file(&client, &drop_collection, true).await?;
+        assert!(client.find_index("teardown_test", &fields).await?.is_some());
+        assert!(client.collection_exists("teardown_test").await?);
+
+        apply_migration_file(&client, &drop_index, false).await?;
+        assert!(client.find_index("teardown_test", &fields).await?.is_none());
+        apply_migration_file(&client, &drop_collection, false).await?;
+        assert!(!client.collection_exists("teardown_test").await?);
+
+        // Dropping again needs if_exists
+        assert!(apply_migration_file(&client, &drop_collection, false)
+            .await
+            .is_err());
+        let drop_if_exists = migration(json!([
+            { "type": "drop_collection", "name": "teardown_test", "if_exists": true }
+        ]));
+        apply_migration_file(&client, &drop_if_exists, false).await?;
+
+        Ok(())
+    }
+}
@@ -47,9 +47,13 @@ pub struct GameStatsDto {
     pub total_plays: i32,
     pub unique_players: i32,
     pub average_players: f64,
+    /// Players by wins, most first
     pub win_rate_distribution: Vec<PlayerWinRateDto>,
     pub popularity_trend: Vec<MonthlyPlaysDto>,
     pub average_duration_minutes: f64,
+    /// Player with the most wins at the game
+    #[serde(default)]
+    pub champion: Option<PlayerWinRateDto>,
     pub last_updated: DateTime<FixedOffset>,
 }
 
@@ -62,8 +66,15 @@ pub struct VenueStatsDto {
     pub unique_players: i32,
     pub average_participants: f64,
     pub popular_games: Vec<GamePopularityDto>,
+    /// Contests per month, oldest first
     pub monthly_contests: Vec<MonthlyContestsDto>,
     pub average_duration_minutes: f64,
+    /// Game played in the most contests at the venue
+    #[serde(default)]
+    pub most_played_game: Option<GamePopularityDto>,
+    /// Day of the week with the most contests, e.g. "Friday"
+    #[serde(default)]
+    pub busiest_day: Option<String>,
     pub last_updated: DateTime<FixedOffset>,
 }
 
@@ -180,6 +191,20 @@ pub struct PlayerAchievementsDto {
     pub total_achievements: i32,
     pub unlocked_achievements: i32,
     pub completion_percentage: f64,
+    /// The closest locked achievement of each category, nearest first
+    #[serde(default)]
+    pub next_achievements: Vec<NextAchievementDto>,
+}
+
+/// Data Transfer Object for a locked achievement and the progress left to it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NextAchievementDto {
+    pub achievement_id: String,
+    pub name: String,
+    pub category: AchievementCategoryDto,
+    pub current_value: i32,
+    pub required_value: i32,
+    pub remaining: i32,
 }
 
 /// Data Transfer Object for Player Ranking
@@ -191,6 +216,30 @@ pub struct PlayerRankingDto {
     pub value: f64,
 }
 
+/// Latest global rating shown on a player's summary
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SummaryRatingDto {
+    pub rating: f64,
+    pub rd: f64,
+    pub games_played: i32,
+}
+
+/// Everything the profile page shows about a player, in one response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerSummaryDto {
+    pub profile: crate::dto::player::PlayerProfileDto,
+    pub stats: PlayerStatsDto,
+    /// None until the player has been through a rating period
+    pub rating: Option<SummaryRatingDto>,
+    pub rankings: Vec<PlayerRankingDto>,
+    /// Placements in the most recent contests, newest first
+    pub recent_form: Vec<i32>,
+    pub most_played_game: Option<GamePerformanceDto>,
+    pub achievements_unlocked: i32,
+    pub achievements_total: i32,
+    pub achievement_completion: f64,
+}
+
 /// Data Transfer Object for Player Data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlayerDataDto {
@@ -217,6 +266,40 @@ pub struct PlayerOpponentDto {
     pub overall_win_rate: f64,
 }
 
+/// Data Transfer Object for a player the requester hasn't faced yet
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NetworkingSuggestionDto {
+    pub player_id: String,
+    pub player_handle: String,
+    pub player_name: String,
+    /// Venues both players have competed at
+    pub shared_venues: i32,
+    /// Games both players have competed in
+    pub shared_games: i32,
+    pub last_played: Option<chrono::DateTime<chrono::FixedOffset>>,
+    pub total_contests: i32,
+    pub overall_win_rate: f64,
+}
+
+/// Data Transfer Object for a venue within a gaming community
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CommunityVenueDto {
+    pub venue_id: String,
+    pub venue_name: String,
+    /// Contest appearances by community members at this venue
+    pub plays: i32,
+}
+
+/// Data Transfer Object for a group of players who frequent the same venues
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GamingCommunityDto {
+    /// Id of the community's busiest venue
+    pub community_id: String,
+    pub total_members: i32,
+    pub member_handles: Vec<String>,
+    pub top_venues: Vec<CommunityVenueDto>,
+}
+
 /// Data Transfer Object for Game Performance
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GamePerformanceDto {
@@ -236,6 +319,24 @@ pub struct GamePerformanceDto {
     pub favorite_venue: Option<String>,
 }
 
+/// Data Transfer Object for a game suggested to a player
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GameRecommendationDto {
+    pub game_id: String,
+    pub game_name: String,
+    /// Contests the game has been played in across the platform
+    pub total_plays: i32,
+    /// Contests the requesting player has played the game in
+    pub player_plays: i32,
+    /// Plays relative to the most played candidate, 0.0 to 1.0
+    pub popularity: f64,
+    /// Overlap with players from games the player has won, 0.0 to 1.0
+    pub similarity: f64,
+    /// Weighted score out of 100
+    pub score: f64,
+    pub reason: String,
+}
+
 /// Data Transfer Object for Head-to-Head Record
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct HeadToHeadRecordDto {
@@ -264,6 +365,43 @@ pub struct HeadToHeadContestDto {
     pub contest_date: chrono::DateTime<chrono::FixedOffset>,
 }
 
+/// One player's record against another within a group of players
+///
+/// Every pair appears twice, once from each side, so the records of a pair
+/// mirror each other: one side's wins are the other's losses.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GroupHeadToHeadRecordDto {
+    pub player_id: String,
+    pub opponent_id: String,
+    pub total_contests: i32,
+    pub wins: i32,
+    pub losses: i32,
+    /// Contests where both finished in the same place
+    pub ties: i32,
+    pub win_rate: f64,
+}
+
+/// One page of a player's contests at a venue, newest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VenueContestsPageDto {
+    /// Contest boards with every participant's placement
+    pub items: Vec<serde_json::Value>,
+    /// Contests matching the filters across all pages
+    pub total: i64,
+    pub limit: i32,
+    pub offset: i32,
+}
+
+/// One page of stored player stats
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerStatsPageDto {
+    pub items: Vec<PlayerStatsDto>,
+    /// Players with stored stats across all pages
+    pub total: i64,
+    pub limit: i32,
+    pub offset: i32,
+}
+
 /// Data Transfer Object for Performance Trends
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PerformanceTrendDto {
@@ -272,8 +410,8 @@ pub struct PerformanceTrendDto {
     pub wins: i32,
     pub win_rate: f64,
     pub average_placement: f64,
+    /// Global rating at the end of the month
     pub skill_rating: f64,
-    pub points_earned: i32,
 }
 
 /// Request for player statistics
@@ -308,6 +446,12 @@ pub struct VenueStatsRequest {
     pub include_trends: bool,
 }
 
+/// Request for the head-to-head records within a group of players
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupHeadToHeadRequest {
+    pub player_ids: Vec<String>,
+}
+
 /// Request for leaderboard
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 pub struct LeaderboardRequest {
@@ -315,6 +459,42 @@ pub struct LeaderboardRequest {
     pub limit: Option<i32>,
     pub offset: Option<i32>,
     pub time_period: Option<TimePeriod>,
+    /// All-time view: keep players who have not played within the
+    /// configured activity window
+    #[serde(default)]
+    pub include_inactive: Option<bool>,
+}
+
+/// Request for a page of stored player stats
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PlayerStatsPageRequest {
+    pub limit: Option<i32>,
+    pub offset: Option<i32>,
+    #[serde(default)]
+    pub sort_by: PlayerStatsSort,
+}
+
+/// Orderings for stored player stats, highest first
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PlayerStatsSort {
+    #[default]
+    #[serde(rename = "skill_rating")]
+    SkillRating,
+    #[serde(rename = "win_rate")]
+    WinRate,
+    #[serde(rename = "total_contests")]
+    TotalContests,
+}
+
+impl PlayerStatsSort {
+    /// `player_stats` attribute this ordering sorts on
+    pub fn field(&self) -> &'static str {
+        match self {
+            PlayerStatsSort::SkillRating => "skill_rating",
+            PlayerStatsSort::WinRate => "win_rate",
+            PlayerStatsSort::TotalContests => "total_contests",
+        }
+    }
 }
 
 /// Leaderboard categories
@@ -453,6 +633,7 @@ impl From<&GameStats> for GameStatsDto {
                 })
                 .collect(),
             average_duration_minutes: stats.average_duration_minutes,
+            champion: None, // Will be populated by backend
             last_updated: stats.last_updated,
         }
     }
@@ -460,22 +641,25 @@ impl From<&GameStats> for GameStatsDto {
 
 impl From<&VenueStats> for VenueStatsDto {
     fn from(stats: &VenueStats) -> Self {
+        let popular_games: Vec<GamePopularityDto> = stats
+            .popular_games
+            .iter()
+            .map(|g| GamePopularityDto {
+                game_id: g.game_id.clone(),
+                game_name: g.game_name.clone(),
+                plays: g.plays,
+                popularity_score: g.popularity_score,
+            })
+            .collect();
+
         Self {
             venue_id: stats.venue_id.clone(),
             venue_name: String::new(), // Will be populated by backend
             total_contests: stats.total_contests,
             unique_players: stats.unique_players,
             average_participants: stats.average_participants,
-            popular_games: stats
-                .popular_games
-                .iter()
-                .map(|g| GamePopularityDto {
-                    game_id: g.game_id.clone(),
-                    game_name: g.game_name.clone(),
-                    plays: g.plays,
-                    popularity_score: g.popularity_score,
-                })
-                .collect(),
+            most_played_game: popular_games.first().cloned(),
+            popular_games,
             monthly_contests: stats
                 .monthly_contests
                 .iter()
@@ -486,6 +670,7 @@ impl From<&VenueStats> for VenueStatsDto {
                 })
                 .collect(),
             average_duration_minutes: stats.average_duration_minutes,
+            busiest_day: None, // Will be populated by backend
             last_updated: stats.last_updated,
         }
     }
@@ -550,6 +735,32 @@ impl From<&Achievement> for AchievementDto {
     }
 }
 
+impl From<&AchievementCategory> for AchievementCategoryDto {
+    fn from(category: &AchievementCategory) -> Self {
+        match category {
+            AchievementCategory::Wins => Self::Wins,
+            AchievementCategory::Contests => Self::Contests,
+            AchievementCategory::Streaks => Self::Streaks,
+            AchievementCategory::Games => Self::Games,
+            AchievementCategory::Venues => Self::Venues,
+            AchievementCategory::Special => Self::Special,
+        }
+    }
+}
+
+impl From<&NextAchievement> for NextAchievementDto {
+    fn from(next: &NextAchievement) -> Self {
+        Self {
+            achievement_id: next.achievement_id.clone(),
+            name: next.name.clone(),
+            category: (&next.category).into(),
+            current_value: next.current_value,
+            required_value: next.required_value,
+            remaining: next.remaining,
+        }
+    }
+}
+
 impl From<&PlayerAchievements> for PlayerAchievementsDto {
     fn from(achievements: &PlayerAchievements) -> Self {
         Self {
@@ -559,6 +770,11 @@ impl From<&PlayerAchievements> for PlayerAchievementsDto {
             total_achievements: achievements.total_achievements,
             unlocked_achievements: achievements.unlocked_achievements,
             completion_percentage: achievements.completion_percentage,
+            next_achievements: achievements
+                .next_achievements
+                .iter()
+                .map(|n| n.into())
+                .collect(),
         }
     }
 }
@@ -594,6 +810,7 @@ mod tests {
         assert_eq!(dto.game_id, "game/789");
         assert_eq!(dto.total_plays, 0);
         assert_eq!(dto.unique_players, 0);
+        assert!(dto.champion.is_none());
     }
 
     #[test]
@@ -603,6 +820,8 @@ mod tests {
         assert_eq!(dto.venue_id, "venue/101");
         assert_eq!(dto.total_contests, 0);
         assert_eq!(dto.unique_players, 0);
+        assert!(dto.most_played_game.is_none());
+        assert!(dto.busiest_day.is_none());
     }
 
     #[test]
@@ -646,4 +865,17 @@ mod tests {
         assert_eq!(de.contest_history.len(), 1);
         assert_eq!(de.contest_history[0].venue_name, "Blue Room");
     }
+
+    #[test]
+    fn test_player_stats_page_request_defaults_to_skill_rating() {
+        let request: PlayerStatsPageRequest = serde_json::from_str("{}").unwrap();
+        assert_eq!(request.sort_by, PlayerStatsSort::SkillRating);
+        assert_eq!(request.limit, None);
+
+        let request: PlayerStatsPageRequest =
+            serde_json::from_str(r#"{"sort_by":"total_contests","limit":5}"#).unwrap();
+        assert_eq!(request.sort_by.field(), "total_contests");
+        assert_eq!(request.limit, Some(5));
+        assert!(serde_json::from_str::<PlayerStatsPageRequest>(r#"{"sort_by":"name"}"#).is_err());
+    }
 }
@@ -14,6 +14,10 @@ pub struct SearchQuery {
 pub struct ErrorResponse {
     /// Error message
     pub error: String,
+    /// Stable machine-readable code, e.g. `player.duplicate_email`; absent
+    /// from errors raised outside the API handlers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
 }
 
 /// Common authentication response
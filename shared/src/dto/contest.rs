@@ -1,6 +1,6 @@
 use crate::dto::game::GameDto;
 use crate::dto::venue::VenueDto;
-use crate::models::contest::Contest;
+use crate::models::contest::{Contest, ContestVisibility};
 use crate::models::venue::Venue;
 use chrono::{DateTime, FixedOffset};
 use serde::{Deserialize, Serialize};
@@ -25,6 +25,12 @@ pub struct ContestDto {
     /// When this contest was created (set by backend)
     #[serde(default)]
     pub created_at: Option<DateTime<FixedOffset>>,
+    /// Whether the contest counts towards ratings
+    #[serde(default = "crate::models::contest::default_rated")]
+    pub rated: bool,
+    /// Whether the contest shows in platform-wide analytics
+    #[serde(default)]
+    pub visibility: ContestVisibility,
 }
 
 impl Validate for ContestDto {
@@ -57,15 +63,24 @@ impl Validate for ContestDto {
 }
 
 /// Data Transfer Object for Contest Outcome
+///
+/// Tied players share a place. Everyone placed first won, so a two-way tie
+/// for first (or a co-op win) is two outcomes with `place` "1".
+///
+/// A guest outcome names someone without a player document: `handle` holds
+/// their name and they are left out of ratings and leaderboards.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct OutcomeDto {
     pub player_id: String,
     pub place: String,
+    /// "won" for first place, "lost" otherwise
     pub result: String,
     #[serde(default)]
     pub email: String,
     #[serde(default)]
     pub handle: String,
+    #[serde(default)]
+    pub guest: bool,
 }
 
 impl From<&Contest> for ContestDto {
@@ -103,6 +118,8 @@ impl From<&Contest> for ContestDto {
             outcomes: Vec::new(),
             creator_id: contest.creator_id.clone(),
             created_at: Some(contest.created_at),
+            rated: contest.rated,
+            visibility: contest.visibility,
         }
     }
 }
@@ -119,6 +136,8 @@ impl From<ContestDto> for Contest {
             created_at: dto
                 .created_at
                 .unwrap_or_else(|| chrono::Utc::now().fixed_offset()),
+            rated: dto.rated,
+            visibility: dto.visibility,
         }
     }
 }
@@ -131,6 +150,8 @@ impl ContestDto {
         contest.start = self.start;
         contest.stop = self.stop;
         contest.creator_id = self.creator_id.clone();
+        contest.rated = self.rated;
+        contest.visibility = self.visibility;
         if let Some(created_at) = self.created_at {
             contest.created_at = created_at;
         }
@@ -139,6 +160,137 @@ impl ContestDto {
     }
 }
 
+impl OutcomeDto {
+    /// Whether this outcome is a win: first place, alone or tied
+    pub fn is_win(&self) -> bool {
+        self.place.trim().parse::<u32>() == Ok(1)
+    }
+}
+
+/// Outcome of one row of a contest import
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContestImportRowDto {
+    /// Line of the file the row starts on
+    pub line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contest_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Per-row report of a contest import
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContestImportReportDto {
+    pub imported: usize,
+    pub failed: usize,
+    pub rows: Vec<ContestImportRowDto>,
+}
+
+/// Kind of entry on a contest timeline.
+///
+/// Declared in the order entries happen, which also breaks ties between
+/// entries that share a timestamp.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum TimelineEventKind {
+    Created,
+    ParticipantRecorded,
+    Started,
+    Finished,
+    Placement,
+}
+
+/// A single entry on a contest timeline
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TimelineEventDto {
+    pub kind: TimelineEventKind,
+    pub at: DateTime<FixedOffset>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub player_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub handle: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub place: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
+}
+
+impl TimelineEventDto {
+    fn at(kind: TimelineEventKind, at: DateTime<FixedOffset>) -> Self {
+        Self {
+            kind,
+            at,
+            player_id: None,
+            handle: None,
+            place: None,
+            result: None,
+        }
+    }
+
+    fn for_outcome(
+        kind: TimelineEventKind,
+        at: DateTime<FixedOffset>,
+        outcome: &OutcomeDto,
+    ) -> Self {
+        Self {
+            player_id: Some(outcome.player_id.clone()),
+            handle: Some(outcome.handle.clone()).filter(|h| !h.is_empty()),
+            place: outcome.place.trim().parse().ok(),
+            result: Some(outcome.result.clone()),
+            ..Self::at(kind, at)
+        }
+    }
+}
+
+/// A contest's events in chronological order, for replaying how it went
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContestTimelineDto {
+    pub contest_id: String,
+    pub name: String,
+    pub events: Vec<TimelineEventDto>,
+}
+
+impl ContestTimelineDto {
+    /// Assembles the timeline from the contest's timestamps and outcomes.
+    ///
+    /// Outcomes carry no timestamps of their own, so participants are placed
+    /// at the moment the contest was recorded and placements at its end,
+    /// best place first.
+    pub fn build(
+        contest_id: String,
+        name: String,
+        created_at: Option<DateTime<FixedOffset>>,
+        start: DateTime<FixedOffset>,
+        stop: DateTime<FixedOffset>,
+        outcomes: &[OutcomeDto],
+    ) -> Self {
+        let recorded_at = created_at.unwrap_or(start);
+        let mut events = Vec::with_capacity(outcomes.len() * 2 + 3);
+        if let Some(created_at) = created_at {
+            events.push(TimelineEventDto::at(TimelineEventKind::Created, created_at));
+        }
+        events.extend(outcomes.iter().map(|o| {
+            TimelineEventDto::for_outcome(TimelineEventKind::ParticipantRecorded, recorded_at, o)
+        }));
+        events.push(TimelineEventDto::at(TimelineEventKind::Started, start));
+        events.push(TimelineEventDto::at(TimelineEventKind::Finished, stop));
+        events.extend(
+            outcomes
+                .iter()
+                .map(|o| TimelineEventDto::for_outcome(TimelineEventKind::Placement, stop, o)),
+        );
+
+        // Outcomes come back in no particular order, so entries that share a
+        // timestamp and kind are ordered by place, unparseable places last
+        events.sort_by_key(|e| (e.at, e.kind, e.place.is_none(), e.place));
+        Self {
+            contest_id,
+            name,
+            events,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -177,9 +329,12 @@ mod tests {
                 result: "won".to_string(),
                 email: "player1@example.com".to_string(),
                 handle: "player1".to_string(),
+                guest: false,
             }],
             creator_id: "player/test-creator".to_string(),
             created_at: Some(DateTime::parse_from_rfc3339("2023-07-15T10:00:00Z").unwrap()),
+            rated: true,
+            visibility: ContestVisibility::Public,
         }
     }
 
@@ -190,6 +345,7 @@ mod tests {
             result: "won".to_string(),
             email: "player@example.com".to_string(),
             handle: "player".to_string(),
+            guest: false,
         }
     }
 
@@ -274,6 +430,8 @@ mod tests {
             stop: DateTime::parse_from_rfc3339("2023-07-15T16:00:00Z").unwrap(),
             creator_id: "player/test-creator".to_string(),
             created_at: DateTime::parse_from_rfc3339("2023-07-15T10:00:00Z").unwrap(),
+            rated: true,
+            visibility: ContestVisibility::Public,
         };
 
         let dto = ContestDto::from(&contest);
@@ -304,6 +462,8 @@ mod tests {
             stop: DateTime::parse_from_rfc3339("2023-01-01T02:00:00Z").unwrap(),
             creator_id: "player/test-creator".to_string(),
             created_at: DateTime::parse_from_rfc3339("2023-01-01T00:00:00Z").unwrap(),
+            rated: true,
+            visibility: ContestVisibility::Public,
         };
 
         dto.update_contest(&mut contest);
@@ -336,6 +496,22 @@ mod tests {
         assert_eq!(outcome.handle, deserialized.handle);
     }
 
+    #[test]
+    fn test_outcome_dto_tie_for_first_is_a_win_for_both() {
+        let first = create_test_outcome_dto();
+        let mut tied = create_test_outcome_dto();
+        tied.player_id = "player/other".to_string();
+        tied.place = " 1".to_string();
+        assert!(first.is_win());
+        assert!(tied.is_win());
+
+        let mut second = create_test_outcome_dto();
+        second.place = "2".to_string();
+        assert!(!second.is_win());
+        second.place = "first".to_string();
+        assert!(!second.is_win());
+    }
+
     #[test]
     fn test_contest_dto_with_empty_games() {
         let mut dto = create_test_contest_dto();
@@ -374,6 +550,7 @@ mod tests {
             result: "lost".to_string(),
             email: "player2@example.com".to_string(),
             handle: "player2".to_string(),
+            guest: false,
         });
         assert!(dto.validate().is_ok());
         assert_eq!(dto.outcomes.len(), 2);
@@ -437,6 +614,8 @@ mod tests {
             stop: DateTime::parse_from_rfc3339("2023-07-15T16:00:00Z").unwrap(),
             creator_id: "player/test-creator".to_string(),
             created_at: DateTime::parse_from_rfc3339("2023-07-15T10:00:00Z").unwrap(),
+            rated: true,
+            visibility: ContestVisibility::Public,
         };
 
         let dto = ContestDto::from(&contest);
@@ -473,6 +652,8 @@ mod tests {
             stop: DateTime::parse_from_rfc3339("2023-01-01T02:00:00Z").unwrap(),
             creator_id: "player/old-creator".to_string(),
             created_at: DateTime::parse_from_rfc3339("2023-01-01T00:00:00Z").unwrap(),
+            rated: true,
+            visibility: ContestVisibility::Public,
         };
 
         dto.update_contest(&mut contest);
@@ -484,4 +665,61 @@ mod tests {
         assert_eq!(contest.creator_id, dto.creator_id);
         assert_eq!(contest.created_at, dto.created_at.unwrap());
     }
+
+    #[test]
+    fn test_contest_timeline_orders_events() {
+        let dto = create_test_contest_dto();
+        let mut second = dto.outcomes[0].clone();
+        second.player_id = "player/test-player-2".to_string();
+        second.place = "2".to_string();
+        second.result = "lost".to_string();
+        // Outcomes in the wrong order still come out by place
+        let outcomes = vec![second, dto.outcomes[0].clone()];
+        let created_at = DateTime::parse_from_rfc3339("2023-07-15T13:00:00Z").unwrap();
+
+        let timeline = ContestTimelineDto::build(
+            dto.id.clone(),
+            dto.name.clone(),
+            Some(created_at),
+            dto.start,
+            dto.stop,
+            &outcomes,
+        );
+
+        let entries: Vec<_> = timeline
+            .events
+            .iter()
+            .map(|e| (e.kind, e.player_id.as_deref(), e.place))
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                (TimelineEventKind::Created, None, None),
+                (
+                    TimelineEventKind::ParticipantRecorded,
+                    Some("player/test-player-1"),
+                    Some(1)
+                ),
+                (
+                    TimelineEventKind::ParticipantRecorded,
+                    Some("player/test-player-2"),
+                    Some(2)
+                ),
+                (TimelineEventKind::Started, None, None),
+                (TimelineEventKind::Finished, None, None),
+                (
+                    TimelineEventKind::Placement,
+                    Some("player/test-player-1"),
+                    Some(1)
+                ),
+                (
+                    TimelineEventKind::Placement,
+                    Some("player/test-player-2"),
+                    Some(2)
+                ),
+            ]
+        );
+        assert_eq!(timeline.events[0].at, created_at);
+        assert_eq!(timeline.events[6].at, dto.stop);
+    }
 }
@@ -2,6 +2,7 @@ use crate::models::player::Player;
 use chrono::{DateTime, FixedOffset};
 use lazy_static::lazy_static;
 use regex::Regex;
+use serde::ser::{SerializeStruct, Serializer};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
@@ -9,14 +10,64 @@ lazy_static! {
     static ref HANDLE_REGEX: Regex = Regex::new(r"^[a-zA-Z0-9_]+$").unwrap();
 }
 
+/// Background colors used for player identity chips
+const AVATAR_COLORS: [&str; 12] = [
+    "#EF4444", "#F97316", "#F59E0B", "#84CC16", "#10B981", "#14B8A6", "#06B6D4", "#3B82F6",
+    "#6366F1", "#8B5CF6", "#D946EF", "#EC4899",
+];
+
+/// Returns a stable avatar color for a handle (case-insensitive)
+pub fn avatar_color_for_handle(handle: &str) -> &'static str {
+    // FNV-1a keeps the mapping identical across native and wasm builds
+    let hash = handle
+        .trim()
+        .to_lowercase()
+        .bytes()
+        .fold(0x811c9dc5u32, |hash, byte| {
+            (hash ^ byte as u32).wrapping_mul(0x01000193)
+        });
+    AVATAR_COLORS[hash as usize % AVATAR_COLORS.len()]
+}
+
+/// Returns up to two uppercase initials for a handle, e.g. "john_doe" -> "JD", "player1" -> "PL"
+pub fn initials_for_handle(handle: &str) -> String {
+    let words: Vec<&str> = handle
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    let initials: String = if words.len() >= 2 {
+        words
+            .iter()
+            .take(2)
+            .filter_map(|w| w.chars().next())
+            .collect()
+    } else {
+        words
+            .first()
+            .map(|word| word.chars().take(2).collect())
+            .unwrap_or_default()
+    };
+
+    if initials.is_empty() {
+        "?".to_string()
+    } else {
+        initials.to_uppercase()
+    }
+}
+
 /// Data Transfer Object for Player
-#[derive(Debug, Serialize, Deserialize, Validate, Clone, PartialEq)]
+///
+/// Serializes with `avatar_color` and `initials` derived from the handle.
+#[derive(Debug, Deserialize, Validate, Clone, PartialEq)]
 pub struct PlayerDto {
     /// Player's ID (ArangoDB _id field, serialized as "_id" in JSON)
     #[serde(rename = "_id")]
     pub id: String,
     #[validate(length(min = 1, message = "First name is required"))]
     pub firstname: String,
+    #[serde(default)]
+    pub lastname: Option<String>,
     #[validate(length(min = 1, message = "Handle is required"))]
     pub handle: String,
     #[validate(email(message = "Invalid email format"))]
@@ -82,12 +133,19 @@ pub struct StoredPlayer {
 }
 
 /// Data Transfer Object for Player Profile
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+///
+/// Serializes with `avatar_color` and `initials` derived from the handle.
+#[derive(Debug, Clone, Deserialize, Validate)]
 pub struct PlayerProfileDto {
     /// Player's first name
     #[validate(length(min = 1, max = 100))]
     pub firstname: String,
 
+    /// Player's last name
+    #[serde(default)]
+    #[validate(length(min = 1, max = 100))]
+    pub lastname: Option<String>,
+
     /// Player's handle/username
     #[validate(length(min = 1, max = 50))]
     pub handle: String,
@@ -95,6 +153,40 @@ pub struct PlayerProfileDto {
     /// Player's email address
     #[validate(email)]
     pub email: String,
+
+    /// When the player last logged in, if ever
+    #[serde(default)]
+    pub last_login_at: Option<DateTime<FixedOffset>>,
+}
+
+impl Serialize for PlayerDto {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("PlayerDto", 9)?;
+        state.serialize_field("_id", &self.id)?;
+        state.serialize_field("firstname", &self.firstname)?;
+        state.serialize_field("lastname", &self.lastname)?;
+        state.serialize_field("handle", &self.handle)?;
+        state.serialize_field("email", &self.email)?;
+        state.serialize_field("createdAt", &self.created_at)?;
+        state.serialize_field("isAdmin", &self.is_admin)?;
+        state.serialize_field("avatar_color", self.avatar_color())?;
+        state.serialize_field("initials", &self.initials())?;
+        state.end()
+    }
+}
+
+impl Serialize for PlayerProfileDto {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("PlayerProfileDto", 7)?;
+        state.serialize_field("firstname", &self.firstname)?;
+        state.serialize_field("lastname", &self.lastname)?;
+        state.serialize_field("handle", &self.handle)?;
+        state.serialize_field("email", &self.email)?;
+        state.serialize_field("last_login_at", &self.last_login_at)?;
+        state.serialize_field("avatar_color", self.avatar_color())?;
+        state.serialize_field("initials", &self.initials())?;
+        state.end()
+    }
 }
 
 impl From<&Player> for PlayerDto {
@@ -102,6 +194,7 @@ impl From<&Player> for PlayerDto {
         Self {
             id: player.id.clone(),
             firstname: player.firstname.clone(),
+            lastname: player.lastname.clone(),
             handle: player.handle.clone(),
             email: player.email.clone(),
             created_at: player.created_at,
@@ -120,15 +213,22 @@ impl From<PlayerDto> for Player {
             dto.created_at,
             false, // Default to non-admin for new players
         )
+        .map(|player| Self {
+            lastname: dto.lastname.clone(),
+            ..player
+        })
         .unwrap_or_else(|_| Self {
             id: dto.id,
             rev: String::new(), // Let ArangoDB set this
             firstname: dto.firstname,
+            lastname: dto.lastname,
             handle: dto.handle,
             email: dto.email,
             password: String::new(), // Password is handled separately
             created_at: dto.created_at,
             is_admin: false,
+            last_login_at: None,
+            last_login_ip: None,
         })
     }
 }
@@ -147,14 +247,38 @@ impl PlayerDto {
         self.validate()?;
         Ok(Player::from(self))
     }
+
+    /// Avatar background color derived from the handle
+    pub fn avatar_color(&self) -> &'static str {
+        avatar_color_for_handle(&self.handle)
+    }
+
+    /// Avatar initials derived from the handle
+    pub fn initials(&self) -> String {
+        initials_for_handle(&self.handle)
+    }
+}
+
+impl PlayerProfileDto {
+    /// Avatar background color derived from the handle
+    pub fn avatar_color(&self) -> &'static str {
+        avatar_color_for_handle(&self.handle)
+    }
+
+    /// Avatar initials derived from the handle
+    pub fn initials(&self) -> String {
+        initials_for_handle(&self.handle)
+    }
 }
 
 impl From<&Player> for PlayerProfileDto {
     fn from(player: &Player) -> Self {
         Self {
             firstname: player.firstname.clone(),
+            lastname: player.lastname.clone(),
             handle: player.handle.clone(),
             email: player.email.clone(),
+            last_login_at: player.last_login_at,
         }
     }
 }
@@ -196,6 +320,22 @@ pub struct UpdatePasswordRequest {
     pub new_password: String,
 }
 
+/// Request for updating player name fields; omitted fields are left unchanged
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate)]
+pub struct UpdateProfileRequest {
+    /// New first name
+    #[validate(length(min = 1, max = 100))]
+    pub firstname: Option<String>,
+
+    /// New last name
+    #[validate(length(min = 1, max = 100))]
+    pub lastname: Option<String>,
+
+    /// Removes the last name; cannot be combined with `lastname`
+    #[serde(default)]
+    pub clear_lastname: bool,
+}
+
 /// Response for successful update operations
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdateResponse {
@@ -205,6 +345,34 @@ pub struct UpdateResponse {
     pub player: PlayerDto,
 }
 
+/// Request for minting an API key
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CreateApiKeyRequest {
+    /// Label telling the owner what the key is for, e.g. "league bot"
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+}
+
+/// An API key as listed to its owner; the key itself is never shown again
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApiKeyDto {
+    pub id: String,
+    pub name: String,
+    /// Leading characters of the key, to tell keys apart
+    pub prefix: String,
+    pub created_at: DateTime<FixedOffset>,
+    pub revoked_at: Option<DateTime<FixedOffset>>,
+}
+
+/// Response for a newly minted API key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatedApiKeyResponse {
+    #[serde(flatten)]
+    pub api_key: ApiKeyDto,
+    /// The key to send as `Authorization: Bearer <key>`; only shown here
+    pub key: String,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -221,6 +389,7 @@ mod tests {
         PlayerDto {
             id: "player/1".to_string(),
             firstname: "John".to_string(),
+            lastname: None,
             handle: "john_doe".to_string(),
             email: "john@example.com".to_string(),
             created_at: chrono::Utc::now().fixed_offset(),
@@ -281,6 +450,7 @@ mod tests {
             id: "player/1".to_string(),
             email: "not-an-email".to_string(),
             firstname: "Player".to_string(),
+            lastname: None,
             handle: "john_doe".to_string(),
             created_at: chrono::Utc::now().fixed_offset(),
             is_admin: false,
@@ -394,8 +564,10 @@ mod tests {
     fn test_player_profile_dto_creation() {
         let profile = PlayerProfileDto {
             firstname: "John".to_string(),
+            lastname: None,
             handle: "john_doe".to_string(),
             email: "john@example.com".to_string(),
+            last_login_at: None,
         };
         assert_eq!(profile.firstname, "John");
     }
@@ -404,8 +576,10 @@ mod tests {
     fn test_player_profile_dto_validation() {
         let profile = PlayerProfileDto {
             firstname: "John".to_string(),
+            lastname: None,
             handle: "john_doe".to_string(),
             email: "john@example.com".to_string(),
+            last_login_at: None,
         };
         assert!(profile.validate().is_ok());
     }
@@ -416,11 +590,14 @@ mod tests {
             id: "player/1".to_string(),
             rev: "1".to_string(),
             firstname: "John".to_string(),
+            lastname: None,
             handle: "john_doe".to_string(),
             email: "john@example.com".to_string(),
             password: "hashed_password".to_string(),
             created_at: chrono::Utc::now().fixed_offset(),
             is_admin: false,
+            last_login_at: None,
+            last_login_ip: None,
         };
 
         let dto = PlayerDto::from(&player);
@@ -436,11 +613,14 @@ mod tests {
             id: "player/1".to_string(),
             rev: "1".to_string(),
             firstname: "John".to_string(),
+            lastname: None,
             handle: "john_doe".to_string(),
             email: "john@example.com".to_string(),
             password: "hashed_password".to_string(),
             created_at: chrono::Utc::now().fixed_offset(),
             is_admin: false,
+            last_login_at: None,
+            last_login_ip: None,
         };
 
         // Note: StoredPlayer doesn't have a From implementation, so we'll test manual creation
@@ -459,11 +639,14 @@ mod tests {
             id: "player/1".to_string(),
             rev: "1".to_string(),
             firstname: "John".to_string(),
+            lastname: None,
             handle: "john_doe".to_string(),
             email: "john@example.com".to_string(),
             password: "hashed_password".to_string(),
             created_at: chrono::Utc::now().fixed_offset(),
             is_admin: false,
+            last_login_at: None,
+            last_login_ip: None,
         };
 
         let profile = PlayerProfileDto::from(&player);
@@ -477,6 +660,7 @@ mod tests {
         let dto = PlayerDto {
             id: "player/1".to_string(),
             firstname: "Test".to_string(),
+            lastname: None,
             handle: Username(EN).fake(),
             email: SafeEmail().fake(),
             created_at: chrono::Utc::now().fixed_offset(),
@@ -559,6 +743,7 @@ mod tests {
         let player = PlayerDto {
             id: "player/1".to_string(),
             firstname: "John & Jane".to_string(),
+            lastname: None,
             handle: "john_jane_123".to_string(),
             email: "john.jane+test@example.com".to_string(),
             created_at: chrono::Utc::now().fixed_offset(),
@@ -582,6 +767,7 @@ mod tests {
             id: "player/1".to_string(),
             email: "".to_string(),
             firstname: "Player".to_string(),
+            lastname: None,
             handle: "john_doe".to_string(),
             created_at: chrono::Utc::now().fixed_offset(),
             is_admin: false,
@@ -604,6 +790,7 @@ mod tests {
             id: "player/1".to_string(),
             email: "".to_string(), // Invalid: empty email
             firstname: "Player".to_string(),
+            lastname: None,
             handle: "john_doe".to_string(),
             created_at: chrono::Utc::now().fixed_offset(),
             is_admin: false,
@@ -611,4 +798,54 @@ mod tests {
         let result = dto.try_into_player();
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_avatar_identity_is_deterministic() {
+        let dto = create_test_player_dto();
+        let profile = PlayerProfileDto {
+            firstname: "Someone".to_string(),
+            lastname: None,
+            handle: dto.handle.clone(),
+            email: "other@example.com".to_string(),
+            last_login_at: None,
+        };
+
+        assert_eq!(dto.avatar_color(), avatar_color_for_handle("john_doe"));
+        assert_eq!(dto.avatar_color(), profile.avatar_color());
+        assert_eq!(dto.initials(), profile.initials());
+        assert_eq!(
+            avatar_color_for_handle("John_Doe"),
+            avatar_color_for_handle("john_doe")
+        );
+        assert!(AVATAR_COLORS.contains(&avatar_color_for_handle("anyone")));
+    }
+
+    #[test]
+    fn test_avatar_identity_is_serialized() {
+        let dto = create_test_player_dto();
+        let json = serde_json::to_value(&dto).unwrap();
+        assert_eq!(json["avatar_color"], avatar_color_for_handle("john_doe"));
+        assert_eq!(json["initials"], "JD");
+        assert_eq!(json["_id"], dto.id);
+        assert_eq!(json["isAdmin"], false);
+
+        // The derived fields are ignored when reading a DTO back
+        let back: PlayerDto = serde_json::from_value(json).unwrap();
+        assert_eq!(back, dto);
+
+        let profile = PlayerProfileDto::from(&Player::from(dto));
+        let json = serde_json::to_value(&profile).unwrap();
+        assert_eq!(json["avatar_color"], avatar_color_for_handle("john_doe"));
+        assert_eq!(json["initials"], "JD");
+    }
+
+    #[test]
+    fn test_initials_for_handle() {
+        assert_eq!(initials_for_handle("john_doe"), "JD");
+        assert_eq!(initials_for_handle("player1"), "PL");
+        assert_eq!(initials_for_handle("x"), "X");
+        assert_eq!(initials_for_handle("__"), "?");
+        assert_eq!(initials_for_handle(""), "?");
+        assert_eq!(initials_for_handle("élodie_ñúñez"), "ÉÑ");
+    }
 }
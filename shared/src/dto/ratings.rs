@@ -44,3 +44,15 @@ pub struct RatingLeaderboardEntryDto {
     pub last_active: Option<String>,
     pub contest_id: Option<String>,
 }
+
+/// A player's global leaderboard rank after the latest period and how it moved
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RatingRankChangeDto {
+    pub player_id: String,
+    pub rank: Option<i32>,
+    pub period_end: Option<String>,
+    pub previous_rank: Option<i32>,
+    pub previous_period_end: Option<String>,
+    /// Places gained since the previous period; positive means moved up
+    pub delta: Option<i32>,
+}
@@ -140,6 +140,7 @@ impl From<ResultedInDto> for ResultedIn {
             label: dto.label,
             place: dto.place,
             result: dto.result,
+            guest_name: None,
         }
     }
 }
@@ -27,6 +27,7 @@ pub mod dto {
 pub mod error;
 pub mod timezone;
 pub mod timezone_cache;
+pub mod validation;
 
 // Re-export commonly used items
 pub use error::{Result, SharedError};
@@ -35,8 +36,8 @@ pub use error::{Result, SharedError};
 pub use models::{
     analytics::{
         Achievement, AchievementCategory, ContestStats, GamePopularity, GameStats, MonthlyContests,
-        MonthlyPlays, PlatformStats, PlayerAchievements, PlayerStats, PlayerWinRate, VenueActivity,
-        VenueStats,
+        MonthlyPlays, NextAchievement, PlatformStats, PlayerAchievements, PlayerStats,
+        PlayerWinRate, VenueActivity, VenueStats,
     },
     auth::{LoginRequest, RegisterRequest, User, UserSession},
     contest::Contest,
@@ -52,8 +53,9 @@ pub use dto::{
         AchievementCategoryDto, AchievementDto, ContestStatsDto, ContestStatsRequest,
         GamePopularityDto, GameStatsDto, GameStatsRequest, LeaderboardCategory, LeaderboardEntry,
         LeaderboardRequest, LeaderboardResponse, MonthlyContestsDto, MonthlyPlaysDto,
-        PlatformStatsDto, PlayerAchievementsDto, PlayerStatsDto, PlayerStatsRequest,
-        PlayerWinRateDto, TimePeriod, VenueActivityDto, VenueStatsDto, VenueStatsRequest,
+        NextAchievementDto, PlatformStatsDto, PlayerAchievementsDto, PlayerStatsDto,
+        PlayerStatsRequest, PlayerWinRateDto, TimePeriod, VenueActivityDto, VenueStatsDto,
+        VenueStatsRequest,
     },
     auth::UserSessionDto,
     common::{AuthResponse, ErrorResponse, SearchQuery},
@@ -79,11 +81,14 @@ mod tests {
             id: "test_player".to_string(),
             rev: "1".to_string(),
             firstname: "John".to_string(),
+            lastname: None,
             handle: "testuser".to_string(),
             email: "test@example.com".to_string(),
             password: "hashed_password".to_string(),
             created_at: chrono::Utc::now().fixed_offset(),
             is_admin: false,
+            last_login_at: None,
+            last_login_ip: None,
         };
 
         assert_eq!(player.handle, "testuser");
@@ -170,6 +170,54 @@ pub struct MonthlyContests {
     pub contests: i32,
 }
 
+/// A standout recent contest with its full results, for the home page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeaturedContest {
+    pub contest_id: String,
+    pub name: String,
+    pub start: DateTime<FixedOffset>,
+    pub stop: DateTime<FixedOffset>,
+    /// Venue display name, if the contest has a venue
+    pub venue: Option<String>,
+    pub games: Vec<String>,
+    /// Contest excitement rating (based on close finishes)
+    pub excitement_rating: f64,
+    /// Placed participants, best place first
+    pub standings: Vec<ContestStanding>,
+}
+
+/// One participant's finish in a contest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContestStanding {
+    pub player_id: String,
+    pub handle: String,
+    pub place: i32,
+    pub result: String,
+}
+
+/// The contest where a winner beat the most highly rated opponent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BiggestUpset {
+    pub contest_id: String,
+    pub contest_name: String,
+    pub start: DateTime<FixedOffset>,
+    /// Lowest-rated player placed first
+    pub winner: UpsetPlayer,
+    /// Highest-rated player the winner finished ahead of
+    pub favourite: UpsetPlayer,
+    /// `favourite.rating - winner.rating`, always positive
+    pub rating_gap: f64,
+}
+
+/// A player in an upset, with the global rating they went in with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpsetPlayer {
+    pub player_id: String,
+    pub handle: String,
+    pub place: i32,
+    pub rating: f64,
+}
+
 /// Platform-wide analytics
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 pub struct PlatformStats {
@@ -270,6 +318,48 @@ pub struct PlayerAchievements {
     pub total_achievements: i32,
     pub unlocked_achievements: i32,
     pub completion_percentage: f64,
+    /// The closest locked achievement of each category, nearest first
+    #[serde(default)]
+    pub next_achievements: Vec<NextAchievement>,
+}
+
+/// A locked achievement and how far the player is from it
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NextAchievement {
+    pub achievement_id: String,
+    pub name: String,
+    pub category: AchievementCategory,
+    pub current_value: i32,
+    pub required_value: i32,
+    /// How much more is needed, e.g. 3 for "3 more wins"
+    pub remaining: i32,
+}
+
+impl NextAchievement {
+    /// Picks the locked achievement closest to unlocking in each category
+    ///
+    /// Sorted by what remains, nearest first; categories with everything
+    /// unlocked are left out.
+    pub fn closest(achievements: &[Achievement]) -> Vec<Self> {
+        let mut next: Vec<Self> = Vec::new();
+        for achievement in achievements.iter().filter(|a| !a.unlocked) {
+            let candidate = Self {
+                achievement_id: achievement.id.clone(),
+                name: achievement.name.clone(),
+                category: achievement.category.clone(),
+                current_value: achievement.current_value,
+                required_value: achievement.required_value,
+                remaining: (achievement.required_value - achievement.current_value).max(0),
+            };
+            match next.iter_mut().find(|n| n.category == candidate.category) {
+                Some(current) if candidate.remaining < current.remaining => *current = candidate,
+                Some(_) => {}
+                None => next.push(candidate),
+            }
+        }
+        next.sort_by_key(|n| n.remaining);
+        next
+    }
 }
 
 /// Player ranking in a specific category
@@ -433,6 +523,48 @@ mod tests {
     use pretty_assertions::assert_eq;
     use test_log::test;
 
+    fn achievement(
+        id: &str,
+        category: AchievementCategory,
+        required: i32,
+        current: i32,
+    ) -> Achievement {
+        Achievement {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            category,
+            required_value: required,
+            current_value: current,
+            unlocked: current >= required,
+            unlocked_at: None,
+        }
+    }
+
+    #[test]
+    fn test_next_achievements_pick_the_closest_locked_one_per_category() {
+        // 47 wins, 24 contests, every game achievement unlocked
+        let achievements = [
+            achievement("first_win", AchievementCategory::Wins, 1, 47),
+            achievement("win_master", AchievementCategory::Wins, 10, 47),
+            achievement("champion", AchievementCategory::Wins, 50, 47),
+            achievement("contestant", AchievementCategory::Contests, 5, 24),
+            achievement("legend", AchievementCategory::Contests, 100, 24),
+            achievement("veteran", AchievementCategory::Contests, 25, 24),
+            achievement("game_explorer", AchievementCategory::Games, 5, 6),
+        ];
+
+        let next = NextAchievement::closest(&achievements);
+        let summary: Vec<(&str, i32)> = next
+            .iter()
+            .map(|n| (n.achievement_id.as_str(), n.remaining))
+            .collect();
+        assert_eq!(summary, [("veteran", 1), ("champion", 3)]);
+        assert_eq!(next[1].category, AchievementCategory::Wins);
+        assert_eq!(next[1].current_value, 47);
+        assert_eq!(next[1].required_value, 50);
+    }
+
     #[test]
     fn test_player_stats_creation() {
         let stats = PlayerStats::new("player/123".to_string());
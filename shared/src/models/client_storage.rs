@@ -36,6 +36,7 @@ pub enum StorageKey {
     VenueData(String),      // venue_id
     PlayerData(String),     // player_id
     SyncMetadata(String),   // player_id
+    DashboardRefreshInterval,
 }
 
 impl StorageKey {
@@ -47,10 +48,58 @@ impl StorageKey {
             StorageKey::VenueData(venue_id) => format!("venue_data_{}", venue_id),
             StorageKey::PlayerData(player_id) => format!("player_data_{}", player_id),
             StorageKey::SyncMetadata(player_id) => format!("sync_metadata_{}", player_id),
+            StorageKey::DashboardRefreshInterval => "dashboard_refresh_interval".to_string(),
         }
     }
 }
 
+/// Auto-refresh interval for the analytics dashboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RefreshInterval {
+    #[default]
+    Off,
+    ThirtySeconds,
+    OneMinute,
+    FiveMinutes,
+}
+
+impl RefreshInterval {
+    pub const ALL: [RefreshInterval; 4] = [
+        RefreshInterval::Off,
+        RefreshInterval::ThirtySeconds,
+        RefreshInterval::OneMinute,
+        RefreshInterval::FiveMinutes,
+    ];
+
+    /// Polling period in milliseconds, or `None` when polling is disabled
+    pub fn as_millis(&self) -> Option<u32> {
+        match self {
+            RefreshInterval::Off => None,
+            RefreshInterval::ThirtySeconds => Some(30_000),
+            RefreshInterval::OneMinute => Some(60_000),
+            RefreshInterval::FiveMinutes => Some(300_000),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RefreshInterval::Off => "Off",
+            RefreshInterval::ThirtySeconds => "30s",
+            RefreshInterval::OneMinute => "1m",
+            RefreshInterval::FiveMinutes => "5m",
+        }
+    }
+
+    /// Parses a value produced by `label`, falling back to `Off`
+    pub fn from_label(label: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|interval| interval.label() == label)
+            .unwrap_or_default()
+    }
+}
+
 /// Metadata about data synchronization
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SyncMetadata {
@@ -544,6 +593,20 @@ mod tests {
         assert_eq!(key.to_string(), "analytics_cache_player123");
     }
 
+    #[test]
+    fn test_refresh_interval_round_trips_labels() {
+        for interval in RefreshInterval::ALL {
+            assert_eq!(RefreshInterval::from_label(interval.label()), interval);
+        }
+        assert_eq!(RefreshInterval::from_label("bogus"), RefreshInterval::Off);
+        assert_eq!(RefreshInterval::Off.as_millis(), None);
+        assert_eq!(RefreshInterval::OneMinute.as_millis(), Some(60_000));
+        assert_eq!(
+            StorageKey::DashboardRefreshInterval.to_string(),
+            "dashboard_refresh_interval"
+        );
+    }
+
     #[test]
     fn test_storage_config_default() {
         let config = StorageConfig::default();
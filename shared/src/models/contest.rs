@@ -33,6 +33,30 @@ pub struct Contest {
 
     /// When this contest was created (UTC)
     pub created_at: DateTime<FixedOffset>,
+
+    /// Whether the contest counts towards ratings; casual and exhibition
+    /// contests are unrated but still appear in stats
+    #[serde(default = "default_rated")]
+    pub rated: bool,
+
+    /// Who can see the contest in platform-wide analytics; private contests
+    /// still count towards their participants' own stats
+    #[serde(default)]
+    pub visibility: ContestVisibility,
+}
+
+/// Whether a contest appears on platform-wide dashboards
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContestVisibility {
+    #[default]
+    Public,
+    Private,
+}
+
+/// Contests stored before the flag existed were all rated
+pub(crate) fn default_rated() -> bool {
+    true
 }
 
 impl Contest {
@@ -54,6 +78,8 @@ impl Contest {
             name,
             creator_id,
             created_at,
+            rated: true,
+            visibility: ContestVisibility::Public,
         };
         contest.validate_fields()?;
         Ok(contest)
@@ -82,6 +108,8 @@ mod tests {
             stop: DateTime::parse_from_rfc3339("2023-07-15T16:00:00Z").unwrap(),
             creator_id: "player/test-creator".to_string(),
             created_at: DateTime::parse_from_rfc3339("2023-07-15T10:00:00Z").unwrap(),
+            rated: true,
+            visibility: ContestVisibility::Public,
         }
     }
 
@@ -152,6 +180,8 @@ mod tests {
             stop: DateTime::parse_from_rfc3339("2023-07-15T16:00:00-05:00").unwrap(),
             creator_id: "player/test-creator".to_string(),
             created_at: DateTime::parse_from_rfc3339("2023-07-15T10:00:00Z").unwrap(),
+            rated: true,
+            visibility: ContestVisibility::Public,
         };
         assert!(contest.validate().is_ok());
     }
@@ -258,4 +288,32 @@ mod tests {
         assert_eq!(contest.creator_id, deserialized.creator_id);
         assert_eq!(contest.created_at, deserialized.created_at);
     }
+
+    #[test]
+    fn test_contest_rated_defaults_to_true() {
+        let mut value = serde_json::to_value(create_test_contest()).unwrap();
+        value.as_object_mut().unwrap().remove("rated");
+        let contest: Contest = serde_json::from_value(value).unwrap();
+        assert!(contest.rated);
+
+        let mut unrated = create_test_contest();
+        unrated.rated = false;
+        let json = serde_json::to_string(&unrated).unwrap();
+        assert!(!serde_json::from_str::<Contest>(&json).unwrap().rated);
+    }
+
+    #[test]
+    fn test_contest_visibility_defaults_to_public() {
+        let mut value = serde_json::to_value(create_test_contest()).unwrap();
+        value.as_object_mut().unwrap().remove("visibility");
+        let contest: Contest = serde_json::from_value(value).unwrap();
+        assert_eq!(contest.visibility, ContestVisibility::Public);
+
+        let mut private = create_test_contest();
+        private.visibility = ContestVisibility::Private;
+        let value = serde_json::to_value(&private).unwrap();
+        assert_eq!(value["visibility"], "private");
+        let contest: Contest = serde_json::from_value(value).unwrap();
+        assert_eq!(contest.visibility, ContestVisibility::Private);
+    }
 }
@@ -25,6 +25,11 @@ pub struct Player {
     #[validate(length(min = 1, max = 100))]
     pub firstname: String,
 
+    /// Player's last name
+    #[serde(default)]
+    #[validate(length(min = 1, max = 100))]
+    pub lastname: Option<String>,
+
     /// Player's handle/username
     #[validate(length(min = 3, max = 50))]
     #[validate(regex = "HANDLE_REGEX")]
@@ -45,6 +50,22 @@ pub struct Player {
     /// Whether the player has administrative privileges
     #[serde(rename = "isAdmin")]
     pub is_admin: bool,
+
+    /// When the player last logged in successfully
+    #[serde(
+        rename = "lastLoginAt",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub last_login_at: Option<DateTime<FixedOffset>>,
+
+    /// Address the player last logged in from
+    #[serde(
+        rename = "lastLoginIp",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub last_login_ip: Option<String>,
 }
 
 impl Player {
@@ -63,11 +84,14 @@ impl Player {
             id,
             rev,
             firstname,
+            lastname: None,
             handle,
             email,
             password,
             created_at,
             is_admin,
+            last_login_at: None,
+            last_login_ip: None,
         };
         player.validate_fields()?;
         Ok(player)
@@ -86,11 +110,14 @@ impl Player {
             id: String::new(),  // Will be set by ArangoDB
             rev: String::new(), // Will be set by ArangoDB
             firstname,
+            lastname: None,
             handle,
             email,
             password,
             created_at,
             is_admin,
+            last_login_at: None,
+            last_login_ip: None,
         };
         player.validate_fields()?;
         Ok(player)
@@ -181,11 +208,14 @@ mod tests {
             id: "player/1".to_string(),
             rev: "1".to_string(),
             firstname: "John".to_string(),
+            lastname: None,
             handle: "john_doe".to_string(),
             email: "john@example.com".to_string(),
             password: "hashed_password".to_string(),
             created_at: chrono::Utc::now().fixed_offset(),
             is_admin: false,
+            last_login_at: None,
+            last_login_ip: None,
         }
     }
 
@@ -287,11 +317,14 @@ mod tests {
             id: "player/1".to_string(),
             rev: "1".to_string(),
             firstname: "Test".to_string(),
+            lastname: None,
             handle: "test_user_123".to_string(),
             email: SafeEmail(EN).fake(),
             password: "hashed_password".to_string(),
             created_at: chrono::Utc::now().fixed_offset(),
             is_admin: false,
+            last_login_at: None,
+            last_login_ip: None,
         };
         assert!(player.validate().is_ok());
     }
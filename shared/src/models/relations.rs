@@ -89,6 +89,10 @@ pub struct ResultedIn {
     /// Result description (e.g., "won", "lost")
     #[validate(length(min = 1))]
     pub result: String,
+
+    /// Name of a guest participant, set when `to` is not a player document
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guest_name: Option<String>,
 }
 
 impl PlayedAt {
@@ -153,6 +157,7 @@ impl ResultedIn {
             label: "RESULTED_IN".to_string(),
             place,
             result,
+            guest_name: None,
         };
         relation.validate_fields()?;
         Ok(relation)
@@ -201,6 +206,7 @@ mod tests {
             label: "RESULTED_IN".to_string(),
             place: 1,
             result: "won".to_string(),
+            guest_name: None,
         }
     }
 
@@ -478,6 +484,7 @@ mod tests {
             label: "RESULTED_IN".to_string(),
             place: 1,
             result: "won (tie-breaker)".to_string(),
+            guest_name: None,
         };
         assert!(resulted_in.validate().is_ok());
     }
@@ -492,6 +499,7 @@ mod tests {
             label: "RESULTED_IN".to_string(),
             place: 999, // Large but valid place
             result: "participated".to_string(),
+            guest_name: None,
         };
         assert!(relation.validate().is_ok());
     }
@@ -0,0 +1,497 @@
+//! Validation rules shared by the backend handlers and the frontend forms.
+//!
+//! Everything here is pure so it compiles for both the server and wasm, and
+//! both sides report the same field names and error codes.
+
+use crate::dto::contest::{ContestDto, OutcomeDto};
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+
+pub const PASSWORD_MIN_LENGTH: usize = 8;
+pub const PASSWORD_MAX_LENGTH: usize = 128;
+pub const EMAIL_MAX_LENGTH: usize = 254;
+
+/// A single failed rule, tied to the field it applies to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldError {
+    pub field: String,
+    /// Stable machine-readable code, e.g. `too_short`
+    pub code: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: impl Into<String>, code: &str, message: impl Into<String>) -> Self {
+        Self {
+            field: field.into(),
+            code: code.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// Joins several errors into one human-readable line
+pub fn describe(errors: &[FieldError]) -> String {
+    errors
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+pub fn validate_email(email: &str) -> Result<(), FieldError> {
+    let email = email.trim();
+    if email.is_empty() {
+        return Err(FieldError::new("email", "required", "Email is required"));
+    }
+    if email.len() > EMAIL_MAX_LENGTH {
+        return Err(FieldError::new(
+            "email",
+            "too_long",
+            format!("Email must be at most {} characters", EMAIL_MAX_LENGTH),
+        ));
+    }
+
+    let well_formed = match email.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && !email.chars().any(char::is_whitespace)
+                && domain
+                    .split_once('.')
+                    .is_some_and(|(host, tld)| !host.is_empty() && !tld.is_empty())
+                && !domain.ends_with('.')
+        }
+        None => false,
+    };
+    if !well_formed {
+        return Err(FieldError::new(
+            "email",
+            "invalid_format",
+            "Email must look like name@example.com",
+        ));
+    }
+    Ok(())
+}
+
+pub fn validate_password(password: &str) -> Result<(), FieldError> {
+    let length = password.chars().count();
+    if length < PASSWORD_MIN_LENGTH {
+        return Err(FieldError::new(
+            "password",
+            "too_short",
+            format!(
+                "Password must be at least {} characters",
+                PASSWORD_MIN_LENGTH
+            ),
+        ));
+    }
+    if length > PASSWORD_MAX_LENGTH {
+        return Err(FieldError::new(
+            "password",
+            "too_long",
+            format!(
+                "Password must be at most {} characters",
+                PASSWORD_MAX_LENGTH
+            ),
+        ));
+    }
+    Ok(())
+}
+
+pub fn validate_schedule(
+    start: DateTime<FixedOffset>,
+    stop: DateTime<FixedOffset>,
+) -> Result<(), FieldError> {
+    if stop <= start {
+        return Err(FieldError::new(
+            "stop",
+            "stop_before_start",
+            "Contest must end after it starts",
+        ));
+    }
+    Ok(())
+}
+
+/// Checks placements, guest names and that no player is listed twice
+///
+/// Places need not be distinct: tied players share a place.
+pub fn validate_outcomes(outcomes: &[OutcomeDto]) -> Result<(), Vec<FieldError>> {
+    let mut errors = Vec::new();
+    let mut seen = HashSet::new();
+    for (i, outcome) in outcomes.iter().enumerate() {
+        if !outcome
+            .place
+            .trim()
+            .parse::<u32>()
+            .is_ok_and(|place| place > 0)
+        {
+            errors.push(FieldError::new(
+                format!("outcomes[{}].place", i),
+                "invalid_place",
+                "Place must be a positive whole number",
+            ));
+        }
+
+        // Guests are keyed by name, existing players by id, new ones by email
+        let identity = if outcome.guest {
+            if outcome.handle.trim().is_empty() {
+                errors.push(FieldError::new(
+                    format!("outcomes[{}].handle", i),
+                    "missing_guest_name",
+                    "A guest needs a name",
+                ));
+            }
+            format!("guest:{}", outcome.handle.trim().to_lowercase())
+        } else if !outcome.player_id.is_empty() {
+            outcome.player_id.clone()
+        } else {
+            outcome.email.trim().to_lowercase()
+        };
+        if !identity.is_empty() && !seen.insert(identity) {
+            errors.push(FieldError::new(
+                format!("outcomes[{}]", i),
+                "duplicate_participant",
+                "A player can only appear once per contest",
+            ));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Checks that the places form a ranking of the participants
+///
+/// Tied players share a place and the places they would have taken are
+/// skipped, so four players may finish 1, 2, 2, 4 but not 1, 2, 2, 3 or
+/// 1, 2, 2, 5. Places that are not valid are left to `validate_outcomes`.
+pub fn validate_ranking(outcomes: &[OutcomeDto]) -> Result<(), FieldError> {
+    let mut places: Vec<u32> = outcomes
+        .iter()
+        .filter_map(|o| o.place.trim().parse().ok())
+        .filter(|&place| place > 0)
+        .collect();
+    places.sort_unstable();
+
+    let mut i = 0;
+    while i < places.len() {
+        let place = places[i];
+        // Everyone already ranked finished ahead of this place
+        let expected = i as u32 + 1;
+        if place > expected {
+            return Err(FieldError::new(
+                "outcomes",
+                "ranking_gap",
+                format!(
+                    "Place {} is missing: {} of {} participants are placed ahead of place {}",
+                    expected,
+                    i,
+                    places.len(),
+                    place
+                ),
+            ));
+        }
+        if place < expected {
+            return Err(FieldError::new(
+                "outcomes",
+                "ranking_duplicate",
+                format!(
+                    "Place {} is already taken by a tie: {} participants are placed ahead of it, so it should be place {}",
+                    place,
+                    i,
+                    expected
+                ),
+            ));
+        }
+        i += places[i..].iter().take_while(|&&p| p == place).count();
+    }
+    Ok(())
+}
+
+/// Checks a contest before it is submitted or stored, collecting every failure
+pub fn validate_contest(contest: &ContestDto) -> Result<(), Vec<FieldError>> {
+    let mut errors = Vec::new();
+
+    if let Err(e) = validate_schedule(contest.start, contest.stop) {
+        errors.push(e);
+    }
+    if contest.venue.display_name.trim().is_empty() {
+        errors.push(FieldError::new("venue", "required", "A venue is required"));
+    }
+    if let Err(outcome_errors) = validate_outcomes(&contest.outcomes) {
+        errors.extend(outcome_errors);
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dto::venue::VenueDto;
+    use crate::models::contest::ContestVisibility;
+    use crate::models::venue::VenueSource;
+    use chrono::{Duration, TimeZone, Utc};
+
+    fn contest() -> ContestDto {
+        let start = Utc
+            .with_ymd_and_hms(2024, 5, 1, 18, 0, 0)
+            .unwrap()
+            .fixed_offset();
+        ContestDto {
+            id: String::new(),
+            name: "Friday Night".to_string(),
+            start,
+            stop: start + Duration::hours(2),
+            venue: VenueDto {
+                id: String::new(),
+                display_name: "Game Cafe".to_string(),
+                formatted_address: "1 Main St".to_string(),
+                place_id: "place".to_string(),
+                lat: 0.0,
+                lng: 0.0,
+                timezone: "UTC".to_string(),
+                source: VenueSource::Database,
+            },
+            games: vec![],
+            outcomes: vec![outcome("player/1", "1"), outcome("player/2", "2")],
+            creator_id: String::new(),
+            created_at: None,
+            rated: true,
+            visibility: ContestVisibility::Public,
+        }
+    }
+
+    fn outcome(player_id: &str, place: &str) -> OutcomeDto {
+        OutcomeDto {
+            player_id: player_id.to_string(),
+            place: place.to_string(),
+            result: "won".to_string(),
+            email: String::new(),
+            handle: String::new(),
+            guest: false,
+        }
+    }
+
+    fn codes(errors: Vec<FieldError>) -> Vec<String> {
+        errors.into_iter().map(|e| e.code).collect()
+    }
+
+    #[test]
+    fn test_validate_email() {
+        assert!(validate_email("player@example.com").is_ok());
+        assert!(validate_email("  player@example.com ").is_ok());
+        assert_eq!(validate_email("").unwrap_err().code, "required");
+        assert_eq!(validate_email("   ").unwrap_err().code, "required");
+        for bad in [
+            "player",
+            "@example.com",
+            "player@",
+            "player@example",
+            "player@@example.com",
+            "pla yer@example.com",
+            "player@example.",
+            "player@.com",
+        ] {
+            assert_eq!(
+                validate_email(bad).unwrap_err().code,
+                "invalid_format",
+                "{}",
+                bad
+            );
+        }
+        let long = format!("{}@example.com", "a".repeat(EMAIL_MAX_LENGTH));
+        assert_eq!(validate_email(&long).unwrap_err().code, "too_long");
+    }
+
+    #[test]
+    fn test_validate_password() {
+        assert!(validate_password("12345678").is_ok());
+        assert_eq!(validate_password("1234567").unwrap_err().code, "too_short");
+        assert_eq!(validate_password("").unwrap_err().code, "too_short");
+        // Length counts characters, not bytes
+        assert_eq!(validate_password("ééééééé").unwrap_err().code, "too_short");
+        assert!(validate_password(&"x".repeat(PASSWORD_MAX_LENGTH)).is_ok());
+        assert_eq!(
+            validate_password(&"x".repeat(PASSWORD_MAX_LENGTH + 1))
+                .unwrap_err()
+                .code,
+            "too_long"
+        );
+        assert_eq!(validate_password("short").unwrap_err().field, "password");
+    }
+
+    #[test]
+    fn test_validate_contest_accepts_valid_contest() {
+        assert!(validate_contest(&contest()).is_ok());
+
+        let mut no_outcomes = contest();
+        no_outcomes.outcomes.clear();
+        assert!(validate_contest(&no_outcomes).is_ok());
+    }
+
+    #[test]
+    fn test_validate_contest_dates() {
+        let mut c = contest();
+        c.stop = c.start;
+        assert_eq!(
+            codes(validate_contest(&c).unwrap_err()),
+            ["stop_before_start"]
+        );
+
+        c.stop = c.start - Duration::minutes(1);
+        let errors = validate_contest(&c).unwrap_err();
+        assert_eq!(errors[0].field, "stop");
+    }
+
+    #[test]
+    fn test_validate_contest_requires_venue() {
+        let mut c = contest();
+        c.venue.display_name = "  ".to_string();
+        assert_eq!(codes(validate_contest(&c).unwrap_err()), ["required"]);
+    }
+
+    #[test]
+    fn test_validate_contest_places() {
+        let mut c = contest();
+        c.outcomes[1].place = "0".to_string();
+        c.outcomes.push(outcome("player/3", "third"));
+        let errors = validate_contest(&c).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].field, "outcomes[1].place");
+        assert_eq!(errors[1].field, "outcomes[2].place");
+        assert!(errors.iter().all(|e| e.code == "invalid_place"));
+    }
+
+    #[test]
+    fn test_validate_contest_allows_tied_places() {
+        let mut c = contest();
+        c.outcomes[1].place = "1".to_string();
+        c.outcomes.push(outcome("player/3", "3"));
+        assert!(validate_contest(&c).is_ok());
+    }
+
+    fn ranking(places: &[&str]) -> Vec<OutcomeDto> {
+        places
+            .iter()
+            .enumerate()
+            .map(|(i, place)| outcome(&format!("player/{}", i), place))
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_ranking_accepts_rankings_with_ties() {
+        assert!(validate_ranking(&[]).is_ok());
+        assert!(validate_ranking(&ranking(&["1", "2", "3", "4"])).is_ok());
+        assert!(validate_ranking(&ranking(&["1", "2", "2", "4"])).is_ok());
+        assert!(validate_ranking(&ranking(&["1", "1", "1", "4"])).is_ok());
+        // Order of the outcomes does not matter
+        assert!(validate_ranking(&ranking(&["4", "2", "1", "2"])).is_ok());
+    }
+
+    #[test]
+    fn test_validate_ranking_rejects_gaps() {
+        let error = validate_ranking(&ranking(&["1", "2", "2", "5"])).unwrap_err();
+        assert_eq!(error.code, "ranking_gap");
+        assert_eq!(error.field, "outcomes");
+        assert!(error.message.starts_with("Place 4 is missing"));
+
+        let error = validate_ranking(&ranking(&["2", "3"])).unwrap_err();
+        assert_eq!(error.code, "ranking_gap");
+        assert!(error.message.starts_with("Place 1 is missing"));
+    }
+
+    #[test]
+    fn test_validate_ranking_rejects_duplicates_a_tie_cannot_explain() {
+        let error = validate_ranking(&ranking(&["1", "2", "2", "3"])).unwrap_err();
+        assert_eq!(error.code, "ranking_duplicate");
+        assert!(error.message.starts_with("Place 3 is already taken"));
+
+        let error = validate_ranking(&ranking(&["1", "1", "2"])).unwrap_err();
+        assert_eq!(error.code, "ranking_duplicate");
+        assert!(error.message.ends_with("it should be place 3"));
+    }
+
+    #[test]
+    fn test_validate_contest_duplicate_participants() {
+        let mut c = contest();
+        c.outcomes.push(outcome("player/1", "3"));
+        let errors = validate_contest(&c).unwrap_err();
+        assert_eq!(codes(errors.clone()), ["duplicate_participant"]);
+        assert_eq!(errors[0].field, "outcomes[2]");
+
+        // New players are matched by email, case-insensitively
+        let mut c = contest();
+        let mut new_a = outcome("", "3");
+        new_a.email = "new@example.com".to_string();
+        let mut new_b = outcome("", "4");
+        new_b.email = "NEW@example.com".to_string();
+        c.outcomes.extend([new_a, new_b]);
+        assert_eq!(
+            codes(validate_contest(&c).unwrap_err()),
+            ["duplicate_participant"]
+        );
+    }
+
+    #[test]
+    fn test_validate_contest_guests() {
+        let guest = |name: &str, place: &str| {
+            let mut o = outcome("", place);
+            o.guest = true;
+            o.handle = name.to_string();
+            o
+        };
+
+        let mut c = contest();
+        c.outcomes
+            .extend([guest("Aunt May", "3"), guest("Uncle Ben", "4")]);
+        assert!(validate_contest(&c).is_ok());
+
+        // Guests are matched by name, case-insensitively
+        let mut c = contest();
+        c.outcomes
+            .extend([guest("Aunt May", "3"), guest("aunt may", "4")]);
+        assert_eq!(
+            codes(validate_contest(&c).unwrap_err()),
+            ["duplicate_participant"]
+        );
+
+        let mut c = contest();
+        c.outcomes.push(guest(" ", "3"));
+        let errors = validate_contest(&c).unwrap_err();
+        assert_eq!(codes(errors.clone()), ["missing_guest_name"]);
+        assert_eq!(errors[0].field, "outcomes[2].handle");
+    }
+
+    #[test]
+    fn test_describe_joins_errors() {
+        let errors = vec![
+            FieldError::new(
+                "stop",
+                "stop_before_start",
+                "Contest must end after it starts",
+            ),
+            FieldError::new("venue", "required", "A venue is required"),
+        ];
+        assert_eq!(
+            describe(&errors),
+            "stop: Contest must end after it starts; venue: A venue is required"
+        );
+    }
+}
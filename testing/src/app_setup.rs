@@ -7,6 +7,7 @@ use actix_web::web;
 use anyhow::{Context, Result};
 use arangors::client::reqwest::ReqwestClient;
 use arangors::{Connection, Database};
+use backend::analytics::AnalyticsCache;
 use backend::player::session::RedisSessionStore;
 use reqwest::Client;
 use std::sync::Arc;
@@ -21,8 +22,21 @@ pub struct TestAppData {
     pub venue_repo: web::Data<backend::venue::repository::VenueRepositoryImpl>,
     pub game_repo: web::Data<backend::game::repository::GameRepositoryImpl>,
     pub contest_repo: web::Data<backend::contest::repository::ContestRepositoryImpl>,
+    pub api_key_repo: web::Data<backend::player::api_key::ApiKeyRepository>,
     pub session_store: web::Data<RedisSessionStore>,
     pub redis_arc: Arc<redis::Client>,
+    /// Cache to hand to analytics routes or use cases under test
+    pub analytics_cache: web::Data<AnalyticsCache>,
+}
+
+impl TestAppData {
+    /// Flush analytics cache entries, as `POST /api/admin/cache/clear` does
+    ///
+    /// Call this between steps that change data and then assert analytics
+    /// numbers, so the assertions see freshly computed values.
+    pub async fn flush_analytics_cache(&self) -> usize {
+        self.analytics_cache.clear_analytics().await
+    }
 }
 
 /// Set up test application data with testcontainers
@@ -59,11 +73,18 @@ pub async fn setup_test_app_data(env: &TestEnvironment) -> Result<TestAppData> {
     }
     let conn = conn.expect("Connection should be established");
 
-    // Use _system database for tests (it always exists)
-    let db: Database<ReqwestClient> = conn
-        .db("_system")
-        .await
-        .context("Failed to access _system database")?;
+    // Use _system database for tests (it always exists), unless the
+    // environment shares its containers and has a database of its own
+    let db_name = env.app_db_name();
+    let db: Database<ReqwestClient> = match conn.db(&db_name).await {
+        Ok(db) => db,
+        Err(_) => {
+            log::info!("Creating database: {}", db_name);
+            conn.create_database(&db_name)
+                .await
+                .with_context(|| format!("Failed to create database {}", db_name))?
+        }
+    };
 
     // Create collections if they don't exist
     // This is a minimal set for player tests - add more as needed
@@ -74,6 +95,8 @@ pub async fn setup_test_app_data(env: &TestEnvironment) -> Result<TestAppData> {
         "contest",
         "player_contests",
         "player_performance",
+        "api_key",
+        "contest_archive",
     ];
     for collection_name in collections {
         match db.collection(&collection_name).await {
@@ -99,7 +122,6 @@ pub async fn setup_test_app_data(env: &TestEnvironment) -> Result<TestAppData> {
 
     // Get the database connection URL for HTTP requests
     let base_url = env.arangodb_url();
-    let db_name = "_system";
 
     for edge_name in edge_collections {
         match db.collection(&edge_name).await {
@@ -180,6 +202,7 @@ pub async fn setup_test_app_data(env: &TestEnvironment) -> Result<TestAppData> {
             api_url: std::env::var("BGG_API_URL")
                 .unwrap_or_else(|_| "https://boardgamegeek.com/xmlapi2".to_string()),
             api_token: std::env::var("BGG_API_TOKEN").ok(),
+            paths: Default::default(),
         });
         log::info!("Using real BGG API for testing (small scenarios only)");
         web::Data::new(backend::game::repository::GameRepositoryImpl::new_with_bgg(
@@ -201,16 +224,22 @@ pub async fn setup_test_app_data(env: &TestEnvironment) -> Result<TestAppData> {
         ),
     );
 
+    let api_key_repo = web::Data::new(backend::player::api_key::ApiKeyRepository::new(db.clone()));
+
     let redis_arc = Arc::new(redis_data.get_ref().clone());
 
+    let analytics_cache = web::Data::new(AnalyticsCache::new_default());
+
     Ok(TestAppData {
         redis_data,
         player_repo,
         venue_repo,
         game_repo,
         contest_repo,
+        api_key_repo,
         session_store,
         redis_arc,
+        analytics_cache,
     })
 }
 
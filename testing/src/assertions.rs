@@ -0,0 +1,68 @@
+//! Assertions on database contents for integration tests
+//!
+//! These connect to the environment's ArangoDB and panic with a description of
+//! what was actually stored when an expectation does not hold.
+
+use anyhow::{Context, Result};
+use arangors::AqlQuery;
+use serde_json::Value;
+
+use super::TestEnvironment;
+
+/// Assert that `collection` holds exactly `expected` documents
+pub async fn assert_collection_count(
+    env: &TestEnvironment,
+    collection: &str,
+    expected: usize,
+) -> Result<()> {
+    let db = env.database().await?;
+    let query = AqlQuery::builder()
+        .query("RETURN LENGTH(@@collection)")
+        .bind_var("@collection", collection)
+        .build();
+    let counts: Vec<usize> = db
+        .aql_query(query)
+        .await
+        .with_context(|| format!("Failed to count documents in '{}'", collection))?;
+    let actual = counts.first().copied().unwrap_or(0);
+
+    assert_eq!(
+        actual,
+        expected,
+        "Expected {} document(s) in collection '{}' of database '{}', found {}",
+        expected,
+        collection,
+        db.name(),
+        actual
+    );
+    Ok(())
+}
+
+/// Assert that a player with `handle` exists, returning their `_id`
+pub async fn assert_player_exists(env: &TestEnvironment, handle: &str) -> Result<String> {
+    let db = env.database().await?;
+    let query = AqlQuery::builder()
+        .query(
+            r#"
+            LET matches = (FOR p IN player FILTER p.handle == @handle LIMIT 1 RETURN p._id)
+            RETURN { id: FIRST(matches), total: LENGTH(player) }
+            "#,
+        )
+        .bind_var("handle", handle)
+        .build();
+    let results: Vec<Value> = db
+        .aql_query(query)
+        .await
+        .with_context(|| format!("Failed to look up player '{}'", handle))?;
+    let result = results.first().cloned().unwrap_or(Value::Null);
+
+    match result["id"].as_str() {
+        Some(id) => Ok(id.to_string()),
+        None => panic!(
+            "Expected a player with handle '{}' in database '{}', found none among {} player(s)",
+            handle,
+            db.name(),
+            result["total"].as_u64().unwrap_or(0)
+        ),
+    }
+}
@@ -7,28 +7,58 @@
 //! automatically stopped/removed when it goes out of scope (RAII pattern).
 
 use anyhow::{Context, Result};
-use arangors::ClientError;
-use std::path::Path;
+use arangors::client::reqwest::ReqwestClient;
+use arangors::{ClientError, Connection, Database};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::{Arc, Mutex, Weak};
 use std::time::Duration;
 use testcontainers::{
-    core::IntoContainerPort, runners::AsyncRunner, ContainerAsync, GenericImage, ImageExt,
+    core::{ContainerPort, IntoContainerPort},
+    runners::AsyncRunner,
+    ContainerAsync, GenericImage, ImageExt,
 };
 
+/// How long a freshly started container has to publish its port and answer
+const CONTAINER_READY_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Pause between readiness checks on a starting container
+const CONTAINER_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 /// Test environment with ArangoDB and Redis containers
 ///
 /// Containers are automatically managed - they start when created and
 /// stop/remove when dropped (ephemeral containers).
 pub struct TestEnvironment {
+    arangodb_db_name: Mutex<String>,
+    /// Whether `setup_test_app_data` seeds `arangodb_db_name` instead of `_system`
+    isolated_database: bool,
+    /// Leave the containers running if the test panics, for debugging
+    keep_containers_on_failure: bool,
+    /// Handle returned by [`Self::database`], with the name it was opened for
+    database: tokio::sync::Mutex<Option<(String, Database<ReqwestClient>)>>,
+    // Keep containers alive for the lifetime of TestEnvironment
+    // When the last environment holding them is dropped, containers are
+    // automatically stopped and removed
+    containers: Arc<Containers>,
+}
+
+/// The ArangoDB and Redis containers behind one or more [`TestEnvironment`]s
+struct Containers {
     arangodb_url: String,
     redis_url: String,
-    arangodb_db_name: std::cell::RefCell<String>,
-    // Keep containers alive for the lifetime of TestEnvironment
-    // When dropped, containers are automatically stopped and removed
-    _arangodb: ContainerAsync<GenericImage>,
-    _redis: ContainerAsync<GenericImage>,
+    arangodb: ContainerAsync<GenericImage>,
+    redis: ContainerAsync<GenericImage>,
 }
 
+/// Containers handed out by [`TestEnvironment::shared`]
+///
+/// Only a weak reference is kept here so the containers are removed once the
+/// last test using them finishes; the next caller then starts a fresh pair.
+static SHARED_CONTAINERS: tokio::sync::Mutex<Weak<Containers>> =
+    tokio::sync::Mutex::const_new(Weak::new());
+
 impl TestEnvironment {
     /// Create a new test environment with ArangoDB and Redis containers
     ///
@@ -59,48 +89,41 @@ impl TestEnvironment {
                     .start()
                     .await
                 {
-                    Ok(container) => {
-                        // Verify container is actually running before proceeding
-                        // Sometimes the container starts but immediately exits
-                        tokio::time::sleep(Duration::from_millis(3000)).await;
-
-                        // Try to get the port - if this fails, container might not be ready
-                        match container.get_host_port_ipv4(8529.tcp()).await {
-                            Ok(port) => {
+                    Ok(container) => match wait_for_arangodb(&container).await {
+                        Ok(port) => {
+                            log::debug!(
+                                "ArangoDB container started successfully on attempt {} (port {})",
+                                attempt + 1,
+                                port
+                            );
+                            container_result = Some(Ok(container));
+                            break;
+                        }
+                        Err(e) => {
+                            log::warn!(
+                                "ArangoDB container started but did not become ready (attempt {}): {:?}",
+                                attempt + 1,
+                                e
+                            );
+                            if attempt < 4 {
+                                // Retry by continuing the loop with exponential backoff
+                                let backoff_ms = 2000 * (attempt + 1) as u64;
                                 log::debug!(
-                                    "ArangoDB container started successfully on attempt {} (port {})",
-                                    attempt + 1,
-                                    port
+                                    "Retrying ArangoDB container start in {}ms...",
+                                    backoff_ms
                                 );
-                                container_result = Some(Ok(container));
-                                break;
-                            }
-                            Err(e) => {
-                                log::warn!(
-                                    "ArangoDB container started but port not available (attempt {}): {:?}",
+                                tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
+                                // Don't set container_result, let it retry
+                            } else {
+                                container_result = Some(Err(anyhow::anyhow!(
+                                    "ArangoDB container not ready after {} attempts: {:?}",
                                     attempt + 1,
                                     e
-                                );
-                                if attempt < 4 {
-                                    // Retry by continuing the loop with exponential backoff
-                                    let backoff_ms = 2000 * (attempt + 1) as u64;
-                                    log::debug!(
-                                        "Retrying ArangoDB container start in {}ms...",
-                                        backoff_ms
-                                    );
-                                    tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
-                                    // Don't set container_result, let it retry
-                                } else {
-                                    container_result = Some(Err(anyhow::anyhow!(
-                                        "ArangoDB container port not available after {} attempts: {:?}",
-                                        attempt + 1,
-                                        e
-                                    )));
-                                    break;
-                                }
+                                )));
+                                break;
                             }
                         }
-                    }
+                    },
                     Err(e) => {
                         if attempt < 4 {
                             log::warn!(
@@ -137,12 +160,8 @@ impl TestEnvironment {
             for attempt in 0..5 {
                 match GenericImage::new("redis", "7-alpine").start().await {
                     Ok(container) => {
-                        // Give it more time to bind ports and start services
-                        // Increased for parallel execution where containers compete for resources
-                        tokio::time::sleep(Duration::from_millis(3000)).await;
-
-                        // Verify port is available (similar to ArangoDB check)
-                        match container.get_host_port_ipv4(6379.tcp()).await {
+                        let deadline = tokio::time::Instant::now() + CONTAINER_READY_TIMEOUT;
+                        match wait_for_host_port(&container, 6379.tcp(), deadline).await {
                             Ok(_) => {
                                 log::debug!(
                                     "Redis container started successfully on attempt {}",
@@ -163,12 +182,7 @@ impl TestEnvironment {
                                     ))
                                     .await;
                                 } else {
-                                    return Err(anyhow::anyhow!(
-                                        "Redis container port not available after {} attempts: {:?}",
-                                        attempt + 1,
-                                        e
-                                    ))
-                                    .context("Failed to start Redis container");
+                                    return Err(e).context("Failed to start Redis container");
                                 }
                             }
                         }
@@ -204,13 +218,12 @@ impl TestEnvironment {
         log::info!("Started ArangoDB container at {}", arangodb_url);
         log::info!("Started Redis container at {}", redis_url);
 
-        Ok(Self {
+        Ok(Self::with_containers(Containers {
             arangodb_url,
             redis_url,
-            arangodb_db_name: std::cell::RefCell::new("smacktalk".to_string()),
-            _arangodb: arangodb,
-            _redis: redis,
-        })
+            arangodb,
+            redis,
+        }))
     }
 
     /// Create test environment from environment variables (fallback mode)
@@ -240,23 +253,79 @@ impl TestEnvironment {
             .await
             .context("Failed to create dummy Redis container (Docker may not be available)")?;
 
-        Ok(Self {
+        Ok(Self::with_containers(Containers {
             arangodb_url,
             redis_url,
-            arangodb_db_name: std::cell::RefCell::new("smacktalk".to_string()),
-            _arangodb: dummy_arangodb,
-            _redis: dummy_redis,
+            arangodb: dummy_arangodb,
+            redis: dummy_redis,
+        }))
+    }
+
+    fn with_containers(containers: Containers) -> Self {
+        Self {
+            arangodb_db_name: Mutex::new("smacktalk".to_string()),
+            isolated_database: false,
+            keep_containers_on_failure: keep_containers_on_failure_from_env(),
+            database: tokio::sync::Mutex::new(None),
+            containers: Arc::new(containers),
+        }
+    }
+
+    /// Create a test environment on containers shared with other tests
+    ///
+    /// The first caller starts the containers; later callers reuse them for
+    /// as long as any environment returned from here is alive, so a test
+    /// module pays the container startup cost once. Each environment gets its
+    /// own randomly named database, which `setup_test_app_data` seeds instead
+    /// of `_system`, so tests do not see each other's data.
+    ///
+    /// Sharing is safe for tests that reach ArangoDB only through
+    /// `setup_test_app_data`, [`Self::app_db_name`] or the helpers in
+    /// [`assertions`]. Keep using [`Self::new`] for tests that:
+    /// - open `_system` (or another fixed database) directly
+    /// - stop containers, e.g. with [`Self::stop_arangodb`]
+    /// - expect Redis to start out empty, since Redis is shared as well
+    /// - restore data dumps, which locate the ArangoDB container by image
+    pub async fn shared() -> Result<Arc<Self>> {
+        TestEnvironmentBuilder::new()
+            .with_shared_containers()
+            .build()
+            .await
+            .map(Arc::new)
+    }
+
+    /// Reuse the shared containers if they are still alive, or start them
+    async fn with_shared_containers() -> Result<Self> {
+        let mut shared = SHARED_CONTAINERS.lock().await;
+        let containers = match shared.upgrade() {
+            Some(containers) => {
+                log::debug!("Reusing shared containers at {}", containers.arangodb_url);
+                containers
+            }
+            None => {
+                let containers = Arc::clone(&Self::new().await?.containers);
+                *shared = Arc::downgrade(&containers);
+                containers
+            }
+        };
+
+        Ok(Self {
+            arangodb_db_name: Mutex::new(random_database_name()),
+            isolated_database: true,
+            keep_containers_on_failure: keep_containers_on_failure_from_env(),
+            database: tokio::sync::Mutex::new(None),
+            containers,
         })
     }
 
     /// Get ArangoDB connection URL
     pub fn arangodb_url(&self) -> &str {
-        &self.arangodb_url
+        &self.containers.arangodb_url
     }
 
     /// Get Redis connection URL
     pub fn redis_url(&self) -> &str {
-        &self.redis_url
+        &self.containers.redis_url
     }
 
     /// Wait for services to be ready
@@ -289,7 +358,7 @@ impl TestEnvironment {
 
             // Try to connect to ArangoDB to verify it's ready
             match arangors::Connection::establish_basic_auth(
-                &self.arangodb_url,
+                self.arangodb_url(),
                 "root",
                 "test_password",
             )
@@ -473,9 +542,82 @@ impl TestEnvironment {
         Ok(())
     }
 
+    /// Stop the ArangoDB container, e.g. to test behaviour during an outage
+    pub async fn stop_arangodb(&self) -> Result<()> {
+        self.containers
+            .arangodb
+            .stop_with_timeout(Some(0))
+            .await
+            .context("Failed to stop ArangoDB container")
+    }
+
+    /// Get an authenticated handle to the environment's database
+    ///
+    /// This is the database named by [`Self::arangodb_db_name`]. On
+    /// containers of its own, while no database by that name exists (e.g. no
+    /// data dump was loaded), it is `_system`, which `setup_test_app_data`
+    /// seeds; a missing per-test database is an error. Only a handle to the
+    /// named database is cached, until the name changes or a data dump is
+    /// loaded.
+    pub async fn database(&self) -> Result<Database<ReqwestClient>> {
+        let db_name = self.arangodb_db_name();
+        let mut cached = self.database.lock().await;
+        if let Some((cached_name, db)) = cached.as_ref() {
+            if *cached_name == db_name {
+                return Ok(db.clone());
+            }
+        }
+
+        let conn = Connection::establish_basic_auth(self.arangodb_url(), "root", "test_password")
+            .await
+            .context("Failed to connect to ArangoDB")?;
+        match conn.db(&db_name).await {
+            Ok(db) => {
+                *cached = Some((db_name, db.clone()));
+                Ok(db)
+            }
+            Err(_) if !self.isolated_database => {
+                let app_db_name = self.app_db_name();
+                conn.db(&app_db_name)
+                    .await
+                    .with_context(|| format!("Failed to access {} database", app_db_name))
+            }
+            Err(e) => Err(e).with_context(|| format!("Failed to access {} database", db_name)),
+        }
+    }
+
+    /// Open a connection to the environment's Redis
+    pub async fn redis_connection(&self) -> Result<redis::aio::Connection> {
+        redis::Client::open(self.redis_url())
+            .context("Failed to create Redis client")?
+            .get_async_connection()
+            .await
+            .context("Failed to connect to Redis")
+    }
+
+    /// Get the IDs of the ArangoDB and Redis containers, in that order
+    pub fn container_ids(&self) -> Vec<String> {
+        vec![
+            self.containers.arangodb.id().to_string(),
+            self.containers.redis.id().to_string(),
+        ]
+    }
+
     /// Get the database name
     pub fn arangodb_db_name(&self) -> String {
-        self.arangodb_db_name.borrow().clone()
+        self.arangodb_db_name.lock().unwrap().clone()
+    }
+
+    /// Get the database `setup_test_app_data` creates collections in
+    ///
+    /// This is `_system` unless the environment is built on shared
+    /// containers, in which case it is the environment's own database.
+    pub fn app_db_name(&self) -> String {
+        if self.isolated_database {
+            self.arangodb_db_name()
+        } else {
+            "_system".to_string()
+        }
     }
 
     /// Get the container ID for the ArangoDB container
@@ -512,16 +654,24 @@ impl TestEnvironment {
     /// Load a data dump into ArangoDB
     ///
     /// This method:
-    /// 1. Copies the backup file (zip) into the container
-    /// 2. Extracts it inside the container
+    /// 1. Copies the backup into the container
+    /// 2. Extracts it inside the container, unless it is a directory
     /// 3. Finds the database directory (handles nested structures)
     /// 4. Uses arangorestore to restore the data
     ///
+    /// The backup may be a `.zip`, `.tar.gz` or `.tgz` archive, or an
+    /// uncompressed arangodump directory; see [`DumpFormat`].
+    ///
+    /// With `overwrite`, the target database is dropped first and
+    /// arangorestore runs with `--overwrite true`, so the dump replaces
+    /// whatever was loaded before instead of being merged into it.
+    ///
     /// Supports various dump structures:
     /// - `dump.zip` containing `smacktalk/` directory
     /// - `dump.zip` containing database files directly
     /// - Nested structures like `dump.zip` -> `backup/` -> `smacktalk/`
-    pub async fn load_data_dump(&self, dump_path: &str) -> Result<()> {
+    /// - The same layouts as `dump.tar.gz` or as a plain directory
+    pub async fn load_data_dump(&self, dump_path: &str, overwrite: bool) -> Result<()> {
         let dump_path = Path::new(dump_path);
 
         if !dump_path.exists() {
@@ -530,21 +680,24 @@ impl TestEnvironment {
                 dump_path.display()
             ));
         }
+        let format = DumpFormat::detect(dump_path)?;
 
         let container_id = self.arangodb_container_id()?;
         log::info!(
-            "Loading data dump from {} into container {}",
+            "Loading {:?} data dump from {} into container {}",
+            format,
             dump_path.display(),
             container_id
         );
 
-        // Step 1: Copy the backup file into the container
+        // Step 1: Copy the backup into the container. A directory's contents
+        // go straight to where archives are extracted to.
+        let (source, target) = match format.archive_path() {
+            Some(archive) => (dump_path.display().to_string(), archive),
+            None => (format!("{}/.", dump_path.display()), "/tmp/dump"),
+        };
         let copy_output = Command::new("docker")
-            .args(&[
-                "cp",
-                dump_path.to_str().unwrap(),
-                &format!("{}:/tmp/backup.zip", container_id),
-            ])
+            .args(&["cp", &source, &format!("{}:{}", container_id, target)])
             .output()
             .context("Failed to copy backup file into container")?;
 
@@ -553,53 +706,66 @@ impl TestEnvironment {
             return Err(anyhow::anyhow!("Failed to copy backup file: {}", error));
         }
 
-        log::info!("Copied backup file into container");
+        log::info!("Copied backup into container");
 
-        // Step 2: Extract the zip file inside the container
-        let extract_output = Command::new("docker")
-            .args(&[
-                "exec",
-                &container_id,
-                "sh",
-                "-c",
-                "cd /tmp && unzip -q -o backup.zip -d /tmp/dump",
-            ])
-            .output()
-            .context("Failed to extract backup file in container")?;
+        // Step 2: Extract the archive inside the container
+        if let Some(extract_cmd) = format.extract_command() {
+            let extract_output = Command::new("docker")
+                .args(&["exec", &container_id, "sh", "-c", extract_cmd])
+                .output()
+                .context("Failed to extract backup file in container")?;
 
-        if !extract_output.status.success() {
-            let error = String::from_utf8_lossy(&extract_output.stderr);
-            return Err(anyhow::anyhow!("Failed to extract backup file: {}", error));
-        }
+            if !extract_output.status.success() {
+                let error = String::from_utf8_lossy(&extract_output.stderr);
+                return Err(anyhow::anyhow!("Failed to extract backup file: {}", error));
+            }
 
-        log::info!("Extracted backup file in container");
+            log::info!("Extracted backup file in container");
+        }
 
         // Step 3: Find the database directory (handles nested structures)
-        let db_name = self.arangodb_db_name.borrow().clone();
+        let db_name = self.arangodb_db_name();
         let dump_dir = self.find_dump_directory(&container_id, &db_name).await?;
 
+        // Fail early, and clearly, if the dump cannot be restored here
+        let version_output = Command::new("docker")
+            .args(&["exec", &container_id, "arangorestore", "--version"])
+            .output()
+            .context("Failed to run arangorestore --version in the container")?;
+        let restore_version = arangorestore_version(&version_output)?;
+        log::info!("Restoring with arangorestore {}", restore_version);
+        check_dump_encryption(&container_id, &dump_dir)?;
+
         // Step 4: Wait a bit for ArangoDB to be fully ready
         tokio::time::sleep(Duration::from_secs(3)).await;
 
+        if overwrite {
+            self.drop_database_if_exists(&db_name).await?;
+        }
+
         // Step 5: Use arangorestore to restore the data
+        let mut restore_args: Vec<&str> = vec![
+            "exec",
+            &container_id,
+            "arangorestore",
+            "--server.endpoint",
+            "tcp://127.0.0.1:8529",
+            "--server.username",
+            "root",
+            "--server.password",
+            "test_password",
+            "--input-directory",
+            &dump_dir,
+            "--create-database",
+            "true",
+            "--server.database",
+            &db_name,
+        ];
+        if overwrite {
+            restore_args.extend(["--overwrite", "true"]);
+        }
         let restore_output = Command::new("docker")
-            .args(&[
-                "exec",
-                &container_id,
-                "arangorestore",
-                "--server.endpoint",
-                "tcp://127.0.0.1:8529",
-                "--server.username",
-                "root",
-                "--server.password",
-                "test_password",
-                "--input-directory",
-                &dump_dir,
-                "--create-database",
-                "true",
-                "--server.database",
-                &db_name,
-            ])
+            .args(&restore_args)
             .output()
             .context("Failed to restore backup using arangorestore")?;
 
@@ -609,33 +775,59 @@ impl TestEnvironment {
             log::warn!("arangorestore stderr: {}", error);
             log::warn!("arangorestore stdout: {}", stdout);
             return Err(anyhow::anyhow!(
-                "Failed to restore backup. Tried directory: {}. Error: {}",
+                "Failed to restore backup with arangorestore {}. Tried directory: {}. Error: {}",
+                restore_version,
                 dump_dir,
                 error
             ));
         }
 
         log::info!("Successfully restored backup into database '{}'", db_name);
+        // The database may only exist now, or may have been recreated
+        *self.database.lock().await = None;
 
         // Cleanup: Remove the backup files from the container
-        let _ = Command::new("docker")
-            .args(&[
-                "exec",
-                &container_id,
-                "rm",
-                "-rf",
-                "/tmp/backup.zip",
-                "/tmp/dump",
-            ])
-            .output();
+        let mut cleanup_args: Vec<&str> = vec!["exec", &container_id, "rm", "-rf", "/tmp/dump"];
+        cleanup_args.extend(format.archive_path());
+        let _ = Command::new("docker").args(&cleanup_args).output();
+
+        Ok(())
+    }
 
+    /// Drop `db_name` so a restore starts from a clean slate
+    ///
+    /// `_system` cannot be dropped; it is left in place and the restore's
+    /// `--overwrite` replaces the collections the dump contains.
+    async fn drop_database_if_exists(&self, db_name: &str) -> Result<()> {
+        if db_name == "_system" {
+            return Ok(());
+        }
+        let conn = arangors::Connection::establish_basic_auth(
+            self.arangodb_url(),
+            "root",
+            "test_password",
+        )
+        .await
+        .context("Failed to connect to ArangoDB")?;
+        let databases = conn
+            .accessible_databases()
+            .await
+            .context("Failed to list databases")?;
+        if databases.contains_key(db_name) {
+            conn.drop_database(db_name)
+                .await
+                .with_context(|| format!("Failed to drop database '{}'", db_name))?;
+            log::info!("Dropped database '{}' before restoring over it", db_name);
+        }
         Ok(())
     }
 
     /// Find the database directory within the extracted dump
     ///
     /// Handles various dump structures by searching for the database name
-    /// in common locations.
+    /// in common locations. A dump taken from a differently named database
+    /// holds a single directory named after that database instead; it is
+    /// used, and restored into `db_name`, when nothing else matches.
     async fn find_dump_directory(&self, container_id: &str, db_name: &str) -> Result<String> {
         // Try common locations in order
         let candidates = vec![
@@ -646,23 +838,39 @@ impl TestEnvironment {
         ];
 
         for candidate in &candidates {
-            // Check if directory exists and contains ArangoDB files
-            let check_cmd = format!(
-                "test -d {} && (test -f {}/_graphs || test -f {}/_collections || ls {} | grep -q '\\.json$')",
-                candidate, candidate, candidate, candidate
-            );
-
-            let check_output = Command::new("docker")
-                .args(&["exec", container_id, "sh", "-c", &check_cmd])
-                .output()
-                .context("Failed to check dump directory")?;
-
-            if check_output.status.success() {
+            if is_dump_directory(container_id, candidate)? {
                 log::info!("Found database dump at: {}", candidate);
                 return Ok(candidate.clone());
             }
         }
 
+        for root in ["/tmp/dump", "/tmp/dump/backup", "/tmp/dump/dump"] {
+            let list_output = Command::new("docker")
+                .args(&[
+                    "exec",
+                    container_id,
+                    "sh",
+                    "-c",
+                    &format!("find {} -mindepth 1 -maxdepth 1 -type d", root),
+                ])
+                .output()
+                .context("Failed to list dump directories")?;
+            let listing = String::from_utf8_lossy(&list_output.stdout);
+            let children: Vec<&str> = listing.lines().filter(|l| !l.is_empty()).collect();
+            if let [child] = children.as_slice() {
+                if is_dump_directory(container_id, child)? {
+                    let dumped_name = child.rsplit('/').next().unwrap_or(child);
+                    log::warn!(
+                        "Dump holds database '{}' rather than '{}'; restoring it into '{}'",
+                        dumped_name,
+                        db_name,
+                        db_name
+                    );
+                    return Ok(child.to_string());
+                }
+            }
+        }
+
         // If no specific directory found, try listing what we have
         let list_output = Command::new("docker")
             .args(&[
@@ -686,19 +894,333 @@ impl TestEnvironment {
     }
 }
 
+impl Drop for TestEnvironment {
+    /// Leak the containers instead of removing them when a test panics with
+    /// `STG_KEEP_CONTAINERS_ON_FAILURE=true` (or
+    /// [`TestEnvironmentBuilder::with_keep_containers_on_failure`]), so the
+    /// state that caused the failure can be inspected
+    ///
+    /// A test that fails by returning `Err` drops its environment before the
+    /// failure is reported, so only panics (e.g. failed asserts) are noticed.
+    fn drop(&mut self) {
+        if !(self.keep_containers_on_failure && std::thread::panicking()) {
+            return;
+        }
+
+        // An extra strong reference that is never released keeps the
+        // containers from being stopped and removed
+        std::mem::forget(Arc::clone(&self.containers));
+        eprintln!(
+            "Test failed; keeping containers for debugging (remove with `docker rm -f {}`)",
+            self.container_ids().join(" ")
+        );
+        eprintln!(
+            "  ArangoDB: {} (database {}, container {})",
+            self.arangodb_url(),
+            self.arangodb_db_name(),
+            self.containers.arangodb.id()
+        );
+        eprintln!(
+            "  Redis: {} (container {})",
+            self.redis_url(),
+            self.containers.redis.id()
+        );
+    }
+}
+
+/// Read `STG_KEEP_CONTAINERS_ON_FAILURE`, which defaults to off
+fn keep_containers_on_failure_from_env() -> bool {
+    std::env::var("STG_KEEP_CONTAINERS_ON_FAILURE")
+        .ok()
+        .and_then(|value| value.parse::<bool>().ok())
+        .unwrap_or(false)
+}
+
+/// Poll until Docker publishes the container's mapping for `port`
+async fn wait_for_host_port(
+    container: &ContainerAsync<GenericImage>,
+    port: ContainerPort,
+    deadline: tokio::time::Instant,
+) -> Result<u16> {
+    loop {
+        match container.get_host_port_ipv4(port).await {
+            Ok(host_port) => return Ok(host_port),
+            Err(e) if tokio::time::Instant::now() >= deadline => {
+                return Err(anyhow::anyhow!(
+                    "Port {} was not published in time: {:?}",
+                    port,
+                    e
+                ))
+            }
+            Err(_) => tokio::time::sleep(CONTAINER_POLL_INTERVAL).await,
+        }
+    }
+}
+
+/// Poll until ArangoDB's port is published and `GET /_api/version` answers
+///
+/// Returns the host port.
+async fn wait_for_arangodb(container: &ContainerAsync<GenericImage>) -> Result<u16> {
+    let deadline = tokio::time::Instant::now() + CONTAINER_READY_TIMEOUT;
+    let port = wait_for_host_port(container, 8529.tcp(), deadline).await?;
+
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(2))
+        .build()
+        .context("Failed to build HTTP client")?;
+    let url = format!("http://localhost:{}/_api/version", port);
+    loop {
+        let response = client
+            .get(&url)
+            .basic_auth("root", Some("test_password"))
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+        match response {
+            Ok(_) => return Ok(port),
+            Err(e) if tokio::time::Instant::now() >= deadline => {
+                return Err(anyhow::anyhow!("ArangoDB did not answer {}: {}", url, e))
+            }
+            Err(_) => tokio::time::sleep(CONTAINER_POLL_INTERVAL).await,
+        }
+    }
+}
+
+/// How a data dump handed to [`TestEnvironment::load_data_dump`] is packaged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DumpFormat {
+    Zip,
+    TarGz,
+    /// Uncompressed arangodump output
+    Directory,
+}
+
+impl DumpFormat {
+    /// Tell the format from the path: a directory, or an archive by extension
+    pub fn detect(path: &Path) -> Result<Self> {
+        if path.is_dir() {
+            return Ok(Self::Directory);
+        }
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if name.ends_with(".zip") {
+            Ok(Self::Zip)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Ok(Self::TarGz)
+        } else {
+            let extension = path
+                .extension()
+                .map(|ext| format!("'.{}'", ext.to_string_lossy()))
+                .unwrap_or_else(|| "no extension".to_string());
+            Err(anyhow::anyhow!(
+                "Unsupported data dump {} ({}): expected a .zip, .tar.gz or .tgz \
+                 archive, or an arangodump directory",
+                path.display(),
+                extension
+            ))
+        }
+    }
+
+    /// Where an archive is copied to in the container
+    fn archive_path(self) -> Option<&'static str> {
+        match self {
+            Self::Zip => Some("/tmp/backup.zip"),
+            Self::TarGz => Some("/tmp/backup.tar.gz"),
+            Self::Directory => None,
+        }
+    }
+
+    /// Shell command extracting the archive into `/tmp/dump`
+    fn extract_command(self) -> Option<&'static str> {
+        match self {
+            Self::Zip => Some("cd /tmp && unzip -q -o backup.zip -d /tmp/dump"),
+            Self::TarGz => Some("mkdir -p /tmp/dump && tar xzf /tmp/backup.tar.gz -C /tmp/dump"),
+            Self::Directory => None,
+        }
+    }
+}
+
+/// Whether `dir` in the container exists and holds arangodump output
+fn is_dump_directory(container_id: &str, dir: &str) -> Result<bool> {
+    let check_cmd = format!(
+        "test -d {} && (test -f {}/_graphs || test -f {}/_collections || ls {} | grep -q '\\.json$')",
+        dir, dir, dir, dir
+    );
+
+    let check_output = Command::new("docker")
+        .args(&["exec", container_id, "sh", "-c", &check_cmd])
+        .output()
+        .context("Failed to check dump directory")?;
+
+    Ok(check_output.status.success())
+}
+
+/// Reads the version from the output of `arangorestore --version`
+///
+/// Fails with a hint on what to change when the binary is missing from the
+/// container or cannot report its version.
+pub fn arangorestore_version(output: &std::process::Output) -> Result<String> {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    // docker exec exits with 126/127 when the command cannot be started
+    let missing =
+        matches!(output.status.code(), Some(126) | Some(127)) || stderr.contains("not found");
+    if missing {
+        return Err(anyhow::anyhow!(
+            "arangorestore is not available in the ArangoDB container. Use an image that \
+             ships the ArangoDB client tools (such as arangodb:3.12.5) to load data dumps. \
+             docker said: {}",
+            stderr.trim()
+        ));
+    }
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "arangorestore --version failed in the ArangoDB container: {}",
+            stderr.trim()
+        ));
+    }
+
+    // Either a bare "3.12.5" line or a "server-version: 3.12.5" detail line
+    stdout
+        .lines()
+        .find_map(|line| {
+            let value = line.trim().strip_prefix("server-version:").unwrap_or(line);
+            let value = value.trim();
+            value
+                .starts_with(|c: char| c.is_ascii_digit())
+                .then(|| value.split_whitespace().next().unwrap_or(value).to_string())
+        })
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Could not read the arangorestore version from: {}",
+                stdout.trim()
+            )
+        })
+}
+
+/// Refuses encrypted dumps, which need a key file this loader cannot supply
+///
+/// arangodump writes an `ENCRYPTION` marker naming the cipher, or `none`.
+fn check_dump_encryption(container_id: &str, dump_dir: &str) -> Result<()> {
+    let output = Command::new("docker")
+        .args(&[
+            "exec",
+            container_id,
+            "cat",
+            &format!("{}/ENCRYPTION", dump_dir),
+        ])
+        .output()
+        .context("Failed to read the dump's encryption marker")?;
+    // Older dumps have no marker and are never encrypted
+    if !output.status.success() {
+        return Ok(());
+    }
+    let cipher = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if cipher.is_empty() || cipher == "none" {
+        return Ok(());
+    }
+    Err(anyhow::anyhow!(
+        "The data dump in {} is encrypted ({}). Create an unencrypted dump with \
+         `arangodump` to load it in tests.",
+        dump_dir,
+        cipher
+    ))
+}
+
+/// Environment variable whose value is sent as the `Authorization` header
+/// when downloading a data dump
+pub const DATA_DUMP_AUTH_ENV: &str = "TEST_DATA_DUMP_AUTH_HEADER";
+
+/// Download a data dump, reusing an earlier download of the same URL
+///
+/// Downloads are cached under the system temp directory, keyed by the URL,
+/// alongside a SHA-256 checksum of their contents. A cached dump is only
+/// reused while its contents still match that checksum, so an interrupted or
+/// corrupted download is fetched again.
+pub async fn download_data_dump(url: &str) -> Result<PathBuf> {
+    let cache_dir = std::env::temp_dir().join("stg-test-dumps");
+    std::fs::create_dir_all(&cache_dir)
+        .with_context(|| format!("Failed to create dump cache {}", cache_dir.display()))?;
+
+    // Keep the archive's extension so load_data_dump can tell its format
+    let url_path = url.split(['?', '#']).next().unwrap_or(url).to_lowercase();
+    let extension = if url_path.ends_with(".tar.gz") {
+        "tar.gz"
+    } else if url_path.ends_with(".tgz") {
+        "tgz"
+    } else {
+        "zip"
+    };
+    let key = hex::encode(Sha256::digest(url.as_bytes()));
+    let dump_path = cache_dir.join(format!("{}.{}", key, extension));
+    let checksum_path = cache_dir.join(format!("{}.{}.sha256", key, extension));
+
+    if let (Ok(content), Ok(expected)) = (
+        std::fs::read(&dump_path),
+        std::fs::read_to_string(&checksum_path),
+    ) {
+        if hex::encode(Sha256::digest(&content)) == expected.trim() {
+            log::info!("Using cached data dump for {}", url);
+            return Ok(dump_path);
+        }
+        log::warn!(
+            "Cached data dump for {} failed its checksum, downloading again",
+            url
+        );
+    }
+
+    log::info!("Downloading data dump from {}", url);
+    let mut request = reqwest::Client::new().get(url);
+    if let Ok(auth) = std::env::var(DATA_DUMP_AUTH_ENV) {
+        request = request.header(reqwest::header::AUTHORIZATION, auth);
+    }
+    let content = request
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .with_context(|| format!("Failed to download data dump from {}", url))?
+        .bytes()
+        .await
+        .with_context(|| format!("Failed to read data dump from {}", url))?;
+
+    // Write under a temporary name so a partial download is never picked up
+    let partial_path = cache_dir.join(format!("{}.zip.part", key));
+    std::fs::write(&partial_path, &content).context("Failed to write data dump")?;
+    std::fs::rename(&partial_path, &dump_path).context("Failed to store data dump")?;
+    std::fs::write(&checksum_path, hex::encode(Sha256::digest(&content)))
+        .context("Failed to write data dump checksum")?;
+
+    Ok(dump_path)
+}
+
+/// A fresh database name, so tests sharing containers get separate databases
+fn random_database_name() -> String {
+    format!("test_{}", uuid::Uuid::new_v4().simple())
+}
+
 /// Helper to create a test environment with sanitized data
 pub struct TestEnvironmentBuilder {
     data_dump_path: Option<String>,
+    data_dump_url: Option<String>,
     database_name: Option<String>,
     skip_data_load_if_missing: bool,
+    overwrite: bool,
+    shared_containers: bool,
+    keep_containers_on_failure: Option<bool>,
 }
 
 impl TestEnvironmentBuilder {
     pub fn new() -> Self {
         Self {
             data_dump_path: None,
+            data_dump_url: None,
             database_name: None,
             skip_data_load_if_missing: false,
+            overwrite: false,
+            shared_containers: false,
+            keep_containers_on_failure: None,
         }
     }
 
@@ -752,6 +1274,15 @@ impl TestEnvironmentBuilder {
         self
     }
 
+    /// Download the data dump from a URL, e.g. object storage, before loading it
+    ///
+    /// Set `TEST_DATA_DUMP_AUTH_HEADER` to send an `Authorization` header.
+    /// Downloads are cached across test runs; see [`download_data_dump`].
+    pub fn with_data_dump_url(mut self, url: &str) -> Self {
+        self.data_dump_url = Some(url.to_string());
+        self
+    }
+
     /// Set the database name (default: "smacktalk")
     pub fn with_database_name(mut self, name: &str) -> Self {
         self.database_name = Some(name.to_string());
@@ -766,20 +1297,66 @@ impl TestEnvironmentBuilder {
         self
     }
 
+    /// Replace the target database with the dump instead of merging into it
+    ///
+    /// See [`TestEnvironment::load_data_dump`].
+    pub fn with_overwrite(mut self, overwrite: bool) -> Self {
+        self.overwrite = overwrite;
+        self
+    }
+
+    /// Run on the containers shared with other tests
+    ///
+    /// Unless a database name is set, the environment gets a random one.
+    /// See [`TestEnvironment::shared`] for which tests can share containers.
+    pub fn with_shared_containers(mut self) -> Self {
+        self.shared_containers = true;
+        self
+    }
+
+    /// Keep the containers running if the test panics, overriding
+    /// `STG_KEEP_CONTAINERS_ON_FAILURE`
+    ///
+    /// The connection URLs and container IDs are printed so the containers
+    /// can be inspected and removed by hand afterwards.
+    pub fn with_keep_containers_on_failure(mut self, keep: bool) -> Self {
+        self.keep_containers_on_failure = Some(keep);
+        self
+    }
+
     /// Build the test environment
     pub async fn build(self) -> Result<TestEnvironment> {
-        let env = TestEnvironment::new().await?;
+        let mut env = if self.shared_containers {
+            TestEnvironment::with_shared_containers().await?
+        } else {
+            TestEnvironment::new().await?
+        };
+        if let Some(keep) = self.keep_containers_on_failure {
+            env.keep_containers_on_failure = keep;
+        }
 
         // Set database name if provided
         if let Some(db_name) = self.database_name {
-            *env.arangodb_db_name.borrow_mut() = db_name;
+            *env.arangodb_db_name.lock().unwrap() = db_name;
+            *env.database.get_mut() = None;
         }
 
         // Wait for services to be ready before loading data
         env.wait_for_ready().await?;
 
+        let mut data_dump_path = self.data_dump_path;
+        if let Some(url) = self.data_dump_url {
+            match download_data_dump(&url).await {
+                Ok(path) => data_dump_path = Some(path.to_string_lossy().into_owned()),
+                Err(e) if self.skip_data_load_if_missing => {
+                    log::warn!("{:#}, skipping data load", e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
         // If data dump is provided, load it into ArangoDB
-        if let Some(dump_path) = self.data_dump_path {
+        if let Some(dump_path) = data_dump_path {
             let dump_path = Path::new(&dump_path);
             if !dump_path.exists() {
                 if self.skip_data_load_if_missing {
@@ -794,7 +1371,8 @@ impl TestEnvironmentBuilder {
                     ));
                 }
             } else {
-                env.load_data_dump(dump_path.to_str().unwrap()).await?;
+                env.load_data_dump(dump_path.to_str().unwrap(), self.overwrite)
+                    .await?;
             }
         }
 
@@ -875,6 +1453,9 @@ pub async fn test_env_with_prod_data_and_db(db_name: &str) -> Result<TestEnviron
 // Re-export app setup for convenience
 pub mod app_setup;
 
+pub mod assertions;
+pub use assertions::{assert_collection_count, assert_player_exists};
+
 /// Helper function to create a test environment with timeouts
 /// This ensures tests fail fast if containers don't start in time
 /// Use this in all integration tests for consistent timeout behavior
@@ -944,4 +1525,48 @@ mod tests {
         assert!(!env.arangodb_url().is_empty());
         assert!(!env.redis_url().is_empty());
     }
+
+    fn sh(script: &str) -> std::process::Output {
+        Command::new("sh").args(["-c", script]).output().unwrap()
+    }
+
+    #[test]
+    fn test_missing_arangorestore_explains_what_to_change() {
+        let output = sh("echo 'OCI runtime exec failed: exec: \"arangorestore\": \
+             executable file not found in $PATH' >&2; exit 127");
+        let error = arangorestore_version(&output).unwrap_err().to_string();
+        assert!(
+            error.contains("arangorestore is not available"),
+            "{}",
+            error
+        );
+        assert!(error.contains("client tools"), "{}", error);
+        assert!(error.contains("executable file not found"), "{}", error);
+    }
+
+    #[test]
+    fn test_dump_format_is_detected_from_the_path() {
+        let detect = |name: &str| DumpFormat::detect(Path::new(name));
+        assert_eq!(detect("backups/smacktalk.zip").unwrap(), DumpFormat::Zip);
+        assert_eq!(detect("backups/smacktalk.ZIP").unwrap(), DumpFormat::Zip);
+        assert_eq!(detect("smacktalk.tar.gz").unwrap(), DumpFormat::TarGz);
+        assert_eq!(detect("smacktalk.tgz").unwrap(), DumpFormat::TarGz);
+        assert_eq!(
+            DumpFormat::detect(&std::env::temp_dir()).unwrap(),
+            DumpFormat::Directory
+        );
+
+        let error = detect("players.json").unwrap_err().to_string();
+        assert!(error.contains("'.json'"), "{}", error);
+        let error = detect("dump").unwrap_err().to_string();
+        assert!(error.contains("no extension"), "{}", error);
+    }
+
+    #[test]
+    fn test_arangorestore_version_is_read_from_either_format() {
+        let bare = sh("printf '3.12.5\\n\\narchitecture: 64bit\\n'");
+        assert_eq!(arangorestore_version(&bare).unwrap(), "3.12.5");
+        let detailed = sh("printf 'Version 3.12.5\\nserver-version: 3.12.5\\n'");
+        assert_eq!(arangorestore_version(&detailed).unwrap(), "3.12.5");
+    }
 }
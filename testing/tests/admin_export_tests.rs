@@ -0,0 +1,129 @@
+//! Integration tests for the admin NDJSON contest export
+
+use anyhow::{Context, Result};
+use arangors::client::reqwest::ReqwestClient;
+use arangors::{Connection, Database};
+use backend::contest::repository::ContestRepository;
+use chrono::{DateTime, Duration, FixedOffset, Utc};
+use futures::StreamExt;
+use shared::dto::contest::{ContestDto, OutcomeDto};
+use shared::dto::game::GameDto;
+use shared::dto::venue::VenueDto;
+use shared::models::contest::ContestVisibility;
+use shared::models::game::GameSource;
+use shared::models::venue::VenueSource;
+use testing::{app_setup, TestEnvironment};
+
+fn contest_dto(name: &str, start: DateTime<FixedOffset>, handles: &[&str]) -> ContestDto {
+    ContestDto {
+        id: String::new(),
+        name: name.to_string(),
+        start,
+        stop: start + Duration::hours(1),
+        venue: VenueDto {
+            id: String::new(),
+            display_name: "Export Venue".to_string(),
+            formatted_address: "1 Export St".to_string(),
+            place_id: "export_place_id".to_string(),
+            lat: 40.7128,
+            lng: -74.0060,
+            timezone: "America/New_York".to_string(),
+            source: VenueSource::Database,
+        },
+        games: vec![GameDto {
+            id: String::new(),
+            name: "Export Game".to_string(),
+            year_published: Some(2020),
+            bgg_id: None,
+            description: None,
+            source: GameSource::Database,
+        }],
+        outcomes: handles
+            .iter()
+            .enumerate()
+            .map(|(i, handle)| OutcomeDto {
+                player_id: String::new(),
+                place: (i + 1).to_string(),
+                result: if i == 0 { "won" } else { "lost" }.to_string(),
+                email: format!("{}@example.com", handle),
+                handle: handle.to_string(),
+                guest: false,
+            })
+            .collect(),
+        creator_id: String::new(),
+        created_at: None,
+        rated: true,
+        visibility: ContestVisibility::Public,
+    }
+}
+
+#[tokio::test]
+async fn test_contest_export_streams_one_line_per_completed_contest() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+
+    let now: DateTime<FixedOffset> = Utc::now().into();
+    let completed = [
+        ("Export One", &["exportone1", "exportone2"][..]),
+        ("Export Two", &["exporttwo1", "exporttwo2"][..]),
+        (
+            "Export Three",
+            &["exportthree1", "exportthree2", "exportthree3"][..],
+        ),
+    ];
+    for (i, (name, handles)) in completed.iter().enumerate() {
+        let start = now - Duration::days(i as i64 + 1);
+        app_data
+            .contest_repo
+            .create_contest(
+                contest_dto(name, start, handles),
+                "player/creator".to_string(),
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to create contest: {}", e))?;
+    }
+    // Contests that have not finished yet are left out of the export
+    app_data
+        .contest_repo
+        .create_contest(
+            contest_dto(
+                "Export Upcoming",
+                now + Duration::days(1),
+                &["exportupcoming1"],
+            ),
+            "player/creator".to_string(),
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to create contest: {}", e))?;
+
+    let conn = Connection::establish_basic_auth(env.arangodb_url(), "root", "test_password")
+        .await
+        .context("Failed to connect to ArangoDB")?;
+    let db: Database<ReqwestClient> = conn
+        .db("_system")
+        .await
+        .context("Failed to access _system database")?;
+
+    let mut body = Vec::new();
+    let mut stream = Box::pin(backend::admin::export::contests_ndjson(db));
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| anyhow::anyhow!("Export stream failed: {}", e))?;
+        body.extend_from_slice(&chunk);
+    }
+    let body = String::from_utf8(body)?;
+
+    let lines: Vec<&str> = body.lines().collect();
+    assert_eq!(lines.len(), completed.len());
+    for (line, (name, handles)) in lines.iter().zip(completed.iter().rev()) {
+        let contest: serde_json::Value =
+            serde_json::from_str(line).context("Each export line should be valid JSON")?;
+        assert_eq!(contest["name"], *name);
+        assert_eq!(
+            contest["participants"].as_array().map(|p| p.len()),
+            Some(handles.len())
+        );
+    }
+
+    Ok(())
+}
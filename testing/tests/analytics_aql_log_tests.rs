@@ -0,0 +1,112 @@
+//! Integration tests for AQL debug logging
+//!
+//! Runs in its own test binary so it can install a capturing logger.
+
+use anyhow::{Context, Result};
+use arangors::{AqlQuery, Connection};
+use backend::analytics::query_log::{self, AQL_LOG_TARGET};
+use backend::analytics::AnalyticsRepository;
+use backend::config::DatabaseConfig;
+use log::{Level, Log, Metadata, Record};
+use std::sync::Mutex;
+use testing::{app_setup, TestEnvironment};
+
+static CAPTURED: Mutex<Vec<(Level, String)>> = Mutex::new(Vec::new());
+
+struct CaptureLogger;
+
+impl Log for CaptureLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.target() == AQL_LOG_TARGET
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            CAPTURED
+                .lock()
+                .unwrap()
+                .push((record.level(), record.args().to_string()));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: CaptureLogger = CaptureLogger;
+
+fn take_captured() -> Vec<(Level, String)> {
+    std::mem::take(&mut *CAPTURED.lock().unwrap())
+}
+
+#[tokio::test]
+async fn test_aql_logging_is_opt_in_and_redacts_players() -> Result<()> {
+    log::set_logger(&LOGGER).expect("logger already set");
+    log::set_max_level(log::LevelFilter::Debug);
+
+    // Emails and document ids are masked; other values are kept
+    let query = AqlQuery::builder()
+        .query("FOR p IN player\n    FILTER p.email == @email OR p._id == @id\n    RETURN p")
+        .bind_var("email", "alice@example.com")
+        .bind_var("id", "player/12345")
+        .bind_var("handle", "alice")
+        .build();
+    query_log::log_query(&query);
+    let captured = take_captured();
+    assert_eq!(captured.len(), 1);
+    let (level, message) = &captured[0];
+    assert_eq!(*level, Level::Debug);
+    assert!(
+        message.contains("FOR p IN player FILTER p.email == @email OR p._id == @id RETURN p"),
+        "{}",
+        message
+    );
+    assert!(message.contains(r#""email":"<email>""#), "{}", message);
+    assert!(message.contains(r#""id":"player/<id>""#), "{}", message);
+    assert!(message.contains(r#""handle":"alice""#), "{}", message);
+    assert!(!message.contains("alice@example.com"), "{}", message);
+    assert!(!message.contains("12345"), "{}", message);
+
+    // The repository only logs its queries once switched on
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    app_setup::setup_test_app_data(&env).await?;
+    let conn = Connection::establish_basic_auth(env.arangodb_url(), "root", "test_password")
+        .await
+        .context("Failed to connect to ArangoDB")?;
+    let db = conn.db("_system").await?;
+    let repo = AnalyticsRepository::new(
+        db,
+        DatabaseConfig {
+            url: env.arangodb_url().to_string(),
+            name: "_system".to_string(),
+            username: "root".to_string(),
+            password: "test_password".to_string(),
+            root_username: "root".to_string(),
+            root_password: "test_password".to_string(),
+            pool_size: 1,
+            _timeout_seconds: 30,
+        },
+    );
+
+    repo.get_play_partners("player/quiet")
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to get play partners: {}", e))?;
+    assert!(take_captured().is_empty());
+
+    let repo = repo.with_aql_logging(true);
+    repo.get_play_partners("player/loud")
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to get play partners: {}", e))?;
+    let captured = take_captured();
+    assert_eq!(captured.len(), 1);
+    let message = &captured[0].1;
+    assert!(
+        message.contains("FOR my_result IN resulted_in"),
+        "{}",
+        message
+    );
+    assert!(message.contains("player/<id>"), "{}", message);
+    assert!(!message.contains("loud"), "{}", message);
+
+    Ok(())
+}
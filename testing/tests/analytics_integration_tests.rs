@@ -0,0 +1,2558 @@
+//! Integration tests for analytics repository persistence
+
+use anyhow::{Context, Result};
+use arangors::client::reqwest::ReqwestClient;
+use arangors::Database;
+use backend::analytics::{AnalyticsRepository, AnalyticsUseCase};
+use backend::config::{AnalyticsConfig, DatabaseConfig};
+use backend::venue::usecase::{VenueUseCase, VenueUseCaseImpl};
+use testing::{app_setup, create_authenticated_user, TestEnvironment};
+
+/// Database settings for the container's `_system` database
+fn database_config(env: &TestEnvironment) -> DatabaseConfig {
+    DatabaseConfig {
+        url: env.arangodb_url().to_string(),
+        name: "_system".to_string(),
+        username: "root".to_string(),
+        password: "test_password".to_string(),
+        root_username: "root".to_string(),
+        root_password: "test_password".to_string(),
+        pool_size: 1,
+        _timeout_seconds: 30,
+    }
+}
+
+/// Builds an analytics repository against the container's `_system` database
+async fn analytics_repo(env: &TestEnvironment) -> Result<AnalyticsRepository<ReqwestClient>> {
+    // Ensures the core document and edge collections exist
+    app_setup::setup_test_app_data(env).await?;
+    let db = env.database().await?;
+
+    let repo = AnalyticsRepository::new(db, database_config(env));
+    repo.create_collections()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to create analytics collections: {}", e))?;
+    Ok(repo)
+}
+
+#[tokio::test]
+async fn test_recompute_platform_stats_persists_live_computation() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+
+    assert!(
+        repo.get_persisted_platform_stats()
+            .await
+            .map_err(|e| anyhow::anyhow!("{}", e))?
+            .is_none(),
+        "No snapshot should exist before the first recompute"
+    );
+
+    let live = repo
+        .get_platform_stats()
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let recomputed = repo
+        .recompute_platform_stats()
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let persisted = repo
+        .get_persisted_platform_stats()
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?
+        .expect("Recompute should persist a platform_stats document");
+
+    // Everything but the timestamp must match the live computation
+    let strip_timestamp = |stats: &shared::models::analytics::PlatformStats| {
+        let mut value = serde_json::to_value(stats).expect("stats serialize");
+        value
+            .as_object_mut()
+            .expect("stats is an object")
+            .remove("last_updated");
+        value
+    };
+    assert_eq!(strip_timestamp(&persisted), strip_timestamp(&live));
+    assert_eq!(persisted.last_updated, recomputed.last_updated);
+
+    // A second recompute replaces the snapshot rather than adding another document
+    let second = repo
+        .recompute_platform_stats()
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let persisted = repo
+        .get_persisted_platform_stats()
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?
+        .expect("Snapshot should still exist");
+    assert_eq!(persisted.last_updated, second.last_updated);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_recommend_games_skips_games_the_player_already_plays() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let db = env.database().await?;
+
+    // "me" wins Heavy Game three times alongside "friend"; friend also plays
+    // Friend Game, while Other Game is only played by a stranger
+    let contests = serde_json::json!([
+        { "key": "rec_c1", "game": "rec_heavy", "players": [["rec_me", "won"], ["rec_friend", "lost"]] },
+        { "key": "rec_c2", "game": "rec_heavy", "players": [["rec_me", "won"], ["rec_friend", "lost"]] },
+        { "key": "rec_c3", "game": "rec_heavy", "players": [["rec_me", "won"], ["rec_friend", "lost"]] },
+        { "key": "rec_c4", "game": "rec_friend_game", "players": [["rec_friend", "won"], ["rec_stranger", "lost"]] },
+        { "key": "rec_c5", "game": "rec_friend_game", "players": [["rec_friend", "won"], ["rec_stranger", "lost"]] },
+        { "key": "rec_c6", "game": "rec_other", "players": [["rec_stranger", "won"]] }
+    ]);
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            LET games = (
+                FOR g IN [["rec_heavy", "Heavy Game"], ["rec_friend_game", "Friend Game"], ["rec_other", "Other Game"]]
+                    INSERT { _key: g[0], name: g[1] } INTO game
+            )
+            LET players = (
+                FOR p IN ["rec_me", "rec_friend", "rec_stranger"]
+                    INSERT { _key: p, handle: p, email: CONCAT(p, "@example.com") } INTO player
+            )
+            LET contests = (
+                FOR c IN @contests
+                    INSERT { _key: c.key, name: c.key, start: "2024-01-01T18:00:00Z", stop: "2024-01-01T20:00:00Z" } INTO contest
+            )
+            LET games_played = (
+                FOR c IN @contests
+                    INSERT { _from: CONCAT("contest/", c.key), _to: CONCAT("game/", c.game) } INTO played_with
+            )
+            LET outcomes = (
+                FOR c IN @contests
+                    FOR p IN c.players
+                        INSERT {
+                            _from: CONCAT("contest/", c.key),
+                            _to: CONCAT("player/", p[0]),
+                            place: p[1] == "won" ? 1 : 2,
+                            result: p[1]
+                        } INTO resulted_in
+            )
+            RETURN LENGTH(outcomes)
+            "#,
+        )
+        .bind_var("contests", contests)
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed recommendation data")?;
+
+    let recommendations = repo
+        .recommend_games("player/rec_me", 10)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    assert!(
+        recommendations
+            .iter()
+            .all(|r| r.game_id != "game/rec_heavy"),
+        "A game the player already plays heavily must not be recommended"
+    );
+    let names: Vec<&str> = recommendations
+        .iter()
+        .map(|r| r.game_name.as_str())
+        .collect();
+    assert_eq!(names, ["Friend Game", "Other Game"]);
+    assert!(recommendations[0].similarity > 0.0);
+    assert_eq!(recommendations[1].similarity, 0.0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_networking_suggests_players_from_shared_venues() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let db = env.database().await?;
+
+    // "me" has only faced "rival" at the Home venue; "regular" also plays at
+    // Home without ever meeting "me", while "outsider" only plays Elsewhere
+    let contests = serde_json::json!([
+        { "key": "net_c1", "venue": "net_home", "players": ["net_me", "net_rival"] },
+        { "key": "net_c2", "venue": "net_home", "players": ["net_regular", "net_rival"] },
+        { "key": "net_c3", "venue": "net_elsewhere", "players": ["net_outsider", "net_rival"] }
+    ]);
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            LET venues = (
+                FOR v IN ["net_home", "net_elsewhere"]
+                    INSERT { _key: v, displayName: v } INTO venue
+            )
+            LET players = (
+                FOR p IN ["net_me", "net_rival", "net_regular", "net_outsider"]
+                    INSERT { _key: p, handle: p, email: CONCAT(p, "@example.com") } INTO player
+            )
+            LET contests = (
+                FOR c IN @contests
+                    INSERT { _key: c.key, name: c.key, start: "2024-01-01T18:00:00Z", stop: "2024-01-01T20:00:00Z" } INTO contest
+            )
+            LET venues_played = (
+                FOR c IN @contests
+                    INSERT { _from: CONCAT("contest/", c.key), _to: CONCAT("venue/", c.venue) } INTO played_at
+            )
+            LET outcomes = (
+                FOR c IN @contests
+                    FOR i IN 0..LENGTH(c.players) - 1
+                        INSERT {
+                            _from: CONCAT("contest/", c.key),
+                            _to: CONCAT("player/", c.players[i]),
+                            place: i + 1,
+                            result: i == 0 ? "won" : "lost"
+                        } INTO resulted_in
+            )
+            RETURN LENGTH(outcomes)
+            "#,
+        )
+        .bind_var("contests", contests)
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed networking data")?;
+
+    let suggestions = repo
+        .get_networking_suggestions("player/net_me")
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    let ids: Vec<&str> = suggestions.iter().map(|s| s.player_id.as_str()).collect();
+    assert_eq!(
+        ids,
+        ["player/net_regular"],
+        "Only unmet players from shared venues should be suggested"
+    );
+    assert_eq!(suggestions[0].shared_venues, 1);
+    assert_eq!(suggestions[0].total_contests, 1);
+    assert_eq!(suggestions[0].overall_win_rate, 100.0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_play_partners_count_shared_contests_whatever_the_outcome() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let db = env.database().await?;
+
+    // "me" beats "often" twice and loses to them once, loses to "once", and
+    // never meets "stranger"; players are listed in finishing order
+    let contests = serde_json::json!([
+        { "key": "pp_c1", "start": "2024-01-01T18:00:00Z", "players": ["pp_me", "pp_often"] },
+        { "key": "pp_c2", "start": "2024-02-01T18:00:00Z", "players": ["pp_often", "pp_me"] },
+        { "key": "pp_c3", "start": "2024-03-01T18:00:00Z", "players": ["pp_me", "pp_often"] },
+        { "key": "pp_c4", "start": "2024-04-01T18:00:00Z", "players": ["pp_once", "pp_me"] },
+        { "key": "pp_c5", "start": "2024-05-01T18:00:00Z", "players": ["pp_stranger", "pp_once"] }
+    ]);
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            LET players = (
+                FOR p IN ["pp_me", "pp_often", "pp_once", "pp_stranger"]
+                    INSERT { _key: p, handle: p, email: CONCAT(p, "@example.com") } INTO player
+            )
+            LET contests = (
+                FOR c IN @contests
+                    INSERT { _key: c.key, name: c.key, start: c.start, stop: c.start } INTO contest
+            )
+            LET outcomes = (
+                FOR c IN @contests
+                    FOR i IN 0..LENGTH(c.players) - 1
+                        INSERT {
+                            _from: CONCAT("contest/", c.key),
+                            _to: CONCAT("player/", c.players[i]),
+                            place: i + 1,
+                            result: i == 0 ? "won" : "lost"
+                        } INTO resulted_in
+            )
+            RETURN LENGTH(outcomes)
+            "#,
+        )
+        .bind_var("contests", contests)
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed play partner data")?;
+
+    let partners = repo
+        .get_play_partners("player/pp_me")
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    let ids: Vec<&str> = partners.iter().map(|p| p.player_id.as_str()).collect();
+    assert_eq!(ids, ["player/pp_often", "player/pp_once"]);
+    assert_eq!(partners[0].contests_played, 3);
+    assert_eq!(partners[0].wins_against_me, 1);
+    assert_eq!(partners[0].losses_to_me, 2);
+    assert_eq!(
+        partners[0].last_played.map(|d| d.to_rfc3339()).as_deref(),
+        Some("2024-03-01T18:00:00+00:00")
+    );
+    assert_eq!(partners[1].contests_played, 1);
+    assert_eq!(partners[1].wins_against_me, 1);
+    assert_eq!(partners[1].total_contests, 2);
+    assert_eq!(partners[1].overall_win_rate, 50.0);
+
+    let lonely = repo
+        .get_play_partners("player/pp_stranger_who_never_played")
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert!(lonely.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_communities_split_disjoint_venue_groups() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let db = env.database().await?;
+
+    // Two regulars at each of two venues that never share a player
+    let contests = serde_json::json!([
+        { "key": "com_n1", "venue": "com_north", "players": ["com_ann", "com_ben"] },
+        { "key": "com_n2", "venue": "com_north", "players": ["com_ben", "com_ann"] },
+        { "key": "com_s1", "venue": "com_south", "players": ["com_cat", "com_dan", "com_eve"] },
+        { "key": "com_s2", "venue": "com_south", "players": ["com_dan", "com_cat", "com_eve"] }
+    ]);
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            LET venues = (
+                FOR v IN ["com_north", "com_south"]
+                    INSERT { _key: v, displayName: v } INTO venue
+            )
+            LET players = (
+                FOR p IN ["com_ann", "com_ben", "com_cat", "com_dan", "com_eve"]
+                    INSERT { _key: p, handle: p, email: CONCAT(p, "@example.com") } INTO player
+            )
+            LET contests = (
+                FOR c IN @contests
+                    INSERT { _key: c.key, name: c.key, start: "2024-01-01T18:00:00Z", stop: "2024-01-01T20:00:00Z" } INTO contest
+            )
+            LET venues_played = (
+                FOR c IN @contests
+                    INSERT { _from: CONCAT("contest/", c.key), _to: CONCAT("venue/", c.venue) } INTO played_at
+            )
+            LET outcomes = (
+                FOR c IN @contests
+                    FOR i IN 0..LENGTH(c.players) - 1
+                        INSERT {
+                            _from: CONCAT("contest/", c.key),
+                            _to: CONCAT("player/", c.players[i]),
+                            place: i + 1,
+                            result: i == 0 ? "won" : "lost"
+                        } INTO resulted_in
+            )
+            RETURN LENGTH(outcomes)
+            "#,
+        )
+        .bind_var("contests", contests)
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed community data")?;
+
+    let communities = repo
+        .get_communities()
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    assert_eq!(communities.len(), 2);
+    assert_eq!(
+        communities[0].member_handles,
+        ["com_cat", "com_dan", "com_eve"]
+    );
+    assert_eq!(communities[0].top_venues[0].venue_name, "com_south");
+    assert_eq!(communities[1].member_handles, ["com_ann", "com_ben"]);
+    assert_eq!(communities[1].top_venues[0].venue_name, "com_north");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_merged_venue_contests_count_once_for_survivor() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let db = env.database().await?;
+
+    // mrg_both was re-recorded at the survivor but its old edge remains
+    let played_at = serde_json::json!([
+        { "contest": "mrg_k1", "venue": "mrg_keep" },
+        { "contest": "mrg_k2", "venue": "mrg_keep" },
+        { "contest": "mrg_g1", "venue": "mrg_gone" },
+        { "contest": "mrg_both", "venue": "mrg_gone" },
+        { "contest": "mrg_both", "venue": "mrg_keep" },
+        { "contest": "mrg_o1", "venue": "mrg_other" },
+        { "contest": "mrg_o2", "venue": "mrg_other" },
+        { "contest": "mrg_o3", "venue": "mrg_other" }
+    ]);
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            LET venues = (
+                FOR v IN ["mrg_keep", "mrg_gone", "mrg_other"]
+                    INSERT { _key: v, displayName: v, formattedAddress: v, place_id: v, lat: 0, lng: 0 } INTO venue
+            )
+            LET contests = (
+                FOR c IN UNIQUE(@played_at[*].contest)
+                    INSERT { _key: c, name: c, start: "2024-01-01T18:00:00Z", stop: "2024-01-01T20:00:00Z" } INTO contest
+            )
+            LET edges = (
+                FOR e IN @played_at
+                    INSERT { _from: CONCAT("contest/", e.contest), _to: CONCAT("venue/", e.venue) } INTO played_at
+            )
+            RETURN LENGTH(edges)
+            "#,
+        )
+        .bind_var("played_at", played_at)
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed venue data")?;
+
+    // Merge mrg_gone into mrg_keep
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+    let venues = VenueUseCaseImpl {
+        repo: app_data.venue_repo.get_ref().clone(),
+    };
+    let survivor = venues
+        .merge_venues("venue/mrg_gone", "venue/mrg_keep")
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(survivor.id, "venue/mrg_keep");
+
+    // A merged venue cannot take in another one
+    let error = venues
+        .merge_venues("venue/mrg_other", "venue/mrg_gone")
+        .await
+        .expect_err("Merging into a merged venue should fail");
+    assert!(matches!(error, shared::SharedError::Conflict(_)));
+
+    let stats = repo
+        .get_platform_stats()
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let top: Vec<_> = stats
+        .top_venues
+        .iter()
+        .map(|v| (v.venue_name.as_str(), v.contests_held))
+        .collect();
+    assert_eq!(top, [("mrg_keep", 4), ("mrg_other", 3)]);
+
+    // Looking up the merged venue resolves to the survivor
+    for venue_id in ["venue/mrg_keep", "venue/mrg_gone"] {
+        let mut contests: Vec<String> = repo
+            .get_venue_contests(venue_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}", e))?
+            .into_iter()
+            .map(|c| c.contest_id)
+            .collect();
+        contests.sort();
+        assert_eq!(
+            contests,
+            [
+                "contest/mrg_both",
+                "contest/mrg_g1",
+                "contest/mrg_k1",
+                "contest/mrg_k2"
+            ],
+            "{}",
+            venue_id
+        );
+    }
+
+    // Venue stats for the survivor combine both venues' contests
+    let stats = repo
+        .compute_venue_stats("venue/mrg_keep")
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(stats.total_contests, 4);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_venue_stats_summarise_activity_at_the_venue() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let db = env.database().await?;
+
+    // Three Fridays and a Saturday at vs_pub across two months, plus a
+    // contest elsewhere that must not count
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            LET venues = (
+                FOR v IN [["vs_pub", "The Pub"], ["vs_cafe", "The Cafe"]]
+                    INSERT { _key: v[0], displayName: v[1] } INTO venue
+            )
+            LET games = (
+                FOR g IN [["vs_catan", "Catan"], ["vs_azul", "Azul"]]
+                    INSERT { _key: g[0], name: g[1] } INTO game
+            )
+            LET players = (
+                FOR p IN ["vs_ann", "vs_ben", "vs_cat"]
+                    INSERT { _key: p, handle: p, email: CONCAT(p, "@example.com") } INTO player
+            )
+            LET contests = (
+                FOR c IN [
+                    ["vs_c1", "2024-03-01T19:00:00Z", "vs_pub", "vs_catan", ["vs_ann", "vs_ben"]],
+                    ["vs_c2", "2024-03-08T19:00:00Z", "vs_pub", "vs_catan", ["vs_ann", "vs_cat"]],
+                    ["vs_c3", "2024-04-05T19:00:00Z", "vs_pub", "vs_azul", ["vs_ann", "vs_ben", "vs_cat"]],
+                    ["vs_c4", "2024-04-06T19:00:00Z", "vs_pub", "vs_catan", ["vs_ben", "vs_cat"]],
+                    ["vs_c5", "2024-04-07T19:00:00Z", "vs_cafe", "vs_azul", ["vs_ann", "vs_ben"]]
+                ]
+                    INSERT { _key: c[0], name: c[0], start: c[1], stop: DATE_ADD(c[1], 2, "hour") } INTO contest
+                    INSERT { _from: CONCAT("contest/", c[0]), _to: CONCAT("venue/", c[2]) } INTO played_at
+                    INSERT { _from: CONCAT("contest/", c[0]), _to: CONCAT("game/", c[3]) } INTO played_with
+                    FOR p IN c[4]
+                        INSERT { _from: CONCAT("contest/", c[0]), _to: CONCAT("player/", p), place: 1, result: "won" } INTO resulted_in
+            )
+            RETURN LENGTH(contests)
+            "#,
+        )
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed venue activity")?;
+
+    let stats = repo
+        .compute_venue_stats("venue/vs_pub")
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(stats.venue_id, "venue/vs_pub");
+    assert_eq!(stats.venue_name, "The Pub");
+    assert_eq!(stats.total_contests, 4);
+    assert_eq!(stats.unique_players, 3);
+    let most_played = stats
+        .most_played_game
+        .as_ref()
+        .map(|g| (g.game_name.as_str(), g.plays));
+    assert_eq!(most_played, Some(("Catan", 3)));
+    assert_eq!(stats.busiest_day.as_deref(), Some("Friday"));
+    let trend: Vec<(i32, u32, i32)> = stats
+        .monthly_contests
+        .iter()
+        .map(|m| (m.year, m.month, m.contests))
+        .collect();
+    assert_eq!(trend, [(2024, 3, 2), (2024, 4, 2)]);
+
+    // A venue nobody has played at has nothing to report
+    let empty = repo
+        .compute_venue_stats("venue/vs_nowhere")
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(empty.total_contests, 0);
+    assert!(empty.most_played_game.is_none());
+    assert!(empty.busiest_day.is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_game_stats_are_computed_from_recorded_plays() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+    let usecase = AnalyticsUseCase::with_cache(repo, app_data.analytics_cache.get_ref().clone());
+    let db = env.database().await?;
+
+    // Three Catan contests won twice by gs_ann, once by gs_ben, plus an Azul
+    // contest that must not count
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            LET games = (
+                FOR g IN [["gs_catan", "Catan"], ["gs_azul", "Azul"]]
+                    INSERT { _key: g[0], name: g[1] } INTO game
+            )
+            LET players = (
+                FOR p IN ["gs_ann", "gs_ben", "gs_cat"]
+                    INSERT { _key: p, handle: p, email: CONCAT(p, "@example.com") } INTO player
+            )
+            LET contests = (
+                FOR c IN [
+                    ["gs_c1", "2024-03-01T19:00:00Z", "gs_catan", ["gs_ann", "gs_ben"]],
+                    ["gs_c2", "2024-03-08T19:00:00Z", "gs_catan", ["gs_ann", "gs_ben", "gs_cat"]],
+                    ["gs_c3", "2024-04-05T19:00:00Z", "gs_catan", ["gs_ben", "gs_ann"]],
+                    ["gs_c4", "2024-04-06T19:00:00Z", "gs_azul", ["gs_cat", "gs_ben"]]
+                ]
+                    INSERT { _key: c[0], name: c[0], start: c[1], stop: DATE_ADD(c[1], 1, "hour") } INTO contest
+                    INSERT { _from: CONCAT("contest/", c[0]), _to: CONCAT("game/", c[2]) } INTO played_with
+                    FOR i IN 0..LENGTH(c[3]) - 1
+                        INSERT { _from: CONCAT("contest/", c[0]), _to: CONCAT("player/", c[3][i]), place: i + 1, result: i == 0 ? "won" : "lost" } INTO resulted_in
+            )
+            RETURN LENGTH(contests)
+            "#,
+        )
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed game plays")?;
+
+    let stats = usecase
+        .get_game_stats("game/gs_catan")
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(stats.game_name, "Catan");
+    assert_eq!(stats.total_plays, 7);
+    assert_eq!(stats.unique_players, 3);
+    assert!((stats.average_players - 17.0 / 7.0).abs() < 1e-9);
+    let distribution: Vec<(&str, i32, i32)> = stats
+        .win_rate_distribution
+        .iter()
+        .map(|p| (p.player_handle.as_str(), p.wins, p.total_plays))
+        .collect();
+    assert_eq!(
+        distribution,
+        [("gs_ann", 2, 3), ("gs_ben", 1, 3), ("gs_cat", 0, 1)]
+    );
+    let champion = stats.champion.as_ref().map(|p| p.player_id.as_str());
+    assert_eq!(champion, Some("player/gs_ann"));
+
+    // Repeat lookups are served from the cache
+    insert_contests(&db, &["gs_c5"]).await?;
+    let replay = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            INSERT { _from: "contest/gs_c5", _to: "game/gs_catan" } INTO played_with
+            INSERT { _from: "contest/gs_c5", _to: "player/gs_cat", place: 1, result: "won" } INTO resulted_in
+            "#,
+        )
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(replay)
+        .await
+        .context("Failed to record another play")?;
+    let cached = usecase
+        .get_game_stats("game/gs_catan")
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(cached.total_plays, 7);
+
+    // An unknown game is reported as missing rather than as an empty game
+    let missing = usecase.get_game_stats("game/gs_nowhere").await;
+    assert!(matches!(missing, Err(shared::SharedError::NotFound(_))));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_platform_stats_list_the_requested_number_of_top_entries() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+    let usecase = AnalyticsUseCase::with_cache(repo, app_data.analytics_cache.get_ref().clone());
+    let db = env.database().await?;
+
+    // Twelve games, each played at its own venue
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            FOR n IN 1..12
+                LET key = CONCAT("top_", n)
+                INSERT { _key: key, name: CONCAT("Top Game ", n) } INTO game
+                INSERT { _key: key, displayName: CONCAT("Top Venue ", n) } INTO venue
+                INSERT { _key: key, name: key, start: "2024-01-01T18:00:00Z", stop: "2024-01-01T20:00:00Z" } INTO contest
+                INSERT { _from: CONCAT("contest/", key), _to: CONCAT("game/", key) } INTO played_with
+                INSERT { _from: CONCAT("contest/", key), _to: CONCAT("venue/", key) } INTO played_at
+            "#,
+        )
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed top games and venues")?;
+
+    let default = usecase
+        .get_platform_stats()
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(default.top_games.len(), 5);
+    assert_eq!(default.top_venues.len(), 5);
+
+    let top_ten = usecase
+        .get_platform_stats_top(10)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(top_ten.top_games.len(), 10);
+    assert_eq!(top_ten.top_venues.len(), 10);
+
+    // Asking for more than exists returns everything, within the cap
+    let everything = usecase
+        .get_platform_stats_top(1000)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(everything.top_games.len(), 12);
+    assert!(everything.top_venues.len() >= 12);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_opponent_win_rates_never_divide_by_zero() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let db = env.database().await?;
+
+    // sd_lonely has never played; sd_beta beat sd_alpha once
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            LET players = (
+                FOR p IN ["sd_alpha", "sd_beta", "sd_lonely"]
+                    INSERT { _key: p, handle: p, email: CONCAT(p, "@example.com") } INTO player
+            )
+            LET contests = (
+                INSERT { _key: "sd_c1", name: "sd_c1", start: "2024-01-01T18:00:00Z", stop: "2024-01-01T20:00:00Z" } INTO contest
+            )
+            LET outcomes = (
+                FOR o IN [
+                    { player: "sd_beta", place: 1, result: "won" },
+                    { player: "sd_alpha", place: 2, result: "lost" }
+                ]
+                    INSERT { _from: "contest/sd_c1", _to: CONCAT("player/", o.player), place: o.place, result: o.result } INTO resulted_in
+            )
+            RETURN LENGTH(outcomes)
+            "#,
+        )
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed opponent data")?;
+
+    let beat_alpha = repo
+        .get_players_who_beat_me("player/sd_alpha")
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(beat_alpha.len(), 1);
+    assert_eq!(beat_alpha[0].player_handle, "sd_beta");
+    assert_eq!(beat_alpha[0].total_contests, 1);
+    assert_eq!(beat_alpha[0].overall_win_rate, 100.0);
+    assert_eq!(beat_alpha[0].win_rate_against_me, 100.0);
+
+    let beaten_by_beta = repo
+        .get_players_i_beat("player/sd_beta")
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(beaten_by_beta.len(), 1);
+    assert_eq!(beaten_by_beta[0].overall_win_rate, 0.0);
+
+    // A player without contests gets empty results, not a query error
+    for opponents in [
+        repo.get_players_who_beat_me("player/sd_lonely").await,
+        repo.get_players_i_beat("player/sd_lonely").await,
+    ] {
+        let opponents = opponents.map_err(|e| anyhow::anyhow!("{}", e))?;
+        assert!(opponents.is_empty());
+    }
+    let suggestions = repo
+        .get_networking_suggestions("player/sd_lonely")
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert!(suggestions.iter().all(|s| s.overall_win_rate.is_finite()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_multi_game_contest_credits_every_game() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let db = env.database().await?;
+
+    // mg_c1 is a two-game session; mg_c2 plays only Catan
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            LET games = (
+                FOR g IN [["mg_catan", "Catan"], ["mg_azul", "Azul"]]
+                    INSERT { _key: g[0], name: g[1] } INTO game
+            )
+            LET players = (
+                FOR p IN ["mg_me", "mg_you"]
+                    INSERT { _key: p, handle: p, email: CONCAT(p, "@example.com") } INTO player
+            )
+            LET contests = (
+                FOR c IN ["mg_c1", "mg_c2"]
+                    INSERT { _key: c, name: c, start: "2024-01-01T18:00:00Z", stop: "2024-01-01T20:00:00Z" } INTO contest
+            )
+            LET games_played = (
+                FOR e IN [["mg_c1", "mg_catan"], ["mg_c1", "mg_azul"], ["mg_c2", "mg_catan"]]
+                    INSERT { _from: CONCAT("contest/", e[0]), _to: CONCAT("game/", e[1]) } INTO played_with
+            )
+            LET outcomes = (
+                FOR c IN ["mg_c1", "mg_c2"]
+                    FOR o IN [["mg_me", 1, "won"], ["mg_you", 2, "lost"]]
+                        INSERT { _from: CONCAT("contest/", c), _to: CONCAT("player/", o[0]), place: o[1], result: o[2] } INTO resulted_in
+            )
+            RETURN LENGTH(outcomes)
+            "#,
+        )
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed multi-game data")?;
+
+    let performance = repo
+        .get_my_game_performance("player/mg_me")
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let plays: Vec<(&str, i32, i32)> = performance
+        .iter()
+        .map(|p| (p.game_name.as_str(), p.total_plays, p.wins))
+        .collect();
+    assert_eq!(plays, [("Catan", 2, 2), ("Azul", 1, 1)]);
+
+    let popularity = repo
+        .get_game_difficulty_popularity()
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let popularity: Vec<(&str, i32)> = popularity
+        .iter()
+        .map(|(name, _, contests, _)| (name.as_str(), *contests))
+        .collect();
+    assert_eq!(popularity, [("Catan", 2), ("Azul", 1)]);
+
+    let stats = repo
+        .get_platform_stats()
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let top_games: Vec<(&str, i32)> = stats
+        .top_games
+        .iter()
+        .map(|g| (g.game_name.as_str(), g.plays))
+        .collect();
+    assert_eq!(top_games, [("Catan", 2), ("Azul", 1)]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_contest_heatmap_scopes_by_date_range_and_venue() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let db = env.database().await?;
+
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            LET recent = DATE_ISO8601(DATE_SUBTRACT(DATE_NOW(), 3, "days"))
+            LET venues = (
+                FOR v IN ["hm_pub", "hm_cafe"]
+                    INSERT { _key: v, displayName: v } INTO venue
+            )
+            LET rows = [
+                ["hm_march_pub", "2024-03-04T18:00:00Z", "hm_pub"],
+                ["hm_march_cafe", "2024-03-05T20:00:00Z", "hm_cafe"],
+                ["hm_june_pub", "2024-06-01T12:00:00Z", "hm_pub"],
+                ["hm_recent_pub", recent, "hm_pub"],
+                ["hm_recent_cafe", recent, "hm_cafe"]
+            ]
+            LET contests = (
+                FOR c IN rows
+                    INSERT { _key: c[0], name: c[0], start: c[1], stop: c[1] } INTO contest
+            )
+            LET venues_played = (
+                FOR c IN rows
+                    INSERT { _from: CONCAT("contest/", c[0]), _to: CONCAT("venue/", c[2]) } INTO played_at
+            )
+            RETURN LENGTH(venues_played)
+            "#,
+        )
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed heatmap data")?;
+
+    let date = |s: &str| chrono::DateTime::parse_from_rfc3339(s).unwrap();
+    let hours = |mut rows: Vec<backend::analytics::repository::HeatRow>| {
+        rows.sort_by_key(|r| r.hour);
+        rows.iter().map(|r| (r.hour, r.plays)).collect::<Vec<_>>()
+    };
+
+    let march = repo
+        .get_contest_heatmap(
+            8,
+            Some(date("2024-03-01T00:00:00Z")),
+            Some(date("2024-03-31T23:59:59Z")),
+            None,
+            None,
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(hours(march), [(18, 1), (20, 1)]);
+
+    let pub_2024 = repo
+        .get_contest_heatmap(
+            8,
+            Some(date("2024-01-01T00:00:00Z")),
+            Some(date("2024-12-31T23:59:59Z")),
+            None,
+            Some("venue/hm_pub"),
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(hours(pub_2024), [(12, 1), (18, 1)]);
+
+    // Without a range the relative window applies, venue filter included
+    let recent_cafe = repo
+        .get_contest_heatmap(8, None, None, None, Some("venue/hm_cafe"))
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(recent_cafe.iter().map(|r| r.plays).sum::<i64>(), 1);
+
+    let recent = repo
+        .get_contest_heatmap(8, None, None, None, None)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(recent.iter().map(|r| r.plays).sum::<i64>(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_contest_heatmap_falls_back_to_default_timezone() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env)
+        .await?
+        .with_default_timezone("America/New_York");
+    let db = env.database().await?;
+
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            LET venues = (
+                FOR v IN [["tz_none", ""], ["tz_berlin", "Europe/Berlin"]]
+                    INSERT { _key: v[0], displayName: v[0], timezone: v[1] } INTO venue
+            )
+            LET rows = [
+                ["tz_none_contest", "2024-03-04T18:00:00Z", "tz_none"],
+                ["tz_berlin_contest", "2024-03-05T20:00:00Z", "tz_berlin"]
+            ]
+            LET contests = (
+                FOR c IN rows
+                    INSERT { _key: c[0], name: c[0], start: c[1], stop: c[1] } INTO contest
+            )
+            LET venues_played = (
+                FOR c IN rows
+                    INSERT { _from: CONCAT("contest/", c[0]), _to: CONCAT("venue/", c[2]) } INTO played_at
+            )
+            RETURN LENGTH(venues_played)
+            "#,
+        )
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed heatmap data")?;
+
+    let date = |s: &str| chrono::DateTime::parse_from_rfc3339(s).unwrap();
+    let mut rows = repo
+        .get_contest_heatmap(
+            8,
+            Some(date("2024-03-01T00:00:00Z")),
+            Some(date("2024-03-31T23:59:59Z")),
+            None,
+            None,
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    rows.sort_by_key(|r| r.hour);
+
+    // 18:00Z is 13:00 in New York (EST); 20:00Z is 21:00 in Berlin (CET)
+    let buckets: Vec<(i32, i64, i64)> = rows
+        .iter()
+        .map(|r| (r.hour, r.plays, r.fallbacks))
+        .collect();
+    assert_eq!(buckets, [(13, 1, 1), (21, 1, 0)]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_featured_contest_picks_the_most_exciting_recent_contest() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let db = env.database().await?;
+
+    // fc_close is the tightest finish completed this week; fc_old is tighter
+    // but a month old, and fc_upcoming has not finished yet
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            INSERT { _key: "fc_venue", displayName: "The Meeple Den" } INTO venue
+            INSERT { _key: "fc_game", name: "Brass" } INTO game
+            LET players = (
+                FOR h IN ["fc_ann", "fc_bo"]
+                    INSERT { _key: h, handle: h, email: CONCAT(h, "@example.com") } INTO player
+            )
+            LET rows = [
+                ["fc_close", DATE_SUBTRACT(DATE_NOW(), 2, "days"), [100, 98]],
+                ["fc_blowout", DATE_SUBTRACT(DATE_NOW(), 1, "days"), [100, 40]],
+                ["fc_old", DATE_SUBTRACT(DATE_NOW(), 30, "days"), [50, 50]],
+                ["fc_upcoming", DATE_ADD(DATE_NOW(), 1, "days"), [70, 70]]
+            ]
+            LET contests = (
+                FOR c IN rows
+                    INSERT {
+                        _key: c[0],
+                        name: c[0],
+                        start: DATE_ISO8601(DATE_SUBTRACT(c[1], 2, "hours")),
+                        stop: DATE_ISO8601(c[1])
+                    } INTO contest
+            )
+            LET edges = (
+                FOR c IN rows
+                    INSERT { _from: CONCAT("contest/", c[0]), _to: "venue/fc_venue" } INTO played_at
+                    INSERT { _from: CONCAT("contest/", c[0]), _to: "game/fc_game" } INTO played_with
+                    RETURN 1
+            )
+            FOR c IN rows
+                FOR i IN [0, 1]
+                    INSERT {
+                        _from: CONCAT("contest/", c[0]),
+                        _to: CONCAT("player/", ["fc_ann", "fc_bo"][i]),
+                        place: i + 1,
+                        result: i == 0 ? "won" : "lost",
+                        score: c[2][i]
+                    } INTO resulted_in
+            "#,
+        )
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed featured contest data")?;
+
+    let featured = repo
+        .get_featured_contest(7)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?
+        .expect("a contest should be featured");
+    assert_eq!(featured.contest_id, "contest/fc_close");
+    assert!((featured.excitement_rating - 9.9).abs() < 1e-9);
+    assert_eq!(featured.venue.as_deref(), Some("The Meeple Den"));
+    assert_eq!(featured.games, ["Brass"]);
+    let standings: Vec<(&str, i32, &str)> = featured
+        .standings
+        .iter()
+        .map(|s| (s.handle.as_str(), s.place, s.result.as_str()))
+        .collect();
+    assert_eq!(standings, [("fc_ann", 1, "won"), ("fc_bo", 2, "lost")]);
+
+    // A wider window reaches the older dead heat
+    let featured = repo
+        .get_featured_contest(60)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?
+        .expect("a contest should be featured");
+    assert_eq!(featured.contest_id, "contest/fc_old");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_biggest_upset_surfaces_low_rated_winner_over_favourite() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let db = env.database().await?;
+
+    // up_high went in at 1800 (rated 1900 since); up_low's win over them is
+    // a 700 point upset, up_mid's win over them only 300
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            LET ratings = [["up_low", 1100], ["up_mid", 1500], ["up_high", 1900]]
+            LET players = (
+                FOR r IN ratings
+                    INSERT { _key: r[0], handle: r[0], email: CONCAT(r[0], "@example.com") } INTO player
+            )
+            LET latest = (
+                FOR r IN ratings
+                    INSERT {
+                        player_id: CONCAT("player/", r[0]),
+                        scope_type: "global",
+                        scope_id: null,
+                        rating: r[1]
+                    } INTO rating_latest
+            )
+            INSERT {
+                player_id: "player/up_high",
+                scope_type: "global",
+                scope_id: null,
+                period_end: "2020-01-01T00:00:00Z",
+                rating: 1800
+            } INTO rating_history
+            LET rows = [
+                ["up_big", 5, [["up_low", 1], ["up_mid", 2], ["up_high", 3]]],
+                ["up_minor", 3, [["up_mid", 1], ["up_high", 2]]],
+                ["up_chalk", 1, [["up_high", 1], ["up_low", 2]]]
+            ]
+            LET contests = (
+                FOR c IN rows
+                    LET stop = DATE_SUBTRACT(DATE_NOW(), c[1], "days")
+                    INSERT {
+                        _key: c[0],
+                        name: c[0],
+                        start: DATE_ISO8601(DATE_SUBTRACT(stop, 2, "hours")),
+                        stop: DATE_ISO8601(stop)
+                    } INTO contest
+            )
+            FOR c IN rows
+                FOR o IN c[2]
+                    INSERT {
+                        _from: CONCAT("contest/", c[0]),
+                        _to: CONCAT("player/", o[0]),
+                        place: o[1],
+                        result: o[1] == 1 ? "won" : "lost"
+                    } INTO resulted_in
+            "#,
+        )
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed upset data")?;
+
+    let upset = repo
+        .get_biggest_upset(30)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?
+        .expect("an upset should be found");
+    assert_eq!(upset.contest_id, "contest/up_big");
+    assert_eq!(upset.winner.handle, "up_low");
+    assert_eq!(upset.winner.rating, 1100.0);
+    assert_eq!(upset.favourite.handle, "up_high");
+    assert_eq!(upset.favourite.place, 3);
+    assert_eq!(upset.favourite.rating, 1800.0);
+    assert_eq!(upset.rating_gap, 700.0);
+
+    // The last two days only hold the favourite's own win
+    assert!(repo
+        .get_biggest_upset(2)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?
+        .is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_player_summary_populates_every_section() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let db = env.database().await?;
+
+    // sum_me plays Catan twice and Azul once, winning the two earliest contests
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            LET games = (
+                FOR g IN [["sum_catan", "Catan"], ["sum_azul", "Azul"]]
+                    INSERT { _key: g[0], name: g[1] } INTO game
+            )
+            LET players = (
+                FOR p IN ["sum_me", "sum_you"]
+                    INSERT {
+                        _key: p,
+                        firstname: "Sam",
+                        lastname: p,
+                        handle: p,
+                        email: CONCAT(p, "@example.com")
+                    } INTO player
+            )
+            INSERT {
+                player_id: "player/sum_me",
+                scope_type: "global",
+                scope_id: null,
+                rating: 1650,
+                rd: 80,
+                games_played: 3
+            } INTO rating_latest
+            LET rows = [
+                ["sum_c1", "2024-01-01", "sum_catan", 1],
+                ["sum_c2", "2024-02-01", "sum_azul", 1],
+                ["sum_c3", "2024-03-01", "sum_catan", 2]
+            ]
+            FOR c IN rows
+                INSERT {
+                    _key: c[0],
+                    name: c[0],
+                    start: CONCAT(c[1], "T18:00:00Z"),
+                    stop: CONCAT(c[1], "T20:00:00Z")
+                } INTO contest
+                INSERT { _from: CONCAT("contest/", c[0]), _to: CONCAT("game/", c[2]) } INTO played_with
+                FOR o IN [["sum_me", c[3]], ["sum_you", 3 - c[3]]]
+                    INSERT {
+                        _from: CONCAT("contest/", c[0]),
+                        _to: CONCAT("player/", o[0]),
+                        place: o[1],
+                        result: o[1] == 1 ? "won" : "lost"
+                    } INTO resulted_in
+            "#,
+        )
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed summary data")?;
+
+    let usecase = AnalyticsUseCase::new(repo);
+    let summary = usecase
+        .get_player_summary("player/sum_me")
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?
+        .expect("seeded player should have a summary");
+
+    assert_eq!(summary.profile.handle, "sum_me");
+    assert_eq!(summary.profile.email, "sum_me@example.com");
+    assert_eq!(summary.stats.total_contests, 3);
+    assert_eq!(summary.stats.total_wins, 2);
+    let rating = summary.rating.expect("rating should be populated");
+    assert_eq!((rating.rating, rating.games_played), (1650.0, 3));
+    assert!(!summary.rankings.is_empty());
+    assert_eq!(summary.recent_form, [2, 1, 1]);
+    let game = summary
+        .most_played_game
+        .expect("most played game should be populated");
+    assert_eq!((game.game_name.as_str(), game.total_plays), ("Catan", 2));
+    assert!(summary.achievements_total > 0);
+    assert!(summary.achievements_unlocked > 0);
+    assert!(summary.achievement_completion > 0.0);
+
+    assert!(usecase
+        .get_player_summary("player/sum_nobody")
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?
+        .is_none());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_group_head_to_head_returns_every_pair_symmetrically() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let db = env.database().await?;
+
+    // gh_x plays alongside the group but is not part of it
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            LET players = (
+                FOR p IN ["gh_a", "gh_b", "gh_c", "gh_x"]
+                    INSERT { _key: p, handle: p, email: CONCAT(p, "@example.com") } INTO player
+            )
+            LET results = [
+                ["gh_c1", "gh_a", 1], ["gh_c1", "gh_b", 2], ["gh_c1", "gh_c", 3],
+                ["gh_c2", "gh_b", 1], ["gh_c2", "gh_a", 2],
+                ["gh_c3", "gh_a", 1], ["gh_c3", "gh_c", 1], ["gh_c3", "gh_x", 2]
+            ]
+            LET contests = (
+                FOR c IN ["gh_c1", "gh_c2", "gh_c3"]
+                    INSERT { _key: c, name: c, start: "2024-01-01T18:00:00Z", stop: "2024-01-01T20:00:00Z" } INTO contest
+            )
+            FOR r IN results
+                INSERT { _from: CONCAT("contest/", r[0]), _to: CONCAT("player/", r[1]), place: r[2], result: r[2] == 1 ? "won" : "lost" } INTO resulted_in
+            "#,
+        )
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed group head-to-head data")?;
+
+    let group: Vec<String> = ["player/gh_a", "player/gh_b", "player/gh_c"]
+        .iter()
+        .map(|id| id.to_string())
+        .collect();
+    let records = repo
+        .get_group_head_to_head(&group)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(records.len(), 6, "every ordered pair of three players");
+    assert!(records.iter().all(|r| r.opponent_id != "player/gh_x"));
+
+    let record = |player: &str, opponent: &str| {
+        records
+            .iter()
+            .find(|r| r.player_id == player && r.opponent_id == opponent)
+            .unwrap_or_else(|| panic!("missing record {} vs {}", player, opponent))
+    };
+    for (i, player) in group.iter().enumerate() {
+        for opponent in &group[i + 1..] {
+            let forward = record(player, opponent);
+            let back = record(opponent, player);
+            assert_eq!(forward.total_contests, back.total_contests);
+            assert_eq!(forward.wins, back.losses);
+            assert_eq!(forward.losses, back.wins);
+            assert_eq!(forward.ties, back.ties);
+        }
+    }
+
+    let a_vs_b = record("player/gh_a", "player/gh_b");
+    assert_eq!((a_vs_b.total_contests, a_vs_b.wins, a_vs_b.ties), (2, 1, 0));
+    assert_eq!(a_vs_b.win_rate, 50.0);
+    let a_vs_c = record("player/gh_a", "player/gh_c");
+    assert_eq!((a_vs_c.total_contests, a_vs_c.wins, a_vs_c.ties), (2, 1, 1));
+    let c_vs_b = record("player/gh_c", "player/gh_b");
+    assert_eq!((c_vs_b.total_contests, c_vs_b.losses), (1, 1));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_display_labels_resolve_in_one_query_and_are_cached() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let db = env.database().await?;
+
+    let _: Vec<serde_json::Value> = db
+        .aql_str(
+            r#"
+            FOR p IN [
+                { _key: "dl_handle", handle: "handle_only", email: "dl_handle@example.com" },
+                { _key: "dl_email", email: "dl_email@example.com" },
+                { _key: "dl_name", firstname: "Dana", lastname: "Lee" }
+            ]
+                INSERT p INTO player
+            "#,
+        )
+        .await
+        .context("Failed to seed players")?;
+
+    let ids: Vec<String> = [
+        "player/dl_handle",
+        "player/dl_email",
+        "player/dl_handle",
+        "player/dl_name",
+        "player/dl_missing",
+        "player/dl_email",
+    ]
+    .iter()
+    .map(|id| id.to_string())
+    .collect();
+
+    let before = repo.queries_run();
+    let labels = repo
+        .get_player_display_labels(&ids)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(repo.queries_run() - before, 1, "one query for the batch");
+    assert_eq!(labels.len(), 3, "one label per existing player");
+    assert_eq!(labels["player/dl_handle"], "handle_only");
+    assert_eq!(labels["player/dl_email"], "dl_email@example.com");
+    assert_eq!(labels["player/dl_name"], "Dana Lee");
+
+    // The usecase reads through its cache, so a second lookup skips the database
+    let usecase = AnalyticsUseCase::new(repo.clone());
+    let first = usecase
+        .get_player_display_labels(&ids)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let before = repo.queries_run();
+    let second = usecase
+        .get_player_display_labels(&ids)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(first, labels);
+    assert_eq!(second, labels);
+    assert_eq!(repo.queries_run(), before, "cached labels need no query");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_win_rate_leaderboard_hides_inactive_players_outside_all_time_view() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let db = env.database().await?;
+
+    // idle_champ won everything two years ago; idle_regular played last week
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            LET players = (
+                FOR p IN ["idle_champ", "idle_regular"]
+                    INSERT { _key: p, handle: p, email: CONCAT(p, "@example.com") } INTO player
+            )
+            LET old_start = DATE_ISO8601(DATE_SUBTRACT(DATE_NOW(), 2, "years"))
+            LET recent_start = DATE_ISO8601(DATE_SUBTRACT(DATE_NOW(), 7, "days"))
+            LET results = [
+                ["idle_c1", old_start, "idle_champ", 1],
+                ["idle_c2", old_start, "idle_champ", 1],
+                ["idle_c2", old_start, "idle_regular", 2],
+                ["idle_c3", recent_start, "idle_regular", 1]
+            ]
+            LET contests = (
+                FOR c IN [["idle_c1", old_start], ["idle_c2", old_start], ["idle_c3", recent_start]]
+                    INSERT { _key: c[0], name: c[0], start: c[1], stop: c[1] } INTO contest
+            )
+            FOR r IN results
+                INSERT { _from: CONCAT("contest/", r[0]), _to: CONCAT("player/", r[2]), place: r[3], result: r[3] == 1 ? "won" : "lost" } INTO resulted_in
+            "#,
+        )
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed leaderboard activity data")?;
+
+    let handles = |board: &[shared::models::analytics::PlayerWinRate]| -> Vec<String> {
+        board.iter().map(|p| p.player_handle.clone()).collect()
+    };
+
+    let all_time = repo
+        .get_leaderboard("win_rate", 10, 0, None)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(handles(&all_time), ["idle_champ", "idle_regular"]);
+
+    let active = repo
+        .get_leaderboard("win_rate", 10, 0, Some(90))
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(handles(&active), ["idle_regular"]);
+
+    // Count-based boards are not filtered by activity
+    let total_wins = repo
+        .get_leaderboard("total_wins", 10, 0, Some(90))
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert!(handles(&total_wins).contains(&"idle_champ".to_string()));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_contests_by_venue_pages_newest_first_within_date_range() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let db = env.database().await?;
+
+    // vh_me played vh_c1..vh_c5 at vh_home, one night apart; vh_skip was at
+    // the same venue without vh_me and vh_away elsewhere
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            LET players = (
+                FOR p IN ["vh_me", "vh_rival"]
+                    INSERT { _key: p, handle: p, email: CONCAT(p, "@example.com") } INTO player
+            )
+            LET venues = (
+                FOR v IN ["vh_home", "vh_away"]
+                    INSERT { _key: v, displayName: v } INTO venue
+            )
+            LET contests = [
+                ["vh_c1", "2024-01-01T19:00:00Z", "vh_home", ["vh_me", "vh_rival"]],
+                ["vh_c2", "2024-01-02T19:00:00Z", "vh_home", ["vh_rival", "vh_me"]],
+                ["vh_c3", "2024-01-03T19:00:00Z", "vh_home", ["vh_me", "vh_rival"]],
+                ["vh_c4", "2024-01-04T19:00:00Z", "vh_home", ["vh_me", "vh_rival"]],
+                ["vh_c5", "2024-01-05T19:00:00Z", "vh_home", ["vh_me", "vh_rival"]],
+                ["vh_skip", "2024-01-06T19:00:00Z", "vh_home", ["vh_rival"]],
+                ["vh_away", "2024-01-07T19:00:00Z", "vh_away", ["vh_me"]]
+            ]
+            FOR c IN contests
+                INSERT { _key: c[0], name: c[0], start: c[1], stop: c[1] } INTO contest
+                LET results = (
+                    FOR i IN 0..LENGTH(c[3]) - 1
+                        INSERT { _from: CONCAT("contest/", c[0]), _to: CONCAT("player/", c[3][i]), place: i + 1, result: i == 0 ? "won" : "lost" } INTO resulted_in
+                )
+                INSERT { _from: CONCAT("contest/", c[0]), _to: CONCAT("venue/", c[2]) } INTO played_at
+            "#,
+        )
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed venue history data")?;
+
+    let page = |from: Option<&str>, to: Option<&str>, limit: i32, offset: i32| {
+        let repo = repo.clone();
+        let parse = |d: Option<&str>| d.map(|d| chrono::DateTime::parse_from_rfc3339(d).unwrap());
+        let (from, to) = (parse(from), parse(to));
+        async move {
+            repo.get_contests_by_venue("player/vh_me", "vh_home", from, to, limit, offset)
+                .await
+                .map_err(|e| anyhow::anyhow!("{}", e))
+        }
+    };
+    let names = |page: &shared::dto::analytics::VenueContestsPageDto| -> Vec<String> {
+        page.items
+            .iter()
+            .map(|c| c["contest_name"].as_str().unwrap_or_default().to_string())
+            .collect()
+    };
+
+    let first = page(None, None, 2, 0).await?;
+    assert_eq!(first.total, 5);
+    assert_eq!(names(&first), ["vh_c5", "vh_c4"]);
+    assert_eq!(first.items[0]["total_players"], 2);
+    assert_eq!(first.items[0]["players"][0]["player_handle"], "vh_me");
+
+    let last = page(None, None, 2, 4).await?;
+    assert_eq!(last.total, 5);
+    assert_eq!(names(&last), ["vh_c1"]);
+
+    let past_end = page(None, None, 2, 5).await?;
+    assert_eq!(past_end.total, 5);
+    assert!(past_end.items.is_empty());
+
+    // Both bounds are inclusive
+    let ranged = page(
+        Some("2024-01-02T19:00:00Z"),
+        Some("2024-01-04T19:00:00Z"),
+        10,
+        0,
+    )
+    .await?;
+    assert_eq!(ranged.total, 3);
+    assert_eq!(names(&ranged), ["vh_c4", "vh_c3", "vh_c2"]);
+    assert_eq!(ranged.items[2]["my_placement"], 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_all_player_stats_sort_by_each_key_and_page() -> Result<()> {
+    use shared::dto::analytics::PlayerStatsSort;
+
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let db = env.database().await?;
+
+    // ps_b and ps_d tie on skill rating and are ordered by player id
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            FOR s IN [
+                ["ps_a", 1600, 40.0, 10],
+                ["ps_b", 1500, 70.0, 3],
+                ["ps_c", 1400, 55.0, 20],
+                ["ps_d", 1500, 10.0, 7]
+            ]
+                INSERT {
+                    player_id: CONCAT("player/", s[0]),
+                    total_contests: s[3],
+                    total_wins: 0,
+                    total_losses: 0,
+                    win_rate: s[2],
+                    average_placement: 2.0,
+                    best_placement: 1,
+                    skill_rating: s[1],
+                    rating_confidence: 0.5,
+                    total_points: 0,
+                    current_streak: 0,
+                    longest_streak: 0,
+                    last_updated: "2024-01-01T00:00:00Z"
+                } INTO player_stats
+            "#,
+        )
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed player stats")?;
+
+    let page = |sort_by: PlayerStatsSort, limit: i32, offset: i32| {
+        let repo = repo.clone();
+        async move {
+            let (stats, total) = repo
+                .get_all_player_stats(sort_by, limit, offset)
+                .await
+                .map_err(|e| anyhow::anyhow!("{}", e))?;
+            let ids: Vec<String> = stats
+                .into_iter()
+                .map(|s| s.player_id.trim_start_matches("player/").to_string())
+                .collect();
+            anyhow::Ok((ids, total))
+        }
+    };
+
+    let (ids, total) = page(PlayerStatsSort::SkillRating, 10, 0).await?;
+    assert_eq!(total, 4);
+    assert_eq!(ids, ["ps_a", "ps_b", "ps_d", "ps_c"]);
+    let (ids, _) = page(PlayerStatsSort::WinRate, 10, 0).await?;
+    assert_eq!(ids, ["ps_b", "ps_c", "ps_a", "ps_d"]);
+    let (ids, _) = page(PlayerStatsSort::TotalContests, 10, 0).await?;
+    assert_eq!(ids, ["ps_c", "ps_a", "ps_d", "ps_b"]);
+
+    // Pages follow on from each other, and the total covers every page
+    let (first, total) = page(PlayerStatsSort::SkillRating, 2, 0).await?;
+    assert_eq!(first, ["ps_a", "ps_b"]);
+    assert_eq!(total, 4);
+    let (second, total) = page(PlayerStatsSort::SkillRating, 2, 2).await?;
+    assert_eq!(second, ["ps_d", "ps_c"]);
+    assert_eq!(total, 4);
+    let (past_end, total) = page(PlayerStatsSort::SkillRating, 2, 4).await?;
+    assert!(past_end.is_empty());
+    assert_eq!(total, 4);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_drifted_player_stats_are_detected_and_repaired() -> Result<()> {
+    use backend::admin::stats_check::{self, StatsDiscrepancy};
+
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    analytics_repo(&env).await?;
+    let db = env.database().await?;
+
+    // sc_ok's stored stats match three contests with two wins; sc_drift won
+    // one of two but its stats were bumped twice more without edges
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            LET contests = [
+                ["sc_c1", ["sc_ok", "sc_drift"]],
+                ["sc_c2", ["sc_drift", "sc_ok"]],
+                ["sc_c3", ["sc_ok"]]
+            ]
+            LET edges = (
+                FOR c IN contests
+                    INSERT { _key: c[0], name: c[0], start: "2024-01-01T19:00:00Z" } INTO contest
+                    FOR i IN 0..LENGTH(c[1]) - 1
+                        INSERT { _from: CONCAT("contest/", c[0]), _to: CONCAT("player/", c[1][i]), place: TO_STRING(i + 1), result: i == 0 ? "won" : "lost" } INTO resulted_in
+            )
+            FOR s IN [["sc_ok", 3, 2], ["sc_drift", 4, 3]]
+                INSERT {
+                    player_id: CONCAT("player/", s[0]),
+                    total_contests: s[1],
+                    total_wins: s[2],
+                    total_losses: s[1] - s[2],
+                    win_rate: s[2] * 100.0 / s[1],
+                    skill_rating: 1200
+                } INTO player_stats
+            "#,
+        )
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed drifted stats")?;
+
+    let discrepancies = stats_check::find_stats_discrepancies(&db)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(
+        discrepancies,
+        [StatsDiscrepancy {
+            player_id: "player/sc_drift".to_string(),
+            stored_contests: 4,
+            actual_contests: 2,
+            stored_wins: 3,
+            actual_wins: 1,
+        }]
+    );
+
+    let repaired = stats_check::repair_player_stats(&db, &discrepancies)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(repaired, 1);
+    assert!(stats_check::find_stats_discrepancies(&db)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?
+        .is_empty());
+
+    let stats: Vec<serde_json::Value> = db
+        .aql_query(
+            arangors::AqlQuery::builder()
+                .query("FOR s IN player_stats FILTER s.player_id == 'player/sc_drift' RETURN s")
+                .build(),
+        )
+        .await
+        .context("Failed to read repaired stats")?;
+    assert_eq!(stats[0]["total_contests"], 2);
+    assert_eq!(stats[0]["total_wins"], 1);
+    assert_eq!(stats[0]["total_losses"], 1);
+    assert_eq!(stats[0]["win_rate"].as_f64(), Some(50.0));
+    assert_eq!(stats[0]["skill_rating"], 1200);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_completion_rate_counts_only_placed_participants() -> Result<()> {
+    use backend::admin::stats_check;
+
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let db = env.database().await?;
+
+    // Two of the four participants dropped out with place 0; the persisted
+    // stats carry the old figure that counted everyone as finished
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            INSERT { _key: "cr_c1", name: "cr_c1", start: "2024-01-01T18:00:00Z", stop: "2024-01-01T20:00:00Z" } INTO contest
+            LET edges = (
+                FOR o IN [["cr_a", 1], ["cr_b", 2], ["cr_c", 0], ["cr_d", 0]]
+                    INSERT { _from: "contest/cr_c1", _to: CONCAT("player/", o[0]), place: o[1], result: o[1] == 1 ? "won" : "lost" } INTO resulted_in
+            )
+            INSERT {
+                contest_id: "contest/cr_c1",
+                participant_count: 4,
+                completion_count: 4,
+                completion_rate: 100.0
+            } INTO contest_stats
+            "#,
+        )
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed partially completed contest")?;
+
+    let recent = repo
+        .get_recent_contests(10)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let contest = recent
+        .iter()
+        .find(|c| c.contest_id == "contest/cr_c1")
+        .expect("Contest should be listed");
+    assert_eq!(contest.participant_count, 4);
+    assert_eq!(contest.completion_count, 2);
+    assert_eq!(contest.completion_rate, 50.0);
+
+    let repaired = stats_check::repair_contest_completion_rates(&db)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(repaired, 1);
+    let stats: Vec<serde_json::Value> = db
+        .aql_query(
+            arangors::AqlQuery::builder()
+                .query("FOR s IN contest_stats FILTER s.contest_id == 'contest/cr_c1' RETURN s")
+                .build(),
+        )
+        .await
+        .context("Failed to read repaired contest stats")?;
+    assert_eq!(stats[0]["completion_count"], 2);
+    assert_eq!(stats[0]["completion_rate"].as_f64(), Some(50.0));
+
+    // Already correct stats are left alone
+    assert_eq!(
+        stats_check::repair_contest_completion_rates(&db)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}", e))?,
+        0
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_private_contest_is_left_out_of_platform_stats_only() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let db = env.database().await?;
+
+    // The same player at a public and a private game night, each with its
+    // own game and venue
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            LET recent = DATE_ISO8601(DATE_SUBTRACT(DATE_NOW(), 3, "days"))
+            INSERT { _key: "vis_p", handle: "vis_p", email: "vis_p@example.com" } INTO player
+            LET rows = [["vis_public", "public"], ["vis_private", "private"]]
+            LET seeded = (
+                FOR r IN rows
+                    INSERT { _key: r[0], name: r[0] } INTO game
+                    INSERT { _key: r[0], displayName: r[0] } INTO venue
+                    INSERT { _key: r[0], name: r[0], start: recent, stop: recent, visibility: r[1] } INTO contest
+                    INSERT { _from: CONCAT("contest/", r[0]), _to: CONCAT("game/", r[0]) } INTO played_with
+                    INSERT { _from: CONCAT("contest/", r[0]), _to: CONCAT("venue/", r[0]) } INTO played_at
+                    INSERT { _from: CONCAT("contest/", r[0]), _to: "player/vis_p", place: 1, result: "won" } INTO resulted_in
+                    RETURN r[0]
+            )
+            RETURN LENGTH(seeded)
+            "#,
+        )
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed public and private contests")?;
+
+    let heatmap = repo
+        .get_contest_heatmap(8, None, None, None, None)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(heatmap.iter().map(|r| r.plays).sum::<i64>(), 1);
+
+    let trends = repo
+        .get_contest_trends(3, true)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(trends.iter().map(|t| t.contests).sum::<i32>(), 1);
+
+    let stats = repo
+        .get_platform_stats_top(10)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let top_games: Vec<&str> = stats
+        .top_games
+        .iter()
+        .map(|g| g.game_name.as_str())
+        .collect();
+    assert_eq!(top_games, ["vis_public"]);
+    assert!(stats
+        .top_venues
+        .iter()
+        .all(|v| v.venue_name != "vis_private" || v.contests_held == 0));
+    assert_eq!(stats.total_contests, 1);
+    assert_eq!(stats.contests_30d, 1);
+
+    let recent = repo
+        .get_recent_contests(10)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert!(recent.iter().all(|c| c.contest_id != "contest/vis_private"));
+
+    // The player's own history still has both contests
+    let mut history: Vec<String> = repo
+        .get_player_contest_results("player/vis_p")
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?
+        .into_iter()
+        .map(|r| r.contest_id)
+        .collect();
+    history.sort();
+    assert_eq!(history, ["contest/vis_private", "contest/vis_public"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_private_contest_is_left_out_of_game_and_venue_stats() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let db = env.database().await?;
+
+    // A public and a private game night with the same game at the same venue
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            INSERT { _key: "pv_game", name: "Private Game" } INTO game
+            INSERT { _key: "pv_venue", displayName: "Private Venue" } INTO venue
+            LET players = (
+                FOR p IN ["pv_ann", "pv_ben"]
+                    INSERT { _key: p, handle: p, email: CONCAT(p, "@example.com") } INTO player
+            )
+            LET contests = (
+                FOR c IN [
+                    ["pv_public", "public", ["pv_ann"]],
+                    ["pv_private", "private", ["pv_ann", "pv_ben"]]
+                ]
+                    INSERT { _key: c[0], name: c[0], start: "2024-05-03T19:00:00Z", stop: "2024-05-03T21:00:00Z", visibility: c[1] } INTO contest
+                    INSERT { _from: CONCAT("contest/", c[0]), _to: "game/pv_game" } INTO played_with
+                    INSERT { _from: CONCAT("contest/", c[0]), _to: "venue/pv_venue" } INTO played_at
+                    FOR p IN c[2]
+                        INSERT { _from: CONCAT("contest/", c[0]), _to: CONCAT("player/", p), place: 1, result: "won" } INTO resulted_in
+            )
+            RETURN LENGTH(contests)
+            "#,
+        )
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed public and private contests")?;
+
+    let game = repo
+        .compute_game_stats("game/pv_game")
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(game.total_plays, 1);
+    assert_eq!(game.unique_players, 1);
+
+    let venue = repo
+        .compute_venue_stats("venue/pv_venue")
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(venue.total_contests, 1);
+    assert_eq!(venue.unique_players, 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_performance_trends_follow_rating_history() -> Result<()> {
+    use chrono::{Datelike, Months, Utc};
+
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let db = env.database().await?;
+
+    // Periods end on the first of the following month, as the ratings
+    // recompute records them; the month before last has no period of its own
+    let this_month = Utc::now().date_naive().with_day(1).unwrap();
+    let month = |back: u32| this_month - Months::new(back);
+    let period_end = |back: u32| {
+        (month(back) + Months::new(1))
+            .format("%Y-%m-01T00:00:00Z")
+            .to_string()
+    };
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            INSERT { _key: "trend_p", handle: "trend_p", email: "trend_p@example.com" } INTO player
+            FOR h IN @history
+                INSERT {
+                    player_id: "player/trend_p",
+                    scope_type: "global",
+                    scope_id: null,
+                    period_end: h[0],
+                    rating: h[1]
+                } INTO rating_history
+            "#,
+        )
+        .bind_var(
+            "history",
+            serde_json::json!([[period_end(2), 1580.0], [period_end(0), 1650.0]]),
+        )
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed rating history")?;
+
+    let trends = repo
+        .get_my_performance_trends("player/trend_p", None, None)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let rating_in = |back: u32| {
+        let key = month(back).format("%Y-%m").to_string();
+        trends
+            .iter()
+            .find(|t| t.month == key)
+            .unwrap_or_else(|| panic!("no trend for {}", key))
+            .skill_rating
+    };
+    assert_eq!(
+        rating_in(3),
+        1500.0,
+        "unrated months use the starting rating"
+    );
+    assert_eq!(rating_in(2), 1580.0);
+    assert_eq!(
+        rating_in(1),
+        1580.0,
+        "a month without a period keeps the last rating"
+    );
+    assert_eq!(rating_in(0), 1650.0);
+
+    Ok(())
+}
+
+async fn insert_contests(db: &Database<ReqwestClient>, keys: &[&str]) -> Result<()> {
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            FOR k IN @keys
+                INSERT { _key: k, name: k, start: "2024-01-01T18:00:00Z", stop: "2024-01-01T20:00:00Z" } INTO contest
+            "#,
+        )
+        .bind_var("keys", keys.to_vec())
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed contests")?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_flushed_analytics_cache_serves_fresh_values() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+    let usecase = AnalyticsUseCase::with_cache(repo, app_data.analytics_cache.get_ref().clone());
+    let db = env.database().await?;
+
+    // First test: computes and caches platform stats
+    insert_contests(&db, &["fc_c1", "fc_c2"]).await?;
+    let first = usecase
+        .get_platform_stats()
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(first.total_contests, 2);
+
+    // Second test: changes the data; the cached value is stale until flushed
+    insert_contests(&db, &["fc_c3"]).await?;
+    let stale = usecase
+        .get_platform_stats()
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(stale.total_contests, 2);
+
+    assert!(app_data.flush_analytics_cache().await > 0);
+    let fresh = usecase
+        .get_platform_stats()
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(fresh.total_contests, 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_heatmap_caches_each_week_range_separately() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+    let usecase = AnalyticsUseCase::with_cache(repo, app_data.analytics_cache.get_ref().clone());
+    let db = env.database().await?;
+
+    let seed_contest = |key: &'static str, days_ago: i32| {
+        arangors::AqlQuery::builder()
+            .query(
+                r#"
+                LET start = DATE_ISO8601(DATE_SUBTRACT(DATE_NOW(), @days_ago, "days"))
+                INSERT { _key: @key, name: @key, start: start, stop: start } INTO contest
+                "#,
+            )
+            .bind_var("key", key)
+            .bind_var("days_ago", days_ago)
+            .build()
+    };
+    let plays = |heatmap: serde_json::Value| -> u64 {
+        heatmap["buckets"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .flat_map(|day| day.as_array().unwrap())
+            .map(|n| n.as_u64().unwrap())
+            .sum()
+    };
+
+    for (key, days_ago) in [("hc_recent", 3), ("hc_older", 70)] {
+        let _: Vec<serde_json::Value> = db
+            .aql_query(seed_contest(key, days_ago))
+            .await
+            .context("Failed to seed heatmap contest")?;
+    }
+
+    let heatmap = |weeks: i32| usecase.get_contest_heatmap(weeks, None, None, None, None);
+    let four_weeks = plays(heatmap(4).await.map_err(|e| anyhow::anyhow!("{}", e))?);
+    let twelve_weeks = plays(heatmap(12).await.map_err(|e| anyhow::anyhow!("{}", e))?);
+    assert_eq!(four_weeks, 1);
+    assert_eq!(
+        twelve_weeks, 2,
+        "the 4 week result must not be served for 12 weeks"
+    );
+
+    // Both ranges are now cached, each with its own value
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed_contest("hc_new", 1))
+        .await
+        .context("Failed to seed heatmap contest")?;
+    assert_eq!(
+        plays(heatmap(4).await.map_err(|e| anyhow::anyhow!("{}", e))?),
+        1
+    );
+    assert_eq!(
+        plays(heatmap(12).await.map_err(|e| anyhow::anyhow!("{}", e))?),
+        2
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_two_way_tie_for_first_credits_both_players_with_a_win() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let db = env.database().await?;
+
+    // tie_a and tie_b share first place; tie_c finishes second
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            INSERT { _key: "tie_game", name: "Pandemic" } INTO game
+            INSERT { _key: "tie_c1", name: "tie_c1", start: "2024-01-01T18:00:00Z", stop: "2024-01-01T20:00:00Z" } INTO contest
+            INSERT { _from: "contest/tie_c1", _to: "game/tie_game" } INTO played_with
+            LET outcomes = [["tie_a", 1, "won"], ["tie_b", 1, "won"], ["tie_c", 2, "lost"]]
+            LET players = (
+                FOR o IN outcomes
+                    INSERT { _key: o[0], handle: o[0], email: CONCAT(o[0], "@example.com") } INTO player
+            )
+            FOR o IN outcomes
+                INSERT { _from: "contest/tie_c1", _to: CONCAT("player/", o[0]), place: o[1], result: o[2] } INTO resulted_in
+            "#,
+        )
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed tied contest")?;
+
+    let leaderboard = repo
+        .get_leaderboard("win_rate", 10, 0, None)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let mut wins: Vec<(&str, i32, i32)> = leaderboard
+        .iter()
+        .map(|p| (p.player_handle.as_str(), p.wins, p.total_plays))
+        .collect();
+    wins.sort();
+    assert_eq!(wins, [("tie_a", 1, 1), ("tie_b", 1, 1), ("tie_c", 0, 1)]);
+
+    for player_id in ["player/tie_a", "player/tie_b"] {
+        let performance = repo
+            .get_my_game_performance(player_id)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        assert_eq!(performance.len(), 1, "{}", player_id);
+        assert_eq!(performance[0].wins, 1, "{}", player_id);
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_player_stats_report_win_streaks_in_start_order() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let db = env.database().await?;
+
+    // Inserted out of order: in start order streak_p wins three, finishes
+    // second, then wins two more, the last a tie for first. The contest with
+    // no start can't be ordered and doesn't break the current run.
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            INSERT { _key: "streak_p", handle: "streak_p", email: "streak_p@example.com" } INTO player
+            LET rows = [
+                ["streak_c6", "2024-01-06T18:00:00Z", 1],
+                ["streak_c1", "2024-01-01T18:00:00Z", 1],
+                ["streak_c4", "2024-01-04T18:00:00Z", 2],
+                ["streak_c2", "2024-01-02T18:00:00Z", 1],
+                ["streak_nostart", null, 3],
+                ["streak_c5", "2024-01-05T18:00:00Z", 1],
+                ["streak_c3", "2024-01-03T18:00:00Z", 1]
+            ]
+            FOR r IN rows
+                INSERT { _key: r[0], name: r[0], start: r[1], stop: r[1] } INTO contest
+                INSERT { _from: CONCAT("contest/", r[0]), _to: "player/streak_p", place: r[2], result: r[2] == 1 ? "won" : "lost" } INTO resulted_in
+            "#,
+        )
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed streak contests")?;
+    let tie = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            INSERT { _key: "streak_rival", handle: "streak_rival", email: "streak_rival@example.com" } INTO player
+            INSERT { _from: "contest/streak_c6", _to: "player/streak_rival", place: 1, result: "won" } INTO resulted_in
+            "#,
+        )
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(tie)
+        .await
+        .context("Failed to seed tied winner")?;
+
+    assert_eq!(
+        repo.get_player_streaks("player/streak_p")
+            .await
+            .map_err(|e| anyhow::anyhow!("{}", e))?,
+        (2, 3)
+    );
+    let stats = repo
+        .get_player_stats("player/streak_p")
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?
+        .context("Player stats should exist")?;
+    assert_eq!((stats.current_streak, stats.longest_streak), (2, 3));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_head_to_head_can_be_narrowed_to_one_game() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let db = env.database().await?;
+
+    // h2h_me beats h2h_opp twice at Catan, once in a contest that also
+    // played Azul, and loses to them once at Azul alone
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            INSERT { _key: "h2h_me", handle: "h2h_me", email: "h2h_me@example.com" } INTO player
+            INSERT { _key: "h2h_opp", handle: "h2h_opp", email: "h2h_opp@example.com" } INTO player
+            INSERT { _key: "h2h_catan", name: "Catan" } INTO game
+            INSERT { _key: "h2h_azul", name: "Azul" } INTO game
+            LET rows = [
+                ["h2h_c1", "2024-01-01T18:00:00Z", ["h2h_catan"], 1, 2],
+                ["h2h_c2", "2024-01-02T18:00:00Z", ["h2h_azul", "h2h_catan"], 1, 2],
+                ["h2h_c3", "2024-01-03T18:00:00Z", ["h2h_azul"], 2, 1]
+            ]
+            FOR r IN rows
+                INSERT { _key: r[0], name: r[0], start: r[1], stop: r[1] } INTO contest
+                LET edges = (
+                    FOR g IN r[2]
+                        INSERT { _from: CONCAT("contest/", r[0]), _to: CONCAT("game/", g) } INTO played_with
+                )
+                INSERT { _from: CONCAT("contest/", r[0]), _to: "player/h2h_me", place: r[3] } INTO resulted_in
+                INSERT { _from: CONCAT("contest/", r[0]), _to: "player/h2h_opp", place: r[4] } INTO resulted_in
+            "#,
+        )
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed head-to-head contests")?;
+
+    let all = repo
+        .get_head_to_head_record("player/h2h_me", "player/h2h_opp", None)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(
+        (all.total_contests, all.my_wins, all.opponent_wins),
+        (3, 2, 1)
+    );
+
+    for game_id in ["game/h2h_catan", "h2h_catan"] {
+        let catan = repo
+            .get_head_to_head_record("player/h2h_me", "player/h2h_opp", Some(game_id))
+            .await
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        assert_eq!(
+            (catan.total_contests, catan.my_wins, catan.opponent_wins),
+            (2, 2, 0),
+            "{}",
+            game_id
+        );
+        assert!(catan.contest_history.iter().all(|c| c.game_name == "Catan"));
+    }
+
+    let azul = repo
+        .get_head_to_head_record("player/h2h_me", "player/h2h_opp", Some("h2h_azul"))
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!((azul.total_contests, azul.my_wins), (2, 1));
+    assert_eq!(azul.my_win_rate, 50.0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_sampled_distribution_and_cohort_keep_the_exact_shape() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let db = env.database().await?;
+
+    // Player n plays (n % 4) + 1 contests, winning the first of them
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            LET contest_keys = (
+                FOR c IN 1..4
+                    INSERT { _key: CONCAT("sp_c", c), name: CONCAT("sp_c", c) } INTO contest
+                    RETURN NEW._key
+            )
+            FOR n IN 0..19
+                INSERT { _key: CONCAT("sp_p", n), handle: CONCAT("sp_p", n) } INTO player
+                FOR c IN 1..((n % 4) + 1)
+                    INSERT {
+                        _from: CONCAT("contest/", contest_keys[c - 1]),
+                        _to: CONCAT("player/sp_p", n),
+                        place: c == 1 ? 1 : 2
+                    } INTO resulted_in
+            "#,
+        )
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed sampling data")?;
+
+    let ranges = ["0-20%", "20-40%", "40-60%", "60-80%", "80-100%"];
+    let exact = repo
+        .get_player_performance_distribution(None)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(exact.iter().map(|(_, count)| count).sum::<i32>(), 20);
+    let full_sample = repo
+        .get_player_performance_distribution(Some(100.0))
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(full_sample, exact);
+    let sampled = repo
+        .get_player_performance_distribution(Some(50.0))
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert!(sampled
+        .iter()
+        .all(|(range, count)| ranges.contains(&range.as_str()) && *count > 0));
+
+    let exact = repo
+        .get_player_retention_cohort(None)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let counts: Vec<(&str, i32)> = exact
+        .iter()
+        .map(|(n, count, _)| (n.as_str(), *count))
+        .collect();
+    assert_eq!(counts, [("1", 20), ("2", 15), ("3", 10), ("4", 5)]);
+    assert_eq!(exact[0].2, 100.0);
+    let sampled = repo
+        .get_player_retention_cohort(Some(50.0))
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert!(sampled.len() <= 4);
+    assert!(sampled
+        .iter()
+        .zip(["1", "2", "3", "4"])
+        .all(|((n, count, rate), expected)| n == expected
+            && *count > 0
+            && (0.0..=100.0).contains(rate)));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_collections_ensures_indexes_idempotently() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    // Runs create_collections once on the fresh database
+    let repo = analytics_repo(&env).await?;
+    repo.create_collections()
+        .await
+        .map_err(|e| anyhow::anyhow!("Second create_collections failed: {}", e))?;
+
+    let db = env.database().await?;
+    for (collection, fields) in [
+        ("player_stats", vec!["player_id"]),
+        ("game_stats", vec!["game_id"]),
+        ("venue_stats", vec!["venue_id"]),
+        ("rating_latest", vec!["player_id", "scope_type"]),
+    ] {
+        let indexes = db
+            .indexes(collection)
+            .await
+            .with_context(|| format!("Failed to list indexes on {}", collection))?
+            .indexes;
+        let matching = indexes.iter().filter(|i| i.fields == fields).count();
+        assert_eq!(
+            matching, 1,
+            "{} should have exactly one index on {:?}",
+            collection, fields
+        );
+    }
+
+    Ok(())
+}
+
+/// Seeds three games, each played once at its own venue
+async fn insert_platform_activity(db: &Database<ReqwestClient>) -> Result<()> {
+    let seed = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            FOR n IN 1..3
+                LET key = CONCAT("timing_", n)
+                INSERT { _key: key, name: CONCAT("Timing Game ", n) } INTO game
+                INSERT { _key: key, displayName: CONCAT("Timing Venue ", n) } INTO venue
+                INSERT { _key: key, name: key, start: "2024-01-01T18:00:00Z", stop: "2024-01-01T20:00:00Z" } INTO contest
+                INSERT { _from: CONCAT("contest/", key), _to: CONCAT("game/", key) } INTO played_with
+                INSERT { _from: CONCAT("contest/", key), _to: CONCAT("venue/", key) } INTO played_at
+            "#,
+        )
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(seed)
+        .await
+        .context("Failed to seed platform activity")?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_concurrent_platform_stats_match_serial_and_time_every_sub_query() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let repo = analytics_repo(&env).await?;
+    let db = env.database().await?;
+    insert_platform_activity(&db).await?;
+
+    let (concurrent, timings) = repo
+        .clone()
+        .with_concurrent_platform_stats(true)
+        .get_platform_stats_timed(5)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let (serial, serial_timings) = repo
+        .with_concurrent_platform_stats(false)
+        .get_platform_stats_timed(5)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    let strip_timestamp = |stats: &shared::models::analytics::PlatformStats| {
+        let mut value = serde_json::to_value(stats).expect("stats serialize");
+        value
+            .as_object_mut()
+            .expect("stats is an object")
+            .remove("last_updated");
+        value
+    };
+    assert_eq!(strip_timestamp(&concurrent), strip_timestamp(&serial));
+    assert_eq!(concurrent.top_games.len(), 3);
+
+    let queries: Vec<_> = timings.iter().map(|t| t.query).collect();
+    assert_eq!(
+        queries,
+        [
+            "total_players",
+            "total_contests",
+            "total_games",
+            "total_venues",
+            "active_players_30d",
+            "active_players_7d",
+            "contests_30d",
+            "average_participants_per_contest",
+            "top_games",
+            "top_venues",
+        ]
+    );
+    assert_eq!(
+        serial_timings.iter().map(|t| t.query).collect::<Vec<_>>(),
+        queries
+    );
+    assert!(timings.iter().all(|t| t.elapsed_ms >= 0.0));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_platform_stats_debug_timing_is_shown_only_to_admins() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+    let db = env.database().await?;
+    insert_platform_activity(&db).await?;
+
+    let analytics_db = db.clone();
+    let db_config = database_config(&env);
+    let redis = app_data.redis_arc.clone();
+    let cache = app_data.analytics_cache.get_ref().clone();
+    let app = actix_web::test::init_service(
+        actix_web::App::new()
+            .app_data(app_data.redis_data.clone())
+            .app_data(app_data.player_repo.clone())
+            .app_data(app_data.session_store.clone())
+            .service(
+                actix_web::web::scope("/api/players")
+                    .service(backend::player::controller::register_handler_prod)
+                    .service(backend::player::controller::login_handler_prod),
+            )
+            .configure(move |cfg| {
+                backend::analytics::controller::configure_routes(
+                    cfg,
+                    analytics_db,
+                    db_config,
+                    redis,
+                    cache,
+                    AnalyticsConfig::default(),
+                )
+            }),
+    )
+    .await;
+
+    let player_session =
+        create_authenticated_user!(app, "timing_player@example.com", "timingplayer");
+    let admin_session = create_authenticated_user!(app, "timing_admin@example.com", "timingadmin");
+    let promote = arangors::AqlQuery::builder()
+        .query("FOR p IN player FILTER p.email == @email UPDATE p WITH { isAdmin: true } IN player")
+        .bind_var("email", "timing_admin@example.com")
+        .build();
+    let _: Vec<serde_json::Value> = db
+        .aql_query(promote)
+        .await
+        .context("Failed to promote admin")?;
+
+    let get_platform = |session: Option<&str>| {
+        let mut req =
+            actix_web::test::TestRequest::get().uri("/api/analytics/platform?debug_timing=true");
+        if let Some(session) = session {
+            req = req.insert_header(("Authorization", format!("Bearer {}", session)));
+        }
+        req.to_request()
+    };
+
+    // Anonymous and non-admin callers get the usual stats, flag ignored
+    for session in [None, Some(player_session.as_str())] {
+        let resp = actix_web::test::call_service(&app, get_platform(session)).await;
+        assert!(resp.status().is_success());
+        let body: serde_json::Value = actix_web::test::read_body_json(resp).await;
+        assert!(body.get("total_contests").is_some(), "{}", body);
+        assert!(body.get("debug_timing").is_none(), "{}", body);
+    }
+
+    let resp = actix_web::test::call_service(&app, get_platform(Some(&admin_session))).await;
+    assert!(resp.status().is_success());
+    let body: serde_json::Value = actix_web::test::read_body_json(resp).await;
+    assert!(body.get("total_contests").is_some(), "{}", body);
+    let timings = body["debug_timing"]
+        .as_array()
+        .expect("admins get a debug_timing list");
+    assert_eq!(timings.len(), 10);
+    assert!(timings
+        .iter()
+        .all(|t| t["query"].is_string() && t["elapsed_ms"].is_number()));
+
+    Ok(())
+}
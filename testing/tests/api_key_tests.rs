@@ -0,0 +1,160 @@
+//! Integration tests for API key authentication
+//!
+//! Mints keys through the player API and submits contests with them
+
+use actix_web::{test, web, App};
+use anyhow::Result;
+use chrono::{DateTime, FixedOffset, Utc};
+use serde_json::json;
+use shared::dto::player::{ApiKeyDto, CreatedApiKeyResponse};
+use testing::create_authenticated_user;
+use testing::{app_setup, TestEnvironment};
+
+fn contest_payload(name: &str) -> serde_json::Value {
+    let start: DateTime<FixedOffset> = Utc::now().into();
+    let stop: DateTime<FixedOffset> = start + chrono::Duration::hours(2);
+    json!({
+        "name": name,
+        "start": start.to_rfc3339(),
+        "stop": stop.to_rfc3339(),
+        "venue": {
+            "displayName": "Bot Venue",
+            "formattedAddress": "1 Bot St",
+            "place_id": "bot_place_id",
+            "lat": 40.7128,
+            "lng": -74.0060,
+            "timezone": "America/New_York",
+            "source": "database"
+        },
+        "games": [{
+            "name": "Bot Game",
+            "year_published": 2020,
+            "source": "database"
+        }],
+        "outcomes": []
+    })
+}
+
+#[tokio::test]
+async fn test_api_key_submits_contests_until_revoked() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+
+    let app = test::init_service(
+        App::new()
+            .wrap(backend::middleware::Logger::new())
+            .app_data(actix_web::web::JsonConfig::default().limit(256 * 1024))
+            .app_data(app_data.redis_data.clone())
+            .app_data(app_data.player_repo.clone())
+            .app_data(app_data.game_repo.clone())
+            .app_data(app_data.venue_repo.clone())
+            .app_data(app_data.contest_repo.clone())
+            .app_data(app_data.session_store.clone())
+            .service(
+                web::scope("/api/players")
+                    .service(backend::player::controller::register_handler_prod)
+                    .service(backend::player::controller::login_handler_prod)
+                    .service(
+                        web::scope("/me")
+                            .wrap(backend::auth::AuthMiddleware {
+                                redis: app_data.redis_arc.clone(),
+                            })
+                            .app_data(app_data.api_key_repo.clone())
+                            .service(backend::player::controller::create_api_key_handler)
+                            .service(backend::player::controller::list_api_keys_handler)
+                            .service(backend::player::controller::revoke_api_key_handler),
+                    ),
+            )
+            .service(
+                web::scope("/api/contests")
+                    .wrap(backend::auth::AuthMiddleware {
+                        redis: app_data.redis_arc.clone(),
+                    })
+                    .wrap(backend::auth::ApiKeyMiddleware {
+                        keys: app_data.api_key_repo.clone().into_inner(),
+                    })
+                    .app_data(actix_web::web::JsonConfig::default().limit(128 * 1024))
+                    .app_data(app_data.player_repo.clone())
+                    .service(backend::contest::controller::create_contest_handler)
+                    .service(backend::contest::controller::search_contests_handler),
+            ),
+    )
+    .await;
+
+    let session_id = create_authenticated_user!(app, "bot_owner@example.com", "botowner");
+
+    // Mint a key; the full key is only returned here
+    let req = test::TestRequest::post()
+        .uri("/api/players/me/api-keys")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .set_json(json!({ "name": "league bot" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 201);
+    let created: CreatedApiKeyResponse = test::read_body_json(resp).await;
+    assert!(created.key.starts_with("stg_"));
+    assert!(created.key.starts_with(&created.api_key.prefix));
+
+    let req = test::TestRequest::get()
+        .uri("/api/players/me/api-keys")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .to_request();
+    let listed: Vec<ApiKeyDto> = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].id, created.api_key.id);
+    assert!(listed[0].revoked_at.is_none());
+
+    // The key submits a contest on behalf of its owner
+    let req = test::TestRequest::post()
+        .uri("/api/contests")
+        .insert_header(("Authorization", format!("Bearer {}", created.key)))
+        .set_json(contest_payload("Bot Contest"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(
+        resp.status().is_success(),
+        "API key should submit a contest, got {}",
+        resp.status()
+    );
+
+    // Keys cannot read
+    let req = test::TestRequest::get()
+        .uri("/api/contests/search")
+        .insert_header(("Authorization", format!("Bearer {}", created.key)))
+        .to_request();
+    let status = match test::try_call_service(&app, req).await {
+        Ok(resp) => resp.status(),
+        Err(e) => e.as_response_error().status_code(),
+    };
+    assert_eq!(status, 401);
+
+    let req = test::TestRequest::delete()
+        .uri(&format!("/api/players/me/api-keys/{}", created.api_key.id))
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 204);
+
+    // A revoked key is rejected
+    let req = test::TestRequest::post()
+        .uri("/api/contests")
+        .insert_header(("Authorization", format!("Bearer {}", created.key)))
+        .set_json(contest_payload("Late Bot Contest"))
+        .to_request();
+    let status = match test::try_call_service(&app, req).await {
+        Ok(resp) => resp.status(),
+        Err(e) => e.as_response_error().status_code(),
+    };
+    assert_eq!(status, 401);
+
+    // Revoking twice reports the key as gone
+    let req = test::TestRequest::delete()
+        .uri(&format!("/api/players/me/api-keys/{}", created.api_key.id))
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+
+    Ok(())
+}
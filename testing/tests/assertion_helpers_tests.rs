@@ -0,0 +1,70 @@
+//! Self-tests for the database assertion helpers
+
+use anyhow::{Context, Result};
+use arangors::Connection;
+use futures::FutureExt;
+use std::panic::AssertUnwindSafe;
+use testing::{app_setup, assert_collection_count, assert_player_exists, TestEnvironment};
+
+/// Message of the panic raised by `future`, if it panicked
+async fn panic_message<F, T>(future: F) -> Option<String>
+where
+    F: std::future::Future<Output = Result<T>>,
+{
+    let payload = AssertUnwindSafe(future).catch_unwind().await.err()?;
+    payload
+        .downcast_ref::<String>()
+        .cloned()
+        .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+}
+
+#[tokio::test]
+async fn test_assertion_helpers_against_seeded_environment() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    // Creates the core collections in `_system`
+    app_setup::setup_test_app_data(&env).await?;
+
+    let conn = Connection::establish_basic_auth(env.arangodb_url(), "root", "test_password")
+        .await
+        .context("Failed to connect to ArangoDB")?;
+    let db = conn.db("_system").await?;
+    let _: Vec<serde_json::Value> = db
+        .aql_str(
+            r#"
+            LET players = (FOR h IN ["alice", "bob"] INSERT { handle: h, email: CONCAT(h, "@example.com") } INTO player)
+            LET contests = (FOR n IN 1..3 INSERT { name: CONCAT("Contest ", n) } INTO contest)
+            RETURN true
+            "#,
+        )
+        .await
+        .context("Failed to seed data")?;
+
+    assert_collection_count(&env, "contest", 3).await?;
+    assert_collection_count(&env, "player", 2).await?;
+    let alice = assert_player_exists(&env, "alice").await?;
+    assert!(alice.starts_with("player/"), "{}", alice);
+
+    let message = panic_message(assert_collection_count(&env, "contest", 5))
+        .await
+        .expect("a wrong count should fail");
+    assert!(
+        message.contains(
+            "Expected 5 document(s) in collection 'contest' of database '_system', found 3"
+        ),
+        "{}",
+        message
+    );
+
+    let message = panic_message(assert_player_exists(&env, "carol"))
+        .await
+        .expect("a missing player should fail");
+    assert!(
+        message.contains("Expected a player with handle 'carol'")
+            && message.contains("found none among 2 player(s)"),
+        "{}",
+        message
+    );
+
+    Ok(())
+}
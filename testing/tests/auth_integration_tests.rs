@@ -741,3 +741,91 @@ async fn test_protected_endpoints_require_auth() -> Result<()> {
 
     Ok(())
 }
+
+/// Register and login routes hashing with `$passwords`
+macro_rules! password_app {
+    ($app_data:expr, $passwords:expr) => {
+        test::init_service(
+            App::new()
+                .app_data(actix_web::web::JsonConfig::default().limit(256 * 1024))
+                .app_data(web::Data::new($passwords))
+                .app_data($app_data.redis_data.clone())
+                .app_data($app_data.player_repo.clone())
+                .app_data($app_data.session_store.clone())
+                .service(
+                    web::scope("/api/players")
+                        .service(backend::player::controller::register_handler_prod)
+                        .service(backend::player::controller::login_handler_prod),
+                ),
+        )
+        .await
+    };
+}
+
+async fn stored_hash(app_data: &app_setup::TestAppData) -> String {
+    use backend::player::repository::PlayerRepository;
+    app_data
+        .player_repo
+        .find_by_email("rehash@example.com")
+        .await
+        .expect("Registered player should exist")
+        .password
+}
+
+#[tokio::test]
+async fn test_login_upgrades_weak_password_hash() -> Result<()> {
+    use backend::config::PasswordConfig;
+
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+    let login = |password: &str| {
+        test::TestRequest::post()
+            .uri("/api/players/login")
+            .set_json(serde_json::json!({
+                "email": "rehash@example.com",
+                "password": password
+            }))
+            .to_request()
+    };
+
+    // Register while the configured cost is weak
+    let weak = PasswordConfig {
+        argon2_memory_kib: 8,
+        argon2_iterations: 1,
+        argon2_parallelism: 1,
+        ..PasswordConfig::default()
+    };
+    let app = password_app!(app_data, weak.clone());
+    let _ = create_authenticated_user!(app, "rehash@example.com", "rehash");
+    let weak_hash = stored_hash(&app_data).await;
+    assert!(weak_hash.starts_with("$argon2id$v=19$m=8,t=1,p=1$"));
+
+    // Raise the cost: the next login verifies against the old hash and
+    // replaces it
+    let stronger = PasswordConfig {
+        argon2_memory_kib: 16,
+        argon2_iterations: 2,
+        ..weak
+    };
+    let app = password_app!(app_data, stronger);
+    let resp = test::call_service(&app, login("wrongpassword")).await;
+    assert!(resp.status().is_client_error());
+    assert_eq!(
+        stored_hash(&app_data).await,
+        weak_hash,
+        "A failed login must not rehash"
+    );
+
+    let resp = test::call_service(&app, login("password123")).await;
+    assert!(resp.status().is_success(), "Weak hash should still verify");
+    let upgraded = stored_hash(&app_data).await;
+    assert!(upgraded.starts_with("$argon2id$v=19$m=16,t=2,p=1$"));
+
+    // The upgraded hash verifies and is left alone from then on
+    let resp = test::call_service(&app, login("password123")).await;
+    assert!(resp.status().is_success());
+    assert_eq!(stored_hash(&app_data).await, upgraded);
+
+    Ok(())
+}
@@ -10,13 +10,20 @@ mod test_helpers;
 
 use actix_web::{test, web, App};
 use anyhow::Result;
+use backend::config::{WebhookConfig, WebhookEndpoint};
+use backend::contest::webhook::ContestWebhooks;
 use chrono::{DateTime, FixedOffset, Utc};
 use serde_json::json;
-use shared::dto::contest::ContestDto;
+use shared::dto::contest::{
+    ContestDto, ContestImportReportDto, ContestTimelineDto, TimelineEventKind,
+};
 use shared::dto::game::GameDto;
+use shared::dto::ratings::{RatingLeaderboardEntryDto, RatingRankChangeDto};
 use shared::dto::venue::VenueDto;
 use shared::models::game::GameSource;
 use shared::models::venue::VenueSource;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::sync::{Arc, Mutex};
 use testing::create_authenticated_user;
 use testing::{app_setup, TestEnvironment};
 
@@ -203,6 +210,62 @@ async fn test_create_contest_validation_errors() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_create_contest_rejects_non_json_body_with_415() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+
+    let app = test::init_service(
+        App::new()
+            .wrap(backend::middleware::Logger::new())
+            .wrap(backend::middleware::cors_middleware())
+            .app_data(backend::error::json_config(256 * 1024))
+            .app_data(app_data.redis_data.clone())
+            .app_data(app_data.player_repo.clone())
+            .app_data(app_data.game_repo.clone())
+            .app_data(app_data.venue_repo.clone())
+            .app_data(app_data.contest_repo.clone())
+            .app_data(app_data.session_store.clone())
+            .service(
+                web::scope("/api/players")
+                    .service(backend::player::controller::register_handler_prod)
+                    .service(backend::player::controller::login_handler_prod),
+            )
+            .service(
+                web::scope("/api/contests")
+                    .wrap(backend::auth::AuthMiddleware {
+                        redis: app_data.redis_arc.clone(),
+                    })
+                    .app_data(backend::error::json_config(128 * 1024))
+                    .app_data(app_data.player_repo.clone())
+                    .service(backend::contest::controller::create_contest_handler),
+            ),
+    )
+    .await;
+
+    let session_id = create_authenticated_user!(app, "contest_415@example.com", "contest415");
+
+    let req = test::TestRequest::post()
+        .uri("/api/contests")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .insert_header(("Content-Type", "text/plain"))
+        .set_payload(r#"{"name": "Plain Text Contest"}"#)
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 415);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["code"], "request.unsupported_media_type");
+    assert_eq!(body["status_code"], 415);
+    assert!(body["message"]
+        .as_str()
+        .unwrap_or_default()
+        .contains("text/plain"));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_get_contest_success() -> Result<()> {
     let env = TestEnvironment::new().await?;
@@ -304,6 +367,142 @@ async fn test_get_contest_success() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_get_contest_timeline() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+
+    let app = test::init_service(
+        App::new()
+            .wrap(backend::middleware::Logger::new())
+            .wrap(backend::middleware::cors_middleware())
+            .app_data(actix_web::web::JsonConfig::default().limit(256 * 1024))
+            .app_data(app_data.redis_data.clone())
+            .app_data(app_data.player_repo.clone())
+            .app_data(app_data.game_repo.clone())
+            .app_data(app_data.venue_repo.clone())
+            .app_data(app_data.contest_repo.clone())
+            .app_data(app_data.session_store.clone())
+            .service(
+                web::scope("/api/players")
+                    .service(backend::player::controller::register_handler_prod)
+                    .service(backend::player::controller::login_handler_prod),
+            )
+            .service(
+                web::scope("/api/contests")
+                    .wrap(backend::auth::AuthMiddleware {
+                        redis: app_data.redis_arc.clone(),
+                    })
+                    .app_data(actix_web::web::JsonConfig::default().limit(128 * 1024))
+                    .app_data(app_data.player_repo.clone())
+                    .service(backend::contest::controller::create_contest_handler)
+                    .service(backend::contest::controller::get_contest_timeline_handler)
+                    .service(backend::contest::controller::get_contest_handler),
+            ),
+    )
+    .await;
+
+    let session_id = create_authenticated_user!(app, "contest_timeline@example.com", "timeline");
+
+    // Scheduled ahead so creation is unambiguously the first event
+    let start: DateTime<FixedOffset> = (Utc::now() + chrono::Duration::hours(1)).into();
+    let stop: DateTime<FixedOffset> = start + chrono::Duration::hours(2);
+    let contest_data = json!({
+        "name": "Timeline Contest",
+        "start": start.to_rfc3339(),
+        "stop": stop.to_rfc3339(),
+        "venue": create_test_venue_dto(),
+        "games": [create_test_game_dto()],
+        "outcomes": [
+            {
+                "player_id": "",
+                "place": "2",
+                "result": "lost",
+                "handle": "timelinesecond",
+                "email": "timelinesecond@example.com"
+            },
+            {
+                "player_id": "",
+                "place": "1",
+                "result": "won",
+                "handle": "timelinefirst",
+                "email": "timelinefirst@example.com"
+            }
+        ]
+    });
+
+    let create_req = test::TestRequest::post()
+        .uri("/api/contests")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .set_json(&contest_data)
+        .to_request();
+    let create_resp = test::call_service(&app, create_req).await;
+    assert!(create_resp.status().is_success());
+    let created_contest: ContestDto = test::read_body_json(create_resp).await;
+    let contest_key = created_contest
+        .id
+        .rsplit('/')
+        .next()
+        .unwrap_or(&created_contest.id)
+        .to_string();
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/contests/{}/timeline", contest_key))
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(
+        resp.status().is_success(),
+        "Getting the timeline should succeed, got: {}",
+        resp.status()
+    );
+
+    let timeline: ContestTimelineDto = test::read_body_json(resp).await;
+    assert_eq!(timeline.contest_id, created_contest.id);
+    let entries: Vec<_> = timeline
+        .events
+        .iter()
+        .map(|e| (e.kind, e.handle.as_deref(), e.place))
+        .collect();
+    assert_eq!(
+        entries,
+        vec![
+            (TimelineEventKind::Created, None, None),
+            (
+                TimelineEventKind::ParticipantRecorded,
+                Some("timelinefirst"),
+                Some(1)
+            ),
+            (
+                TimelineEventKind::ParticipantRecorded,
+                Some("timelinesecond"),
+                Some(2)
+            ),
+            (TimelineEventKind::Started, None, None),
+            (TimelineEventKind::Finished, None, None),
+            (TimelineEventKind::Placement, Some("timelinefirst"), Some(1)),
+            (
+                TimelineEventKind::Placement,
+                Some("timelinesecond"),
+                Some(2)
+            ),
+        ]
+    );
+    assert_eq!(timeline.events[3].at, start);
+    assert_eq!(timeline.events[4].at, stop);
+
+    // Unknown contests are a 404
+    let req = test::TestRequest::get()
+        .uri("/api/contests/does-not-exist/timeline")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_get_contest_not_found() -> Result<()> {
     let env = TestEnvironment::new().await?;
@@ -522,6 +721,211 @@ async fn test_search_contests() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_search_contests_pagination_headers() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+
+    let app = test::init_service(
+        App::new()
+            .wrap(backend::middleware::Logger::new())
+            .app_data(actix_web::web::JsonConfig::default().limit(256 * 1024))
+            .app_data(app_data.redis_data.clone())
+            .app_data(app_data.player_repo.clone())
+            .app_data(app_data.game_repo.clone())
+            .app_data(app_data.venue_repo.clone())
+            .app_data(app_data.contest_repo.clone())
+            .app_data(app_data.session_store.clone())
+            .service(
+                web::scope("/api/players")
+                    .service(backend::player::controller::register_handler_prod)
+                    .service(backend::player::controller::login_handler_prod),
+            )
+            .service(
+                web::scope("/api/contests")
+                    .wrap(backend::auth::AuthMiddleware {
+                        redis: app_data.redis_arc.clone(),
+                    })
+                    .app_data(actix_web::web::JsonConfig::default().limit(128 * 1024))
+                    .app_data(app_data.player_repo.clone())
+                    .service(backend::contest::controller::create_contest_handler)
+                    .service(backend::contest::controller::search_contests_handler),
+            ),
+    )
+    .await;
+
+    let session_id = create_authenticated_user!(app, "contest_pages@example.com", "contestpages");
+
+    // Three contests over pages of two: page 1 is full, page 2 holds the rest
+    for i in 1..=3 {
+        let start: DateTime<FixedOffset> = Utc::now().into();
+        let stop: DateTime<FixedOffset> = start + chrono::Duration::hours(2);
+        let contest_data = json!({
+            "name": format!("Paged Contest {}", i),
+            "start": start.to_rfc3339(),
+            "stop": stop.to_rfc3339(),
+            "venue": create_test_venue_dto(),
+            "games": [create_test_game_dto()],
+            "outcomes": []
+        });
+        let req = test::TestRequest::post()
+            .uri("/api/contests")
+            .insert_header(("Authorization", format!("Bearer {}", session_id)))
+            .set_json(&contest_data)
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+    }
+
+    let header = |resp: &actix_web::dev::ServiceResponse, name: &str| {
+        resp.headers()
+            .get(name)
+            .map(|v| v.to_str().unwrap().to_string())
+    };
+
+    let req = test::TestRequest::get()
+        .uri("/api/contests/search?scope=all&page_size=2&page=1")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    assert_eq!(header(&resp, "X-Total-Count").as_deref(), Some("3"));
+    assert_eq!(
+        header(&resp, "Link").as_deref(),
+        Some("</api/contests/search?scope=all&page_size=2&page=2>; rel=\"next\"")
+    );
+
+    // The last page links back only
+    let req = test::TestRequest::get()
+        .uri("/api/contests/search?scope=all&page_size=2&page=2")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    assert_eq!(header(&resp, "X-Total-Count").as_deref(), Some("3"));
+    assert_eq!(
+        header(&resp, "Link").as_deref(),
+        Some("</api/contests/search?scope=all&page_size=2&page=1>; rel=\"prev\"")
+    );
+    let page: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(page["items"].as_array().map(|items| items.len()), Some(1));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_search_contests_matches_words_in_descriptions() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+
+    let app = test::init_service(
+        App::new()
+            .wrap(backend::middleware::Logger::new())
+            .app_data(actix_web::web::JsonConfig::default().limit(256 * 1024))
+            .app_data(app_data.redis_data.clone())
+            .app_data(app_data.player_repo.clone())
+            .app_data(app_data.game_repo.clone())
+            .app_data(app_data.venue_repo.clone())
+            .app_data(app_data.contest_repo.clone())
+            .app_data(app_data.session_store.clone())
+            .service(
+                web::scope("/api/players")
+                    .service(backend::player::controller::register_handler_prod)
+                    .service(backend::player::controller::login_handler_prod),
+            )
+            .service(
+                web::scope("/api/contests")
+                    .wrap(backend::auth::AuthMiddleware {
+                        redis: app_data.redis_arc.clone(),
+                    })
+                    .app_data(actix_web::web::JsonConfig::default().limit(128 * 1024))
+                    .app_data(app_data.player_repo.clone())
+                    .service(backend::contest::controller::create_contest_handler)
+                    .service(backend::contest::controller::search_contests_handler),
+            ),
+    )
+    .await;
+
+    let session_id = create_authenticated_user!(app, "contest_text@example.com", "contesttext");
+
+    let mut ids = Vec::new();
+    for name in ["Spring Finals", "Club Night", "Casual Meetup"] {
+        let start: DateTime<FixedOffset> = Utc::now().into();
+        let contest_data = json!({
+            "name": name,
+            "start": start.to_rfc3339(),
+            "stop": (start + chrono::Duration::hours(2)).to_rfc3339(),
+            "venue": create_test_venue_dto(),
+            "games": [create_test_game_dto()],
+            "outcomes": []
+        });
+        let req = test::TestRequest::post()
+            .uri("/api/contests")
+            .insert_header(("Authorization", format!("Bearer {}", session_id)))
+            .set_json(&contest_data)
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+        let created: ContestDto = test::read_body_json(resp).await;
+        ids.push(created.id);
+    }
+
+    // Only the club night mentions the finals in its description
+    let describe = arangors::AqlQuery::builder()
+        .query("UPDATE PARSE_IDENTIFIER(@id).key WITH { description: @description } IN contest")
+        .bind_var("id", ids[1].clone())
+        .bind_var("description", "Warm-up for the regional FINALS qualifier")
+        .build();
+    let _: Vec<serde_json::Value> = app_data
+        .contest_repo
+        .db
+        .aql_query(describe)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to describe contest: {}", e))?;
+
+    let search = |q: &str| {
+        test::TestRequest::get()
+            .uri(&format!("/api/contests/search?scope=all&q={}", q))
+            .insert_header(("Authorization", format!("Bearer {}", session_id)))
+            .to_request()
+    };
+    let names = |page: &serde_json::Value| -> Vec<String> {
+        page["items"]
+            .as_array()
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|c| c["name"].as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    // Name matches rank above description-only matches
+    let resp = test::call_service(&app, search("finals")).await;
+    assert!(resp.status().is_success());
+    let page: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(names(&page), ["Spring Finals", "Club Night"]);
+    assert_eq!(page["total"], 2);
+
+    // Every word has to match somewhere
+    let resp = test::call_service(&app, search("regional%20qualifier")).await;
+    let page: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(names(&page), ["Club Night"]);
+    assert_eq!(
+        page["items"][0]["description"],
+        "Warm-up for the regional FINALS qualifier"
+    );
+
+    let resp = test::call_service(&app, search("finals%20meetup")).await;
+    let page: serde_json::Value = test::read_body_json(resp).await;
+    assert!(names(&page).is_empty());
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_contest_unauthorized_access() -> Result<()> {
     let env = TestEnvironment::new().await?;
@@ -587,3 +991,1385 @@ async fn test_contest_unauthorized_access() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_create_contest_participant_limit() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+
+    let app = test::init_service(
+        App::new()
+            .app_data(actix_web::web::JsonConfig::default().limit(256 * 1024))
+            .app_data(app_data.redis_data.clone())
+            .app_data(app_data.player_repo.clone())
+            .app_data(app_data.game_repo.clone())
+            .app_data(app_data.venue_repo.clone())
+            .app_data(app_data.contest_repo.clone())
+            .app_data(app_data.session_store.clone())
+            .service(
+                web::scope("/api/players")
+                    .service(backend::player::controller::register_handler_prod)
+                    .service(backend::player::controller::login_handler_prod),
+            )
+            .service(
+                web::scope("/api/contests")
+                    .wrap(backend::auth::AuthMiddleware {
+                        redis: app_data.redis_arc.clone(),
+                    })
+                    .app_data(app_data.player_repo.clone())
+                    .app_data(web::Data::new(backend::config::ContestConfig {
+                        max_participants: 2,
+                        ..Default::default()
+                    }))
+                    .service(backend::contest::controller::create_contest_handler),
+            ),
+    )
+    .await;
+
+    let session_id = create_authenticated_user!(app, "contest_limit@example.com", "contestlimit");
+
+    let contest_with = |count: usize| {
+        let start: DateTime<FixedOffset> = Utc::now().into();
+        let stop: DateTime<FixedOffset> = start + chrono::Duration::hours(1);
+        let outcomes: Vec<_> = (0..count)
+            .map(|i| {
+                json!({
+                    "player_id": "",
+                    "place": (i + 1).to_string(),
+                    "result": if i == 0 { "won" } else { "lost" },
+                    "handle": format!("limitplayer{}", i),
+                    "email": format!("limitplayer{}@example.com", i)
+                })
+            })
+            .collect();
+        json!({
+            "name": format!("Limit Contest {}", count),
+            "start": start.to_rfc3339(),
+            "stop": stop.to_rfc3339(),
+            "venue": create_test_venue_dto(),
+            "games": [create_test_game_dto()],
+            "outcomes": outcomes
+        })
+    };
+
+    // Exactly at the limit is accepted
+    let req = test::TestRequest::post()
+        .uri("/api/contests")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .set_json(&contest_with(2))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(
+        resp.status().is_success(),
+        "Contest at the participant limit should succeed, got: {}",
+        resp.status()
+    );
+
+    // One over the limit is rejected
+    let req = test::TestRequest::post()
+        .uri("/api/contests")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .set_json(&contest_with(3))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(
+        resp.status(),
+        422,
+        "Contest over the limit should be rejected"
+    );
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["error"], "too_many_participants");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_contest_ranking() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+
+    let app = test::init_service(
+        App::new()
+            .app_data(actix_web::web::JsonConfig::default().limit(256 * 1024))
+            .app_data(app_data.redis_data.clone())
+            .app_data(app_data.player_repo.clone())
+            .app_data(app_data.game_repo.clone())
+            .app_data(app_data.venue_repo.clone())
+            .app_data(app_data.contest_repo.clone())
+            .app_data(app_data.session_store.clone())
+            .service(
+                web::scope("/api/players")
+                    .service(backend::player::controller::register_handler_prod)
+                    .service(backend::player::controller::login_handler_prod),
+            )
+            .service(
+                web::scope("/api/contests")
+                    .wrap(backend::auth::AuthMiddleware {
+                        redis: app_data.redis_arc.clone(),
+                    })
+                    .app_data(app_data.player_repo.clone())
+                    .service(backend::contest::controller::create_contest_handler),
+            ),
+    )
+    .await;
+
+    let session_id = create_authenticated_user!(app, "contest_ranking@example.com", "ranking");
+
+    let contest_with = |places: &[&str]| {
+        let start: DateTime<FixedOffset> = Utc::now().into();
+        let stop: DateTime<FixedOffset> = start + chrono::Duration::hours(1);
+        let outcomes: Vec<_> = places
+            .iter()
+            .enumerate()
+            .map(|(i, place)| {
+                json!({
+                    "player_id": "",
+                    "place": place,
+                    "result": if *place == "1" { "won" } else { "lost" },
+                    "handle": format!("rankplayer{}", i),
+                    "email": format!("rankplayer{}@example.com", i)
+                })
+            })
+            .collect();
+        json!({
+            "name": format!("Ranking Contest {}", places.join("-")),
+            "start": start.to_rfc3339(),
+            "stop": stop.to_rfc3339(),
+            "venue": create_test_venue_dto(),
+            "games": [create_test_game_dto()],
+            "outcomes": outcomes
+        })
+    };
+
+    // A gap and a duplicate that no tie accounts for are both rejected
+    for (places, code) in [
+        (["1", "2", "2", "5"], "ranking_gap"),
+        (["1", "2", "2", "3"], "ranking_duplicate"),
+    ] {
+        let req = test::TestRequest::post()
+            .uri("/api/contests")
+            .insert_header(("Authorization", format!("Bearer {}", session_id)))
+            .set_json(&contest_with(&places))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 422, "Places {:?} should be rejected", places);
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["error"], "invalid_ranking");
+        assert_eq!(body["code"], "contest.invalid_ranking");
+        assert_eq!(body["fields"][0]["code"], code);
+    }
+
+    // Tied players share a place and the next place is skipped
+    let req = test::TestRequest::post()
+        .uri("/api/contests")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .set_json(&contest_with(&["1", "2", "2", "4"]))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(
+        resp.status().is_success(),
+        "A valid ranking with a tie should succeed, got: {}",
+        resp.status()
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_create_contest_start_window() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+
+    let app = test::init_service(
+        App::new()
+            .app_data(actix_web::web::JsonConfig::default().limit(256 * 1024))
+            .app_data(app_data.redis_data.clone())
+            .app_data(app_data.player_repo.clone())
+            .app_data(app_data.game_repo.clone())
+            .app_data(app_data.venue_repo.clone())
+            .app_data(app_data.contest_repo.clone())
+            .app_data(app_data.session_store.clone())
+            .service(
+                web::scope("/api/players")
+                    .service(backend::player::controller::register_handler_prod)
+                    .service(backend::player::controller::login_handler_prod),
+            )
+            .service(
+                web::scope("/api/contests")
+                    .wrap(backend::auth::AuthMiddleware {
+                        redis: app_data.redis_arc.clone(),
+                    })
+                    .app_data(app_data.player_repo.clone())
+                    .app_data(web::Data::new(backend::config::ContestConfig {
+                        max_start_days_past: 30,
+                        max_start_days_future: 7,
+                        ..Default::default()
+                    }))
+                    .service(backend::contest::controller::create_contest_handler),
+            ),
+    )
+    .await;
+
+    let session_id = create_authenticated_user!(app, "contest_window@example.com", "contestwindow");
+
+    let contest_at = |name: &str, start: DateTime<FixedOffset>| {
+        let stop = start + chrono::Duration::hours(1);
+        json!({
+            "name": name,
+            "start": start.to_rfc3339(),
+            "stop": stop.to_rfc3339(),
+            "venue": create_test_venue_dto(),
+            "games": [create_test_game_dto()],
+            "outcomes": [
+                {"player_id": "", "place": "1", "result": "won", "handle": "windowplayer1", "email": "windowplayer1@example.com"},
+                {"player_id": "", "place": "2", "result": "lost", "handle": "windowplayer2", "email": "windowplayer2@example.com"}
+            ]
+        })
+    };
+    let now: DateTime<FixedOffset> = Utc::now().into();
+    let margin = chrono::Duration::minutes(5);
+    let cases = [
+        (
+            "Just inside past",
+            now - chrono::Duration::days(30) + margin,
+            true,
+        ),
+        (
+            "Just outside past",
+            now - chrono::Duration::days(30) - margin,
+            false,
+        ),
+        (
+            "Just inside future",
+            now + chrono::Duration::days(7) - margin,
+            true,
+        ),
+        (
+            "Just outside future",
+            now + chrono::Duration::days(7) + margin,
+            false,
+        ),
+        (
+            "Epoch",
+            DateTime::parse_from_rfc3339("1970-01-01T00:00:00+00:00")?,
+            false,
+        ),
+        (
+            "Far future",
+            DateTime::parse_from_rfc3339("2200-01-01T00:00:00+00:00")?,
+            false,
+        ),
+    ];
+    for (name, start, accepted) in cases {
+        let req = test::TestRequest::post()
+            .uri("/api/contests")
+            .insert_header(("Authorization", format!("Bearer {}", session_id)))
+            .set_json(&contest_at(name, start))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        if accepted {
+            assert!(
+                resp.status().is_success(),
+                "{} should be accepted, got: {}",
+                name,
+                resp.status()
+            );
+        } else {
+            assert_eq!(resp.status(), 422, "{} should be rejected", name);
+            let body: serde_json::Value = test::read_body_json(resp).await;
+            assert_eq!(body["error"], "start_out_of_range");
+            assert_eq!(body["code"], "contest.start_out_of_range");
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unrated_contest_skips_ratings_but_counts_in_stats() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+
+    let db = app_data.contest_repo.db.clone();
+    for collection in ["rating_latest", "rating_history"] {
+        db.create_collection(collection)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to create {}: {}", collection, e))?;
+    }
+    let ratings_repo = backend::ratings::repository::RatingsRepository::new(db);
+    let ratings = backend::ratings::usecase::RatingsUsecase::new(ratings_repo.clone());
+
+    let app = test::init_service(
+        App::new()
+            .wrap(backend::middleware::Logger::new())
+            .wrap(backend::middleware::cors_middleware())
+            .app_data(actix_web::web::JsonConfig::default().limit(256 * 1024))
+            .app_data(app_data.redis_data.clone())
+            .app_data(app_data.player_repo.clone())
+            .app_data(app_data.game_repo.clone())
+            .app_data(app_data.venue_repo.clone())
+            .app_data(app_data.contest_repo.clone())
+            .app_data(app_data.session_store.clone())
+            .service(
+                web::scope("/api/players")
+                    .service(backend::player::controller::register_handler_prod)
+                    .service(backend::player::controller::login_handler_prod),
+            )
+            .service(
+                web::scope("/api/contests")
+                    .wrap(backend::auth::AuthMiddleware {
+                        redis: app_data.redis_arc.clone(),
+                    })
+                    .app_data(actix_web::web::JsonConfig::default().limit(128 * 1024))
+                    .app_data(app_data.player_repo.clone())
+                    .service(backend::contest::controller::create_contest_handler),
+            ),
+    )
+    .await;
+
+    let session_id = create_authenticated_user!(app, "contest_rated@example.com", "contestrated");
+
+    let start: DateTime<FixedOffset> = DateTime::parse_from_rfc3339("2024-01-10T18:00:00Z")?;
+    let rated = json!({
+        "name": "Rated Contest",
+        "start": start.to_rfc3339(),
+        "stop": (start + chrono::Duration::hours(2)).to_rfc3339(),
+        "venue": create_test_venue_dto(),
+        "games": [create_test_game_dto()],
+        "outcomes": [
+            { "player_id": "", "place": "1", "result": "won", "handle": "ratedalpha", "email": "ratedalpha@example.com" },
+            { "player_id": "", "place": "2", "result": "lost", "handle": "ratedbeta", "email": "ratedbeta@example.com" }
+        ]
+    });
+    let req = test::TestRequest::post()
+        .uri("/api/contests")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .set_json(&rated)
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    let created: ContestDto = test::read_body_json(resp).await;
+    assert!(created.rated, "Contests are rated unless marked otherwise");
+    let alpha = created.outcomes[0].clone();
+    let beta = created.outcomes[1].clone();
+
+    ratings
+        .recompute_month(Some("2024-01".to_string()))
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let latest_rating = |player_id: String| {
+        let repo = ratings_repo.clone();
+        async move {
+            let doc = repo
+                .get_latest_rating("global", None, &player_id)
+                .await
+                .map_err(|e| anyhow::anyhow!("{}", e))?
+                .expect("Player should have a latest rating");
+            anyhow::Ok((
+                doc["rating"].as_f64(),
+                doc["rd"].as_f64(),
+                doc["games_played"].as_i64(),
+            ))
+        }
+    };
+    let before = latest_rating(alpha.player_id.clone()).await?;
+    assert_eq!(before.2, Some(1));
+
+    // A casual rematch in the same month that alpha loses
+    let later = start + chrono::Duration::days(10);
+    let unrated = json!({
+        "name": "Casual Contest",
+        "start": later.to_rfc3339(),
+        "stop": (later + chrono::Duration::hours(2)).to_rfc3339(),
+        "venue": create_test_venue_dto(),
+        "games": [create_test_game_dto()],
+        "outcomes": [
+            { "player_id": beta.player_id, "place": "1", "result": "won", "handle": beta.handle, "email": beta.email },
+            { "player_id": alpha.player_id, "place": "2", "result": "lost", "handle": alpha.handle, "email": alpha.email }
+        ],
+        "rated": false
+    });
+    let req = test::TestRequest::post()
+        .uri("/api/contests")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .set_json(&unrated)
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    let created: ContestDto = test::read_body_json(resp).await;
+    assert!(!created.rated);
+
+    ratings
+        .recompute_month(Some("2024-01".to_string()))
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(
+        latest_rating(alpha.player_id.clone()).await?,
+        before,
+        "An unrated contest must not move ratings"
+    );
+
+    // ...but it still shows up in the player's contest count
+    let leaderboard = ratings_repo
+        .get_leaderboard("global", None, 0, 10)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let alpha_entry = leaderboard
+        .iter()
+        .find(|entry| entry["player_id"] == alpha.player_id.as_str())
+        .expect("alpha should be on the leaderboard");
+    assert_eq!(alpha_entry["games_played"], 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_guest_is_on_the_contest_board_but_not_on_leaderboards() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+
+    let db = app_data.contest_repo.db.clone();
+    for collection in ["rating_latest", "rating_history"] {
+        db.create_collection(collection)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to create {}: {}", collection, e))?;
+    }
+    let ratings_repo = backend::ratings::repository::RatingsRepository::new(db.clone());
+    let ratings = backend::ratings::usecase::RatingsUsecase::new(ratings_repo.clone());
+    let analytics = backend::analytics::AnalyticsRepository::new(
+        db,
+        backend::config::DatabaseConfig {
+            url: env.arangodb_url().to_string(),
+            name: env.app_db_name(),
+            username: "root".to_string(),
+            password: "test_password".to_string(),
+            root_username: "root".to_string(),
+            root_password: "test_password".to_string(),
+            pool_size: 1,
+            _timeout_seconds: 30,
+        },
+    );
+
+    let app = test::init_service(
+        App::new()
+            .wrap(backend::middleware::Logger::new())
+            .wrap(backend::middleware::cors_middleware())
+            .app_data(app_data.redis_data.clone())
+            .app_data(app_data.player_repo.clone())
+            .app_data(app_data.game_repo.clone())
+            .app_data(app_data.venue_repo.clone())
+            .app_data(app_data.contest_repo.clone())
+            .app_data(app_data.session_store.clone())
+            .service(
+                web::scope("/api/players")
+                    .service(backend::player::controller::register_handler_prod)
+                    .service(backend::player::controller::login_handler_prod),
+            )
+            .service(
+                web::scope("/api/contests")
+                    .wrap(backend::auth::AuthMiddleware {
+                        redis: app_data.redis_arc.clone(),
+                    })
+                    .app_data(app_data.player_repo.clone())
+                    .service(backend::contest::controller::create_contest_handler),
+            ),
+    )
+    .await;
+
+    let session_id = create_authenticated_user!(app, "contest_guest@example.com", "contestguest");
+
+    // The guest wins, so a leaderboard that counted them would rank them first
+    let start: DateTime<FixedOffset> = DateTime::parse_from_rfc3339("2024-03-05T18:00:00Z")?;
+    let contest = json!({
+        "name": "Family Night",
+        "start": start.to_rfc3339(),
+        "stop": (start + chrono::Duration::hours(2)).to_rfc3339(),
+        "venue": create_test_venue_dto(),
+        "games": [create_test_game_dto()],
+        "outcomes": [
+            { "player_id": "", "place": "1", "result": "won", "handle": "Aunt May", "guest": true },
+            { "player_id": "", "place": "2", "result": "lost", "handle": "guesthost", "email": "guesthost@example.com" },
+            { "player_id": "", "place": "3", "result": "lost", "handle": "guestfriend", "email": "guestfriend@example.com" }
+        ]
+    });
+    let req = test::TestRequest::post()
+        .uri("/api/contests")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .set_json(&contest)
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    let created: ContestDto = test::read_body_json(resp).await;
+
+    let board = app_data
+        .contest_repo
+        .find_details_by_id(&created.id)
+        .await
+        .expect("Contest should be found");
+    let mut handles: Vec<(&str, &str, bool)> = board
+        .outcomes
+        .iter()
+        .map(|o| (o.handle.as_str(), o.place.as_str(), o.guest))
+        .collect();
+    handles.sort_by_key(|&(_, place, _)| place);
+    assert_eq!(
+        handles,
+        [
+            ("Aunt May", "1", true),
+            ("guesthost", "2", false),
+            ("guestfriend", "3", false)
+        ]
+    );
+
+    let stats = analytics
+        .get_contest_stats(&created.id)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?
+        .expect("Contest should have stats");
+    assert_eq!(stats.participant_count, 3);
+
+    ratings
+        .recompute_month(Some("2024-03".to_string()))
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let rating_board = ratings_repo
+        .get_leaderboard("global", None, 0, 10)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let rated: Vec<&str> = rating_board
+        .iter()
+        .filter_map(|entry| entry["player_id"].as_str())
+        .collect();
+    assert_eq!(rated.len(), 2, "only the players are rated: {:?}", rated);
+    assert!(rated.iter().all(|id| id.starts_with("player/")));
+
+    for category in ["win_rate", "total_wins", "total_contests"] {
+        let leaderboard = analytics
+            .get_leaderboard(category, 10, 0, None)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        assert!(
+            leaderboard.iter().all(|p| p.player_handle != "Aunt May"),
+            "guest on the {} leaderboard",
+            category
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rank_change_reports_climb_since_previous_period() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+
+    let db = app_data.contest_repo.db.clone();
+    for collection in ["rating_latest", "rating_history"] {
+        db.create_collection(collection)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to create {}: {}", collection, e))?;
+    }
+    let ratings_repo = backend::ratings::repository::RatingsRepository::new(db.clone());
+    let ratings = backend::ratings::usecase::RatingsUsecase::new(ratings_repo);
+    let scheduler = backend::ratings::scheduler::RatingsScheduler::new(ratings.clone());
+    let redis = (*app_data.redis_arc).clone();
+
+    let app = test::init_service(
+        App::new()
+            .wrap(backend::middleware::Logger::new())
+            .app_data(actix_web::web::JsonConfig::default().limit(256 * 1024))
+            .app_data(app_data.redis_data.clone())
+            .app_data(app_data.player_repo.clone())
+            .app_data(app_data.game_repo.clone())
+            .app_data(app_data.venue_repo.clone())
+            .app_data(app_data.contest_repo.clone())
+            .app_data(app_data.session_store.clone())
+            .service(
+                web::scope("/api/players")
+                    .service(backend::player::controller::register_handler_prod)
+                    .service(backend::player::controller::login_handler_prod),
+            )
+            .service(
+                web::scope("/api/contests")
+                    .wrap(backend::auth::AuthMiddleware {
+                        redis: app_data.redis_arc.clone(),
+                    })
+                    .app_data(actix_web::web::JsonConfig::default().limit(128 * 1024))
+                    .app_data(app_data.player_repo.clone())
+                    .service(backend::contest::controller::create_contest_handler),
+            )
+            .configure(|cfg| {
+                backend::ratings::controller::RatingsController::configure_routes(
+                    cfg,
+                    db.clone(),
+                    scheduler,
+                    redis,
+                    backend::ratings::glicko::Glicko2Params::default(),
+                    std::time::Duration::ZERO,
+                );
+            }),
+    )
+    .await;
+
+    let session_id = create_authenticated_user!(app, "rankclimber@example.com", "rankclimber");
+    let climber_id = ratings
+        .get_player_id_by_email("rankclimber@example.com")
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?
+        .expect("Registered user should have a player");
+
+    // January: the climber finishes last behind two new rivals. February:
+    // the climber beats both of them twice.
+    let mut players = vec![
+        json!({ "player_id": climber_id, "handle": "rankclimber", "email": "rankclimber@example.com" }),
+        json!({ "player_id": "", "handle": "rankrival0", "email": "rankrival0@example.com" }),
+        json!({ "player_id": "", "handle": "rankrival1", "email": "rankrival1@example.com" }),
+    ];
+    let schedule = [
+        ("2024-01-10T18:00:00Z", ["3", "1", "2"]),
+        ("2024-02-07T18:00:00Z", ["1", "2", "3"]),
+        ("2024-02-21T18:00:00Z", ["1", "2", "3"]),
+    ];
+    for (start, places) in schedule {
+        let start: DateTime<FixedOffset> = DateTime::parse_from_rfc3339(start)?;
+        let outcomes: Vec<_> = players
+            .iter()
+            .zip(places)
+            .map(|(player, place)| {
+                let mut outcome = player.clone();
+                outcome["place"] = json!(place);
+                outcome["result"] = json!(if place == "1" { "won" } else { "lost" });
+                outcome
+            })
+            .collect();
+        let req = test::TestRequest::post()
+            .uri("/api/contests")
+            .insert_header(("Authorization", format!("Bearer {}", session_id)))
+            .set_json(json!({
+                "name": format!("Rank Contest {}", start.to_rfc3339()),
+                "start": start.to_rfc3339(),
+                "stop": (start + chrono::Duration::hours(2)).to_rfc3339(),
+                "venue": create_test_venue_dto(),
+                "games": [create_test_game_dto()],
+                "outcomes": outcomes
+            }))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert!(resp.status().is_success());
+        let created: ContestDto = test::read_body_json(resp).await;
+        // Later contests reuse the rivals created by the first one
+        for player in players.iter_mut() {
+            let outcome = created
+                .outcomes
+                .iter()
+                .find(|outcome| player["handle"] == outcome.handle.as_str())
+                .expect("Every participant should have an outcome");
+            player["player_id"] = json!(outcome.player_id);
+        }
+    }
+
+    let req = test::TestRequest::get()
+        .uri("/api/ratings/me/rank-change")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .to_request();
+    let unranked: RatingRankChangeDto = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(unranked.rank, None);
+    assert_eq!(unranked.delta, None);
+
+    for period in ["2024-01", "2024-02"] {
+        ratings
+            .recompute_month_with_history(Some(period.to_string()))
+            .await
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+    }
+
+    let req = test::TestRequest::get()
+        .uri("/api/ratings/me/rank-change")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .to_request();
+    let change: RatingRankChangeDto = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(change.player_id, climber_id);
+    assert_eq!(change.previous_rank, Some(3));
+    assert_eq!(change.rank, Some(1));
+    assert_eq!(
+        change.delta,
+        Some(2),
+        "Climbing from 3rd to 1st is +2 places"
+    );
+    assert_eq!(
+        change.previous_period_end.as_deref(),
+        Some("2024-02-01T00:00:00Z")
+    );
+    assert_eq!(change.period_end.as_deref(), Some("2024-03-01T00:00:00Z"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_similar_rated_lists_players_inside_the_window_by_closeness() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+
+    let db = app_data.contest_repo.db.clone();
+    db.create_collection("rating_latest")
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to create rating_latest: {}", e))?;
+    let ratings_repo = backend::ratings::repository::RatingsRepository::new(db.clone());
+    let ratings = backend::ratings::usecase::RatingsUsecase::new(ratings_repo);
+    let scheduler = backend::ratings::scheduler::RatingsScheduler::new(ratings.clone());
+    let redis = (*app_data.redis_arc).clone();
+
+    let app = test::init_service(
+        App::new()
+            .app_data(app_data.redis_data.clone())
+            .app_data(app_data.player_repo.clone())
+            .app_data(app_data.session_store.clone())
+            .service(
+                web::scope("/api/players")
+                    .service(backend::player::controller::register_handler_prod)
+                    .service(backend::player::controller::login_handler_prod),
+            )
+            .configure(|cfg| {
+                backend::ratings::controller::RatingsController::configure_routes(
+                    cfg,
+                    db.clone(),
+                    scheduler,
+                    redis,
+                    backend::ratings::glicko::Glicko2Params::default(),
+                    std::time::Duration::ZERO,
+                );
+            }),
+    )
+    .await;
+
+    let session_id = create_authenticated_user!(app, "matchmaker@example.com", "matchmaker");
+    let me = ratings
+        .get_player_id_by_email("matchmaker@example.com")
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?
+        .expect("Registered user should have a player");
+
+    let req = test::TestRequest::get()
+        .uri("/api/ratings/me/similar")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .to_request();
+    let unrated: Vec<RatingLeaderboardEntryDto> = test::call_and_read_body_json(&app, req).await;
+    assert!(
+        unrated.is_empty(),
+        "An unrated player has no similar players"
+    );
+
+    let _: Vec<serde_json::Value> = db
+        .aql_query(
+            arangors::AqlQuery::builder()
+                .query(
+                    r#"
+                    LET others = (
+                      FOR p IN @players
+                        INSERT { handle: p.handle } INTO player
+                        RETURN { player_id: NEW._id, rating: p.rating }
+                    )
+                    FOR r IN APPEND(others, [{ player_id: @me, rating: 1500 }])
+                      INSERT { player_id: r.player_id, scope_type: "global", scope_id: null,
+                               rating: r.rating, rd: 80, games_played: 12,
+                               last_period_end: "2024-03-01T00:00:00Z" } INTO rating_latest
+                    "#,
+                )
+                .bind_var(
+                    "players",
+                    json!([
+                        { "handle": "far_above", "rating": 1700 },
+                        { "handle": "just_above", "rating": 1540 },
+                        { "handle": "just_below", "rating": 1480 },
+                        { "handle": "edge_below", "rating": 1400 },
+                        { "handle": "far_below", "rating": 1250 },
+                    ]),
+                )
+                .bind_var("me", me.as_str())
+                .build(),
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to seed ratings: {}", e))?;
+
+    let req = test::TestRequest::get()
+        .uri("/api/ratings/me/similar?window=100")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .to_request();
+    let similar: Vec<RatingLeaderboardEntryDto> = test::call_and_read_body_json(&app, req).await;
+    let handles: Vec<_> = similar.iter().filter_map(|e| e.handle.as_deref()).collect();
+    assert_eq!(handles, ["just_below", "just_above", "edge_below"]);
+    assert!(similar.iter().all(|e| e.player_id != me));
+    assert_eq!(similar[0].rating, 1480.0);
+    assert_eq!(similar[0].games_played, 12);
+
+    let req = test::TestRequest::get()
+        .uri("/api/ratings/me/similar?window=-5")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 400);
+
+    Ok(())
+}
+
+/// Answers every request with `status`, recording the request bodies
+fn mock_webhook_server(status: u16) -> (String, Arc<Mutex<Vec<serde_json::Value>>>) {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    let bodies = Arc::new(Mutex::new(Vec::new()));
+    let recorded = bodies.clone();
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let mut reader = BufReader::new(stream);
+            let mut content_length = 0;
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap_or(0) > 2 {
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap_or(0);
+                    }
+                }
+                line.clear();
+            }
+            let mut body = vec![0; content_length];
+            if reader.read_exact(&mut body).is_ok() {
+                if let Ok(json) = serde_json::from_slice(&body) {
+                    recorded.lock().unwrap().push(json);
+                }
+            }
+            let response = format!(
+                "HTTP/1.1 {} Status\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                status
+            );
+            let _ = reader.get_mut().write_all(response.as_bytes());
+        }
+    });
+    (url, bodies)
+}
+
+#[tokio::test]
+async fn test_create_contest_posts_webhooks_without_failing_on_errors() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+
+    let (ok_url, ok_bodies) = mock_webhook_server(200);
+    let (failing_url, failing_bodies) = mock_webhook_server(500);
+    let webhooks = ContestWebhooks::new(WebhookConfig {
+        endpoints: vec![
+            WebhookEndpoint {
+                url: ok_url,
+                secret: None,
+            },
+            WebhookEndpoint {
+                url: failing_url,
+                secret: None,
+            },
+        ],
+        timeout_ms: 1000,
+        max_attempts: 2,
+        initial_backoff_ms: 10,
+    });
+
+    let app = test::init_service(
+        App::new()
+            .app_data(actix_web::web::JsonConfig::default().limit(256 * 1024))
+            .app_data(app_data.redis_data.clone())
+            .app_data(app_data.player_repo.clone())
+            .app_data(app_data.game_repo.clone())
+            .app_data(app_data.venue_repo.clone())
+            .app_data(app_data.contest_repo.clone())
+            .app_data(app_data.session_store.clone())
+            .service(
+                web::scope("/api/players")
+                    .service(backend::player::controller::register_handler_prod)
+                    .service(backend::player::controller::login_handler_prod),
+            )
+            .service(
+                web::scope("/api/contests")
+                    .wrap(backend::auth::AuthMiddleware {
+                        redis: app_data.redis_arc.clone(),
+                    })
+                    .app_data(app_data.player_repo.clone())
+                    .app_data(web::Data::new(webhooks))
+                    .service(backend::contest::controller::create_contest_handler),
+            ),
+    )
+    .await;
+
+    let session_id = create_authenticated_user!(app, "webhook@example.com", "webhookuser");
+
+    let start: DateTime<FixedOffset> = Utc::now().into();
+    let req = test::TestRequest::post()
+        .uri("/api/contests")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .set_json(&json!({
+            "name": "Webhook Contest",
+            "start": start.to_rfc3339(),
+            "stop": (start + chrono::Duration::hours(1)).to_rfc3339(),
+            "venue": create_test_venue_dto(),
+            "games": [create_test_game_dto()],
+            "outcomes": []
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(
+        resp.status().is_success(),
+        "A failing webhook must not fail contest creation, got: {}",
+        resp.status()
+    );
+    let created: ContestDto = test::read_body_json(resp).await;
+
+    // Delivery happens in the background; the failing endpoint is retried once
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+    while (ok_bodies.lock().unwrap().is_empty() || failing_bodies.lock().unwrap().len() < 2)
+        && std::time::Instant::now() < deadline
+    {
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+
+    let ok_bodies = ok_bodies.lock().unwrap();
+    assert_eq!(ok_bodies.len(), 1);
+    let payload = &ok_bodies[0];
+    assert_eq!(payload["event"], "contest.created");
+    assert_eq!(payload["contest_id"], created.id.as_str());
+    assert_eq!(payload["name"], "Webhook Contest");
+    assert_eq!(payload["venue"], "Test Venue");
+    assert_eq!(payload["games"], json!(["Test Game"]));
+    assert_eq!(failing_bodies.lock().unwrap().len(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_csv_template_round_trips_through_import() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+
+    let app = test::init_service(
+        App::new()
+            .wrap(backend::middleware::Logger::new())
+            .app_data(actix_web::web::JsonConfig::default().limit(256 * 1024))
+            .app_data(app_data.redis_data.clone())
+            .app_data(app_data.player_repo.clone())
+            .app_data(app_data.game_repo.clone())
+            .app_data(app_data.venue_repo.clone())
+            .app_data(app_data.contest_repo.clone())
+            .app_data(app_data.session_store.clone())
+            .service(
+                web::scope("/api/players")
+                    .service(backend::player::controller::register_handler_prod)
+                    .service(backend::player::controller::login_handler_prod),
+            )
+            .service(
+                web::scope("/api/contests")
+                    .wrap(backend::auth::AuthMiddleware {
+                        redis: app_data.redis_arc.clone(),
+                    })
+                    .app_data(actix_web::web::JsonConfig::default().limit(128 * 1024))
+                    .app_data(app_data.player_repo.clone())
+                    .service(backend::contest::controller::create_contest_handler)
+                    .service(backend::contest::controller::contest_csv_template_handler)
+                    .service(backend::contest::controller::import_contests_csv_handler)
+                    .service(backend::contest::controller::get_contest_handler),
+            ),
+    )
+    .await;
+
+    // The template's example row names these players, venue and games
+    create_authenticated_user!(app, "alice@example.com", "alice");
+    create_authenticated_user!(app, "bob@example.com", "bob");
+    let session_id = create_authenticated_user!(app, "organizer@example.com", "organizer");
+    let start: DateTime<FixedOffset> = Utc::now().into();
+    let seed = json!({
+        "name": "Seed Contest",
+        "start": start.to_rfc3339(),
+        "stop": (start + chrono::Duration::hours(2)).to_rfc3339(),
+        "venue": VenueDto {
+            display_name: "The Board Room".to_string(),
+            ..create_test_venue_dto()
+        },
+        "games": [
+            GameDto { name: "Catan".to_string(), ..create_test_game_dto() },
+            GameDto { name: "Azul".to_string(), ..create_test_game_dto() }
+        ],
+        "outcomes": []
+    });
+    let req = test::TestRequest::post()
+        .uri("/api/contests")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .set_json(&seed)
+        .to_request();
+    assert!(test::call_service(&app, req).await.status().is_success());
+
+    let req = test::TestRequest::get()
+        .uri("/api/contests/template.csv")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    let content_type = resp.headers().get("content-type").expect("content type");
+    assert!(content_type.to_str()?.starts_with("text/csv"));
+    let template = String::from_utf8(test::read_body(resp).await.to_vec())?;
+    assert!(template.starts_with("name,start,stop,venue,games,participants\r\n"));
+
+    // The example row, plus one naming a venue that does not exist
+    let upload = format!(
+        "{}\"Unknown \"\"Annex\"\"\",2026-01-10T19:00:00Z,2026-01-10T21:00:00Z,Annex,Catan,alice:1\r\n",
+        template
+    );
+    let req = test::TestRequest::post()
+        .uri("/api/contests/import.csv")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .insert_header(("Content-Type", "text/csv"))
+        .set_payload(upload)
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    let report: ContestImportReportDto = test::read_body_json(resp).await;
+    assert_eq!((report.imported, report.failed), (1, 1));
+    assert_eq!(report.rows[0].line, 2);
+    assert_eq!(report.rows[1].line, 3);
+    assert_eq!(
+        report.rows[1].error.as_deref(),
+        Some("Unknown venue 'Annex'")
+    );
+
+    let contest_id = report.rows[0].contest_id.clone().expect("imported row");
+    let req = test::TestRequest::get()
+        .uri(&format!(
+            "/api/contests/{}",
+            contest_id.trim_start_matches("contest/")
+        ))
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .to_request();
+    let imported: ContestDto = test::call_and_read_body_json(&app, req).await;
+    assert_eq!(imported.name, "Friday Night, Table 1");
+    assert_eq!(imported.venue.display_name, "The Board Room");
+    let mut games: Vec<&str> = imported.games.iter().map(|g| g.name.as_str()).collect();
+    games.sort();
+    assert_eq!(games, ["Azul", "Catan"]);
+    let mut outcomes: Vec<(&str, &str, &str)> = imported
+        .outcomes
+        .iter()
+        .map(|o| (o.handle.as_str(), o.place.as_str(), o.result.as_str()))
+        .collect();
+    outcomes.sort();
+    assert_eq!(outcomes, [("alice", "1", "won"), ("bob", "2", "lost")]);
+
+    // A file without the template header is rejected outright
+    let req = test::TestRequest::post()
+        .uri("/api/contests/import.csv")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .set_payload("name,start\r\n")
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 400);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_undo_within_window_removes_contest_from_stats_and_ratings() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+
+    let db = app_data.contest_repo.db.clone();
+    for collection in ["rating_latest", "rating_history"] {
+        db.create_collection(collection)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to create {}: {}", collection, e))?;
+    }
+    let ratings_repo = backend::ratings::repository::RatingsRepository::new(db.clone());
+    let ratings = backend::ratings::usecase::RatingsUsecase::new(ratings_repo);
+    let scheduler = backend::ratings::scheduler::RatingsScheduler::new(ratings.clone());
+
+    let app = test::init_service(
+        App::new()
+            .app_data(actix_web::web::JsonConfig::default().limit(256 * 1024))
+            .app_data(app_data.redis_data.clone())
+            .app_data(app_data.player_repo.clone())
+            .app_data(app_data.game_repo.clone())
+            .app_data(app_data.venue_repo.clone())
+            .app_data(app_data.contest_repo.clone())
+            .app_data(app_data.session_store.clone())
+            .app_data(web::Data::new(scheduler))
+            .service(
+                web::scope("/api/players")
+                    .service(backend::player::controller::register_handler_prod)
+                    .service(backend::player::controller::login_handler_prod),
+            )
+            .service(
+                web::scope("/api/contests")
+                    .wrap(backend::auth::AuthMiddleware {
+                        redis: app_data.redis_arc.clone(),
+                    })
+                    .app_data(app_data.player_repo.clone())
+                    .service(backend::contest::controller::create_contest_handler)
+                    .service(backend::contest::controller::get_contest_handler)
+                    .service(backend::contest::controller::delete_contest_handler),
+            ),
+    )
+    .await;
+
+    let session_id = create_authenticated_user!(app, "contest_undo@example.com", "contestundo");
+
+    let january: DateTime<FixedOffset> = DateTime::parse_from_rfc3339("2024-01-10T18:00:00Z")?;
+    let contest = |name: &str, start: DateTime<FixedOffset>, outcomes: serde_json::Value| {
+        json!({
+            "name": name,
+            "start": start.to_rfc3339(),
+            "stop": (start + chrono::Duration::hours(2)).to_rfc3339(),
+            "venue": create_test_venue_dto(),
+            "games": [create_test_game_dto()],
+            "outcomes": outcomes
+        })
+    };
+    let req = test::TestRequest::post()
+        .uri("/api/contests")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .set_json(contest(
+            "Undo Kept",
+            january,
+            json!([
+                { "player_id": "", "place": "1", "result": "won", "handle": "undoalpha", "email": "undoalpha@example.com" },
+                { "player_id": "", "place": "2", "result": "lost", "handle": "undobeta", "email": "undobeta@example.com" }
+            ]),
+        ))
+        .to_request();
+    let kept: ContestDto = test::call_and_read_body_json(&app, req).await;
+    let alpha = kept.outcomes[0].clone();
+    let beta = kept.outcomes[1].clone();
+
+    // The fat-fingered rematch, with the result the wrong way round
+    let req = test::TestRequest::post()
+        .uri("/api/contests")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .set_json(contest(
+            "Undo Mistake",
+            january,
+            json!([
+                { "player_id": beta.player_id, "place": "1", "result": "won", "handle": beta.handle, "email": beta.email },
+                { "player_id": alpha.player_id, "place": "2", "result": "lost", "handle": alpha.handle, "email": alpha.email }
+            ]),
+        ))
+        .to_request();
+    let mistake: ContestDto = test::call_and_read_body_json(&app, req).await;
+
+    // A later month builds on January's ratings
+    let req = test::TestRequest::post()
+        .uri("/api/contests")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .set_json(contest(
+            "Undo Later",
+            january + chrono::Duration::days(31),
+            json!([
+                { "player_id": alpha.player_id, "place": "1", "result": "won", "handle": alpha.handle, "email": alpha.email },
+                { "player_id": beta.player_id, "place": "2", "result": "lost", "handle": beta.handle, "email": beta.email }
+            ]),
+        ))
+        .to_request();
+    assert!(test::call_service(&app, req).await.status().is_success());
+
+    for period in ["2024-01", "2024-02"] {
+        ratings
+            .recompute_month_with_history(Some(period.to_string()))
+            .await
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+    }
+    let rating_at = |player_id: String, period_end: &'static str| {
+        let db = db.clone();
+        async move {
+            let query = arangors::AqlQuery::builder()
+                .query(
+                    r#"
+                    FOR h IN rating_history
+                        FILTER h.player_id == @player_id AND h.period_end == @period_end
+                        RETURN h
+                    "#,
+                )
+                .bind_var("player_id", player_id)
+                .bind_var("period_end", period_end)
+                .build();
+            let rows: Vec<serde_json::Value> = db
+                .aql_query(query)
+                .await
+                .map_err(|e| anyhow::anyhow!("{}", e))?;
+            assert_eq!(rows.len(), 1, "Expected one history row for {}", period_end);
+            anyhow::Ok((rows[0]["rating"].as_f64(), rows[0]["period_games"].as_i64()))
+        }
+    };
+    let january_end = "2024-02-01T00:00:00Z";
+    let february_end = "2024-03-01T00:00:00Z";
+    assert_eq!(
+        rating_at(alpha.player_id.clone(), january_end).await?.1,
+        Some(2)
+    );
+    let february_before = rating_at(alpha.player_id.clone(), february_end).await?.0;
+
+    let mistake_key = mistake.id.trim_start_matches("contest/").to_string();
+    let req = test::TestRequest::delete()
+        .uri(&format!("/api/contests/{}", mistake_key))
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 204);
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/contests/{}", mistake_key))
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 404);
+
+    // The contest no longer counts towards either player
+    let played = app_data
+        .contest_repo
+        .find_contests_by_player_and_game(&alpha.player_id, &kept.games[0].id)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(played.len(), 1);
+    assert_eq!(
+        rating_at(alpha.player_id.clone(), january_end).await?.1,
+        Some(1),
+        "Undo should re-rate the month without the deleted contest"
+    );
+    assert_eq!(
+        rating_at(beta.player_id.clone(), january_end).await?.1,
+        Some(1)
+    );
+
+    // Later months are re-rated on top of the corrected January
+    let february_after = rating_at(alpha.player_id.clone(), february_end).await?.0;
+    assert_ne!(february_after, february_before);
+
+    // Each player has exactly one history row per month
+    let duplicates = arangors::AqlQuery::builder()
+        .query(
+            r#"
+            FOR h IN rating_history
+                COLLECT player_id = h.player_id, period_end = h.period_end WITH COUNT INTO rows
+                FILTER rows > 1
+                RETURN { player_id, period_end, rows }
+            "#,
+        )
+        .build();
+    let duplicates: Vec<serde_json::Value> = db
+        .aql_query(duplicates)
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert!(
+        duplicates.is_empty(),
+        "Duplicate history rows: {:?}",
+        duplicates
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_undo_after_window_requires_admin() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+
+    let app = test::init_service(
+        App::new()
+            .app_data(actix_web::web::JsonConfig::default().limit(256 * 1024))
+            .app_data(app_data.redis_data.clone())
+            .app_data(app_data.player_repo.clone())
+            .app_data(app_data.game_repo.clone())
+            .app_data(app_data.venue_repo.clone())
+            .app_data(app_data.contest_repo.clone())
+            .app_data(app_data.session_store.clone())
+            .service(
+                web::scope("/api/players")
+                    .service(backend::player::controller::register_handler_prod)
+                    .service(backend::player::controller::login_handler_prod),
+            )
+            .service(
+                web::scope("/api/contests")
+                    .wrap(backend::auth::AuthMiddleware {
+                        redis: app_data.redis_arc.clone(),
+                    })
+                    .app_data(app_data.player_repo.clone())
+                    .app_data(web::Data::new(backend::config::ContestConfig {
+                        undo_window_minutes: 0,
+                        ..Default::default()
+                    }))
+                    .service(backend::contest::controller::create_contest_handler)
+                    .service(backend::contest::controller::get_contest_handler)
+                    .service(backend::contest::controller::delete_contest_handler),
+            ),
+    )
+    .await;
+
+    let creator_session = create_authenticated_user!(app, "undo_late@example.com", "undolate");
+    let admin_session = create_authenticated_user!(app, "undo_admin@example.com", "undoadmin");
+    let db = app_data.contest_repo.db.clone();
+    db.aql_query::<serde_json::Value>(
+        arangors::AqlQuery::builder()
+            .query("FOR p IN player FILTER p.email == @email UPDATE p WITH { isAdmin: true } IN player")
+            .bind_var("email", "undo_admin@example.com")
+            .build(),
+    )
+    .await
+    .map_err(|e| anyhow::anyhow!("Failed to promote admin: {}", e))?;
+
+    let start: DateTime<FixedOffset> = Utc::now().into();
+    let req = test::TestRequest::post()
+        .uri("/api/contests")
+        .insert_header(("Authorization", format!("Bearer {}", creator_session)))
+        .set_json(json!({
+            "name": "Undo Too Late",
+            "start": start.to_rfc3339(),
+            "stop": (start + chrono::Duration::hours(1)).to_rfc3339(),
+            "venue": create_test_venue_dto(),
+            "games": [create_test_game_dto()],
+            "outcomes": [
+                { "player_id": "", "place": "1", "result": "won", "handle": "latealpha", "email": "latealpha@example.com" },
+                { "player_id": "", "place": "2", "result": "lost", "handle": "latebeta", "email": "latebeta@example.com" }
+            ]
+        }))
+        .to_request();
+    let created: ContestDto = test::call_and_read_body_json(&app, req).await;
+    let uri = format!(
+        "/api/contests/{}",
+        created.id.trim_start_matches("contest/")
+    );
+
+    let req = test::TestRequest::delete()
+        .uri(&uri)
+        .insert_header(("Authorization", format!("Bearer {}", creator_session)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 403);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["error"], "undo_window_closed");
+
+    let req = test::TestRequest::get()
+        .uri(&uri)
+        .insert_header(("Authorization", format!("Bearer {}", creator_session)))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 200);
+
+    let req = test::TestRequest::delete()
+        .uri(&uri)
+        .insert_header(("Authorization", format!("Bearer {}", admin_session)))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 204);
+
+    let req = test::TestRequest::get()
+        .uri(&uri)
+        .insert_header(("Authorization", format!("Bearer {}", admin_session)))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), 404);
+
+    Ok(())
+}
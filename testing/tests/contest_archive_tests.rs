@@ -0,0 +1,227 @@
+//! Integration tests for contest archival
+//!
+//! Archived contests leave the default listing, can still be counted by
+//! analytics on request, and come back intact when restored
+
+use actix_web::{test, web, App};
+use anyhow::{Context, Result};
+use arangors::client::reqwest::ReqwestClient;
+use arangors::{AqlQuery, Database};
+use backend::admin::archive;
+use backend::analytics::AnalyticsRepository;
+use backend::config::DatabaseConfig;
+use backend::contest::repository::ContestRepository;
+use chrono::{DateTime, Duration, FixedOffset, Utc};
+use shared::dto::contest::{ContestDto, OutcomeDto};
+use shared::dto::game::GameDto;
+use shared::dto::venue::VenueDto;
+use shared::models::contest::ContestVisibility;
+use shared::models::game::GameSource;
+use shared::models::venue::VenueSource;
+use testing::create_authenticated_user;
+use testing::{app_setup, TestEnvironment};
+
+fn contest_dto(name: &str, start: DateTime<FixedOffset>, handles: &[&str]) -> ContestDto {
+    ContestDto {
+        id: String::new(),
+        name: name.to_string(),
+        start,
+        stop: start + Duration::hours(1),
+        venue: VenueDto {
+            id: String::new(),
+            display_name: "Archive Venue".to_string(),
+            formatted_address: "1 Archive St".to_string(),
+            place_id: "archive_place_id".to_string(),
+            lat: 40.7128,
+            lng: -74.0060,
+            timezone: "America/New_York".to_string(),
+            source: VenueSource::Database,
+        },
+        games: vec![GameDto {
+            id: String::new(),
+            name: "Archive Game".to_string(),
+            year_published: Some(2020),
+            bgg_id: None,
+            description: None,
+            source: GameSource::Database,
+        }],
+        outcomes: handles
+            .iter()
+            .enumerate()
+            .map(|(i, handle)| OutcomeDto {
+                player_id: String::new(),
+                place: (i + 1).to_string(),
+                result: if i == 0 { "won" } else { "lost" }.to_string(),
+                email: format!("{}@example.com", handle),
+                handle: handle.to_string(),
+                guest: false,
+            })
+            .collect(),
+        creator_id: String::new(),
+        created_at: None,
+        rated: true,
+        visibility: ContestVisibility::Public,
+    }
+}
+
+/// Number of edges in `collection` leaving `contest_id`
+async fn edge_count(
+    db: &Database<ReqwestClient>,
+    collection: &str,
+    contest_id: &str,
+) -> Result<usize> {
+    let query = AqlQuery::builder()
+        .query("FOR e IN @@collection FILTER e._from == @contest RETURN 1")
+        .bind_var("@collection", collection)
+        .bind_var("contest", contest_id)
+        .build();
+    let edges: Vec<i32> = db
+        .aql_query(query)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to count {} edges: {}", collection, e))?;
+    Ok(edges.len())
+}
+
+#[tokio::test]
+async fn test_archived_contests_leave_default_listing_and_restore_intact() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+    let db = env.database().await?;
+
+    let now: DateTime<FixedOffset> = Utc::now().into();
+    let old = app_data
+        .contest_repo
+        .create_contest(
+            contest_dto(
+                "Archive Old Night",
+                now - Duration::days(800),
+                &["archiveold1", "archiveold2"],
+            ),
+            "player/creator".to_string(),
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to create contest: {}", e))?;
+    app_data
+        .contest_repo
+        .create_contest(
+            contest_dto(
+                "Archive Recent Night",
+                now - Duration::days(1),
+                &["archiverecent1", "archiverecent2"],
+            ),
+            "player/creator".to_string(),
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to create contest: {}", e))?;
+
+    let archived = archive::archive_contests_before(&db, now - Duration::days(365))
+        .await
+        .map_err(|e| anyhow::anyhow!("Archival failed: {}", e))?;
+    assert_eq!(archived, [old.id.clone()]);
+    assert!(app_data.contest_repo.find_by_id(&old.id).await.is_none());
+    for collection in ["resulted_in", "played_with", "played_at"] {
+        assert_eq!(edge_count(&db, collection, &old.id).await?, 0);
+    }
+
+    // The default listing only shows the recent contest
+    let app = test::init_service(
+        App::new()
+            .app_data(app_data.redis_data.clone())
+            .app_data(app_data.player_repo.clone())
+            .app_data(app_data.contest_repo.clone())
+            .app_data(app_data.session_store.clone())
+            .service(
+                web::scope("/api/players")
+                    .service(backend::player::controller::register_handler_prod)
+                    .service(backend::player::controller::login_handler_prod),
+            )
+            .service(
+                web::scope("/api/contests")
+                    .wrap(backend::auth::AuthMiddleware {
+                        redis: app_data.redis_arc.clone(),
+                    })
+                    .app_data(app_data.player_repo.clone())
+                    .service(backend::contest::controller::search_contests_handler),
+            ),
+    )
+    .await;
+    let session_id = create_authenticated_user!(app, "archive@example.com", "archivist");
+    let search = |session_id: &str| {
+        test::TestRequest::get()
+            .uri("/api/contests/search?q=Archive&scope=all")
+            .insert_header(("Authorization", format!("Bearer {}", session_id)))
+            .to_request()
+    };
+    let body: serde_json::Value =
+        test::read_body_json(test::call_service(&app, search(&session_id)).await).await;
+    let listing = body.to_string();
+    assert!(listing.contains("Archive Recent Night"));
+    assert!(!listing.contains("Archive Old Night"));
+
+    // Analytics count archived contests only when asked to
+    let analytics = AnalyticsRepository::new(
+        db.clone(),
+        DatabaseConfig {
+            url: env.arangodb_url().to_string(),
+            name: "_system".to_string(),
+            username: "root".to_string(),
+            password: "test_password".to_string(),
+            root_username: "root".to_string(),
+            root_password: "test_password".to_string(),
+            pool_size: 1,
+            _timeout_seconds: 30,
+        },
+    );
+    let contests_in = |trends: Vec<shared::models::analytics::MonthlyContests>| {
+        trends.iter().map(|month| month.contests).sum::<i32>()
+    };
+    let hot = analytics
+        .get_contest_trends(36, false)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to get contest trends: {}", e))?;
+    assert_eq!(contests_in(hot), 1);
+    let all = analytics
+        .get_contest_trends(36, true)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to get contest trends: {}", e))?;
+    assert_eq!(contests_in(all), 2);
+
+    // Restoring brings the contest and its edges back, and empties the archive
+    let key = old.id.trim_start_matches("contest/");
+    let restored = archive::restore_contest(&db, key)
+        .await
+        .map_err(|e| anyhow::anyhow!("Restore failed: {}", e))?;
+    assert_eq!(restored.as_deref(), Some(old.id.as_str()));
+    let contest = app_data
+        .contest_repo
+        .find_by_id(&old.id)
+        .await
+        .context("Restored contest should be found")?;
+    assert_eq!(contest.name, "Archive Old Night");
+    assert_eq!(edge_count(&db, "resulted_in", &old.id).await?, 2);
+    assert_eq!(edge_count(&db, "played_with", &old.id).await?, 1);
+    assert_eq!(edge_count(&db, "played_at", &old.id).await?, 1);
+    let remaining: Vec<usize> = db
+        .aql_query(
+            AqlQuery::builder()
+                .query("RETURN LENGTH(@@archive)")
+                .bind_var("@archive", archive::ARCHIVE_COLLECTION)
+                .build(),
+        )
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to count the archive: {}", e))?;
+    assert_eq!(remaining, [0]);
+    assert_eq!(
+        archive::restore_contest(&db, key)
+            .await
+            .map_err(|e| anyhow::anyhow!("Restore failed: {}", e))?,
+        None
+    );
+
+    let body: serde_json::Value =
+        test::read_body_json(test::call_service(&app, search(&session_id)).await).await;
+    assert!(body.to_string().contains("Archive Old Night"));
+
+    Ok(())
+}
@@ -0,0 +1,332 @@
+//! Tests for downloading and loading data dumps
+//!
+//! Runs in its own test binary because it sets the dump auth environment
+//! variable.
+
+use anyhow::{Context, Result};
+use arangors::Connection;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::path::Path;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+use testing::{download_data_dump, TestEnvironmentBuilder, DATA_DUMP_AUTH_ENV};
+
+/// Serves `body` to every request, recording each request's auth header
+fn serve_dump(body: Vec<u8>) -> Result<(String, Arc<Mutex<Vec<Option<String>>>>)> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let url = format!("http://{}/dumps/dump.zip", listener.local_addr()?);
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let recorded = requests.clone();
+
+    std::thread::spawn(move || {
+        for mut stream in listener.incoming().flatten() {
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                match stream.read(&mut buf) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => request.extend_from_slice(&buf[..n]),
+                }
+            }
+            let auth = String::from_utf8_lossy(&request).lines().find_map(|line| {
+                let (name, value) = line.split_once(':')?;
+                name.eq_ignore_ascii_case("authorization")
+                    .then(|| value.trim().to_string())
+            });
+            recorded.lock().unwrap().push(auth);
+
+            let header = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/zip\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                body.len()
+            );
+            let _ = stream.write_all(header.as_bytes());
+            let _ = stream.write_all(&body);
+        }
+    });
+
+    Ok((url, requests))
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// Builds an uncompressed zip archive from (name, content) entries
+fn zip_archive(entries: &[(&str, String)]) -> Vec<u8> {
+    let mut archive = Vec::new();
+    let mut central = Vec::new();
+    for (name, content) in entries {
+        let offset = archive.len() as u32;
+        let crc = crc32(content.as_bytes());
+        let size = content.len() as u32;
+        let name_len = name.len() as u16;
+
+        archive.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        archive.extend_from_slice(&20u16.to_le_bytes()); // version needed
+        archive.extend_from_slice(&[0; 4]); // flags, method (stored)
+        archive.extend_from_slice(&0u16.to_le_bytes()); // time
+        archive.extend_from_slice(&0x21u16.to_le_bytes()); // date, 1980-01-01
+        archive.extend_from_slice(&crc.to_le_bytes());
+        archive.extend_from_slice(&size.to_le_bytes());
+        archive.extend_from_slice(&size.to_le_bytes());
+        archive.extend_from_slice(&name_len.to_le_bytes());
+        archive.extend_from_slice(&0u16.to_le_bytes()); // extra length
+        archive.extend_from_slice(name.as_bytes());
+        archive.extend_from_slice(content.as_bytes());
+
+        central.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+        central.extend_from_slice(&20u16.to_le_bytes()); // version made by
+        central.extend_from_slice(&20u16.to_le_bytes()); // version needed
+        central.extend_from_slice(&[0; 4]); // flags, method (stored)
+        central.extend_from_slice(&0u16.to_le_bytes()); // time
+        central.extend_from_slice(&0x21u16.to_le_bytes()); // date
+        central.extend_from_slice(&crc.to_le_bytes());
+        central.extend_from_slice(&size.to_le_bytes());
+        central.extend_from_slice(&size.to_le_bytes());
+        central.extend_from_slice(&name_len.to_le_bytes());
+        central.extend_from_slice(&[0; 8]); // extra, comment, disk, internal attrs
+        central.extend_from_slice(&0u32.to_le_bytes()); // external attrs
+        central.extend_from_slice(&offset.to_le_bytes());
+        central.extend_from_slice(name.as_bytes());
+    }
+
+    let central_offset = archive.len() as u32;
+    let count = entries.len() as u16;
+    archive.extend_from_slice(&central);
+    archive.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+    archive.extend_from_slice(&[0; 4]); // disk numbers
+    archive.extend_from_slice(&count.to_le_bytes());
+    archive.extend_from_slice(&count.to_le_bytes());
+    archive.extend_from_slice(&(central.len() as u32).to_le_bytes());
+    archive.extend_from_slice(&central_offset.to_le_bytes());
+    archive.extend_from_slice(&0u16.to_le_bytes()); // comment length
+    archive
+}
+
+/// arangodump output for a single `dump_players` collection, as (path, content)
+fn player_dump_files(db_name: &str, handles: &[&str]) -> Vec<(String, String)> {
+    let structure = serde_json::json!({
+        "parameters": { "name": "dump_players", "type": 2 },
+        "indexes": []
+    });
+    let data = handles
+        .iter()
+        .map(|handle| serde_json::json!({ "_key": handle, "handle": handle }).to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+    vec![
+        (
+            format!("{}/dump_players.structure.json", db_name),
+            structure.to_string(),
+        ),
+        (format!("{}/dump_players.data.json", db_name), data),
+    ]
+}
+
+/// An arangodump-style archive with a single `dump_players` collection
+fn player_dump(db_name: &str, handles: &[&str]) -> Vec<u8> {
+    let files = player_dump_files(db_name, handles);
+    let entries: Vec<(&str, String)> = files
+        .iter()
+        .map(|(name, content)| (name.as_str(), content.clone()))
+        .collect();
+    zip_archive(&entries)
+}
+
+/// Writes the `dump_players` dump as a directory under `dir`
+fn write_player_dump_dir(dir: &Path, db_name: &str, handles: &[&str]) -> Result<()> {
+    for (name, content) in player_dump_files(db_name, handles) {
+        let path = dir.join(name);
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        std::fs::write(path, content)?;
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_data_dump_url_is_downloaded_cached_and_loaded() -> Result<()> {
+    std::env::set_var(DATA_DUMP_AUTH_ENV, "Bearer dump-token");
+    let (url, requests) = serve_dump(player_dump("dump_test", &["ann", "ben", "cat"]))?;
+
+    // A second download of the same URL comes from the cache
+    let first = download_data_dump(&url).await?;
+    let second = download_data_dump(&url).await?;
+    assert_eq!(first, second);
+    assert_eq!(
+        *requests.lock().unwrap(),
+        [Some("Bearer dump-token".to_string())]
+    );
+
+    let env = TestEnvironmentBuilder::new()
+        .with_data_dump_url(&url)
+        .with_database_name("dump_test")
+        .build()
+        .await?;
+    assert_eq!(requests.lock().unwrap().len(), 1);
+
+    let conn = Connection::establish_basic_auth(env.arangodb_url(), "root", "test_password")
+        .await
+        .context("Failed to connect to ArangoDB")?;
+    let db = conn
+        .db("dump_test")
+        .await
+        .context("Dump database should exist")?;
+    let handles: Vec<String> = db
+        .aql_str("FOR p IN dump_players SORT p.handle RETURN p.handle")
+        .await
+        .context("Failed to query restored collection")?;
+    assert_eq!(handles, ["ann", "ben", "cat"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_reloading_with_overwrite_replaces_instead_of_merging() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("stg-overwrite-dump-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let first = dir.join("first.zip");
+    let second = dir.join("second.zip");
+    std::fs::write(
+        &first,
+        player_dump("overwrite_test", &["ann", "ben", "cat"]),
+    )?;
+    std::fs::write(&second, player_dump("overwrite_test", &["dan"]))?;
+
+    let env = TestEnvironmentBuilder::new()
+        .with_data_dump(first.to_str().unwrap())
+        .with_database_name("overwrite_test")
+        .with_overwrite(true)
+        .build()
+        .await?;
+
+    // Data the second dump knows nothing about
+    let conn = Connection::establish_basic_auth(env.arangodb_url(), "root", "test_password")
+        .await
+        .context("Failed to connect to ArangoDB")?;
+    let db = conn.db("overwrite_test").await?;
+    let _: Vec<serde_json::Value> = db
+        .aql_str(r#"INSERT { _key: "zed", handle: "zed" } INTO dump_players"#)
+        .await
+        .context("Failed to add a player")?;
+    db.create_collection("stale_notes")
+        .await
+        .context("Failed to create a collection")?;
+
+    env.load_data_dump(second.to_str().unwrap(), true).await?;
+
+    let db = conn.db("overwrite_test").await?;
+    let handles: Vec<String> = db
+        .aql_str("FOR p IN dump_players SORT p.handle RETURN p.handle")
+        .await
+        .context("Failed to query restored collection")?;
+    assert_eq!(handles, ["dan"]);
+    assert!(
+        db.collection("stale_notes").await.is_err(),
+        "collections outside the dump should not survive an overwrite"
+    );
+
+    let _ = std::fs::remove_dir_all(&dir);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dump_from_another_database_name_restores_under_target() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("stg-renamed-dump-{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    let dump = dir.join("production.zip");
+    std::fs::write(&dump, player_dump("smacktalk_prod", &["ann", "ben"]))?;
+
+    let env = TestEnvironmentBuilder::new()
+        .with_data_dump(dump.to_str().unwrap())
+        .with_database_name("renamed_test")
+        .build()
+        .await?;
+
+    let conn = Connection::establish_basic_auth(env.arangodb_url(), "root", "test_password")
+        .await
+        .context("Failed to connect to ArangoDB")?;
+    let db = conn
+        .db("renamed_test")
+        .await
+        .context("Dump should be restored under the target name")?;
+    let handles: Vec<String> = db
+        .aql_str("FOR p IN dump_players SORT p.handle RETURN p.handle")
+        .await
+        .context("Failed to query restored collection")?;
+    assert_eq!(handles, ["ann", "ben"]);
+    assert!(
+        !conn
+            .accessible_databases()
+            .await?
+            .contains_key("smacktalk_prod"),
+        "the dump's own database name should not be created"
+    );
+
+    let _ = std::fs::remove_dir_all(&dir);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_directory_and_tar_gz_dumps_are_loaded() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("stg-dump-formats-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let plain = dir.join("plain");
+    write_player_dump_dir(&plain, "format_test", &["ann", "ben"])?;
+    let staged = dir.join("staged");
+    write_player_dump_dir(&staged, "format_test", &["dan"])?;
+    let archive = dir.join("dump.tar.gz");
+    let tar = Command::new("tar")
+        .arg("czf")
+        .arg(&archive)
+        .arg("-C")
+        .arg(&staged)
+        .arg("format_test")
+        .status()?;
+    assert!(tar.success(), "tar should create the archive");
+
+    let env = TestEnvironmentBuilder::new()
+        .with_data_dump(plain.to_str().unwrap())
+        .with_database_name("format_test")
+        .build()
+        .await?;
+    let handles: Vec<String> = env
+        .database()
+        .await?
+        .aql_str("FOR p IN dump_players SORT p.handle RETURN p.handle")
+        .await
+        .context("Failed to query dump loaded from a directory")?;
+    assert_eq!(handles, ["ann", "ben"]);
+
+    env.load_data_dump(archive.to_str().unwrap(), true).await?;
+    let handles: Vec<String> = env
+        .database()
+        .await?
+        .aql_str("FOR p IN dump_players SORT p.handle RETURN p.handle")
+        .await
+        .context("Failed to query dump loaded from a tar.gz")?;
+    assert_eq!(handles, ["dan"]);
+
+    let stray = dir.join("players.json");
+    std::fs::write(&stray, "[]")?;
+    let error = env
+        .load_data_dump(stray.to_str().unwrap(), false)
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("'.json'"), "{}", error);
+
+    let _ = std::fs::remove_dir_all(&dir);
+    Ok(())
+}
@@ -0,0 +1,302 @@
+//! Integration tests for the data loader
+//!
+//! The loader must wait for ArangoDB rather than fail when it is started
+//! alongside the database container, and read exports element by element
+
+use anyhow::{Context, Result};
+use arangors::{AqlQuery, Connection};
+use dataload::db::DbClient;
+use dataload::models::StgContest;
+use dataload::records::{self, ContestStream, ValidationSummary};
+use std::time::{Duration, Instant};
+use testing::TestEnvironment;
+use tokio::net::{TcpListener, TcpStream};
+
+/// Forwards `port` to `target` once `delay` has passed, so connections made
+/// before then are refused as if the database were still starting
+fn forward_after(delay: Duration, port: u16, target: String) {
+    tokio::spawn(async move {
+        tokio::time::sleep(delay).await;
+        let listener = TcpListener::bind(("127.0.0.1", port))
+            .await
+            .expect("Failed to bind proxy port");
+        loop {
+            let Ok((mut inbound, _)) = listener.accept().await else {
+                return;
+            };
+            let target = target.clone();
+            tokio::spawn(async move {
+                if let Ok(mut outbound) = TcpStream::connect(target).await {
+                    let _ = tokio::io::copy_bidirectional(&mut inbound, &mut outbound).await;
+                }
+            });
+        }
+    });
+}
+
+#[tokio::test]
+async fn test_loader_waits_for_database_to_come_up() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+
+    // Reserve a free port, then leave it closed until the proxy starts
+    let port = {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+        listener.local_addr()?.port()
+    };
+    let target = env
+        .arangodb_url()
+        .trim_start_matches("http://")
+        .replace("localhost", "127.0.0.1");
+    forward_after(Duration::from_secs(2), port, target);
+
+    let started = Instant::now();
+    let mut loader = DbClient::connect(
+        &format!("http://127.0.0.1:{}", port),
+        "dataload_retry",
+        "root",
+        "test_password",
+        Duration::from_secs(30),
+    )
+    .await
+    .context("Loader should connect once the database is up")?;
+    assert!(started.elapsed() >= Duration::from_secs(2));
+
+    let contest: StgContest = serde_json::from_value(serde_json::json!({
+        "name": "Retry Night",
+        "start": "2024-05-01T18:00:00",
+        "startoffset": "+00:00",
+        "stop": "2024-05-01T20:00:00",
+        "stopoffset": "+00:00",
+        "venue": {
+            "displayName": "Retry Venue",
+            "formattedAddress": "1 Retry St",
+            "lat": 40.7128,
+            "lng": -74.0060
+        },
+        "games": ["Retry Game"],
+        "outcome": []
+    }))?;
+    loader.load_records(vec![contest]).await?;
+
+    let conn = Connection::establish_basic_auth(env.arangodb_url(), "root", "test_password")
+        .await
+        .context("Failed to connect to ArangoDB")?;
+    let db = conn.db("dataload_retry").await?;
+    let names: Vec<String> = db
+        .aql_query(
+            AqlQuery::builder()
+                .query("FOR c IN contest RETURN c.name")
+                .build(),
+        )
+        .await?;
+    assert_eq!(names, ["Retry Night"]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_loader_gives_up_after_connect_timeout() {
+    // Nothing listens here, so every attempt is refused
+    let port = {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.local_addr().unwrap().port()
+    };
+
+    let started = Instant::now();
+    let result = DbClient::connect(
+        &format!("http://127.0.0.1:{}", port),
+        "dataload_unreachable",
+        "root",
+        "test_password",
+        Duration::from_secs(2),
+    )
+    .await;
+    assert!(result.is_err());
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[tokio::test]
+async fn test_upsert_reload_adds_no_duplicates() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+
+    let contest = || -> Result<StgContest> {
+        Ok(serde_json::from_value(serde_json::json!({
+            "name": "Reload Night",
+            "start": "2024-05-01T18:00:00",
+            "startoffset": "+00:00",
+            "stop": "2024-05-01T20:00:00",
+            "stopoffset": "+00:00",
+            "venue": {
+                "displayName": "Reload Venue",
+                "formattedAddress": "1 Reload St",
+                "place_id": "reload-place",
+                "lat": 40.7128,
+                "lng": -74.0060
+            },
+            "games": ["Reload Game", "Second Game"],
+            "outcome": [
+                { "player_id": "Reload Alice", "place": 1, "result": "won" },
+                { "player_id": "Reload Bob", "place": 2, "result": "lost" }
+            ]
+        }))?)
+    };
+
+    let mut loader = DbClient::connect(
+        env.arangodb_url(),
+        "dataload_upsert",
+        "root",
+        "test_password",
+        Duration::from_secs(30),
+    )
+    .await?;
+    loader.load_records(vec![contest()?]).await?;
+
+    // A second run over the same export, twice over, finds everything it loads
+    let mut reloader = DbClient::connect_existing(
+        env.arangodb_url(),
+        "dataload_upsert",
+        "root",
+        "test_password",
+        Duration::from_secs(30),
+    )
+    .await?
+    .with_upsert(true);
+    reloader.load_records(vec![contest()?, contest()?]).await?;
+
+    let conn = Connection::establish_basic_auth(env.arangodb_url(), "root", "test_password")
+        .await
+        .context("Failed to connect to ArangoDB")?;
+    let db = conn.db("dataload_upsert").await?;
+    for (collection, expected) in [
+        ("contest", 1),
+        ("venue", 1),
+        ("game", 2),
+        ("player", 2),
+        ("played_at", 1),
+        ("played_with", 2),
+        ("resulted_in", 2),
+    ] {
+        let count: Vec<usize> = db
+            .aql_query(
+                AqlQuery::builder()
+                    .query("RETURN LENGTH(@@collection)")
+                    .bind_var("@collection", collection)
+                    .build(),
+            )
+            .await?;
+        assert_eq!(count, [expected], "documents in {}", collection);
+    }
+
+    Ok(())
+}
+
+/// A contest element named `name`, as it appears in an export
+fn contest_json(name: &str) -> String {
+    serde_json::json!({
+        "name": name,
+        "start": "2024-05-01T18:00:00",
+        "startoffset": "+00:00",
+        "stop": "2024-05-01T20:00:00",
+        "stopoffset": "+00:00",
+        "venue": {
+            "displayName": "Stream Venue",
+            "formattedAddress": "1 Stream St",
+            "lat": 40.7128,
+            "lng": -74.0060
+        },
+        "games": ["Stream Game"],
+        "outcome": []
+    })
+    .to_string()
+}
+
+#[test]
+fn test_contest_stream_reads_every_element_of_the_array() -> Result<()> {
+    let export = format!(
+        "[\n  {},\n  {} ,{}\n]\n",
+        contest_json("First"),
+        contest_json("Second"),
+        contest_json("Third")
+    );
+
+    let names = ContestStream::new(export.as_bytes())
+        .map(|contest| contest.map(|c| c.name))
+        .collect::<Result<Vec<_>>>()?;
+    assert_eq!(names, ["First", "Second", "Third"]);
+
+    assert_eq!(ContestStream::new(&b" [ ] "[..]).count(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_contest_stream_reports_the_element_that_failed() {
+    let export = format!(
+        "[{}, {{\"name\": \"Broken\"}}, {}]",
+        contest_json("First"),
+        contest_json("Third")
+    );
+
+    let mut contests = ContestStream::new(export.as_bytes());
+    assert!(contests.next().unwrap().is_ok());
+    let err = contests.next().unwrap().unwrap_err();
+    assert!(
+        format!("{:#}", err).contains("element 1"),
+        "unexpected error: {:#}",
+        err
+    );
+    // An element that is not a contest does not stop the stream
+    assert_eq!(contests.next().unwrap().unwrap().name, "Third");
+    assert!(contests.next().is_none());
+
+    // Malformed JSON does
+    let truncated = format!("[{}, {{\"name\": ", contest_json("First"));
+    let mut contests = ContestStream::new(truncated.as_bytes());
+    assert!(contests.next().unwrap().is_ok());
+    assert!(contests.next().unwrap().is_err());
+    assert!(contests.next().is_none());
+
+    let not_an_array = contest_json("Alone");
+    let err = ContestStream::new(not_an_array.as_bytes())
+        .next()
+        .unwrap()
+        .unwrap_err();
+    assert!(err.to_string().contains("must be an array"));
+}
+
+#[test]
+fn test_validate_counts_records_and_failures_without_a_database() -> Result<()> {
+    let mut other_venue: serde_json::Value = serde_json::from_str(&contest_json("Second"))?;
+    other_venue["venue"]["displayName"] = "Other Venue".into();
+    other_venue["games"] = serde_json::json!(["Stream Game", "Other Game"]);
+    other_venue["outcome"] = serde_json::json!([
+        { "player_id": "player/a", "place": 1, "result": "won" },
+        { "player_id": "player/b", "place": 2, "result": "lost" }
+    ]);
+    let export = format!(
+        "[{}, {}, {{\"name\": \"Broken\"}}, [], {}]",
+        contest_json("First"),
+        other_venue,
+        contest_json("Third")
+    );
+
+    let mut failures = Vec::new();
+    let summary = records::validate(export.as_bytes(), |e| failures.push(format!("{:#}", e)))?;
+    assert_eq!(
+        summary,
+        ValidationSummary {
+            contests: 3,
+            games: 2,
+            venues: 2,
+            outcomes: 2,
+            failed: 2,
+        }
+    );
+    assert_eq!(failures.len(), 2);
+    assert!(failures[0].contains("element 2"));
+    assert!(failures[1].contains("element 3"));
+
+    assert!(records::validate(&b"{}"[..], |_| {}).is_err());
+    Ok(())
+}
@@ -312,6 +312,45 @@ async fn test_404_not_found_resources() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_unmatched_routes_return_json_errors() -> Result<()> {
+    let app = test::init_service(
+        App::new()
+            .wrap(backend::error::json_error_handlers())
+            .service(web::resource("/api/ping").route(web::post().to(|| async { "pong" })))
+            .default_service(web::route().to(backend::error::not_found_handler)),
+    )
+    .await;
+
+    // Unknown path
+    let req = test::TestRequest::get()
+        .uri("/api/does-not-exist")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+    assert_eq!(
+        resp.headers().get("content-type").unwrap(),
+        "application/json"
+    );
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["error"], "NOT_FOUND");
+    assert_eq!(body["code"], "resource.not_found");
+
+    // Known path, wrong method
+    let req = test::TestRequest::get().uri("/api/ping").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 405);
+    assert_eq!(
+        resp.headers().get("content-type").unwrap(),
+        "application/json"
+    );
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["error"], "METHOD_NOT_ALLOWED");
+    assert_eq!(body["code"], "request.method_not_allowed");
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_malformed_json_request() -> Result<()> {
     let env = TestEnvironment::new().await?;
@@ -637,3 +676,106 @@ async fn test_invalid_content_type() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_handlers_emit_stable_error_codes() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+
+    let app = test::init_service(
+        App::new()
+            .app_data(actix_web::web::JsonConfig::default().limit(256 * 1024))
+            .app_data(app_data.redis_data.clone())
+            .app_data(app_data.player_repo.clone())
+            .app_data(app_data.contest_repo.clone())
+            .app_data(app_data.session_store.clone())
+            .service(
+                web::scope("/api/players")
+                    .service(backend::player::controller::register_handler_prod)
+                    .service(backend::player::controller::login_handler_prod),
+            )
+            .service(
+                web::scope("/api/contests")
+                    .wrap(backend::auth::AuthMiddleware {
+                        redis: app_data.redis_arc.clone(),
+                    })
+                    .app_data(app_data.player_repo.clone())
+                    .service(backend::contest::controller::create_contest_handler)
+                    .service(backend::contest::controller::get_contest_handler),
+            ),
+    )
+    .await;
+    let session_id = create_authenticated_user!(app, "error_codes@example.com", "errorcodes");
+
+    // Registering the same email again
+    let req = test::TestRequest::post()
+        .uri("/api/players/register")
+        .set_json(json!({
+            "handle": "errorcodes2",
+            "email": "error_codes@example.com",
+            "password": "password123"
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 400);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["code"], "player.duplicate_email");
+
+    // Logging in with the wrong password
+    let req = test::TestRequest::post()
+        .uri("/api/players/login")
+        .set_json(json!({
+            "email": "error_codes@example.com",
+            "password": "wrongpassword"
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 401);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["code"], "player.invalid_credentials");
+
+    // A contest with a place that is not a number
+    let start = chrono::Utc::now();
+    let req = test::TestRequest::post()
+        .uri("/api/contests")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .set_json(json!({
+            "name": "Bad Placement",
+            "start": start.to_rfc3339(),
+            "stop": (start + chrono::Duration::hours(1)).to_rfc3339(),
+            "venue": {
+                "displayName": "Codes Venue",
+                "formattedAddress": "1 Code St",
+                "place_id": "codes_place_id",
+                "lat": 40.7128,
+                "lng": -74.0060,
+                "timezone": "America/New_York",
+                "source": "database"
+            },
+            "games": [],
+            "outcomes": [{
+                "player_id": "",
+                "place": "first",
+                "result": "won",
+                "email": "error_codes@example.com",
+                "handle": "errorcodes"
+            }]
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 400);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["code"], "contest.invalid_placement");
+
+    let req = test::TestRequest::get()
+        .uri("/api/contests/no_such_contest")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 404);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["code"], "contest.not_found");
+
+    Ok(())
+}
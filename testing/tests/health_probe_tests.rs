@@ -0,0 +1,49 @@
+//! Integration tests for the liveness and readiness probes
+
+use actix_web::{http::StatusCode, test, web, App};
+use anyhow::{Context, Result};
+use arangors::Connection;
+use backend::health::{liveness, readiness};
+use testing::TestEnvironment;
+
+#[tokio::test]
+async fn test_readiness_fails_but_liveness_passes_when_database_is_down() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+
+    let conn = Connection::establish_basic_auth(env.arangodb_url(), "root", "test_password")
+        .await
+        .context("Failed to connect to ArangoDB")?;
+    let db = conn.db("_system").await?;
+    let redis_client =
+        redis::Client::open(env.redis_url()).context("Failed to create Redis client")?;
+
+    let app = test::init_service(
+        App::new()
+            .app_data(web::Data::new(db))
+            .app_data(web::Data::new(redis_client))
+            .service(liveness)
+            .service(readiness),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/readyz").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    env.stop_arangodb().await?;
+
+    let req = test::TestRequest::get().uri("/readyz").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["status"], "not_ready");
+    assert_eq!(body["services"]["database"]["status"], "unhealthy");
+    assert_eq!(body["services"]["redis"]["status"], "healthy");
+
+    let req = test::TestRequest::get().uri("/healthz").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    Ok(())
+}
@@ -23,8 +23,7 @@ async fn test_redis_connection() -> Result<()> {
     let env = TestEnvironment::new().await?;
     env.wait_for_ready().await?;
 
-    let client = redis::Client::open(env.redis_url())?;
-    let mut conn = client.get_async_connection().await?;
+    let mut conn = env.redis_connection().await?;
 
     // Test basic Redis operations
     let _: () = conn.set("test_key", "test_value").await?;
@@ -58,14 +57,9 @@ async fn test_environment_with_data_dump() -> Result<()> {
         .build()
         .await?;
 
-    // Verify the database was created and has data
-    // We can check by connecting to ArangoDB and querying collections
-    use arangors::Connection;
-    let conn =
-        Connection::establish_basic_auth(env.arangodb_url(), "root", "test_password").await?;
-
     // Verify we can access the database (the backup should have created it)
-    let _db = conn.db(&env.arangodb_db_name()).await?;
+    let db = env.database().await?;
+    assert_eq!(db.name(), env.arangodb_db_name());
 
     // Check that we have collections (the backup should have created them)
     // This is a basic sanity check that the restore worked
@@ -77,6 +71,22 @@ async fn test_environment_with_data_dump() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_database_handle_falls_back_to_seeded_database() -> Result<()> {
+    let env = TestEnvironmentBuilder::new()
+        .with_database_name("handle_test")
+        .build()
+        .await?;
+
+    // No dump created `handle_test`, so tests seed `_system` instead
+    let db = env.database().await?;
+    assert_eq!(db.name(), "_system");
+    let again = env.database().await?;
+    assert_eq!(again.name(), db.name());
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_with_automatic_prod_data_discovery() -> Result<()> {
     // This test demonstrates the convenience helper that automatically
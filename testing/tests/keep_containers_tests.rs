@@ -0,0 +1,62 @@
+//! Tests for keeping containers around after a failed test
+
+use anyhow::{Context, Result};
+use std::panic::AssertUnwindSafe;
+use std::process::Command;
+use testing::{TestEnvironment, TestEnvironmentBuilder};
+
+/// Whether Docker still reports the container as running
+fn container_is_running(id: &str) -> Result<bool> {
+    let output = Command::new("docker")
+        .args(["inspect", "--format", "{{.State.Running}}", id])
+        .output()
+        .context("Failed to inspect container")?;
+    Ok(output.status.success() && String::from_utf8_lossy(&output.stdout).trim() == "true")
+}
+
+/// Drop `env` while panicking, as a failed assert in a test would
+fn fail_test_holding(env: TestEnvironment) {
+    let result = std::panic::catch_unwind(AssertUnwindSafe(move || {
+        let _env = env;
+        panic!("simulated test failure");
+    }));
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_containers_are_kept_after_a_failure_when_asked() -> Result<()> {
+    let env = TestEnvironmentBuilder::new()
+        .with_keep_containers_on_failure(true)
+        .build()
+        .await?;
+    let ids = env.container_ids();
+
+    fail_test_holding(env);
+
+    for id in &ids {
+        assert!(container_is_running(id)?, "container {} was removed", id);
+    }
+
+    Command::new("docker")
+        .args(["rm", "-f"])
+        .args(&ids)
+        .output()
+        .context("Failed to remove kept containers")?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_containers_are_removed_after_a_failure_by_default() -> Result<()> {
+    let env = TestEnvironmentBuilder::new()
+        .with_keep_containers_on_failure(false)
+        .build()
+        .await?;
+    let ids = env.container_ids();
+
+    fail_test_holding(env);
+
+    for id in &ids {
+        assert!(!container_is_running(id)?, "container {} was kept", id);
+    }
+    Ok(())
+}
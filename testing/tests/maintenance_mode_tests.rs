@@ -0,0 +1,154 @@
+//! Integration tests for maintenance mode
+//!
+//! Writes are refused with 503 while reads and health probes keep working
+
+use actix_web::{test, web, App};
+use anyhow::Result;
+use backend::config::MaintenanceConfig;
+use chrono::{DateTime, FixedOffset, Utc};
+use serde_json::json;
+use testing::create_authenticated_user;
+use testing::{app_setup, TestEnvironment};
+
+fn contest_payload(name: &str) -> serde_json::Value {
+    let start: DateTime<FixedOffset> = Utc::now().into();
+    let stop: DateTime<FixedOffset> = start + chrono::Duration::hours(2);
+    json!({
+        "name": name,
+        "start": start.to_rfc3339(),
+        "stop": stop.to_rfc3339(),
+        "venue": {
+            "displayName": "Maintenance Venue",
+            "formattedAddress": "1 Quiet St",
+            "place_id": "maintenance_place_id",
+            "lat": 40.7128,
+            "lng": -74.0060,
+            "timezone": "America/New_York",
+            "source": "database"
+        },
+        "games": [{
+            "name": "Maintenance Game",
+            "year_published": 2020,
+            "source": "database"
+        }],
+        "outcomes": []
+    })
+}
+
+/// The contest API behind maintenance mode set to `$enabled`
+macro_rules! contest_app {
+    ($app_data:expr, $enabled:expr) => {
+        test::init_service(
+            App::new()
+                .wrap(backend::middleware::MaintenanceMode::new(
+                    &MaintenanceConfig {
+                        enabled: $enabled,
+                        message: "Back after the backfill".to_string(),
+                    },
+                ))
+                .wrap(backend::middleware::Logger::new())
+                .app_data(actix_web::web::JsonConfig::default().limit(256 * 1024))
+                .app_data($app_data.redis_data.clone())
+                .app_data($app_data.player_repo.clone())
+                .app_data($app_data.game_repo.clone())
+                .app_data($app_data.venue_repo.clone())
+                .app_data($app_data.contest_repo.clone())
+                .app_data($app_data.session_store.clone())
+                .service(backend::health::liveness)
+                .service(
+                    web::scope("/api/players")
+                        .service(backend::player::controller::register_handler_prod)
+                        .service(backend::player::controller::login_handler_prod),
+                )
+                .service(
+                    web::scope("/api/contests")
+                        .wrap(backend::auth::AuthMiddleware {
+                            redis: $app_data.redis_arc.clone(),
+                        })
+                        .app_data(actix_web::web::JsonConfig::default().limit(128 * 1024))
+                        .app_data($app_data.player_repo.clone())
+                        .service(backend::contest::controller::create_contest_handler)
+                        .service(backend::contest::controller::search_contests_handler),
+                ),
+        )
+        .await
+    };
+}
+
+#[tokio::test]
+async fn test_maintenance_mode_refuses_contest_create_but_serves_reads() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+
+    // Sign up and record a contest before maintenance starts
+    let app = contest_app!(app_data, false);
+    let session_id = create_authenticated_user!(app, "maintenance@example.com", "maintenance");
+    let req = test::TestRequest::post()
+        .uri("/api/contests")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .set_json(contest_payload("Before Maintenance"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(
+        resp.status().is_success(),
+        "Contest create should succeed outside maintenance, got {}",
+        resp.status()
+    );
+
+    let app = contest_app!(app_data, true);
+
+    let req = test::TestRequest::post()
+        .uri("/api/contests")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .set_json(contest_payload("During Maintenance"))
+        .to_request();
+    let resp = match test::try_call_service(&app, req).await {
+        Ok(resp) => panic!("Contest create should be refused, got {}", resp.status()),
+        Err(e) => e.error_response(),
+    };
+    assert_eq!(resp.status(), 503);
+    let body = actix_web::body::to_bytes(resp.into_body())
+        .await
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let body: serde_json::Value = serde_json::from_slice(&body)?;
+    assert_eq!(body["error"], "MAINTENANCE");
+    assert_eq!(body["message"], "Back after the backfill");
+
+    // Reads still work, and only the contest from before maintenance exists
+    let req = test::TestRequest::get()
+        .uri("/api/contests/search?q=Maintenance&scope=all")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(
+        resp.status().is_success(),
+        "Reads should succeed in maintenance mode, got {}",
+        resp.status()
+    );
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    let names = body.to_string();
+    assert!(names.contains("Before Maintenance"));
+    assert!(!names.contains("During Maintenance"));
+
+    let req = test::TestRequest::get().uri("/healthz").to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 200);
+
+    // Signing in is not a write that maintenance pauses
+    let req = test::TestRequest::post()
+        .uri("/api/players/login")
+        .set_json(json!({
+            "email": "maintenance@example.com",
+            "password": "password123"
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(
+        resp.status().is_success(),
+        "Login should succeed in maintenance mode, got {}",
+        resp.status()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,179 @@
+//! Integration tests for player profile editing and login tracking
+
+use actix_web::{test, web, App};
+use anyhow::Result;
+use backend::player::repository::PlayerRepository;
+use serde_json::json;
+use shared::dto::player::{PlayerDto, PlayerProfileDto};
+use testing::create_authenticated_user;
+use testing::{app_setup, TestEnvironment};
+
+macro_rules! profile_app {
+    ($app_data:expr) => {
+        test::init_service(
+            App::new()
+                .app_data(actix_web::web::JsonConfig::default().limit(64 * 1024))
+                .app_data($app_data.redis_data.clone())
+                .app_data($app_data.player_repo.clone())
+                .app_data($app_data.session_store.clone())
+                .service(
+                    web::scope("/api/players")
+                        .service(backend::player::controller::register_handler_prod)
+                        .service(backend::player::controller::login_handler_prod)
+                        .service(
+                            web::scope("/me")
+                                .wrap(backend::auth::AuthMiddleware {
+                                    redis: $app_data.redis_arc.clone(),
+                                })
+                                .service(backend::player::controller::me_handler_prod)
+                                .service(backend::player::controller::update_profile_handler_prod),
+                        ),
+                ),
+        )
+        .await
+    };
+}
+
+#[tokio::test]
+async fn test_update_profile_names_and_read_back() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+    let app = profile_app!(app_data);
+
+    let session_id = create_authenticated_user!(app, "profile_names@example.com", "profilenames");
+
+    let req = test::TestRequest::patch()
+        .uri("/api/players/me/profile")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .set_json(&json!({ "firstname": "Ada", "lastname": "Lovelace" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(
+        resp.status().is_success(),
+        "Profile update should succeed, got: {}",
+        resp.status()
+    );
+    let profile: PlayerProfileDto = test::read_body_json(resp).await;
+    assert_eq!(profile.firstname, "Ada");
+    assert_eq!(profile.lastname.as_deref(), Some("Lovelace"));
+
+    // Omitted fields are left unchanged
+    let req = test::TestRequest::patch()
+        .uri("/api/players/me/profile")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .set_json(&json!({ "firstname": "Augusta" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+
+    let req = test::TestRequest::get()
+        .uri("/api/players/me")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    let player: PlayerDto = test::read_body_json(resp).await;
+    assert_eq!(player.firstname, "Augusta");
+    assert_eq!(player.lastname.as_deref(), Some("Lovelace"));
+
+    // The last name can be removed, but not set and removed at once
+    let req = test::TestRequest::patch()
+        .uri("/api/players/me/profile")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .set_json(&json!({ "lastname": "King", "clear_lastname": true }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 400);
+
+    let req = test::TestRequest::patch()
+        .uri("/api/players/me/profile")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .set_json(&json!({ "clear_lastname": true }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    let profile: PlayerProfileDto = test::read_body_json(resp).await;
+    assert_eq!(profile.firstname, "Augusta");
+    assert_eq!(profile.lastname, None);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_profile_rejects_blank_names() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+    let app = profile_app!(app_data);
+
+    let session_id = create_authenticated_user!(app, "profile_blank@example.com", "profileblank");
+
+    let req = test::TestRequest::patch()
+        .uri("/api/players/me/profile")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .set_json(&json!({ "lastname": "   " }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 400, "Blank last name should be rejected");
+
+    let req = test::TestRequest::patch()
+        .uri("/api/players/me/profile")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .set_json(&json!({ "firstname": "x".repeat(101) }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), 400, "Overlong first name should be rejected");
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_each_login_advances_last_login_at() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+    let app = profile_app!(app_data);
+
+    let email = "profile_login@example.com";
+    let session_id = create_authenticated_user!(app, email, "profilelogin");
+    let first = app_data
+        .player_repo
+        .find_by_email(email)
+        .await
+        .and_then(|player| player.last_login_at)
+        .expect("First login should be recorded");
+
+    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    let req = test::TestRequest::post()
+        .uri("/api/players/login")
+        .peer_addr("203.0.113.7:40000".parse()?)
+        .set_json(&json!({ "email": email, "password": "password123" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success(), "Second login should succeed");
+
+    let player = app_data
+        .player_repo
+        .find_by_email(email)
+        .await
+        .expect("Player should exist");
+    let second = player
+        .last_login_at
+        .expect("Second login should be recorded");
+    assert!(second > first, "{} should be after {}", second, first);
+    assert_eq!(player.last_login_ip.as_deref(), Some("203.0.113.7"));
+
+    // The profile reports the latest login
+    let req = test::TestRequest::patch()
+        .uri("/api/players/me/profile")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .set_json(&json!({ "firstname": "Logan" }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(resp.status().is_success());
+    let profile: PlayerProfileDto = test::read_body_json(resp).await;
+    assert_eq!(profile.last_login_at, Some(second));
+
+    Ok(())
+}
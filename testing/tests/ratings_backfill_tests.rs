@@ -0,0 +1,275 @@
+//! Integration tests for the ratings backfill
+//!
+//! Historical players get ratings without waiting to play again, and the
+//! backfill can be rerun or resumed without counting a contest twice
+
+use anyhow::Result;
+use arangors::client::reqwest::ReqwestClient;
+use arangors::{AqlQuery, Database};
+use backend::contest::repository::ContestRepository;
+use backend::ratings::clock::FixedClock;
+use backend::ratings::repository::{RatingsRepository, BACKFILL_COLLECTION};
+use backend::ratings::usecase::RatingsUsecase;
+use chrono::{DateTime, Datelike, Duration, FixedOffset, TimeZone, Utc};
+use shared::dto::contest::{ContestDto, OutcomeDto};
+use shared::dto::game::GameDto;
+use shared::dto::venue::VenueDto;
+use shared::models::contest::ContestVisibility;
+use shared::models::game::GameSource;
+use shared::models::venue::VenueSource;
+use std::collections::HashMap;
+use std::sync::Arc;
+use testing::{app_setup, TestEnvironment};
+
+fn contest_dto(name: &str, start: DateTime<FixedOffset>, handles: &[&str]) -> ContestDto {
+    ContestDto {
+        id: String::new(),
+        name: name.to_string(),
+        start,
+        stop: start + Duration::hours(1),
+        venue: VenueDto {
+            id: String::new(),
+            display_name: "Backfill Venue".to_string(),
+            formatted_address: "1 Backfill St".to_string(),
+            place_id: "backfill_place_id".to_string(),
+            lat: 40.7128,
+            lng: -74.0060,
+            timezone: "America/New_York".to_string(),
+            source: VenueSource::Database,
+        },
+        games: vec![GameDto {
+            id: String::new(),
+            name: "Backfill Game".to_string(),
+            year_published: Some(2020),
+            bgg_id: None,
+            description: None,
+            source: GameSource::Database,
+        }],
+        outcomes: handles
+            .iter()
+            .enumerate()
+            .map(|(i, handle)| OutcomeDto {
+                player_id: String::new(),
+                place: (i + 1).to_string(),
+                result: if i == 0 { "won" } else { "lost" }.to_string(),
+                email: format!("{}@example.com", handle),
+                handle: handle.to_string(),
+                guest: false,
+            })
+            .collect(),
+        creator_id: String::new(),
+        created_at: None,
+        rated: true,
+        visibility: ContestVisibility::Public,
+    }
+}
+
+async fn query<T: serde::de::DeserializeOwned>(
+    db: &Database<ReqwestClient>,
+    aql: &str,
+) -> Result<Vec<T>> {
+    db.aql_query(AqlQuery::builder().query(aql).build())
+        .await
+        .map_err(|e| anyhow::anyhow!("Query failed: {}", e))
+}
+
+/// Global latest rating per player
+async fn latest_ratings(db: &Database<ReqwestClient>) -> Result<HashMap<String, f64>> {
+    let rows: Vec<(String, f64)> = query(
+        db,
+        r#"FOR r IN rating_latest FILTER r.scope_type == "global" RETURN [r.player_id, r.rating]"#,
+    )
+    .await?;
+    Ok(rows.into_iter().collect())
+}
+
+async fn history_rows(db: &Database<ReqwestClient>) -> Result<usize> {
+    let count: Vec<usize> = query(db, "RETURN LENGTH(rating_history)").await?;
+    Ok(count[0])
+}
+
+#[tokio::test]
+async fn test_backfill_rates_every_historical_participant_once() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+
+    let db = app_data.contest_repo.db.clone();
+    for collection in ["rating_latest", "rating_history", BACKFILL_COLLECTION] {
+        db.create_collection(collection)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to create {}: {}", collection, e))?;
+    }
+    let ratings_repo = RatingsRepository::new(db.clone());
+    let ratings = RatingsUsecase::new(ratings_repo.clone());
+
+    // Two contests in different past months, nobody rated yet
+    let now: DateTime<FixedOffset> = Utc::now().into();
+    let first_start = now - Duration::days(95);
+    for (name, start, handles) in [
+        (
+            "Backfill Opening",
+            first_start,
+            &["bfann", "bfben", "bfcat"][..],
+        ),
+        (
+            "Backfill Rematch",
+            now - Duration::days(62),
+            &["bfben", "bfdan"][..],
+        ),
+    ] {
+        app_data
+            .contest_repo
+            .create_contest(
+                contest_dto(name, start, handles),
+                "player/creator".to_string(),
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to create contest: {}", e))?;
+    }
+    assert!(latest_ratings(&db).await?.is_empty());
+
+    let report = ratings
+        .backfill_ratings()
+        .await
+        .map_err(|e| anyhow::anyhow!("Backfill failed: {}", e))?;
+    assert!(!report.already_complete);
+    assert!(report.periods_processed >= 2);
+
+    let mut participants: Vec<String> =
+        query(&db, "FOR r IN resulted_in RETURN DISTINCT r._to").await?;
+    participants.sort();
+    let ratings_after = latest_ratings(&db).await?;
+    let mut rated: Vec<String> = ratings_after.keys().cloned().collect();
+    rated.sort();
+    assert_eq!(participants.len(), 4);
+    assert_eq!(rated, participants);
+    let history = history_rows(&db).await?;
+    assert!(history > 0);
+
+    // A finished backfill leaves ratings alone when rerun
+    let rerun = ratings
+        .backfill_ratings()
+        .await
+        .map_err(|e| anyhow::anyhow!("Backfill rerun failed: {}", e))?;
+    assert!(rerun.already_complete);
+    assert_eq!(rerun.periods_processed, 0);
+    assert_eq!(history_rows(&db).await?, history);
+
+    // Resuming after the first month replays only the later ones and lands
+    // on the same ratings
+    let (year, month) = if first_start.month() == 12 {
+        (first_start.year() + 1, 1)
+    } else {
+        (first_start.year(), first_start.month() + 1)
+    };
+    let first_period_end = format!("{:04}-{:02}-01T00:00:00Z", year, month);
+    ratings_repo
+        .save_backfill_progress(Some(&first_period_end), false)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to rewind progress: {}", e))?;
+    let resumed = ratings
+        .backfill_ratings()
+        .await
+        .map_err(|e| anyhow::anyhow!("Resumed backfill failed: {}", e))?;
+    assert_eq!(
+        resumed.resumed_after.as_deref(),
+        Some(first_period_end.as_str())
+    );
+    assert!(resumed.periods_processed > 0);
+    assert!(resumed.periods_processed < report.periods_processed);
+    assert_eq!(history_rows(&db).await?, history);
+    let ratings_resumed = latest_ratings(&db).await?;
+    assert_eq!(ratings_resumed.len(), ratings_after.len());
+    for (player_id, rating) in &ratings_after {
+        assert!(
+            (ratings_resumed[player_id] - rating).abs() < 1e-6,
+            "{} rated {} after resuming, {} before",
+            player_id,
+            ratings_resumed[player_id],
+            rating
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_backfill_with_a_fixed_clock_is_reproducible() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+
+    let db = app_data.contest_repo.db.clone();
+    for collection in ["rating_latest", "rating_history", BACKFILL_COLLECTION] {
+        db.create_collection(collection)
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to create {}: {}", collection, e))?;
+    }
+    let ratings_repo = RatingsRepository::new(db.clone());
+    let clock = Arc::new(FixedClock(
+        Utc.with_ymd_and_hms(2024, 4, 15, 12, 0, 0).unwrap(),
+    ));
+    let ratings = RatingsUsecase::new(ratings_repo.clone()).with_clock(clock);
+
+    for (name, start, handles) in [
+        (
+            "Clock Opening",
+            "2024-01-10T19:00:00Z",
+            &["ckann", "ckben", "ckcat"][..],
+        ),
+        (
+            "Clock Rematch",
+            "2024-02-12T19:00:00Z",
+            &["ckben", "ckdan", "ckann"][..],
+        ),
+    ] {
+        app_data
+            .contest_repo
+            .create_contest(
+                contest_dto(name, DateTime::parse_from_rfc3339(start)?, handles),
+                "player/creator".to_string(),
+            )
+            .await
+            .map_err(|e| anyhow::anyhow!("Failed to create contest: {}", e))?;
+    }
+
+    // Every document the run writes, timestamps included
+    let snapshot = || async {
+        let latest: Vec<serde_json::Value> = query(
+            &db,
+            r#"FOR r IN rating_latest SORT r.player_id RETURN UNSET(r, "_key", "_id", "_rev")"#,
+        )
+        .await?;
+        let history: Vec<serde_json::Value> = query(
+            &db,
+            r#"FOR h IN rating_history SORT h.period_end, h.player_id RETURN UNSET(h, "_key", "_id", "_rev")"#,
+        )
+        .await?;
+        anyhow::Ok((latest, history))
+    };
+
+    let first = ratings
+        .backfill_ratings()
+        .await
+        .map_err(|e| anyhow::anyhow!("Backfill failed: {}", e))?;
+    // January through March: the clock's own month is left to the scheduler
+    assert_eq!(first.periods_processed, 3);
+    let first_run = snapshot().await?;
+    assert_eq!(first_run.0.len(), 4);
+    assert_eq!(first_run.0[0]["updated_at"], "2024-04-15T12:00:00+00:00");
+
+    // Forget the finished backfill so the second run starts from scratch
+    ratings_repo
+        .save_backfill_progress(None, false)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to reset progress: {}", e))?;
+    let second = ratings
+        .backfill_ratings()
+        .await
+        .map_err(|e| anyhow::anyhow!("Second backfill failed: {}", e))?;
+    assert_eq!(second.periods_processed, first.periods_processed);
+    assert_eq!(snapshot().await?, first_run);
+
+    Ok(())
+}
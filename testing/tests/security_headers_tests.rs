@@ -0,0 +1,89 @@
+//! Integration tests for the security headers and CORS-exempt health probes
+
+use actix_web::http::header::{ACCESS_CONTROL_ALLOW_ORIGIN, ORIGIN};
+use actix_web::{http::StatusCode, test, web, App};
+use anyhow::Result;
+use backend::config::SecurityConfig;
+use testing::create_authenticated_user;
+use testing::{app_setup, TestEnvironment};
+
+#[tokio::test]
+async fn test_api_responses_carry_configured_security_headers() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    env.wait_for_ready().await?;
+    let app_data = app_setup::setup_test_app_data(&env).await?;
+
+    let security = SecurityConfig {
+        content_security_policy: Some("default-src 'self'".to_string()),
+        hsts_max_age_secs: Some(86400),
+        ..SecurityConfig::default()
+    };
+    let app = test::init_service(
+        App::new()
+            .wrap(backend::middleware::SecurityHeaders::new(&security))
+            .wrap(backend::middleware::cors_middleware_with_exempt_paths(
+                &security.cors_exempt_paths,
+            ))
+            .app_data(app_data.redis_data.clone())
+            .app_data(app_data.player_repo.clone())
+            .app_data(app_data.contest_repo.clone())
+            .app_data(app_data.session_store.clone())
+            .service(backend::health::liveness)
+            .service(
+                web::scope("/api/players")
+                    .service(backend::player::controller::register_handler_prod)
+                    .service(backend::player::controller::login_handler_prod),
+            )
+            .service(
+                web::scope("/api/contests")
+                    .wrap(backend::auth::AuthMiddleware {
+                        redis: app_data.redis_arc.clone(),
+                    })
+                    .app_data(app_data.player_repo.clone())
+                    .service(backend::contest::controller::search_contests_handler),
+            ),
+    )
+    .await;
+    let session_id = create_authenticated_user!(app, "headers@example.com", "headers");
+
+    let req = test::TestRequest::get()
+        .uri("/api/contests/search?q=Headers&scope=all")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let headers = resp.headers();
+    assert_eq!(headers.get("x-content-type-options").unwrap(), "nosniff");
+    assert_eq!(headers.get("x-frame-options").unwrap(), "DENY");
+    assert_eq!(
+        headers.get("content-security-policy").unwrap(),
+        "default-src 'self'"
+    );
+    assert_eq!(
+        headers.get("strict-transport-security").unwrap(),
+        "max-age=86400; includeSubDomains"
+    );
+
+    // A monitor on another host may probe health, but not call the API
+    let req = test::TestRequest::get()
+        .uri("/healthz")
+        .insert_header((ORIGIN, "https://status.example.org"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers().get(ACCESS_CONTROL_ALLOW_ORIGIN).unwrap(),
+        "https://status.example.org"
+    );
+    assert_eq!(resp.headers().get("x-frame-options").unwrap(), "DENY");
+
+    let req = test::TestRequest::get()
+        .uri("/api/contests/search?q=Headers&scope=all")
+        .insert_header(("Authorization", format!("Bearer {}", session_id)))
+        .insert_header((ORIGIN, "https://status.example.org"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert!(!resp.headers().contains_key(ACCESS_CONTROL_ALLOW_ORIGIN));
+
+    Ok(())
+}
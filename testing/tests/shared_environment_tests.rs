@@ -0,0 +1,59 @@
+//! Tests for environments that share containers across tests
+
+use anyhow::{Context, Result};
+use arangors::Connection;
+use testing::{app_setup, assert_collection_count, TestEnvironment};
+
+#[tokio::test]
+async fn test_shared_environments_reuse_containers_with_separate_databases() -> Result<()> {
+    let first = TestEnvironment::shared().await?;
+    let second = TestEnvironment::shared().await?;
+
+    assert_eq!(first.arangodb_url(), second.arangodb_url());
+    assert_eq!(first.redis_url(), second.redis_url());
+    assert_ne!(first.app_db_name(), second.app_db_name());
+    assert_ne!(first.app_db_name(), "_system");
+
+    app_setup::setup_test_app_data(&first).await?;
+    app_setup::setup_test_app_data(&second).await?;
+
+    let conn = Connection::establish_basic_auth(first.arangodb_url(), "root", "test_password")
+        .await
+        .context("Failed to connect to ArangoDB")?;
+    let db = conn.db(&first.app_db_name()).await?;
+    let _: Vec<serde_json::Value> = db
+        .aql_str(r#"FOR n IN 1..2 INSERT { name: CONCAT("Contest ", n) } INTO contest"#)
+        .await
+        .context("Failed to seed data")?;
+
+    assert_collection_count(&first, "contest", 2).await?;
+    assert_collection_count(&second, "contest", 0).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_new_environment_keeps_seeding_system_database() -> Result<()> {
+    let env = TestEnvironment::new().await?;
+    assert_eq!(env.app_db_name(), "_system");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_missing_per_test_database_is_an_error_not_system() -> Result<()> {
+    let env = TestEnvironment::shared().await?;
+
+    let error = env
+        .database()
+        .await
+        .expect_err("The per-test database does not exist before setup");
+    assert!(
+        format!("{:#}", error).contains(&env.app_db_name()),
+        "{:#}",
+        error
+    );
+
+    app_setup::setup_test_app_data(&env).await?;
+    let db = env.database().await?;
+    assert_eq!(db.name(), env.app_db_name());
+    Ok(())
+}